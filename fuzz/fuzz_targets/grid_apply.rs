@@ -0,0 +1,71 @@
+#![no_main]
+
+use growterm_grid::Grid;
+use growterm_vt_parser::VtParser;
+use libfuzzer_sys::fuzz_target;
+
+// 입력 형식: [cols, rows, chunk_len, bytes...]
+// 0xFF 바이트가 나오면 다음 두 바이트로 resize (PTY 크기 변경 흉내)
+fuzz_target!(|data: &[u8]| {
+    let [cols, rows, chunk_len, rest @ ..] = data else { return };
+    let mut grid = Grid::new(*cols as u16 % 64, *rows as u16 % 32);
+    let mut parser = VtParser::new();
+    let mut commands = Vec::new();
+    let chunk_len = (*chunk_len as usize).max(1);
+
+    let mut bytes = rest.iter();
+    let mut chunk = Vec::with_capacity(chunk_len);
+    loop {
+        chunk.clear();
+        let mut resize = None;
+        for &byte in bytes.by_ref() {
+            if byte == 0xFF {
+                resize = Some((bytes.next(), bytes.next()));
+                break;
+            }
+            chunk.push(byte);
+            if chunk.len() == chunk_len {
+                break;
+            }
+        }
+        if chunk.is_empty() && resize.is_none() {
+            break;
+        }
+
+        commands.clear();
+        parser.parse_to(&chunk, &mut commands);
+        grid.apply_batch(&commands);
+        check_invariants(&grid);
+
+        if let Some((cols, rows)) = resize {
+            let cols = cols.copied().unwrap_or(0) as u16 % 64;
+            let rows = rows.copied().unwrap_or(0) as u16 % 32;
+            grid.resize(cols, rows);
+            check_invariants(&grid);
+        }
+    }
+
+    let snapshot = grid.snapshot();
+    let restored = Grid::restore(&snapshot);
+    check_invariants(&restored);
+});
+
+fn check_invariants(grid: &Grid) {
+    let cells = grid.screen_rows();
+    assert!(grid.screen_row(0).is_some());
+    let cols = cells[0].width();
+    assert!(cols > 0);
+    assert!(cells.clone().all(|row| row.width() == cols), "screen rows differ in length");
+    assert!(
+        grid.scrollback_rows().all(|row| row.width() == cols),
+        "scrollback rows differ in length"
+    );
+
+    // cursor_col == cols는 마지막 열에 출력한 뒤의 pending wrap
+    let (row, col) = grid.cursor_pos();
+    assert!((row as usize) < cells.len(), "cursor row {row} out of {} rows", cells.len());
+    assert!((col as usize) <= cols, "cursor col {col} out of {cols} cols");
+
+    assert!(grid.scroll_offset() <= grid.scrollback_len());
+    assert_eq!(grid.visible_rows().len(), cells.len());
+}
@@ -0,0 +1,16 @@
+#![no_main]
+
+use growterm_vt_parser::VtParser;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    // 한 번에 파싱한 결과와 바이트 단위로 나눠 파싱한 결과가 같아야 함
+    let whole = VtParser::new().parse(data);
+
+    let mut parser = VtParser::new();
+    let mut split = Vec::new();
+    for byte in data.chunks(1) {
+        parser.parse_to(byte, &mut split);
+    }
+    assert_eq!(whole, split);
+});
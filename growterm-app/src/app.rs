@@ -1,12 +1,14 @@
 use std::io::Write;
-use std::sync::atomic::Ordering;
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::{mpsc, Arc};
 use std::time::{Duration, Instant};
 
-use growterm_gpu_draw::GpuDrawer;
-use growterm_macos::{AppEvent, MacWindow, Modifiers};
+use growterm_gpu_draw::tab_strip::TabStripHit;
+use growterm_gpu_draw::{Overlays, Renderer};
+use growterm_pty::PtyOptions;
+use growterm_window::{AppEvent, Modifiers, MouseCursor, TerminalWindow};
 
-use crate::config::CopyModeAction;
+use crate::config::{CopyModeAction, WindowPadding};
 
 /// Freeze diagnostic logger — writes directly to ~/.config/growterm/freeze.log.
 /// Truncates the file every 200 lines to keep only recent events.
@@ -40,11 +42,20 @@ impl FreezeLog {
         self.lines += 1;
     }
 }
+use crate::bell::Bell;
+use crate::click_to_move;
 use crate::copy_mode::CopyMode;
+use crate::diagnostics::{self, MemoryWatchdog};
+use crate::frame_buffer::ScreenSnapshot;
+use crate::frame_pacing::{FrameDecision, FrameScheduler, FrameStats};
 use crate::ink_workaround::InkImeState;
+use crate::link_scan::ViewKey;
+use crate::paste;
 use crate::pomodoro::{Pomodoro, TickResult};
 use crate::selection::{self, Selection};
-use crate::tab::{Tab, TabManager};
+use crate::session::{Session, SessionSaver};
+use crate::smooth_scroll::SmoothScroll;
+use crate::tab::{ColorOverride, Tab, TabManager};
 use crate::url;
 use crate::zoom;
 
@@ -57,14 +68,59 @@ fn copy_to_clipboard(text: &str) {
     }
 }
 
+/// 탭의 OSC 10/11 기본 색상을 반영한 팔레트
+fn tab_palette(palette: growterm_render_cmd::TerminalPalette, base_palette: growterm_render_cmd::TerminalPalette) -> growterm_render_cmd::TerminalPalette {
+    growterm_render_cmd::TerminalPalette {
+        default_fg: palette.default_fg,
+        default_bg: palette.default_bg,
+        ..base_palette
+    }
+}
+
+/// Copy the selection. With `rich`, colors and bold/italic also go on the
+/// clipboard as HTML/RTF so pasting into Keynote or Slack keeps them.
+fn copy_selection(window: &dyn TerminalWindow, tab: &Tab, sel: &Selection, rich: Option<growterm_render_cmd::TerminalPalette>) {
+    let state = tab.terminal.lock().unwrap();
+    let text = selection::extract_text_absolute(&state.grid, sel);
+    let styled = rich.map(|base_palette| {
+        let palette = tab_palette(state.palette, base_palette);
+        let rows = selection::extract_cells_absolute(&state.grid, sel);
+        let rows = || rows.iter().map(Vec::as_slice);
+        (
+            growterm_render_cmd::export::to_html_fragment(rows(), palette),
+            growterm_render_cmd::export::to_rtf(rows(), palette),
+        )
+    });
+    drop(state);
+    match styled {
+        Some((html, rtf)) if !text.is_empty() => {
+            if !window.set_rich_clipboard(&text, &html, &rtf) {
+                if let Ok(mut clipboard) = arboard::Clipboard::new() {
+                    let _ = clipboard.set_html(html, Some(text));
+                }
+            }
+        }
+        _ => copy_to_clipboard(&text),
+    }
+}
+
 /// Send SGR mouse report to PTY. `suffix` is 'M' for press/motion, 'm' for release.
-fn send_sgr_mouse(tab: &mut Tab, x: f64, y: f64, y_offset: f32, cw: f32, ch: f32, button: u32, suffix: char) {
-    let (row, col) = selection::pixel_to_cell(x as f32, y as f32 - y_offset, cw, ch);
+fn send_sgr_mouse(tab: &mut Tab, x: f64, y: f64, origin: (f32, f32), cw: f32, ch: f32, button: u32, suffix: char) {
+    let (row, col) = selection::mouse_pixel_to_cell(x as f32, y as f32, cw, ch, origin);
     let seq = format!("\x1b[<{button};{};{}{suffix}", col as u32 + 1, row as u32 + 1);
     let _ = tab.pty_writer.write_all(seq.as_bytes());
     let _ = tab.pty_writer.flush();
 }
 
+/// ?1004가 켜져 있으면 포커스 변화를 PTY에 알림 (vim autoread, tmux focus-events)
+fn send_focus_report(tab: &mut Tab, focused: bool) {
+    if tab.focus_reporting.load(Ordering::Relaxed) {
+        let seq: &[u8] = if focused { b"\x1b[I" } else { b"\x1b[O" };
+        let _ = tab.pty_writer.write_all(seq);
+        let _ = tab.pty_writer.flush();
+    }
+}
+
 /// Apply scrollbar drag: compute scroll offset from mouse Y position.
 fn apply_scrollbar_drag(tabs: &TabManager, y: f64, screen_h: f32, tab_bar_offset: f32) {
     let content_h = screen_h - tab_bar_offset;
@@ -72,7 +128,7 @@ fn apply_scrollbar_drag(tabs: &TabManager, y: f64, screen_h: f32, tab_bar_offset
     if let Some(tab) = tabs.active_tab() {
         let mut state = tab.terminal.lock().unwrap();
         let scrollback_len = state.grid.scrollback_len();
-        let rows = state.grid.cells().len();
+        let rows = state.grid.screen_rows().len();
         let total = scrollback_len + rows;
         let target_top_row = (ratio * total as f32) as usize;
         let offset = scrollback_len.saturating_sub(target_top_row).min(scrollback_len);
@@ -91,26 +147,29 @@ fn resize_all_tabs(tabs: &mut TabManager, cols: u16, rows: u16) {
 }
 
 /// Save copy mode state to the current tab before switching away.
-fn save_tab_state(copy_mode: &mut CopyMode, sel: &mut Selection, tabs: &mut TabManager) {
+fn save_tab_state(copy_mode: &mut CopyMode, sel: &mut Selection, ink_state: &InkImeState, tabs: &mut TabManager) {
     if let Some(tab) = tabs.active_tab_mut() {
         tab.copy_mode = copy_mode.clone();
         tab.selection = *sel;
+        tab.ink_ime = ink_state.clone();
     }
 }
 
 /// Restore copy mode state from the newly active tab after switching.
-fn restore_tab_state(copy_mode: &mut CopyMode, sel: &mut Selection, preedit: &mut String, window: &MacWindow, tabs: &TabManager) {
+/// The IME anchor follows the tab, so composition restarts at its cursor.
+fn restore_tab_state(copy_mode: &mut CopyMode, sel: &mut Selection, preedit: &mut String, ink_state: &mut InkImeState, window: &dyn TerminalWindow, tabs: &TabManager) {
     preedit.clear();
     window.discard_marked_text();
     if let Some(tab) = tabs.active_tab() {
         *copy_mode = tab.copy_mode.clone();
         *sel = tab.selection;
+        *ink_state = tab.ink_ime.clone();
         window.set_copy_mode(copy_mode.active);
     }
 }
 
 /// Exit copy mode: clear selection, reset scroll, update window state.
-fn exit_copy_mode(copy_mode: &mut CopyMode, sel: &mut Selection, window: &MacWindow, tabs: &TabManager) {
+fn exit_copy_mode(copy_mode: &mut CopyMode, sel: &mut Selection, window: &dyn TerminalWindow, tabs: &TabManager) {
     copy_mode.exit(sel);
     window.set_copy_mode(false);
     if let Some(tab) = tabs.active_tab() {
@@ -119,6 +178,90 @@ fn exit_copy_mode(copy_mode: &mut CopyMode, sel: &mut Selection, window: &MacWin
     }
 }
 
+fn set_scroll_on_output(tabs: &TabManager, enabled: bool) {
+    for tab in tabs.tabs() {
+        tab.terminal.lock().unwrap().grid.set_scroll_on_output(enabled);
+    }
+}
+
+fn set_max_scrollback(tabs: &TabManager, max: Option<usize>) {
+    for tab in tabs.tabs() {
+        tab.terminal.lock().unwrap().grid.set_max_scrollback(max);
+    }
+}
+
+fn set_flow_control(tabs: &TabManager, enabled: bool) {
+    for tab in tabs.tabs() {
+        tab.flow_control.store(enabled, Ordering::Relaxed);
+    }
+}
+
+/// Cmd+K: 활성 탭의 스크롤백과 화면을 지움. 쉘에는 아무것도 보내지 않음.
+/// 지워진 행에 붙어 있던 실행 시간 표시와 명령 출력 위치도 함께 지움
+fn clear_scrollback_and_screen(tabs: &mut TabManager) {
+    if let Some(tab) = tabs.active_tab_mut() {
+        tab.terminal.lock().unwrap().grid.clear_scrollback_and_screen();
+        tab.command_durations.clear();
+        tab.output_marks.lock().unwrap().clear();
+        tab.dirty.store(true, Ordering::Relaxed);
+    }
+}
+
+/// 확장자가 .html/.htm이면 색상을 포함한 HTML로 내보냄
+fn is_html_path(path: &std::path::Path) -> bool {
+    path.extension()
+        .and_then(|ext| ext.to_str())
+        .is_some_and(|ext| ext.eq_ignore_ascii_case("html") || ext.eq_ignore_ascii_case("htm"))
+}
+
+/// Export Buffer…: 탭의 스크롤백과 화면 전체를 파일로 씀
+fn export_buffer(tab: &Tab, path: &std::path::Path, base_palette: growterm_render_cmd::TerminalPalette) -> std::io::Result<()> {
+    let state = tab.terminal.lock().unwrap();
+    let rows = state.grid.rows().map(|row| &row[..]);
+    let content = if is_html_path(path) {
+        growterm_render_cmd::export::to_html(rows, tab_palette(state.palette, base_palette))
+    } else {
+        growterm_render_cmd::export::to_text(rows)
+    };
+    drop(state);
+    std::fs::write(path, content)
+}
+
+/// Snap the active tab back to the bottom when the user types.
+fn scroll_to_bottom_on_keystroke(tabs: &TabManager, config: &crate::config::Config) {
+    if !config.scroll_to_bottom_on_keystroke {
+        return;
+    }
+    if let Some(tab) = tabs.active_tab() {
+        tab.terminal.lock().unwrap().grid.reset_scroll();
+    }
+}
+
+/// Respawn the tabs of a saved session in their previous directories.
+fn restore_session(tabs: &mut TabManager, session: &Session, rows: u16, cols: u16, window: &Arc<dyn TerminalWindow>, base: &PtyOptions) {
+    for tab_session in &session.tabs {
+        let cwd = tab_session.cwd.as_deref().filter(|p| p.is_dir());
+        match Tab::spawn_with_options(rows, cols, window.clone(), base.clone().cwd(cwd), None) {
+            Ok(tab) => {
+                {
+                    let mut state = tab.terminal.lock().unwrap();
+                    let replay = crate::session::replay_scrollback(tab_session);
+                    let commands = state.vt_parser.parse(&replay);
+                    for cmd in &commands {
+                        state.grid.apply(cmd);
+                    }
+                    state.grid.set_scroll_offset(tab_session.scroll_offset);
+                }
+                tabs.add_tab(tab);
+            }
+            Err(e) => eprintln!("Failed to restore tab: {e}"),
+        }
+    }
+    if session.active < tabs.tab_count() {
+        tabs.switch_to(session.active);
+    }
+}
+
 /// Convert screen row to absolute row (including scrollback).
 fn screen_to_abs_row(tabs: &TabManager, screen_row: u16) -> u32 {
     if let Some(tab) = tabs.active_tab() {
@@ -130,14 +273,36 @@ fn screen_to_abs_row(tabs: &TabManager, screen_row: u16) -> u32 {
     }
 }
 
-pub fn run(window: Arc<MacWindow>, rx: mpsc::Receiver<AppEvent>, mut drawer: GpuDrawer, mut config: crate::config::Config) {
+/// 마우스가 가리키는 화면 열 → 셀 열. `bidi`가 켜져 있으면 RTL 행은 그려진 순서가 다름
+fn mouse_cell_col(tabs: &TabManager, abs_row: u32, col: u16, bidi: bool) -> u16 {
+    if !bidi {
+        return col;
+    }
+    let Some(tab) = tabs.active_tab() else {
+        return col;
+    };
+    let state = tab.terminal.lock().unwrap();
+    match state.grid.row(abs_row as usize).and_then(|line| growterm_render_cmd::bidi::layout(line)) {
+        Some(visual) => visual.logical_col(col),
+        None => col,
+    }
+}
+
+pub fn run(window: Arc<dyn TerminalWindow>, rx: mpsc::Receiver<AppEvent>, mut drawer: Box<dyn Renderer + Send>, mut config: crate::config::Config, launch: Option<crate::config::Profile>, saved_session: Option<Session>) {
+    drawer.set_subpixel_positioning(config.subpixel_positioning);
+    drawer.set_cell_spacing(config.line_height, config.cell_padding);
+    drawer.set_scale_factor(window.backing_scale_factor());
+    drawer.set_present_mode(config.present_mode.into());
     let (cell_w, cell_h) = drawer.cell_size();
-    let mut font_size = config.font_size;
+    // 명령행 플래그(--font-size, --profile)는 설정 파일에 저장하지 않음
+    let mut font_size = launch.as_ref().and_then(|p| p.font_size).unwrap_or(config.font_size);
     let (width, height) = window.inner_size();
 
-    let cols = (width as f32 / cell_w).floor().max(1.0) as u16;
-
     let mut tabs = TabManager::new();
+    let mut dark_appearance = window.is_dark_appearance();
+    tabs.set_base_palette(config.theme_palette(dark_appearance));
+    tabs.set_window_padding(config.window_padding, window.backing_scale_factor());
+    let cols = tabs.term_cols(width, cell_w);
 
     // Spawn initial tab (no tab bar for single tab)
     let initial_title_bar_height = if config.transparent_tab_bar {
@@ -145,16 +310,45 @@ pub fn run(window: Arc<MacWindow>, rx: mpsc::Receiver<AppEvent>, mut drawer: Gpu
     } else {
         0.0
     };
-    let rows = tabs.term_rows(height, cell_h, drawer.tab_bar_height(), initial_title_bar_height, false);
-    match Tab::spawn(rows, cols, window.clone()) {
-        Ok(tab) => {
-            tabs.add_tab(tab);
-        }
-        Err(e) => {
-            eprintln!("Failed to spawn PTY: {e}");
-            return;
+    if let Some(session) = saved_session {
+        let y_off = crate::tab::content_y_offset(
+            session.tabs.len() > 1,
+            drawer.tab_bar_height(),
+            initial_title_bar_height,
+            false,
+        );
+        let padding = tabs.window_padding();
+        let rows = ((height as f32 - y_off - padding.top - padding.bottom) / cell_h).floor().max(1.0) as u16;
+        restore_session(&mut tabs, &session, rows, cols, &window, &config.pty_options());
+        // Some saved tabs may have failed to spawn; fit the survivors.
+        let rows = tabs.term_rows(height, cell_h, drawer.tab_bar_height(), initial_title_bar_height, false);
+        resize_all_tabs(&mut tabs, cols, rows);
+    }
+    if tabs.is_empty() {
+        let rows = tabs.term_rows(height, cell_h, drawer.tab_bar_height(), initial_title_bar_height, false);
+        let banner = crate::banner::load(
+            config.startup_banner_file.as_deref(),
+            config.startup_banner_command.as_deref(),
+        );
+        let options = launch.as_ref().map_or_else(|| config.pty_options(), |p| p.pty_options(config.pty_options()));
+        match Tab::spawn_with_options(rows, cols, window.clone(), options, banner.as_deref()) {
+            Ok(mut tab) => {
+                if let Some(profile) = &launch {
+                    tab.color_override = profile.color_override();
+                }
+                tabs.add_tab(tab);
+            }
+            Err(e) => {
+                eprintln!("Failed to spawn PTY: {e}");
+                return;
+            }
         }
     }
+    set_scroll_on_output(&tabs, config.scroll_to_bottom_on_output);
+    set_max_scrollback(&tabs, config.max_scrollback());
+    set_flow_control(&tabs, config.flow_control);
+    let mut session_saver = SessionSaver::new(window.clone());
+    let mut memory_watchdog = MemoryWatchdog::new(config.scrollback_memory_cap_mb);
 
     // Periodic 1-second redraw for pomodoro timer display
     {
@@ -168,11 +362,20 @@ pub fn run(window: Arc<MacWindow>, rx: mpsc::Receiver<AppEvent>, mut drawer: Gpu
     }
 
     let mut preedit = String::new();
+    // 확인 대화상자 응답을 기다리는 붙여넣기 텍스트
+    let mut pending_paste: Option<String> = None;
+    // 확인 대화상자가 떠 있는 동안 닫기를 기다리는 탭 id
+    let mut pending_close_tab: Option<u64> = None;
     let mut prev_preedit = String::new();
     let mut sel = Selection::default();
     let mut scroll_accum: f64 = 0.0;
+    let mut smooth_scroll = SmoothScroll::new();
+    // 디버그 뷰에서 HUD로 표시
+    let mut frame_stats = FrameStats::default();
     let mut deferred: Option<AppEvent> = None;
     let grid_dump_path = std::env::var("GROWTERM_GRID_DUMP").ok();
+    // 덤프와 함께 셀 속성까지 담은 JSON 스냅샷을 남김
+    let grid_snapshot_path = std::env::var("GROWTERM_GRID_SNAPSHOT").ok();
     let test_input = std::env::var("GROWTERM_TEST_INPUT").ok();
     let test_dropped_path = std::env::var("GROWTERM_TEST_DROPPED_PATH").ok();
     let mut test_input_sent = false;
@@ -180,7 +383,7 @@ pub fn run(window: Arc<MacWindow>, rx: mpsc::Receiver<AppEvent>, mut drawer: Gpu
     let mut ink_state = InkImeState::new();
     let mut response_timer_enabled = config.response_timer;
     if response_timer_enabled {
-        if let Some(tab) = tabs.active_tab_mut() {
+        for tab in tabs.tabs_mut() {
             tab.response_timer.set_enabled(true);
         }
         window.set_response_timer_checked(true);
@@ -197,8 +400,20 @@ pub fn run(window: Arc<MacWindow>, rx: mpsc::Receiver<AppEvent>, mut drawer: Gpu
     window.set_coaching_menu_enabled(config.pomodoro);
     let mut transparent_tab_bar = config.transparent_tab_bar;
     let mut header_opacity = config.header_opacity;
+    let mut base_palette = config.palette();
+    let mut bell = Bell::new(config.bell_audible, config.bell_visual, config.bell_badge, config.bell_attention);
+    let mut window_focused = true;
+    let mut dock_badged = false;
+    // 디버그 보기: 줄 끝 공백을 점으로, C0 제어문자를 제어 그림 문자로 표시
+    let mut debug_view = false;
+    // 응답 시간 리포트 오버레이 (활성 탭의 최근 명령들)
+    let mut response_report = false;
+    // 지연 HUD: 입력→화면, PTY 읽기 속도, 프레임 시간, 아틀라스 사용량
+    let mut latency_hud = false;
+    const RESPONSE_REPORT_ROWS: usize = 15;
     window.set_transparent_tab_bar_checked(transparent_tab_bar);
     window.set_transparent_mode(transparent_tab_bar);
+    window.set_profiles(&profile_names(&config));
     let title_bar_height = if transparent_tab_bar {
         window.title_bar_height() as f32
     } else {
@@ -207,6 +422,7 @@ pub fn run(window: Arc<MacWindow>, rx: mpsc::Receiver<AppEvent>, mut drawer: Gpu
     let mut title_bar_height = title_bar_height;
     // hover_url_range: (abs_row, start_col, end_col) for Cmd+hover URL underline
     let mut hover_url_range: Option<(u32, u16, u16)> = None;
+    let mut mouse_cursor = MouseCursor::Arrow;
     let mut scrollbar_dragging = false;
     let mut scrollbar_visible_until: Option<Instant> = None;
     const SCROLLBAR_HIT_WIDTH: f32 = 20.0;
@@ -216,28 +432,180 @@ pub fn run(window: Arc<MacWindow>, rx: mpsc::Receiver<AppEvent>, mut drawer: Gpu
     const COPY_FLASH_DURATION: Duration = Duration::from_millis(150);
     let mut tab_dragging: Option<usize> = None;
     let mut tab_drag_start_x: f32 = 0.0;
+    // 더블클릭 판정: 직전 클릭 시각과 셀
+    let mut last_click: Option<(Instant, u32, u16)> = None;
+    const DOUBLE_CLICK_INTERVAL: Duration = Duration::from_millis(400);
+    // 휠 이벤트에는 위치가 없으므로 탭바 위인지 판단하기 위해 기억
+    let mut last_mouse_y: f64 = 0.0;
     let mut last_title: Option<String> = None;
     let mut last_ime_cursor_rect: Option<(f32, f32, f32, f32)> = None;
 
+    // 페이싱으로 미뤄진 프레임(또는 벨 깜빡임이 끝나는 프레임)을 그릴 시각
+    let mut frame_deadline: Option<Instant> = None;
+
+    // 한 프레임에 쓰는 UI 상태를 한 번에 모음
+    macro_rules! frame_ui {
+        ($show_scrollbar:expr, $bell_flash:expr) => {
+            FrameUi {
+                hover_url_range,
+                is_break: pomodoro.is_input_blocked(),
+                break_text: pomodoro.break_text(coaching_enabled),
+                show_scrollbar: $show_scrollbar,
+                copy_flash,
+                tab_dragging,
+                transparent_tab_bar,
+                title_bar_height,
+                header_opacity,
+                debug_view,
+                bell_flash: $bell_flash,
+                duration_annotation: config.command_duration_annotation,
+                underline_links: config.underline_links,
+                bidi: config.bidi,
+                base_palette,
+                smooth_scroll: &smooth_scroll,
+                hud: if latency_hud { Some(frame_stats.latency_hud_text(drawer.atlas_usage().1)) } else if debug_view { frame_stats.hud_text() } else { None },
+                report: if response_report { tabs.active_tab().map(|t| t.response_timer.report_lines(RESPONSE_REPORT_ROWS)) } else { None },
+                window_focused,
+            }
+        };
+    }
+
     macro_rules! do_render {
         () => {
-            if render_with_tabs(&mut drawer, &tabs, &preedit, &sel, &ink_state, hover_url_range, pomodoro.is_input_blocked(), pomodoro.coaching_lines().as_deref(), scrollbar_dragging || scrollbar_visible_until.map_or(false, |t| t > Instant::now()), copy_flash, tab_dragging, transparent_tab_bar, title_bar_height, header_opacity) {
-                window.request_redraw();
-            }
+            do_render!(@draw scrollbar_dragging || scrollbar_visible_until.map_or(false, |t| t > Instant::now()))
         };
         (scrollbar: true) => {
-            if render_with_tabs(&mut drawer, &tabs, &preedit, &sel, &ink_state, hover_url_range, pomodoro.is_input_blocked(), pomodoro.coaching_lines().as_deref(), true, copy_flash, tab_dragging, transparent_tab_bar, title_bar_height, header_opacity) {
+            do_render!(@draw true)
+        };
+        (@draw $show_scrollbar:expr) => {{
+            let frame_start = Instant::now();
+            let bell_flash_until = bell.flash_deadline(frame_start);
+            let ui = frame_ui!($show_scrollbar, bell_flash_until.is_some());
+            if render_with_tabs(drawer.as_mut(), &tabs, &preedit, &sel, &ink_state, &ui) {
                 window.request_redraw();
             }
+            if let Some(at) = bell_flash_until {
+                frame_deadline = Some(frame_deadline.map_or(at, |d| d.min(at)));
+            }
+            frame_stats.record(frame_start, frame_start.elapsed());
+        }};
+        // 화면에 보이는 그대로 한 프레임을 그려 RGBA로 읽어 옴 (Save Screenshot)
+        (capture) => {{
+            let bell_flash_until = bell.flash_deadline(Instant::now());
+            let ui = frame_ui!(scrollbar_dragging || scrollbar_visible_until.map_or(false, |t| t > Instant::now()), bell_flash_until.is_some());
+            let mut capture = crate::screenshot::Capture::new(drawer.as_mut());
+            render_with_tabs(&mut capture, &tabs, &preedit, &sel, &ink_state, &ui);
+            capture.pixels
+        }};
+    }
+
+    macro_rules! close_tab {
+        ($index:expr) => {
+            let had_tab_bar = tabs.show_tab_bar();
+            tabs.close_tab($index);
+            if tabs.is_empty() {
+                session_saver.close(&tabs, &config);
+                window.close();
+                return;
+            }
+            restore_tab_state(&mut copy_mode, &mut sel, &mut preedit, &mut ink_state, &window, &tabs);
+            // Tab bar just disappeared — expand remaining tab by 1 row
+            if had_tab_bar && !tabs.show_tab_bar() {
+                let (cw, ch) = drawer.cell_size();
+                let (w, h) = window.inner_size();
+                let cols = tabs.term_cols(w, cw);
+                let has_scrollback = tabs.active_tab().map_or(false, |t| t.terminal.lock().unwrap().grid.scrollback_len() > 0);
+                let rows = tabs.term_rows(h, ch, drawer.tab_bar_height(), title_bar_height, has_scrollback);
+                if let Some(t) = tabs.active_tab_mut() {
+                    let mut st = t.terminal.lock().unwrap();
+                    st.grid.resize(cols, rows);
+                    drop(st);
+                    let _ = t.pty_writer.resize(rows, cols);
+                }
+            }
         };
     }
 
+    macro_rules! open_tab {
+        ($options:expr, $color:expr) => {
+            let (cw, ch) = drawer.cell_size();
+            let (w, h) = window.inner_size();
+            let padding = tabs.window_padding();
+            let content_w = (w as f32 - padding.left - padding.right).max(0.0) as u32;
+            let (cols, _rows) = zoom::calc_grid_size(content_w, h, cw, ch);
+            let had_no_tab_bar = !tabs.show_tab_bar();
+            // After adding a tab, tab bar will show — compute rows with tab bar
+            let next_title_bar_height = if transparent_tab_bar {
+                title_bar_height
+            } else {
+                0.0
+            };
+            let term_rows = tabs.term_rows(h, ch, drawer.tab_bar_height(), next_title_bar_height, false);
+            match Tab::spawn_with_options(term_rows, cols, window.clone(), $options, None) {
+                Ok(mut tab) => {
+                    tab.color_override = $color;
+                    tab.response_timer.set_enabled(response_timer_enabled);
+                    if debug_view {
+                        tab.terminal.lock().unwrap().vt_parser.set_show_control_pictures(true);
+                    }
+                    tab.terminal.lock().unwrap().grid.set_scroll_on_output(config.scroll_to_bottom_on_output);
+                    tab.terminal.lock().unwrap().grid.set_max_scrollback(config.max_scrollback());
+                    tab.flow_control.store(config.flow_control, Ordering::Relaxed);
+                    save_tab_state(&mut copy_mode, &mut sel, &ink_state, &mut tabs);
+                    tabs.add_tab(tab);
+                    // New tab has no copy mode state, so reset
+                    copy_mode = CopyMode::new();
+                    sel = Selection::default();
+                    window.set_copy_mode(false);
+                    preedit.clear();
+                    ink_state = InkImeState::new();
+                    window.discard_marked_text();
+                    // Tab bar just appeared — shrink existing tabs by 1 row
+                    if had_no_tab_bar && tabs.show_tab_bar() {
+                        for t in tabs.tabs_mut() {
+                            let mut st = t.terminal.lock().unwrap();
+                            st.grid.resize(cols, term_rows);
+                            drop(st);
+                            let _ = t.pty_writer.resize(term_rows, cols);
+                        }
+                    }
+                }
+                Err(e) => eprintln!("Failed to spawn tab: {e}"),
+            }
+        };
+    }
+
+    // `growterm cli` 요청은 리드로우 때 처리
+    let ipc_inbox = crate::ipc::register(window.clone());
+
+    // 설정 파일이 바뀌면 다음 리드로우에서 다시 읽음
+    let config_changed = Arc::new(AtomicBool::new(false));
+    {
+        let changed = Arc::clone(&config_changed);
+        let w = window.clone();
+        crate::config_watch::watch(crate::config::config_path(), move || {
+            changed.store(true, Ordering::Relaxed);
+            w.request_redraw();
+        });
+    }
+
+    let mut frame_scheduler = FrameScheduler::new(config.max_fps, config.low_latency);
+
     let mut flog = FreezeLog::new();
 
     loop {
         if let Some(f) = flog.as_mut() { f.log("waiting_event"); }
         let event = if let Some(evt) = deferred.take() {
             evt
+        } else if let Some(deadline) = frame_deadline {
+            match rx.recv_timeout(deadline.saturating_duration_since(Instant::now())) {
+                Ok(evt) => evt,
+                Err(mpsc::RecvTimeoutError::Timeout) => {
+                    frame_deadline = None;
+                    AppEvent::RedrawRequested
+                }
+                Err(mpsc::RecvTimeoutError::Disconnected) => break,
+            }
         } else {
             match rx.recv() {
                 Ok(evt) => evt,
@@ -264,6 +632,15 @@ pub fn run(window: Arc<MacWindow>, rx: mpsc::Receiver<AppEvent>, mut drawer: Gpu
         match event {
             AppEvent::TextCommit(text) => {
                 preedit.clear();
+                // IME를 거친 Space도 Quick Look (KeyInput 쪽과 같음)
+                if text == " " {
+                    let target = tabs.active_tab().and_then(|tab| quick_look_target(tab, &sel, hover_url_range));
+                    if let Some(path) = target {
+                        if window.toggle_quick_look(&path.to_string_lossy()) {
+                            continue;
+                        }
+                    }
+                }
                 // 백틱(`) 또는 ₩: 복사모드 진입/종료
                 if (text == "`" || text == "₩") && !copy_mode.active {
                     if let Some(tab) = tabs.active_tab() {
@@ -271,7 +648,7 @@ pub fn run(window: Arc<MacWindow>, rx: mpsc::Receiver<AppEvent>, mut drawer: Gpu
                         let sb_len = state.grid.scrollback_len() as u32;
                         let (cursor_row, _cursor_col) = state.grid.cursor_pos();
                         let abs_cursor_row = sb_len + cursor_row as u32;
-                        let cols = state.grid.cells().first().map_or(80, |r| r.len()) as u16;
+                        let cols = state.grid.size().0;
                         drop(state);
                         copy_mode.enter(abs_cursor_row, cols, &mut sel);
                         window.set_copy_mode(true);
@@ -285,6 +662,9 @@ pub fn run(window: Arc<MacWindow>, rx: mpsc::Receiver<AppEvent>, mut drawer: Gpu
                     continue;
                 }
                 pomodoro.on_input(&tab_scrollback_lens(&tabs));
+                scroll_to_bottom_on_keystroke(&tabs, &config);
+                frame_scheduler.on_input();
+                frame_stats.on_input(Instant::now());
                 if let Some(tab) = tabs.active_tab_mut() {
                     let _ = tab.pty_writer.write_all(text.as_bytes());
                     let _ = tab.pty_writer.flush();
@@ -304,82 +684,61 @@ pub fn run(window: Arc<MacWindow>, rx: mpsc::Receiver<AppEvent>, mut drawer: Gpu
                 characters,
                 modifiers,
             } => {
-                use growterm_macos::key_convert::keycode as kc;
+                use growterm_window::key_convert::keycode as kc;
+
+                // Space: Cmd+hover 중인 파일이나 선택한 경로를 Quick Look으로 미리보기 (복사모드, Cmd+Space)
+                if keycode == kc::SPACE && !modifiers.intersects(Modifiers::CONTROL | Modifiers::ALT | Modifiers::SHIFT) {
+                    let target = tabs.active_tab().and_then(|tab| quick_look_target(tab, &sel, hover_url_range));
+                    if let Some(path) = target {
+                        if window.toggle_quick_look(&path.to_string_lossy()) {
+                            continue;
+                        }
+                    }
+                }
 
                 if modifiers.contains(Modifiers::SUPER) {
-                    // Cmd+N: new window (spawn new process)
+                    // Cmd+N: new window (같은 프로세스, 불가능하면 새 프로세스)
                     if keycode == kc::ANSI_N {
-                        spawn_new_window();
+                        if !window.open_window(false) {
+                            spawn_new_window();
+                        }
                         continue;
                     }
 
-                    // Cmd+T: new tab (inherit CWD from active tab)
+                    // Cmd+T (native_tabs): 네이티브 윈도우 탭
+                    if keycode == kc::ANSI_T && config.native_tabs && window.open_window(true) {
+                        continue;
+                    }
+
+                    // Cmd+T: new tab
                     if keycode == kc::ANSI_T {
-                        let (cw, ch) = drawer.cell_size();
-                        let (w, h) = window.inner_size();
-                        let (cols, _rows) = zoom::calc_grid_size(w, h, cw, ch);
-                        let had_no_tab_bar = !tabs.show_tab_bar();
-                        // After adding a tab, tab bar will show — compute rows with tab bar
-                        let next_title_bar_height = if transparent_tab_bar {
-                            title_bar_height
-                        } else {
-                            0.0
-                        };
-                        let term_rows = tabs.term_rows(h, ch, drawer.tab_bar_height(), next_title_bar_height, false);
-                        let active_cwd = tabs
-                            .active_tab()
-                            .and_then(|t| t.pty_writer.child_pid())
-                            .and_then(growterm_pty::child_cwd);
-                        match Tab::spawn_with_cwd(term_rows, cols, window.clone(), active_cwd.as_deref()) {
-                            Ok(mut tab) => {
-                                tab.response_timer.set_enabled(response_timer_enabled);
-                                save_tab_state(&mut copy_mode, &mut sel, &mut tabs);
-                                tabs.add_tab(tab);
-                                // New tab has no copy mode state, so reset
-                                copy_mode = CopyMode::new();
-                                sel = Selection::default();
-                                window.set_copy_mode(false);
-                                preedit.clear();
-                                window.discard_marked_text();
-                                // Tab bar just appeared — shrink existing tabs by 1 row
-                                if had_no_tab_bar && tabs.show_tab_bar() {
-                                    for t in tabs.tabs_mut() {
-                                        let mut st = t.terminal.lock().unwrap();
-                                        st.grid.resize(cols, term_rows);
-                                        drop(st);
-                                        let _ = t.pty_writer.resize(term_rows, cols);
-                                    }
-                                }
-                            }
-                            Err(e) => eprintln!("Failed to spawn tab: {e}"),
+                        deferred = Some(AppEvent::NewTab);
+                        continue;
+                    }
+
+                    // Cmd+K: clear scrollback & screen (메뉴가 없는 백엔드용)
+                    if keycode == kc::ANSI_K {
+                        if copy_mode.active {
+                            exit_copy_mode(&mut copy_mode, &mut sel, &window, &tabs);
                         }
+                        clear_scrollback_and_screen(&mut tabs);
+                        sel = Selection::default();
+                        hover_url_range = None;
+                        smooth_scroll.stop();
                         do_render!();
                         continue;
                     }
 
-                    // Cmd+W: close tab
+                    // Cmd+W: close tab (실행 중인 프로세스가 있으면 먼저 확인)
                     if keycode == kc::ANSI_W {
-                        let had_tab_bar = tabs.show_tab_bar();
-                        tabs.close_active();
-                        if tabs.is_empty() {
-                            std::process::exit(0);
-                        }
-                        restore_tab_state(&mut copy_mode, &mut sel, &mut preedit, &window, &tabs);
-                        // Tab bar just disappeared — expand remaining tab by 1 row
-                        if had_tab_bar && !tabs.show_tab_bar() {
-                            let (cw, ch) = drawer.cell_size();
-                            let (w, h) = window.inner_size();
-                            let cols = (w as f32 / cw).floor().max(1.0) as u16;
-                            let has_scrollback = tabs.active_tab().map_or(false, |t| t.terminal.lock().unwrap().grid.scrollback_len() > 0);
-                            let rows = tabs.term_rows(h, ch, drawer.tab_bar_height(), title_bar_height, has_scrollback);
-                            if let Some(t) = tabs.active_tab_mut() {
-                                let mut st = t.terminal.lock().unwrap();
-                                st.grid.resize(cols, rows);
-                                drop(st);
-                                let _ = t.pty_writer.resize(rows, cols);
-                            }
+                        let names = tabs.active_tab().map(|t| t.running_process_names()).unwrap_or_default();
+                        if let Some(message) = crate::tab::running_processes_message(&names) {
+                            pending_close_tab = tabs.active_tab().map(|t| t.id);
+                            window.show_alert("Close this tab?", &message, Some(("Close", AppEvent::ConfirmCloseTab)));
+                        } else {
+                            close_tab!(tabs.active_index());
+                            do_render!();
                         }
-                        do_render!();
                         continue;
                     }
 
@@ -387,16 +746,16 @@ pub fn run(window: Arc<MacWindow>, rx: mpsc::Receiver<AppEvent>, mut drawer: Gpu
                     if modifiers.contains(Modifiers::SHIFT) {
                         // Cmd+Shift+R: reload config — 메뉴(reloadConfig:)로 처리됨
                         if keycode == kc::ANSI_LEFT_BRACKET {
-                            save_tab_state(&mut copy_mode, &mut sel, &mut tabs);
+                            save_tab_state(&mut copy_mode, &mut sel, &ink_state, &mut tabs);
                             tabs.prev_tab();
-                            restore_tab_state(&mut copy_mode, &mut sel, &mut preedit, &window, &tabs);
+                            restore_tab_state(&mut copy_mode, &mut sel, &mut preedit, &mut ink_state, &window, &tabs);
                             do_render!();
                             continue;
                         }
                         if keycode == kc::ANSI_RIGHT_BRACKET {
-                            save_tab_state(&mut copy_mode, &mut sel, &mut tabs);
+                            save_tab_state(&mut copy_mode, &mut sel, &ink_state, &mut tabs);
                             tabs.next_tab();
-                            restore_tab_state(&mut copy_mode, &mut sel, &mut preedit, &window, &tabs);
+                            restore_tab_state(&mut copy_mode, &mut sel, &mut preedit, &mut ink_state, &window, &tabs);
                             do_render!();
                             continue;
                         }
@@ -417,9 +776,9 @@ pub fn run(window: Arc<MacWindow>, rx: mpsc::Receiver<AppEvent>, mut drawer: Gpu
                     };
                     if let Some(idx) = tab_num {
                         if idx < tabs.tab_count() {
-                            save_tab_state(&mut copy_mode, &mut sel, &mut tabs);
+                            save_tab_state(&mut copy_mode, &mut sel, &ink_state, &mut tabs);
                             tabs.switch_to(idx);
-                            restore_tab_state(&mut copy_mode, &mut sel, &mut preedit, &window, &tabs);
+                            restore_tab_state(&mut copy_mode, &mut sel, &mut preedit, &mut ink_state, &window, &tabs);
                             do_render!();
                         }
                         continue;
@@ -429,7 +788,7 @@ pub fn run(window: Arc<MacWindow>, rx: mpsc::Receiver<AppEvent>, mut drawer: Gpu
                     if keycode == kc::PAGE_UP || keycode == kc::PAGE_DOWN {
                         if let Some(tab) = tabs.active_tab() {
                             let mut state = tab.terminal.lock().unwrap();
-                            let row_count = state.grid.cells().len();
+                            let row_count = state.grid.screen_rows().len();
                             if keycode == kc::PAGE_UP {
                                 state.grid.scroll_up_view(row_count);
                             } else {
@@ -457,6 +816,22 @@ pub fn run(window: Arc<MacWindow>, rx: mpsc::Receiver<AppEvent>, mut drawer: Gpu
                         continue;
                     }
 
+                    // Cmd+Shift+A: select and copy the last command's output (OSC 133 C..D)
+                    if keycode == kc::ANSI_A && modifiers.contains(Modifiers::SHIFT) {
+                        if let Some(tab) = tabs.active_tab() {
+                            let cols = tab.terminal.lock().unwrap().grid.size().0;
+                            let output = tab.output_marks.lock().unwrap().last_output(cols);
+                            if let Some(((start_row, start_col), (end_row, end_col))) = output {
+                                sel.begin(start_row, start_col);
+                                sel.update(end_row, end_col);
+                                sel.finish();
+                                copy_selection(window.as_ref(), tab, &sel, config.copy_rich_text.then_some(base_palette));
+                                do_render!();
+                            }
+                        }
+                        continue;
+                    }
+
                     // Cmd+A: copy input line to clipboard
                     if keycode == kc::ANSI_A {
                         if let Some(tab) = tabs.active_tab() {
@@ -484,7 +859,7 @@ pub fn run(window: Arc<MacWindow>, rx: mpsc::Receiver<AppEvent>, mut drawer: Gpu
                             let sb_len = state.grid.scrollback_len() as u32;
                             let (cursor_row, _cursor_col) = state.grid.cursor_pos();
                             let abs_cursor_row = sb_len + cursor_row as u32;
-                            let cols = state.grid.cells().first().map_or(80, |r| r.len()) as u16;
+                            let cols = state.grid.size().0;
                             drop(state);
                             copy_mode.enter(abs_cursor_row, cols, &mut sel);
                             window.set_copy_mode(true);
@@ -498,10 +873,7 @@ pub fn run(window: Arc<MacWindow>, rx: mpsc::Receiver<AppEvent>, mut drawer: Gpu
                     if keycode == kc::ANSI_C {
                         if !sel.is_empty() {
                             if let Some(tab) = tabs.active_tab() {
-                                let state = tab.terminal.lock().unwrap();
-                                let text = selection::extract_text_absolute(&state.grid, &sel);
-                                drop(state);
-                                copy_to_clipboard(&text);
+                                copy_selection(window.as_ref(), tab, &sel, config.copy_rich_text.then_some(base_palette));
                             }
                         }
                         continue;
@@ -511,17 +883,15 @@ pub fn run(window: Arc<MacWindow>, rx: mpsc::Receiver<AppEvent>, mut drawer: Gpu
                     if keycode == kc::ANSI_V {
                         if let Ok(mut clipboard) = arboard::Clipboard::new() {
                             if let Ok(text) = clipboard.get_text() {
-                                if !text.is_empty() {
+                                let opts = config.paste_options();
+                                let text = paste::transform(&text, &opts);
+                                if paste::needs_confirmation(&text, &opts) {
+                                    let (title, message) = paste::confirmation_message(&text);
+                                    pending_paste = Some(text);
+                                    window.show_alert(&title, &message, Some(("Paste", AppEvent::ConfirmPaste)));
+                                } else if !text.is_empty() {
                                     if let Some(tab) = tabs.active_tab_mut() {
-                                        let bp = tab.bracketed_paste.load(std::sync::atomic::Ordering::Relaxed);
-                                        if bp {
-                                            let _ = tab.pty_writer.write_all(b"\x1b[200~");
-                                        }
-                                        let _ = tab.pty_writer.write_all(text.as_bytes());
-                                        if bp {
-                                            let _ = tab.pty_writer.write_all(b"\x1b[201~");
-                                        }
-                                        let _ = tab.pty_writer.flush();
+                                        write_paste(tab, &text);
                                     }
                                 }
                             }
@@ -540,7 +910,7 @@ pub fn run(window: Arc<MacWindow>, rx: mpsc::Receiver<AppEvent>, mut drawer: Gpu
                         drawer.set_font_size(font_size);
                         let (cw, ch) = drawer.cell_size();
                         let (w, h) = window.inner_size();
-                        let cols = (w as f32 / cw).floor().max(1.0) as u16;
+                        let cols = tabs.term_cols(w, cw);
                         let has_sb = tabs.active_tab().map_or(false, |t| t.terminal.lock().unwrap().grid.scrollback_len() > 0);
                         let term_rows = tabs.term_rows(h, ch, drawer.tab_bar_height(), title_bar_height, has_sb);
                         resize_all_tabs(&mut tabs, cols, term_rows);
@@ -554,12 +924,12 @@ pub fn run(window: Arc<MacWindow>, rx: mpsc::Receiver<AppEvent>, mut drawer: Gpu
                 if copy_mode.active {
                     let cols = tabs.active_tab().map_or(80u16, |t| {
                         let state = t.terminal.lock().unwrap();
-                        state.grid.cells().first().map_or(80, |r| r.len()) as u16
+                        state.grid.size().0
                     });
                     let max_row = tabs.active_tab().map_or(0u32, |t| {
                         let state = t.terminal.lock().unwrap();
                         let sb_len = state.grid.scrollback_len() as u32;
-                        let screen_rows = state.grid.cells().len() as u32;
+                        let screen_rows = state.grid.screen_rows().len() as u32;
                         sb_len + screen_rows - 1
                     });
 
@@ -584,10 +954,7 @@ pub fn run(window: Arc<MacWindow>, rx: mpsc::Receiver<AppEvent>, mut drawer: Gpu
                                 // y: 선택 텍스트 클립보드에 복사 후 모드 종료
                                 if !sel.is_empty() {
                                     if let Some(tab) = tabs.active_tab() {
-                                        let state = tab.terminal.lock().unwrap();
-                                        let text = selection::extract_text_absolute(&state.grid, &sel);
-                                        drop(state);
-                                        copy_to_clipboard(&text);
+                                        copy_selection(window.as_ref(), tab, &sel, config.copy_rich_text.then_some(base_palette));
                                     }
                                 }
                                 exit_copy_mode(&mut copy_mode, &mut sel, &window, &tabs);
@@ -604,9 +971,7 @@ pub fn run(window: Arc<MacWindow>, rx: mpsc::Receiver<AppEvent>, mut drawer: Gpu
                                     }
                                     drop(state);
                                     for u in &urls {
-                                        let _ = std::process::Command::new("open")
-                                            .arg(u)
-                                            .spawn();
+                                        open_url(u);
                                     }
                                 }
                             }
@@ -621,7 +986,7 @@ pub fn run(window: Arc<MacWindow>, rx: mpsc::Receiver<AppEvent>, mut drawer: Gpu
                     if let Some(tab) = tabs.active_tab() {
                         let mut state = tab.terminal.lock().unwrap();
                         let sb_len = state.grid.scrollback_len();
-                        let visible_rows = state.grid.cells().len();
+                        let visible_rows = state.grid.screen_rows().len();
                         let offset = state.grid.scroll_offset();
                         let view_top = sb_len.saturating_sub(offset) as u32;
                         let view_bottom = view_top + visible_rows as u32;
@@ -645,14 +1010,25 @@ pub fn run(window: Arc<MacWindow>, rx: mpsc::Receiver<AppEvent>, mut drawer: Gpu
                     continue;
                 }
                 if let Some(key_event) =
-                    growterm_macos::convert_key(keycode, characters.as_deref(), modifiers)
+                    growterm_window::convert_key(keycode, characters.as_deref(), modifiers)
                 {
-                    let bytes = growterm_input::encode(key_event);
+                    let kitty_flags = tabs
+                        .active_tab()
+                        .map_or(0, |tab| tab.kitty_keyboard_flags.load(Ordering::Relaxed));
+                    let bytes = growterm_input::encode_with_flags(key_event, kitty_flags);
                     pomodoro.on_input(&tab_scrollback_lens(&tabs));
-                    if bytes == b"\r" || bytes == b"\n" {
+                    scroll_to_bottom_on_keystroke(&tabs, &config);
+                    frame_scheduler.on_input();
+                    frame_stats.on_input(Instant::now());
+                    if bytes == b"\r" || bytes == b"\n" || bytes == b"\x1b[13u" {
                         ink_state.on_enter();
                         if let Some(tab) = tabs.active_tab_mut() {
                             tab.response_timer.on_enter();
+                            let prompt_row = {
+                                let state = tab.terminal.lock().unwrap();
+                                state.grid.scrollback_len() as u32 + state.grid.cursor_pos().0 as u32
+                            };
+                            tab.command_durations.on_enter(prompt_row, Instant::now());
                         }
                     } else {
                         ink_state.on_key_input(&bytes);
@@ -663,27 +1039,60 @@ pub fn run(window: Arc<MacWindow>, rx: mpsc::Receiver<AppEvent>, mut drawer: Gpu
                     }
                 }
             }
+            AppEvent::KeyRelease {
+                keycode,
+                characters,
+                modifiers,
+            } => {
+                if copy_mode.active || pomodoro.is_input_blocked() {
+                    continue;
+                }
+                let Some(tab) = tabs.active_tab_mut() else {
+                    continue;
+                };
+                let kitty_flags = tab.kitty_keyboard_flags.load(Ordering::Relaxed);
+                if let Some(bytes) =
+                    growterm_window::convert_key(keycode, characters.as_deref(), modifiers)
+                        .and_then(|key_event| growterm_input::encode_release(key_event, kitty_flags))
+                {
+                    let _ = tab.pty_writer.write_all(&bytes);
+                    let _ = tab.pty_writer.flush();
+                }
+            }
             AppEvent::MouseDown(x, y, modifiers) => {
                 let (cw, ch) = drawer.cell_size();
 
                 // Tab bar click: start drag
                 if tabs.show_tab_bar() && crate::tab::hit_test_tab_bar(y as f32, drawer.tab_bar_height(), tabs.tab_bar_y(title_bar_height)) {
                     let screen_w = window.inner_size().0 as f32;
-                    if let Some(index) = tabs.tab_index_at_x(x as f32, screen_w) {
-                        tab_dragging = Some(index);
-                        tab_drag_start_x = x as f32;
-                        window.request_redraw();
+                    match tabs.tab_bar_hit(x as f32, screen_w) {
+                        Some(TabStripHit::Tab(index)) => {
+                            tab_dragging = Some(index);
+                            tab_drag_start_x = x as f32;
+                            window.request_redraw();
+                        }
+                        Some(TabStripHit::ScrollLeft) => {
+                            let tab_w = tabs.tab_strip(screen_w).tab_width;
+                            tabs.scroll_tab_bar(-tab_w, screen_w);
+                            window.request_redraw();
+                        }
+                        Some(TabStripHit::ScrollRight) => {
+                            let tab_w = tabs.tab_strip(screen_w).tab_width;
+                            tabs.scroll_tab_bar(tab_w, screen_w);
+                            window.request_redraw();
+                        }
+                        None => {}
                     }
                     continue;
                 }
 
                 // Mouse tracking: send SGR report to PTY
                 {
-                    let y_offset = tabs.mouse_y_offset(drawer.tab_bar_height(), title_bar_height, has_scrollback);
+                    let origin = tabs.content_origin(drawer.tab_bar_height(), title_bar_height, has_scrollback);
                     if let Some(tab) = tabs.active_tab_mut() {
                         let mode = tab.mouse_mode.load(Ordering::Relaxed);
                         if mode > 0 {
-                            send_sgr_mouse(tab, x, y, y_offset, cw, ch, 0, 'M');
+                            send_sgr_mouse(tab, x, y, origin, cw, ch, 0, 'M');
                             continue;
                         }
                     }
@@ -707,8 +1116,38 @@ pub fn run(window: Arc<MacWindow>, rx: mpsc::Receiver<AppEvent>, mut drawer: Gpu
                 }
 
                 let (screen_row, col) =
-                    selection::mouse_pixel_to_cell(x as f32, y as f32, cw, ch, tabs.mouse_y_offset(drawer.tab_bar_height(), title_bar_height, has_scrollback));
+                    selection::mouse_pixel_to_cell(x as f32, y as f32, cw, ch, tabs.content_origin(drawer.tab_bar_height(), title_bar_height, has_scrollback));
                 let abs_row = screen_to_abs_row(&tabs, screen_row);
+                let col = mouse_cell_col(&tabs, abs_row, col, config.bidi);
+
+                // Option+Click: move the shell cursor to the clicked cell with arrow keys
+                if modifiers.contains(Modifiers::ALT) && !modifiers.contains(Modifiers::SUPER) && !copy_mode.active {
+                    if let Some(tab) = tabs.active_tab_mut() {
+                        let state = tab.terminal.lock().unwrap();
+                        let grid = &state.grid;
+                        // 기본 화면 맨 아래에서 커서가 보일 때만 (쉘 프롬프트로 간주)
+                        let at_prompt = grid.private_mode(1049) == Some(false)
+                            && grid.scroll_offset() == 0
+                            && grid.cursor_visible();
+                        let moves = if at_prompt {
+                            click_to_move::arrow_moves(
+                                &grid.screen_rows().collect::<Vec<_>>(),
+                                grid.cursor_pos(),
+                                (screen_row, col),
+                            )
+                        } else {
+                            None
+                        };
+                        drop(state);
+                        if let Some(moves) = moves.filter(|&m| m != 0) {
+                            let _ = tab.pty_writer.write_all(&click_to_move::arrow_bytes(moves));
+                            let _ = tab.pty_writer.flush();
+                        }
+                    }
+                    sel.clear();
+                    window.request_redraw();
+                    continue;
+                }
 
                 // Cmd+Click: open URL under cursor
                 if modifiers.contains(Modifiers::SUPER) {
@@ -719,9 +1158,13 @@ pub fn run(window: Arc<MacWindow>, rx: mpsc::Receiver<AppEvent>, mut drawer: Gpu
                         drop(state);
                         let char_col = selection::cell_col_to_char_index(&row_cells, col as usize);
                         if let Some(found_url) = url::find_url_at(&row_text, char_col) {
-                            let _ = std::process::Command::new("open")
-                                .arg(found_url)
-                                .spawn();
+                            open_url(found_url);
+                        } else if let Some(link) = url::find_file_link_at(&row_text, char_col) {
+                            if let Some(path) = resolve_file_link(tab, &link) {
+                                let editor = std::env::var("EDITOR").ok();
+                                let (program, args) = url::editor_command(editor.as_deref(), &path.to_string_lossy(), &link);
+                                let _ = std::process::Command::new(program).args(args).spawn();
+                            }
                         }
                     }
                     hover_url_range = None;
@@ -729,7 +1172,24 @@ pub fn run(window: Arc<MacWindow>, rx: mpsc::Receiver<AppEvent>, mut drawer: Gpu
                     continue;
                 }
 
-                sel.begin(abs_row, col);
+                // Double-click: select the word under the cursor
+                let now = Instant::now();
+                let double_click = last_click.is_some_and(|(at, row, c)| {
+                    row == abs_row && c == col && now.duration_since(at) < DOUBLE_CLICK_INTERVAL
+                });
+                if double_click {
+                    last_click = None;
+                    if let Some(tab) = tabs.active_tab() {
+                        let row_cells = selection::row_cells_absolute(&tab.terminal.lock().unwrap().grid, abs_row);
+                        let (start, end) = selection::word_bounds(&row_cells, col as usize, &config.word_separators);
+                        sel.begin(abs_row, start as u16);
+                        sel.update(abs_row, end as u16);
+                        sel.finish();
+                    }
+                } else {
+                    last_click = Some((now, abs_row, col));
+                    sel.begin(abs_row, col);
+                }
                 window.request_redraw();
             }
             AppEvent::MouseDragged(x, y) => {
@@ -746,12 +1206,12 @@ pub fn run(window: Arc<MacWindow>, rx: mpsc::Receiver<AppEvent>, mut drawer: Gpu
                 }
                 // Mouse tracking: send SGR drag report to PTY
                 {
-                    let y_offset = tabs.mouse_y_offset(drawer.tab_bar_height(), title_bar_height, has_scrollback);
+                    let origin = tabs.content_origin(drawer.tab_bar_height(), title_bar_height, has_scrollback);
                     if let Some(tab) = tabs.active_tab_mut() {
                         let mode = tab.mouse_mode.load(Ordering::Relaxed);
                         if mode >= 2 {
                             let (cw, ch) = drawer.cell_size();
-                            send_sgr_mouse(tab, x, y, y_offset, cw, ch, 32, 'M');
+                            send_sgr_mouse(tab, x, y, origin, cw, ch, 32, 'M');
                             continue;
                         }
                     }
@@ -766,9 +1226,10 @@ pub fn run(window: Arc<MacWindow>, rx: mpsc::Receiver<AppEvent>, mut drawer: Gpu
                     let (cw, ch) = drawer.cell_size();
                     let (screen_row, col) = selection::mouse_pixel_to_cell(
                         x as f32, y as f32, cw, ch,
-                        tabs.mouse_y_offset(drawer.tab_bar_height(), title_bar_height, has_scrollback),
+                        tabs.content_origin(drawer.tab_bar_height(), title_bar_height, has_scrollback),
                     );
                     let abs_row = screen_to_abs_row(&tabs, screen_row);
+                    let col = mouse_cell_col(&tabs, abs_row, col, config.bidi);
                     sel.update(abs_row, col);
                     window.request_redraw();
                 }
@@ -777,12 +1238,12 @@ pub fn run(window: Arc<MacWindow>, rx: mpsc::Receiver<AppEvent>, mut drawer: Gpu
                 if let Some(drag_idx) = tab_dragging.take() {
                     let drag_distance = (x as f32 - tab_drag_start_x).abs();
                     let screen_w = window.inner_size().0 as f32;
-                    let tab_w = screen_w / tabs.tab_count().max(1) as f32;
+                    let tab_w = tabs.tab_strip(screen_w).tab_width;
                     if drag_distance < tab_w * 0.3 {
                         // Small movement = click: switch to tab
-                        save_tab_state(&mut copy_mode, &mut sel, &mut tabs);
+                        save_tab_state(&mut copy_mode, &mut sel, &ink_state, &mut tabs);
                         tabs.switch_to(drag_idx);
-                        restore_tab_state(&mut copy_mode, &mut sel, &mut preedit, &window, &tabs);
+                        restore_tab_state(&mut copy_mode, &mut sel, &mut preedit, &mut ink_state, &window, &tabs);
                     }
                     window.request_redraw();
                     continue;
@@ -798,58 +1259,76 @@ pub fn run(window: Arc<MacWindow>, rx: mpsc::Receiver<AppEvent>, mut drawer: Gpu
 
                 // Mouse tracking: send SGR release report to PTY
                 {
-                    let y_offset = tabs.mouse_y_offset(drawer.tab_bar_height(), title_bar_height, has_scrollback);
+                    let origin = tabs.content_origin(drawer.tab_bar_height(), title_bar_height, has_scrollback);
                     if let Some(tab) = tabs.active_tab_mut() {
                         let mode = tab.mouse_mode.load(Ordering::Relaxed);
                         if mode > 0 {
-                            send_sgr_mouse(tab, x, y, y_offset, cw, ch, 0, 'm');
+                            send_sgr_mouse(tab, x, y, origin, cw, ch, 0, 'm');
                             continue;
                         }
                     }
                 }
 
                 let (screen_row, col) =
-                    selection::mouse_pixel_to_cell(x as f32, y as f32, cw, ch, tabs.mouse_y_offset(drawer.tab_bar_height(), title_bar_height, has_scrollback));
+                    selection::mouse_pixel_to_cell(x as f32, y as f32, cw, ch, tabs.content_origin(drawer.tab_bar_height(), title_bar_height, has_scrollback));
                 let abs_row = screen_to_abs_row(&tabs, screen_row);
-                sel.update(abs_row, col);
+                let col = mouse_cell_col(&tabs, abs_row, col, config.bidi);
+                // 더블클릭으로 이미 단어를 선택했으면 끝점을 건드리지 않음
+                if sel.active {
+                    sel.update(abs_row, col);
+                }
                 sel.finish();
                 window.request_redraw();
             }
             AppEvent::MouseMoved(x, y, modifiers) => {
+                last_mouse_y = y;
                 let new_range = if modifiers.contains(Modifiers::SUPER) {
                     let (cw, ch) = drawer.cell_size();
                     let (screen_row, col) = selection::mouse_pixel_to_cell(
                         x as f32, y as f32, cw, ch,
-                        tabs.mouse_y_offset(drawer.tab_bar_height(), title_bar_height, has_scrollback),
+                        tabs.content_origin(drawer.tab_bar_height(), title_bar_height, has_scrollback),
                     );
                     if let Some(tab) = tabs.active_tab() {
                         let abs_row = screen_to_abs_row(&tabs, screen_row);
+                        let col = mouse_cell_col(&tabs, abs_row, col, config.bidi);
                         let state = tab.terminal.lock().unwrap();
-                        let row_text = selection::row_text_absolute(&state.grid, abs_row);
-                        let row_cells = selection::row_cells_absolute(&state.grid, abs_row);
+                        let view_base = state.grid.scrollback_len().saturating_sub(state.grid.scroll_offset()) as u32;
+                        let key = ViewKey { generation: state.grid.generation(), view_base };
+                        // 스캔 결과가 지금 화면 것이면 찾아보기만, 아니면 이 행만 스캔
+                        let span = tab.links.link_at(key, abs_row, col, || selection::row_cells_absolute(&state.grid, abs_row));
                         drop(state);
-                        let char_col = selection::cell_col_to_char_index(&row_cells, col as usize);
-                        if let Some((start, end)) = url::find_url_range_at(&row_text, char_col)
-                        {
-                            let start_cell = selection::char_index_to_cell_col(&row_cells, start) as u16;
-                            let end_cell = selection::char_index_to_cell_col(&row_cells, end) as u16;
-                            Some((abs_row, start_cell, end_cell))
-                        } else {
-                            None
-                        }
+                        span.filter(|span| match &span.link {
+                            url::Link::Url(_) => true,
+                            url::Link::File(link) => resolve_file_link(tab, link).is_some(),
+                        })
+                        .map(|span| (abs_row, span.start, span.end))
                     } else {
                         None
                     }
                 } else {
                     None
                 };
+                let on_tab_bar = tabs.show_tab_bar()
+                    && crate::tab::hit_test_tab_bar(y as f32, drawer.tab_bar_height(), tabs.tab_bar_y(title_bar_height));
+                let on_scrollbar = has_scrollback && (x as f32) >= window.inner_size().0 as f32 - SCROLLBAR_HIT_WIDTH;
+                let cursor = mouse_cursor_for(on_tab_bar, on_scrollbar, new_range.is_some());
+                if cursor != mouse_cursor {
+                    window.set_mouse_cursor(cursor);
+                    mouse_cursor = cursor;
+                }
                 if new_range != hover_url_range {
-                    window.set_pointing_hand_cursor(new_range.is_some());
                     hover_url_range = new_range;
                     window.request_redraw();
                 }
             }
-            AppEvent::ScrollWheel(delta_y) => {
+            AppEvent::ScrollWheel(delta_y, precise) => {
+                // 탭바 위 휠: 넘친 탭바를 가로 스크롤
+                if tabs.show_tab_bar() && crate::tab::hit_test_tab_bar(last_mouse_y as f32, drawer.tab_bar_height(), tabs.tab_bar_y(title_bar_height)) {
+                    let screen_w = window.inner_size().0 as f32;
+                    tabs.scroll_tab_bar(-delta_y as f32, screen_w);
+                    window.request_redraw();
+                    continue;
+                }
                 // Mouse tracking: send SGR scroll report to PTY
                 if let Some(tab) = tabs.active_tab_mut() {
                     let mode = tab.mouse_mode.load(Ordering::Relaxed);
@@ -873,22 +1352,31 @@ pub fn run(window: Arc<MacWindow>, rx: mpsc::Receiver<AppEvent>, mut drawer: Gpu
                         continue;
                     }
                 }
-                scroll_accum += delta_y;
                 let (_, ch) = drawer.cell_size();
                 let line_height = if ch > 0.0 { ch as f64 } else { 20.0 };
-                let lines = (scroll_accum / line_height).trunc() as i32;
-                if lines != 0 {
-                    scroll_accum -= lines as f64 * line_height;
-                    if let Some(tab) = tabs.active_tab() {
-                        let mut state = tab.terminal.lock().unwrap();
-                        if lines > 0 {
-                            state.grid.scroll_up_view(lines as usize);
-                        } else {
-                            state.grid.scroll_down_view((-lines) as usize);
-                        }
+                if let Some(tab) = tabs.active_tab() {
+                    let mut state = tab.terminal.lock().unwrap();
+                    let before = state.grid.scroll_offset();
+                    // 트랙패드는 픽셀 단위로 부드럽게, 마우스 휠은 줄 단위로
+                    let rows = if precise {
+                        smooth_scroll.scroll_pixels(delta_y, line_height, before, Instant::now())
+                    } else {
+                        smooth_scroll.scroll_lines(delta_y, line_height)
+                    };
+                    scroll_view_rows(&mut state.grid, rows);
+                    let after = state.grid.scroll_offset();
+                    smooth_scroll.settle(before, rows, after);
+                    drop(state);
+                    if before == after && !precise {
+                        continue;
                     }
-                    scrollbar_visible_until = Some(Instant::now() + SCROLLBAR_SHOW_DURATION);
-                    do_render!(scrollbar: true);
+                }
+                scrollbar_visible_until = Some(Instant::now() + SCROLLBAR_SHOW_DURATION);
+                do_render!(scrollbar: true);
+            }
+            AppEvent::ScrollGestureEnded => {
+                if smooth_scroll.release(Instant::now()) {
+                    window.request_redraw();
                 }
             }
             AppEvent::Resize(mut w, mut h) => {
@@ -907,19 +1395,178 @@ pub fn run(window: Arc<MacWindow>, rx: mpsc::Receiver<AppEvent>, mut drawer: Gpu
                 }
                 drawer.resize(w, h);
                 let (cw, ch) = drawer.cell_size();
-                let cols = (w as f32 / cw).floor().max(1.0) as u16;
+                let cols = tabs.term_cols(w, cw);
                 let has_sb = tabs.active_tab().map_or(false, |t| t.terminal.lock().unwrap().grid.scrollback_len() > 0);
                 let term_rows = tabs.term_rows(h, ch, drawer.tab_bar_height(), title_bar_height, has_sb);
                 resize_all_tabs(&mut tabs, cols, term_rows);
+                save_window_frame(window.as_ref(), &mut config);
                 do_render!();
             }
+            AppEvent::ClearScrollback => {
+                if copy_mode.active {
+                    exit_copy_mode(&mut copy_mode, &mut sel, &window, &tabs);
+                }
+                clear_scrollback_and_screen(&mut tabs);
+                sel = Selection::default();
+                hover_url_range = None;
+                smooth_scroll.stop();
+                do_render!();
+            }
+            AppEvent::ExportBuffer => {
+                window.show_save_panel("growterm.txt", AppEvent::ExportBufferTo);
+            }
+            AppEvent::ExportBufferTo(path) => {
+                if let Some(tab) = tabs.active_tab() {
+                    if let Err(e) = export_buffer(tab, std::path::Path::new(&path), base_palette) {
+                        window.show_alert("Export Failed", &format!("{path}: {e}"), None);
+                    }
+                }
+            }
+            AppEvent::CopyScreenText => {
+                if let Some(tab) = tabs.active_tab() {
+                    let text = crate::screenshot::screen_text(&tab.terminal.lock().unwrap().grid);
+                    copy_to_clipboard(&text);
+                }
+            }
+            AppEvent::SaveScreenshot => {
+                window.show_save_panel("growterm.png", AppEvent::SaveScreenshotTo);
+            }
+            AppEvent::SaveScreenshotTo(path) => {
+                let (width, height) = drawer.surface_size();
+                let saved = match do_render!(capture) {
+                    Some(pixels) => crate::screenshot::write_png(std::path::Path::new(&path), width, height, &pixels),
+                    None => Err(std::io::Error::other("this renderer can't read frames back")),
+                };
+                if let Err(e) = saved {
+                    window.show_alert("Screenshot Failed", &format!("{path}: {e}"), None);
+                }
+            }
+            AppEvent::WindowMoved => {
+                save_window_frame(window.as_ref(), &mut config);
+            }
+            AppEvent::FullScreenChanged(enabled) => {
+                if config.fullscreen != enabled {
+                    config.fullscreen = enabled;
+                    config.save();
+                }
+                // 전환 애니메이션 중 크기 변경을 놓쳤을 수 있으니 최종 크기로 다시 맞춤
+                let (w, h) = window.inner_size();
+                deferred = Some(AppEvent::Resize(w, h));
+            }
+            AppEvent::FocusChanged(focused) => {
+                window_focused = focused;
+                if focused {
+                    crate::ipc::set_focused(ipc_inbox.id);
+                }
+                if let Some(tab) = tabs.active_tab_mut() {
+                    send_focus_report(tab, focused);
+                }
+                window.request_redraw();
+            }
+            AppEvent::ScaleFactorChanged(scale) => {
+                drawer.set_scale_factor(scale);
+                tabs.set_window_padding(config.window_padding, scale);
+                if transparent_tab_bar {
+                    title_bar_height = window.title_bar_height() as f32;
+                }
+                let (cw, ch) = drawer.cell_size();
+                let (w, h) = window.inner_size();
+                let cols = tabs.term_cols(w, cw);
+                let has_sb = tabs.active_tab().map_or(false, |t| t.terminal.lock().unwrap().grid.scrollback_len() > 0);
+                let term_rows = tabs.term_rows(h, ch, drawer.tab_bar_height(), title_bar_height, has_sb);
+                resize_all_tabs(&mut tabs, cols, term_rows);
+                window.request_redraw();
+            }
             AppEvent::RedrawRequested => {
+                tabs.reveal_active_tab(window.inner_size().0 as f32);
+                if deferred.is_none() && config_changed.swap(false, Ordering::Relaxed) {
+                    deferred = Some(AppEvent::ReloadConfig);
+                }
+                for (request, reply) in ipc_inbox.take() {
+                    use crate::ipc::{Request, Response};
+                    let find_tab = |tabs: &TabManager, id: Option<u64>| match id {
+                        Some(id) => tabs.tabs().iter().position(|t| t.id == id),
+                        None => (!tabs.is_empty()).then(|| tabs.active_index()),
+                    };
+                    let response = match request {
+                        Request::NewTab { cwd, command, .. } => {
+                            let profile = crate::config::Profile { command, cwd, ..Default::default() };
+                            let before = tabs.tab_count();
+                            open_tab!(profile.pty_options(config.pty_options()), ColorOverride::default());
+                            match tabs.active_tab() {
+                                Some(tab) if tabs.tab_count() > before => Response::ok(format!("{}\n", tab.id)),
+                                _ => Response::error("failed to spawn tab"),
+                            }
+                        }
+                        Request::SendText { tab, text, .. } => match find_tab(&tabs, tab) {
+                            Some(index) => {
+                                let tab = &mut tabs.tabs_mut()[index];
+                                let _ = tab.pty_writer.write_all(text.as_bytes());
+                                let _ = tab.pty_writer.flush();
+                                Response::ok("")
+                            }
+                            None => Response::error(format!("no tab {}", tab.unwrap_or_default())),
+                        },
+                        Request::ListTabs => Response::ok(list_tabs(&tabs, ipc_inbox.id)),
+                        Request::Activate { tab, .. } => match find_tab(&tabs, tab) {
+                            Some(index) => {
+                                if index != tabs.active_index() {
+                                    save_tab_state(&mut copy_mode, &mut sel, &ink_state, &mut tabs);
+                                    tabs.switch_to(index);
+                                    restore_tab_state(&mut copy_mode, &mut sel, &mut preedit, &mut ink_state, &window, &tabs);
+                                }
+                                window.focus();
+                                Response::ok("")
+                            }
+                            None => Response::error(format!("no tab {}", tab.unwrap_or_default())),
+                        },
+                    };
+                    let _ = reply.send(response);
+                }
+                // 관성 스크롤: 프레임마다 한 단계씩 진행
+                if smooth_scroll.is_animating() {
+                    let (_, ch) = drawer.cell_size();
+                    let line_height = if ch > 0.0 { ch as f64 } else { 20.0 };
+                    match tabs.active_tab() {
+                        Some(tab) => {
+                            let mut state = tab.terminal.lock().unwrap();
+                            let before = state.grid.scroll_offset();
+                            if let Some(rows) = smooth_scroll.tick(line_height, before, Instant::now()) {
+                                scroll_view_rows(&mut state.grid, rows);
+                                smooth_scroll.settle(before, rows, state.grid.scroll_offset());
+                            }
+                        }
+                        None => smooth_scroll.stop(),
+                    }
+                    scrollbar_visible_until = Some(Instant::now() + SCROLLBAR_SHOW_DURATION);
+                }
                 // Expire copy flash
                 if let Some((_, _, t)) = copy_flash {
                     if t.elapsed() >= COPY_FLASH_DURATION {
                         copy_flash = None;
                     }
                 }
+                // 쉘이 종료된 탭: 닫거나 종료 안내를 남김
+                let exited: Vec<(u64, growterm_pty::ChildExit)> = tabs
+                    .tabs()
+                    .iter()
+                    .filter(|t| !t.exit_handled)
+                    .filter_map(|t| t.child_exit.lock().unwrap().map(|exit| (t.id, exit)))
+                    .collect();
+                for (id, exit) in exited {
+                    if config.close_tab_on_exit {
+                        if let Some(index) = tabs.tabs().iter().position(|t| t.id == id) {
+                            close_tab!(index);
+                        }
+                    } else if let Some(tab) = tabs.tabs_mut().iter_mut().find(|t| t.id == id) {
+                        tab.exit_handled = true;
+                        let mut st = tab.terminal.lock().unwrap();
+                        let crate::tab::TerminalState { grid, vt_parser, .. } = &mut *st;
+                        vt_parser.parse_with(crate::tab::exit_message(exit).as_bytes(), |cmd| grid.apply(&cmd));
+                        drop(st);
+                        tab.dirty.store(true, Ordering::Relaxed);
+                    }
+                }
                 let tick_result = pomodoro.tick();
                 if tick_result == TickResult::StartedBreak {
                     if let Some(f) = flog.as_mut() { f.log("break_started"); }
@@ -934,13 +1581,70 @@ pub fn run(window: Arc<MacWindow>, rx: mpsc::Receiver<AppEvent>, mut drawer: Gpu
                         }
                     }
                 }
+                session_saver.maybe_save(&tabs, &config);
+                if config.scrollback_memory_cap_mb.is_some() {
+                    let report = diagnostics::collect(&tabs);
+                    let total = diagnostics::total_bytes(&report);
+                    if memory_watchdog.check(total) {
+                        window.show_alert(
+                            "Scrollback memory is high",
+                            &diagnostics::format_report(&report, drawer.atlas_usage()),
+                            Some(("Trim Scrollback", AppEvent::TrimScrollback)),
+                        );
+                    }
+                }
                 // Feed PTY output timestamp to each tab's response timer
+                let active_id = tabs.active_tab().map(|t| t.id);
+                let silence_after = config.tab_silence_seconds.map(Duration::from_secs);
                 for tab in tabs.tabs_mut() {
+                    let is_active = Some(tab.id) == active_id;
                     let ts = tab.last_pty_output_at.lock().unwrap().take();
                     if let Some(ts) = ts {
+                        if is_active {
+                            frame_stats.on_pty_output(ts);
+                        }
                         tab.response_timer.on_pty_output(ts);
+                        tab.activity.on_output(ts, is_active);
                     }
+                    tab.activity.tick(Instant::now(), is_active, silence_after);
+                    tab.ssh.refresh(&tab.pty_writer, Instant::now());
+                    tab.foreground.refresh(&tab.pty_writer, Instant::now());
                     tab.response_timer.tick();
+                    let output = tab.command_output.lock().unwrap().take_last();
+                    if let Some(output) = output {
+                        tab.response_timer.on_command_output(output);
+                        tab.command_durations.on_command_done(Instant::now());
+                    }
+                }
+                frame_stats.record_pty_bytes(
+                    Instant::now(),
+                    tabs.tabs().iter().map(|t| t.pty_bytes_read.load(Ordering::Relaxed)).sum(),
+                );
+                // BEL: 경고음, 화면 깜빡임, 백그라운드 탭 표시 + Dock 배지,
+                // 포커스가 없으면 Dock 아이콘 튀김
+                let now = Instant::now();
+                for tab in tabs.tabs_mut() {
+                    let is_active = Some(tab.id) == active_id;
+                    if tab.bell.swap(false, Ordering::Relaxed) {
+                        let actions = bell.on_bell(is_active, window_focused, now);
+                        if actions.beep {
+                            window.beep();
+                        }
+                        if actions.badge {
+                            tab.bell_pending = true;
+                        }
+                        if actions.attention {
+                            window.request_attention();
+                        }
+                    }
+                    if is_active && window_focused {
+                        tab.bell_pending = false;
+                    }
+                }
+                let any_bell_pending = tabs.tabs().iter().any(|t| t.bell_pending);
+                if any_bell_pending != dock_badged {
+                    window.set_dock_badge(any_bell_pending.then_some("!"));
+                    dock_badged = any_bell_pending;
                 }
                 // Skip rendering while the PTY app is inside a synchronized
                 // output block to avoid painting an intermediate state.
@@ -951,6 +1655,17 @@ pub fn run(window: Arc<MacWindow>, rx: mpsc::Receiver<AppEvent>, mut drawer: Gpu
                     if let Some(f) = flog.as_mut() { f.log("skip_sync_output"); }
                     continue;
                 }
+                // Output-only frames are coalesced to the frame rate; the
+                // deferred frame is drawn when frame_deadline expires.
+                let output_dirty = tabs
+                    .active_tab()
+                    .map_or(false, |t| t.dirty.load(Ordering::Relaxed));
+                if output_dirty {
+                    if let FrameDecision::Defer(at) = frame_scheduler.decide(Instant::now()) {
+                        frame_deadline = Some(frame_deadline.map_or(at, |d| d.min(at)));
+                        continue;
+                    }
+                }
                 let was_dirty = tabs
                     .active_tab()
                     .map_or(false, |t| t.dirty.swap(false, Ordering::Relaxed));
@@ -960,23 +1675,29 @@ pub fn run(window: Arc<MacWindow>, rx: mpsc::Receiver<AppEvent>, mut drawer: Gpu
                 }
                 let (cw, ch) = drawer.cell_size();
                 let next_ime_cursor_rect = tabs.active_tab().and_then(|tab| {
-                    let state = tab.terminal.lock().unwrap();
-                    let scrolled = state.grid.scroll_offset() > 0;
-                    let cursor = if scrolled || !state.grid.cursor_visible() {
+                    let mut frame = tab.frame.lock().unwrap();
+                    let snap = frame.latest(&tab.terminal);
+                    let scrolled = snap.scroll_offset > 0;
+                    let cursor = if scrolled || !snap.cursor_visible {
                         None
                     } else {
-                        Some(state.grid.cursor_pos())
+                        Some(snap.cursor)
                     };
-                    let preedit_pos_override = if preedit.is_empty() || scrolled {
+                    let preedit_pos_override = if preedit.is_empty() {
                         None
                     } else {
-                        let visible = state.grid.visible_cells();
-                        ink_state.preedit_pos(&visible)
+                        preedit_anchor(snap, &ink_state).and_then(|anchor| {
+                            let cols = snap.rows.first().map_or(0, |line| line.len()) as u16;
+                            growterm_render_cmd::preedit_layout(&preedit, anchor, cols, snap.rows.len() as u16)
+                                .first()
+                                .map(|&(row, col, _)| (row, col))
+                        })
                     };
                     ime_cursor_rect_pixels(
                         tabs.show_tab_bar(),
                         drawer.tab_bar_height(),
                         title_bar_height,
+                        tabs.window_padding(),
                         cw,
                         ch,
                         cursor,
@@ -995,6 +1716,10 @@ pub fn run(window: Arc<MacWindow>, rx: mpsc::Receiver<AppEvent>, mut drawer: Gpu
                 }
                 if let Some(f) = flog.as_mut() { f.log("render_start"); }
                 do_render!();
+                frame_scheduler.frame_drawn(Instant::now());
+                if smooth_scroll.is_animating() {
+                    window.request_redraw();
+                }
                 if let Some(f) = flog.as_mut() { f.log("render_done"); }
                 if was_dirty || preedit_changed {
                     if let Some(ref path) = grid_dump_path {
@@ -1007,16 +1732,15 @@ pub fn run(window: Arc<MacWindow>, rx: mpsc::Receiver<AppEvent>, mut drawer: Gpu
                             let has_content =
                                 state
                                     .grid
-                                    .cells()
-                                    .iter()
-                                    .any(|row: &Vec<growterm_types::Cell>| {
+                                    .screen_rows()
+                                    .any(|row: &growterm_grid::Row| {
                                         row.iter()
                                             .any(|c| c.character != '\0' && c.character != ' ')
                                     });
                             if has_content {
                                 let (crow, ccol) = state.grid.cursor_pos();
                                 let mut dump = format!("cursor:{crow},{ccol}\ngrid:\n");
-                                for (row_idx, row) in state.grid.cells().iter().enumerate() {
+                                for (row_idx, row) in state.grid.screen_rows().enumerate() {
                                     let mut text: String = row
                                         .iter()
                                         .map(|c: &growterm_types::Cell| c.character)
@@ -1039,6 +1763,7 @@ pub fn run(window: Arc<MacWindow>, rx: mpsc::Receiver<AppEvent>, mut drawer: Gpu
                                     );
                                     dump.push('\n');
                                 }
+                                let snapshot = grid_snapshot_path.as_ref().map(|_| state.grid.snapshot());
                                 drop(state);
                                 if let Some(ref dropped_path) = test_dropped_path {
                                     if !test_drop_sent && !dropped_path.is_empty() {
@@ -1056,6 +1781,11 @@ pub fn run(window: Arc<MacWindow>, rx: mpsc::Receiver<AppEvent>, mut drawer: Gpu
                                         continue;
                                     }
                                 }
+                                if let (Some(snapshot_path), Some(snapshot)) = (&grid_snapshot_path, snapshot) {
+                                    if let Ok(json) = serde_json::to_string(&snapshot) {
+                                        let _ = std::fs::write(snapshot_path, json);
+                                    }
+                                }
                                 let _ = std::fs::write(path, &dump);
                             }
                         }
@@ -1091,6 +1821,16 @@ pub fn run(window: Arc<MacWindow>, rx: mpsc::Receiver<AppEvent>, mut drawer: Gpu
                     window.set_title(&title);
                 }
             }
+            AppEvent::SkipBreak => {
+                if pomodoro.skip_break() {
+                    if let Some(f) = flog.as_mut() { f.log("break_skipped"); }
+                    do_render!();
+                }
+            }
+            AppEvent::ExtendBreak => {
+                pomodoro.extend_break(crate::pomodoro::BREAK_EXTENSION_SECS);
+                do_render!();
+            }
             AppEvent::ToggleResponseTimer => {
                 response_timer_enabled = !response_timer_enabled;
                 for tab in tabs.tabs_mut() {
@@ -1123,16 +1863,62 @@ pub fn run(window: Arc<MacWindow>, rx: mpsc::Receiver<AppEvent>, mut drawer: Gpu
                     0.0
                 };
             }
+            AppEvent::ToggleDebugView => {
+                debug_view = !debug_view;
+                window.set_debug_view_checked(debug_view);
+                for tab in tabs.tabs() {
+                    tab.terminal.lock().unwrap().vt_parser.set_show_control_pictures(debug_view);
+                }
+                do_render!();
+            }
+            AppEvent::ToggleLatencyHud => {
+                latency_hud = !latency_hud;
+                window.set_latency_hud_checked(latency_hud);
+                do_render!();
+            }
+            AppEvent::ToggleResponseReport => {
+                response_report = !response_report;
+                do_render!();
+            }
+            AppEvent::ExportResponseTimes => {
+                window.show_save_panel("response-times.csv", AppEvent::ExportResponseTimesTo);
+            }
+            AppEvent::ExportResponseTimesTo(path) => {
+                if let Some(tab) = tabs.active_tab() {
+                    if let Err(e) = std::fs::write(&path, tab.response_timer.to_csv()) {
+                        window.show_alert("Export Failed", &format!("{path}: {e}"), None);
+                    }
+                }
+            }
             AppEvent::ReloadConfig => {
-                let new_config = crate::config::Config::load();
+                let new_config = match crate::config::Config::try_load() {
+                    Ok(c) => c,
+                    Err(e) => {
+                        eprintln!("config.toml: {e}");
+                        continue;
+                    }
+                };
+                // 앱이 직접 저장한 경우(창 위치 등)는 바뀐 것이 없음
+                if new_config == config {
+                    continue;
+                }
                 // Apply font changes
-                if new_config.font_family != config.font_family || new_config.font_size != config.font_size {
+                if new_config.font_family != config.font_family
+                    || new_config.font_size != config.font_size
+                    || new_config.subpixel_positioning != config.subpixel_positioning
+                    || new_config.line_height != config.line_height
+                    || new_config.cell_padding != config.cell_padding
+                    || new_config.window_padding != config.window_padding
+                {
                     font_size = new_config.font_size;
                     let font_path = crate::resolve_font_path(&new_config.font_family);
                     drawer.set_font(font_path.as_deref(), font_size);
+                    drawer.set_subpixel_positioning(new_config.subpixel_positioning);
+                    drawer.set_cell_spacing(new_config.line_height, new_config.cell_padding);
+                    tabs.set_window_padding(new_config.window_padding, window.backing_scale_factor());
                     let (cw, ch) = drawer.cell_size();
                     let (w, h) = window.inner_size();
-                    let cols = (w as f32 / cw).floor().max(1.0) as u16;
+                    let cols = tabs.term_cols(w, cw);
                     let has_sb = tabs.active_tab().map_or(false, |t| t.terminal.lock().unwrap().grid.scrollback_len() > 0);
                     let term_rows = tabs.term_rows(h, ch, drawer.tab_bar_height(), title_bar_height, has_sb);
                     resize_all_tabs(&mut tabs, cols, term_rows);
@@ -1177,18 +1963,147 @@ pub fn run(window: Arc<MacWindow>, rx: mpsc::Receiver<AppEvent>, mut drawer: Gpu
                         0.0
                     };
                 }
+                if new_config.scroll_to_bottom_on_output != config.scroll_to_bottom_on_output {
+                    set_scroll_on_output(&tabs, new_config.scroll_to_bottom_on_output);
+                }
+                if new_config.scrollback_lines != config.scrollback_lines {
+                    set_max_scrollback(&tabs, new_config.max_scrollback());
+                }
+                if new_config.flow_control != config.flow_control {
+                    set_flow_control(&tabs, new_config.flow_control);
+                }
+                bell.audible = new_config.bell_audible;
+                bell.visual = new_config.bell_visual;
+                bell.badge = new_config.bell_badge;
+                bell.attention = new_config.bell_attention;
+                if new_config.scrollback_memory_cap_mb != config.scrollback_memory_cap_mb {
+                    memory_watchdog.set_cap_mb(new_config.scrollback_memory_cap_mb);
+                }
+                frame_scheduler.set_max_fps(new_config.max_fps);
+                drawer.set_present_mode(new_config.present_mode.into());
+                frame_scheduler.set_low_latency(new_config.low_latency);
                 header_opacity = new_config.header_opacity;
+                base_palette = new_config.palette();
+                tabs.set_base_palette(new_config.theme_palette(dark_appearance));
                 copy_mode_action_map = new_config.copy_mode_keys.build_action_map();
+                if new_config.profiles != config.profiles {
+                    window.set_profiles(&profile_names(&new_config));
+                }
                 config = new_config;
             }
+            // New tab: inherit CWD from active tab, or its ssh host
+            AppEvent::NewTab => {
+                let active_cwd = tabs.active_tab().and_then(Tab::cwd);
+                let ssh_command = tabs
+                    .active_tab()
+                    .filter(|_| config.new_tab_reconnects_ssh)
+                    .and_then(|t| t.ssh.target())
+                    .map(|target| target.command());
+                let profile = crate::config::Profile { command: ssh_command, ..Default::default() };
+                open_tab!(profile.pty_options(config.pty_options()).cwd(active_cwd), ColorOverride::default());
+                do_render!();
+            }
+            AppEvent::NewTabAt(dir) => {
+                let profile = crate::config::Profile { cwd: Some(dir), ..Default::default() };
+                open_tab!(profile.pty_options(config.pty_options()), ColorOverride::default());
+                window.focus();
+                do_render!();
+            }
+            AppEvent::NewTabWithProfile(index) => {
+                let Some(profile) = config.profiles.get(index).cloned() else {
+                    continue;
+                };
+                // 폰트 크기는 창 단위라 줌처럼 모든 탭에 적용
+                if let Some(size) = profile.font_size {
+                    if size != font_size {
+                        font_size = size;
+                        drawer.set_font_size(font_size);
+                        let (cw, ch) = drawer.cell_size();
+                        let (w, h) = window.inner_size();
+                        let cols = tabs.term_cols(w, cw);
+                        let has_sb = tabs.active_tab().map_or(false, |t| t.terminal.lock().unwrap().grid.scrollback_len() > 0);
+                        let term_rows = tabs.term_rows(h, ch, drawer.tab_bar_height(), title_bar_height, has_sb);
+                        resize_all_tabs(&mut tabs, cols, term_rows);
+                    }
+                }
+                open_tab!(profile.pty_options(config.pty_options()), profile.color_override());
+                do_render!();
+            }
+            AppEvent::ShowDiagnostics => {
+                let report = diagnostics::collect(&tabs);
+                window.show_alert(
+                    "Diagnostics",
+                    &diagnostics::format_report(&report, drawer.atlas_usage()),
+                    None,
+                );
+            }
+            AppEvent::TrimScrollback => {
+                let report = diagnostics::collect(&tabs);
+                if let Some(keep) = memory_watchdog.trim_target_rows(&report) {
+                    for tab in tabs.tabs_mut() {
+                        tab.terminal.lock().unwrap().grid.truncate_scrollback(keep);
+                    }
+                    do_render!();
+                }
+            }
+            AppEvent::ConfirmPaste => {
+                if let Some(text) = pending_paste.take() {
+                    if let Some(tab) = tabs.active_tab_mut() {
+                        write_paste(tab, &text);
+                    }
+                }
+            }
+            AppEvent::AppearanceChanged(dark) => {
+                if dark != dark_appearance {
+                    dark_appearance = dark;
+                    tabs.set_base_palette(config.theme_palette(dark));
+                    do_render!();
+                }
+            }
+            AppEvent::ConfirmCloseTab => {
+                let index = pending_close_tab
+                    .take()
+                    .and_then(|id| tabs.tabs().iter().position(|t| t.id == id));
+                if let Some(index) = index {
+                    close_tab!(index);
+                    do_render!();
+                }
+            }
+            AppEvent::QuitRequested => {
+                let names: Vec<String> = tabs.tabs().iter().flat_map(|t| t.running_process_names()).collect();
+                if let Some(message) = crate::tab::running_processes_message(&names) {
+                    window.show_alert("Quit growTerm?", &message, Some(("Quit", AppEvent::ConfirmQuit)));
+                } else {
+                    session_saver.finish(&tabs, &config);
+                    window.quit();
+                }
+            }
+            AppEvent::ConfirmQuit => {
+                session_saver.finish(&tabs, &config);
+                window.quit();
+            }
+            AppEvent::SaveSession => {
+                session_saver.answer_save_request(&tabs, &config);
+            }
             AppEvent::CloseRequested => {
-                std::process::exit(0);
+                session_saver.close(&tabs, &config);
+                window.close();
+                return;
             }
         }
     }
 }
 
 
+/// 기본 브라우저/핸들러로 URL 열기
+fn open_url(url: &str) {
+    #[cfg(target_os = "macos")]
+    let opener = "open";
+    #[cfg(not(target_os = "macos"))]
+    let opener = "xdg-open";
+    let _ = std::process::Command::new(opener).arg(url).spawn();
+}
+
 fn spawn_new_window() {
     let Ok(exe) = std::env::current_exe() else { return };
     let exe = exe.canonicalize().unwrap_or(exe);
@@ -1224,7 +2139,7 @@ fn tab_scrollback_lens(tabs: &TabManager) -> Vec<(u64, usize)> {
 /// Extract text from a grid starting from `start_abs` row.
 /// Returns the text of all rows from `start_abs` to the end.
 pub fn extract_grid_text(grid: &growterm_grid::Grid, start_abs: usize) -> String {
-    let current_total = grid.scrollback_len() + grid.cells().len();
+    let current_total = grid.scrollback_len() + grid.screen_rows().len();
     if current_total <= start_abs {
         return String::new();
     }
@@ -1272,18 +2187,50 @@ fn maybe_remember_title_update(last_title: &mut Option<String>, next_title: Stri
     }
 }
 
+/// Where preedit starts on screen: the real cursor (or Ink's drawn
+/// cursor) shifted by the scroll offset. When scrolling has pushed it
+/// below the viewport the preedit floats on the bottom row instead of
+/// disappearing. Ink's cursor is only looked for while the view is at
+/// the bottom, since the snapshot holds just the rows in view.
+fn preedit_anchor(snap: &ScreenSnapshot, ink_state: &InkImeState) -> Option<(u16, u16)> {
+    let rows = snap.rows.len();
+    if rows == 0 {
+        return None;
+    }
+    let ink_pos = if snap.scroll_offset == 0 {
+        ink_state.preedit_pos(&snap.rows)
+    } else {
+        None
+    };
+    let (row, col) = ink_pos.unwrap_or(snap.cursor);
+    let row = (row as usize + snap.scroll_offset).min(rows - 1);
+    Some((row as u16, col))
+}
+
+/// 탭바·스크롤바 위는 화살표, 링크 위는 손가락, 나머지 텍스트 영역은 I-beam
+fn mouse_cursor_for(on_tab_bar: bool, on_scrollbar: bool, over_link: bool) -> MouseCursor {
+    if on_tab_bar || on_scrollbar {
+        MouseCursor::Arrow
+    } else if over_link {
+        MouseCursor::PointingHand
+    } else {
+        MouseCursor::IBeam
+    }
+}
+
 fn ime_cursor_rect_pixels(
     show_tab_bar: bool,
     tab_bar_h: f32,
     title_bar_h: f32,
+    padding: WindowPadding,
     cell_w: f32,
     cell_h: f32,
     cursor: Option<(u16, u16)>,
     preedit_pos_override: Option<(u16, u16)>,
 ) -> Option<(f32, f32, f32, f32)> {
     let (row, col) = preedit_pos_override.or(cursor)?;
-    let y_offset = crate::tab::content_y_offset(show_tab_bar, tab_bar_h, title_bar_h, false);
-    Some((col as f32 * cell_w, y_offset + row as f32 * cell_h, cell_w, cell_h))
+    let y_offset = crate::tab::content_y_offset(show_tab_bar, tab_bar_h, title_bar_h, false) + padding.top;
+    Some((padding.left + col as f32 * cell_w, y_offset + row as f32 * cell_h, cell_w, cell_h))
 }
 
 fn build_title(pomodoro: &Pomodoro, tabs: &TabManager) -> String {
@@ -1321,30 +2268,190 @@ fn shell_escape(path: &str) -> String {
     }
 }
 
+/// Overlay `text` at the right edge of `screen_row`, leaving one blank column
+/// before it. Skipped when the row's content would be covered.
+fn place_right_aligned(
+    commands: &mut Vec<growterm_types::RenderCommand>,
+    line: &[growterm_types::Cell],
+    screen_row: u16,
+    text: &str,
+    palette: growterm_render_cmd::TerminalPalette,
+) {
+    use unicode_width::UnicodeWidthChar;
+    let width: usize = text.chars().map(|c| c.width().unwrap_or(1)).sum();
+    let cols = line.len();
+    if width + 1 > cols {
+        return;
+    }
+    let start = cols - width;
+    if line[start - 1..].iter().any(|c| c.character != ' ') {
+        return;
+    }
+    let fg = growterm_types::Rgb::new(
+        ((palette.default_fg.r as u16 + palette.default_bg.r as u16) / 2) as u8,
+        ((palette.default_fg.g as u16 + palette.default_bg.g as u16) / 2) as u8,
+        ((palette.default_fg.b as u16 + palette.default_bg.b as u16) / 2) as u8,
+    );
+    let mut col = start as u16;
+    for character in text.chars() {
+        let w = character.width().unwrap_or(1) as u16;
+        commands.retain(|cmd| !(cmd.row == screen_row && cmd.col >= col && cmd.col < col + w));
+        commands.push(growterm_types::RenderCommand {
+            col,
+            row: screen_row,
+            character,
+            fg,
+            bg: palette.default_bg,
+            flags: if w > 1 {
+                growterm_types::CellFlags::WIDE_CHAR
+            } else {
+                growterm_types::CellFlags::empty()
+            },
+            zerowidth: None,
+            underline_style: growterm_types::UnderlineStyle::Single,
+            underline_color: fg,
+        });
+        col += w;
+    }
+}
+
+/// "Window too small" message drawn on the first row instead of the grid.
+fn too_small_placeholder(cols: u16, palette: growterm_render_cmd::TerminalPalette) -> Vec<growterm_types::RenderCommand> {
+    zoom::too_small_text(cols)
+        .chars()
+        .enumerate()
+        .map(|(col, character)| growterm_types::RenderCommand {
+            col: col as u16,
+            row: 0,
+            character,
+            fg: palette.default_fg,
+            bg: palette.default_bg,
+            flags: growterm_types::CellFlags::empty(),
+            zerowidth: None,
+            underline_style: growterm_types::UnderlineStyle::Single,
+            underline_color: palette.default_fg,
+        })
+        .collect()
+}
+
+fn write_paste(tab: &mut Tab, text: &str) {
+    let bracketed = tab.bracketed_paste.load(Ordering::Relaxed);
+    let _ = tab.pty_writer.write_all(&paste::to_pty_bytes(text, bracketed));
+    let _ = tab.pty_writer.flush();
+}
+
+/// Resolve a clicked file path against the shell's CWD; None if the file doesn't exist.
+/// Quick Look할 파일: Cmd+hover 중인 파일 링크, 없으면 한 줄짜리 선택
+fn quick_look_target(tab: &Tab, sel: &Selection, hover_url_range: Option<(u32, u16, u16)>) -> Option<std::path::PathBuf> {
+    let state = tab.terminal.lock().unwrap();
+    let link = if let Some((abs_row, start_cell, _)) = hover_url_range {
+        let row_text = selection::row_text_absolute(&state.grid, abs_row);
+        let row_cells = selection::row_cells_absolute(&state.grid, abs_row);
+        let char_col = selection::cell_col_to_char_index(&row_cells, start_cell as usize);
+        url::find_file_link_at(&row_text, char_col)?
+    } else if !sel.is_empty() {
+        selected_file_link(&selection::extract_text_absolute(&state.grid, sel))?
+    } else {
+        return None;
+    };
+    drop(state);
+    resolve_file_link(tab, &link)
+}
+
+/// 직접 선택한 텍스트는 `/`가 없는 파일 이름(`notes.txt`)도 경로로 봄
+fn selected_file_link(text: &str) -> Option<url::FileLink> {
+    let token = text.trim();
+    if token.is_empty() || token.contains('\n') {
+        return None;
+    }
+    url::parse_file_link(token).or_else(|| {
+        Some(url::FileLink { path: token.to_string(), line: None, column: None })
+    })
+}
+
+fn resolve_file_link(tab: &Tab, link: &url::FileLink) -> Option<std::path::PathBuf> {
+    let path = std::path::Path::new(&link.path);
+    let resolved = if let Some(rest) = link.path.strip_prefix("~/") {
+        std::path::PathBuf::from(std::env::var("HOME").ok()?).join(rest)
+    } else if path.is_absolute() {
+        path.to_path_buf()
+    } else {
+        let cwd = tab.cwd()?;
+        cwd.join(path)
+    };
+    resolved.exists().then_some(resolved)
+}
+
+/// Per-frame UI state drawn over the active tab, built once per `do_render!`.
+struct FrameUi<'a> {
+    /// Cmd+hover URL (absolute row, start col, end col)
+    hover_url_range: Option<(u32, u16, u16)>,
+    is_break: bool,
+    break_text: Option<Vec<String>>,
+    show_scrollbar: bool,
+    copy_flash: Option<(u16, u16, Instant)>,
+    tab_dragging: Option<usize>,
+    transparent_tab_bar: bool,
+    title_bar_height: f32,
+    header_opacity: f32,
+    debug_view: bool,
+    bell_flash: bool,
+    duration_annotation: bool,
+    underline_links: bool,
+    bidi: bool,
+    base_palette: growterm_render_cmd::TerminalPalette,
+    smooth_scroll: &'a SmoothScroll,
+    hud: Option<String>,
+    report: Option<Vec<String>>,
+    window_focused: bool,
+}
+
 /// Returns true if the glyph budget was exceeded and another redraw is needed.
-fn render_with_tabs(drawer: &mut GpuDrawer, tabs: &TabManager, preedit: &str, sel: &Selection, ink_state: &InkImeState, hover_url_range: Option<(u32, u16, u16)>, is_break: bool, break_text: Option<&[String]>, show_scrollbar: bool, copy_flash: Option<(u16, u16, Instant)>, tab_dragging: Option<usize>, transparent_tab_bar: bool, title_bar_height: f32, header_opacity: f32) -> bool {
+fn render_with_tabs(drawer: &mut dyn Renderer, tabs: &TabManager, preedit: &str, sel: &Selection, ink_state: &InkImeState, ui: &FrameUi) -> bool {
+    let FrameUi {
+        hover_url_range,
+        is_break,
+        ref break_text,
+        show_scrollbar,
+        copy_flash,
+        tab_dragging,
+        transparent_tab_bar,
+        title_bar_height,
+        header_opacity,
+        debug_view,
+        bell_flash,
+        duration_annotation,
+        underline_links,
+        bidi,
+        base_palette,
+        smooth_scroll,
+        ref hud,
+        ref report,
+        window_focused,
+    } = *ui;
     let tab = match tabs.active_tab() {
         Some(t) => t,
         None => return false,
     };
 
-    let state = tab.terminal.lock().unwrap();
-    let scrolled = state.grid.scroll_offset() > 0;
-    let cursor_pos = state.grid.cursor_pos();
-    let cursor = if scrolled || !state.grid.cursor_visible() {
+    // IO 스레드가 파싱 중이어도 기다리지 않고 마지막으로 올린 화면을 그림
+    let mut frame = tab.frame.lock().unwrap();
+    let snap = frame.latest(&tab.terminal);
+    let scrolled = snap.scroll_offset > 0;
+    let cursor = if scrolled || !snap.cursor_visible {
         None
     } else {
-        Some(cursor_pos)
+        Some(snap.cursor)
     };
-    let preedit_str = if preedit.is_empty() || scrolled {
+    let preedit_str = if preedit.is_empty() {
         None
     } else {
         Some(preedit)
     };
 
-    let scrollback_len = state.grid.scrollback_len();
-    let rows = state.grid.cells().len();
-    let scroll_offset = state.grid.scroll_offset();
+    let scrollback_len = snap.scrollback_len;
+    let rows = snap.rows.len();
+    let scroll_offset = snap.scroll_offset;
     let scrollbar = if show_scrollbar && scrollback_len > 0 {
         let total = (scrollback_len + rows) as f32;
         let thumb_height = rows as f32 / total;
@@ -1353,39 +2460,49 @@ fn render_with_tabs(drawer: &mut GpuDrawer, tabs: &TabManager, preedit: &str, se
     } else {
         None
     };
-    let visible = state.grid.visible_cells();
-    let view_base = (state
-        .grid
-        .scrollback_len()
-        .saturating_sub(state.grid.scroll_offset())) as u32;
+    let scroll_badge = scrollbar.and(scroll_badge_text(scroll_offset, scrollback_len));
+    let visible = &snap.rows;
+    let view_base = scrollback_len.saturating_sub(scroll_offset) as u32;
     let visible_rows = visible.len() as u16;
-    let sel_range = sel.screen_normalized(view_base, visible_rows);
 
     let show_tab_bar = tabs.show_tab_bar();
-    let preedit_pos_override = if preedit_str.is_some() {
-        ink_state.preedit_pos(&visible)
-    } else {
-        None
-    };
-    let mut commands = growterm_render_cmd::generate_with_offset(
-        &visible,
-        cursor,
-        preedit_str,
-        sel_range,
-        0,
-        state.palette,
-        preedit_pos_override,
-        if scrolled { None } else { Some(cursor_pos) },
-    );
-
-    // Post-process: add UNDERLINE flag for hover URL range
+    let preedit_anchor = preedit_str.and_then(|_| preedit_anchor(snap, ink_state));
+    // 포커스가 없으면 블록 대신 외곽선 커서를 렌더러가 그림
+    let block_cursor = if window_focused { cursor } else { None };
+    let mut highlights: Vec<growterm_render_cmd::Highlight> = sel
+        .screen_normalized(view_base, visible_rows)
+        .map(|(start, end)| growterm_render_cmd::Highlight::selection(start, end))
+        .into_iter()
+        .collect();
+    // 화면이 바뀌었으면 링크 스캔 요청. 끝나면 스캐너가 다시 그리게 함
+    let view_key = ViewKey { generation: snap.generation(), view_base };
+    tab.links.request(view_key, visible);
+    if underline_links {
+        if let Some(index) = tab.links.index().as_ref().filter(|index| index.key == view_key) {
+            for (row, span) in index.spans() {
+                highlights.extend(growterm_render_cmd::Highlight::underline(row, span.start, span.end));
+            }
+        }
+    }
+    // Cmd+hover 중인 URL 밑줄 (절대 행 → 화면 행)
     if let Some((abs_row, start_col, end_col)) = hover_url_range {
         if abs_row >= view_base && abs_row < view_base + visible_rows as u32 {
-            let screen_row = (abs_row - view_base) as u16;
-            for cmd in commands.iter_mut() {
-                if cmd.row == screen_row && cmd.col >= start_col && cmd.col < end_col {
-                    cmd.flags |= growterm_types::CellFlags::UNDERLINE;
-                }
+            highlights.extend(growterm_render_cmd::Highlight::underline((abs_row - view_base) as u16, start_col, end_col));
+        }
+    }
+    let mut commands = growterm_render_cmd::RenderPlan::new(visible, tab_palette(snap.palette, base_palette))
+        .cursor(block_cursor)
+        .preedit(preedit_str.filter(|_| preedit_anchor.is_some()))
+        .preedit_anchor(preedit_anchor)
+        .highlights(&highlights)
+        .bidi(bidi)
+        .render();
+
+    // Command duration: right-aligned on the prompt row, only over blank cells
+    if duration_annotation {
+        for (screen_row, text) in tab.command_durations.visible(view_base, visible_rows) {
+            if let Some(line) = visible.get(screen_row as usize) {
+                place_right_aligned(&mut commands, line, screen_row, text, snap.palette);
             }
         }
     }
@@ -1401,7 +2518,54 @@ fn render_with_tabs(drawer: &mut GpuDrawer, tabs: &TabManager, preedit: &str, se
         }
     }
 
-    drop(state);
+    // 픽셀 단위 스크롤 중이면 뷰 바로 위 행이 일부 보임: 0번 행으로 끼워 넣음
+    let fraction = smooth_scroll.fraction(scroll_offset);
+    let row_above = if fraction > 0.0 {
+        snap.row_above.as_ref()
+    } else {
+        None
+    };
+    let partial_row = row_above.is_some();
+    if let Some(row) = row_above {
+        for cmd in commands.iter_mut() {
+            cmd.row += 1;
+        }
+        let above = growterm_render_cmd::RenderPlan::new(std::slice::from_ref(row), tab_palette(snap.palette, base_palette))
+            .bidi(bidi)
+            .render();
+        commands.splice(0..0, above);
+    }
+
+    if debug_view {
+        growterm_render_cmd::mark_trailing_whitespace(&mut commands);
+    }
+
+    // Visual bell: briefly invert the whole screen
+    if bell_flash {
+        for cmd in commands.iter_mut() {
+            std::mem::swap(&mut cmd.fg, &mut cmd.bg);
+        }
+    }
+
+    let palette = snap.palette;
+    let mut hollow_cursor = if window_focused { None } else { cursor }.map(|(row, col)| {
+        let line = visible.get(row as usize);
+        let wide = line
+            .and_then(|line| line.get(col as usize))
+            .is_some_and(|cell| cell.flags.contains(growterm_types::CellFlags::WIDE_CHAR));
+        let col = match line.filter(|_| bidi).and_then(|line| growterm_render_cmd::bidi::layout(line)) {
+            Some(visual) => visual.visual_col(col),
+            None => col,
+        };
+        let cursor_palette = tab_palette(palette, base_palette);
+        growterm_gpu_draw::HollowCursor {
+            row: row + partial_row as u16,
+            col,
+            width: if wide { 2 } else { 1 },
+            color: cursor_palette.cursor.bg.unwrap_or(cursor_palette.default_fg),
+        }
+    });
+    drop(frame);
 
     let tab_bar = if show_tab_bar {
         let info = tabs.tab_bar_info();
@@ -1409,14 +2573,81 @@ fn render_with_tabs(drawer: &mut GpuDrawer, tabs: &TabManager, preedit: &str, se
             titles: info.titles,
             active_index: info.active_index,
             dragging_index: tab_dragging,
+            scroll_offset: info.scroll_offset,
         })
     } else {
         None
     };
 
     let has_scrollback = scrollback_len > 0;
-    let y_offset = crate::tab::content_y_offset(show_tab_bar, drawer.tab_bar_height(), title_bar_height, has_scrollback);
-    drawer.draw(&commands, scrollbar, tab_bar.as_ref(), is_break, break_text, transparent_tab_bar, y_offset, title_bar_height, header_opacity)
+    let padding = tabs.window_padding();
+    let y_offset = tabs.mouse_y_offset(drawer.tab_bar_height(), title_bar_height, has_scrollback);
+    let (screen_w, screen_h) = drawer.surface_size();
+    let (cw, ch) = drawer.cell_size();
+    let mut row_shift = if partial_row { fraction - ch } else { 0.0 };
+    let content_w = screen_w as f32 - padding.left - padding.right;
+    if zoom::is_too_small(content_w, screen_h as f32 - y_offset - padding.bottom, cw, ch) {
+        commands = too_small_placeholder((content_w / cw).floor() as u16, palette);
+        row_shift = 0.0;
+        hollow_cursor = None;
+    }
+    let overlays = Overlays {
+        scrollbar,
+        tab_bar: tab_bar.as_ref(),
+        is_break,
+        break_text: break_text.as_deref(),
+        transparent_tab_bar,
+        content_x_offset: padding.left,
+        content_y_offset: y_offset + row_shift,
+        content_clip_top: y_offset,
+        title_bar_height,
+        header_opacity,
+        hud: hud.as_deref(),
+        scroll_badge: scroll_badge.as_deref(),
+        report: report.as_deref(),
+        hollow_cursor,
+    };
+    drawer.draw(&commands, &overlays)
+}
+
+/// "lines back/total" next to the scrollbar while the view is scrolled up.
+fn scroll_badge_text(scroll_offset: usize, scrollback_len: usize) -> Option<String> {
+    (scroll_offset > 0).then(|| format!("{scroll_offset}/{scrollback_len}"))
+}
+
+/// Scroll the view by whole rows (positive = toward scrollback).
+fn scroll_view_rows(grid: &mut growterm_grid::Grid, rows: i32) {
+    if rows > 0 {
+        grid.scroll_up_view(rows as usize);
+    } else if rows < 0 {
+        grid.scroll_down_view(rows.unsigned_abs() as usize);
+    }
+}
+
+/// 윈도우 위치·크기가 바뀌었으면 설정에 저장. 전체 화면 크기는 저장하지 않음
+fn save_window_frame(window: &dyn TerminalWindow, config: &mut crate::config::Config) {
+    if let Some(frame) = window.window_frame() {
+        if config.set_window_frame(frame) {
+            config.save();
+        }
+    }
+}
+
+/// `growterm cli list-tabs` 한 줄: 윈도우, 탭 id, 활성(*), 작업 디렉터리
+fn list_tabs(tabs: &TabManager, window_id: u64) -> String {
+    let mut out = String::new();
+    for (index, tab) in tabs.tabs().iter().enumerate() {
+        let active = if index == tabs.active_index() { "*" } else { " " };
+        let cwd = tab
+            .cwd()
+            .map_or_else(|| "-".to_string(), |p| p.display().to_string());
+        out.push_str(&format!("{window_id}\t{}\t{active}\t{cwd}\n", tab.id));
+    }
+    out
+}
+
+fn profile_names(config: &crate::config::Config) -> Vec<String> {
+    config.profiles.iter().map(|p| p.name.clone()).collect()
 }
 
 #[cfg(test)]
@@ -1424,6 +2655,12 @@ mod tests {
     use super::*;
     use growterm_types::TerminalCommand;
 
+    #[test]
+    fn scroll_badge_only_when_scrolled() {
+        assert_eq!(scroll_badge_text(0, 10000), None);
+        assert_eq!(scroll_badge_text(1024, 10000).as_deref(), Some("1024/10000"));
+    }
+
     #[test]
     fn shell_escape_plain_path() {
         assert_eq!(shell_escape("/Users/me/file.txt"), "/Users/me/file.txt");
@@ -1483,7 +2720,7 @@ mod tests {
     #[test]
     fn extract_grid_text_empty_when_no_new_output() {
         let grid = make_grid_with_lines(80, 24, &["hello"]);
-        let total = grid.scrollback_len() + grid.cells().len();
+        let total = grid.scrollback_len() + grid.screen_rows().len();
         // start_abs == current_total → no new output
         let text = extract_grid_text(&grid, total);
         assert!(text.trim().is_empty());
@@ -1544,7 +2781,7 @@ mod tests {
     #[test]
     fn simulate_pomodoro_no_new_output() {
         let grid = make_grid_with_lines(80, 24, &["$ hello"]);
-        let total = grid.scrollback_len() + grid.cells().len();
+        let total = grid.scrollback_len() + grid.screen_rows().len();
         // No new output after snapshot
         let text = extract_grid_text(&grid, total);
         assert!(text.trim().is_empty());
@@ -1644,16 +2881,56 @@ mod tests {
         assert_eq!(cache.as_deref(), Some("25:00"));
     }
 
+    #[test]
+    fn preedit_anchor_follows_cursor_into_scrolled_view() {
+        let mut grid = growterm_grid::Grid::new(20, 5);
+        for _ in 0..10 {
+            grid.apply(&TerminalCommand::Newline);
+        }
+        grid.apply(&TerminalCommand::CursorPosition { row: 2, col: 4 });
+        let ink = InkImeState::new();
+        let mut snap = ScreenSnapshot::default();
+        snap.update_from(&grid, growterm_render_cmd::TerminalPalette::default());
+        assert_eq!(preedit_anchor(&snap, &ink), Some((1, 3)));
+
+        grid.set_scroll_offset(2);
+        snap.update_from(&grid, growterm_render_cmd::TerminalPalette::default());
+        assert_eq!(preedit_anchor(&snap, &ink), Some((3, 3)));
+
+        // 커서가 화면 아래로 밀려나면 마지막 줄에 붙음
+        grid.set_scroll_offset(5);
+        snap.update_from(&grid, growterm_render_cmd::TerminalPalette::default());
+        assert_eq!(preedit_anchor(&snap, &ink), Some((4, 3)));
+    }
+
+    #[test]
+    fn selected_file_link_accepts_single_line_paths() {
+        let link = selected_file_link("  src/main.rs:12 \n").unwrap();
+        assert_eq!(link.path, "src/main.rs");
+        assert_eq!(link.line, Some(12));
+        assert_eq!(selected_file_link("notes.txt").unwrap().path, "notes.txt");
+        assert!(selected_file_link("a\nb").is_none());
+        assert!(selected_file_link("   ").is_none());
+    }
+
+    #[test]
+    fn mouse_cursor_shape_by_area() {
+        assert_eq!(mouse_cursor_for(false, false, false), MouseCursor::IBeam);
+        assert_eq!(mouse_cursor_for(false, false, true), MouseCursor::PointingHand);
+        assert_eq!(mouse_cursor_for(true, false, false), MouseCursor::Arrow);
+        assert_eq!(mouse_cursor_for(false, true, true), MouseCursor::Arrow);
+    }
+
     #[test]
     fn ime_cursor_rect_pixels_returns_none_without_cursor() {
-        let rect = ime_cursor_rect_pixels(false, 24.0, 18.0, 10.0, 20.0, None, None);
+        let rect = ime_cursor_rect_pixels(false, 24.0, 18.0, WindowPadding::default(), 10.0, 20.0, None, None);
 
         assert!(rect.is_none());
     }
 
     #[test]
     fn ime_cursor_rect_pixels_uses_cursor_position() {
-        let rect = ime_cursor_rect_pixels(false, 24.0, 18.0, 10.0, 20.0, Some((2, 3)), None);
+        let rect = ime_cursor_rect_pixels(false, 24.0, 18.0, WindowPadding::default(), 10.0, 20.0, Some((2, 3)), None);
 
         assert_eq!(rect, Some((30.0, 58.0, 10.0, 20.0)));
     }
@@ -1664,6 +2941,7 @@ mod tests {
             false,
             24.0,
             18.0,
+            WindowPadding::default(),
             10.0,
             20.0,
             Some((2, 3)),
@@ -1675,8 +2953,24 @@ mod tests {
 
     #[test]
     fn ime_cursor_rect_pixels_includes_tab_bar_offset() {
-        let rect = ime_cursor_rect_pixels(true, 24.0, 18.0, 10.0, 20.0, Some((1, 2)), None);
+        let rect = ime_cursor_rect_pixels(true, 24.0, 18.0, WindowPadding::default(), 10.0, 20.0, Some((1, 2)), None);
 
         assert_eq!(rect, Some((20.0, 62.0, 10.0, 20.0)));
     }
+
+    #[test]
+    fn ime_cursor_rect_pixels_includes_window_padding() {
+        let padding = WindowPadding { top: 4.0, right: 0.0, bottom: 0.0, left: 6.0 };
+        let rect = ime_cursor_rect_pixels(false, 24.0, 18.0, padding, 10.0, 20.0, Some((1, 2)), None);
+
+        assert_eq!(rect, Some((26.0, 42.0, 10.0, 20.0)));
+    }
+
+    #[test]
+    fn export_format_follows_extension() {
+        assert!(is_html_path(std::path::Path::new("/tmp/out.html")));
+        assert!(is_html_path(std::path::Path::new("/tmp/out.HTM")));
+        assert!(!is_html_path(std::path::Path::new("/tmp/out.txt")));
+        assert!(!is_html_path(std::path::Path::new("/tmp/html")));
+    }
 }
@@ -0,0 +1,139 @@
+use std::io::Read;
+use std::path::PathBuf;
+use std::process::{Command, Stdio};
+use std::sync::mpsc;
+use std::time::{Duration, Instant};
+
+/// A banner command still running after this is killed and shows nothing.
+const COMMAND_TIMEOUT: Duration = Duration::from_secs(1);
+const COMMAND_POLL: Duration = Duration::from_millis(10);
+
+/// Startup banner bytes: the banner file wins over the banner command.
+/// The result is printed into the first tab's grid only, never sent to the PTY.
+pub fn load(banner_file: Option<&str>, banner_command: Option<&str>) -> Option<Vec<u8>> {
+    let raw = if let Some(path) = banner_file {
+        std::fs::read(expand_home(path)).ok()?
+    } else if let Some(cmd) = banner_command {
+        run_command(cmd)?
+    } else {
+        return None;
+    };
+    if raw.is_empty() {
+        return None;
+    }
+    Some(to_terminal_newlines(&raw))
+}
+
+/// stdout of `/bin/sh -c cmd`, or None if it fails to start or outlives
+/// `COMMAND_TIMEOUT` (the first tab waits on it).
+fn run_command(cmd: &str) -> Option<Vec<u8>> {
+    let mut child = Command::new("/bin/sh")
+        .arg("-c")
+        .arg(cmd)
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::null())
+        .spawn()
+        .ok()?;
+    // 파이프가 가득 차 자식이 멈추지 않도록 따로 읽음
+    let mut stdout = child.stdout.take()?;
+    let (tx, rx) = mpsc::channel();
+    std::thread::spawn(move || {
+        let mut buf = Vec::new();
+        let _ = stdout.read_to_end(&mut buf);
+        let _ = tx.send(buf);
+    });
+    let deadline = Instant::now() + COMMAND_TIMEOUT;
+    loop {
+        match child.try_wait() {
+            Ok(Some(_)) => break,
+            Ok(None) if Instant::now() < deadline => std::thread::sleep(COMMAND_POLL),
+            _ => {
+                let _ = child.kill();
+                let _ = child.wait();
+                return None;
+            }
+        }
+    }
+    // 백그라운드로 띄운 손자 프로세스가 stdout을 쥐고 있으면 기다리지 않음
+    rx.recv_timeout(deadline.saturating_duration_since(Instant::now()) + COMMAND_POLL).ok()
+}
+
+fn expand_home(path: &str) -> PathBuf {
+    match path.strip_prefix("~/") {
+        Some(rest) => {
+            let home = std::env::var("HOME").unwrap_or_else(|_| ".".to_string());
+            PathBuf::from(home).join(rest)
+        }
+        None => PathBuf::from(path),
+    }
+}
+
+/// Bare `\n` → `\r\n`, since there is no tty line discipline (ONLCR) in between.
+/// Ensures the banner ends on a fresh line so the prompt starts at column 0.
+pub fn to_terminal_newlines(raw: &[u8]) -> Vec<u8> {
+    let mut out = Vec::with_capacity(raw.len() + raw.len() / 16);
+    let mut prev = 0u8;
+    for &b in raw {
+        if b == b'\n' && prev != b'\r' {
+            out.push(b'\r');
+        }
+        out.push(b);
+        prev = b;
+    }
+    if !out.ends_with(b"\n") {
+        out.extend_from_slice(b"\r\n");
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn converts_bare_newlines() {
+        assert_eq!(to_terminal_newlines(b"a\nb\n"), b"a\r\nb\r\n");
+    }
+
+    #[test]
+    fn keeps_existing_crlf() {
+        assert_eq!(to_terminal_newlines(b"a\r\nb\r\n"), b"a\r\nb\r\n");
+    }
+
+    #[test]
+    fn appends_trailing_newline() {
+        assert_eq!(to_terminal_newlines(b"hello"), b"hello\r\n");
+    }
+
+    #[test]
+    fn no_banner_configured() {
+        assert_eq!(load(None, None), None);
+    }
+
+    #[test]
+    fn missing_banner_file() {
+        assert_eq!(load(Some("/nonexistent/growterm-banner.txt"), None), None);
+    }
+
+    #[test]
+    fn banner_file_wins_over_command() {
+        let path = std::env::temp_dir().join(format!("growterm-banner-{}.txt", std::process::id()));
+        std::fs::write(&path, "from file\n").unwrap();
+        let banner = load(Some(path.to_str().unwrap()), Some("echo from command"));
+        let _ = std::fs::remove_file(&path);
+        assert_eq!(banner, Some(b"from file\r\n".to_vec()));
+    }
+
+    #[test]
+    fn banner_command_output() {
+        assert_eq!(load(None, Some("printf 'hi\\n'")), Some(b"hi\r\n".to_vec()));
+    }
+
+    #[test]
+    fn slow_banner_command_is_killed() {
+        let start = Instant::now();
+        assert_eq!(load(None, Some("sleep 10; echo late")), None);
+        assert!(start.elapsed() < Duration::from_secs(5));
+    }
+}
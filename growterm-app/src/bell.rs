@@ -0,0 +1,139 @@
+use std::time::{Duration, Instant};
+
+const VISUAL_BELL_DURATION: Duration = Duration::from_millis(100);
+
+/// What to do for one BEL, decided from config and whether the tab is active.
+#[derive(Debug, Default, PartialEq)]
+pub struct BellActions {
+    pub beep: bool,
+    pub flash: bool,
+    /// Mark the tab and badge the dock icon (bell in a background tab,
+    /// or in any tab while the window is unfocused)
+    pub badge: bool,
+    /// Bounce the Dock icon (bell while the window is unfocused)
+    pub attention: bool,
+}
+
+pub struct Bell {
+    pub audible: bool,
+    pub visual: bool,
+    pub badge: bool,
+    pub attention: bool,
+    flash_until: Option<Instant>,
+}
+
+impl Bell {
+    pub fn new(audible: bool, visual: bool, badge: bool, attention: bool) -> Self {
+        Self {
+            audible,
+            visual,
+            badge,
+            attention,
+            flash_until: None,
+        }
+    }
+
+    /// A bell counts as seen only in the active tab of the focused window.
+    pub fn on_bell(&mut self, active_tab: bool, window_focused: bool, now: Instant) -> BellActions {
+        let seen = active_tab && window_focused;
+        let actions = BellActions {
+            beep: self.audible,
+            flash: self.visual && active_tab,
+            badge: self.badge && !seen,
+            attention: self.attention && !window_focused,
+        };
+        if actions.flash {
+            self.flash_until = Some(now + VISUAL_BELL_DURATION);
+        }
+        actions
+    }
+
+    /// While the visual bell should invert the screen, the instant it ends.
+    /// Call once per frame and redraw at that instant, or the last inverted
+    /// frame stays up until something else redraws.
+    pub fn flash_deadline(&mut self, now: Instant) -> Option<Instant> {
+        match self.flash_until {
+            Some(t) if now < t => Some(t),
+            _ => {
+                self.flash_until = None;
+                None
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn all_disabled_does_nothing() {
+        let mut bell = Bell::new(false, false, false, false);
+        let now = Instant::now();
+        assert_eq!(bell.on_bell(true, true, now), BellActions::default());
+        assert_eq!(bell.flash_deadline(now), None);
+    }
+
+    #[test]
+    fn audible_beeps_for_any_tab() {
+        let mut bell = Bell::new(true, false, false, false);
+        let now = Instant::now();
+        assert!(bell.on_bell(true, true, now).beep);
+        assert!(bell.on_bell(false, true, now).beep);
+    }
+
+    #[test]
+    fn visual_flashes_only_active_tab() {
+        let mut bell = Bell::new(false, true, false, false);
+        let now = Instant::now();
+        assert!(!bell.on_bell(false, true, now).flash);
+        assert_eq!(bell.flash_deadline(now), None);
+        assert!(bell.on_bell(true, true, now).flash);
+        assert!(bell.flash_deadline(now).is_some());
+    }
+
+    #[test]
+    fn flash_expires() {
+        let mut bell = Bell::new(false, true, false, false);
+        let now = Instant::now();
+        bell.on_bell(true, true, now);
+        assert_eq!(bell.flash_deadline(now + VISUAL_BELL_DURATION), None);
+    }
+
+    #[test]
+    fn flash_asks_for_the_frame_that_ends_it() {
+        let mut bell = Bell::new(false, true, false, false);
+        let now = Instant::now();
+        bell.on_bell(true, true, now);
+        let end = bell.flash_deadline(now).expect("flashing");
+        assert_eq!(end, now + VISUAL_BELL_DURATION);
+        // a frame just before the end still inverts and asks for the same redraw
+        assert_eq!(bell.flash_deadline(end - Duration::from_millis(1)), Some(end));
+        // the frame drawn at `end` is the normal one
+        assert_eq!(bell.flash_deadline(end), None);
+    }
+
+    #[test]
+    fn badge_only_for_background_tab() {
+        let mut bell = Bell::new(false, false, true, false);
+        let now = Instant::now();
+        assert!(!bell.on_bell(true, true, now).badge);
+        assert!(bell.on_bell(false, true, now).badge);
+    }
+
+    #[test]
+    fn badge_for_active_tab_of_unfocused_window() {
+        let mut bell = Bell::new(false, false, true, false);
+        let now = Instant::now();
+        assert!(bell.on_bell(true, false, now).badge);
+    }
+
+    #[test]
+    fn attention_only_while_unfocused() {
+        let mut bell = Bell::new(false, false, false, true);
+        let now = Instant::now();
+        assert!(!bell.on_bell(false, true, now).attention);
+        assert!(bell.on_bell(true, false, now).attention);
+        assert!(bell.on_bell(false, false, now).attention);
+    }
+}
@@ -0,0 +1,258 @@
+use std::path::PathBuf;
+
+use crate::config::Profile;
+use crate::ipc::Request;
+
+pub const USAGE: &str = "\
+Usage: growterm [OPTIONS]
+       growterm cli <COMMAND>
+
+Options:
+  --working-directory <DIR>  Start the first tab in DIR
+  --command <CMD>            Run CMD in a login shell instead of an interactive shell
+  --profile <NAME>           Start the first tab with a [[profiles]] entry from the config
+  --font-size <PT>           Font size for this launch (not saved to the config)
+  --config <FILE>            Read and save settings in FILE instead of ~/.config/growterm/config.toml
+  -h, --help                 Print this help
+
+Commands for a running growTerm (the one owning this shell, else the newest):
+  cli list-tabs                                   Print window, tab id, active (*) and directory
+  cli new-tab [--window N] [--cwd DIR] [--command CMD]
+  cli send-text [--window N] [--tab ID] <TEXT>    Type TEXT into a tab (default: active tab)
+  cli activate [--window N] [--tab ID]            Bring a window and tab to the front
+";
+
+/// Flags for `growterm` and `open -a growTerm --args ...`. They only
+/// affect the first window; Cmd+N windows start from the config.
+#[derive(Debug, Default, PartialEq)]
+pub struct CliArgs {
+    pub working_directory: Option<String>,
+    pub command: Option<String>,
+    pub profile: Option<String>,
+    pub font_size: Option<f32>,
+    pub config: Option<PathBuf>,
+    pub help: bool,
+    /// `growterm cli ...`: send this to a running instance instead of starting one
+    pub remote: Option<Request>,
+}
+
+impl CliArgs {
+    /// Parse arguments without the program name. Accepts `--flag value` and `--flag=value`.
+    pub fn parse(args: impl IntoIterator<Item = String>) -> Result<Self, String> {
+        let mut parsed = CliArgs::default();
+        let mut args = args.into_iter().peekable();
+        if args.peek().is_some_and(|a| a == "cli") {
+            args.next();
+            parsed.remote = Some(parse_remote(args)?);
+            return Ok(parsed);
+        }
+        while let Some(arg) = args.next() {
+            // Finder로 실행할 때 예전 macOS가 붙이는 프로세스 일련번호
+            if arg.starts_with("-psn_") {
+                continue;
+            }
+            if arg == "-h" || arg == "--help" {
+                parsed.help = true;
+                continue;
+            }
+            let (flag, inline) = match arg.split_once('=') {
+                Some((flag, value)) if flag.starts_with("--") => (flag.to_string(), Some(value.to_string())),
+                _ => (arg, None),
+            };
+            let mut value = || {
+                inline
+                    .clone()
+                    .or_else(|| args.next())
+                    .ok_or_else(|| format!("{flag} needs a value"))
+            };
+            match flag.as_str() {
+                "--working-directory" => parsed.working_directory = Some(value()?),
+                "--command" => parsed.command = Some(value()?),
+                "--profile" => parsed.profile = Some(value()?),
+                "--font-size" => {
+                    let v = value()?;
+                    let size = v
+                        .parse::<f32>()
+                        .ok()
+                        .filter(|s| s.is_finite() && *s > 0.0)
+                        .ok_or_else(|| format!("invalid --font-size: {v}"))?;
+                    parsed.font_size = Some(size);
+                }
+                "--config" => parsed.config = Some(PathBuf::from(value()?)),
+                _ => return Err(format!("unknown option: {flag}")),
+            }
+        }
+        Ok(parsed)
+    }
+
+    /// The first tab's settings: the `--profile` entry with the other flags
+    /// on top. None when no flag changes the first tab.
+    pub fn launch_profile(&self, profiles: &[Profile]) -> Result<Option<Profile>, String> {
+        let mut profile = match &self.profile {
+            Some(name) => profiles
+                .iter()
+                .find(|p| &p.name == name)
+                .cloned()
+                .ok_or_else(|| format!("no profile named {name:?} in the config"))?,
+            None if self.command.is_none() && self.working_directory.is_none() && self.font_size.is_none() => {
+                return Ok(None);
+            }
+            None => Profile::default(),
+        };
+        if self.command.is_some() {
+            profile.command = self.command.clone();
+        }
+        if self.working_directory.is_some() {
+            profile.cwd = self.working_directory.clone();
+        }
+        if self.font_size.is_some() {
+            profile.font_size = self.font_size;
+        }
+        Ok(Some(profile))
+    }
+}
+
+fn parse_remote(mut args: impl Iterator<Item = String>) -> Result<Request, String> {
+    let command = args.next().ok_or("cli needs a command: list-tabs, new-tab, send-text, activate")?;
+    let mut window = None;
+    let mut tab = None;
+    let mut cwd = None;
+    let mut shell_command = None;
+    let mut text = None;
+    while let Some(arg) = args.next() {
+        let (flag, inline) = match arg.split_once('=') {
+            Some((flag, value)) if flag.starts_with("--") => (flag.to_string(), Some(value.to_string())),
+            _ => (arg, None),
+        };
+        if !flag.starts_with("--") {
+            if text.replace(flag).is_some() {
+                return Err("too many arguments".to_string());
+            }
+            continue;
+        }
+        let value = inline
+            .or_else(|| args.next())
+            .ok_or_else(|| format!("{flag} needs a value"))?;
+        let id = || value.parse::<u64>().map_err(|_| format!("invalid {flag}: {value}"));
+        match flag.as_str() {
+            "--window" => window = Some(id()?),
+            "--tab" if command != "new-tab" => tab = Some(id()?),
+            "--cwd" if command == "new-tab" => cwd = Some(value),
+            "--command" if command == "new-tab" => shell_command = Some(value),
+            _ => return Err(format!("unknown option for {command}: {flag}")),
+        }
+    }
+    let no_text = |request: Request| match text {
+        Some(_) => Err("too many arguments".to_string()),
+        None => Ok(request),
+    };
+    match command.as_str() {
+        "list-tabs" if window.is_none() && tab.is_none() => no_text(Request::ListTabs),
+        "new-tab" => no_text(Request::NewTab { window, cwd, command: shell_command }),
+        "send-text" => Ok(Request::SendText {
+            window,
+            tab,
+            text: text.ok_or("send-text needs TEXT")?,
+        }),
+        "activate" => no_text(Request::Activate { window, tab }),
+        "list-tabs" => Err("list-tabs takes no options".to_string()),
+        other => Err(format!("unknown cli command: {other}")),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(args: &[&str]) -> Result<CliArgs, String> {
+        CliArgs::parse(args.iter().map(|s| s.to_string()))
+    }
+
+    #[test]
+    fn no_args_is_default() {
+        assert_eq!(parse(&[]), Ok(CliArgs::default()));
+    }
+
+    #[test]
+    fn separate_and_inline_values() {
+        let args = parse(&["--working-directory", "~/src", "--command=htop", "--font-size", "18"]).unwrap();
+        assert_eq!(args.working_directory.as_deref(), Some("~/src"));
+        assert_eq!(args.command.as_deref(), Some("htop"));
+        assert_eq!(args.font_size, Some(18.0));
+    }
+
+    #[test]
+    fn inline_value_may_contain_equals() {
+        let args = parse(&["--command=env FOO=1 make"]).unwrap();
+        assert_eq!(args.command.as_deref(), Some("env FOO=1 make"));
+    }
+
+    #[test]
+    fn config_path_and_help() {
+        let args = parse(&["--config", "/tmp/g.toml", "-h"]).unwrap();
+        assert_eq!(args.config, Some(PathBuf::from("/tmp/g.toml")));
+        assert!(args.help);
+    }
+
+    #[test]
+    fn skips_process_serial_number() {
+        assert_eq!(parse(&["-psn_0_12345"]), Ok(CliArgs::default()));
+    }
+
+    #[test]
+    fn errors() {
+        assert_eq!(parse(&["--bogus"]), Err("unknown option: --bogus".to_string()));
+        assert_eq!(parse(&["--command"]), Err("--command needs a value".to_string()));
+        assert!(parse(&["--font-size", "big"]).is_err());
+        assert!(parse(&["--font-size=0"]).is_err());
+    }
+
+    #[test]
+    fn cli_subcommands() {
+        assert_eq!(parse(&["cli", "list-tabs"]).unwrap().remote, Some(Request::ListTabs));
+        assert_eq!(
+            parse(&["cli", "new-tab", "--cwd", "/tmp", "--command=htop"]).unwrap().remote,
+            Some(Request::NewTab { window: None, cwd: Some("/tmp".into()), command: Some("htop".into()) })
+        );
+        assert_eq!(
+            parse(&["cli", "send-text", "--tab", "3", "make\n"]).unwrap().remote,
+            Some(Request::SendText { window: None, tab: Some(3), text: "make\n".into() })
+        );
+        assert_eq!(
+            parse(&["cli", "activate", "--window=2"]).unwrap().remote,
+            Some(Request::Activate { window: Some(2), tab: None })
+        );
+    }
+
+    #[test]
+    fn cli_errors() {
+        assert!(parse(&["cli"]).is_err());
+        assert!(parse(&["cli", "reboot"]).is_err());
+        assert!(parse(&["cli", "send-text"]).is_err());
+        assert!(parse(&["cli", "send-text", "a", "b"]).is_err());
+        assert!(parse(&["cli", "activate", "--tab", "x"]).is_err());
+        assert!(parse(&["cli", "new-tab", "--tab", "1"]).is_err());
+    }
+
+    #[test]
+    fn launch_profile_merges_flags_over_profile() {
+        let profiles = vec![Profile {
+            name: "logs".into(),
+            command: Some("tail -f log".into()),
+            cwd: Some("/var/log".into()),
+            font_size: Some(12.0),
+            ..Profile::default()
+        }];
+        let args = parse(&["--profile", "logs", "--working-directory", "/tmp"]).unwrap();
+        let profile = args.launch_profile(&profiles).unwrap().unwrap();
+        assert_eq!(profile.command.as_deref(), Some("tail -f log"));
+        assert_eq!(profile.cwd.as_deref(), Some("/tmp"));
+        assert_eq!(profile.font_size, Some(12.0));
+
+        assert_eq!(CliArgs::default().launch_profile(&profiles), Ok(None));
+        assert!(parse(&["--profile", "nope"]).unwrap().launch_profile(&profiles).is_err());
+        let only_command = parse(&["--command", "vim"]).unwrap().launch_profile(&profiles).unwrap().unwrap();
+        assert_eq!(only_command.command.as_deref(), Some("vim"));
+        assert_eq!(only_command.cwd, None);
+    }
+}
@@ -0,0 +1,121 @@
+//! Option+click: 클릭한 칸으로 쉘 커서를 옮기는 화살표 키 계산 (iTerm2 방식).
+//! 프롬프트 끝은 알 수 없으므로 커서가 있는 논리 줄(자동 줄바꿈으로 이어진
+//! 행들)을 입력 줄로 보고, 그 밖을 클릭하면 아무것도 하지 않는다.
+
+use growterm_types::{Cell, CellFlags, Key, KeyEvent, Modifiers};
+
+fn wraps(row: &[Cell]) -> bool {
+    row.last().is_some_and(|c| c.flags.contains(CellFlags::WRAPLINE))
+}
+
+fn is_blank(cell: &Cell) -> bool {
+    cell.character == ' ' || cell.character == '\0'
+}
+
+/// Characters the cursor has to move from `cursor` to reach `target`, both
+/// (row, col) on screen; negative is leftward. None when `target` is off
+/// the cursor's line. Clicks past the end of the typed text stop at its end,
+/// and a wide character counts once.
+pub fn arrow_moves<R: AsRef<[Cell]>>(rows: &[R], cursor: (u16, u16), target: (u16, u16)) -> Option<i32> {
+    let cursor_row = cursor.0 as usize;
+    if cursor_row >= rows.len() {
+        return None;
+    }
+    let mut first = cursor_row;
+    while first > 0 && wraps(rows[first - 1].as_ref()) {
+        first -= 1;
+    }
+    let mut last = cursor_row;
+    while last + 1 < rows.len() && wraps(rows[last].as_ref()) {
+        last += 1;
+    }
+    if (target.0 as usize) < first || target.0 as usize > last {
+        return None;
+    }
+
+    // 논리 줄의 글자 시작 칸들 (넓은 글자의 두 번째 칸은 건너뜀)
+    let mut starts = Vec::new();
+    let mut text_end = 0;
+    for (r, row) in rows.iter().enumerate().take(last + 1).skip(first) {
+        let row = row.as_ref();
+        let mut col = 0;
+        while col < row.len() {
+            starts.push((r as u16, col as u16));
+            if !is_blank(&row[col]) {
+                text_end = starts.len();
+            }
+            col += if row[col].flags.contains(CellFlags::WIDE_CHAR) { 2 } else { 1 };
+        }
+    }
+    let index = |pos: (u16, u16)| starts.iter().take_while(|&&start| start < pos).count();
+    let from = index(cursor);
+    let to = index(target).min(text_end.max(from));
+    Some(to as i32 - from as i32)
+}
+
+/// Bytes for `moves` left (negative) or right arrow presses.
+pub fn arrow_bytes(moves: i32) -> Vec<u8> {
+    let key = if moves < 0 { Key::ArrowLeft } else { Key::ArrowRight };
+    let press = growterm_input::encode(KeyEvent { key, modifiers: Modifiers::empty() });
+    press.repeat(moves.unsigned_abs() as usize)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn row(text: &str, cols: usize) -> Vec<Cell> {
+        let mut cells: Vec<Cell> = Vec::new();
+        for c in text.chars() {
+            if unicode_width::UnicodeWidthChar::width(c) == Some(2) {
+                cells.push(Cell { character: c, flags: CellFlags::WIDE_CHAR, ..Cell::default() });
+                cells.push(Cell { character: '\0', ..Cell::default() });
+            } else {
+                cells.push(Cell { character: c, ..Cell::default() });
+            }
+        }
+        cells.resize(cols, Cell::default());
+        cells
+    }
+
+    fn wrapped(text: &str, cols: usize) -> Vec<Cell> {
+        let mut cells = row(text, cols);
+        cells[cols - 1].flags.insert(CellFlags::WRAPLINE);
+        cells
+    }
+
+    #[test]
+    fn moves_left_and_right_on_the_prompt_line() {
+        let rows = vec![row("$ echo hi", 20)];
+        assert_eq!(arrow_moves(&rows, (0, 9), (0, 2)), Some(-7));
+        assert_eq!(arrow_moves(&rows, (0, 2), (0, 7)), Some(5));
+    }
+
+    #[test]
+    fn click_past_the_text_stops_at_its_end() {
+        let rows = vec![row("$ ls", 20)];
+        assert_eq!(arrow_moves(&rows, (0, 2), (0, 15)), Some(2));
+    }
+
+    #[test]
+    fn wide_characters_take_one_press() {
+        let rows = vec![row("$ 한글abc", 20)];
+        // 커서는 끝(10열), 'a'는 6열
+        assert_eq!(arrow_moves(&rows, (0, 9), (0, 6)), Some(-3));
+    }
+
+    #[test]
+    fn follows_wrapped_rows_of_the_input_line() {
+        let rows = vec![row("output", 6), wrapped("$ abcd", 6), row("efg", 6)];
+        assert_eq!(arrow_moves(&rows, (2, 3), (1, 2)), Some(-5));
+        assert_eq!(arrow_moves(&rows, (1, 2), (2, 1)), Some(5));
+        assert_eq!(arrow_moves(&rows, (2, 3), (0, 1)), None);
+    }
+
+    #[test]
+    fn arrow_bytes_repeat_the_key() {
+        assert_eq!(arrow_bytes(-2), b"\x1b[D\x1b[D");
+        assert_eq!(arrow_bytes(1), b"\x1b[C");
+        assert!(arrow_bytes(0).is_empty());
+    }
+}
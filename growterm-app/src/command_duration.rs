@@ -0,0 +1,133 @@
+use std::collections::VecDeque;
+use std::time::{Duration, Instant};
+
+const MAX_ANNOTATIONS: usize = 200;
+
+/// Per-tab "⏱ 3.2s" annotations keyed to the absolute row of each prompt.
+/// Enter records the prompt row; OSC 133;D closes it with the elapsed time.
+/// Nothing is written to the PTY — the app draws these as an overlay.
+#[derive(Default)]
+pub struct CommandDurations {
+    pending: Option<(u32, Instant)>,
+    done: VecDeque<(u32, String)>,
+}
+
+impl CommandDurations {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn on_enter(&mut self, prompt_abs_row: u32, now: Instant) {
+        self.pending = Some((prompt_abs_row, now));
+    }
+
+    pub fn on_command_done(&mut self, now: Instant) {
+        let Some((row, started)) = self.pending.take() else {
+            return;
+        };
+        let text = format!("⏱ {}", format_duration(now.saturating_duration_since(started)));
+        self.done.retain(|(r, _)| *r != row);
+        self.done.push_back((row, text));
+        while self.done.len() > MAX_ANNOTATIONS {
+            self.done.pop_front();
+        }
+    }
+
+    /// Cmd+K: the rows annotations point at are gone, along with the
+    /// prompt of a command still running.
+    pub fn clear(&mut self) {
+        self.pending = None;
+        self.done.clear();
+    }
+
+    /// Annotations on screen as (screen_row, text).
+    pub fn visible(&self, view_base: u32, rows: u16) -> impl Iterator<Item = (u16, &str)> {
+        self.done.iter().filter_map(move |(row, text)| {
+            if *row >= view_base && *row < view_base + rows as u32 {
+                Some(((*row - view_base) as u16, text.as_str()))
+            } else {
+                None
+            }
+        })
+    }
+}
+
+pub fn format_duration(d: Duration) -> String {
+    let secs = d.as_secs_f64();
+    if secs < 60.0 {
+        format!("{:.1}s", secs)
+    } else {
+        let total = d.as_secs();
+        if total < 3600 {
+            format!("{}m{:02}s", total / 60, total % 60)
+        } else {
+            format!("{}h{:02}m", total / 3600, (total % 3600) / 60)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn format_seconds() {
+        assert_eq!(format_duration(Duration::from_millis(3200)), "3.2s");
+        assert_eq!(format_duration(Duration::from_millis(40)), "0.0s");
+    }
+
+    #[test]
+    fn format_minutes_and_hours() {
+        assert_eq!(format_duration(Duration::from_secs(65)), "1m05s");
+        assert_eq!(format_duration(Duration::from_secs(3 * 3600 + 120)), "3h02m");
+    }
+
+    #[test]
+    fn done_without_enter_is_ignored() {
+        let mut d = CommandDurations::new();
+        d.on_command_done(Instant::now());
+        assert_eq!(d.visible(0, 24).count(), 0);
+    }
+
+    #[test]
+    fn clear_drops_annotations_and_running_command() {
+        let mut d = CommandDurations::new();
+        let t0 = Instant::now();
+        d.on_enter(3, t0);
+        d.on_command_done(t0);
+        d.on_enter(5, t0);
+        d.clear();
+        d.on_command_done(t0 + Duration::from_secs(1));
+        assert_eq!(d.visible(0, 24).count(), 0);
+    }
+
+    #[test]
+    fn annotation_keyed_to_prompt_row() {
+        let mut d = CommandDurations::new();
+        let t0 = Instant::now();
+        d.on_enter(10, t0);
+        d.on_command_done(t0 + Duration::from_millis(1500));
+        let visible: Vec<_> = d.visible(5, 24).collect();
+        assert_eq!(visible, vec![(5, "⏱ 1.5s")]);
+    }
+
+    #[test]
+    fn scrolled_out_annotation_is_hidden() {
+        let mut d = CommandDurations::new();
+        let t0 = Instant::now();
+        d.on_enter(3, t0);
+        d.on_command_done(t0);
+        assert_eq!(d.visible(4, 24).count(), 0);
+    }
+
+    #[test]
+    fn second_done_needs_new_enter() {
+        let mut d = CommandDurations::new();
+        let t0 = Instant::now();
+        d.on_enter(1, t0);
+        d.on_command_done(t0 + Duration::from_secs(1));
+        d.on_command_done(t0 + Duration::from_secs(9));
+        let visible: Vec<_> = d.visible(0, 24).collect();
+        assert_eq!(visible, vec![(1, "⏱ 1.0s")]);
+    }
+}
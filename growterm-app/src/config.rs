@@ -1,8 +1,15 @@
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use std::path::PathBuf;
+use std::sync::OnceLock;
 
-use growterm_macos::key_convert::char_to_keycode;
+use growterm_pty::PtyOptions;
+use growterm_render_cmd::{CursorColors, DimStyle, SelectionColors, TerminalPalette};
+use growterm_types::Rgb;
+use growterm_window::key_convert::char_to_keycode;
+
+use crate::paste::PasteOptions;
+use crate::tab::ColorOverride;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum CopyModeAction {
@@ -16,6 +23,37 @@ pub enum CopyModeAction {
     Exit,
 }
 
+/// `present_mode` in config.toml
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum PresentMode {
+    #[default]
+    Fifo,
+    Mailbox,
+    Immediate,
+}
+
+impl From<PresentMode> for growterm_gpu_draw::PresentMode {
+    fn from(mode: PresentMode) -> Self {
+        match mode {
+            PresentMode::Fifo => Self::Fifo,
+            PresentMode::Mailbox => Self::Mailbox,
+            PresentMode::Immediate => Self::Immediate,
+        }
+    }
+}
+
+/// `renderer` in config.toml. Read at window creation; `gpu` falls back to
+/// `software` when wgpu can't initialize. Only backends that can present
+/// pixels (macOS) support `software`; elsewhere growterm exits with an error.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum RendererKind {
+    #[default]
+    Gpu,
+    Software,
+}
+
 fn deserialize_keys<'de, D>(deserializer: D) -> Result<Vec<String>, D::Error>
 where
     D: serde::Deserializer<'de>,
@@ -100,6 +138,88 @@ impl CopyModeKeys {
     }
 }
 
+/// 라이트/다크 모드별 기본 색상 ("#rrggbb"). 비어 있으면 내장 기본값.
+#[derive(Debug, Clone, Default, Deserialize, Serialize, PartialEq)]
+pub struct ThemeColors {
+    #[serde(default)]
+    pub foreground: Option<String>,
+    #[serde(default)]
+    pub background: Option<String>,
+}
+
+/// 터미널 내용 영역 둘레 여백 (논리 픽셀). 탭바/타이틀바 아래부터 잰다
+#[derive(Debug, Clone, Copy, Default, Deserialize, Serialize, PartialEq)]
+#[serde(default)]
+pub struct WindowPadding {
+    pub top: f32,
+    pub right: f32,
+    pub bottom: f32,
+    pub left: f32,
+}
+
+/// "New Tab With Profile" 메뉴로 여는 탭 프리셋
+#[derive(Debug, Clone, Default, Deserialize, Serialize, PartialEq)]
+pub struct Profile {
+    pub name: String,
+    /// 로그인 쉘의 `-c`로 실행. 없으면 쉘만 띄움
+    #[serde(default)]
+    pub command: Option<String>,
+    #[serde(default)]
+    pub cwd: Option<String>,
+    #[serde(default)]
+    pub foreground: Option<String>,
+    #[serde(default)]
+    pub background: Option<String>,
+    /// 탭을 열 때 창 전체 폰트 크기를 이 값으로 바꿈 (줌과 같음)
+    #[serde(default)]
+    pub font_size: Option<f32>,
+    /// 설정 전체의 `env`/`env_remove` 위에 덧붙임
+    #[serde(default)]
+    pub env: BTreeMap<String, String>,
+    #[serde(default)]
+    pub env_remove: Vec<String>,
+}
+
+impl Profile {
+    /// `base`(보통 `Config::pty_options()`) 위에 이 프로필의 명령·cwd·환경변수를 얹음
+    pub fn pty_options(&self, base: PtyOptions) -> PtyOptions {
+        let mut options = apply_env(base, &self.env, &self.env_remove);
+        if let Some(command) = &self.command {
+            let shell = std::env::var("SHELL").unwrap_or_else(|_| "/bin/sh".to_string());
+            options = options.program(shell).login_shell(true).arg("-c").arg(command);
+        }
+        options.cwd(self.cwd.as_deref().map(expand_home))
+    }
+
+    pub fn color_override(&self) -> ColorOverride {
+        ColorOverride {
+            fg: self.foreground.as_deref().and_then(parse_hex_color),
+            bg: self.background.as_deref().and_then(parse_hex_color),
+        }
+    }
+}
+
+/// 지울 변수를 먼저 지우고, 같은 이름을 `env`에 쓰면 그 값이 이김
+fn apply_env(mut options: PtyOptions, env: &BTreeMap<String, String>, remove: &[String]) -> PtyOptions {
+    for key in remove {
+        options = options.env_remove(key);
+    }
+    for (key, value) in env {
+        options = options.env(key, value);
+    }
+    options
+}
+
+/// 앞의 `~`를 HOME으로
+fn expand_home(path: &str) -> PathBuf {
+    match (path.strip_prefix('~'), std::env::var_os("HOME")) {
+        (Some(rest), Some(home)) if rest.is_empty() || rest.starts_with('/') => {
+            PathBuf::from(home).join(rest.trim_start_matches('/'))
+        }
+        _ => PathBuf::from(path),
+    }
+}
+
 #[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
 pub struct Config {
     #[serde(default = "default_font_family")]
@@ -132,6 +252,110 @@ pub struct Config {
     pub window_x: Option<f64>,
     #[serde(default)]
     pub window_y: Option<f64>,
+    #[serde(default)]
+    pub fullscreen: bool,
+    #[serde(default = "default_true")]
+    pub restore_session: bool,
+    #[serde(default)]
+    pub session_scrollback_lines: usize,
+    #[serde(default)]
+    pub scrollback_memory_cap_mb: Option<u64>,
+    #[serde(default = "default_scrollback_lines")]
+    pub scrollback_lines: usize,
+    #[serde(default)]
+    pub startup_banner_file: Option<String>,
+    #[serde(default)]
+    pub startup_banner_command: Option<String>,
+    #[serde(default)]
+    pub bell_audible: bool,
+    #[serde(default)]
+    pub bell_visual: bool,
+    #[serde(default)]
+    pub bell_badge: bool,
+    /// 포커스가 없을 때 벨이 울리면 Dock 아이콘을 튀김
+    #[serde(default = "default_true")]
+    pub bell_attention: bool,
+    #[serde(default)]
+    pub command_duration_annotation: bool,
+    #[serde(default)]
+    pub tab_silence_seconds: Option<u64>,
+    #[serde(default = "default_true")]
+    pub paste_confirm: bool,
+    #[serde(default)]
+    pub paste_strip_trailing_newline: bool,
+    #[serde(default = "default_true")]
+    pub paste_newline_to_cr: bool,
+    #[serde(default = "default_true")]
+    pub scroll_to_bottom_on_keystroke: bool,
+    #[serde(default)]
+    pub scroll_to_bottom_on_output: bool,
+    #[serde(default = "default_max_fps")]
+    pub max_fps: u32,
+    #[serde(default = "default_true")]
+    pub low_latency: bool,
+    /// 출력 폭주 시 파싱 대기 백로그가 크면 PTY 읽기를 멈춤
+    #[serde(default = "default_true")]
+    pub flow_control: bool,
+    #[serde(default)]
+    pub present_mode: PresentMode,
+    #[serde(default)]
+    pub renderer: RendererKind,
+    #[serde(default = "default_true")]
+    pub close_tab_on_exit: bool,
+    #[serde(default)]
+    pub selection_foreground: Option<String>,
+    #[serde(default)]
+    pub selection_background: Option<String>,
+    #[serde(default = "default_selection_opacity")]
+    pub selection_opacity: f32,
+    /// 블록 커서 색. 없으면 셀 fg/bg를 뒤집음
+    #[serde(default)]
+    pub cursor_color: Option<String>,
+    /// 커서 아래 글자 색. 없으면 셀 배경색
+    #[serde(default)]
+    pub cursor_text_color: Option<String>,
+    #[serde(default = "default_dim_factor")]
+    pub dim_factor: f32,
+    #[serde(default)]
+    pub dim_blend_to_background: bool,
+    #[serde(default = "default_true")]
+    pub bold_is_bright: bool,
+    #[serde(default)]
+    pub subpixel_positioning: bool,
+    /// 행 높이 배율 (글꼴의 줄 간격 기준)
+    #[serde(default = "default_line_height")]
+    pub line_height: f32,
+    /// 셀마다 더하는 가로 여백, 글꼴 크기와 같은 단위
+    #[serde(default)]
+    pub cell_padding: f32,
+    #[serde(default)]
+    pub window_padding: WindowPadding,
+    #[serde(default)]
+    pub light_theme: Option<ThemeColors>,
+    #[serde(default)]
+    pub dark_theme: Option<ThemeColors>,
+    #[serde(default)]
+    pub native_tabs: bool,
+    #[serde(default)]
+    pub new_tab_reconnects_ssh: bool,
+    #[serde(default)]
+    pub copy_rich_text: bool,
+    /// Cmd 없이도 화면의 URL/파일 경로에 항상 밑줄
+    #[serde(default)]
+    pub underline_links: bool,
+    /// 아랍어/히브리어가 든 행을 오른쪽→왼쪽 순서로 그리고 아랍 글자를 이어 그림
+    #[serde(default)]
+    pub bidi: bool,
+    #[serde(default = "default_word_separators")]
+    pub word_separators: String,
+    #[serde(default)]
+    pub profiles: Vec<Profile>,
+    /// 새 쉘에 넣을 환경변수 (프록시, 로케일 등)
+    #[serde(default)]
+    pub env: BTreeMap<String, String>,
+    /// 새 쉘에서 뺄 상속 환경변수
+    #[serde(default)]
+    pub env_remove: Vec<String>,
 }
 
 fn default_font_family() -> String {
@@ -154,6 +378,30 @@ fn default_header_opacity() -> f32 {
     0.8
 }
 
+fn default_word_separators() -> String {
+    crate::selection::DEFAULT_WORD_SEPARATORS.to_string()
+}
+
+fn default_selection_opacity() -> f32 {
+    1.0
+}
+
+fn default_dim_factor() -> f32 {
+    0.5
+}
+
+fn default_line_height() -> f32 {
+    1.0
+}
+
+fn default_scrollback_lines() -> usize {
+    growterm_grid::DEFAULT_MAX_SCROLLBACK
+}
+
+fn default_max_fps() -> u32 {
+    120
+}
+
 fn default_true() -> bool {
     true
 }
@@ -176,6 +424,53 @@ impl Default for Config {
             window_height: None,
             window_x: None,
             window_y: None,
+            fullscreen: false,
+            restore_session: true,
+            session_scrollback_lines: 0,
+            scrollback_memory_cap_mb: None,
+            scrollback_lines: default_scrollback_lines(),
+            startup_banner_file: None,
+            startup_banner_command: None,
+            bell_audible: false,
+            bell_visual: false,
+            bell_badge: false,
+            bell_attention: true,
+            command_duration_annotation: false,
+            tab_silence_seconds: None,
+            paste_confirm: true,
+            paste_strip_trailing_newline: false,
+            paste_newline_to_cr: true,
+            scroll_to_bottom_on_keystroke: true,
+            scroll_to_bottom_on_output: false,
+            max_fps: default_max_fps(),
+            low_latency: true,
+            flow_control: true,
+            present_mode: PresentMode::Fifo,
+            renderer: RendererKind::Gpu,
+            close_tab_on_exit: true,
+            selection_foreground: None,
+            selection_background: None,
+            selection_opacity: default_selection_opacity(),
+            cursor_color: None,
+            cursor_text_color: None,
+            dim_factor: default_dim_factor(),
+            dim_blend_to_background: false,
+            bold_is_bright: true,
+            subpixel_positioning: false,
+            line_height: default_line_height(),
+            cell_padding: 0.0,
+            window_padding: WindowPadding::default(),
+            light_theme: None,
+            dark_theme: None,
+            native_tabs: false,
+            new_tab_reconnects_ssh: false,
+            copy_rich_text: false,
+            underline_links: false,
+            bidi: false,
+            word_separators: default_word_separators(),
+            profiles: Vec::new(),
+            env: BTreeMap::new(),
+            env_remove: Vec::new(),
         }
     }
 }
@@ -191,6 +486,96 @@ impl Config {
             _ => None,
         }
     }
+
+    /// 윈도우 위치·크기 기록. 바뀌었으면 true (저장이 필요함)
+    pub fn set_window_frame(&mut self, (x, y, width, height): (f64, f64, f64, f64)) -> bool {
+        let frame = (Some(x.round()), Some(y.round()), Some(width.round()), Some(height.round()));
+        if (self.window_x, self.window_y, self.window_width, self.window_height) == frame {
+            return false;
+        }
+        (self.window_x, self.window_y, self.window_width, self.window_height) = frame;
+        true
+    }
+
+    /// 탭마다 남길 스크롤백 줄 수. 0이면 무제한
+    pub fn max_scrollback(&self) -> Option<usize> {
+        (self.scrollback_lines > 0).then_some(self.scrollback_lines)
+    }
+
+    /// 모든 탭의 기본 PTY 옵션 (설정의 환경변수 적용)
+    pub fn pty_options(&self) -> PtyOptions {
+        apply_env(PtyOptions::new(), &self.env, &self.env_remove)
+    }
+
+    pub fn paste_options(&self) -> PasteOptions {
+        PasteOptions {
+            confirm: self.paste_confirm,
+            strip_trailing_newline: self.paste_strip_trailing_newline,
+            newline_to_cr: self.paste_newline_to_cr,
+        }
+    }
+
+    /// 선택 영역 색상. 배경색이 없거나 잘못된 값이면 기존처럼 fg/bg 반전.
+    pub fn selection_colors(&self) -> SelectionColors {
+        SelectionColors {
+            fg: self.selection_foreground.as_deref().and_then(parse_hex_color),
+            bg: self.selection_background.as_deref().and_then(parse_hex_color),
+            alpha: (self.selection_opacity.clamp(0.0, 1.0) * 255.0).round() as u8,
+        }
+    }
+
+    pub fn cursor_colors(&self) -> CursorColors {
+        CursorColors {
+            fg: self.cursor_text_color.as_deref().and_then(parse_hex_color),
+            bg: self.cursor_color.as_deref().and_then(parse_hex_color),
+        }
+    }
+
+    /// DIM 텍스트 표시 방식. `dim_factor`는 남길 밝기 비율.
+    pub fn dim_style(&self) -> DimStyle {
+        let keep = (self.dim_factor.clamp(0.0, 1.0) * 100.0).round() as u8;
+        if self.dim_blend_to_background {
+            DimStyle::BlendToBackground(keep)
+        } else {
+            DimStyle::Scale(keep)
+        }
+    }
+
+    /// 시스템 모드에 맞는 테마의 기본 fg/bg
+    pub fn theme_palette(&self, dark: bool) -> TerminalPalette {
+        let theme = if dark { &self.dark_theme } else { &self.light_theme };
+        let mut palette = TerminalPalette::DEFAULT;
+        if let Some(theme) = theme {
+            if let Some(fg) = theme.foreground.as_deref().and_then(parse_hex_color) {
+                palette.default_fg = fg;
+            }
+            if let Some(bg) = theme.background.as_deref().and_then(parse_hex_color) {
+                palette.default_bg = bg;
+            }
+        }
+        palette
+    }
+
+    /// 설정에서 온 렌더링 옵션. 기본 fg/bg는 각 탭의 OSC 10/11 상태로 덮어씀.
+    pub fn palette(&self) -> TerminalPalette {
+        TerminalPalette {
+            selection: self.selection_colors(),
+            cursor: self.cursor_colors(),
+            dim: self.dim_style(),
+            bold_is_bright: self.bold_is_bright,
+            ..TerminalPalette::DEFAULT
+        }
+    }
+}
+
+/// "#rrggbb" 또는 "rrggbb"
+fn parse_hex_color(s: &str) -> Option<Rgb> {
+    let hex = s.trim().trim_start_matches('#');
+    if hex.len() != 6 || !hex.is_ascii() {
+        return None;
+    }
+    let channel = |i: usize| u8::from_str_radix(&hex[i..i + 2], 16).ok();
+    Some(Rgb::new(channel(0)?, channel(2)?, channel(4)?))
 }
 
 pub fn config_dir() -> PathBuf {
@@ -198,14 +583,25 @@ pub fn config_dir() -> PathBuf {
     PathBuf::from(home).join(".config").join("growterm")
 }
 
-fn config_path() -> PathBuf {
-    config_dir().join("config.toml")
+/// `--config`로 지정한 설정 파일
+static CONFIG_PATH_OVERRIDE: OnceLock<PathBuf> = OnceLock::new();
+
+/// 다른 설정 파일을 씀. 설정을 처음 읽기 전에 호출
+pub fn set_config_path(path: PathBuf) {
+    let _ = CONFIG_PATH_OVERRIDE.set(path);
+}
+
+pub fn config_path() -> PathBuf {
+    match CONFIG_PATH_OVERRIDE.get() {
+        Some(path) => path.clone(),
+        None => config_dir().join("config.toml"),
+    }
 }
 
 impl Config {
     pub fn load() -> Self {
         let path = config_path();
-        if path.exists() {
+        if path.exists() || CONFIG_PATH_OVERRIDE.get().is_some() {
             return Self::load_from_file(&path);
         }
 
@@ -225,6 +621,13 @@ impl Config {
         Self::default()
     }
 
+    /// Reload for a running app: a typo while editing the file keeps the
+    /// current settings instead of resetting everything to defaults.
+    pub fn try_load() -> Result<Self, String> {
+        let contents = std::fs::read_to_string(config_path()).map_err(|e| e.to_string())?;
+        toml::from_str(&contents).map_err(|e| e.to_string())
+    }
+
     fn load_from_file(path: &std::path::Path) -> Self {
         match std::fs::read_to_string(path) {
             Ok(contents) => toml::from_str(&contents).unwrap_or_default(),
@@ -262,14 +665,63 @@ impl Config {
             window_height: None,
             window_x: None,
             window_y: None,
+            fullscreen: false,
+            restore_session: true,
+            session_scrollback_lines: 0,
+            scrollback_memory_cap_mb: None,
+            scrollback_lines: default_scrollback_lines(),
+            startup_banner_file: None,
+            startup_banner_command: None,
+            bell_audible: false,
+            bell_visual: false,
+            bell_badge: false,
+            bell_attention: true,
+            command_duration_annotation: false,
+            tab_silence_seconds: None,
+            paste_confirm: true,
+            paste_strip_trailing_newline: false,
+            paste_newline_to_cr: true,
+            scroll_to_bottom_on_keystroke: true,
+            scroll_to_bottom_on_output: false,
+            max_fps: default_max_fps(),
+            low_latency: true,
+            flow_control: true,
+            present_mode: PresentMode::Fifo,
+            renderer: RendererKind::Gpu,
+            close_tab_on_exit: true,
+            selection_foreground: None,
+            selection_background: None,
+            selection_opacity: default_selection_opacity(),
+            cursor_color: None,
+            cursor_text_color: None,
+            dim_factor: default_dim_factor(),
+            dim_blend_to_background: false,
+            bold_is_bright: true,
+            subpixel_positioning: false,
+            line_height: default_line_height(),
+            cell_padding: 0.0,
+            window_padding: WindowPadding::default(),
+            light_theme: None,
+            dark_theme: None,
+            native_tabs: false,
+            new_tab_reconnects_ssh: false,
+            copy_rich_text: false,
+            underline_links: false,
+            bidi: false,
+            word_separators: default_word_separators(),
+            profiles: Vec::new(),
+            env: BTreeMap::new(),
+            env_remove: Vec::new(),
         }
     }
 
     pub fn save(&self) {
-        let dir = config_dir();
-        let _ = std::fs::create_dir_all(&dir);
+        let path = config_path();
+        if let Some(dir) = path.parent() {
+            let _ = std::fs::create_dir_all(dir);
+        }
         if let Ok(content) = toml::to_string(self) {
-            let _ = std::fs::write(config_path(), content);
+            let _ = std::fs::write(path, content);
         }
     }
 }
@@ -336,6 +788,311 @@ coaching_command = "claude -p --system 'You are a coach' '{prompt}'"
         assert!(config.coaching);
         assert!(!config.transparent_tab_bar);
         assert_eq!(config.header_opacity, 0.8);
+        assert!(config.restore_session);
+        assert_eq!(config.session_scrollback_lines, 0);
+    }
+
+    #[test]
+    fn parse_session_options() {
+        let toml = "restore_session = false\nsession_scrollback_lines = 200\n";
+        let config: Config = toml::from_str(toml).unwrap();
+        assert!(!config.restore_session);
+        assert_eq!(config.session_scrollback_lines, 200);
+    }
+
+    #[test]
+    fn parse_scrollback_memory_cap() {
+        let config: Config = toml::from_str("scrollback_memory_cap_mb = 256\n").unwrap();
+        assert_eq!(config.scrollback_memory_cap_mb, Some(256));
+        assert_eq!(Config::default().scrollback_memory_cap_mb, None);
+    }
+
+    #[test]
+    fn parse_scrollback_lines() {
+        assert_eq!(Config::default().max_scrollback(), Some(10_000));
+        let config: Config = toml::from_str("scrollback_lines = 500\n").unwrap();
+        assert_eq!(config.max_scrollback(), Some(500));
+        let config: Config = toml::from_str("scrollback_lines = 0\n").unwrap();
+        assert_eq!(config.max_scrollback(), None);
+    }
+
+    #[test]
+    fn parse_frame_pacing() {
+        let config: Config = toml::from_str("max_fps = 60\nlow_latency = false\n").unwrap();
+        assert_eq!(config.max_fps, 60);
+        assert!(!config.low_latency);
+        assert_eq!(Config::default().max_fps, 120);
+        assert!(Config::default().low_latency);
+    }
+
+    #[test]
+    fn parse_flow_control() {
+        assert!(Config::default().flow_control);
+        let config: Config = toml::from_str("flow_control = false\n").unwrap();
+        assert!(!config.flow_control);
+    }
+
+    #[test]
+    fn parse_subpixel_positioning() {
+        let config: Config = toml::from_str("subpixel_positioning = true\n").unwrap();
+        assert!(config.subpixel_positioning);
+        assert!(!Config::default().subpixel_positioning);
+    }
+
+    #[test]
+    fn parse_cell_spacing() {
+        let config: Config = toml::from_str("line_height = 1.2\ncell_padding = 2.0\n").unwrap();
+        assert_eq!((config.line_height, config.cell_padding), (1.2, 2.0));
+        let default = Config::default();
+        assert_eq!((default.line_height, default.cell_padding), (1.0, 0.0));
+    }
+
+    #[test]
+    fn parse_window_padding() {
+        let config: Config = toml::from_str("[window_padding]\ntop = 8.0\nleft = 12.0\n").unwrap();
+        assert_eq!(
+            config.window_padding,
+            WindowPadding { top: 8.0, right: 0.0, bottom: 0.0, left: 12.0 }
+        );
+        assert_eq!(Config::default().window_padding, WindowPadding::default());
+    }
+
+    #[test]
+    fn parse_present_mode() {
+        let config: Config = toml::from_str("present_mode = \"mailbox\"\n").unwrap();
+        assert_eq!(config.present_mode, PresentMode::Mailbox);
+        assert_eq!(Config::default().present_mode, PresentMode::Fifo);
+        assert!(toml::from_str::<Config>("present_mode = \"vsync\"\n").is_err());
+    }
+
+    #[test]
+    fn parse_renderer() {
+        let config: Config = toml::from_str("renderer = \"software\"\n").unwrap();
+        assert_eq!(config.renderer, RendererKind::Software);
+        assert_eq!(Config::default().renderer, RendererKind::Gpu);
+    }
+
+    #[test]
+    fn parse_native_tabs() {
+        let config: Config = toml::from_str("native_tabs = true\n").unwrap();
+        assert!(config.native_tabs);
+        assert!(!Config::default().native_tabs);
+    }
+
+    #[test]
+    fn parse_new_tab_reconnects_ssh() {
+        let config: Config = toml::from_str("new_tab_reconnects_ssh = true\n").unwrap();
+        assert!(config.new_tab_reconnects_ssh);
+        assert!(!Config::default().new_tab_reconnects_ssh);
+    }
+
+    #[test]
+    fn parse_word_separators() {
+        let config: Config = toml::from_str("word_separators = \" /.-\"\n").unwrap();
+        assert_eq!(config.word_separators, " /.-");
+        assert_eq!(Config::default().word_separators, crate::selection::DEFAULT_WORD_SEPARATORS);
+    }
+
+    #[test]
+    fn parse_underline_links() {
+        let config: Config = toml::from_str("underline_links = true\n").unwrap();
+        assert!(config.underline_links);
+        assert!(!Config::default().underline_links);
+    }
+
+    #[test]
+    fn parse_bidi() {
+        let config: Config = toml::from_str("bidi = true
+").unwrap();
+        assert!(config.bidi);
+        assert!(!Config::default().bidi);
+    }
+
+    #[test]
+    fn parse_copy_rich_text() {
+        let config: Config = toml::from_str("copy_rich_text = true\n").unwrap();
+        assert!(config.copy_rich_text);
+        assert!(!Config::default().copy_rich_text);
+    }
+
+    #[test]
+    fn parse_profiles() {
+        let toml = r##"
+[[profiles]]
+name = "prod ssh"
+command = "ssh prod"
+background = "#3a0000"
+font_size = 28
+
+[[profiles]]
+name = "notes"
+cwd = "/tmp"
+"##;
+        let config: Config = toml::from_str(toml).unwrap();
+        assert_eq!(config.profiles.len(), 2);
+        let prod = &config.profiles[0];
+        assert_eq!(prod.name, "prod ssh");
+        assert_eq!(prod.font_size, Some(28.0));
+        assert_eq!(prod.color_override(), ColorOverride { fg: None, bg: Some(Rgb::new(0x3a, 0, 0)) });
+        assert_eq!(config.profiles[1].cwd.as_deref(), Some("/tmp"));
+        assert!(Config::default().profiles.is_empty());
+    }
+
+    #[test]
+    fn profile_without_command_uses_default_shell() {
+        let profile = Profile { name: "p".into(), cwd: Some("/tmp".into()), ..Profile::default() };
+        assert_eq!(profile.pty_options(PtyOptions::new()), PtyOptions::new().cwd(Some("/tmp")));
+    }
+
+    #[test]
+    fn parse_env_and_profile_env() {
+        let toml = r##"
+env_remove = ["NO_PROXY"]
+
+[env]
+HTTP_PROXY = "http://proxy:3128"
+LANG = "ko_KR.UTF-8"
+
+[[profiles]]
+name = "direct"
+env_remove = ["HTTP_PROXY"]
+env = { LANG = "en_US.UTF-8" }
+"##;
+        let config: Config = toml::from_str(toml).unwrap();
+        let base = config.pty_options();
+        assert_eq!(
+            base,
+            PtyOptions::new()
+                .env_remove("NO_PROXY")
+                .env("HTTP_PROXY", "http://proxy:3128")
+                .env("LANG", "ko_KR.UTF-8")
+        );
+        assert_eq!(
+            config.profiles[0].pty_options(base),
+            PtyOptions::new()
+                .env_remove("NO_PROXY")
+                .env_remove("HTTP_PROXY")
+                .env("LANG", "en_US.UTF-8")
+        );
+        assert_eq!(Config::default().pty_options(), PtyOptions::new());
+    }
+
+    #[test]
+    fn parse_close_tab_on_exit() {
+        let config: Config = toml::from_str("close_tab_on_exit = false\n").unwrap();
+        assert!(!config.close_tab_on_exit);
+        assert!(Config::default().close_tab_on_exit);
+    }
+
+    #[test]
+    fn parse_selection_colors() {
+        let config: Config = toml::from_str(
+            "selection_foreground = \"#ffffff\"\nselection_background = \"2850a0\"\nselection_opacity = 0.5\n",
+        )
+        .unwrap();
+        let colors = config.selection_colors();
+        assert_eq!(colors.fg, Some(Rgb::new(255, 255, 255)));
+        assert_eq!(colors.bg, Some(Rgb::new(0x28, 0x50, 0xa0)));
+        assert_eq!(colors.alpha, 128);
+        assert_eq!(Config::default().selection_colors(), SelectionColors::SWAP);
+    }
+
+    #[test]
+    fn parse_cursor_colors() {
+        let config: Config = toml::from_str("cursor_color = \"#ffcc00\"\ncursor_text_color = \"000000\"\n").unwrap();
+        let cursor = config.palette().cursor;
+        assert_eq!(cursor.bg, Some(Rgb::new(0xff, 0xcc, 0x00)));
+        assert_eq!(cursor.fg, Some(Rgb::new(0, 0, 0)));
+        assert_eq!(Config::default().cursor_colors(), CursorColors::SWAP);
+    }
+
+    #[test]
+    fn parse_dim_and_bold_options() {
+        let config: Config = toml::from_str(
+            "dim_factor = 0.7\ndim_blend_to_background = true\nbold_is_bright = false\n",
+        )
+        .unwrap();
+        let palette = config.palette();
+        assert_eq!(palette.dim, DimStyle::BlendToBackground(70));
+        assert!(!palette.bold_is_bright);
+        assert_eq!(Config::default().palette(), TerminalPalette::DEFAULT);
+    }
+
+    #[test]
+    fn parse_light_and_dark_themes() {
+        let config: Config = toml::from_str(
+            "[light_theme]\nforeground = \"#202020\"\nbackground = \"#fafafa\"\n\n[dark_theme]\nbackground = \"#101010\"\n",
+        )
+        .unwrap();
+        let light = config.theme_palette(false);
+        assert_eq!(light.default_fg, Rgb::new(0x20, 0x20, 0x20));
+        assert_eq!(light.default_bg, Rgb::new(0xfa, 0xfa, 0xfa));
+        let dark = config.theme_palette(true);
+        assert_eq!(dark.default_fg, TerminalPalette::DEFAULT.default_fg);
+        assert_eq!(dark.default_bg, Rgb::new(0x10, 0x10, 0x10));
+        assert_eq!(Config::default().theme_palette(false), TerminalPalette::DEFAULT);
+    }
+
+    #[test]
+    fn invalid_selection_color_is_ignored() {
+        let config: Config = toml::from_str("selection_background = \"#12345\"\n").unwrap();
+        assert_eq!(config.selection_colors().bg, None);
+        assert_eq!(parse_hex_color("#gg0000"), None);
+    }
+
+    #[test]
+    fn parse_scroll_to_bottom_options() {
+        let config: Config = toml::from_str("scroll_to_bottom_on_keystroke = false\nscroll_to_bottom_on_output = true\n").unwrap();
+        assert!(!config.scroll_to_bottom_on_keystroke);
+        assert!(config.scroll_to_bottom_on_output);
+        let default = Config::default();
+        assert!(default.scroll_to_bottom_on_keystroke);
+        assert!(!default.scroll_to_bottom_on_output);
+    }
+
+    #[test]
+    fn parse_paste_options() {
+        let config: Config = toml::from_str("paste_confirm = false\npaste_strip_trailing_newline = true\n").unwrap();
+        let opts = config.paste_options();
+        assert!(!opts.confirm);
+        assert!(opts.strip_trailing_newline);
+        assert!(opts.newline_to_cr);
+        assert_eq!(Config::default().paste_options(), PasteOptions::default());
+    }
+
+    #[test]
+    fn parse_tab_silence_seconds() {
+        let config: Config = toml::from_str("tab_silence_seconds = 30\n").unwrap();
+        assert_eq!(config.tab_silence_seconds, Some(30));
+        assert_eq!(Config::default().tab_silence_seconds, None);
+    }
+
+    #[test]
+    fn parse_command_duration_annotation() {
+        let config: Config = toml::from_str("command_duration_annotation = true\n").unwrap();
+        assert!(config.command_duration_annotation);
+        assert!(!Config::default().command_duration_annotation);
+    }
+
+    #[test]
+    fn parse_bell_options() {
+        let toml = "bell_audible = true\nbell_visual = true\n";
+        let config: Config = toml::from_str(toml).unwrap();
+        assert!(config.bell_audible);
+        assert!(config.bell_visual);
+        assert!(!config.bell_badge);
+        assert!(config.bell_attention);
+        let config: Config = toml::from_str("bell_attention = false\n").unwrap();
+        assert!(!config.bell_attention);
+    }
+
+    #[test]
+    fn parse_startup_banner() {
+        let toml = "startup_banner_file = \"~/.config/growterm/motd\"\nstartup_banner_command = \"fortune\"\n";
+        let config: Config = toml::from_str(toml).unwrap();
+        assert_eq!(config.startup_banner_file.as_deref(), Some("~/.config/growterm/motd"));
+        assert_eq!(config.startup_banner_command.as_deref(), Some("fortune"));
+        assert_eq!(Config::default().startup_banner_file, None);
     }
 
     #[test]
@@ -391,7 +1148,7 @@ exit = "q"
 
     #[test]
     fn build_action_map_default() {
-        use growterm_macos::key_convert::keycode as kc;
+        use growterm_window::key_convert::keycode as kc;
         let keys = CopyModeKeys::default();
         let map = keys.build_action_map();
         assert_eq!(map.get(&kc::ANSI_J), Some(&CopyModeAction::Down));
@@ -410,7 +1167,7 @@ exit = "q"
 
     #[test]
     fn build_action_map_custom() {
-        use growterm_macos::key_convert::keycode as kc;
+        use growterm_window::key_convert::keycode as kc;
         let mut keys = CopyModeKeys::default();
         keys.down = vec!["n".into()];
         let map = keys.build_action_map();
@@ -445,6 +1202,22 @@ window_y = 50
         assert_eq!(config.window_position(), None);
     }
 
+    #[test]
+    fn set_window_frame_reports_changes() {
+        let mut config = Config::default();
+        assert!(config.set_window_frame((100.4, 50.0, 1200.0, 800.0)));
+        assert_eq!(config.window_position(), Some((100.0, 50.0)));
+        assert_eq!(config.window_size(), (1200.0, 800.0));
+        assert!(!config.set_window_frame((100.0, 50.2, 1200.0, 800.0)));
+    }
+
+    #[test]
+    fn parse_fullscreen() {
+        let config: Config = toml::from_str("fullscreen = true\n").unwrap();
+        assert!(config.fullscreen);
+        assert!(!Config::default().fullscreen);
+    }
+
     #[test]
     fn pomodoro_time_defaults() {
         let config: Config = toml::from_str("").unwrap();
@@ -0,0 +1,98 @@
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+
+/// Editors write in several steps (truncate, write, rename); wait for them to finish.
+const SETTLE: Duration = Duration::from_millis(150);
+/// Used where kqueue isn't available, or while the file doesn't exist yet.
+const POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Calls `on_change` from a background thread whenever the file at `path`
+/// is written, replaced or created. Uses kqueue on macOS and polls the
+/// modification time elsewhere.
+pub fn watch(path: PathBuf, on_change: impl Fn() + Send + 'static) {
+    std::thread::spawn(move || loop {
+        wait_for_change(&path);
+        std::thread::sleep(SETTLE);
+        on_change();
+    });
+}
+
+#[cfg(target_os = "macos")]
+fn wait_for_change(path: &Path) {
+    use std::os::unix::ffi::OsStrExt;
+
+    let Ok(c_path) = std::ffi::CString::new(path.as_os_str().as_bytes()) else {
+        return poll_for_change(path);
+    };
+    unsafe {
+        let fd = libc::open(c_path.as_ptr(), libc::O_EVTONLY);
+        if fd < 0 {
+            // 아직 파일이 없음: 생길 때까지 폴링
+            return poll_for_change(path);
+        }
+        let kq = libc::kqueue();
+        if kq < 0 {
+            libc::close(fd);
+            return poll_for_change(path);
+        }
+        let mut change: libc::kevent = std::mem::zeroed();
+        change.ident = fd as usize;
+        change.filter = libc::EVFILT_VNODE;
+        change.flags = libc::EV_ADD | libc::EV_CLEAR;
+        // 저장 시 새 파일로 바꿔치기하는 편집기는 DELETE/RENAME으로 옴
+        change.fflags = libc::NOTE_WRITE | libc::NOTE_EXTEND | libc::NOTE_DELETE | libc::NOTE_RENAME;
+        let mut event: libc::kevent = std::mem::zeroed();
+        let n = libc::kevent(kq, &change, 1, &mut event, 1, std::ptr::null());
+        libc::close(kq);
+        libc::close(fd);
+        if n < 0 {
+            std::thread::sleep(POLL_INTERVAL);
+        }
+    }
+}
+
+#[cfg(not(target_os = "macos"))]
+fn wait_for_change(path: &Path) {
+    poll_for_change(path);
+}
+
+fn poll_for_change(path: &Path) {
+    let initial = modified(path);
+    loop {
+        std::thread::sleep(POLL_INTERVAL);
+        if modified(path) != initial {
+            return;
+        }
+    }
+}
+
+fn modified(path: &Path) -> Option<SystemTime> {
+    std::fs::metadata(path).and_then(|m| m.modified()).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::mpsc;
+
+    #[test]
+    fn notices_file_creation_and_write() {
+        let dir = std::env::temp_dir().join(format!("growterm-config-watch-{}", std::process::id()));
+        let _ = std::fs::create_dir_all(&dir);
+        let path = dir.join("config.toml");
+        let _ = std::fs::remove_file(&path);
+
+        let (tx, rx) = mpsc::channel();
+        watch(path.clone(), move || {
+            let _ = tx.send(());
+        });
+        std::thread::sleep(Duration::from_millis(200));
+        std::fs::write(&path, "font_size = 14.0\n").unwrap();
+        assert!(rx.recv_timeout(Duration::from_secs(5)).is_ok());
+
+        std::thread::sleep(Duration::from_millis(1100));
+        std::fs::write(&path, "font_size = 16.0\n").unwrap();
+        assert!(rx.recv_timeout(Duration::from_secs(5)).is_ok());
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
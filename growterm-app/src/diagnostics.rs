@@ -0,0 +1,183 @@
+use growterm_types::Cell;
+
+use crate::tab::TabManager;
+
+/// Per-tab numbers shown in the Diagnostics report.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TabDiagnostics {
+    pub scrollback_rows: usize,
+    pub screen_rows: usize,
+    pub cols: usize,
+    pub pid: Option<u32>,
+}
+
+impl TabDiagnostics {
+    /// Estimated bytes held by the scrollback and the visible screen.
+    pub fn estimated_bytes(&self) -> usize {
+        estimate_bytes(self.scrollback_rows + self.screen_rows, self.cols)
+    }
+}
+
+pub fn estimate_bytes(rows: usize, cols: usize) -> usize {
+    // 행마다 Arc 포인터 + 참조 카운트 두 개. 공유된 빈 행도 따로 셈
+    let row_overhead = std::mem::size_of::<growterm_grid::Row>() + 2 * std::mem::size_of::<usize>();
+    rows * (cols * std::mem::size_of::<Cell>() + row_overhead)
+}
+
+pub fn collect(tabs: &TabManager) -> Vec<TabDiagnostics> {
+    tabs.tabs()
+        .iter()
+        .map(|tab| {
+            let state = tab.terminal.lock().unwrap();
+            let (cols, rows) = state.grid.size();
+            TabDiagnostics {
+                scrollback_rows: state.grid.scrollback_len(),
+                screen_rows: rows as usize,
+                cols: cols as usize,
+                pid: tab.pty_writer.child_pid(),
+            }
+        })
+        .collect()
+}
+
+pub fn total_bytes(tabs: &[TabDiagnostics]) -> usize {
+    tabs.iter().map(|t| t.estimated_bytes()).sum()
+}
+
+fn format_bytes(bytes: usize) -> String {
+    const MB: f64 = 1024.0 * 1024.0;
+    if bytes as f64 >= MB {
+        format!("{:.1} MB", bytes as f64 / MB)
+    } else {
+        format!("{:.1} KB", bytes as f64 / 1024.0)
+    }
+}
+
+/// Human-readable report. `atlas` is (cached glyphs, texture fill ratio).
+pub fn format_report(tabs: &[TabDiagnostics], atlas: (usize, f32)) -> String {
+    let mut lines = Vec::new();
+    for (i, tab) in tabs.iter().enumerate() {
+        let pid = tab
+            .pid
+            .map_or_else(|| "-".to_string(), |p| p.to_string());
+        lines.push(format!(
+            "Tab {}: {} scrollback rows, ~{}, pid {}",
+            i + 1,
+            tab.scrollback_rows,
+            format_bytes(tab.estimated_bytes()),
+            pid
+        ));
+    }
+    lines.push(format!("Total: ~{}", format_bytes(total_bytes(tabs))));
+    lines.push(format!(
+        "Glyph atlas: {} glyphs, {:.0}% used",
+        atlas.0,
+        atlas.1 * 100.0
+    ));
+    let open = tabs.iter().filter(|t| t.pid.is_some()).count();
+    lines.push(format!("Open PTYs: {}", open));
+    lines.join("\n")
+}
+
+/// Warns once each time total scrollback memory crosses the cap.
+pub struct MemoryWatchdog {
+    cap_bytes: Option<usize>,
+    warned: bool,
+}
+
+impl MemoryWatchdog {
+    pub fn new(cap_mb: Option<u64>) -> Self {
+        Self {
+            cap_bytes: cap_mb.map(|mb| mb as usize * 1024 * 1024),
+            warned: false,
+        }
+    }
+
+    pub fn set_cap_mb(&mut self, cap_mb: Option<u64>) {
+        *self = Self::new(cap_mb);
+    }
+
+    /// Returns true when a warning should be shown for `total` bytes.
+    pub fn check(&mut self, total: usize) -> bool {
+        let Some(cap) = self.cap_bytes else {
+            return false;
+        };
+        if total <= cap {
+            self.warned = false;
+            return false;
+        }
+        if self.warned {
+            return false;
+        }
+        self.warned = true;
+        true
+    }
+
+    /// Scrollback rows each tab may keep so that the total fits in half the cap.
+    pub fn trim_target_rows(&self, tabs: &[TabDiagnostics]) -> Option<usize> {
+        let cap = self.cap_bytes?;
+        let row_bytes = tabs
+            .iter()
+            .map(|t| estimate_bytes(1, t.cols))
+            .max()
+            .unwrap_or(0);
+        if row_bytes == 0 || tabs.is_empty() {
+            return None;
+        }
+        Some(cap / 2 / row_bytes / tabs.len())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tab(scrollback_rows: usize) -> TabDiagnostics {
+        TabDiagnostics {
+            scrollback_rows,
+            screen_rows: 24,
+            cols: 80,
+            pid: Some(42),
+        }
+    }
+
+    #[test]
+    fn estimate_grows_with_rows_and_cols() {
+        assert!(estimate_bytes(10, 80) > estimate_bytes(5, 80));
+        assert!(estimate_bytes(10, 80) > estimate_bytes(10, 40));
+        assert_eq!(estimate_bytes(0, 80), 0);
+    }
+
+    #[test]
+    fn report_lists_each_tab_and_totals() {
+        let report = format_report(&[tab(100), tab(0)], (12, 0.25));
+        assert!(report.contains("Tab 1: 100 scrollback rows"));
+        assert!(report.contains("Tab 2: 0 scrollback rows"));
+        assert!(report.contains("Glyph atlas: 12 glyphs, 25% used"));
+        assert!(report.contains("Open PTYs: 2"));
+    }
+
+    #[test]
+    fn watchdog_disabled_without_cap() {
+        let mut dog = MemoryWatchdog::new(None);
+        assert!(!dog.check(usize::MAX));
+    }
+
+    #[test]
+    fn watchdog_warns_once_until_back_under_cap() {
+        let mut dog = MemoryWatchdog::new(Some(1));
+        let over = 2 * 1024 * 1024;
+        assert!(dog.check(over));
+        assert!(!dog.check(over));
+        assert!(!dog.check(1024));
+        assert!(dog.check(over));
+    }
+
+    #[test]
+    fn trim_target_splits_half_cap_across_tabs() {
+        let dog = MemoryWatchdog::new(Some(1));
+        let tabs = [tab(0), tab(0)];
+        let rows = dog.trim_target_rows(&tabs).unwrap();
+        assert_eq!(rows, 1024 * 1024 / 2 / estimate_bytes(1, 80) / 2);
+    }
+}
@@ -0,0 +1,86 @@
+use std::time::{Duration, Instant};
+
+use growterm_pty::PtyWriter;
+
+/// 프로세스 인자 조회는 비싸므로 탭마다 이 간격으로만 확인
+const CHECK_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Longest title shown in a tab before it's cut with `…`.
+const MAX_TITLE_CHARS: usize = 24;
+
+/// Name of the program in a tab's foreground (`vim`, `cargo`), shown in
+/// the tab bar when the program hasn't set an OSC title.
+#[derive(Default)]
+pub struct ForegroundWatch {
+    name: Option<String>,
+    checked_at: Option<Instant>,
+}
+
+impl ForegroundWatch {
+    /// None while the shell itself is in the foreground.
+    pub fn name(&self) -> Option<&str> {
+        self.name.as_deref()
+    }
+
+    /// Re-inspect the foreground process at most once per second.
+    /// Returns true if the name changed.
+    pub fn refresh(&mut self, pty: &PtyWriter, now: Instant) -> bool {
+        if self.checked_at.is_some_and(|t| now.saturating_duration_since(t) < CHECK_INTERVAL) {
+            return false;
+        }
+        self.checked_at = Some(now);
+        let name = pty
+            .foreground_pid()
+            .filter(|&pid| Some(pid) != pty.child_pid())
+            .and_then(growterm_pty::process_args)
+            .and_then(|args| program_name(&args));
+        if name == self.name {
+            return false;
+        }
+        self.name = name;
+        true
+    }
+}
+
+/// `/usr/bin/vim` → `vim`, `-zsh` → `zsh`
+fn program_name(args: &[String]) -> Option<String> {
+    let program = args.first()?;
+    let name = program.rsplit('/').next().unwrap_or(program).trim_start_matches('-');
+    (!name.is_empty()).then(|| name.to_string())
+}
+
+/// Cut `title` to fit a tab, marking the cut with `…`.
+pub fn short_title(title: &str) -> String {
+    if title.chars().count() <= MAX_TITLE_CHARS {
+        return title.to_string();
+    }
+    let mut short: String = title.chars().take(MAX_TITLE_CHARS - 1).collect();
+    short.push('…');
+    short
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(list: &[&str]) -> Vec<String> {
+        list.iter().map(|s| s.to_string()).collect()
+    }
+
+    #[test]
+    fn program_name_drops_directory_and_login_dash() {
+        assert_eq!(program_name(&args(&["/usr/bin/vim", "a.rs"])).as_deref(), Some("vim"));
+        assert_eq!(program_name(&args(&["-zsh"])).as_deref(), Some("zsh"));
+        assert_eq!(program_name(&args(&["cargo", "build"])).as_deref(), Some("cargo"));
+        assert_eq!(program_name(&[]), None);
+    }
+
+    #[test]
+    fn short_title_cuts_long_titles() {
+        assert_eq!(short_title("vim"), "vim");
+        let long = "a".repeat(MAX_TITLE_CHARS + 5);
+        let short = short_title(&long);
+        assert_eq!(short.chars().count(), MAX_TITLE_CHARS);
+        assert!(short.ends_with('…'));
+    }
+}
@@ -0,0 +1,178 @@
+use std::sync::{Arc, Mutex};
+
+use growterm_grid::{Grid, Row};
+use growterm_render_cmd::TerminalPalette;
+
+use crate::tab::TerminalState;
+
+/// What the renderer needs from a tab's screen, copied out so drawing
+/// never holds the terminal lock.
+#[derive(Debug, Clone, Default)]
+pub struct ScreenSnapshot {
+    /// Rows in view, scrollback included when scrolled. Shared with the
+    /// grid, so only the pointers are copied.
+    pub rows: Vec<Row>,
+    /// Row just above the view, half-shown while smooth scrolling
+    pub row_above: Option<Row>,
+    pub cursor: (u16, u16),
+    pub cursor_visible: bool,
+    pub scroll_offset: usize,
+    pub scrollback_len: usize,
+    pub palette: TerminalPalette,
+    /// Grid generation `rows` reflect; 0 means never filled
+    generation: u64,
+}
+
+impl ScreenSnapshot {
+    /// Bring this copy up to date with `grid`. At the bottom of the
+    /// scrollback only rows changed since the last update are copied.
+    pub fn update_from(&mut self, grid: &Grid, palette: TerminalPalette) {
+        let offset = grid.scroll_offset();
+        if offset == 0 && self.scroll_offset == 0 && self.generation != 0 {
+            self.rows.resize(grid.screen_rows().len(), Row::default());
+            for update in grid.diff_since(self.generation) {
+                self.rows[update.row as usize] = update.cells;
+            }
+        } else {
+            self.rows.clear();
+            self.rows.extend(grid.visible_rows().cloned());
+        }
+        let view_base = grid.scrollback_len().saturating_sub(offset);
+        self.row_above = view_base
+            .checked_sub(1)
+            .and_then(|row| grid.row(row))
+            .cloned();
+        self.cursor = grid.cursor_pos();
+        self.cursor_visible = grid.cursor_visible();
+        self.scroll_offset = offset;
+        self.scrollback_len = grid.scrollback_len();
+        self.palette = palette;
+        self.generation = grid.generation();
+    }
+
+    pub fn generation(&self) -> u64 {
+        self.generation
+    }
+}
+
+/// Triple buffer between a tab's IO thread and the renderer: the IO
+/// thread fills its own copy and swaps it into the middle slot, the
+/// renderer swaps the middle slot out. The lock only covers the swap.
+struct Middle {
+    screen: ScreenSnapshot,
+    fresh: bool,
+}
+
+pub fn frame_buffer() -> (FramePublisher, FrameReader) {
+    let middle = Arc::new(Mutex::new(Middle { screen: ScreenSnapshot::default(), fresh: false }));
+    (
+        FramePublisher { back: ScreenSnapshot::default(), middle: Arc::clone(&middle) },
+        FrameReader { front: ScreenSnapshot::default(), middle },
+    )
+}
+
+/// IO thread side
+pub struct FramePublisher {
+    back: ScreenSnapshot,
+    middle: Arc<Mutex<Middle>>,
+}
+
+impl FramePublisher {
+    /// Called with the terminal locked, right after parsing.
+    pub fn publish(&mut self, grid: &Grid, palette: TerminalPalette) {
+        self.back.update_from(grid, palette);
+        let mut middle = self.middle.lock().unwrap();
+        std::mem::swap(&mut self.back, &mut middle.screen);
+        middle.fresh = true;
+    }
+}
+
+/// Render side
+pub struct FrameReader {
+    front: ScreenSnapshot,
+    middle: Arc<Mutex<Middle>>,
+}
+
+impl FrameReader {
+    /// The screen to draw: read straight from `terminal` when nobody
+    /// holds it, so scrolling and resizes show up at once; while the IO
+    /// thread is busy parsing, the last screen it published.
+    pub fn latest(&mut self, terminal: &Mutex<TerminalState>) -> &ScreenSnapshot {
+        match terminal.try_lock() {
+            Ok(state) => self.front.update_from(&state.grid, state.palette),
+            Err(_) => {
+                let mut middle = self.middle.lock().unwrap();
+                // 직접 읽은 front가 더 최신이면 바꾸지 않음
+                if middle.fresh && middle.screen.generation >= self.front.generation {
+                    std::mem::swap(&mut self.front, &mut middle.screen);
+                    middle.fresh = false;
+                }
+            }
+        }
+        &self.front
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use growterm_types::{Cell, TerminalCommand};
+
+    fn print(grid: &mut Grid, text: &str) {
+        for ch in text.chars() {
+            grid.apply(&TerminalCommand::Print(ch));
+        }
+    }
+
+    fn row_text(row: &[Cell]) -> String {
+        row.iter().map(|c| c.character).collect::<String>().trim_end().to_string()
+    }
+
+    #[test]
+    fn incremental_update_matches_grid() {
+        let mut grid = Grid::new(10, 3);
+        let mut snap = ScreenSnapshot::default();
+        print(&mut grid, "one");
+        snap.update_from(&grid, TerminalPalette::default());
+        grid.apply(&TerminalCommand::Newline);
+        print(&mut grid, "two");
+        snap.update_from(&grid, TerminalPalette::default());
+        assert!(snap.rows.iter().eq(grid.screen_rows()));
+        assert_eq!(snap.cursor, grid.cursor_pos());
+    }
+
+    #[test]
+    fn scrolled_view_copies_visible_rows() {
+        let mut grid = Grid::new(10, 2);
+        for line in ["a", "b", "c", "d"] {
+            print(&mut grid, line);
+            grid.apply(&TerminalCommand::Newline);
+            grid.apply(&TerminalCommand::CarriageReturn);
+        }
+        let mut snap = ScreenSnapshot::default();
+        snap.update_from(&grid, TerminalPalette::default());
+        grid.set_scroll_offset(1);
+        snap.update_from(&grid, TerminalPalette::default());
+        assert!(snap.rows.iter().eq(grid.visible_rows()));
+        assert_eq!(snap.scroll_offset, 1);
+        assert_eq!(snap.row_above.as_deref().map(row_text).as_deref(), Some("b"));
+    }
+
+    #[test]
+    fn reader_uses_published_screen_while_terminal_is_locked() {
+        let (mut publisher, mut reader) = frame_buffer();
+        let mut grid = Grid::new(10, 2);
+        print(&mut grid, "hello");
+        publisher.publish(&grid, TerminalPalette::default());
+
+        let terminal = Mutex::new(TerminalState {
+            grid: Grid::new(10, 2),
+            vt_parser: growterm_vt_parser::VtParser::new(),
+            palette: TerminalPalette::default(),
+            base_palette: TerminalPalette::default(),
+        });
+        let _io = terminal.lock().unwrap();
+        let snap = reader.latest(&terminal);
+        assert_eq!(row_text(&snap.rows[0]), "hello");
+    }
+}
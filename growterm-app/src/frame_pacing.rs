@@ -0,0 +1,286 @@
+use std::collections::VecDeque;
+use std::time::{Duration, Instant};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FrameDecision {
+    Draw,
+    /// Too soon after the last frame; draw at this instant instead
+    Defer(Instant),
+}
+
+/// Coalesces output-driven redraws to at most `max_fps` frames per second.
+/// A frame that follows user input is never deferred, so echo latency stays
+/// below one frame even while `cat` of a huge file saturates the PTY.
+pub struct FrameScheduler {
+    interval: Option<Duration>,
+    low_latency: bool,
+    last_frame: Option<Instant>,
+    input_pending: bool,
+}
+
+impl FrameScheduler {
+    /// `max_fps == 0` disables pacing.
+    pub fn new(max_fps: u32, low_latency: bool) -> Self {
+        Self {
+            interval: frame_interval(max_fps),
+            low_latency,
+            last_frame: None,
+            input_pending: false,
+        }
+    }
+
+    pub fn set_max_fps(&mut self, max_fps: u32) {
+        self.interval = frame_interval(max_fps);
+    }
+
+    pub fn set_low_latency(&mut self, enabled: bool) {
+        self.low_latency = enabled;
+    }
+
+    pub fn on_input(&mut self) {
+        self.input_pending = true;
+    }
+
+    pub fn decide(&self, now: Instant) -> FrameDecision {
+        let (Some(interval), Some(last)) = (self.interval, self.last_frame) else {
+            return FrameDecision::Draw;
+        };
+        if self.low_latency && self.input_pending {
+            return FrameDecision::Draw;
+        }
+        let next = last + interval;
+        if now >= next {
+            FrameDecision::Draw
+        } else {
+            FrameDecision::Defer(next)
+        }
+    }
+
+    pub fn frame_drawn(&mut self, now: Instant) {
+        self.last_frame = Some(now);
+        self.input_pending = false;
+    }
+}
+
+/// Frames averaged for the debug HUD.
+const STATS_WINDOW: usize = 60;
+/// Input the PTY never echoed is dropped from the latency average after this.
+const INPUT_ECHO_TIMEOUT: Duration = Duration::from_secs(1);
+
+/// Recent frame timings, shown in the debug view and the latency HUD.
+#[derive(Default)]
+pub struct FrameStats {
+    /// (frame start, time spent building and submitting it)
+    frames: VecDeque<(Instant, Duration)>,
+    /// First input not yet shown on screen, and whether the PTY has answered it
+    pending_input: Option<(Instant, bool)>,
+    /// Input → end of the first frame after the PTY echoed it
+    input_latencies: VecDeque<Duration>,
+    /// (sample time, total PTY bytes read by the IO threads)
+    pty_samples: VecDeque<(Instant, u64)>,
+}
+
+fn push_capped<T>(queue: &mut VecDeque<T>, value: T) {
+    if queue.len() == STATS_WINDOW {
+        queue.pop_front();
+    }
+    queue.push_back(value);
+}
+
+impl FrameStats {
+    pub fn record(&mut self, start: Instant, draw: Duration) {
+        push_capped(&mut self.frames, (start, draw));
+        match self.pending_input {
+            Some((input, true)) => {
+                push_capped(&mut self.input_latencies, (start + draw).saturating_duration_since(input));
+                self.pending_input = None;
+            }
+            Some((input, false)) if start.saturating_duration_since(input) > INPUT_ECHO_TIMEOUT => {
+                self.pending_input = None;
+            }
+            _ => {}
+        }
+    }
+
+    /// Key or text input was written to the PTY.
+    pub fn on_input(&mut self, at: Instant) {
+        self.pending_input.get_or_insert((at, false));
+    }
+
+    /// The active tab's IO thread read output at `at`; the next frame
+    /// shows the echo of any input sent before it.
+    pub fn on_pty_output(&mut self, at: Instant) {
+        if let Some((input, echoed)) = self.pending_input.as_mut() {
+            *echoed |= at >= *input;
+        }
+    }
+
+    /// `total` is the running byte count across all tabs' IO threads.
+    pub fn record_pty_bytes(&mut self, now: Instant, total: u64) {
+        push_capped(&mut self.pty_samples, (now, total));
+    }
+
+    fn pty_bytes_per_sec(&self) -> Option<f64> {
+        let (first_at, first) = self.pty_samples.front()?;
+        let (last_at, last) = self.pty_samples.back()?;
+        let secs = last_at.duration_since(*first_at).as_secs_f64();
+        (secs > 0.0).then(|| last.saturating_sub(*first) as f64 / secs)
+    }
+
+    /// Latency HUD: frame timings, then `in` (input → frame), PTY read
+    /// throughput and glyph atlas fill, each only once it has data.
+    pub fn latency_hud_text(&self, atlas_fill: f32) -> String {
+        let mut parts: Vec<String> = self.hud_text().into_iter().collect();
+        if !self.input_latencies.is_empty() {
+            let avg = self.input_latencies.iter().sum::<Duration>() / self.input_latencies.len() as u32;
+            parts.push(format!("in {:.1}ms", avg.as_secs_f64() * 1000.0));
+        }
+        if let Some(rate) = self.pty_bytes_per_sec() {
+            parts.push(format!("pty {}", format_rate(rate)));
+        }
+        parts.push(format!("atlas {:.0}%", atlas_fill * 100.0));
+        parts.join(" ")
+    }
+
+    /// `16.7ms 60fps draw 1.2ms`: average frame interval, rate and draw time.
+    pub fn hud_text(&self) -> Option<String> {
+        let (first, _) = self.frames.front()?;
+        let (last, _) = self.frames.back()?;
+        let intervals = self.frames.len() - 1;
+        if intervals == 0 {
+            return None;
+        }
+        let frame = last.duration_since(*first).as_secs_f64() / intervals as f64;
+        let draw = self.frames.iter().map(|(_, d)| d.as_secs_f64()).sum::<f64>() / self.frames.len() as f64;
+        let fps = if frame > 0.0 { 1.0 / frame } else { 0.0 };
+        Some(format!("{:.1}ms {:.0}fps draw {:.1}ms", frame * 1000.0, fps, draw * 1000.0))
+    }
+}
+
+fn format_rate(bytes_per_sec: f64) -> String {
+    if bytes_per_sec >= 1_000_000.0 {
+        format!("{:.1}MB/s", bytes_per_sec / 1_000_000.0)
+    } else if bytes_per_sec >= 1_000.0 {
+        format!("{:.0}KB/s", bytes_per_sec / 1_000.0)
+    } else {
+        format!("{:.0}B/s", bytes_per_sec)
+    }
+}
+
+fn frame_interval(max_fps: u32) -> Option<Duration> {
+    (max_fps > 0).then(|| Duration::from_secs(1) / max_fps)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn stats_average_interval_and_draw_time() {
+        let mut stats = FrameStats::default();
+        let t0 = Instant::now();
+        stats.record(t0, Duration::from_millis(1));
+        assert_eq!(stats.hud_text(), None);
+        stats.record(t0 + Duration::from_millis(10), Duration::from_millis(3));
+        stats.record(t0 + Duration::from_millis(20), Duration::from_millis(2));
+        assert_eq!(stats.hud_text().as_deref(), Some("10.0ms 100fps draw 2.0ms"));
+    }
+
+    #[test]
+    fn stats_keep_recent_frames_only() {
+        let mut stats = FrameStats::default();
+        let t0 = Instant::now();
+        for i in 0..STATS_WINDOW as u64 * 2 {
+            let interval = if i < STATS_WINDOW as u64 { 100 } else { 20 };
+            stats.record(t0 + Duration::from_millis(i * interval), Duration::ZERO);
+        }
+        assert_eq!(stats.frames.len(), STATS_WINDOW);
+        assert!(stats.hud_text().unwrap().starts_with("20.0ms 50fps"));
+    }
+
+    #[test]
+    fn input_latency_measured_to_next_frame() {
+        let mut stats = FrameStats::default();
+        let t0 = Instant::now();
+        stats.on_input(t0);
+        stats.on_input(t0 + Duration::from_millis(2)); // first input of the frame counts
+        stats.record(t0 + Duration::from_millis(5), Duration::from_millis(1)); // before echo
+        assert!(stats.input_latencies.is_empty());
+        stats.on_pty_output(t0 + Duration::from_millis(8));
+        stats.record(t0 + Duration::from_millis(15), Duration::from_millis(1));
+        stats.record(t0 + Duration::from_millis(25), Duration::from_millis(1)); // no input
+        assert_eq!(stats.input_latencies, [Duration::from_millis(16)]);
+        assert_eq!(stats.latency_hud_text(0.25), "10.0ms 100fps draw 1.0ms in 16.0ms atlas 25%");
+    }
+
+    #[test]
+    fn unechoed_input_is_dropped() {
+        let mut stats = FrameStats::default();
+        let t0 = Instant::now();
+        stats.on_input(t0);
+        stats.on_pty_output(t0 - Duration::from_millis(1)); // output from before the key
+        stats.record(t0 + INPUT_ECHO_TIMEOUT * 2, Duration::ZERO);
+        stats.on_pty_output(t0 + INPUT_ECHO_TIMEOUT * 3);
+        stats.record(t0 + INPUT_ECHO_TIMEOUT * 3, Duration::ZERO);
+        assert!(stats.input_latencies.is_empty());
+    }
+
+    #[test]
+    fn pty_throughput_over_samples() {
+        let mut stats = FrameStats::default();
+        let t0 = Instant::now();
+        assert_eq!(stats.latency_hud_text(0.0), "atlas 0%");
+        stats.record_pty_bytes(t0, 1_000);
+        stats.record_pty_bytes(t0 + Duration::from_millis(500), 601_000);
+        assert_eq!(stats.latency_hud_text(0.0), "pty 1.2MB/s atlas 0%");
+        // A closed tab lowers the total; never report a negative rate
+        stats.record_pty_bytes(t0 + Duration::from_secs(1), 0);
+        assert_eq!(stats.latency_hud_text(0.0), "pty 0B/s atlas 0%");
+    }
+
+    #[test]
+    fn first_frame_draws() {
+        let s = FrameScheduler::new(120, true);
+        assert_eq!(s.decide(Instant::now()), FrameDecision::Draw);
+    }
+
+    #[test]
+    fn frame_within_interval_is_deferred() {
+        let mut s = FrameScheduler::new(100, true);
+        let t0 = Instant::now();
+        s.frame_drawn(t0);
+        assert_eq!(
+            s.decide(t0 + Duration::from_millis(3)),
+            FrameDecision::Defer(t0 + Duration::from_millis(10))
+        );
+        assert_eq!(s.decide(t0 + Duration::from_millis(10)), FrameDecision::Draw);
+    }
+
+    #[test]
+    fn input_bypasses_pacing_in_low_latency_mode() {
+        let mut s = FrameScheduler::new(100, true);
+        let t0 = Instant::now();
+        s.frame_drawn(t0);
+        s.on_input();
+        assert_eq!(s.decide(t0 + Duration::from_millis(1)), FrameDecision::Draw);
+        s.frame_drawn(t0 + Duration::from_millis(1));
+        assert!(matches!(s.decide(t0 + Duration::from_millis(2)), FrameDecision::Defer(_)));
+    }
+
+    #[test]
+    fn input_is_paced_without_low_latency() {
+        let mut s = FrameScheduler::new(100, false);
+        let t0 = Instant::now();
+        s.frame_drawn(t0);
+        s.on_input();
+        assert!(matches!(s.decide(t0 + Duration::from_millis(1)), FrameDecision::Defer(_)));
+    }
+
+    #[test]
+    fn zero_fps_disables_pacing() {
+        let mut s = FrameScheduler::new(0, false);
+        let t0 = Instant::now();
+        s.frame_drawn(t0);
+        assert_eq!(s.decide(t0), FrameDecision::Draw);
+    }
+}
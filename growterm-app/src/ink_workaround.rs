@@ -6,6 +6,7 @@ use growterm_types::{Cell, CellFlags};
 
 const CLAUDE_PROCESS_NAME: &str = "claude";
 
+#[derive(Debug, Clone)]
 pub struct InkImeState {
     ink_app_cached: Option<bool>,
     trailing_spaces: u16,
@@ -78,7 +79,7 @@ impl InkImeState {
     /// Ink renders its cursor as an INVERSE cell. We find that cell in the
     /// input area and use its position. Falls back to cell content scan
     /// with trailing space tracking when no INVERSE cell is found.
-    pub fn preedit_pos(&self, cells: &[Vec<Cell>]) -> Option<(u16, u16)> {
+    pub fn preedit_pos<R: AsRef<[Cell]>>(&self, cells: &[R]) -> Option<(u16, u16)> {
         if !self.is_active() {
             return None;
         }
@@ -97,9 +98,9 @@ impl InkImeState {
 }
 
 /// Find Ink's cursor (INVERSE cell) in the input area.
-fn find_ink_cursor(cells: &[Vec<Cell>], prompt_row: usize, bottom: usize) -> Option<(u16, u16)> {
+fn find_ink_cursor<R: AsRef<[Cell]>>(cells: &[R], prompt_row: usize, bottom: usize) -> Option<(u16, u16)> {
     for row_idx in (prompt_row..=bottom).rev() {
-        for (col, cell) in cells[row_idx].iter().enumerate() {
+        for (col, cell) in cells[row_idx].as_ref().iter().enumerate() {
             if cell.flags.contains(CellFlags::INVERSE) {
                 return Some((row_idx as u16, col as u16));
             }
@@ -109,9 +110,10 @@ fn find_ink_cursor(cells: &[Vec<Cell>], prompt_row: usize, bottom: usize) -> Opt
 }
 
 /// Find the row index of the last input row (before the next separator).
-pub fn find_input_bottom(cells: &[Vec<Cell>], prompt_row: usize) -> usize {
+pub fn find_input_bottom<R: AsRef<[Cell]>>(cells: &[R], prompt_row: usize) -> usize {
     for row_idx in (prompt_row + 1)..cells.len() {
         if cells[row_idx]
+            .as_ref()
             .first()
             .map_or(false, |c| c.character == '─')
         {
@@ -123,12 +125,12 @@ pub fn find_input_bottom(cells: &[Vec<Cell>], prompt_row: usize) -> usize {
 
 /// Scan from prompt_row to bottom to find the position just after the last
 /// non-blank cell.
-fn find_input_end(cells: &[Vec<Cell>], prompt_row: usize, bottom: usize) -> (u16, u16) {
+fn find_input_end<R: AsRef<[Cell]>>(cells: &[R], prompt_row: usize, bottom: usize) -> (u16, u16) {
     let mut last_row = prompt_row;
     let mut last_col_end: usize = 0;
 
     for row_idx in prompt_row..=bottom {
-        let row = &cells[row_idx];
+        let row = cells[row_idx].as_ref();
         for (col, cell) in row.iter().enumerate() {
             if cell.character != ' ' && cell.character != '\0' {
                 last_row = row_idx;
@@ -187,20 +189,20 @@ fn has_descendant_named(root_pid: u32, name: &str) -> bool {
 }
 
 /// Find the prompt row (❯) between two separator lines (─) in the grid.
-pub fn find_prompt_row(cells: &[Vec<Cell>]) -> Option<usize> {
+pub fn find_prompt_row<R: AsRef<[Cell]>>(cells: &[R]) -> Option<usize> {
     let is_separator = |row: &[Cell]| -> bool {
         row.first().map_or(false, |c| c.character == '─')
     };
     let separators: Vec<usize> = cells
         .iter()
         .enumerate()
-        .filter(|(_, row)| is_separator(row))
+        .filter(|(_, row)| is_separator(row.as_ref()))
         .map(|(i, _)| i)
         .collect();
     // Check after the last separator first (new prompt may lack bottom separator)
     if let Some(&last_sep) = separators.last() {
         for row_idx in (last_sep + 1)..cells.len() {
-            if cells[row_idx].iter().any(|c| c.character == '❯') {
+            if cells[row_idx].as_ref().iter().any(|c| c.character == '❯') {
                 return Some(row_idx);
             }
         }
@@ -209,7 +211,7 @@ pub fn find_prompt_row(cells: &[Vec<Cell>]) -> Option<usize> {
     for window in separators.windows(2).rev() {
         let (top, bottom) = (window[0], window[1]);
         for row_idx in (top + 1)..bottom {
-            if cells[row_idx].iter().any(|c| c.character == '❯') {
+            if cells[row_idx].as_ref().iter().any(|c| c.character == '❯') {
                 return Some(row_idx);
             }
         }
@@ -0,0 +1,295 @@
+//! `growterm cli ...`가 실행 중인 growTerm을 제어하는 유닉스 소켓.
+//!
+//! 요청·응답은 한 줄짜리 JSON. 소켓은 프로세스마다 하나이고, 요청은
+//! 마지막으로 포커스를 받은 윈도우의 앱 루프가 리드로우 때 처리함.
+
+use std::io::{BufRead, BufReader, Write};
+use std::os::unix::net::{UnixListener, UnixStream};
+use std::path::{Path, PathBuf};
+use std::sync::{mpsc, Arc, Mutex, Weak};
+use std::time::Duration;
+
+use growterm_window::TerminalWindow;
+use serde::{Deserialize, Serialize};
+
+/// Shells started by growTerm get this, so `growterm cli` inside a tab
+/// talks to the instance that owns the tab.
+pub const SOCKET_ENV: &str = "GROWTERM_SOCKET";
+const REPLY_TIMEOUT: Duration = Duration::from_secs(5);
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "cmd", rename_all = "kebab-case")]
+pub enum Request {
+    NewTab {
+        window: Option<u64>,
+        cwd: Option<String>,
+        command: Option<String>,
+    },
+    SendText {
+        window: Option<u64>,
+        tab: Option<u64>,
+        text: String,
+    },
+    ListTabs,
+    Activate {
+        window: Option<u64>,
+        tab: Option<u64>,
+    },
+}
+
+impl Request {
+    fn window(&self) -> Option<u64> {
+        match self {
+            Request::NewTab { window, .. }
+            | Request::SendText { window, .. }
+            | Request::Activate { window, .. } => *window,
+            Request::ListTabs => None,
+        }
+    }
+}
+
+/// `output` is printed by the CLI; on failure it is the error message.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Response {
+    pub ok: bool,
+    pub output: String,
+}
+
+impl Response {
+    pub fn ok(output: impl Into<String>) -> Self {
+        Self { ok: true, output: output.into() }
+    }
+
+    pub fn error(message: impl Into<String>) -> Self {
+        Self { ok: false, output: message.into() }
+    }
+}
+
+/// Requests waiting for one window's app loop.
+pub struct Inbox {
+    pub id: u64,
+    window: Arc<dyn TerminalWindow>,
+    pending: Mutex<Vec<(Request, mpsc::Sender<Response>)>>,
+}
+
+impl Inbox {
+    pub fn take(&self) -> Vec<(Request, mpsc::Sender<Response>)> {
+        std::mem::take(&mut *self.pending.lock().unwrap())
+    }
+
+    fn deliver(&self, request: Request) -> Response {
+        let (tx, rx) = mpsc::channel();
+        self.pending.lock().unwrap().push((request, tx));
+        self.window.request_redraw();
+        rx.recv_timeout(REPLY_TIMEOUT)
+            .unwrap_or_else(|_| Response::error("window did not respond"))
+    }
+}
+
+struct Registry {
+    windows: Vec<Weak<Inbox>>,
+    focused: Option<u64>,
+    next_id: u64,
+}
+
+static REGISTRY: Mutex<Registry> = Mutex::new(Registry {
+    windows: Vec::new(),
+    focused: None,
+    next_id: 1,
+});
+
+/// Register a window; it stops receiving requests once the inbox is dropped.
+pub fn register(window: Arc<dyn TerminalWindow>) -> Arc<Inbox> {
+    let mut registry = REGISTRY.lock().unwrap();
+    let inbox = Arc::new(Inbox {
+        id: registry.next_id,
+        window,
+        pending: Mutex::new(Vec::new()),
+    });
+    registry.next_id += 1;
+    registry.windows.retain(|w| w.strong_count() > 0);
+    registry.windows.push(Arc::downgrade(&inbox));
+    registry.focused = Some(inbox.id);
+    inbox
+}
+
+pub fn set_focused(id: u64) {
+    REGISTRY.lock().unwrap().focused = Some(id);
+}
+
+fn live_windows() -> (Vec<Arc<Inbox>>, Option<u64>) {
+    let registry = REGISTRY.lock().unwrap();
+    let windows = registry.windows.iter().filter_map(Weak::upgrade).collect();
+    (windows, registry.focused)
+}
+
+fn dispatch(request: Request) -> Response {
+    let (windows, focused) = live_windows();
+    if request == Request::ListTabs {
+        let mut output = String::new();
+        for inbox in &windows {
+            let response = inbox.deliver(Request::ListTabs);
+            if !response.ok {
+                return response;
+            }
+            output.push_str(&response.output);
+        }
+        return Response::ok(output);
+    }
+    let target = match request.window() {
+        Some(id) => windows.iter().find(|w| w.id == id),
+        None => focused
+            .and_then(|id| windows.iter().find(|w| w.id == id))
+            .or(windows.last()),
+    };
+    match target {
+        Some(inbox) => inbox.deliver(request),
+        None => Response::error(match request.window() {
+            Some(id) => format!("no window {id}"),
+            None => "no open window".to_string(),
+        }),
+    }
+}
+
+/// macOS: ~/Library/Application Support/growterm, 그 외: $XDG_RUNTIME_DIR/growterm
+pub fn socket_dir() -> PathBuf {
+    if cfg!(target_os = "macos") {
+        let home = std::env::var("HOME").unwrap_or_else(|_| ".".to_string());
+        return PathBuf::from(home).join("Library/Application Support/growterm");
+    }
+    match std::env::var_os("XDG_RUNTIME_DIR") {
+        Some(dir) => PathBuf::from(dir).join("growterm"),
+        None => crate::config::config_dir(),
+    }
+}
+
+/// Listen on `gui-<pid>.sock` and export its path to child shells.
+pub fn serve() {
+    use std::os::unix::fs::PermissionsExt;
+
+    let dir = socket_dir();
+    if let Err(e) = std::fs::create_dir_all(&dir) {
+        eprintln!("growterm: cannot create {}: {e}", dir.display());
+        return;
+    }
+    let _ = std::fs::set_permissions(&dir, std::fs::Permissions::from_mode(0o700));
+    remove_stale_sockets(&dir);
+    let path = dir.join(format!("gui-{}.sock", std::process::id()));
+    let _ = std::fs::remove_file(&path);
+    let listener = match UnixListener::bind(&path) {
+        Ok(l) => l,
+        Err(e) => {
+            eprintln!("growterm: cannot listen on {}: {e}", path.display());
+            return;
+        }
+    };
+    std::env::set_var(SOCKET_ENV, &path);
+    std::thread::spawn(move || {
+        for stream in listener.incoming().flatten() {
+            std::thread::spawn(move || handle_client(stream));
+        }
+    });
+}
+
+fn handle_client(stream: UnixStream) {
+    let mut line = String::new();
+    if BufReader::new(&stream).read_line(&mut line).is_err() {
+        return;
+    }
+    let response = match serde_json::from_str::<Request>(&line) {
+        Ok(request) => dispatch(request),
+        Err(e) => Response::error(format!("bad request: {e}")),
+    };
+    if let Ok(json) = serde_json::to_string(&response) {
+        let mut stream = &stream;
+        let _ = writeln!(stream, "{json}");
+    }
+}
+
+/// 죽은 프로세스가 남긴 소켓 정리
+fn remove_stale_sockets(dir: &Path) {
+    for path in sockets_in(dir) {
+        if UnixStream::connect(&path).is_err() {
+            let _ = std::fs::remove_file(&path);
+        }
+    }
+}
+
+fn sockets_in(dir: &Path) -> Vec<PathBuf> {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    entries
+        .flatten()
+        .map(|e| e.path())
+        .filter(|p| {
+            p.file_name()
+                .and_then(|n| n.to_str())
+                .is_some_and(|n| n.starts_with("gui-") && n.ends_with(".sock"))
+        })
+        .collect()
+}
+
+/// `$GROWTERM_SOCKET` if set, otherwise the most recently started instance.
+fn connect() -> Result<UnixStream, String> {
+    if let Some(path) = std::env::var_os(SOCKET_ENV) {
+        if let Ok(stream) = UnixStream::connect(&path) {
+            return Ok(stream);
+        }
+    }
+    let mut sockets: Vec<(std::time::SystemTime, PathBuf)> = sockets_in(&socket_dir())
+        .into_iter()
+        .filter_map(|p| Some((std::fs::metadata(&p).ok()?.modified().ok()?, p)))
+        .collect();
+    sockets.sort();
+    sockets
+        .iter()
+        .rev()
+        .find_map(|(_, p)| UnixStream::connect(p).ok())
+        .ok_or_else(|| "no running growTerm found".to_string())
+}
+
+/// Client side of `growterm cli`.
+pub fn send(request: &Request) -> Result<Response, String> {
+    let mut stream = connect()?;
+    let json = serde_json::to_string(request).map_err(|e| e.to_string())?;
+    writeln!(stream, "{json}").map_err(|e| e.to_string())?;
+    let mut line = String::new();
+    BufReader::new(&stream)
+        .read_line(&mut line)
+        .map_err(|e| e.to_string())?;
+    serde_json::from_str(&line).map_err(|e| format!("bad response: {e}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn request_json_is_tagged_by_command() {
+        let request = Request::SendText {
+            window: None,
+            tab: Some(2),
+            text: "ls\n".into(),
+        };
+        let json = serde_json::to_string(&request).unwrap();
+        assert_eq!(json, r#"{"cmd":"send-text","window":null,"tab":2,"text":"ls\n"}"#);
+        assert_eq!(serde_json::from_str::<Request>(&json).unwrap(), request);
+        assert_eq!(
+            serde_json::from_str::<Request>(r#"{"cmd":"list-tabs"}"#).unwrap(),
+            Request::ListTabs
+        );
+    }
+
+    #[test]
+    fn missing_optional_fields_default_to_none() {
+        let request: Request = serde_json::from_str(r#"{"cmd":"activate"}"#).unwrap();
+        assert_eq!(request, Request::Activate { window: None, tab: None });
+    }
+
+    #[test]
+    fn dispatch_without_windows_fails() {
+        let response = dispatch(Request::Activate { window: Some(999), tab: None });
+        assert_eq!(response, Response::error("no window 999"));
+    }
+}
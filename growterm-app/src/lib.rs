@@ -1,9 +1,29 @@
+pub mod banner;
+pub mod bell;
+pub mod cli;
+pub mod click_to_move;
+pub mod command_duration;
 pub mod config;
+pub mod config_watch;
 pub mod copy_mode;
+pub mod diagnostics;
+pub mod foreground;
+pub mod frame_buffer;
+pub mod frame_pacing;
 pub mod ink_workaround;
+pub mod ipc;
+pub mod link_scan;
+pub mod output_marks;
+pub mod output_throttle;
+pub mod paste;
 pub mod pomodoro;
 pub mod response_timer;
+pub mod screenshot;
 pub mod selection;
+pub mod session;
+pub mod smooth_scroll;
+pub mod ssh;
 pub mod tab;
+pub mod tab_activity;
 pub mod url;
 pub mod zoom;
@@ -0,0 +1,184 @@
+//! 보이는 화면의 URL/파일 경로를 탭별 스레드에서 미리 찾아 둠.
+//! 화면이 바뀌면 다시 스캔하고, Cmd+hover와 상시 밑줄은 결과를 찾아보기만 함.
+
+use std::sync::mpsc;
+use std::sync::{Arc, Mutex, MutexGuard};
+
+use growterm_grid::Row;
+use growterm_types::Cell;
+
+use crate::selection;
+use crate::url::{self, Link};
+
+/// The screen a scan was made from: grid generation and the absolute row
+/// at the top of the view.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ViewKey {
+    pub generation: u64,
+    pub view_base: u32,
+}
+
+/// A link on one row, in cells; `end` is exclusive.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LinkSpan {
+    pub start: u16,
+    pub end: u16,
+    pub link: Link,
+}
+
+/// Links of every row in view, indexed by screen row.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LinkIndex {
+    pub key: ViewKey,
+    rows: Vec<Vec<LinkSpan>>,
+}
+
+impl LinkIndex {
+    pub fn at(&self, abs_row: u32, col: u16) -> Option<&LinkSpan> {
+        let row = abs_row.checked_sub(self.key.view_base)? as usize;
+        self.rows.get(row)?.iter().find(|span| col >= span.start && col < span.end)
+    }
+
+    /// (screen row, span) for every link in view
+    pub fn spans(&self) -> impl Iterator<Item = (u16, &LinkSpan)> {
+        self.rows
+            .iter()
+            .enumerate()
+            .flat_map(|(row, spans)| spans.iter().map(move |span| (row as u16, span)))
+    }
+}
+
+pub fn scan_row(line: &[Cell]) -> Vec<LinkSpan> {
+    let text = selection::collect_line_text(line);
+    url::find_all_links(&text)
+        .into_iter()
+        .map(|(start, end, link)| LinkSpan {
+            start: selection::char_index_to_cell_col(line, start) as u16,
+            end: selection::char_index_to_cell_col(line, end) as u16,
+            link,
+        })
+        .collect()
+}
+
+pub fn scan(key: ViewKey, rows: &[Row]) -> LinkIndex {
+    LinkIndex { key, rows: rows.iter().map(|row| scan_row(row)).collect() }
+}
+
+/// Per-tab scanner thread. The renderer hands it each new screen; the
+/// thread skips screens that are already stale and keeps the latest index.
+pub struct LinkScanner {
+    requests: mpsc::Sender<(ViewKey, Vec<Row>)>,
+    requested: Mutex<Option<ViewKey>>,
+    index: Arc<Mutex<Option<LinkIndex>>>,
+}
+
+impl LinkScanner {
+    /// `on_change` runs on the scanner thread when the links in view change.
+    pub fn new(on_change: impl Fn() + Send + 'static) -> Self {
+        let (requests, rx) = mpsc::channel::<(ViewKey, Vec<Row>)>();
+        let index = Arc::new(Mutex::new(None));
+        let shared = Arc::clone(&index);
+        // 탭이 닫혀 Sender가 사라지면 recv가 실패하고 스레드 종료
+        std::thread::spawn(move || {
+            while let Ok(mut request) = rx.recv() {
+                while let Ok(newer) = rx.try_recv() {
+                    request = newer;
+                }
+                let (key, rows) = request;
+                let scanned = scan(key, &rows);
+                let mut index = shared.lock().unwrap();
+                let changed = !matches!(index.as_ref(), Some(old) if old.rows == scanned.rows);
+                *index = Some(scanned);
+                drop(index);
+                if changed {
+                    on_change();
+                }
+            }
+        });
+        Self { requests, requested: Mutex::new(None), index }
+    }
+
+    /// Queue `rows` for scanning unless this screen was already sent.
+    pub fn request(&self, key: ViewKey, rows: &[Row]) {
+        let mut requested = self.requested.lock().unwrap();
+        if *requested == Some(key) {
+            return;
+        }
+        *requested = Some(key);
+        let _ = self.requests.send((key, rows.to_vec()));
+    }
+
+    /// Latest scan; check its `key` against the screen before trusting it.
+    pub fn index(&self) -> MutexGuard<'_, Option<LinkIndex>> {
+        self.index.lock().unwrap()
+    }
+
+    /// The link at (abs_row, col) on the screen `key`: from the index when
+    /// it is current, otherwise by scanning just that row.
+    pub fn link_at(&self, key: ViewKey, abs_row: u32, col: u16, line: impl FnOnce() -> Vec<Cell>) -> Option<LinkSpan> {
+        if let Some(index) = self.index().as_ref().filter(|index| index.key == key) {
+            return index.at(abs_row, col).cloned();
+        }
+        scan_row(&line()).into_iter().find(|span| col >= span.start && col < span.end)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    fn row(text: &str) -> Row {
+        let mut cells: Vec<Cell> = text.chars().map(|c| Cell { character: c, ..Cell::default() }).collect();
+        cells.resize(40, Cell::default());
+        cells.into()
+    }
+
+    const KEY: ViewKey = ViewKey { generation: 3, view_base: 10 };
+
+    #[test]
+    fn index_finds_link_by_absolute_row_and_cell() {
+        let index = scan(KEY, &[row("plain"), row("go to https://x.io now")]);
+        let span = index.at(11, 8).unwrap();
+        assert_eq!((span.start, span.end), (6, 18));
+        assert_eq!(span.link, Link::Url("https://x.io".to_string()));
+        assert!(index.at(11, 18).is_none());
+        assert!(index.at(10, 8).is_none());
+        assert!(index.at(9, 8).is_none());
+        assert_eq!(index.spans().map(|(row, _)| row).collect::<Vec<_>>(), vec![1]);
+    }
+
+    #[test]
+    fn wide_characters_shift_link_cells() {
+        let mut cells = vec![Cell { character: '한', flags: growterm_types::CellFlags::WIDE_CHAR, ..Cell::default() }];
+        cells.push(Cell::default());
+        cells.extend(" src/a.rs:1".chars().map(|c| Cell { character: c, ..Cell::default() }));
+        let spans = scan_row(&cells);
+        assert_eq!(spans.len(), 1);
+        assert_eq!((spans[0].start, spans[0].end), (3, 13));
+    }
+
+    #[test]
+    fn stale_index_falls_back_to_scanning_the_row() {
+        let scanner = LinkScanner::new(|| {});
+        *scanner.index.lock().unwrap() = Some(scan(KEY, &[row("https://old.io")]));
+        let newer = ViewKey { generation: 4, ..KEY };
+        let span = scanner.link_at(newer, 10, 5, || row("see ./new/file.txt").to_vec()).unwrap();
+        assert_eq!((span.start, span.end), (4, 18));
+        assert_eq!(scanner.link_at(KEY, 10, 2, Vec::new).map(|s| s.link), Some(Link::Url("https://old.io".to_string())));
+    }
+
+    #[test]
+    fn scanner_thread_publishes_index_and_reports_change() {
+        let (tx, rx) = mpsc::channel();
+        let scanner = LinkScanner::new(move || {
+            let _ = tx.send(());
+        });
+        scanner.request(KEY, &[row("https://x.io")]);
+        rx.recv_timeout(Duration::from_secs(5)).unwrap();
+        assert_eq!(scanner.index().as_ref().map(|index| index.key), Some(KEY));
+        // 같은 화면은 다시 보내지 않음
+        scanner.request(KEY, &[row("https://x.io")]);
+        assert!(rx.recv_timeout(Duration::from_millis(50)).is_err());
+    }
+}
@@ -1,32 +1,161 @@
 mod app;
+mod banner;
+mod bell;
+mod cli;
+mod click_to_move;
+mod command_duration;
 mod config;
+mod config_watch;
 mod copy_mode;
+mod diagnostics;
+mod foreground;
+mod frame_buffer;
+mod frame_pacing;
 mod ink_workaround;
+mod ipc;
+mod link_scan;
+mod output_marks;
+mod output_throttle;
+mod paste;
 mod pomodoro;
 mod response_timer;
+mod screenshot;
 #[allow(dead_code)]
 mod selection;
+mod session;
+mod smooth_scroll;
+mod ssh;
 mod tab;
+mod tab_activity;
 mod url;
 mod zoom;
 
+#[cfg(target_os = "macos")]
+use growterm_macos as backend;
+#[cfg(not(target_os = "macos"))]
+use growterm_winit as backend;
+use growterm_window::TerminalWindow;
+
 fn main() {
+    let args = match cli::CliArgs::parse(std::env::args().skip(1)) {
+        Ok(args) => args,
+        Err(e) => {
+            eprintln!("growterm: {e}\n\n{}", cli::USAGE);
+            std::process::exit(2);
+        }
+    };
+    if args.help {
+        print!("{}", cli::USAGE);
+        return;
+    }
+    if let Some(request) = &args.remote {
+        match ipc::send(request) {
+            Ok(response) if response.ok => print!("{}", response.output),
+            Ok(response) => {
+                eprintln!("growterm: {}", response.output);
+                std::process::exit(1);
+            }
+            Err(e) => {
+                eprintln!("growterm: {e}");
+                std::process::exit(1);
+            }
+        }
+        return;
+    }
+    if let Some(path) = args.config.clone() {
+        config::set_config_path(path);
+    }
     let config = config::Config::load();
-    let font_size = config.font_size;
+    // 어느 경로로 종료하든 모든 윈도우의 탭을 한 번에 저장
+    growterm_window::set_before_exit(session::save_on_exit);
+    // 첫 실행 시 ~/.terminfo에 growterm 항목 설치. 실패하면 TERM=xterm-256color
+    if let Err(e) = growterm_pty::install_terminfo() {
+        eprintln!("growterm: terminfo not installed: {e}");
+    }
+    // 첫 윈도우의 첫 탭에만 적용
+    let launch = match args.launch_profile(&config.profiles) {
+        Ok(launch) => launch,
+        Err(e) => {
+            eprintln!("growterm: {e}");
+            std::process::exit(2);
+        }
+    };
+    let font_size = launch.as_ref().and_then(|p| p.font_size).unwrap_or(config.font_size);
     let font_family = config.font_family.clone();
 
     let window_size = config.window_size();
     let window_position = config.window_position();
+    let first_window = std::cell::Cell::new(true);
+    // 세션은 프로세스의 첫 윈도우들만 복원. 명령행으로 첫 탭을 지정하면 복원하지 않음
+    let restore = if config.restore_session && launch.is_none() {
+        session::Session::take_all()
+    } else {
+        Vec::new()
+    };
+    let restore = std::cell::RefCell::new(std::collections::VecDeque::from(restore));
+    let launch = std::cell::Cell::new(launch);
+
+    backend::run(window_size, window_position, move |window, rx| {
+        // 같은 프로세스에서 나중에 연 윈도우는 최신 설정을 씀
+        let first = first_window.replace(false);
+        let config = if first { config.clone() } else { config::Config::load() };
+        let launch = launch.take();
+        let font_size = if first { font_size } else { config.font_size };
+        // 저장된 윈도우마다 하나씩 넘겨줌. 나중에 Cmd+N으로 연 윈도우는 새 쉘로 시작
+        let session = restore.borrow_mut().pop_front();
+        if first {
+            for _ in 0..restore.borrow().len() {
+                if !window.open_window(false) {
+                    restore.borrow_mut().clear();
+                    break;
+                }
+            }
+        }
 
-    growterm_macos::run(window_size, window_position, move |window, rx| {
         // GpuDrawer must be created on the main thread (Metal requirement)
         let (width, height) = window.inner_size();
         let font_path = resolve_font_path(&font_family);
-        let drawer = growterm_gpu_draw::GpuDrawer::new(window.clone(), width, height, font_size, font_path.as_deref());
-
-        let config = config.clone();
+        let gpu = match config.renderer {
+            config::RendererKind::Gpu => {
+                growterm_gpu_draw::GpuDrawer::try_new(window.clone(), width, height, font_size, font_path.as_deref())
+            }
+            config::RendererKind::Software => None,
+        };
+        let drawer: Box<dyn growterm_gpu_draw::Renderer + Send> = match gpu {
+            Some(gpu) => Box::new(gpu),
+            None if !window.can_present_pixels() => {
+                // 그릴 곳 없는 소프트웨어 렌더러로 빈 창을 띄우지 않음
+                match config.renderer {
+                    config::RendererKind::Gpu => eprintln!(
+                        "growterm: GPU renderer unavailable, and this platform can't display software-rendered frames"
+                    ),
+                    config::RendererKind::Software => eprintln!(
+                        "growterm: renderer = \"software\" is not supported on this platform; use renderer = \"gpu\""
+                    ),
+                }
+                std::process::exit(1);
+            }
+            None => {
+                if config.renderer == config::RendererKind::Gpu {
+                    eprintln!("growterm: GPU renderer unavailable, falling back to software rendering");
+                }
+                let target = window.clone();
+                Box::new(
+                    growterm_gpu_draw::SoftwareRenderer::new(width, height, font_size, font_path.as_deref())
+                        .with_presenter(Box::new(move |rgba, w, h| target.present_pixels(rgba, w, h))),
+                )
+            }
+        };
+        if first {
+            // .app 번들로 다시 실행된 뒤에 소켓을 엶
+            ipc::serve();
+            if config.fullscreen {
+                window.set_fullscreen(true);
+            }
+        }
+        let window: std::sync::Arc<dyn TerminalWindow> = window;
         std::thread::spawn(move || {
-            app::run(window, rx, drawer, config);
+            app::run(window, rx, drawer, config, launch, session);
         });
     });
 }
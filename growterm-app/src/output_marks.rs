@@ -0,0 +1,97 @@
+use growterm_types::ShellMark;
+
+/// Where the last command's output sits, from OSC 133 C (output start)
+/// and D (command finished). Positions are (absolute row, col) like
+/// selections, taken from the cursor when each mark arrives.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct OutputMarks {
+    pending: Option<(u32, u16)>,
+    /// Output start, and the position just past its last cell
+    last: Option<((u32, u16), (u32, u16))>,
+}
+
+impl OutputMarks {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn on_mark(&mut self, mark: ShellMark, cursor: (u32, u16)) {
+        match mark {
+            ShellMark::OutputStart => self.pending = Some(cursor),
+            // D를 보내지 않는 쉘은 다음 프롬프트(A)에서 닫음
+            ShellMark::CommandFinished(_) | ShellMark::PromptStart => {
+                if let Some(start) = self.pending.take() {
+                    self.last = Some((start, cursor));
+                }
+            }
+            ShellMark::CommandStart => {}
+        }
+    }
+
+    /// Cmd+K: the rows the marks point at are gone.
+    pub fn clear(&mut self) {
+        *self = Self::default();
+    }
+
+    /// Inclusive selection bounds of the last command's output, or None
+    /// when it printed nothing. `cols` is the grid width, for output that
+    /// ends with a newline.
+    pub fn last_output(&self, cols: u16) -> Option<((u32, u16), (u32, u16))> {
+        let (start, (end_row, end_col)) = self.last?;
+        let end = if end_col > 0 {
+            (end_row, end_col - 1)
+        } else {
+            (end_row.checked_sub(1)?, cols.saturating_sub(1))
+        };
+        (end >= start).then_some((start, end))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn output_ending_in_newline_stops_at_previous_row() {
+        let mut marks = OutputMarks::new();
+        marks.on_mark(ShellMark::OutputStart, (4, 0));
+        marks.on_mark(ShellMark::CommandFinished(Some(0)), (7, 0));
+        assert_eq!(marks.last_output(80), Some(((4, 0), (6, 79))));
+    }
+
+    #[test]
+    fn output_without_trailing_newline_ends_before_cursor() {
+        let mut marks = OutputMarks::new();
+        marks.on_mark(ShellMark::OutputStart, (4, 0));
+        marks.on_mark(ShellMark::CommandFinished(None), (5, 3));
+        assert_eq!(marks.last_output(80), Some(((4, 0), (5, 2))));
+    }
+
+    #[test]
+    fn command_without_output_has_nothing_to_select() {
+        let mut marks = OutputMarks::new();
+        marks.on_mark(ShellMark::OutputStart, (4, 0));
+        marks.on_mark(ShellMark::CommandFinished(Some(0)), (4, 0));
+        assert_eq!(marks.last_output(80), None);
+    }
+
+    #[test]
+    fn prompt_closes_output_when_d_is_missing() {
+        let mut marks = OutputMarks::new();
+        marks.on_mark(ShellMark::OutputStart, (1, 0));
+        marks.on_mark(ShellMark::PromptStart, (3, 0));
+        assert_eq!(marks.last_output(80), Some(((1, 0), (2, 79))));
+        // 다음 프롬프트의 D는 열린 출력이 없으니 무시
+        marks.on_mark(ShellMark::CommandFinished(Some(0)), (9, 0));
+        assert_eq!(marks.last_output(80), Some(((1, 0), (2, 79))));
+    }
+
+    #[test]
+    fn clear_forgets_output() {
+        let mut marks = OutputMarks::new();
+        marks.on_mark(ShellMark::OutputStart, (1, 0));
+        marks.on_mark(ShellMark::CommandFinished(Some(0)), (3, 0));
+        marks.clear();
+        assert_eq!(marks.last_output(80), None);
+    }
+}
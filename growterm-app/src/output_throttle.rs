@@ -0,0 +1,125 @@
+use std::time::{Duration, Instant};
+
+use growterm_pty::ReadFlow;
+
+/// Window the parse budget applies to, about one frame.
+const FRAME: Duration = Duration::from_millis(16);
+/// Bytes parsed into the grid per `FRAME`.
+const FRAME_BUDGET: usize = 1024 * 1024;
+/// Backlog above which the PTY stops being read (`flow_control = true`).
+const PAUSE_BACKLOG: usize = 4 * 1024 * 1024;
+
+/// Caps how much PTY output is parsed per frame so a flood (`yes`,
+/// `cat huge.log`) can't hold the terminal lock long enough to starve
+/// the UI. The rest waits in a backlog that later ticks drain.
+pub struct OutputThrottle {
+    backlog: Vec<u8>,
+    frame_start: Option<Instant>,
+    parsed: usize,
+}
+
+impl Default for OutputThrottle {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl OutputThrottle {
+    pub fn new() -> Self {
+        Self {
+            backlog: Vec::new(),
+            frame_start: None,
+            parsed: 0,
+        }
+    }
+
+    /// Queue `bytes` and return what may be parsed now, oldest first.
+    pub fn feed(&mut self, bytes: &[u8], now: Instant) -> Vec<u8> {
+        self.backlog.extend_from_slice(bytes);
+        self.take(now)
+    }
+
+    /// Bytes from the backlog that fit in the current frame's budget.
+    pub fn take(&mut self, now: Instant) -> Vec<u8> {
+        if self
+            .frame_start
+            .map_or(true, |start| now.saturating_duration_since(start) >= FRAME)
+        {
+            self.frame_start = Some(now);
+            self.parsed = 0;
+        }
+        let n = self.backlog.len().min(FRAME_BUDGET - self.parsed);
+        self.parsed += n;
+        let rest = self.backlog.split_off(n);
+        std::mem::replace(&mut self.backlog, rest)
+    }
+
+    /// Everything left, ignoring the budget (the PTY closed).
+    pub fn take_all(&mut self) -> Vec<u8> {
+        std::mem::take(&mut self.backlog)
+    }
+
+    pub fn backlog(&self) -> usize {
+        self.backlog.len()
+    }
+
+    /// What the reactor should do next. Without `pause_reading` the
+    /// backlog can grow without bound, but the UI still stays responsive.
+    pub fn flow(&self, pause_reading: bool) -> ReadFlow {
+        if self.backlog.is_empty() {
+            ReadFlow::Continue
+        } else if pause_reading && self.backlog.len() > PAUSE_BACKLOG {
+            ReadFlow::Pause
+        } else {
+            ReadFlow::Drain
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn small_output_passes_straight_through() {
+        let mut throttle = OutputThrottle::new();
+        let now = Instant::now();
+        assert_eq!(throttle.feed(b"hello", now), b"hello");
+        assert_eq!(throttle.backlog(), 0);
+        assert_eq!(throttle.flow(true), ReadFlow::Continue);
+    }
+
+    #[test]
+    fn output_over_budget_waits_for_next_frame() {
+        let mut throttle = OutputThrottle::new();
+        let now = Instant::now();
+        let flood = vec![b'y'; FRAME_BUDGET + 100];
+        assert_eq!(throttle.feed(&flood, now).len(), FRAME_BUDGET);
+        assert_eq!(throttle.backlog(), 100);
+        assert_eq!(throttle.flow(true), ReadFlow::Drain);
+
+        assert!(throttle.take(now + FRAME / 2).is_empty());
+        assert_eq!(throttle.take(now + FRAME).len(), 100);
+        assert_eq!(throttle.flow(true), ReadFlow::Continue);
+    }
+
+    #[test]
+    fn backlog_keeps_order() {
+        let mut throttle = OutputThrottle::new();
+        let now = Instant::now();
+        let mut first = vec![b'a'; FRAME_BUDGET];
+        first.extend_from_slice(b"bc");
+        throttle.feed(&first, now);
+        assert!(throttle.feed(b"d", now).is_empty());
+        assert_eq!(throttle.take(now + FRAME), b"bcd");
+    }
+
+    #[test]
+    fn large_backlog_pauses_only_when_enabled() {
+        let mut throttle = OutputThrottle::new();
+        let now = Instant::now();
+        throttle.feed(&vec![b'y'; FRAME_BUDGET + PAUSE_BACKLOG + 1], now);
+        assert_eq!(throttle.flow(true), ReadFlow::Pause);
+        assert_eq!(throttle.flow(false), ReadFlow::Drain);
+    }
+}
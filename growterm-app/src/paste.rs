@@ -0,0 +1,166 @@
+const PREVIEW_LINES: usize = 5;
+const PREVIEW_LINE_CHARS: usize = 80;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PasteOptions {
+    /// 여러 줄이나 제어문자가 있으면 붙여넣기 전에 확인
+    pub confirm: bool,
+    pub strip_trailing_newline: bool,
+    /// \n, \r\n을 \r로 바꿔 쉘이 Enter로 받게 함
+    pub newline_to_cr: bool,
+}
+
+impl Default for PasteOptions {
+    fn default() -> Self {
+        Self {
+            confirm: true,
+            strip_trailing_newline: false,
+            newline_to_cr: true,
+        }
+    }
+}
+
+/// Apply the configured transformations to clipboard text.
+pub fn transform(text: &str, opts: &PasteOptions) -> String {
+    let mut out = text.to_string();
+    if opts.strip_trailing_newline {
+        while out.ends_with('\n') || out.ends_with('\r') {
+            out.pop();
+        }
+    }
+    if opts.newline_to_cr {
+        out = out.replace("\r\n", "\r").replace('\n', "\r");
+    }
+    out
+}
+
+/// Bytes to write to the PTY. With bracketed paste the end marker is removed
+/// from the payload so pasted text can't terminate the bracket early.
+pub fn to_pty_bytes(text: &str, bracketed: bool) -> Vec<u8> {
+    if !bracketed {
+        return text.as_bytes().to_vec();
+    }
+    let body = text.replace("\x1b[201~", "");
+    let mut bytes = Vec::with_capacity(body.len() + 12);
+    bytes.extend_from_slice(b"\x1b[200~");
+    bytes.extend_from_slice(body.as_bytes());
+    bytes.extend_from_slice(b"\x1b[201~");
+    bytes
+}
+
+fn is_unsafe_control(c: char) -> bool {
+    c.is_control() && c != '\t' && c != '\n' && c != '\r'
+}
+
+/// True if the (transformed) text would run more than one command or carries control bytes.
+pub fn needs_confirmation(text: &str, opts: &PasteOptions) -> bool {
+    opts.confirm && (text.contains(['\n', '\r']) || text.chars().any(is_unsafe_control))
+}
+
+/// Alert title and preview for the confirmation dialog.
+pub fn confirmation_message(text: &str) -> (String, String) {
+    let lines: Vec<&str> = text.split(['\n', '\r']).filter(|l| !l.is_empty()).collect();
+    let controls = text.chars().filter(|&c| is_unsafe_control(c)).count();
+    let title = if lines.len() > 1 {
+        format!("Paste {} lines?", lines.len())
+    } else {
+        "Paste text with control characters?".to_string()
+    };
+    let mut message = String::new();
+    for line in lines.iter().take(PREVIEW_LINES) {
+        message.push_str(&preview_line(line));
+        message.push('\n');
+    }
+    if lines.len() > PREVIEW_LINES {
+        message.push_str(&format!("… and {} more\n", lines.len() - PREVIEW_LINES));
+    }
+    if controls > 0 {
+        message.push_str(&format!("\n{controls} control character(s)"));
+    }
+    (title, message.trim_end().to_string())
+}
+
+/// Show control characters in caret notation (ESC → ^[) and cut long lines.
+fn preview_line(line: &str) -> String {
+    let mut out = String::new();
+    for (i, c) in line.chars().enumerate() {
+        if i >= PREVIEW_LINE_CHARS {
+            out.push('…');
+            break;
+        }
+        if is_unsafe_control(c) {
+            out.push('^');
+            out.push(((c as u8) ^ 0x40) as char);
+        } else {
+            out.push(c);
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn newlines_become_carriage_returns() {
+        let opts = PasteOptions::default();
+        assert_eq!(transform("a\nb\r\nc", &opts), "a\rb\rc");
+    }
+
+    #[test]
+    fn newline_conversion_can_be_disabled() {
+        let opts = PasteOptions { newline_to_cr: false, ..PasteOptions::default() };
+        assert_eq!(transform("a\nb", &opts), "a\nb");
+    }
+
+    #[test]
+    fn strip_trailing_newline() {
+        let opts = PasteOptions { strip_trailing_newline: true, ..PasteOptions::default() };
+        assert_eq!(transform("ls -la\n", &opts), "ls -la");
+        assert_eq!(transform("ls\r\n\n", &opts), "ls");
+    }
+
+    #[test]
+    fn single_line_needs_no_confirmation() {
+        let opts = PasteOptions::default();
+        assert!(!needs_confirmation("echo hi", &opts));
+        assert!(!needs_confirmation("a\tb", &opts));
+    }
+
+    #[test]
+    fn multiline_and_controls_need_confirmation() {
+        let opts = PasteOptions::default();
+        assert!(needs_confirmation("a\rb", &opts));
+        assert!(needs_confirmation("rm -rf x\x1b[A", &opts));
+        let off = PasteOptions { confirm: false, ..opts };
+        assert!(!needs_confirmation("a\rb", &off));
+    }
+
+    #[test]
+    fn bracketed_paste_wraps_and_strips_end_marker() {
+        assert_eq!(to_pty_bytes("hi", false), b"hi");
+        assert_eq!(to_pty_bytes("a\x1b[201~b", true), b"\x1b[200~ab\x1b[201~");
+    }
+
+    #[test]
+    fn message_counts_lines_and_previews() {
+        let (title, message) = confirmation_message("one\rtwo\rthree");
+        assert_eq!(title, "Paste 3 lines?");
+        assert_eq!(message, "one\ntwo\nthree");
+    }
+
+    #[test]
+    fn message_truncates_long_paste() {
+        let text = (1..=8).map(|i| i.to_string()).collect::<Vec<_>>().join("\n");
+        let (_, message) = confirmation_message(&text);
+        assert!(message.ends_with("… and 3 more"));
+    }
+
+    #[test]
+    fn message_shows_control_characters() {
+        let (title, message) = confirmation_message("ls\x1b[A");
+        assert_eq!(title, "Paste text with control characters?");
+        assert_eq!(message, "ls^[[A\n\n1 control character(s)");
+    }
+}
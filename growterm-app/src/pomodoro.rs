@@ -7,6 +7,9 @@ const DEFAULT_WORK_SECS: u64 = 25 * 60;
 #[cfg(test)]
 const DEFAULT_BREAK_SECS: u64 = 3 * 60;
 
+/// How much "Extend Break" adds to the current break.
+pub const BREAK_EXTENSION_SECS: u64 = 5 * 60;
+
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub enum Phase {
     Idle,
@@ -26,6 +29,8 @@ pub struct Pomodoro {
     started_at: Option<Instant>,
     work_secs: u64,
     break_secs: u64,
+    /// Added to `break_secs` by Extend Break; reset when the break ends.
+    break_extra_secs: u64,
     /// Tab index → scrollback length at the moment Working phase started.
     scrollback_snapshot: HashMap<u64, usize>,
     /// AI coaching response lines, shared with the background thread.
@@ -40,6 +45,7 @@ impl Pomodoro {
             started_at: None,
             work_secs,
             break_secs,
+            break_extra_secs: 0,
             scrollback_snapshot: HashMap::new(),
             ai_response: Arc::new(Mutex::new(None)),
         }
@@ -48,13 +54,44 @@ impl Pomodoro {
     pub fn toggle(&mut self) {
         self.enabled = !self.enabled;
         if !self.enabled {
-            self.phase = Phase::Idle;
-            self.started_at = None;
-            self.scrollback_snapshot.clear();
+            self.end_break();
             *self.ai_response.lock().unwrap() = None;
         }
     }
 
+    fn end_break(&mut self) {
+        self.phase = Phase::Idle;
+        self.started_at = None;
+        self.break_extra_secs = 0;
+        self.scrollback_snapshot.clear();
+    }
+
+    /// Ends the current break early. Returns false when not on a break.
+    pub fn skip_break(&mut self) -> bool {
+        if !self.is_input_blocked() {
+            return false;
+        }
+        self.end_break();
+        true
+    }
+
+    /// Lengthens the current break by `secs`. Returns false when not on a break.
+    pub fn extend_break(&mut self, secs: u64) -> bool {
+        if !self.is_input_blocked() {
+            return false;
+        }
+        self.break_extra_secs += secs;
+        true
+    }
+
+    fn break_remaining_at(&self, now: Instant) -> Option<u64> {
+        if self.phase != Phase::Break {
+            return None;
+        }
+        let elapsed = now.duration_since(self.started_at?).as_secs();
+        Some((self.break_secs + self.break_extra_secs).saturating_sub(elapsed))
+    }
+
     pub fn is_enabled(&self) -> bool {
         self.enabled
     }
@@ -112,10 +149,8 @@ impl Pomodoro {
                 }
             }
             Phase::Break => {
-                if elapsed >= self.break_secs {
-                    self.phase = Phase::Idle;
-                    self.started_at = None;
-                    self.scrollback_snapshot.clear();
+                if elapsed >= self.break_secs + self.break_extra_secs {
+                    self.end_break();
                 }
             }
             Phase::Idle => {}
@@ -155,6 +190,24 @@ impl Pomodoro {
         }
     }
 
+    /// Lines for the break overlay: the countdown, then coaching if enabled.
+    pub fn break_text(&self, coaching: bool) -> Option<Vec<String>> {
+        self.break_text_at(Instant::now(), coaching)
+    }
+
+    fn break_text_at(&self, now: Instant, coaching: bool) -> Option<Vec<String>> {
+        if !self.enabled {
+            return None;
+        }
+        let remaining = self.break_remaining_at(now)?;
+        let mut lines = vec![format!("Break {:02}:{:02}", remaining / 60, remaining % 60)];
+        if coaching {
+            lines.push(String::new());
+            lines.extend(self.coaching_lines()?);
+        }
+        Some(lines)
+    }
+
     /// Returns display text for the timer, or None if idle.
     pub fn display_text(&self) -> Option<String> {
         self.display_text_at(Instant::now())
@@ -174,7 +227,7 @@ impl Pomodoro {
                 Some(format!("\u{1F345} {m:02}:{s:02}"))
             }
             Phase::Break => {
-                let remaining = self.break_secs.saturating_sub(elapsed);
+                let remaining = (self.break_secs + self.break_extra_secs).saturating_sub(elapsed);
                 let m = remaining / 60;
                 let s = remaining % 60;
                 Some(format!("\u{2615} {m:02}:{s:02}"))
@@ -480,6 +533,64 @@ mod tests {
         assert!(p.coaching_lines().is_none(), "Working -> None");
     }
 
+    #[test]
+    fn break_text_starts_with_countdown() {
+        let mut p = enabled_pomodoro();
+        let now = Instant::now();
+        p.on_input_at(now, &[(0, 0)]);
+        assert!(p.break_text_at(now, true).is_none(), "Working -> None");
+
+        let break_start = now + Duration::from_secs(DEFAULT_WORK_SECS);
+        p.tick_at(break_start);
+        let at = break_start + Duration::from_secs(15);
+        assert_eq!(p.break_text_at(at, false).unwrap(), vec!["Break 02:45".to_string()]);
+
+        let lines = p.break_text_at(at, true).unwrap();
+        assert_eq!(lines[0], "Break 02:45");
+        assert_eq!(lines[2], "[Coaching]");
+    }
+
+    #[test]
+    fn extend_break_delays_break_end() {
+        let mut p = enabled_pomodoro();
+        let now = Instant::now();
+        p.on_input_at(now, &[(0, 0)]);
+        let break_start = now + Duration::from_secs(DEFAULT_WORK_SECS);
+        p.tick_at(break_start);
+
+        assert!(p.extend_break(BREAK_EXTENSION_SECS));
+        let text = p.display_text_at(break_start + Duration::from_secs(60)).unwrap();
+        assert!(text.contains("07:00"));
+
+        p.tick_at(break_start + Duration::from_secs(DEFAULT_BREAK_SECS));
+        assert!(p.is_input_blocked());
+        p.tick_at(break_start + Duration::from_secs(DEFAULT_BREAK_SECS + BREAK_EXTENSION_SECS));
+        assert_eq!(p.phase(), Phase::Idle);
+
+        // The extension only applies to the break it was given to
+        p.on_input_at(break_start, &[(0, 0)]);
+        let next_break = break_start + Duration::from_secs(DEFAULT_WORK_SECS);
+        p.tick_at(next_break);
+        p.tick_at(next_break + Duration::from_secs(DEFAULT_BREAK_SECS));
+        assert_eq!(p.phase(), Phase::Idle);
+    }
+
+    #[test]
+    fn skip_break_unblocks_input() {
+        let mut p = enabled_pomodoro();
+        let now = Instant::now();
+        assert!(!p.skip_break(), "Idle -> nothing to skip");
+        assert!(!p.extend_break(60));
+
+        p.on_input_at(now, &[(0, 0)]);
+        p.tick_at(now + Duration::from_secs(DEFAULT_WORK_SECS));
+        assert!(p.is_input_blocked());
+        assert!(p.skip_break());
+        assert!(!p.is_input_blocked());
+        assert_eq!(p.phase(), Phase::Idle);
+        assert!(p.scrollback_snapshot().is_empty());
+    }
+
     #[test]
     fn display_text_during_working() {
         let mut p = enabled_pomodoro();
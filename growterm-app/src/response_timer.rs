@@ -1,7 +1,12 @@
-use std::time::{Duration, Instant};
+use std::collections::VecDeque;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+use growterm_types::{OscCommand, ShellMark, TerminalCommand};
 
 const COMPLETION_TIMEOUT: Duration = Duration::from_millis(500);
 const MIN_DURATION_FOR_AVG: Duration = Duration::from_secs(1);
+/// Finished commands kept per tab; older ones are dropped.
+const HISTORY_LIMIT: usize = 500;
 
 #[derive(Debug, Clone, Copy, PartialEq)]
 enum State {
@@ -20,6 +25,103 @@ pub struct ResponseTimer {
     last_total: Option<Duration>,
     total_sum: Duration,
     count: u32,
+    last_output: Option<CommandOutput>,
+    enter_wall: Option<SystemTime>,
+    history: VecDeque<ResponseRecord>,
+}
+
+/// One finished command in the per-tab latency log.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ResponseRecord {
+    /// When Enter was pressed; the report shows how long ago.
+    pub started: Instant,
+    /// Wall-clock time of Enter, for CSV export.
+    pub started_at: SystemTime,
+    pub ttfb: Option<Duration>,
+    pub total: Duration,
+    pub output: Option<CommandOutput>,
+}
+
+/// Bytes and lines a single command printed between OSC 133 C and D.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct CommandOutput {
+    pub bytes: u64,
+    pub lines: u64,
+}
+
+/// Counts PTY output per command. Fed parsed commands from the IO thread;
+/// shell integration marks (OSC 133 C/D) delimit each command's output.
+/// Escape sequences aren't output, so only printed text and line breaks count.
+#[derive(Debug, Default)]
+pub struct CommandOutputCounter {
+    in_command: bool,
+    current: CommandOutput,
+    last: Option<CommandOutput>,
+}
+
+impl CommandOutputCounter {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn on_command(&mut self, cmd: &TerminalCommand) {
+        match cmd {
+            TerminalCommand::Osc(OscCommand::ShellMark(ShellMark::OutputStart)) => {
+                self.in_command = true;
+                self.current = CommandOutput::default();
+            }
+            TerminalCommand::Osc(OscCommand::ShellMark(ShellMark::CommandFinished(_))) => {
+                if self.in_command {
+                    self.last = Some(self.current);
+                    self.in_command = false;
+                }
+            }
+            _ if !self.in_command => {}
+            TerminalCommand::Print(c) => self.current.bytes += c.len_utf8() as u64,
+            TerminalCommand::Newline => {
+                self.current.bytes += 1;
+                self.current.lines += 1;
+            }
+            TerminalCommand::CarriageReturn | TerminalCommand::Tab | TerminalCommand::Backspace => {
+                self.current.bytes += 1;
+            }
+            _ => {}
+        }
+    }
+
+    /// Output of the last finished command, if it has not been taken yet.
+    pub fn take_last(&mut self) -> Option<CommandOutput> {
+        self.last.take()
+    }
+}
+
+fn format_count(n: u64) -> String {
+    if n >= 1_000_000 {
+        format!("{:.1}M", n as f64 / 1_000_000.0)
+    } else if n >= 1_000 {
+        format!("{:.1}K", n as f64 / 1_000.0)
+    } else {
+        n.to_string()
+    }
+}
+
+fn format_latency(d: Duration) -> String {
+    if d < Duration::from_secs(1) {
+        format!("{}ms", d.as_millis())
+    } else {
+        format!("{:.1}s", d.as_secs_f64())
+    }
+}
+
+fn format_ago(d: Duration) -> String {
+    let secs = d.as_secs();
+    if secs < 60 {
+        format!("{secs}s")
+    } else if secs < 3600 {
+        format!("{}m", secs / 60)
+    } else {
+        format!("{}h", secs / 3600)
+    }
 }
 
 impl ResponseTimer {
@@ -34,6 +136,9 @@ impl ResponseTimer {
             last_total: None,
             total_sum: Duration::ZERO,
             count: 0,
+            last_output: None,
+            enter_wall: None,
+            history: VecDeque::new(),
         }
     }
 
@@ -48,6 +153,9 @@ impl ResponseTimer {
             self.last_total = None;
             self.total_sum = Duration::ZERO;
             self.count = 0;
+            self.last_output = None;
+            self.enter_wall = None;
+            self.history.clear();
         }
     }
 
@@ -65,8 +173,10 @@ impl ResponseTimer {
         }
         self.state = State::WaitingForFirstByte;
         self.enter_at = Some(now);
+        self.enter_wall = Some(SystemTime::now());
         self.first_byte_at = None;
         self.last_output_at = None;
+        self.last_output = None;
     }
 
     pub fn on_pty_output(&mut self, ts: Instant) {
@@ -108,6 +218,16 @@ impl ResponseTimer {
                             self.total_sum += total;
                             self.count += 1;
                         }
+                        if self.history.len() == HISTORY_LIMIT {
+                            self.history.pop_front();
+                        }
+                        self.history.push_back(ResponseRecord {
+                            started: enter,
+                            started_at: self.enter_wall.unwrap_or_else(SystemTime::now),
+                            ttfb: self.last_ttfb,
+                            total,
+                            output: self.last_output,
+                        });
                     }
                     self.state = State::Idle;
                 }
@@ -115,10 +235,90 @@ impl ResponseTimer {
         }
     }
 
+    pub fn on_command_output(&mut self, output: CommandOutput) {
+        if !self.enabled {
+            return;
+        }
+        self.last_output = Some(output);
+        // The D mark arrived after the silence timeout: attach to the recorded command.
+        if self.state == State::Idle {
+            if let Some(record) = self.history.back_mut().filter(|r| r.output.is_none()) {
+                record.output = Some(output);
+            }
+        }
+    }
+
+    pub fn last_output(&self) -> Option<CommandOutput> {
+        self.last_output
+    }
+
     pub fn stats(&self) -> (Duration, u32) {
         (self.total_sum, self.count)
     }
 
+    /// Finished commands, oldest first.
+    pub fn history(&self) -> impl DoubleEndedIterator<Item = &ResponseRecord> {
+        self.history.iter()
+    }
+
+    /// Report overlay lines: a header, then the newest `limit` commands.
+    /// Every line has the same width so the right-aligned boxes line up.
+    pub fn report_lines(&self, limit: usize) -> Vec<String> {
+        self.report_lines_at(Instant::now(), limit)
+    }
+
+    fn report_lines_at(&self, now: Instant, limit: usize) -> Vec<String> {
+        let mut lines = vec![format!(
+            "{:>5} {:>6} {:>6} {:>6} {:>6}",
+            "ago", "ttfb", "total", "lines", "bytes"
+        )];
+        for r in self.history.iter().rev().take(limit) {
+            let (lines_text, bytes_text) = r
+                .output
+                .map(|o| (format_count(o.lines), format_count(o.bytes)))
+                .unwrap_or_else(|| ("-".into(), "-".into()));
+            lines.push(format!(
+                "{:>5} {:>6} {:>6} {:>6} {:>6}",
+                format_ago(now.saturating_duration_since(r.started)),
+                r.ttfb.map(format_latency).unwrap_or_else(|| "-".into()),
+                format_latency(r.total),
+                lines_text,
+                bytes_text,
+            ));
+        }
+        let title = if self.history.is_empty() {
+            "Response times: none yet".to_string()
+        } else {
+            format!("Response times: {} of {}", lines.len() - 1, self.history.len())
+        };
+        let width = lines[0].chars().count().max(title.chars().count());
+        lines.insert(0, title);
+        lines.iter().map(|l| format!("{l:<width$}")).collect()
+    }
+
+    /// Whole history as CSV (unix seconds, milliseconds, output counts).
+    pub fn to_csv(&self) -> String {
+        let mut csv = String::from("started_unix,ttfb_ms,total_ms,output_lines,output_bytes\n");
+        for r in &self.history {
+            let started = r.started_at.duration_since(UNIX_EPOCH).unwrap_or_default();
+            let ttfb = r.ttfb.map(|d| d.as_millis().to_string()).unwrap_or_default();
+            let (lines, bytes) = r
+                .output
+                .map(|o| (o.lines.to_string(), o.bytes.to_string()))
+                .unwrap_or_default();
+            csv.push_str(&format!(
+                "{}.{:03},{},{},{},{}\n",
+                started.as_secs(),
+                started.subsec_millis(),
+                ttfb,
+                r.total.as_millis(),
+                lines,
+                bytes
+            ));
+        }
+        csv
+    }
+
     pub fn set_enabled(&mut self, enabled: bool) {
         if enabled && !self.enabled {
             self.enabled = true;
@@ -147,7 +347,11 @@ impl ResponseTimer {
             }
             State::Idle => {
                 let total = self.last_total?;
-                Some(format!("⏱\u{200A}{}s{}", total.as_secs(), avg_part))
+                let output_part = self
+                    .last_output
+                    .map(|o| format!(" {}L/{}B", format_count(o.lines), format_count(o.bytes)))
+                    .unwrap_or_default();
+                Some(format!("⏱\u{200A}{}s{}{}", total.as_secs(), avg_part, output_part))
             }
         }
     }
@@ -390,4 +594,163 @@ mod tests {
         assert_eq!(rt.state, State::WaitingForFirstByte);
         assert!(rt.first_byte_at.is_none());
     }
+
+    fn feed(counter: &mut CommandOutputCounter, parser: &mut growterm_vt_parser::VtParser, bytes: &[u8]) {
+        for cmd in parser.parse(bytes) {
+            counter.on_command(&cmd);
+        }
+    }
+
+    #[test]
+    fn counter_counts_bytes_and_lines_between_marks() {
+        let mut c = CommandOutputCounter::new();
+        let mut parser = growterm_vt_parser::VtParser::new();
+        feed(&mut c, &mut parser, b"ignored\n\x1b]133;C\x07a\nbc\n\x1b]133;D;0\x07prompt\n");
+        // "a\nbc\n" = 5 bytes; the marks' terminators aren't output
+        assert_eq!(c.take_last(), Some(CommandOutput { bytes: 5, lines: 2 }));
+        assert_eq!(c.take_last(), None);
+    }
+
+    #[test]
+    fn counter_handles_marks_split_across_reads() {
+        let mut c = CommandOutputCounter::new();
+        let mut parser = growterm_vt_parser::VtParser::new();
+        feed(&mut c, &mut parser, b"\x1b]13");
+        feed(&mut c, &mut parser, b"3;C\x1b\\");
+        feed(&mut c, &mut parser, b"line\n\x1b]1");
+        feed(&mut c, &mut parser, b"33;D\x07");
+        assert_eq!(c.take_last(), Some(CommandOutput { bytes: 5, lines: 1 }));
+    }
+
+    #[test]
+    fn counter_skips_escape_sequences() {
+        let mut c = CommandOutputCounter::new();
+        let mut parser = growterm_vt_parser::VtParser::new();
+        feed(&mut c, &mut parser, b"\x1b]133;C\x07\x1b[31mred\x1b[0m\n\x1b]133;D\x07");
+        assert_eq!(c.take_last(), Some(CommandOutput { bytes: 4, lines: 1 }));
+    }
+
+    #[test]
+    fn counter_ignores_d_without_c() {
+        let mut c = CommandOutputCounter::new();
+        let mut parser = growterm_vt_parser::VtParser::new();
+        feed(&mut c, &mut parser, b"\x1b]133;D\x07");
+        assert_eq!(c.take_last(), None);
+    }
+
+    #[test]
+    fn idle_display_shows_command_output() {
+        let mut rt = enabled_timer();
+        let now = Instant::now();
+        rt.on_enter_at(now);
+        rt.on_pty_output(now + Duration::from_secs(2));
+        rt.tick_at(now + Duration::from_millis(2500));
+        rt.on_command_output(CommandOutput { bytes: 34_000_000, lines: 1_200_000 });
+
+        let text = rt
+            .display_text_at(now + Duration::from_secs(3))
+            .unwrap();
+        assert_eq!(text, "⏱\u{200A}2s(2s/1) 1.2ML/34.0MB");
+    }
+
+    fn run_command(rt: &mut ResponseTimer, at: Instant, ttfb: Duration, total: Duration) {
+        rt.on_enter_at(at);
+        rt.on_pty_output(at + ttfb);
+        rt.on_pty_output(at + total);
+        rt.tick_at(at + total + COMPLETION_TIMEOUT);
+    }
+
+    #[test]
+    fn history_records_every_finished_command() {
+        let mut rt = enabled_timer();
+        let now = Instant::now();
+        run_command(&mut rt, now, Duration::from_millis(20), Duration::from_millis(50));
+        rt.on_enter_at(now + Duration::from_secs(1));
+        rt.on_pty_output(now + Duration::from_secs(2));
+        rt.on_command_output(CommandOutput { bytes: 12, lines: 2 });
+        rt.tick_at(now + Duration::from_secs(3));
+
+        let history: Vec<_> = rt.history().collect();
+        assert_eq!(history.len(), 2);
+        assert_eq!(history[0].ttfb, Some(Duration::from_millis(20)));
+        assert_eq!(history[0].total, Duration::from_millis(50));
+        assert_eq!(history[0].output, None);
+        assert_eq!(history[1].total, Duration::from_secs(1));
+        assert_eq!(history[1].output, Some(CommandOutput { bytes: 12, lines: 2 }));
+        // Sub-second commands are logged even though they are left out of the average.
+        assert_eq!(rt.stats().1, 1);
+    }
+
+    #[test]
+    fn late_command_output_attaches_to_last_record() {
+        let mut rt = enabled_timer();
+        run_command(&mut rt, Instant::now(), Duration::from_millis(5), Duration::from_millis(5));
+        rt.on_command_output(CommandOutput { bytes: 3, lines: 1 });
+        assert_eq!(rt.history().last().unwrap().output, Some(CommandOutput { bytes: 3, lines: 1 }));
+    }
+
+    #[test]
+    fn history_is_capped_and_cleared_on_disable() {
+        let mut rt = enabled_timer();
+        let now = Instant::now();
+        for i in 0..HISTORY_LIMIT as u64 + 3 {
+            run_command(&mut rt, now + Duration::from_secs(i), Duration::ZERO, Duration::from_millis(i));
+        }
+        assert_eq!(rt.history().count(), HISTORY_LIMIT);
+        assert_eq!(rt.history().next().unwrap().total, Duration::from_millis(3));
+        rt.toggle();
+        assert_eq!(rt.history().count(), 0);
+    }
+
+    #[test]
+    fn report_lists_newest_first_with_equal_widths() {
+        let mut rt = enabled_timer();
+        let now = Instant::now();
+        run_command(&mut rt, now, Duration::from_millis(40), Duration::from_millis(800));
+        run_command(&mut rt, now + Duration::from_secs(10), Duration::from_millis(7), Duration::from_millis(2500));
+        rt.on_command_output(CommandOutput { bytes: 1500, lines: 20 });
+
+        let lines = rt.report_lines_at(now + Duration::from_secs(100), 10);
+        assert_eq!(lines.len(), 4);
+        assert_eq!(lines[0].trim_end(), "Response times: 2 of 2");
+        assert_eq!(lines[1].trim_end(), "  ago   ttfb  total  lines  bytes");
+        assert_eq!(lines[2].trim_end(), "   1m    7ms   2.5s     20   1.5K");
+        assert_eq!(lines[3].trim_end(), "   1m   40ms  800ms      -      -");
+        assert!(lines.iter().all(|l| l.chars().count() == lines[0].chars().count()));
+
+        assert_eq!(rt.report_lines_at(now, 1).len(), 3);
+    }
+
+    #[test]
+    fn report_without_history() {
+        let rt = enabled_timer();
+        let lines = rt.report_lines(10);
+        assert_eq!(lines[0].trim_end(), "Response times: none yet");
+        assert_eq!(lines.len(), 2);
+    }
+
+    #[test]
+    fn csv_export() {
+        let mut rt = enabled_timer();
+        run_command(&mut rt, Instant::now(), Duration::from_millis(12), Duration::from_millis(1500));
+        rt.on_command_output(CommandOutput { bytes: 100, lines: 4 });
+        run_command(&mut rt, Instant::now(), Duration::from_millis(3), Duration::from_millis(3));
+        for (i, r) in rt.history.iter_mut().enumerate() {
+            r.started_at = UNIX_EPOCH + Duration::from_millis(1_700_000_000_250 + i as u64 * 1000);
+        }
+        assert_eq!(
+            rt.to_csv(),
+            "started_unix,ttfb_ms,total_ms,output_lines,output_bytes\n\
+             1700000000.250,12,1500,4,100\n\
+             1700000001.250,3,3,,\n"
+        );
+    }
+
+    #[test]
+    fn new_enter_clears_command_output() {
+        let mut rt = enabled_timer();
+        rt.on_command_output(CommandOutput { bytes: 10, lines: 1 });
+        rt.on_enter_at(Instant::now());
+        assert!(rt.last_output().is_none());
+    }
 }
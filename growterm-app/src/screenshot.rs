@@ -0,0 +1,112 @@
+//! Copy Screen as Text, Save Screenshot (PNG)
+
+use std::path::Path;
+
+use growterm_gpu_draw::{Overlays, Renderer};
+use growterm_grid::Grid;
+use growterm_types::RenderCommand;
+
+/// Rows in view (scrollback too when scrolled) as text shaped like the screen
+pub fn screen_text(grid: &Grid) -> String {
+    growterm_render_cmd::export::to_screen_text(grid.visible_rows().map(|row| &row[..]))
+}
+
+/// Stands in for the window's renderer for one frame: the frame is read
+/// back instead of shown, so the screenshot is exactly what is on screen.
+pub struct Capture<'a> {
+    inner: &'a mut dyn Renderer,
+    pub pixels: Option<Vec<u8>>,
+}
+
+impl<'a> Capture<'a> {
+    pub fn new(inner: &'a mut dyn Renderer) -> Self {
+        Self { inner, pixels: None }
+    }
+}
+
+impl Renderer for Capture<'_> {
+    fn draw(&mut self, commands: &[RenderCommand], overlays: &Overlays) -> bool {
+        self.pixels = self.inner.capture(commands, overlays);
+        false
+    }
+
+    fn resize(&mut self, width: u32, height: u32) {
+        self.inner.resize(width, height)
+    }
+
+    fn cell_size(&self) -> (f32, f32) {
+        self.inner.cell_size()
+    }
+
+    fn set_font(&mut self, font_path: Option<&str>, size: f32) {
+        self.inner.set_font(font_path, size)
+    }
+
+    fn set_font_size(&mut self, size: f32) {
+        self.inner.set_font_size(size)
+    }
+
+    fn tab_bar_height(&self) -> f32 {
+        self.inner.tab_bar_height()
+    }
+
+    fn surface_size(&self) -> (u32, u32) {
+        self.inner.surface_size()
+    }
+}
+
+/// RGBA8 pixels as a PNG file
+pub fn write_png(path: &Path, width: u32, height: u32, pixels: &[u8]) -> std::io::Result<()> {
+    let file = std::fs::File::create(path)?;
+    let mut encoder = png::Encoder::new(std::io::BufWriter::new(file), width, height);
+    encoder.set_color(png::ColorType::Rgba);
+    encoder.set_depth(png::BitDepth::Eight);
+    let mut writer = encoder.write_header()?;
+    writer.write_image_data(pixels)?;
+    writer.finish()?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use growterm_gpu_draw::{RecordingRenderer, SoftwareRenderer};
+    use growterm_types::TerminalCommand;
+
+    #[test]
+    fn screen_text_follows_rows() {
+        let mut grid = Grid::new(4, 3);
+        for ch in "abcdef".chars() {
+            grid.apply(&TerminalCommand::Print(ch));
+        }
+        assert_eq!(screen_text(&grid), "abcd\nef\n");
+    }
+
+    #[test]
+    fn capture_reads_frame_back_instead_of_drawing() {
+        let mut renderer = SoftwareRenderer::new(40, 20, 24.0, None);
+        let mut capture = Capture::new(&mut renderer);
+        assert!(!capture.draw(&[], &Overlays::default()));
+        assert_eq!(capture.pixels.map(|p| p.len()), Some(40 * 20 * 4));
+
+        // 읽어 올 수 없는 렌더러면 None
+        let mut recorder = RecordingRenderer::new(40, 20, 20.0);
+        let mut capture = Capture::new(&mut recorder);
+        capture.draw(&[], &Overlays::default());
+        assert_eq!(capture.pixels, None);
+        assert_eq!(recorder.frames, 0);
+    }
+
+    #[test]
+    fn png_round_trips() {
+        let path = std::env::temp_dir().join(format!("growterm-screenshot-{}.png", std::process::id()));
+        let pixels: Vec<u8> = (0..2 * 2 * 4).map(|i| i as u8 * 10).collect();
+        write_png(&path, 2, 2, &pixels).unwrap();
+        let decoder = png::Decoder::new(std::fs::File::open(&path).unwrap());
+        let mut reader = decoder.read_info().unwrap();
+        let mut buf = vec![0; reader.output_buffer_size()];
+        reader.next_frame(&mut buf).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(buf, pixels);
+    }
+}
@@ -90,15 +90,24 @@ pub fn pixel_to_cell(x: f32, y: f32, cell_w: f32, cell_h: f32) -> (u16, u16) {
 }
 
 /// Convert raw mouse pixel coordinates to cell coordinates, accounting for
-/// content y-offset (tab bar + title bar in transparent mode).
+/// the content origin (window padding, plus tab bar + title bar in
+/// transparent mode).
 pub fn mouse_pixel_to_cell(
     x: f32,
     y: f32,
     cell_w: f32,
     cell_h: f32,
-    content_y_offset: f32,
+    (origin_x, origin_y): (f32, f32),
 ) -> (u16, u16) {
-    pixel_to_cell(x, y - content_y_offset, cell_w, cell_h)
+    pixel_to_cell(x - origin_x, y - origin_y, cell_w, cell_h)
+}
+
+/// A cell's character plus any combining marks / ZWJ continuation on it.
+fn push_cell_text(text: &mut String, cell: &Cell) {
+    text.push(cell.character);
+    if let Some(zerowidth) = cell.zerowidth {
+        text.push_str(zerowidth.as_str());
+    }
 }
 
 /// Extract text from a slice of cells, skipping wide-char spacer cells.
@@ -106,7 +115,7 @@ fn collect_cells_text(line: &[Cell], col_start: usize, col_end: usize) -> String
     let mut text = String::new();
     let mut col = col_start;
     while col < col_end {
-        text.push(line[col].character);
+        push_cell_text(&mut text, &line[col]);
         if line[col].flags.contains(CellFlags::WIDE_CHAR) {
             col += 2;
         } else {
@@ -117,19 +126,19 @@ fn collect_cells_text(line: &[Cell], col_start: usize, col_end: usize) -> String
 }
 
 /// Extract full line text, replacing null chars with spaces and skipping wide-char spacers.
-fn collect_line_text(line: &[Cell]) -> String {
+pub fn collect_line_text(line: &[Cell]) -> String {
     let mut text = String::new();
     let mut col = 0;
     while col < line.len() {
         let cell = &line[col];
         if cell.flags.contains(CellFlags::WIDE_CHAR) {
-            text.push(cell.character);
+            push_cell_text(&mut text, cell);
             col += 2;
         } else if cell.character == '\0' {
             text.push(' ');
             col += 1;
         } else {
-            text.push(cell.character);
+            push_cell_text(&mut text, cell);
             col += 1;
         }
     }
@@ -170,9 +179,9 @@ pub fn extract_text(cells: &[Vec<Cell>], selection: &Selection) -> String {
 /// falling back to the cursor line.
 /// Returns (text, prompt_row) where prompt_row is the screen row for flash.
 pub fn input_line_text(grid: &growterm_grid::Grid) -> (String, u16, u16) {
-    let cells = grid.cells();
-    if let Some(prompt_row) = crate::ink_workaround::find_prompt_row(cells) {
-        let bottom = crate::ink_workaround::find_input_bottom(cells, prompt_row);
+    let cells: Vec<_> = grid.screen_rows().collect();
+    if let Some(prompt_row) = crate::ink_workaround::find_prompt_row(&cells) {
+        let bottom = crate::ink_workaround::find_input_bottom(&cells, prompt_row);
         let mut result = String::new();
         for row_idx in prompt_row..=bottom {
             let line = &cells[row_idx];
@@ -186,13 +195,13 @@ pub fn input_line_text(grid: &growterm_grid::Grid) -> (String, u16, u16) {
                     continue;
                 }
                 if cell.flags.contains(CellFlags::WIDE_CHAR) {
-                    line_text.push(cell.character);
+                    push_cell_text(&mut line_text, cell);
                     col += 2;
                 } else if cell.character == '\0' {
                     line_text.push(' ');
                     col += 1;
                 } else {
-                    line_text.push(cell.character);
+                    push_cell_text(&mut line_text, cell);
                     col += 1;
                 }
             }
@@ -224,12 +233,10 @@ pub fn input_line_text(grid: &growterm_grid::Grid) -> (String, u16, u16) {
 /// Extract the text of the cursor line from the grid (trailing whitespace trimmed).
 pub fn cursor_line_text(grid: &growterm_grid::Grid) -> String {
     let (cursor_row, _) = grid.cursor_pos();
-    let cells = grid.cells();
-    let row = cursor_row as usize;
-    if row >= cells.len() {
-        return String::new();
+    match grid.screen_row(cursor_row as usize) {
+        Some(row) => collect_line_text(row).trim_end().to_string(),
+        None => String::new(),
     }
-    collect_line_text(&cells[row]).trim_end().to_string()
 }
 
 /// Extract text using absolute row coordinates from scrollback + screen cells.
@@ -238,20 +245,11 @@ pub fn extract_text_absolute(grid: &growterm_grid::Grid, selection: &Selection)
         return String::new();
     }
     let ((sr, sc), (er, ec)) = selection.normalized();
-    let scrollback = grid.scrollback();
-    let screen = grid.cells();
-    let sb_len = scrollback.len() as u32;
     let mut result = String::new();
 
     for row in sr..=er {
-        let line: &[Cell] = if row < sb_len {
-            &scrollback[row as usize]
-        } else {
-            let screen_row = (row - sb_len) as usize;
-            if screen_row >= screen.len() {
-                break;
-            }
-            &screen[screen_row]
+        let Some(line) = grid.row(row as usize) else {
+            break;
         };
         let col_start = if row == sr { sc as usize } else { 0 };
         let col_end = if row == er {
@@ -270,23 +268,31 @@ pub fn extract_text_absolute(grid: &growterm_grid::Grid, selection: &Selection)
     result
 }
 
+/// The selected cells of each row, for styled (HTML/RTF) copies.
+pub fn extract_cells_absolute(grid: &growterm_grid::Grid, selection: &Selection) -> Vec<Vec<Cell>> {
+    if selection.is_empty() {
+        return Vec::new();
+    }
+    let ((sr, sc), (er, ec)) = selection.normalized();
+    let mut rows = Vec::new();
+    for row in sr..=er {
+        let Some(line) = grid.row(row as usize) else {
+            break;
+        };
+        let col_start = if row == sr { (sc as usize).min(line.len()) } else { 0 };
+        let col_end = if row == er {
+            (ec as usize + 1).min(line.len())
+        } else {
+            line.len()
+        };
+        rows.push(line[col_start..col_end.max(col_start)].to_vec());
+    }
+    rows
+}
+
 /// Extract a single row's text using absolute row coordinate (scrollback + screen).
 pub fn row_text_absolute(grid: &growterm_grid::Grid, abs_row: u32) -> String {
-    let scrollback = grid.scrollback();
-    let screen = grid.cells();
-    let sb_len = scrollback.len() as u32;
-
-    let line: &[Cell] = if abs_row < sb_len {
-        &scrollback[abs_row as usize]
-    } else {
-        let screen_row = (abs_row - sb_len) as usize;
-        if screen_row >= screen.len() {
-            return String::new();
-        }
-        &screen[screen_row]
-    };
-
-    collect_line_text(line)
+    grid.row(abs_row as usize).map(|line| collect_line_text(line)).unwrap_or_default()
 }
 
 /// Convert cell column (wide char = 2 cols) to char index (wide char = 1).
@@ -321,43 +327,64 @@ pub fn char_index_to_cell_col(line: &[Cell], char_idx: usize) -> usize {
 
 /// Get cell slice for an absolute row (scrollback + screen).
 pub fn row_cells_absolute(grid: &growterm_grid::Grid, abs_row: u32) -> Vec<Cell> {
-    let scrollback = grid.scrollback();
-    let screen = grid.cells();
-    let sb_len = scrollback.len() as u32;
-    if abs_row < sb_len {
-        scrollback[abs_row as usize].clone()
-    } else {
-        let screen_row = (abs_row - sb_len) as usize;
-        if screen_row >= screen.len() {
-            Vec::new()
-        } else {
-            screen[screen_row].clone()
-        }
+    grid.row(abs_row as usize).map(|line| line.to_vec()).unwrap_or_default()
+}
+
+/// Default `word_separators`. Whitespace always separates words on top of these.
+pub const DEFAULT_WORD_SEPARATORS: &str = ",│`|:\"' ()[]{}<>";
+
+/// Inclusive column range of the word under `col`, for double-click selection.
+/// A click on a separator selects just that cell.
+pub fn word_bounds(line: &[Cell], col: usize, separators: &str) -> (usize, usize) {
+    // A wide char's spacer cell belongs to the char before it
+    let is_separator = |i: usize| {
+        let i = if i > 0 && line[i - 1].flags.contains(CellFlags::WIDE_CHAR) { i - 1 } else { i };
+        let c = line[i].character;
+        c == '\0' || c.is_whitespace() || separators.contains(c)
+    };
+    if col >= line.len() || is_separator(col) {
+        return (col, col);
+    }
+    let mut start = col;
+    while start > 0 && !is_separator(start - 1) {
+        start -= 1;
     }
+    let mut end = col;
+    while end + 1 < line.len() && !is_separator(end + 1) {
+        end += 1;
+    }
+    (start, end)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use growterm_types::{Cell, CellFlags, Color};
+    use growterm_types::{Cell, CellFlags, Color, UnderlineStyle};
 
     #[test]
     fn mouse_pixel_to_cell_no_offset() {
         // No title bar / tab bar offset
-        assert_eq!(mouse_pixel_to_cell(15.0, 25.0, 10.0, 20.0, 0.0), (1, 1));
+        assert_eq!(mouse_pixel_to_cell(15.0, 25.0, 10.0, 20.0, (0.0, 0.0)), (1, 1));
     }
 
     #[test]
     fn mouse_pixel_to_cell_with_offset() {
         // Transparent mode: title_bar(50) + tab_bar(30) = 80px offset
         // Click at y=100 → content y=20 → row 1
-        assert_eq!(mouse_pixel_to_cell(15.0, 100.0, 10.0, 20.0, 80.0), (1, 1));
+        assert_eq!(mouse_pixel_to_cell(15.0, 100.0, 10.0, 20.0, (0.0, 80.0)), (1, 1));
     }
 
     #[test]
     fn mouse_pixel_to_cell_click_in_header_clamps() {
         // Click at y=30, offset=80 → content y=-50 → clamped to row 0
-        assert_eq!(mouse_pixel_to_cell(0.0, 30.0, 10.0, 20.0, 80.0), (0, 0));
+        assert_eq!(mouse_pixel_to_cell(0.0, 30.0, 10.0, 20.0, (0.0, 80.0)), (0, 0));
+    }
+
+    #[test]
+    fn mouse_pixel_to_cell_with_left_padding() {
+        // 왼쪽 여백 12px: x=25 → content x=13 → col 1, 여백 안 클릭은 col 0
+        assert_eq!(mouse_pixel_to_cell(25.0, 25.0, 10.0, 20.0, (12.0, 0.0)), (1, 1));
+        assert_eq!(mouse_pixel_to_cell(5.0, 25.0, 10.0, 20.0, (12.0, 0.0)), (1, 0));
     }
 
     #[test]
@@ -431,6 +458,9 @@ mod tests {
                         fg: Color::Default,
                         bg: Color::Default,
                         flags: CellFlags::empty(),
+                        zerowidth: None,
+                        underline_style: UnderlineStyle::Single,
+                        underline_color: Color::Default,
                     })
                     .collect()
             })
@@ -451,6 +481,9 @@ mod tests {
                         fg: Color::Default,
                         bg: Color::Default,
                         flags: if w == 2 { CellFlags::WIDE_CHAR } else { CellFlags::empty() },
+                        zerowidth: None,
+                        underline_style: UnderlineStyle::Single,
+                        underline_color: Color::Default,
                     });
                     if w == 2 {
                         row.push(Cell::default()); // spacer
@@ -461,6 +494,28 @@ mod tests {
             .collect()
     }
 
+    #[test]
+    fn word_bounds_uses_separators() {
+        let cells = make_cells(&["cd ~/src/growterm-app (main)"]);
+        let line = &cells[0];
+        assert_eq!(word_bounds(line, 5, DEFAULT_WORD_SEPARATORS), (3, 20));
+        assert_eq!(word_bounds(line, 23, DEFAULT_WORD_SEPARATORS), (23, 26));
+        // '/', '-' 를 구분자로 넣으면 경로 조각만 선택
+        assert_eq!(word_bounds(line, 12, "/-"), (9, 16));
+        // 구분자 위를 누르면 그 칸만
+        assert_eq!(word_bounds(line, 2, DEFAULT_WORD_SEPARATORS), (2, 2));
+        assert_eq!(word_bounds(line, 99, DEFAULT_WORD_SEPARATORS), (99, 99));
+    }
+
+    #[test]
+    fn word_bounds_keeps_wide_char_spacers() {
+        let cells = make_cells_with_wide(&["a 한글 b"]);
+        let line = &cells[0];
+        // "한글" occupies cols 2..=5 (two wide chars with spacers)
+        assert_eq!(word_bounds(line, 3, DEFAULT_WORD_SEPARATORS), (2, 5));
+        assert_eq!(word_bounds(line, 5, DEFAULT_WORD_SEPARATORS), (2, 5));
+    }
+
     #[test]
     fn extract_text_single_line() {
         let cells = make_cells(&["Hello World"]);
@@ -470,6 +525,16 @@ mod tests {
         assert_eq!(extract_text(&cells, &sel), "Hello");
     }
 
+    #[test]
+    fn extract_text_includes_combining_marks() {
+        let mut cells = make_cells(&["ex"]);
+        cells[0][0].zerowidth = growterm_types::ZeroWidth::intern("\u{0301}");
+        let mut sel = Selection::default();
+        sel.start = (0, 0);
+        sel.end = (0, 1);
+        assert_eq!(extract_text(&cells, &sel), "e\u{0301}x");
+    }
+
     #[test]
     fn extract_text_multi_line() {
         let cells = make_cells(&["Hello  ", "World  "]);
@@ -522,6 +587,25 @@ mod tests {
         assert_eq!(extract_text(&cells, &sel), "World");
     }
 
+    #[test]
+    fn extract_cells_absolute_clips_first_and_last_rows() {
+        use growterm_grid::Grid;
+        use growterm_types::TerminalCommand;
+
+        let mut grid = Grid::new(6, 3);
+        for c in "abcdef".chars() {
+            grid.apply(&TerminalCommand::Print(c));
+        }
+        grid.apply(&TerminalCommand::CursorPosition { row: 2, col: 1 });
+        for c in "ghi".chars() {
+            grid.apply(&TerminalCommand::Print(c));
+        }
+        let sel = Selection { start: (1, 1), end: (0, 4), active: false };
+        let rows = extract_cells_absolute(&grid, &sel);
+        let text: Vec<String> = rows.iter().map(|r| r.iter().map(|c| c.character).collect()).collect();
+        assert_eq!(text, vec!["ef", "gh"]);
+    }
+
     #[test]
     fn input_line_text_with_ink_prompt() {
         use growterm_grid::Grid;
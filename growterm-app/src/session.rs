@@ -0,0 +1,344 @@
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Condvar, Mutex};
+use std::time::{Duration, Instant};
+
+use growterm_window::{AppEvent, TerminalWindow};
+use serde::{Deserialize, Serialize};
+
+use crate::config::Config;
+use crate::selection;
+use crate::tab::TabManager;
+
+/// Minimum interval between crash-insurance writes while the app is running.
+/// The real save happens on quit.
+const SAVE_INTERVAL: Duration = Duration::from_secs(60);
+/// How long quitting waits for every window to send its final snapshot.
+const EXIT_SAVE_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// One window's tabs.
+#[derive(Debug, Clone, Default, Deserialize, Serialize, PartialEq)]
+pub struct Session {
+    #[serde(default)]
+    pub active: usize,
+    #[serde(default)]
+    pub tabs: Vec<TabSession>,
+}
+
+#[derive(Debug, Clone, Default, Deserialize, Serialize, PartialEq)]
+pub struct TabSession {
+    #[serde(default)]
+    pub cwd: Option<PathBuf>,
+    #[serde(default)]
+    pub scroll_offset: usize,
+    /// Last lines of output above the cursor, oldest first.
+    #[serde(default)]
+    pub scrollback: Vec<String>,
+}
+
+/// session.json: every open window, in the order they were opened.
+#[derive(Debug, Clone, Default, Deserialize, Serialize, PartialEq)]
+struct SavedWindows {
+    #[serde(default)]
+    windows: Vec<Session>,
+}
+
+/// One open window, as seen by `save_on_exit`.
+struct OpenWindow {
+    id: u64,
+    /// Latest snapshot; None until the window saves or with `restore_session` off
+    session: Option<Session>,
+    /// Save requests are posted here, straight to the window's app loop
+    window: Arc<dyn TerminalWindow>,
+    /// Last save request this window answered
+    answered: u64,
+    /// Quitting or closed: `session` is final and no answer will come
+    finished: bool,
+}
+
+struct Registry {
+    windows: Vec<OpenWindow>,
+    request: u64,
+}
+
+impl Registry {
+    fn entry(&mut self, id: u64) -> Option<&mut OpenWindow> {
+        self.windows.iter_mut().find(|w| w.id == id)
+    }
+
+    /// Written from every window so windows don't overwrite each other.
+    fn write(&self) {
+        let saved = SavedWindows {
+            windows: self
+                .windows
+                .iter()
+                .filter_map(|w| w.session.clone())
+                .filter(|s| !s.tabs.is_empty())
+                .collect(),
+        };
+        let dir = crate::config::config_dir();
+        let _ = std::fs::create_dir_all(&dir);
+        if let Ok(content) = serde_json::to_string_pretty(&saved) {
+            let _ = std::fs::write(session_path(), content);
+        }
+    }
+}
+
+static OPEN_WINDOWS: Mutex<Registry> = Mutex::new(Registry { windows: Vec::new(), request: 0 });
+/// Signalled whenever a window answers a save request or finishes.
+static ANSWERED: Condvar = Condvar::new();
+static NEXT_WINDOW_ID: AtomicU64 = AtomicU64::new(0);
+
+fn session_path() -> PathBuf {
+    crate::config::config_dir().join("session.json")
+}
+
+fn registry() -> std::sync::MutexGuard<'static, Registry> {
+    OPEN_WINDOWS.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+fn snapshot(tabs: &TabManager, config: &Config) -> Option<Session> {
+    config
+        .restore_session
+        .then(|| Session::capture(tabs, config.session_scrollback_lines))
+}
+
+/// Runs on the thread that ends the process (`growterm_window::set_before_exit`):
+/// asks every open window for a fresh snapshot, waits for the answers and
+/// writes session.json once for the whole app.
+pub fn save_on_exit() {
+    let mut registry = registry();
+    registry.request += 1;
+    let request = registry.request;
+    for w in registry.windows.iter().filter(|w| !w.finished) {
+        w.window.post_event(AppEvent::SaveSession);
+    }
+    let deadline = Instant::now() + EXIT_SAVE_TIMEOUT;
+    while !registry.windows.iter().all(|w| w.finished || w.answered >= request) {
+        let left = deadline.saturating_duration_since(Instant::now());
+        if left.is_zero() {
+            break;
+        }
+        registry = ANSWERED.wait_timeout(registry, left).unwrap_or_else(|e| e.into_inner()).0;
+    }
+    if registry.windows.iter().any(|w| w.session.is_some()) {
+        registry.write();
+    }
+}
+
+impl Session {
+    /// Capture open tabs. `scrollback_lines` caps how many output lines per tab are kept.
+    pub fn capture(tabs: &TabManager, scrollback_lines: usize) -> Self {
+        let tabs_state = tabs
+            .tabs()
+            .iter()
+            .map(|tab| {
+                let cwd = tab.cwd();
+                let state = tab.terminal.lock().unwrap();
+                TabSession {
+                    cwd,
+                    scroll_offset: state.grid.scroll_offset(),
+                    scrollback: tail_lines(&state.grid, scrollback_lines),
+                }
+            })
+            .collect();
+        Self {
+            active: tabs.active_index(),
+            tabs: tabs_state,
+        }
+    }
+
+    /// Load and remove the saved windows so a second process started later
+    /// does not restore the same tabs again.
+    pub fn take_all() -> Vec<Self> {
+        let path = session_path();
+        let Ok(contents) = std::fs::read_to_string(&path) else {
+            return Vec::new();
+        };
+        let _ = std::fs::remove_file(&path);
+        let saved: SavedWindows = serde_json::from_str(&contents).unwrap_or_default();
+        saved.windows.into_iter().filter(|s| !s.tabs.is_empty()).collect()
+    }
+}
+
+/// Last `n` non-trailing-blank lines above the cursor row.
+fn tail_lines(grid: &growterm_grid::Grid, n: usize) -> Vec<String> {
+    if n == 0 {
+        return Vec::new();
+    }
+    let (cursor_row, _) = grid.cursor_pos();
+    let end = grid.scrollback_len() + cursor_row as usize;
+    let start = end.saturating_sub(n);
+    let mut lines: Vec<String> = (start..end)
+        .map(|abs| selection::row_text_absolute(grid, abs as u32).trim_end().to_string())
+        .collect();
+    while lines.first().is_some_and(|l| l.is_empty()) {
+        lines.remove(0);
+    }
+    lines
+}
+
+/// Replay saved output lines into a fresh grid before the shell prints its prompt.
+pub fn replay_scrollback(tab_session: &TabSession) -> Vec<u8> {
+    let mut bytes = Vec::new();
+    for line in &tab_session.scrollback {
+        bytes.extend_from_slice(line.as_bytes());
+        bytes.extend_from_slice(b"\r\n");
+    }
+    bytes
+}
+
+/// This window's part of the saved session. The session is written when the
+/// app quits (`save_on_exit`); the periodic save is only crash insurance.
+pub struct SessionSaver {
+    id: u64,
+    last_saved: Option<Session>,
+    last_save_at: Option<Instant>,
+}
+
+impl SessionSaver {
+    pub fn new(window: Arc<dyn TerminalWindow>) -> Self {
+        let id = NEXT_WINDOW_ID.fetch_add(1, Ordering::Relaxed);
+        registry().windows.push(OpenWindow { id, session: None, window, answered: 0, finished: false });
+        Self {
+            id,
+            last_saved: None,
+            last_save_at: None,
+        }
+    }
+
+    pub fn maybe_save(&mut self, tabs: &TabManager, config: &Config) {
+        self.maybe_save_at(tabs, config, Instant::now());
+    }
+
+    fn maybe_save_at(&mut self, tabs: &TabManager, config: &Config, now: Instant) {
+        if !config.restore_session || !self.is_due(now) || tabs.is_empty() {
+            return;
+        }
+        self.last_save_at = Some(now);
+        let session = Session::capture(tabs, config.session_scrollback_lines);
+        if self.last_saved.as_ref() != Some(&session) {
+            let mut registry = registry();
+            if let Some(entry) = registry.entry(self.id) {
+                entry.session = Some(session.clone());
+            }
+            registry.write();
+            self.last_saved = Some(session);
+        }
+    }
+
+    /// `AppEvent::SaveSession`: hand `save_on_exit` a fresh snapshot.
+    pub fn answer_save_request(&self, tabs: &TabManager, config: &Config) {
+        let mut registry = registry();
+        let request = registry.request;
+        if let Some(entry) = registry.entry(self.id) {
+            entry.session = snapshot(tabs, config);
+            entry.answered = request;
+        }
+        ANSWERED.notify_all();
+    }
+
+    /// This window is about to quit the app: leave its final snapshot so
+    /// `save_on_exit` doesn't wait for an answer that would come too late.
+    pub fn finish(&self, tabs: &TabManager, config: &Config) {
+        if let Some(entry) = registry().entry(self.id) {
+            entry.session = snapshot(tabs, config);
+            entry.finished = true;
+        }
+        ANSWERED.notify_all();
+    }
+
+    /// The window was closed on its own: forget its tabs. The last window
+    /// keeps a final snapshot, since closing it quits the app, unless its
+    /// last tab exited.
+    pub fn close(self, tabs: &TabManager, config: &Config) {
+        let mut registry = registry();
+        if registry.windows.len() > 1 || tabs.is_empty() {
+            registry.windows.retain(|w| w.id != self.id);
+            if config.restore_session {
+                registry.write();
+            }
+        } else if let Some(entry) = registry.entry(self.id) {
+            entry.session = snapshot(tabs, config);
+            entry.finished = true;
+        }
+        ANSWERED.notify_all();
+    }
+
+    fn is_due(&self, now: Instant) -> bool {
+        self.last_save_at
+            .map_or(true, |t| now.duration_since(t) >= SAVE_INTERVAL)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn session_roundtrips_through_json() {
+        let session = Session {
+            active: 1,
+            tabs: vec![
+                TabSession {
+                    cwd: Some(PathBuf::from("/tmp")),
+                    scroll_offset: 3,
+                    scrollback: vec!["$ ls".to_string(), "a b".to_string()],
+                },
+                TabSession::default(),
+            ],
+        };
+        let json = serde_json::to_string(&session).unwrap();
+        let parsed: Session = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed, session);
+    }
+
+    #[test]
+    fn saved_windows_roundtrip_and_skip_missing_fields() {
+        let json = r#"{"windows":[{"tabs":[{"cwd":"/tmp"}]},{"active":1,"tabs":[{},{}]}]}"#;
+        let saved: SavedWindows = serde_json::from_str(json).unwrap();
+        assert_eq!(saved.windows.len(), 2);
+        assert_eq!(saved.windows[0].tabs[0].cwd, Some(PathBuf::from("/tmp")));
+        assert_eq!(saved.windows[1].active, 1);
+        let parsed: SavedWindows = serde_json::from_str(&serde_json::to_string(&saved).unwrap()).unwrap();
+        assert_eq!(parsed, saved);
+    }
+
+    #[test]
+    fn missing_fields_use_defaults() {
+        let parsed: Session = serde_json::from_str(r#"{"tabs":[{}]}"#).unwrap();
+        assert_eq!(parsed.active, 0);
+        assert_eq!(parsed.tabs, vec![TabSession::default()]);
+    }
+
+    #[test]
+    fn tail_lines_takes_last_rows_above_cursor() {
+        let mut grid = growterm_grid::Grid::new(10, 3);
+        let mut parser = growterm_vt_parser::VtParser::new();
+        for cmd in parser.parse(b"one\r\ntwo\r\nthree\r\nfour\r\n$ ") {
+            grid.apply(&cmd);
+        }
+        assert_eq!(tail_lines(&grid, 2), vec!["three", "four"]);
+        assert_eq!(tail_lines(&grid, 10), vec!["one", "two", "three", "four"]);
+        assert!(tail_lines(&grid, 0).is_empty());
+    }
+
+    #[test]
+    fn replay_scrollback_joins_lines_with_crlf() {
+        let tab = TabSession {
+            scrollback: vec!["a".to_string(), "b".to_string()],
+            ..Default::default()
+        };
+        assert_eq!(replay_scrollback(&tab), b"a\r\nb\r\n");
+    }
+
+    #[test]
+    fn saver_is_throttled() {
+        let mut saver = SessionSaver { id: u64::MAX, last_saved: None, last_save_at: None };
+        let now = Instant::now();
+        assert!(saver.is_due(now));
+        saver.last_save_at = Some(now);
+        assert!(!saver.is_due(now + Duration::from_secs(1)));
+        assert!(saver.is_due(now + SAVE_INTERVAL));
+    }
+}
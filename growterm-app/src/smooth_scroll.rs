@@ -0,0 +1,218 @@
+use std::time::{Duration, Instant};
+
+/// Momentum decays with this time constant once the fingers lift.
+const FRICTION: f64 = 0.325;
+/// Below this speed (px/s) momentum stops.
+const MIN_VELOCITY: f64 = 30.0;
+/// A gesture pause longer than this means the user stopped before lifting.
+const VELOCITY_STALE: Duration = Duration::from_millis(100);
+
+/// Pixel-precise viewport scrolling for trackpads, with momentum for
+/// platforms that don't synthesize it. The grid still scrolls in whole rows;
+/// the remainder is drawn as a fractional row offset.
+#[derive(Default)]
+pub struct SmoothScroll {
+    /// Pixels scrolled past the whole rows, 0..line_height, toward scrollback
+    offset: f64,
+    /// Grid scroll offset `offset` was computed for
+    anchor: usize,
+    /// px/s, positive = toward scrollback
+    velocity: f64,
+    last_delta: Option<Instant>,
+    momentum_tick: Option<Instant>,
+    /// Mouse wheel remainder, kept apart so wheel rows round toward zero
+    wheel_accum: f64,
+}
+
+impl SmoothScroll {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Trackpad delta in pixels (positive = up). Returns whole rows to scroll the grid.
+    pub fn scroll_pixels(&mut self, delta: f64, line_height: f64, scroll_offset: usize, now: Instant) -> i32 {
+        if let Some(last) = self.last_delta {
+            let dt = now.saturating_duration_since(last).as_secs_f64();
+            if dt > 0.0 && dt < VELOCITY_STALE.as_secs_f64() {
+                // 급격한 샘플 하나에 흔들리지 않도록 평활화
+                self.velocity = 0.8 * (delta / dt) + 0.2 * self.velocity;
+            } else {
+                self.velocity = 0.0;
+            }
+        }
+        self.last_delta = Some(now);
+        self.momentum_tick = None;
+        self.wheel_accum = 0.0;
+        self.advance(delta, line_height, scroll_offset)
+    }
+
+    /// Mouse wheel delta in pixels: whole rows only, no fraction or momentum.
+    pub fn scroll_lines(&mut self, delta: f64, line_height: f64) -> i32 {
+        self.stop();
+        self.offset = 0.0;
+        self.wheel_accum += delta;
+        let rows = (self.wheel_accum / line_height).trunc();
+        self.wheel_accum -= rows * line_height;
+        rows as i32
+    }
+
+    /// Fingers lifted: keep scrolling with the last gesture velocity.
+    /// Returns true if momentum started.
+    pub fn release(&mut self, now: Instant) -> bool {
+        let fresh = self
+            .last_delta
+            .is_some_and(|t| now.saturating_duration_since(t) < VELOCITY_STALE);
+        self.last_delta = None;
+        if fresh && self.velocity.abs() >= MIN_VELOCITY {
+            self.momentum_tick = Some(now);
+            true
+        } else {
+            self.velocity = 0.0;
+            false
+        }
+    }
+
+    /// Advance momentum to `now`. Returns whole rows to scroll, or None when idle.
+    pub fn tick(&mut self, line_height: f64, scroll_offset: usize, now: Instant) -> Option<i32> {
+        let last = self.momentum_tick?;
+        let dt = now.saturating_duration_since(last).as_secs_f64();
+        let decay = (-dt / FRICTION).exp();
+        // 감쇠 구간 동안 이동한 거리의 적분
+        let distance = self.velocity * FRICTION * (1.0 - decay);
+        self.velocity *= decay;
+        if self.velocity.abs() < MIN_VELOCITY {
+            self.stop();
+        } else {
+            self.momentum_tick = Some(now);
+        }
+        Some(self.advance(distance, line_height, scroll_offset))
+    }
+
+    pub fn is_animating(&self) -> bool {
+        self.momentum_tick.is_some()
+    }
+
+    pub fn stop(&mut self) {
+        self.velocity = 0.0;
+        self.last_delta = None;
+        self.momentum_tick = None;
+    }
+
+    /// The grid scrolled `requested` rows but ended at `scroll_offset`.
+    /// Hitting either end of the scrollback drops the fraction and momentum.
+    pub fn settle(&mut self, before: usize, requested: i32, scroll_offset: usize) {
+        if before as i64 + requested as i64 != scroll_offset as i64 {
+            self.offset = 0.0;
+            self.stop();
+        }
+        self.anchor = scroll_offset;
+    }
+
+    /// Pixels to shift the view down by, if the grid is still where the
+    /// fraction was computed (output, keys or a tab switch reset it).
+    pub fn fraction(&self, scroll_offset: usize) -> f32 {
+        if self.anchor == scroll_offset {
+            self.offset as f32
+        } else {
+            0.0
+        }
+    }
+
+    fn advance(&mut self, delta: f64, line_height: f64, scroll_offset: usize) -> i32 {
+        if self.anchor != scroll_offset {
+            self.offset = 0.0;
+        }
+        let pos = self.offset + delta;
+        let rows = (pos / line_height).floor();
+        self.offset = pos - rows * line_height;
+        self.anchor = (scroll_offset as i64 + rows as i64).max(0) as usize;
+        rows as i32
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const LH: f64 = 20.0;
+
+    #[test]
+    fn pixels_accumulate_into_fraction_then_rows() {
+        let mut s = SmoothScroll::new();
+        let t0 = Instant::now();
+        assert_eq!(s.scroll_pixels(5.0, LH, 0, t0), 0);
+        assert_eq!(s.fraction(0), 5.0);
+        assert_eq!(s.scroll_pixels(30.0, LH, 0, t0 + Duration::from_millis(16)), 1);
+        s.settle(0, 1, 1);
+        assert_eq!(s.fraction(1), 15.0);
+    }
+
+    #[test]
+    fn scrolling_down_borrows_a_row() {
+        let mut s = SmoothScroll::new();
+        let t0 = Instant::now();
+        assert_eq!(s.scroll_pixels(-5.0, LH, 3, t0), -1);
+        s.settle(3, -1, 2);
+        assert_eq!(s.fraction(2), 15.0);
+    }
+
+    #[test]
+    fn hitting_bottom_clears_fraction() {
+        let mut s = SmoothScroll::new();
+        let rows = s.scroll_pixels(-5.0, LH, 0, Instant::now());
+        assert_eq!(rows, -1);
+        s.settle(0, rows, 0);
+        assert_eq!(s.fraction(0), 0.0);
+    }
+
+    #[test]
+    fn fraction_hidden_when_grid_moved_elsewhere() {
+        let mut s = SmoothScroll::new();
+        s.scroll_pixels(5.0, LH, 4, Instant::now());
+        s.settle(4, 0, 4);
+        assert_eq!(s.fraction(0), 0.0);
+    }
+
+    #[test]
+    fn wheel_scrolls_whole_rows_without_fraction() {
+        let mut s = SmoothScroll::new();
+        assert_eq!(s.scroll_lines(45.0, LH), 2);
+        s.settle(0, 2, 2);
+        assert_eq!(s.fraction(2), 0.0);
+        assert_eq!(s.scroll_lines(-45.0, LH), -2);
+    }
+
+    #[test]
+    fn momentum_continues_then_stops() {
+        let mut s = SmoothScroll::new();
+        let t0 = Instant::now();
+        let mut offset = 0usize;
+        for i in 0..5 {
+            let rows = s.scroll_pixels(16.0, LH, offset, t0 + Duration::from_millis(16 * i));
+            offset = (offset as i32 + rows) as usize;
+            s.settle(offset - rows as usize, rows, offset);
+        }
+        assert!(s.release(t0 + Duration::from_millis(70)));
+        let mut t = t0 + Duration::from_millis(70);
+        let mut moved = 0;
+        while s.is_animating() {
+            t += Duration::from_millis(16);
+            let rows = s.tick(LH, offset, t).unwrap();
+            moved += rows;
+            offset = (offset as i32 + rows) as usize;
+            s.settle(offset - rows as usize, rows, offset);
+        }
+        assert!(moved > 0);
+        assert_eq!(s.tick(LH, offset, t), None);
+    }
+
+    #[test]
+    fn release_after_pause_has_no_momentum() {
+        let mut s = SmoothScroll::new();
+        let t0 = Instant::now();
+        s.scroll_pixels(16.0, LH, 0, t0);
+        s.scroll_pixels(16.0, LH, 0, t0 + Duration::from_millis(16));
+        assert!(!s.release(t0 + Duration::from_millis(500)));
+        assert!(!s.is_animating());
+    }
+}
@@ -0,0 +1,187 @@
+use std::time::{Duration, Instant};
+
+use growterm_pty::PtyWriter;
+
+/// 프로세스 목록 조회는 비싸므로 탭마다 이 간격으로만 확인
+const CHECK_INTERVAL: Duration = Duration::from_secs(1);
+
+/// ssh options that take a value (`ssh -p 2222 host`).
+const OPTIONS_WITH_VALUE: &str = "BbcDEeFIiJLlmOoPpQRSWw";
+
+/// An `ssh` session running in the foreground of a tab.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SshTarget {
+    pub user: Option<String>,
+    pub host: String,
+    /// ssh arguments up to and including the destination, without the remote command
+    connect_args: Vec<String>,
+}
+
+impl SshTarget {
+    /// Parse an argv like `ssh -p 22 -l me host ls`. None unless it is ssh with a destination.
+    pub fn from_args(args: &[String]) -> Option<Self> {
+        let program = args.first()?;
+        let name = program.rsplit('/').next().unwrap_or(program);
+        if name != "ssh" {
+            return None;
+        }
+        let mut login = None;
+        let mut i = 1;
+        while i < args.len() {
+            let arg = &args[i];
+            if arg == "--" {
+                i += 1;
+                break;
+            }
+            let Some(flags) = arg.strip_prefix('-').filter(|f| !f.is_empty()) else {
+                break;
+            };
+            // -4vA 같은 묶음 플래그: 값을 받는 옵션이 나오면 나머지(또는 다음 인자)가 값
+            for (pos, flag) in flags.char_indices() {
+                if OPTIONS_WITH_VALUE.contains(flag) {
+                    let inline = &flags[pos + flag.len_utf8()..];
+                    let value = if inline.is_empty() {
+                        i += 1;
+                        args.get(i)?.clone()
+                    } else {
+                        inline.to_string()
+                    };
+                    if flag == 'l' {
+                        login = Some(value);
+                    }
+                    break;
+                }
+            }
+            i += 1;
+        }
+        let destination = args.get(i)?;
+        let (user, host) = parse_destination(destination);
+        Some(Self {
+            user: user.or(login),
+            host,
+            connect_args: args[1..=i].to_vec(),
+        })
+    }
+
+    /// Tab title: `user@host`, or just the host when ssh picks the user.
+    pub fn title(&self) -> String {
+        match &self.user {
+            Some(user) => format!("{user}@{}", self.host),
+            None => self.host.clone(),
+        }
+    }
+
+    /// Shell command that opens another session to the same host.
+    pub fn command(&self) -> String {
+        let mut command = String::from("ssh");
+        for arg in &self.connect_args {
+            command.push(' ');
+            command.push_str(&shell_quote(arg));
+        }
+        command
+    }
+}
+
+/// `user@host`, `host`, `ssh://user@host:port`
+fn parse_destination(destination: &str) -> (Option<String>, String) {
+    let uri = destination.strip_prefix("ssh://");
+    let rest = uri.unwrap_or(destination);
+    let (user, host) = match rest.rsplit_once('@') {
+        Some((user, host)) => (Some(user.to_string()), host),
+        None => (None, rest),
+    };
+    let host = match uri {
+        Some(_) => host.split(':').next().unwrap_or(host),
+        None => host,
+    };
+    (user, host.to_string())
+}
+
+fn shell_quote(arg: &str) -> String {
+    let safe = |c: char| c.is_ascii_alphanumeric() || "-_./@:=,+%".contains(c);
+    if !arg.is_empty() && arg.chars().all(safe) {
+        arg.to_string()
+    } else {
+        format!("'{}'", arg.replace('\'', r"'\''"))
+    }
+}
+
+/// Tracks whether a tab's foreground process is ssh.
+#[derive(Default)]
+pub struct SshWatch {
+    target: Option<SshTarget>,
+    checked_at: Option<Instant>,
+}
+
+impl SshWatch {
+    pub fn target(&self) -> Option<&SshTarget> {
+        self.target.as_ref()
+    }
+
+    /// Re-inspect the foreground process at most once per second.
+    /// Returns true if the target changed.
+    pub fn refresh(&mut self, pty: &PtyWriter, now: Instant) -> bool {
+        if self.checked_at.is_some_and(|t| now.saturating_duration_since(t) < CHECK_INTERVAL) {
+            return false;
+        }
+        self.checked_at = Some(now);
+        // 쉘 자신이 포그라운드면 ssh가 아님
+        let target = pty
+            .foreground_pid()
+            .filter(|&pid| Some(pid) != pty.child_pid())
+            .and_then(growterm_pty::process_args)
+            .and_then(|args| SshTarget::from_args(&args));
+        if target == self.target {
+            return false;
+        }
+        self.target = target;
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn target(args: &[&str]) -> Option<SshTarget> {
+        let args: Vec<String> = args.iter().map(|s| s.to_string()).collect();
+        SshTarget::from_args(&args)
+    }
+
+    #[test]
+    fn user_at_host() {
+        let t = target(&["ssh", "me@example.com"]).unwrap();
+        assert_eq!(t.title(), "me@example.com");
+        assert_eq!(t.command(), "ssh me@example.com");
+    }
+
+    #[test]
+    fn options_and_remote_command() {
+        let t = target(&["/usr/bin/ssh", "-A", "-p", "2222", "-l", "deploy", "web1", "tail", "-f", "log"]).unwrap();
+        assert_eq!(t.user.as_deref(), Some("deploy"));
+        assert_eq!(t.host, "web1");
+        assert_eq!(t.command(), "ssh -A -p 2222 -l deploy web1");
+    }
+
+    #[test]
+    fn bundled_and_inline_option_values() {
+        let t = target(&["ssh", "-4vp2222", "-iid_ed25519", "host"]).unwrap();
+        assert_eq!(t.title(), "host");
+        assert_eq!(t.command(), "ssh -4vp2222 -iid_ed25519 host");
+    }
+
+    #[test]
+    fn ssh_uri_and_quoting() {
+        let t = target(&["ssh", "-o", "ProxyCommand=nc -X 5 %h %p", "ssh://me@box:2200"]).unwrap();
+        assert_eq!(t.title(), "me@box");
+        assert_eq!(t.command(), "ssh -o 'ProxyCommand=nc -X 5 %h %p' ssh://me@box:2200");
+    }
+
+    #[test]
+    fn not_an_ssh_session() {
+        assert_eq!(target(&["vim", "me@host"]), None);
+        assert_eq!(target(&["ssh"]), None);
+        assert_eq!(target(&["ssh", "-p"]), None);
+        assert_eq!(target(&["sshd", "host"]), None);
+    }
+}
@@ -1,50 +1,138 @@
-use std::io::{Read, Write};
+use std::io::Write;
 use std::path::PathBuf;
-use std::sync::atomic::{AtomicBool, AtomicU8, Ordering};
+use std::sync::atomic::{AtomicBool, AtomicU16, AtomicU64, AtomicU8, Ordering};
 use std::sync::{Arc, Mutex};
 use std::time::Instant;
 
+use growterm_gpu_draw::tab_strip::{TabStrip, TabStripHit};
 use growterm_grid::Grid;
-use growterm_macos::MacWindow;
-use growterm_pty::PtyWriter;
+use growterm_window::TerminalWindow;
+use growterm_pty::{ChildExit, PtyOptions, PtyWriter, ReadFlow};
 use growterm_render_cmd::TerminalPalette;
-use growterm_types::Rgb;
+use growterm_types::{OscCommand, Rgb, TerminalCommand};
 use growterm_vt_parser::VtParser;
 
+use crate::command_duration::CommandDurations;
+use crate::config::WindowPadding;
 use crate::copy_mode::CopyMode;
-use crate::response_timer::ResponseTimer;
+use crate::ink_workaround::InkImeState;
+use crate::foreground::{self, ForegroundWatch};
+use crate::frame_buffer::{self, FramePublisher, FrameReader};
+use crate::link_scan::LinkScanner;
+use crate::output_marks::OutputMarks;
+use crate::output_throttle::OutputThrottle;
+use crate::response_timer::{CommandOutputCounter, ResponseTimer};
 use crate::selection::Selection;
+use crate::ssh::SshWatch;
+use crate::tab_activity::TabActivity;
 
 pub struct Tab {
     pub id: u64,
     pub terminal: Arc<Mutex<TerminalState>>,
+    /// IO 스레드가 파싱할 때마다 올리는 화면 사본. 렌더링은 이것을 읽음
+    pub frame: Mutex<FrameReader>,
     pub pty_writer: PtyWriter,
     pub dirty: Arc<AtomicBool>,
     pub sync_output: Arc<AtomicBool>,
     pub last_pty_output_at: Arc<Mutex<Option<Instant>>>,
+    /// Total bytes the IO thread has read from the PTY (latency HUD)
+    pub pty_bytes_read: Arc<AtomicU64>,
+    /// Stop reading the PTY while too much output waits to be parsed
+    pub flow_control: Arc<AtomicBool>,
     pub response_timer: ResponseTimer,
+    pub command_output: Arc<Mutex<CommandOutputCounter>>,
     pub bracketed_paste: Arc<AtomicBool>,
     pub mouse_mode: Arc<AtomicU8>,
+    /// ?1004: 포커스 변화를 ESC[I / ESC[O 로 알림
+    pub focus_reporting: Arc<AtomicBool>,
+    pub kitty_keyboard_flags: Arc<AtomicU16>,
+    /// IO 스레드가 BEL을 받으면 true, 메인 스레드가 소비
+    pub bell: Arc<AtomicBool>,
+    /// 백그라운드 탭에서 울린 벨 — 탭바에 표시, 탭 활성화 시 해제
+    pub bell_pending: bool,
+    pub command_durations: CommandDurations,
+    pub activity: TabActivity,
+    /// 포그라운드 프로세스가 ssh면 탭바에 user@host 표시
+    pub ssh: SshWatch,
+    /// OSC 0/2 제목이 없을 때 탭바에 보일 포그라운드 프로그램 이름
+    pub foreground: ForegroundWatch,
+    /// 마지막 OSC 0/2 제목. IO 스레드가 채움, 빈 제목이나 RIS면 None
+    pub osc_title: Arc<Mutex<Option<String>>>,
+    /// 쉘이 OSC 7로 알린 작업 디렉터리. 프로세스에서 못 읽을 때 씀
+    pub osc_cwd: Arc<Mutex<Option<PathBuf>>>,
+    /// 마지막 명령 출력 위치 (OSC 133 C/D). IO 스레드가 채움
+    pub output_marks: Arc<Mutex<OutputMarks>>,
+    /// 화면에 보이는 URL/파일 경로. 렌더링할 때 새 화면을 넘기면 백그라운드에서 스캔
+    pub links: LinkScanner,
+    /// 쉘 종료 시 watcher 스레드가 채움, 메인 스레드가 탭 닫기/안내 표시
+    pub child_exit: Arc<Mutex<Option<ChildExit>>>,
+    /// 종료 안내를 이미 그리드에 썼는지
+    pub exit_handled: bool,
     pub copy_mode: CopyMode,
     pub selection: Selection,
+    /// 탭을 떠날 때 저장해 두는 Ink IME 보정 상태 (claude 감지, 뒤 공백 수)
+    pub ink_ime: InkImeState,
+    /// 프로필로 연 탭의 전경/배경색. 테마가 바뀌어도 유지됨
+    pub color_override: ColorOverride,
+}
+
+/// 테마 기본 색상 위에 덮어쓰는 탭별 색상
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ColorOverride {
+    pub fg: Option<Rgb>,
+    pub bg: Option<Rgb>,
+}
+
+impl ColorOverride {
+    pub fn apply(&self, base: TerminalPalette) -> TerminalPalette {
+        TerminalPalette {
+            default_fg: self.fg.unwrap_or(base.default_fg),
+            default_bg: self.bg.unwrap_or(base.default_bg),
+            ..base
+        }
+    }
 }
 
 pub struct TerminalState {
     pub grid: Grid,
     pub vt_parser: VtParser,
     pub palette: TerminalPalette,
+    /// 라이트/다크 테마의 기본 색상. RIS는 이 값으로 되돌림
+    pub base_palette: TerminalPalette,
+}
+
+impl TerminalState {
+    /// 테마 교체. 프로그램이 OSC 10/11로 바꾼 색은 유지하고
+    /// 테마 기본값을 쓰던 색만 새 테마로 바꿈.
+    pub fn set_base_palette(&mut self, base: TerminalPalette) {
+        if self.palette.default_fg == self.base_palette.default_fg {
+            self.palette.default_fg = base.default_fg;
+        }
+        if self.palette.default_bg == self.base_palette.default_bg {
+            self.palette.default_bg = base.default_bg;
+        }
+        self.base_palette = base;
+    }
 }
 
 pub struct TabManager {
     tabs: Vec<Tab>,
     active: usize,
     next_id: u64,
+    base_palette: TerminalPalette,
+    /// 탭바 가로 스크롤 위치 (픽셀, 그릴 때 클램프)
+    tab_scroll: f32,
+    /// 마지막으로 탭바에 보이게 스크롤한 활성 탭 id
+    revealed_tab: Option<u64>,
+    /// 내용 영역 둘레 여백 (물리 픽셀)
+    padding: WindowPadding,
 }
 
 /// Info passed to the renderer for drawing the tab bar.
 pub struct TabBarInfo {
     pub titles: Vec<String>,
     pub active_index: usize,
+    pub scroll_offset: f32,
 }
 
 fn vt_capture_path_from_env_with(
@@ -98,10 +186,16 @@ impl TabManager {
             tabs: Vec::new(),
             active: 0,
             next_id: 0,
+            base_palette: TerminalPalette::default(),
+            tab_scroll: 0.0,
+            revealed_tab: None,
+            padding: WindowPadding::default(),
         }
     }
 
     pub fn add_tab(&mut self, mut tab: Tab) {
+        let base = tab.color_override.apply(self.base_palette);
+        tab.terminal.lock().unwrap().set_base_palette(base);
         tab.id = self.next_id;
         self.next_id += 1;
         let insert_at = if self.tabs.is_empty() {
@@ -113,6 +207,16 @@ impl TabManager {
         self.active = insert_at;
     }
 
+    /// 모든 탭(이후 추가되는 탭 포함)의 테마 색상을 바꾸고 다시 그리게 함
+    pub fn set_base_palette(&mut self, base: TerminalPalette) {
+        self.base_palette = base;
+        for tab in &self.tabs {
+            let base = tab.color_override.apply(base);
+            tab.terminal.lock().unwrap().set_base_palette(base);
+            tab.dirty.store(true, Ordering::Relaxed);
+        }
+    }
+
     pub fn close_tab(&mut self, index: usize) -> Option<Tab> {
         if index >= self.tabs.len() {
             return None;
@@ -177,6 +281,21 @@ impl TabManager {
     }
 
     #[allow(dead_code)]
+    /// 설정의 여백(논리 픽셀)을 배율을 곱해 저장
+    pub fn set_window_padding(&mut self, padding: WindowPadding, scale: f64) {
+        let scale = scale as f32;
+        self.padding = WindowPadding {
+            top: padding.top.max(0.0) * scale,
+            right: padding.right.max(0.0) * scale,
+            bottom: padding.bottom.max(0.0) * scale,
+            left: padding.left.max(0.0) * scale,
+        };
+    }
+
+    pub fn window_padding(&self) -> WindowPadding {
+        self.padding
+    }
+
     pub fn tabs(&self) -> &[Tab] {
         &self.tabs
     }
@@ -194,12 +313,24 @@ impl TabManager {
     /// so content fills the entire screen when drawn from y=0.
     pub fn term_rows(&self, screen_h: u32, cell_h: f32, tab_bar_h: f32, title_bar_h: f32, has_scrollback: bool) -> u16 {
         let y_off = content_y_offset(self.show_tab_bar(), tab_bar_h, title_bar_h, has_scrollback);
-        ((screen_h as f32 - y_off) / cell_h).floor().max(1.0) as u16
+        let padding = self.padding.top + self.padding.bottom;
+        ((screen_h as f32 - y_off - padding) / cell_h).floor().max(1.0) as u16
+    }
+
+    /// Terminal columns left between the left and right padding.
+    pub fn term_cols(&self, screen_w: u32, cell_w: f32) -> u16 {
+        let padding = self.padding.left + self.padding.right;
+        ((screen_w as f32 - padding) / cell_w).floor().max(1.0) as u16
     }
 
     /// Y pixel offset for mouse events — mirrors renderer y_off logic.
     pub fn mouse_y_offset(&self, tab_bar_h: f32, title_bar_h: f32, has_scrollback: bool) -> f32 {
-        content_y_offset(self.show_tab_bar(), tab_bar_h, title_bar_h, has_scrollback)
+        content_y_offset(self.show_tab_bar(), tab_bar_h, title_bar_h, has_scrollback) + self.padding.top
+    }
+
+    /// Pixel position of the first cell, for mouse hit-testing.
+    pub fn content_origin(&self, tab_bar_h: f32, title_bar_h: f32, has_scrollback: bool) -> (f32, f32) {
+        (self.padding.left, self.mouse_y_offset(tab_bar_h, title_bar_h, has_scrollback))
     }
 
     pub fn tab_bar_y(&self, title_bar_h: f32) -> f32 {
@@ -227,19 +358,44 @@ impl TabManager {
         }
     }
 
-    /// Returns the tab index at pixel x, given the screen width.
+    pub fn tab_strip(&self, screen_w: f32) -> TabStrip {
+        TabStrip::new(self.tabs.len(), screen_w, self.tab_scroll)
+    }
+
+    /// What is under pixel x in the tab bar: a tab or an overflow arrow.
     /// Returns `None` if the tab bar is not shown or x is out of range.
-    pub fn tab_index_at_x(&self, x: f32, screen_w: f32) -> Option<usize> {
-        if !self.show_tab_bar() || self.tabs.is_empty() || screen_w <= 0.0 {
+    pub fn tab_bar_hit(&self, x: f32, screen_w: f32) -> Option<TabStripHit> {
+        if !self.show_tab_bar() || screen_w <= 0.0 {
             return None;
         }
-        let tab_w = screen_w / self.tabs.len() as f32;
-        let index = (x / tab_w) as usize;
-        if index < self.tabs.len() {
-            Some(index)
-        } else {
-            None
+        self.tab_strip(screen_w).hit(x)
+    }
+
+    /// Returns the tab index at pixel x, given the screen width.
+    /// Returns `None` if the tab bar is not shown, x is out of range or on an arrow.
+    pub fn tab_index_at_x(&self, x: f32, screen_w: f32) -> Option<usize> {
+        match self.tab_bar_hit(x, screen_w)? {
+            TabStripHit::Tab(index) => Some(index),
+            TabStripHit::ScrollLeft | TabStripHit::ScrollRight => None,
+        }
+    }
+
+    /// 넘친 탭바를 가로로 스크롤 (양수면 오른쪽 탭 쪽으로)
+    pub fn scroll_tab_bar(&mut self, delta: f32, screen_w: f32) {
+        self.tab_scroll = TabStrip::new(self.tabs.len(), screen_w, self.tab_scroll + delta).scroll;
+    }
+
+    /// 활성 탭이 바뀌었으면 탭바에 보이도록 스크롤.
+    /// 사용자가 직접 스크롤한 위치는 다음 탭 전환까지 유지됨.
+    pub fn reveal_active_tab(&mut self, screen_w: f32) {
+        let Some(id) = self.active_tab().map(|tab| tab.id) else {
+            return;
+        };
+        if self.revealed_tab == Some(id) {
+            return;
         }
+        self.revealed_tab = Some(id);
+        self.tab_scroll = self.tab_strip(screen_w).scroll_to_reveal(self.active);
     }
 
     pub fn tab_bar_info(&self) -> TabBarInfo {
@@ -250,11 +406,28 @@ impl TabManager {
                 .enumerate()
                 .map(|(idx, tab)| {
                     let num = idx + 1;
-                    let label = if num <= 9 {
+                    let mut label = if num <= 9 {
                         format!("⌘{}", num)
                     } else {
                         format!("{}", num)
                     };
+                    if tab.bell_pending {
+                        label.push_str(" •");
+                    }
+                    if let Some(ssh) = tab.ssh.target() {
+                        label.push(' ');
+                        label.push_str(&ssh.title());
+                    } else if let Some(title) = tab.osc_title.lock().unwrap().as_deref() {
+                        label.push(' ');
+                        label.push_str(&foreground::short_title(title));
+                    } else if let Some(name) = tab.foreground.name() {
+                        label.push(' ');
+                        label.push_str(name);
+                    }
+                    if let Some(indicator) = tab.activity.indicator() {
+                        label.push(' ');
+                        label.push_str(indicator.symbol());
+                    }
                     if let Some(timer_text) = tab.response_timer.display_text() {
                         format!("{} {}", label, timer_text)
                     } else {
@@ -263,49 +436,113 @@ impl TabManager {
                 })
                 .collect(),
             active_index: self.active,
+            scroll_offset: self.tab_scroll,
         }
     }
 }
 
 impl Tab {
-    pub fn spawn(rows: u16, cols: u16, window: Arc<MacWindow>) -> Result<Self, std::io::Error> {
-        Self::spawn_with_cwd(rows, cols, window, None)
+    pub fn spawn_with_cwd(
+        rows: u16,
+        cols: u16,
+        window: Arc<dyn TerminalWindow>,
+        cwd: Option<&std::path::Path>,
+    ) -> Result<Self, std::io::Error> {
+        Self::spawn_with_banner(rows, cols, window, cwd, None)
     }
 
-    pub fn spawn_with_cwd(
+    /// `banner` is parsed into the grid before the PTY reader starts,
+    /// so it appears above the shell prompt without reaching the shell.
+    pub fn spawn_with_banner(
         rows: u16,
         cols: u16,
-        window: Arc<MacWindow>,
+        window: Arc<dyn TerminalWindow>,
         cwd: Option<&std::path::Path>,
+        banner: Option<&[u8]>,
     ) -> Result<Self, std::io::Error> {
-        let grid = Grid::new(cols, rows);
-        let vt_parser = VtParser::new();
+        Self::spawn_with_options(rows, cols, window, PtyOptions::new().cwd(cwd), banner)
+    }
+
+    /// Spawn with explicit PTY options (program, args, cwd). `rows`/`cols`
+    /// override the size in `options`.
+    pub fn spawn_with_options(
+        rows: u16,
+        cols: u16,
+        window: Arc<dyn TerminalWindow>,
+        options: PtyOptions,
+        banner: Option<&[u8]>,
+    ) -> Result<Self, std::io::Error> {
+        let mut grid = Grid::new(cols, rows);
+        let mut vt_parser = VtParser::new();
+        if let Some(bytes) = banner {
+            for cmd in vt_parser.parse(bytes) {
+                grid.apply(&cmd);
+            }
+        }
         let terminal = Arc::new(Mutex::new(TerminalState {
             grid,
             vt_parser,
             palette: TerminalPalette::default(),
+            base_palette: TerminalPalette::default(),
         }));
         let dirty = Arc::new(AtomicBool::new(false));
         let sync_output = Arc::new(AtomicBool::new(false));
         let last_pty_output_at = Arc::new(Mutex::new(None));
+        let pty_bytes_read = Arc::new(AtomicU64::new(0));
+        let flow_control = Arc::new(AtomicBool::new(true));
+        let command_output = Arc::new(Mutex::new(CommandOutputCounter::new()));
         let bracketed_paste = Arc::new(AtomicBool::new(false));
         let mouse_mode = Arc::new(AtomicU8::new(0));
+        let focus_reporting = Arc::new(AtomicBool::new(false));
+        let kitty_keyboard_flags = Arc::new(AtomicU16::new(0));
+        let bell = Arc::new(AtomicBool::new(false));
         let mouse_sgr = Arc::new(AtomicBool::new(false));
-        let pty_writer = match growterm_pty::spawn_with_cwd(rows, cols, cwd) {
-            Ok((reader, writer)) => {
+        let child_exit = Arc::new(Mutex::new(None));
+        let osc_title = Arc::new(Mutex::new(None));
+        let osc_cwd = Arc::new(Mutex::new(None));
+        let output_marks = Arc::new(Mutex::new(OutputMarks::new()));
+        let links = {
+            let window = Arc::clone(&window);
+            LinkScanner::new(move || window.request_redraw())
+        };
+        let (frame_publisher, frame) = frame_buffer::frame_buffer();
+        let pty_writer = match growterm_pty::spawn_with_options(&options.size(rows, cols)) {
+            Ok((reader, mut writer)) => {
                 let responder = writer.responder();
-                start_io_thread(
-                    reader,
+                {
+                    let child_exit = Arc::clone(&child_exit);
+                    let window = Arc::clone(&window);
+                    writer.on_exit(move |exit| {
+                        *child_exit.lock().unwrap() = Some(exit);
+                        window.request_redraw();
+                    });
+                }
+                let io = TabIo {
                     responder,
-                    Arc::clone(&terminal),
-                    Arc::clone(&dirty),
-                    Arc::clone(&sync_output),
-                    Arc::clone(&last_pty_output_at),
-                    Arc::clone(&bracketed_paste),
-                    Arc::clone(&mouse_mode),
-                    Arc::clone(&mouse_sgr),
+                    terminal: Arc::clone(&terminal),
+                    frame: frame_publisher,
+                    dirty: Arc::clone(&dirty),
+                    sync_output: Arc::clone(&sync_output),
+                    last_pty_output_at: Arc::clone(&last_pty_output_at),
+                    pty_bytes_read: Arc::clone(&pty_bytes_read),
+                    command_output: Arc::clone(&command_output),
+                    bracketed_paste: Arc::clone(&bracketed_paste),
+                    mouse_mode: Arc::clone(&mouse_mode),
+                    focus_reporting: Arc::clone(&focus_reporting),
+                    shared_kitty_flags: Arc::clone(&kitty_keyboard_flags),
+                    bell: Arc::clone(&bell),
+                    mouse_sgr: Arc::clone(&mouse_sgr),
+                    osc_title: Arc::clone(&osc_title),
+                    osc_cwd: Arc::clone(&osc_cwd),
+                    output_marks: Arc::clone(&output_marks),
                     window,
-                );
+                    vt_capture: open_vt_capture_file(),
+                    kitty_keyboard_flags: 0,
+                    kitty_keyboard_stack: Vec::new(),
+                    throttle: OutputThrottle::new(),
+                    flow_control: Arc::clone(&flow_control),
+                };
+                growterm_pty::register(reader, Box::new(io))?;
                 writer
             }
             Err(e) => return Err(e),
@@ -314,142 +551,296 @@ impl Tab {
         Ok(Tab {
             id: 0, // assigned by TabManager::add_tab
             terminal,
+            frame: Mutex::new(frame),
             pty_writer,
             dirty,
             sync_output,
             last_pty_output_at,
+            pty_bytes_read,
+            flow_control,
             response_timer: ResponseTimer::new(),
+            command_output,
             bracketed_paste,
             mouse_mode,
+            focus_reporting,
+            kitty_keyboard_flags,
+            bell,
+            bell_pending: false,
+            command_durations: CommandDurations::new(),
+            activity: TabActivity::new(),
+            ssh: SshWatch::default(),
+            foreground: ForegroundWatch::default(),
+            osc_title,
+            osc_cwd,
+            output_marks,
+            links,
+            child_exit,
+            exit_handled: false,
             copy_mode: CopyMode::new(),
             selection: Selection::default(),
+            ink_ime: InkImeState::new(),
+            color_override: ColorOverride::default(),
         })
     }
 }
 
-fn start_io_thread(
-    mut reader: growterm_pty::PtyReader,
+impl Tab {
+    /// Working directory of the shell: read from the process where the
+    /// platform allows it, otherwise the last OSC 7 the shell sent.
+    pub fn cwd(&self) -> Option<PathBuf> {
+        self.pty_writer
+            .child_pid()
+            .and_then(growterm_pty::child_cwd)
+            .or_else(|| self.osc_cwd.lock().unwrap().clone())
+    }
+
+    /// Names of foreground processes other than the shell (`vim`, `ssh`).
+    pub fn running_process_names(&self) -> Vec<String> {
+        self.pty_writer
+            .running_processes()
+            .into_iter()
+            .map(|p| p.name)
+            .collect()
+    }
+}
+
+/// Body of the close/quit confirmation, or `None` when nothing would be killed.
+pub fn running_processes_message(names: &[String]) -> Option<String> {
+    if names.is_empty() {
+        return None;
+    }
+    Some(format!("These processes will be terminated:\n{}", names.join("\n")))
+}
+
+/// Shown in the tab when the shell exits and `close_tab_on_exit` is off.
+pub fn exit_message(exit: ChildExit) -> String {
+    format!("\r\n[process exited with status {}]\r\n", exit.code)
+}
+
+/// Per-tab state the reactor thread needs to turn PTY output into grid
+/// updates, mode changes and query responses.
+struct TabIo {
     responder: growterm_pty::PtyResponder,
     terminal: Arc<Mutex<TerminalState>>,
+    frame: FramePublisher,
     dirty: Arc<AtomicBool>,
     sync_output: Arc<AtomicBool>,
     last_pty_output_at: Arc<Mutex<Option<Instant>>>,
+    pty_bytes_read: Arc<AtomicU64>,
+    command_output: Arc<Mutex<CommandOutputCounter>>,
     bracketed_paste: Arc<AtomicBool>,
     mouse_mode: Arc<AtomicU8>,
+    focus_reporting: Arc<AtomicBool>,
+    shared_kitty_flags: Arc<AtomicU16>,
+    bell: Arc<AtomicBool>,
     mouse_sgr: Arc<AtomicBool>,
-    window: Arc<MacWindow>,
-) {
-    std::thread::spawn(move || {
-        let mut buf = [0u8; 65536];
-        let mut vt_capture = open_vt_capture_file();
-        let mut pending_queries: Vec<u8> = Vec::new();
-        let mut kitty_keyboard_flags: u16 = 0;
-        let mut kitty_keyboard_stack: Vec<u16> = Vec::new();
-        // sync_output is now a shared Arc<AtomicBool> passed as parameter
-        loop {
-            match reader.read(&mut buf) {
-                Ok(0) => break,
-                Ok(n) => {
-                    if let Some(file) = vt_capture.as_mut() {
-                        let _ = file.write_all(&buf[..n]);
-                        let _ = file.flush();
-                    }
-                    *last_pty_output_at.lock().unwrap() = Some(Instant::now());
-                    pending_queries.extend_from_slice(&buf[..n]);
-                    let controls = extract_terminal_controls(&mut pending_queries);
-
-                    let mut responses = Vec::new();
-                    let mut state = terminal.lock().unwrap();
-                    let commands = state.vt_parser.parse(&buf[..n]);
-                    for cmd in &commands {
-                        state.grid.apply(cmd);
-                    }
-                    if state.grid.scroll_offset() == 0 {
-                        state.grid.reset_scroll();
+    osc_title: Arc<Mutex<Option<String>>>,
+    osc_cwd: Arc<Mutex<Option<PathBuf>>>,
+    output_marks: Arc<Mutex<OutputMarks>>,
+    window: Arc<dyn TerminalWindow>,
+    vt_capture: Option<std::fs::File>,
+    kitty_keyboard_flags: u16,
+    kitty_keyboard_stack: Vec<u16>,
+    throttle: OutputThrottle,
+    flow_control: Arc<AtomicBool>,
+}
+
+impl growterm_pty::PtyHandler for TabIo {
+    fn on_data(&mut self, bytes: &[u8]) -> ReadFlow {
+        if let Some(file) = self.vt_capture.as_mut() {
+            let _ = file.write_all(bytes);
+            let _ = file.flush();
+        }
+        let now = Instant::now();
+        *self.last_pty_output_at.lock().unwrap() = Some(now);
+        self.pty_bytes_read.fetch_add(bytes.len() as u64, Ordering::Relaxed);
+        let chunk = self.throttle.feed(bytes, now);
+        self.parse(&chunk);
+        self.throttle.flow(self.flow_control.load(Ordering::Relaxed))
+    }
+
+    fn on_tick(&mut self) -> ReadFlow {
+        let chunk = self.throttle.take(Instant::now());
+        self.parse(&chunk);
+        self.throttle.flow(self.flow_control.load(Ordering::Relaxed))
+    }
+
+    fn on_close(&mut self) {
+        // 남은 백로그는 버리지 않고 그리드에 반영
+        let rest = self.throttle.take_all();
+        self.parse(&rest);
+        self.window.request_redraw();
+    }
+}
+
+impl TabIo {
+    fn parse(&mut self, bytes: &[u8]) {
+        if bytes.is_empty() {
+            return;
+        }
+        let TabIo {
+            responder,
+            terminal,
+            frame,
+            dirty,
+            sync_output,
+            bracketed_paste,
+            mouse_mode,
+            focus_reporting,
+            shared_kitty_flags,
+            bell,
+            mouse_sgr,
+            osc_title,
+            osc_cwd,
+            output_marks,
+            command_output,
+            window,
+            kitty_keyboard_flags,
+            kitty_keyboard_stack,
+            ..
+        } = self;
+
+        let mut responses = Vec::new();
+        let mut state = terminal.lock().unwrap();
+        let mut saw_bell = false;
+        {
+            let TerminalState { grid, vt_parser, palette, base_palette } = &mut *state;
+            let mut command_output = command_output.lock().unwrap();
+            vt_parser.parse_with(bytes, |cmd| {
+                command_output.on_command(&cmd);
+                match &cmd {
+                    TerminalCommand::Bell => saw_bell = true,
+                    TerminalCommand::Dcs { intermediates, action, data, .. } => {
+                        if let Some(query) = dcs_query(intermediates, *action, data) {
+                            responses.push(encode_terminal_query_response(
+                                query,
+                                grid.cursor_pos(),
+                                *kitty_keyboard_flags,
+                                *palette,
+                            ));
+                        } else if let Some(response) = xtgettcap_response(intermediates, *action, data) {
+                            responses.push(response);
+                        }
                     }
-                    let cursor = state.grid.cursor_pos();
-                    for control in controls {
-                        match control {
-                            TerminalControl::Query(query) => {
-                                let response = encode_terminal_query_response(
-                                    query,
-                                    cursor,
-                                    kitty_keyboard_flags,
-                                    state.palette,
-                                );
-                                responses.push(response);
-                            }
-                            TerminalControl::KittyKeyboardPush(flags) => {
-                                kitty_keyboard_stack.push(kitty_keyboard_flags);
-                                kitty_keyboard_flags = flags;
-                            }
-                            TerminalControl::KittyKeyboardPop(count) => {
-                                let mut remaining = count.max(1);
-                                while remaining > 0 {
-                                    if let Some(prev) = kitty_keyboard_stack.pop() {
-                                        kitty_keyboard_flags = prev;
-                                    } else {
-                                        kitty_keyboard_flags = 0;
-                                        break;
-                                    }
-                                    remaining -= 1;
-                                }
-                            }
-                            TerminalControl::SetDefaultForegroundColor(color) => {
-                                state.palette.default_fg = color;
-                            }
-                            TerminalControl::SetDefaultBackgroundColor(color) => {
-                                state.palette.default_bg = color;
-                            }
-                            TerminalControl::SyncOutputBegin => {
-                                sync_output.store(true, Ordering::Relaxed);
-                            }
-                            TerminalControl::SyncOutputEnd => {
-                                sync_output.store(false, Ordering::Relaxed);
+                    TerminalCommand::Osc(osc) => {
+                        match osc {
+                            OscCommand::SetTitle(title) => {
+                                *osc_title.lock().unwrap() = (!title.is_empty()).then(|| title.clone());
                             }
-                            TerminalControl::BracketedPasteEnable => {
-                                bracketed_paste.store(true, Ordering::Relaxed);
+                            OscCommand::WorkingDirectory(path) => {
+                                *osc_cwd.lock().unwrap() = Some(PathBuf::from(path));
                             }
-                            TerminalControl::BracketedPasteDisable => {
-                                bracketed_paste.store(false, Ordering::Relaxed);
-                            }
-                            TerminalControl::MouseModeSet(mode) => {
-                                mouse_mode.store(mode, Ordering::Relaxed);
-                            }
-                            TerminalControl::MouseSgrEnable => {
-                                mouse_sgr.store(true, Ordering::Relaxed);
-                            }
-                            TerminalControl::MouseSgrDisable => {
-                                mouse_sgr.store(false, Ordering::Relaxed);
+                            OscCommand::ShellMark(mark) => {
+                                let (row, col) = grid.cursor_pos();
+                                let abs_row = (grid.scrollback_len() + row as usize) as u32;
+                                output_marks.lock().unwrap().on_mark(*mark, (abs_row, col));
                             }
+                            _ => {}
+                        }
+                        if let Some(query) = apply_osc(osc, palette) {
+                            responses.push(encode_terminal_query_response(
+                                query,
+                                grid.cursor_pos(),
+                                *kitty_keyboard_flags,
+                                *palette,
+                            ));
                         }
                     }
-                    drop(state);
-
-                    for response in responses {
-                        let _ = responder.write_all_flush(response.as_bytes());
-                    }
-
-                    if !sync_output.load(Ordering::Relaxed) {
-                        // Only request redraw if dirty was previously false.
-                        // This coalesces multiple PTY reads into a single
-                        // redraw, avoiding redundant dispatch_async_f overhead.
-                        let was_clean = !dirty.swap(true, Ordering::Relaxed);
-                        if was_clean {
-                            window.request_redraw();
+                    TerminalCommand::SetMode(mode, enabled) => {
+                        match mode_change(*mode, *enabled) {
+                            Some(ModeChange::SyncOutput(on)) => sync_output.store(on, Ordering::Relaxed),
+                            Some(ModeChange::BracketedPaste(on)) => bracketed_paste.store(on, Ordering::Relaxed),
+                            Some(ModeChange::MouseMode(m)) => mouse_mode.store(m, Ordering::Relaxed),
+                            Some(ModeChange::MouseSgr(on)) => mouse_sgr.store(on, Ordering::Relaxed),
+                            Some(ModeChange::FocusReporting(on)) => focus_reporting.store(on, Ordering::Relaxed),
+                            None => {}
                         }
                     }
-                }
-                Err(e) => {
-                    if e.raw_os_error() == Some(libc::EIO) {
-                        break;
+                    TerminalCommand::QueryCursorPosition
+                    | TerminalCommand::QueryPrimaryDeviceAttributes
+                    | TerminalCommand::QuerySecondaryDeviceAttributes
+                    | TerminalCommand::QueryKittyKeyboard => {
+                        let query = match cmd {
+                            TerminalCommand::QueryCursorPosition => TerminalQuery::CursorPositionReport,
+                            TerminalCommand::QueryPrimaryDeviceAttributes => TerminalQuery::PrimaryDeviceAttributes,
+                            TerminalCommand::QuerySecondaryDeviceAttributes => TerminalQuery::SecondaryDeviceAttributes,
+                            _ => TerminalQuery::KittyKeyboardQuery,
+                        };
+                        responses.push(encode_terminal_query_response(
+                            query,
+                            grid.cursor_pos(),
+                            *kitty_keyboard_flags,
+                            *palette,
+                        ));
                     }
-                    break;
+                    TerminalCommand::PushKittyKeyboard(_)
+                    | TerminalCommand::PopKittyKeyboard(_)
+                    | TerminalCommand::SetKittyKeyboard(..) => {
+                        apply_kitty_keyboard_control(&cmd, kitty_keyboard_flags, kitty_keyboard_stack);
+                        shared_kitty_flags.store(*kitty_keyboard_flags, Ordering::Relaxed);
+                    }
+                    TerminalCommand::QueryMode(mode) => {
+                        let mode = *mode;
+                        let state = grid.private_mode(mode).or(match mode {
+                            2026 => Some(sync_output.load(Ordering::Relaxed)),
+                            2004 => Some(bracketed_paste.load(Ordering::Relaxed)),
+                            1000 | 1002 | 1003 => Some(
+                                mouse_mode.load(Ordering::Relaxed) == mouse_tracking_level(mode),
+                            ),
+                            1006 => Some(mouse_sgr.load(Ordering::Relaxed)),
+                            1004 => Some(focus_reporting.load(Ordering::Relaxed)),
+                            _ => None,
+                        });
+                        responses.push(encode_terminal_query_response(
+                            TerminalQuery::PrivateModeReport(mode, state),
+                            grid.cursor_pos(),
+                            *kitty_keyboard_flags,
+                            *palette,
+                        ));
+                    }
+                    TerminalCommand::FullReset | TerminalCommand::SoftReset => {
+                        // `reset`/`tput init` 후 앱 쪽 모드도 초기 상태로
+                        sync_output.store(false, Ordering::Relaxed);
+                        bracketed_paste.store(false, Ordering::Relaxed);
+                        mouse_mode.store(0, Ordering::Relaxed);
+                        mouse_sgr.store(false, Ordering::Relaxed);
+                        focus_reporting.store(false, Ordering::Relaxed);
+                        *kitty_keyboard_flags = 0;
+                        kitty_keyboard_stack.clear();
+                        shared_kitty_flags.store(0, Ordering::Relaxed);
+                        if matches!(cmd, TerminalCommand::FullReset) {
+                            *palette = *base_palette;
+                            *osc_title.lock().unwrap() = None;
+                        }
+                    }
+                    _ => {}
                 }
+                grid.apply(&cmd);
+            });
+        }
+        if saw_bell {
+            bell.store(true, Ordering::Relaxed);
+        }
+        if state.grid.scroll_offset() == 0 {
+            state.grid.reset_scroll();
+        }
+        frame.publish(&state.grid, state.palette);
+        drop(state);
+
+        for response in responses {
+            responder.respond(response.as_bytes());
+        }
+
+        if !sync_output.load(Ordering::Relaxed) {
+            // Only request redraw if dirty was previously false.
+            // This coalesces multiple PTY reads into a single
+            // redraw, avoiding redundant dispatch_async_f overhead.
+            let was_clean = !dirty.swap(true, Ordering::Relaxed);
+            if was_clean {
+                window.request_redraw();
             }
         }
-        window.request_redraw();
-    });
+    }
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -461,410 +852,161 @@ enum TerminalQuery {
     ForegroundColorQuery,
     BackgroundColorQuery,
     RequestStatusStringSgr,
+    /// DECRQSS for a setting we don't report
+    RequestStatusStringUnsupported,
+    /// DECRQM reply; `None` = mode not recognized
+    PrivateModeReport(u16, Option<bool>),
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
-enum TerminalControl {
-    Query(TerminalQuery),
-    KittyKeyboardPush(u16),
-    KittyKeyboardPop(u16),
-    SetDefaultForegroundColor(Rgb),
-    SetDefaultBackgroundColor(Rgb),
-    SyncOutputBegin,
-    SyncOutputEnd,
-    BracketedPasteEnable,
-    BracketedPasteDisable,
-    MouseModeSet(u8),
-    MouseSgrEnable,
-    MouseSgrDisable,
-}
-
-fn extract_terminal_controls(pending: &mut Vec<u8>) -> Vec<TerminalControl> {
-    let mut controls = Vec::new();
-    let mut i = 0usize;
-    let mut keep_from = None;
-
-    while i < pending.len() {
-        if pending[i] != 0x1b {
-            i += 1;
-            continue;
-        }
-
-        let rest = &pending[i..];
-        if rest.starts_with(b"\x1b[6n") {
-            controls.push(TerminalControl::Query(TerminalQuery::CursorPositionReport));
-            i += 4;
-            continue;
-        }
-        if rest.starts_with(b"\x1b[?2026h") {
-            controls.push(TerminalControl::SyncOutputBegin);
-            i += 8;
-            continue;
-        }
-        if rest.starts_with(b"\x1b[?2026l") {
-            controls.push(TerminalControl::SyncOutputEnd);
-            i += 8;
-            continue;
-        }
-        if rest.starts_with(b"\x1b[?2004h") {
-            controls.push(TerminalControl::BracketedPasteEnable);
-            i += 8;
-            continue;
-        }
-        if rest.starts_with(b"\x1b[?2004l") {
-            controls.push(TerminalControl::BracketedPasteDisable);
-            i += 8;
-            continue;
-        }
-        // Mouse tracking modes: ?1000, ?1002, ?1003
-        if rest.starts_with(b"\x1b[?1000h") {
-            controls.push(TerminalControl::MouseModeSet(1));
-            i += 8;
-            continue;
-        }
-        if rest.starts_with(b"\x1b[?1000l") {
-            controls.push(TerminalControl::MouseModeSet(0));
-            i += 8;
-            continue;
-        }
-        if rest.starts_with(b"\x1b[?1002h") {
-            controls.push(TerminalControl::MouseModeSet(2));
-            i += 8;
-            continue;
-        }
-        if rest.starts_with(b"\x1b[?1002l") {
-            controls.push(TerminalControl::MouseModeSet(0));
-            i += 8;
-            continue;
-        }
-        if rest.starts_with(b"\x1b[?1003h") {
-            controls.push(TerminalControl::MouseModeSet(3));
-            i += 8;
-            continue;
-        }
-        if rest.starts_with(b"\x1b[?1003l") {
-            controls.push(TerminalControl::MouseModeSet(0));
-            i += 8;
-            continue;
-        }
-        // SGR mouse encoding: ?1006
-        if rest.starts_with(b"\x1b[?1006h") {
-            controls.push(TerminalControl::MouseSgrEnable);
-            i += 8;
-            continue;
-        }
-        if rest.starts_with(b"\x1b[?1006l") {
-            controls.push(TerminalControl::MouseSgrDisable);
-            i += 8;
-            continue;
-        }
-        if rest.starts_with(b"\x1b[?u") {
-            controls.push(TerminalControl::Query(TerminalQuery::KittyKeyboardQuery));
-            i += 4;
-            continue;
-        }
-        if rest.starts_with(b"\x1b[c") {
-            controls.push(TerminalControl::Query(
-                TerminalQuery::PrimaryDeviceAttributes,
-            ));
-            i += 3;
-            continue;
-        }
-        if rest.starts_with(b"\x1b[>c") {
-            controls.push(TerminalControl::Query(
-                TerminalQuery::SecondaryDeviceAttributes,
-            ));
-            i += 4;
-            continue;
-        }
-        if rest.starts_with(b"\x1b[>0c") {
-            controls.push(TerminalControl::Query(
-                TerminalQuery::SecondaryDeviceAttributes,
-            ));
-            i += 5;
-            continue;
-        }
-        if rest.starts_with(b"\x1b]10;?\x1b\\") {
-            controls.push(TerminalControl::Query(TerminalQuery::ForegroundColorQuery));
-            i += 8;
-            continue;
-        }
-        if rest.starts_with(b"\x1b]10;?\x07") {
-            controls.push(TerminalControl::Query(TerminalQuery::ForegroundColorQuery));
-            i += 7;
-            continue;
-        }
-        if rest.starts_with(b"\x1b]11;?\x1b\\") {
-            controls.push(TerminalControl::Query(TerminalQuery::BackgroundColorQuery));
-            i += 8;
-            continue;
+/// Push/pop/set of the kitty keyboard flags. Bits the encoder doesn't
+/// support are dropped so `CSI ? u` reports what keys actually send.
+fn apply_kitty_keyboard_control(cmd: &TerminalCommand, flags: &mut u16, stack: &mut Vec<u16>) {
+    match *cmd {
+        TerminalCommand::PushKittyKeyboard(value) => {
+            stack.push(*flags);
+            *flags = value & growterm_input::KITTY_SUPPORTED_FLAGS;
         }
-        if rest.starts_with(b"\x1b]11;?\x07") {
-            controls.push(TerminalControl::Query(TerminalQuery::BackgroundColorQuery));
-            i += 7;
-            continue;
-        }
-        if rest.starts_with(b"\x1bP$qm\x1b\\") {
-            controls.push(TerminalControl::Query(
-                TerminalQuery::RequestStatusStringSgr,
-            ));
-            i += 7;
-            continue;
-        }
-        if rest.starts_with(b"\x1b]10;") || rest.starts_with(b"\x1b]11;") {
-            match parse_osc_default_color_control(rest) {
-                SequenceParse::Matched(control, consumed) => {
-                    controls.push(control);
-                    i += consumed;
-                    continue;
-                }
-                SequenceParse::NeedMore => {
-                    keep_from = Some(i);
-                    break;
-                }
-                SequenceParse::NoMatch => {
-                    if let Some(consumed) = osc_sequence_len(rest) {
-                        i += consumed;
-                        continue;
-                    }
-                    keep_from = Some(i);
+        TerminalCommand::PopKittyKeyboard(count) => {
+            let mut remaining = count.max(1);
+            while remaining > 0 {
+                if let Some(prev) = stack.pop() {
+                    *flags = prev;
+                } else {
+                    *flags = 0;
                     break;
                 }
+                remaining -= 1;
             }
         }
-
-        match parse_kitty_keyboard_control(rest) {
-            SequenceParse::Matched(control, consumed) => {
-                controls.push(control);
-                i += consumed;
-                continue;
-            }
-            SequenceParse::NeedMore => {
-                keep_from = Some(i);
-                break;
+        TerminalCommand::SetKittyKeyboard(value, mode) => {
+            let value = value & growterm_input::KITTY_SUPPORTED_FLAGS;
+            match mode {
+                2 => *flags |= value,
+                3 => *flags &= !value,
+                _ => *flags = value,
             }
-            SequenceParse::NoMatch => {}
         }
-
-        if is_known_control_prefix(rest) {
-            keep_from = Some(i);
-            break;
-        }
-
-        i += 1;
+        _ => {}
     }
-
-    if let Some(start) = keep_from {
-        pending.drain(..start);
-    } else {
-        pending.clear();
-    }
-
-    controls
-}
-
-fn is_known_control_prefix(rest: &[u8]) -> bool {
-    [
-        b"\x1b[?2026h".as_slice(),
-        b"\x1b[?2026l".as_slice(),
-        b"\x1b[?2004h".as_slice(),
-        b"\x1b[?2004l".as_slice(),
-        b"\x1b[?1000h".as_slice(),
-        b"\x1b[?1000l".as_slice(),
-        b"\x1b[?1002h".as_slice(),
-        b"\x1b[?1002l".as_slice(),
-        b"\x1b[?1003h".as_slice(),
-        b"\x1b[?1003l".as_slice(),
-        b"\x1b[?1006h".as_slice(),
-        b"\x1b[?1006l".as_slice(),
-        b"\x1b[6n".as_slice(),
-        b"\x1b[?u".as_slice(),
-        b"\x1b[c".as_slice(),
-        b"\x1b[>c".as_slice(),
-        b"\x1b[>0c".as_slice(),
-        b"\x1b]10;?\x1b\\".as_slice(),
-        b"\x1b]10;?\x07".as_slice(),
-        b"\x1b]11;?\x1b\\".as_slice(),
-        b"\x1b]11;?\x07".as_slice(),
-        b"\x1bP$qm\x1b\\".as_slice(),
-    ]
-    .iter()
-    .any(|pat| pat.starts_with(rest))
-        || b"\x1b]10;".starts_with(rest)
-        || b"\x1b]11;".starts_with(rest)
-        || is_kitty_keyboard_control_prefix(rest)
 }
 
+/// DEC private modes the IO thread mirrors into shared tab state.
+/// Screen modes (?25, ?1049) are handled by the grid itself.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
-enum SequenceParse<T> {
-    Matched(T, usize),
-    NeedMore,
-    NoMatch,
+enum ModeChange {
+    SyncOutput(bool),
+    BracketedPaste(bool),
+    /// 0 = off, 1 = normal (?1000), 2 = button (?1002), 3 = any (?1003)
+    MouseMode(u8),
+    MouseSgr(bool),
+    FocusReporting(bool),
 }
 
-fn parse_kitty_keyboard_control(rest: &[u8]) -> SequenceParse<TerminalControl> {
-    if !rest.starts_with(b"\x1b[") {
-        return SequenceParse::NoMatch;
-    }
-    if rest.len() < 3 {
-        return SequenceParse::NeedMore;
-    }
-    let mode = rest[2];
-    if mode != b'>' && mode != b'<' {
-        return SequenceParse::NoMatch;
-    }
-    if rest.len() == 3 {
-        return SequenceParse::NeedMore;
+/// DCS requests that need a response. Only DECRQSS (`DCS $ q Pt ST`) for now.
+fn dcs_query(intermediates: &[u8], action: char, data: &[u8]) -> Option<TerminalQuery> {
+    if intermediates != b"$" || action != 'q' {
+        return None;
     }
+    Some(match data {
+        b"m" => TerminalQuery::RequestStatusStringSgr,
+        _ => TerminalQuery::RequestStatusStringUnsupported,
+    })
+}
 
-    let mut idx = 3usize;
-    while idx < rest.len() && rest[idx].is_ascii_digit() {
-        idx += 1;
-    }
-    if idx == rest.len() {
-        return SequenceParse::NeedMore;
-    }
-    if rest[idx] != b'u' {
-        return SequenceParse::NoMatch;
+/// Capabilities answered to XTGETTCAP besides `TN`; `None` is a boolean
+/// capability. All of them are in the bundled `growterm` terminfo entry.
+const TERMCAPS: &[(&str, Option<&str>)] = &[
+    ("Co", Some("256")),
+    ("colors", Some("256")),
+    ("RGB", Some("8/8/8")),
+    ("Tc", None),
+    ("bce", None),
+    ("setrgbf", Some("\x1b[38;2;%p1%d;%p2%d;%p3%dm")),
+    ("setrgbb", Some("\x1b[48;2;%p1%d;%p2%d;%p3%dm")),
+    ("Smulx", Some("\x1b[4:%p1%dm")),
+    ("Setulc", Some("\x1b[58:2::%p1%{65536}%/%d:%p1%{256}%/%{255}%&%d:%p1%{255}%&%dm")),
+];
+
+/// XTGETTCAP (`DCS + q name;name… ST`, names hex-encoded). One reply per
+/// name: `DCS 1 + r name=value ST`, or `DCS 0 + r name ST` if unknown.
+fn xtgettcap_response(intermediates: &[u8], action: char, data: &[u8]) -> Option<String> {
+    if intermediates != b"+" || action != 'q' {
+        return None;
     }
-
-    let digits = &rest[3..idx];
-    if mode == b'>' && digits.is_empty() {
-        return SequenceParse::NoMatch;
+    let mut response = String::new();
+    for hex_name in data.split(|&b| b == b';').filter(|name| !name.is_empty()) {
+        let hex_name = String::from_utf8_lossy(hex_name);
+        let cap = decode_hex(&hex_name).and_then(|name| termcap(&name));
+        match cap {
+            Some(Some(value)) => {
+                response.push_str(&format!("\x1bP1+r{hex_name}={}\x1b\\", encode_hex(value)));
+            }
+            Some(None) => response.push_str(&format!("\x1bP1+r{hex_name}\x1b\\")),
+            None => response.push_str(&format!("\x1bP0+r{hex_name}\x1b\\")),
+        }
     }
-
-    let value = if digits.is_empty() {
-        1
-    } else {
-        parse_u16_saturating(digits)
-    };
-
-    let control = if mode == b'>' {
-        TerminalControl::KittyKeyboardPush(value)
-    } else {
-        TerminalControl::KittyKeyboardPop(value)
-    };
-    SequenceParse::Matched(control, idx + 1)
+    (!response.is_empty()).then_some(response)
 }
 
-fn is_kitty_keyboard_control_prefix(rest: &[u8]) -> bool {
-    if !b"\x1b[".starts_with(rest) {
-        return false;
-    }
-    if rest.len() <= 2 {
-        return true;
+/// Value of capability `name`: `Some(None)` for a boolean that is set.
+/// `TN` is the TERM new PTYs get, so programs load the same description.
+fn termcap(name: &[u8]) -> Option<Option<&'static str>> {
+    if name == b"TN" {
+        return Some(Some(growterm_pty::term_name()));
     }
-    let mode = rest[2];
-    if mode != b'>' && mode != b'<' {
-        return false;
-    }
-    rest[3..]
+    TERMCAPS
         .iter()
-        .all(|byte| byte.is_ascii_digit() || *byte == b'u')
+        .find(|(cap, _)| cap.as_bytes() == name)
+        .map(|&(_, value)| value)
 }
 
-fn parse_osc_default_color_control(rest: &[u8]) -> SequenceParse<TerminalControl> {
-    let prefix = if rest.starts_with(b"\x1b]10;") {
-        (5usize, true)
-    } else if rest.starts_with(b"\x1b]11;") {
-        (5usize, false)
-    } else {
-        return SequenceParse::NoMatch;
-    };
-
-    let (payload_start, is_foreground) = prefix;
-    if rest.len() <= payload_start {
-        return SequenceParse::NeedMore;
+fn decode_hex(hex: &str) -> Option<Vec<u8>> {
+    if hex.len() % 2 != 0 {
+        return None;
     }
-
-    let Some((terminator_index, terminator_len)) = find_osc_terminator(rest) else {
-        return SequenceParse::NeedMore;
-    };
-    let payload = &rest[payload_start..terminator_index];
-    let Some(color) = parse_osc_color(payload) else {
-        return SequenceParse::NoMatch;
-    };
-
-    let control = if is_foreground {
-        TerminalControl::SetDefaultForegroundColor(color)
-    } else {
-        TerminalControl::SetDefaultBackgroundColor(color)
-    };
-    SequenceParse::Matched(control, terminator_index + terminator_len)
+    (0..hex.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(hex.get(i..i + 2)?, 16).ok())
+        .collect()
 }
 
-fn osc_sequence_len(rest: &[u8]) -> Option<usize> {
-    find_osc_terminator(rest)
-        .map(|(terminator_index, terminator_len)| terminator_index + terminator_len)
+fn encode_hex(text: &str) -> String {
+    text.bytes().map(|b| format!("{b:02X}")).collect()
 }
 
-fn find_osc_terminator(rest: &[u8]) -> Option<(usize, usize)> {
-    let mut idx = 0usize;
-    while idx < rest.len() {
-        match rest[idx] {
-            0x07 => return Some((idx, 1)),
-            0x1b => {
-                if idx + 1 >= rest.len() {
-                    return None;
-                }
-                if rest[idx + 1] == b'\\' {
-                    return Some((idx, 2));
-                }
-            }
-            _ => {}
-        }
-        idx += 1;
+/// Apply an OSC the app acts on; returns a query that needs a response.
+fn apply_osc(osc: &OscCommand, palette: &mut TerminalPalette) -> Option<TerminalQuery> {
+    match osc {
+        OscCommand::SetDefaultForeground(color) => palette.default_fg = *color,
+        OscCommand::SetDefaultBackground(color) => palette.default_bg = *color,
+        OscCommand::QueryDefaultForeground => return Some(TerminalQuery::ForegroundColorQuery),
+        OscCommand::QueryDefaultBackground => return Some(TerminalQuery::BackgroundColorQuery),
+        _ => {}
     }
     None
 }
 
-fn parse_osc_color(payload: &[u8]) -> Option<Rgb> {
-    let text = std::str::from_utf8(payload).ok()?.trim();
-    if let Some(rgb) = text.strip_prefix("rgb:") {
-        let parts: Vec<&str> = rgb.split('/').collect();
-        if parts.len() != 3 {
-            return None;
+fn mode_change(mode: u16, enabled: bool) -> Option<ModeChange> {
+    match mode {
+        2026 => Some(ModeChange::SyncOutput(enabled)),
+        2004 => Some(ModeChange::BracketedPaste(enabled)),
+        1000 | 1002 | 1003 => {
+            let tracking = if enabled { mouse_tracking_level(mode) } else { 0 };
+            Some(ModeChange::MouseMode(tracking))
         }
-        return Some(Rgb::new(
-            parse_scaled_hex(parts[0])?,
-            parse_scaled_hex(parts[1])?,
-            parse_scaled_hex(parts[2])?,
-        ));
+        1006 => Some(ModeChange::MouseSgr(enabled)),
+        1004 => Some(ModeChange::FocusReporting(enabled)),
+        _ => None,
     }
-
-    if let Some(hex) = text.strip_prefix('#') {
-        if hex.is_empty() || hex.len() % 3 != 0 {
-            return None;
-        }
-        let comp_len = hex.len() / 3;
-        if comp_len == 0 || comp_len > 4 {
-            return None;
-        }
-        return Some(Rgb::new(
-            parse_scaled_hex(&hex[..comp_len])?,
-            parse_scaled_hex(&hex[comp_len..comp_len * 2])?,
-            parse_scaled_hex(&hex[comp_len * 2..])?,
-        ));
-    }
-
-    None
 }
 
-fn parse_scaled_hex(hex: &str) -> Option<u8> {
-    if hex.is_empty() || hex.len() > 4 {
-        return None;
+fn mouse_tracking_level(mode: u16) -> u8 {
+    match mode {
+        1000 => 1,
+        1002 => 2,
+        1003 => 3,
+        _ => 0,
     }
-    let value = u16::from_str_radix(hex, 16).ok()? as u32;
-    let max = (1u32 << (hex.len() * 4)) - 1;
-    Some(((value * 255 + (max / 2)) / max) as u8)
-}
-
-fn parse_u16_saturating(bytes: &[u8]) -> u16 {
-    std::str::from_utf8(bytes)
-        .ok()
-        .and_then(|s| s.parse::<u32>().ok())
-        .map(|n| n.min(u16::MAX as u32) as u16)
-        .unwrap_or(0)
 }
 
 fn encode_terminal_query_response(
@@ -881,7 +1023,9 @@ fn encode_terminal_query_response(
         }
         TerminalQuery::PrimaryDeviceAttributes => "\x1b[?1;2c".to_string(),
         TerminalQuery::SecondaryDeviceAttributes => "\x1b[>0;95;0c".to_string(),
-        TerminalQuery::KittyKeyboardQuery => format!("\x1b[?{kitty_keyboard_flags}u"),
+        TerminalQuery::KittyKeyboardQuery => {
+            format!("\x1b[?{}u", kitty_keyboard_flags & growterm_input::KITTY_SUPPORTED_FLAGS)
+        }
         TerminalQuery::ForegroundColorQuery => {
             encode_osc_color_query_response(10, palette.default_fg)
         }
@@ -889,6 +1033,15 @@ fn encode_terminal_query_response(
             encode_osc_color_query_response(11, palette.default_bg)
         }
         TerminalQuery::RequestStatusStringSgr => "\x1bP1$r0m\x1b\\".to_string(),
+        TerminalQuery::RequestStatusStringUnsupported => "\x1bP0$r\x1b\\".to_string(),
+        TerminalQuery::PrivateModeReport(mode, state) => {
+            let value = match state {
+                Some(true) => 1,
+                Some(false) => 2,
+                None => 0,
+            };
+            format!("\x1b[?{mode};{value}$y")
+        }
     }
 }
 
@@ -1003,6 +1156,7 @@ mod tests {
         TerminalPalette {
             default_fg: growterm_types::Rgb::new(0x12, 0x34, 0x56),
             default_bg: growterm_types::Rgb::new(0x9a, 0xbc, 0xde),
+            ..TerminalPalette::DEFAULT
         }
     }
 
@@ -1021,6 +1175,7 @@ mod tests {
             grid,
             vt_parser,
             palette: TerminalPalette::default(),
+            base_palette: TerminalPalette::default(),
         }));
         let dirty = Arc::new(AtomicBool::new(false));
         // We can't create a real PtyWriter without spawning, so we test TabManager logic
@@ -1030,15 +1185,35 @@ mod tests {
         Tab {
             id: 0, // assigned by TabManager::add_tab
             terminal,
+            frame: Mutex::new(frame_buffer::frame_buffer().1),
             pty_writer,
             dirty,
             sync_output: Arc::new(AtomicBool::new(false)),
             last_pty_output_at: Arc::new(Mutex::new(None)),
+            pty_bytes_read: Arc::new(AtomicU64::new(0)),
+            flow_control: Arc::new(AtomicBool::new(true)),
             response_timer: ResponseTimer::new(),
+            command_output: Arc::new(Mutex::new(CommandOutputCounter::new())),
             bracketed_paste: Arc::new(AtomicBool::new(false)),
             mouse_mode: Arc::new(AtomicU8::new(0)),
+            focus_reporting: Arc::new(AtomicBool::new(false)),
+            kitty_keyboard_flags: Arc::new(AtomicU16::new(0)),
+            bell: Arc::new(AtomicBool::new(false)),
+            bell_pending: false,
+            command_durations: CommandDurations::new(),
+            activity: TabActivity::new(),
+            ssh: SshWatch::default(),
+            foreground: ForegroundWatch::default(),
+            osc_title: Arc::new(Mutex::new(None)),
+            osc_cwd: Arc::new(Mutex::new(None)),
+            output_marks: Arc::new(Mutex::new(OutputMarks::new())),
+            links: LinkScanner::new(|| {}),
+            child_exit: Arc::new(Mutex::new(None)),
+            exit_handled: false,
             copy_mode: CopyMode::new(),
             selection: Selection::default(),
+            ink_ime: InkImeState::new(),
+            color_override: ColorOverride::default(),
         }
     }
 
@@ -1161,77 +1336,111 @@ mod tests {
     }
 
     #[test]
-    fn extract_terminal_queries_detects_known_queries() {
-        let mut pending = b"\x1b[6n\x1b[?u\x1b[c\x1b[>0c".to_vec();
-        let controls = extract_terminal_controls(&mut pending);
+    fn tab_bar_info_shows_osc_title() {
+        let mut mgr = TabManager::new();
+        mgr.add_tab(dummy_tab());
+        *mgr.tabs()[0].osc_title.lock().unwrap() = Some("make test".into());
+        assert_eq!(mgr.tab_bar_info().titles, vec!["⌘1 make test"]);
+    }
+
+    #[test]
+    fn apply_osc_color_queries() {
+        let mut palette = test_palette();
+        assert_eq!(
+            apply_osc(&OscCommand::QueryDefaultForeground, &mut palette),
+            Some(TerminalQuery::ForegroundColorQuery)
+        );
         assert_eq!(
-            controls,
-            vec![
-                TerminalControl::Query(TerminalQuery::CursorPositionReport),
-                TerminalControl::Query(TerminalQuery::KittyKeyboardQuery),
-                TerminalControl::Query(TerminalQuery::PrimaryDeviceAttributes),
-                TerminalControl::Query(TerminalQuery::SecondaryDeviceAttributes),
-            ]
+            apply_osc(&OscCommand::QueryDefaultBackground, &mut palette),
+            Some(TerminalQuery::BackgroundColorQuery)
         );
-        assert!(pending.is_empty());
     }
 
     #[test]
-    fn extract_terminal_queries_keeps_partial_sequence() {
-        let mut pending = b"\x1b[6".to_vec();
-        let controls = extract_terminal_controls(&mut pending);
-        assert!(controls.is_empty());
-        assert_eq!(pending, b"\x1b[6");
+    fn dcs_query_detects_decrqss() {
+        assert_eq!(dcs_query(b"$", 'q', b"m"), Some(TerminalQuery::RequestStatusStringSgr));
+        assert_eq!(dcs_query(b"$", 'q', b"r"), Some(TerminalQuery::RequestStatusStringUnsupported));
+        assert_eq!(dcs_query(b"+", 'q', b"544e"), None);
+        assert_eq!(dcs_query(b"", 'q', b"m"), None);
     }
 
     #[test]
-    fn extract_terminal_queries_detects_osc_color_queries_with_bel() {
-        let mut pending = b"\x1b]10;?\x07\x1b]11;?\x07".to_vec();
-        let controls = extract_terminal_controls(&mut pending);
+    fn xtgettcap_answers_known_capabilities_in_hex() {
+        // "Co" → "256"
+        assert_eq!(
+            xtgettcap_response(b"+", 'q', b"436f").as_deref(),
+            Some("\x1bP1+r436f=323536\x1b\\")
+        );
+        // "RGB" → "8/8/8"
+        assert_eq!(
+            xtgettcap_response(b"+", 'q', b"524742").as_deref(),
+            Some("\x1bP1+r524742=382F382F38\x1b\\")
+        );
+        // "bce"는 불리언: 값 없이 1+r
         assert_eq!(
-            controls,
-            vec![
-                TerminalControl::Query(TerminalQuery::ForegroundColorQuery),
-                TerminalControl::Query(TerminalQuery::BackgroundColorQuery)
-            ]
+            xtgettcap_response(b"+", 'q', b"626365").as_deref(),
+            Some("\x1bP1+r626365\x1b\\")
         );
-        assert!(pending.is_empty());
     }
 
     #[test]
-    fn extract_terminal_controls_detects_kitty_push_query_pop() {
-        let mut pending = b"\x1b[>7u\x1b[?u\x1b[<1u".to_vec();
-        let controls = extract_terminal_controls(&mut pending);
+    fn xtgettcap_replies_per_name_and_rejects_unknown() {
+        // "Co;xx": 아는 것과 모르는 것 각각 응답
         assert_eq!(
-            controls,
-            vec![
-                TerminalControl::KittyKeyboardPush(7),
-                TerminalControl::Query(TerminalQuery::KittyKeyboardQuery),
-                TerminalControl::KittyKeyboardPop(1)
-            ]
+            xtgettcap_response(b"+", 'q', b"436F;7878").as_deref(),
+            Some("\x1bP1+r436F=323536\x1b\\\x1bP0+r7878\x1b\\")
         );
-        assert!(pending.is_empty());
+        // 잘못된 hex도 0+r
+        assert_eq!(xtgettcap_response(b"+", 'q', b"4g").as_deref(), Some("\x1bP0+r4g\x1b\\"));
+        assert_eq!(xtgettcap_response(b"$", 'q', b"436f"), None);
     }
 
     #[test]
-    fn extract_terminal_controls_detects_decrqss_sgr_query() {
-        let mut pending = b"\x1bP$qm\x1b\\".to_vec();
-        let controls = extract_terminal_controls(&mut pending);
-        assert_eq!(
-            controls,
-            vec![TerminalControl::Query(
-                TerminalQuery::RequestStatusStringSgr
-            )]
-        );
-        assert!(pending.is_empty());
+    fn xtgettcap_tn_matches_spawned_term() {
+        use std::io::Read;
+        let opts = PtyOptions::new().program("/bin/sh").args(["-c", "printf '<%s>' \"$TERM\""]);
+        let (mut reader, _writer) = growterm_pty::spawn_with_options(&opts).unwrap();
+        let mut out = Vec::new();
+        let mut buf = [0u8; 256];
+        // 자식이 끝나면 EOF 또는 EIO
+        while let Ok(n) = reader.read(&mut buf) {
+            if n == 0 {
+                break;
+            }
+            out.extend_from_slice(&buf[..n]);
+        }
+        let out = String::from_utf8_lossy(&out);
+        let term = out.split('<').nth(1).and_then(|rest| rest.split('>').next()).expect("TERM printed");
+
+        let response = xtgettcap_response(b"+", 'q', b"544E").unwrap();
+        assert_eq!(response, format!("\x1bP1+r544E={}\x1b\\", encode_hex(term)));
+    }
+
+    #[test]
+    fn kitty_keyboard_push_set_pop_follow_spec() {
+        let mut flags = 0;
+        let mut stack = Vec::new();
+        // CSI > 1 u, CSI = 8 ; 2 u → 0b1001
+        apply_kitty_keyboard_control(&TerminalCommand::PushKittyKeyboard(1), &mut flags, &mut stack);
+        apply_kitty_keyboard_control(&TerminalCommand::SetKittyKeyboard(8, 2), &mut flags, &mut stack);
+        assert_eq!(flags, 9);
+        // CSI = 1 ; 3 u → 0b1000
+        apply_kitty_keyboard_control(&TerminalCommand::SetKittyKeyboard(1, 3), &mut flags, &mut stack);
+        assert_eq!(flags, 8);
+        // CSI = 31 u → 모든 플래그로 교체
+        apply_kitty_keyboard_control(&TerminalCommand::SetKittyKeyboard(31, 1), &mut flags, &mut stack);
+        assert_eq!(flags, 31);
+        // CSI < u → push 전 값
+        apply_kitty_keyboard_control(&TerminalCommand::PopKittyKeyboard(1), &mut flags, &mut stack);
+        assert_eq!(flags, 0);
     }
 
     #[test]
-    fn extract_terminal_controls_keeps_partial_kitty_push() {
-        let mut pending = b"\x1b[>7".to_vec();
-        let controls = extract_terminal_controls(&mut pending);
-        assert!(controls.is_empty());
-        assert_eq!(pending, b"\x1b[>7");
+    fn kitty_keyboard_push_drops_unsupported_bits() {
+        let mut flags = 0;
+        let mut stack = Vec::new();
+        apply_kitty_keyboard_control(&TerminalCommand::PushKittyKeyboard(0b10_0001), &mut flags, &mut stack);
+        assert_eq!(flags, 1);
     }
 
     #[test]
@@ -1243,6 +1452,13 @@ mod tests {
             test_palette(),
         );
         assert_eq!(response, "\x1b[?7u");
+        let response = encode_terminal_query_response(
+            TerminalQuery::KittyKeyboardQuery,
+            (0, 0),
+            0b10_1011,
+            test_palette(),
+        );
+        assert_eq!(response, "\x1b[?11u");
     }
 
     #[test]
@@ -1275,55 +1491,87 @@ mod tests {
     }
 
     #[test]
-    fn extract_terminal_controls_detects_sync_output_begin() {
-        let mut pending = b"\x1b[?2026h".to_vec();
-        let controls = extract_terminal_controls(&mut pending);
-        assert_eq!(controls, vec![TerminalControl::SyncOutputBegin]);
-        assert!(pending.is_empty());
+    fn private_mode_report_response() {
+        let report = |state| {
+            encode_terminal_query_response(
+                TerminalQuery::PrivateModeReport(2027, state),
+                (0, 0),
+                0,
+                test_palette(),
+            )
+        };
+        assert_eq!(report(Some(true)), "\x1b[?2027;1$y");
+        assert_eq!(report(Some(false)), "\x1b[?2027;2$y");
+        assert_eq!(report(None), "\x1b[?2027;0$y");
+    }
+
+    #[test]
+    fn decrqss_unsupported_response_reports_invalid() {
+        let response = encode_terminal_query_response(
+            TerminalQuery::RequestStatusStringUnsupported,
+            (0, 0),
+            0,
+            test_palette(),
+        );
+        assert_eq!(response, "\x1bP0$r\x1b\\");
     }
 
     #[test]
-    fn extract_terminal_controls_detects_sync_output_end() {
-        let mut pending = b"\x1b[?2026l".to_vec();
-        let controls = extract_terminal_controls(&mut pending);
-        assert_eq!(controls, vec![TerminalControl::SyncOutputEnd]);
-        assert!(pending.is_empty());
+    fn apply_osc_sets_default_colors() {
+        let mut palette = test_palette();
+        let fg = growterm_types::Rgb::new(255, 0, 0);
+        let bg = growterm_types::Rgb::new(17, 34, 51);
+        assert_eq!(apply_osc(&OscCommand::SetDefaultForeground(fg), &mut palette), None);
+        assert_eq!(apply_osc(&OscCommand::SetDefaultBackground(bg), &mut palette), None);
+        assert_eq!(palette.default_fg, fg);
+        assert_eq!(palette.default_bg, bg);
     }
 
     #[test]
-    fn extract_terminal_controls_keeps_partial_sync_output() {
-        let mut pending = b"\x1b[?2026".to_vec();
-        let controls = extract_terminal_controls(&mut pending);
-        assert!(controls.is_empty());
-        assert_eq!(pending, b"\x1b[?2026");
+    fn base_palette_keeps_osc_overrides() {
+        let mut mgr = TabManager::new();
+        mgr.add_tab(dummy_tab());
+        let osc_bg = growterm_types::Rgb::new(1, 2, 3);
+        {
+            let mut state = mgr.tabs()[0].terminal.lock().unwrap();
+            apply_osc(&OscCommand::SetDefaultBackground(osc_bg), &mut state.palette);
+        }
+        mgr.set_base_palette(test_palette());
+        let tab = &mgr.tabs()[0];
+        let state = tab.terminal.lock().unwrap();
+        assert_eq!(state.palette.default_fg, test_palette().default_fg);
+        assert_eq!(state.palette.default_bg, osc_bg);
+        assert_eq!(state.base_palette, test_palette());
+        assert!(tab.dirty.load(Ordering::Relaxed));
     }
 
     #[test]
-    fn extract_terminal_controls_detects_sync_sequences_amid_text() {
-        let mut pending = b"abc\x1b[?2026hdef\x1b[?2026lghi".to_vec();
-        let controls = extract_terminal_controls(&mut pending);
-        assert_eq!(
-            controls,
-            vec![
-                TerminalControl::SyncOutputBegin,
-                TerminalControl::SyncOutputEnd,
-            ]
-        );
-        assert!(pending.is_empty());
+    fn added_tab_uses_current_base_palette() {
+        let mut mgr = TabManager::new();
+        mgr.set_base_palette(test_palette());
+        mgr.add_tab(dummy_tab());
+        let state = mgr.tabs()[0].terminal.lock().unwrap();
+        assert_eq!(state.palette.default_bg, test_palette().default_bg);
     }
 
     #[test]
-    fn extract_terminal_controls_detects_osc_color_set_sequences() {
-        let mut pending = b"\x1b]10;rgb:ffff/0000/0000\x07\x1b]11;#112233\x1b\\".to_vec();
-        let controls = extract_terminal_controls(&mut pending);
-        assert_eq!(
-            controls,
-            vec![
-                TerminalControl::SetDefaultForegroundColor(growterm_types::Rgb::new(255, 0, 0)),
-                TerminalControl::SetDefaultBackgroundColor(growterm_types::Rgb::new(17, 34, 51)),
-            ]
-        );
-        assert!(pending.is_empty());
+    fn color_override_survives_theme_change() {
+        let mut mgr = TabManager::new();
+        let bg = growterm_types::Rgb::new(0x40, 0, 0);
+        let mut tab = dummy_tab();
+        tab.color_override = ColorOverride { fg: None, bg: Some(bg) };
+        mgr.add_tab(tab);
+        mgr.set_base_palette(test_palette());
+        let state = mgr.tabs()[0].terminal.lock().unwrap();
+        assert_eq!(state.palette.default_bg, bg);
+        assert_eq!(state.palette.default_fg, test_palette().default_fg);
+    }
+
+    #[test]
+    fn apply_osc_ignores_other_commands() {
+        let mut palette = test_palette();
+        assert_eq!(apply_osc(&OscCommand::SetTitle("t".into()), &mut palette), None);
+        assert_eq!(palette, test_palette());
     }
 
     #[test]
@@ -1348,6 +1596,44 @@ mod tests {
         assert_eq!(mgr.tab_index_at_x(899.0, 900.0), Some(2));
     }
 
+    #[test]
+    fn tab_bar_overflow_scrolls_and_hits_arrows() {
+        let mut mgr = TabManager::new();
+        for _ in 0..10 {
+            mgr.add_tab(dummy_tab());
+        }
+        let screen_w = 800.0;
+        assert_eq!(mgr.tab_bar_hit(5.0, screen_w), Some(TabStripHit::ScrollLeft));
+        assert_eq!(mgr.tab_index_at_x(5.0, screen_w), None);
+        let first = growterm_gpu_draw::tab_strip::ARROW_WIDTH + 1.0;
+        assert_eq!(mgr.tab_index_at_x(first, screen_w), Some(0));
+        let tab_w = mgr.tab_strip(screen_w).tab_width;
+        mgr.scroll_tab_bar(tab_w, screen_w);
+        assert_eq!(mgr.tab_index_at_x(first, screen_w), Some(1));
+        mgr.scroll_tab_bar(-1.0e6, screen_w);
+        assert_eq!(mgr.tab_strip(screen_w).scroll, 0.0);
+    }
+
+    #[test]
+    fn reveal_active_tab_scrolls_once_per_switch() {
+        let mut mgr = TabManager::new();
+        for _ in 0..10 {
+            mgr.add_tab(dummy_tab());
+        }
+        let screen_w = 800.0;
+        // 마지막으로 추가된 탭이 활성 → 끝까지 스크롤
+        mgr.reveal_active_tab(screen_w);
+        let strip = mgr.tab_strip(screen_w);
+        assert_eq!(strip.scroll, strip.max_scroll());
+        // 사용자가 스크롤한 위치는 같은 탭에서 유지
+        mgr.scroll_tab_bar(-1.0e6, screen_w);
+        mgr.reveal_active_tab(screen_w);
+        assert_eq!(mgr.tab_strip(screen_w).scroll, 0.0);
+        mgr.switch_to(9);
+        mgr.reveal_active_tab(screen_w);
+        assert_eq!(mgr.tab_strip(screen_w).scroll, strip.max_scroll());
+    }
+
     #[test]
     fn tab_index_at_x_out_of_range() {
         let mut mgr = TabManager::new();
@@ -1436,6 +1722,28 @@ mod tests {
         assert_eq!(mgr.term_rows(600, 20.0, 30.0, 60.0, true), 28);
     }
 
+    #[test]
+    fn window_padding_shrinks_grid() {
+        let mut mgr = TabManager::new();
+        mgr.add_tab(dummy_tab());
+        mgr.set_window_padding(WindowPadding { top: 10.0, right: 5.0, bottom: 10.0, left: 15.0 }, 2.0);
+        // (600 - 20 - 20) / 20 = 28
+        assert_eq!(mgr.term_rows(600, 20.0, 30.0, 0.0, false), 28);
+        // (800 - 30 - 10) / 10 = 76
+        assert_eq!(mgr.term_cols(800, 10.0), 76);
+        assert_eq!(mgr.term_cols(20, 10.0), 1);
+    }
+
+    #[test]
+    fn window_padding_moves_content_origin() {
+        let mut mgr = TabManager::new();
+        mgr.add_tab(dummy_tab());
+        mgr.add_tab(dummy_tab());
+        mgr.set_window_padding(WindowPadding { top: 4.0, right: 0.0, bottom: 0.0, left: 6.0 }, 1.0);
+        assert_eq!(mgr.mouse_y_offset(30.0, 50.0, true), 34.0);
+        assert_eq!(mgr.content_origin(30.0, 50.0, true), (6.0, 34.0));
+    }
+
 
     #[test]
     fn move_tab_forward() {
@@ -1511,107 +1819,60 @@ mod tests {
     }
 
     #[test]
-    fn extract_terminal_controls_detects_bracketed_paste_enable() {
-        let mut pending = b"\x1b[?2004h".to_vec();
-        let controls = extract_terminal_controls(&mut pending);
-        assert_eq!(controls, vec![TerminalControl::BracketedPasteEnable]);
-        assert!(pending.is_empty());
-    }
-
-    #[test]
-    fn extract_terminal_controls_detects_bracketed_paste_disable() {
-        let mut pending = b"\x1b[?2004l".to_vec();
-        let controls = extract_terminal_controls(&mut pending);
-        assert_eq!(controls, vec![TerminalControl::BracketedPasteDisable]);
-        assert!(pending.is_empty());
+    fn exit_message_shows_status() {
+        assert_eq!(
+            exit_message(ChildExit { code: 127 }),
+            "\r\n[process exited with status 127]\r\n"
+        );
     }
 
     #[test]
-    fn extract_terminal_controls_keeps_partial_bracketed_paste() {
-        let mut pending = b"\x1b[?2004".to_vec();
-        let controls = extract_terminal_controls(&mut pending);
-        assert!(controls.is_empty());
-        assert_eq!(pending, b"\x1b[?2004");
+    fn running_processes_message_lists_names() {
+        assert_eq!(running_processes_message(&[]), None);
+        assert_eq!(
+            running_processes_message(&["vim".to_string(), "ssh".to_string()]).as_deref(),
+            Some("These processes will be terminated:\nvim\nssh")
+        );
     }
 
     #[test]
-    fn extract_terminal_controls_detects_mouse_normal_mode() {
-        let mut pending = b"\x1b[?1000h".to_vec();
-        let controls = extract_terminal_controls(&mut pending);
-        assert_eq!(controls, vec![TerminalControl::MouseModeSet(1)]);
-        assert!(pending.is_empty());
-
-        let mut pending = b"\x1b[?1000l".to_vec();
-        let controls = extract_terminal_controls(&mut pending);
-        assert_eq!(controls, vec![TerminalControl::MouseModeSet(0)]);
-        assert!(pending.is_empty());
+    fn mode_change_sync_output() {
+        assert_eq!(mode_change(2026, true), Some(ModeChange::SyncOutput(true)));
+        assert_eq!(mode_change(2026, false), Some(ModeChange::SyncOutput(false)));
     }
 
     #[test]
-    fn extract_terminal_controls_detects_mouse_button_mode() {
-        let mut pending = b"\x1b[?1002h".to_vec();
-        let controls = extract_terminal_controls(&mut pending);
-        assert_eq!(controls, vec![TerminalControl::MouseModeSet(2)]);
-        assert!(pending.is_empty());
-
-        let mut pending = b"\x1b[?1002l".to_vec();
-        let controls = extract_terminal_controls(&mut pending);
-        assert_eq!(controls, vec![TerminalControl::MouseModeSet(0)]);
-        assert!(pending.is_empty());
+    fn mode_change_bracketed_paste() {
+        assert_eq!(mode_change(2004, true), Some(ModeChange::BracketedPaste(true)));
+        assert_eq!(mode_change(2004, false), Some(ModeChange::BracketedPaste(false)));
     }
 
     #[test]
-    fn extract_terminal_controls_detects_mouse_any_mode() {
-        let mut pending = b"\x1b[?1003h".to_vec();
-        let controls = extract_terminal_controls(&mut pending);
-        assert_eq!(controls, vec![TerminalControl::MouseModeSet(3)]);
-        assert!(pending.is_empty());
-
-        let mut pending = b"\x1b[?1003l".to_vec();
-        let controls = extract_terminal_controls(&mut pending);
-        assert_eq!(controls, vec![TerminalControl::MouseModeSet(0)]);
-        assert!(pending.is_empty());
+    fn mode_change_focus_reporting() {
+        assert_eq!(mode_change(1004, true), Some(ModeChange::FocusReporting(true)));
+        assert_eq!(mode_change(1004, false), Some(ModeChange::FocusReporting(false)));
     }
 
     #[test]
-    fn extract_terminal_controls_detects_mouse_sgr_mode() {
-        let mut pending = b"\x1b[?1006h".to_vec();
-        let controls = extract_terminal_controls(&mut pending);
-        assert_eq!(controls, vec![TerminalControl::MouseSgrEnable]);
-        assert!(pending.is_empty());
-
-        let mut pending = b"\x1b[?1006l".to_vec();
-        let controls = extract_terminal_controls(&mut pending);
-        assert_eq!(controls, vec![TerminalControl::MouseSgrDisable]);
-        assert!(pending.is_empty());
+    fn mode_change_mouse_tracking() {
+        assert_eq!(mode_change(1000, true), Some(ModeChange::MouseMode(1)));
+        assert_eq!(mode_change(1002, true), Some(ModeChange::MouseMode(2)));
+        assert_eq!(mode_change(1003, true), Some(ModeChange::MouseMode(3)));
+        assert_eq!(mode_change(1000, false), Some(ModeChange::MouseMode(0)));
+        assert_eq!(mode_change(1003, false), Some(ModeChange::MouseMode(0)));
     }
 
     #[test]
-    fn extract_terminal_controls_keeps_partial_mouse_mode() {
-        let mut pending = b"\x1b[?1000".to_vec();
-        let controls = extract_terminal_controls(&mut pending);
-        assert!(controls.is_empty());
-        assert_eq!(pending, b"\x1b[?1000");
-
-        let mut pending = b"\x1b[?100".to_vec();
-        let controls = extract_terminal_controls(&mut pending);
-        assert!(controls.is_empty());
-        assert_eq!(pending, b"\x1b[?100");
+    fn mode_change_mouse_sgr() {
+        assert_eq!(mode_change(1006, true), Some(ModeChange::MouseSgr(true)));
+        assert_eq!(mode_change(1006, false), Some(ModeChange::MouseSgr(false)));
     }
 
     #[test]
-    fn extract_terminal_controls_detects_combined_mouse_sequences() {
-        // vim typically sends: enable any-event tracking + SGR encoding
-        let mut pending = b"\x1b[?1003h\x1b[?1006h".to_vec();
-        let controls = extract_terminal_controls(&mut pending);
-        assert_eq!(
-            controls,
-            vec![
-                TerminalControl::MouseModeSet(3),
-                TerminalControl::MouseSgrEnable,
-            ]
-        );
-        assert!(pending.is_empty());
+    fn mode_change_ignores_screen_modes() {
+        // ?25, ?1049 are applied by the grid
+        assert_eq!(mode_change(25, false), None);
+        assert_eq!(mode_change(1049, true), None);
     }
 
     #[test]
@@ -0,0 +1,121 @@
+use std::time::{Duration, Instant};
+
+/// Background-tab indicator state shown in the tab bar.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Indicator {
+    /// Output arrived while the tab was in the background
+    Activity,
+    /// Background output stopped for the configured silence period
+    Silence,
+}
+
+impl Indicator {
+    pub fn symbol(self) -> &'static str {
+        match self {
+            Indicator::Activity => "●",
+            Indicator::Silence => "○",
+        }
+    }
+}
+
+#[derive(Default)]
+pub struct TabActivity {
+    indicator: Option<Indicator>,
+    last_output_at: Option<Instant>,
+}
+
+impl TabActivity {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn on_output(&mut self, at: Instant, is_active: bool) {
+        self.last_output_at = Some(at);
+        if !is_active {
+            self.indicator = Some(Indicator::Activity);
+        }
+    }
+
+    /// Active tabs never show an indicator; a background tab with activity
+    /// flips to Silence once no output arrived for `silence_after`.
+    pub fn tick(&mut self, now: Instant, is_active: bool, silence_after: Option<Duration>) {
+        if is_active {
+            self.indicator = None;
+            return;
+        }
+        if let (Some(Indicator::Activity), Some(after), Some(last)) =
+            (self.indicator, silence_after, self.last_output_at)
+        {
+            if now.saturating_duration_since(last) >= after {
+                self.indicator = Some(Indicator::Silence);
+            }
+        }
+    }
+
+    pub fn indicator(&self) -> Option<Indicator> {
+        self.indicator
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn active_tab_output_has_no_indicator() {
+        let mut a = TabActivity::new();
+        let now = Instant::now();
+        a.on_output(now, true);
+        a.tick(now, true, None);
+        assert_eq!(a.indicator(), None);
+    }
+
+    #[test]
+    fn background_output_marks_activity() {
+        let mut a = TabActivity::new();
+        let now = Instant::now();
+        a.on_output(now, false);
+        a.tick(now, false, None);
+        assert_eq!(a.indicator(), Some(Indicator::Activity));
+    }
+
+    #[test]
+    fn activity_becomes_silence_after_period() {
+        let mut a = TabActivity::new();
+        let now = Instant::now();
+        let after = Duration::from_secs(10);
+        a.on_output(now, false);
+        a.tick(now + Duration::from_secs(5), false, Some(after));
+        assert_eq!(a.indicator(), Some(Indicator::Activity));
+        a.tick(now + after, false, Some(after));
+        assert_eq!(a.indicator(), Some(Indicator::Silence));
+    }
+
+    #[test]
+    fn new_output_after_silence_is_activity_again() {
+        let mut a = TabActivity::new();
+        let now = Instant::now();
+        let after = Duration::from_secs(1);
+        a.on_output(now, false);
+        a.tick(now + after, false, Some(after));
+        a.on_output(now + after * 2, false);
+        assert_eq!(a.indicator(), Some(Indicator::Activity));
+    }
+
+    #[test]
+    fn switching_to_tab_clears_indicator() {
+        let mut a = TabActivity::new();
+        let now = Instant::now();
+        a.on_output(now, false);
+        a.tick(now, true, None);
+        assert_eq!(a.indicator(), None);
+    }
+
+    #[test]
+    fn silence_without_prior_activity_stays_clear() {
+        let mut a = TabActivity::new();
+        let now = Instant::now();
+        a.tick(now + Duration::from_secs(100), false, Some(Duration::from_secs(1)));
+        assert_eq!(a.indicator(), None);
+    }
+}
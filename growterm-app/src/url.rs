@@ -8,7 +8,11 @@ pub fn find_url_at(text: &str, col: usize) -> Option<&str> {
 
 /// Find all URLs in the text.
 pub fn find_all_urls(text: &str) -> Vec<&str> {
-    let mut urls = Vec::new();
+    url_byte_ranges(text).into_iter().map(|(start, end)| &text[start..end]).collect()
+}
+
+fn url_byte_ranges(text: &str) -> Vec<(usize, usize)> {
+    let mut ranges = Vec::new();
     let mut search_start = 0;
     loop {
         let rest = &text[search_start..];
@@ -22,18 +26,54 @@ pub fn find_all_urls(text: &str) -> Vec<&str> {
         };
         let url_start = search_start + offset;
         let url_end = find_url_end(text, url_start);
-        urls.push(&text[url_start..url_end]);
+        ranges.push((url_start, url_end));
         search_start = if url_end > url_start { url_end } else { url_start + 1 };
         if search_start >= text.len() {
             break;
         }
     }
-    urls
+    ranges
+}
+
+/// A URL or file reference found in a line of text.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Link {
+    Url(String),
+    File(FileLink),
+}
+
+/// Every URL and file link in the text as (start, end) character ranges,
+/// left to right. A file link inside a URL isn't reported separately.
+pub fn find_all_links(text: &str) -> Vec<(usize, usize, Link)> {
+    let mut links: Vec<(usize, usize, Link)> = url_byte_ranges(text)
+        .into_iter()
+        .map(|(start, end)| (byte_to_char(text, start), byte_to_char(text, end), Link::Url(text[start..end].to_string())))
+        .collect();
+    let chars: Vec<char> = text.chars().collect();
+    let mut col = 0;
+    while col < chars.len() {
+        let Some((start, end)) = token_range_at(text, col) else {
+            col += 1;
+            continue;
+        };
+        let inside_url = links.iter().any(|&(s, e, _)| s < end && start < e);
+        // 앞의 ':'는 링크가 아니므로 그 다음 글자에서 찾음
+        let first = (start..end).find(|&i| chars[i] != ':').unwrap_or(start);
+        if !inside_url {
+            if let (Some(range), Some(link)) = (find_file_link_range_at(text, first), find_file_link_at(text, first)) {
+                links.push((range.0, range.1, Link::File(link)));
+            }
+        }
+        col = end;
+    }
+    links.sort_by_key(|&(start, _, _)| start);
+    links
 }
 
 /// Find the column (character index) range of a URL at the given column position.
 /// Returns (start_col, end_col) character indices if `col` falls within a URL range.
-pub fn find_url_range_at(text: &str, col: usize) -> Option<(usize, usize)> {
+#[cfg(test)]
+fn find_url_range_at(text: &str, col: usize) -> Option<(usize, usize)> {
     let byte_col = char_to_byte(text, col)?;
     let (byte_start, byte_end) = find_url_byte_range_at(text, byte_col)?;
     let col_start = byte_to_char(text, byte_start);
@@ -131,6 +171,129 @@ fn find_url_end(text: &str, start: usize) -> usize {
     i
 }
 
+/// A `path[:line[:column]]` reference such as `src/lib.rs:42:7` in compiler output.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FileLink {
+    pub path: String,
+    pub line: Option<u32>,
+    pub column: Option<u32>,
+}
+
+/// Find the column (character index) range of a file path at the given column.
+pub fn find_file_link_range_at(text: &str, col: usize) -> Option<(usize, usize)> {
+    let (start, end) = token_range_at(text, col)?;
+    let token: String = text.chars().skip(start).take(end - start).collect();
+    let trimmed = trim_link_token(&token);
+    parse_file_link(trimmed)?;
+    let lead = token.chars().count() - token.trim_start_matches(':').chars().count();
+    let start = start + lead;
+    let end = start + trimmed.chars().count();
+    (col >= start && col < end).then_some((start, end))
+}
+
+/// Find a file path (with optional line/column) at the given column.
+pub fn find_file_link_at(text: &str, col: usize) -> Option<FileLink> {
+    let (start, end) = find_file_link_range_at(text, col)?;
+    let token: String = text.chars().skip(start).take(end - start).collect();
+    parse_file_link(&token)
+}
+
+/// Split `path:line:col` and reject things that don't look like a path.
+/// Without a line number the token needs a `/` to count as a path.
+pub fn parse_file_link(token: &str) -> Option<FileLink> {
+    if token.is_empty() || token.contains("://") {
+        return None;
+    }
+    let mut parts = token.splitn(3, ':');
+    let path = parts.next()?;
+    let line = match parts.next() {
+        Some(s) => Some(s.parse::<u32>().ok()?),
+        None => None,
+    };
+    let column = match parts.next() {
+        Some(s) => Some(s.parse::<u32>().ok()?),
+        None => None,
+    };
+    if path.is_empty() || path.chars().all(|c| c == '.' || c == '/') {
+        return None;
+    }
+    let file_name = path.rsplit('/').next().unwrap_or(path);
+    let has_extension = file_name
+        .rsplit_once('.')
+        .map_or(false, |(stem, ext)| !stem.is_empty() && !ext.is_empty() && ext.chars().all(|c| c.is_ascii_alphanumeric()));
+    let looks_like_path = path.contains('/') || (line.is_some() && has_extension);
+    if !looks_like_path {
+        return None;
+    }
+    Some(FileLink { path: path.to_string(), line, column })
+}
+
+/// Program and arguments used to open a file link.
+/// Editors known to take a line number get one; anything else goes through `open`.
+pub fn editor_command(editor: Option<&str>, path: &str, link: &FileLink) -> (String, Vec<String>) {
+    let editor = editor.map(str::trim).filter(|e| !e.is_empty());
+    if let Some(editor) = editor {
+        let mut words = editor.split_whitespace();
+        let program = words.next().unwrap_or(editor);
+        let mut args: Vec<String> = words.map(String::from).collect();
+        let name = program.rsplit('/').next().unwrap_or(program);
+        let with_position = match (link.line, link.column) {
+            (Some(l), Some(c)) => format!("{path}:{l}:{c}"),
+            (Some(l), None) => format!("{path}:{l}"),
+            _ => path.to_string(),
+        };
+        match name {
+            "code" | "cursor" | "codium" => {
+                args.push("-g".into());
+                args.push(with_position);
+                return (program.to_string(), args);
+            }
+            "subl" | "zed" => {
+                args.push(with_position);
+                return (program.to_string(), args);
+            }
+            "mate" => {
+                if let Some(l) = link.line {
+                    args.push("-l".into());
+                    args.push(l.to_string());
+                }
+                args.push(path.to_string());
+                return (program.to_string(), args);
+            }
+            _ => {}
+        }
+    }
+    ("open".to_string(), vec![path.to_string()])
+}
+
+fn is_link_boundary(c: char) -> bool {
+    c.is_whitespace() || matches!(c, '"' | '\'' | '`' | '<' | '>' | '(' | ')' | '[' | ']' | '{' | '}' | ',')
+}
+
+/// Character range of the whitespace/bracket-delimited token containing `col`.
+fn token_range_at(text: &str, col: usize) -> Option<(usize, usize)> {
+    let chars: Vec<char> = text.chars().collect();
+    if col >= chars.len() || is_link_boundary(chars[col]) {
+        return None;
+    }
+    let mut start = col;
+    while start > 0 && !is_link_boundary(chars[start - 1]) {
+        start -= 1;
+    }
+    let mut end = col + 1;
+    while end < chars.len() && !is_link_boundary(chars[end]) {
+        end += 1;
+    }
+    Some((start, end))
+}
+
+/// Drop leading ':' and trailing punctuation (`file.rs:12:` → `file.rs:12`).
+fn trim_link_token(token: &str) -> &str {
+    token
+        .trim_start_matches(':')
+        .trim_end_matches(|c| matches!(c, '.' | ',' | ';' | ':' | '!' | '?'))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -302,4 +465,99 @@ mod tests {
         let text = "한글";
         assert_eq!(find_url_at(text, 100), None);
     }
+
+    #[test]
+    fn file_link_with_line_and_column() {
+        let text = "error at src/lib.rs:42:7 here";
+        assert_eq!(
+            find_file_link_at(text, 12),
+            Some(FileLink { path: "src/lib.rs".into(), line: Some(42), column: Some(7) })
+        );
+        assert_eq!(find_file_link_range_at(text, 9), Some((9, 24)));
+    }
+
+    #[test]
+    fn file_link_rust_arrow_prefix() {
+        let text = "  --> growterm-app/src/app.rs:10:5";
+        let link = find_file_link_at(text, 8).unwrap();
+        assert_eq!(link.path, "growterm-app/src/app.rs");
+        assert_eq!(link.line, Some(10));
+        assert_eq!(find_file_link_at(text, 2), None);
+    }
+
+    #[test]
+    fn file_link_trailing_colon_trimmed() {
+        let text = "main.c:3: warning";
+        assert_eq!(
+            find_file_link_at(text, 0),
+            Some(FileLink { path: "main.c".into(), line: Some(3), column: None })
+        );
+        assert_eq!(find_file_link_range_at(text, 0), Some((0, 8)));
+    }
+
+    #[test]
+    fn file_link_path_without_line() {
+        let text = "see ./docs/README.md";
+        let link = find_file_link_at(text, 6).unwrap();
+        assert_eq!(link.path, "./docs/README.md");
+        assert_eq!(link.line, None);
+    }
+
+    #[test]
+    fn file_link_rejects_plain_words_and_urls() {
+        assert_eq!(find_file_link_at("hello world", 2), None);
+        assert_eq!(find_file_link_at("version 1.2", 9), None);
+        assert_eq!(find_file_link_at("time 12:30", 6), None);
+        assert_eq!(find_file_link_at("https://example.com/a.rs:1", 3), None);
+        assert_eq!(find_file_link_at("lib.rs:abc", 0), None);
+    }
+
+    #[test]
+    fn file_link_in_parentheses() {
+        let text = "(src/main.rs:1)";
+        assert_eq!(find_file_link_range_at(text, 3), Some((1, 14)));
+    }
+
+    #[test]
+    fn find_all_links_reports_urls_and_paths_in_order() {
+        let text = "src/main.rs:3:5 see https://a.com/x.rs and ./notes/todo.md";
+        let links = find_all_links(text);
+        let ranges: Vec<(usize, usize)> = links.iter().map(|&(s, e, _)| (s, e)).collect();
+        assert_eq!(ranges, vec![(0, 15), (20, 38), (43, 58)]);
+        assert_eq!(links[1].2, Link::Url("https://a.com/x.rs".to_string()));
+        assert_eq!(
+            links[0].2,
+            Link::File(FileLink { path: "src/main.rs".to_string(), line: Some(3), column: Some(5) })
+        );
+    }
+
+    #[test]
+    fn find_all_links_empty_for_plain_text() {
+        assert!(find_all_links("no links here: just words.").is_empty());
+        assert!(find_all_links("").is_empty());
+    }
+
+    #[test]
+    fn editor_command_vscode_gets_goto() {
+        let link = FileLink { path: "a.rs".into(), line: Some(4), column: Some(2) };
+        assert_eq!(
+            editor_command(Some("code --wait"), "/p/a.rs", &link),
+            ("code".to_string(), vec!["--wait".to_string(), "-g".to_string(), "/p/a.rs:4:2".to_string()])
+        );
+    }
+
+    #[test]
+    fn editor_command_sublime_and_mate() {
+        let link = FileLink { path: "a.rs".into(), line: Some(4), column: None };
+        assert_eq!(editor_command(Some("/usr/local/bin/subl"), "/p/a.rs", &link).1, vec!["/p/a.rs:4"]);
+        assert_eq!(editor_command(Some("mate"), "/p/a.rs", &link).1, vec!["-l", "4", "/p/a.rs"]);
+    }
+
+    #[test]
+    fn editor_command_falls_back_to_open() {
+        let link = FileLink { path: "a.rs".into(), line: Some(4), column: None };
+        assert_eq!(editor_command(None, "/p/a.rs", &link), ("open".to_string(), vec!["/p/a.rs".to_string()]));
+        // 터미널 에디터는 GUI 앱에서 띄울 수 없으므로 open으로 대체
+        assert_eq!(editor_command(Some("vim"), "/p/a.rs", &link).0, "open");
+    }
 }
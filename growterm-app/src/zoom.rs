@@ -5,12 +5,33 @@ pub fn apply_zoom(current: f32, delta: f32) -> f32 {
     (current + delta).clamp(MIN_FONT_SIZE, MAX_FONT_SIZE)
 }
 
+/// `cell_w`/`cell_h` come from the renderer, with `line_height` and
+/// `cell_padding` already applied.
 pub fn calc_grid_size(width: u32, height: u32, cell_w: f32, cell_h: f32) -> (u16, u16) {
     let cols = (width as f32 / cell_w).floor() as u16;
     let rows = (height as f32 / cell_h).floor() as u16;
     (cols.max(1), rows.max(1))
 }
 
+/// Below this many columns the terminal is unusable, so a placeholder is drawn instead.
+pub const MIN_USABLE_COLS: u16 = 8;
+const TOO_SMALL_TEXT: &str = "Window too small";
+
+/// True when the content area can't hold a usable grid.
+/// Width/height are the drawable area in pixels (after title/tab bar offsets).
+pub fn is_too_small(width: f32, height: f32, cell_w: f32, cell_h: f32) -> bool {
+    if cell_w <= 0.0 || cell_h <= 0.0 {
+        return true;
+    }
+    (width / cell_w).floor() < MIN_USABLE_COLS as f32 || (height / cell_h).floor() < 1.0
+}
+
+/// Placeholder message truncated to fit `cols` columns.
+pub fn too_small_text(cols: u16) -> &'static str {
+    let len = (cols as usize).clamp(1, TOO_SMALL_TEXT.len());
+    &TOO_SMALL_TEXT[..len]
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -64,4 +85,30 @@ mod tests {
         // Very small window → at least 1x1
         assert_eq!(calc_grid_size(1, 1, 100.0, 100.0), (1, 1));
     }
+
+    // too-small placeholder tests
+
+    #[test]
+    fn is_too_small_narrow_window() {
+        assert!(is_too_small(70.0, 600.0, 10.0, 20.0));
+        assert!(!is_too_small(80.0, 600.0, 10.0, 20.0));
+    }
+
+    #[test]
+    fn is_too_small_short_window() {
+        assert!(is_too_small(800.0, 19.0, 10.0, 20.0));
+        assert!(is_too_small(800.0, -5.0, 10.0, 20.0));
+    }
+
+    #[test]
+    fn is_too_small_zero_cell_size() {
+        assert!(is_too_small(800.0, 600.0, 0.0, 20.0));
+    }
+
+    #[test]
+    fn too_small_text_truncates_to_cols() {
+        assert_eq!(too_small_text(80), "Window too small");
+        assert_eq!(too_small_text(6), "Window");
+        assert_eq!(too_small_text(0), "W");
+    }
 }
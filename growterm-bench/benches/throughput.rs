@@ -0,0 +1,108 @@
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use growterm_grid::Grid;
+use growterm_vt_parser::VtParser;
+
+fn corpora() -> Vec<(&'static str, Vec<u8>)> {
+    growterm_bench::CORPUS_NAMES
+        .iter()
+        .map(|name| (*name, growterm_bench::load(name).expect("known corpus")))
+        .collect()
+}
+
+fn parse(c: &mut Criterion) {
+    let mut group = c.benchmark_group("VtParser::parse");
+    for (name, bytes) in corpora() {
+        group.throughput(Throughput::Bytes(bytes.len() as u64));
+        group.bench_with_input(BenchmarkId::from_parameter(name), &bytes, |b, bytes| {
+            b.iter(|| {
+                let mut parser = VtParser::new();
+                black_box(parser.parse(bytes));
+            });
+        });
+    }
+    group.finish();
+}
+
+fn apply(c: &mut Criterion) {
+    let mut group = c.benchmark_group("Grid::apply");
+    for (name, bytes) in corpora() {
+        let commands = VtParser::new().parse(&bytes);
+        group.throughput(Throughput::Bytes(bytes.len() as u64));
+        group.bench_with_input(BenchmarkId::from_parameter(name), &commands, |b, commands| {
+            b.iter(|| {
+                let mut grid = Grid::new(80, 24);
+                for cmd in commands {
+                    grid.apply(cmd);
+                }
+                black_box(grid.cursor_pos());
+            });
+        });
+        group.bench_with_input(BenchmarkId::new("batch", name), &commands, |b, commands| {
+            b.iter(|| {
+                let mut grid = Grid::new(80, 24);
+                grid.apply_batch(commands);
+                black_box(grid.cursor_pos());
+            });
+        });
+    }
+    group.finish();
+}
+
+/// The IO thread's hot loop: parse a PTY read and apply it in 64KB chunks.
+fn parse_and_apply(c: &mut Criterion) {
+    let mut group = c.benchmark_group("parse+apply");
+    for (name, bytes) in corpora() {
+        group.throughput(Throughput::Bytes(bytes.len() as u64));
+        group.bench_with_input(BenchmarkId::from_parameter(name), &bytes, |b, bytes| {
+            b.iter(|| {
+                let mut parser = VtParser::new();
+                let mut grid = Grid::new(80, 24);
+                let mut commands = Vec::new();
+                for chunk in bytes.chunks(65536) {
+                    commands.clear();
+                    parser.parse_to(chunk, &mut commands);
+                    grid.apply_batch(&commands);
+                }
+                black_box(grid.cursor_pos());
+            });
+        });
+        group.bench_with_input(BenchmarkId::new("streaming", name), &bytes, |b, bytes| {
+            b.iter(|| {
+                let mut parser = VtParser::new();
+                let mut grid = Grid::new(80, 24);
+                for chunk in bytes.chunks(65536) {
+                    parser.parse_with(chunk, |cmd| grid.apply(&cmd));
+                }
+                black_box(grid.cursor_pos());
+            });
+        });
+    }
+    group.finish();
+}
+
+/// Per-chunk overhead: the same output handed over in small reads (a
+/// build printing line by line) versus reads coalesced by the PTY reactor.
+fn chunk_size(c: &mut Criterion) {
+    let mut group = c.benchmark_group("parse+apply/chunk");
+    let bytes = growterm_bench::load("colored").expect("known corpus");
+    group.throughput(Throughput::Bytes(bytes.len() as u64));
+    for size in [64, 512, 4096, 65536] {
+        group.bench_with_input(BenchmarkId::from_parameter(size), &bytes, |b, bytes| {
+            b.iter(|| {
+                let mut parser = VtParser::new();
+                let mut grid = Grid::new(80, 24);
+                let mut commands = Vec::new();
+                for chunk in bytes.chunks(size) {
+                    commands.clear();
+                    parser.parse_to(chunk, &mut commands);
+                    grid.apply_batch(&commands);
+                }
+                black_box(grid.cursor_pos());
+            });
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, parse, apply, parse_and_apply, chunk_size);
+criterion_main!(benches);
@@ -0,0 +1,22 @@
+//! Write the benchmark corpora to a directory for use with
+//! `GROWTERM_BENCH_FIXTURES`.
+//!
+//! cargo run -p growterm-bench --features bench-fixtures --bin generate-fixtures -- <dir>
+
+fn main() {
+    let dir = std::env::args().nth(1).unwrap_or_else(|| "bench-fixtures".to_string());
+    let dir = std::path::Path::new(&dir);
+    if let Err(e) = std::fs::create_dir_all(dir) {
+        eprintln!("Failed to create {}: {e}", dir.display());
+        std::process::exit(1);
+    }
+    for name in growterm_bench::CORPUS_NAMES {
+        let bytes = growterm_bench::generate(name).expect("known corpus");
+        let path = dir.join(format!("{name}.vt"));
+        if let Err(e) = std::fs::write(&path, &bytes) {
+            eprintln!("Failed to write {}: {e}", path.display());
+            std::process::exit(1);
+        }
+        println!("{} ({} bytes)", path.display(), bytes.len());
+    }
+}
@@ -0,0 +1,131 @@
+//! Byte corpora for parser/grid throughput benchmarks.
+//!
+//! Corpora are generated deterministically so runs are comparable across
+//! machines. Set `GROWTERM_BENCH_FIXTURES` to a directory written by the
+//! `generate-fixtures` binary (or containing captured VT streams with the
+//! same file names) to benchmark against files instead.
+
+use std::path::Path;
+
+pub const CORPUS_NAMES: [&str; 3] = ["scrolling", "colored", "cjk"];
+
+/// Small LCG so corpora don't depend on a rand crate.
+struct Lcg(u64);
+
+impl Lcg {
+    fn next(&mut self) -> u32 {
+        self.0 = self.0.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+        (self.0 >> 33) as u32
+    }
+
+    fn below(&mut self, n: u32) -> u32 {
+        self.next() % n
+    }
+}
+
+/// vtebench-style plain scrolling: full-width ASCII lines ending in CRLF.
+pub fn scrolling(lines: usize, cols: usize) -> Vec<u8> {
+    let mut rng = Lcg(1);
+    let mut out = Vec::with_capacity(lines * (cols + 2));
+    for _ in 0..lines {
+        for _ in 0..cols {
+            out.push(b'!' + rng.below(94) as u8);
+        }
+        out.extend_from_slice(b"\r\n");
+    }
+    out
+}
+
+/// Compiler/ls-like output: short words with 256-color and truecolor SGR changes.
+pub fn colored(lines: usize) -> Vec<u8> {
+    let mut rng = Lcg(2);
+    let mut out = Vec::new();
+    for _ in 0..lines {
+        for _ in 0..8 {
+            match rng.below(3) {
+                0 => out.extend_from_slice(format!("\x1b[38;5;{}m", rng.below(256)).as_bytes()),
+                1 => out.extend_from_slice(
+                    format!("\x1b[1;38;2;{};{};{}m", rng.below(256), rng.below(256), rng.below(256)).as_bytes(),
+                ),
+                _ => out.extend_from_slice(b"\x1b[4;7m"),
+            }
+            for _ in 0..(3 + rng.below(6)) {
+                out.push(b'a' + rng.below(26) as u8);
+            }
+            out.extend_from_slice(b"\x1b[0m ");
+        }
+        out.extend_from_slice(b"\r\n");
+    }
+    out
+}
+
+/// Wide CJK text mixed with ASCII, wrapping at the grid edge.
+pub fn cjk(lines: usize) -> Vec<u8> {
+    const SAMPLES: [&str; 6] = ["한글", "터미널", "漢字", "日本語", "테스트", "中文"];
+    let mut rng = Lcg(3);
+    let mut out = String::new();
+    for _ in 0..lines {
+        for _ in 0..12 {
+            out.push_str(SAMPLES[rng.below(SAMPLES.len() as u32) as usize]);
+            out.push(' ');
+        }
+        out.push_str("\r\n");
+    }
+    out.into_bytes()
+}
+
+pub fn generate(name: &str) -> Option<Vec<u8>> {
+    match name {
+        "scrolling" => Some(scrolling(10_000, 80)),
+        "colored" => Some(colored(10_000)),
+        "cjk" => Some(cjk(10_000)),
+        _ => None,
+    }
+}
+
+/// Read `<dir>/<name>.vt` from `GROWTERM_BENCH_FIXTURES` if set, else generate.
+pub fn load(name: &str) -> Option<Vec<u8>> {
+    if let Ok(dir) = std::env::var("GROWTERM_BENCH_FIXTURES") {
+        if let Ok(bytes) = std::fs::read(Path::new(&dir).join(format!("{name}.vt"))) {
+            return Some(bytes);
+        }
+    }
+    generate(name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn corpora_are_deterministic() {
+        for name in CORPUS_NAMES {
+            assert_eq!(generate(name), generate(name), "{name}");
+        }
+    }
+
+    #[test]
+    fn scrolling_has_requested_shape() {
+        let bytes = scrolling(3, 10);
+        assert_eq!(bytes.len(), 3 * 12);
+        assert!(bytes.ends_with(b"\r\n"));
+    }
+
+    #[test]
+    fn colored_contains_sgr() {
+        let text = String::from_utf8(colored(5)).unwrap();
+        assert!(text.contains("\x1b[0m"));
+        assert!(text.contains("\x1b["));
+    }
+
+    #[test]
+    fn cjk_is_valid_utf8() {
+        let text = String::from_utf8(cjk(5)).unwrap();
+        assert!(text.chars().any(|c| c as u32 >= 0x1100));
+    }
+
+    #[test]
+    fn unknown_corpus() {
+        assert_eq!(generate("nope"), None);
+    }
+}
@@ -0,0 +1,197 @@
+//! Headless terminal emulation: VT parser + grid + palette, no window or PTY.
+//!
+//! ```ignore
+//! let mut term = Terminal::new(80, 24);
+//! term.feed(b"\x1b[31mhi\x1b[0m");
+//! assert_eq!(term.row_text(0), "hi");
+//! assert_eq!(term.cursor(), (0, 2));
+//! ```
+
+use growterm_grid::{Grid, RowIter};
+use growterm_render_cmd::TerminalPalette;
+use growterm_types::{CellFlags, OscCommand, RenderCommand, TerminalCommand};
+use growterm_vt_parser::VtParser;
+
+pub struct Terminal {
+    parser: VtParser,
+    grid: Grid,
+    palette: TerminalPalette,
+    title: Option<String>,
+    bell_count: usize,
+}
+
+impl Terminal {
+    pub fn new(cols: u16, rows: u16) -> Self {
+        Self {
+            parser: VtParser::new(),
+            grid: Grid::new(cols, rows),
+            palette: TerminalPalette::default(),
+            title: None,
+            bell_count: 0,
+        }
+    }
+
+    /// Parse `bytes` and apply them to the screen. Escape sequences may be
+    /// split across calls.
+    pub fn feed(&mut self, bytes: &[u8]) {
+        let Self { parser, grid, palette, title, bell_count } = self;
+        parser.parse_with(bytes, |cmd| {
+            match &cmd {
+                TerminalCommand::Bell => *bell_count += 1,
+                TerminalCommand::Osc(OscCommand::SetTitle(t)) => *title = Some(t.clone()),
+                TerminalCommand::Osc(OscCommand::SetDefaultForeground(c)) => palette.default_fg = *c,
+                TerminalCommand::Osc(OscCommand::SetDefaultBackground(c)) => palette.default_bg = *c,
+                TerminalCommand::FullReset => {
+                    *palette = TerminalPalette::default();
+                    *title = None;
+                }
+                _ => {}
+            }
+            grid.apply(&cmd);
+        });
+    }
+
+    /// Rows of the active screen (main or alternate), ignoring any scrollback view.
+    pub fn screen(&self) -> RowIter<'_> {
+        self.grid.screen_rows()
+    }
+
+    /// Text of one screen row with trailing blanks trimmed. Wide characters
+    /// appear once; their spacer cells are skipped.
+    pub fn row_text(&self, row: usize) -> String {
+        let rows = self.grid.screen_rows();
+        let line = &rows[row];
+        let mut text = String::new();
+        let mut col = 0;
+        while col < line.len() {
+            let cell = &line[col];
+            text.push(cell.character);
+            if let Some(zerowidth) = cell.zerowidth {
+                text.push_str(zerowidth.as_str());
+            }
+            col += if cell.flags.contains(CellFlags::WIDE_CHAR) { 2 } else { 1 };
+        }
+        text.trim_end().to_string()
+    }
+
+    pub fn screen_text(&self) -> Vec<String> {
+        (0..self.grid.screen_rows().len()).map(|row| self.row_text(row)).collect()
+    }
+
+    /// (row, col) of the cursor.
+    pub fn cursor(&self) -> (u16, u16) {
+        self.grid.cursor_pos()
+    }
+
+    pub fn resize(&mut self, cols: u16, rows: u16) {
+        self.grid.resize(cols, rows);
+    }
+
+    pub fn grid(&self) -> &Grid {
+        &self.grid
+    }
+
+    pub fn grid_mut(&mut self) -> &mut Grid {
+        &mut self.grid
+    }
+
+    pub fn into_grid(self) -> Grid {
+        self.grid
+    }
+
+    pub fn palette(&self) -> TerminalPalette {
+        self.palette
+    }
+
+    /// Last title set via OSC 0/2.
+    pub fn title(&self) -> Option<&str> {
+        self.title.as_deref()
+    }
+
+    /// Number of BEL characters received so far.
+    pub fn bell_count(&self) -> usize {
+        self.bell_count
+    }
+
+    /// Draw list for the current screen, as the GUI would render it.
+    pub fn render(&self) -> Vec<RenderCommand> {
+        let cursor = self.grid.cursor_visible().then(|| self.grid.cursor_pos());
+        let rows: Vec<_> = self.grid.screen_rows().collect();
+        growterm_render_cmd::generate(&rows, cursor, None, None, self.palette)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use growterm_types::{Color, Rgb};
+
+    #[test]
+    fn feed_prints_text_and_moves_cursor() {
+        let mut term = Terminal::new(80, 24);
+        term.feed(b"hi\r\nls");
+        assert_eq!(term.row_text(0), "hi");
+        assert_eq!(term.row_text(1), "ls");
+        assert_eq!(term.cursor(), (1, 2));
+    }
+
+    #[test]
+    fn feed_handles_sequences_split_across_calls() {
+        let mut term = Terminal::new(80, 24);
+        term.feed(b"\x1b[3");
+        term.feed(b"1mR");
+        assert_eq!(term.screen()[0][0].character, 'R');
+        assert_eq!(term.screen()[0][0].fg, Color::Indexed(1));
+    }
+
+    #[test]
+    fn wide_chars_appear_once_in_row_text() {
+        let mut term = Terminal::new(80, 24);
+        term.feed("안녕".as_bytes());
+        assert_eq!(term.row_text(0), "안녕");
+        assert_eq!(term.cursor(), (0, 4));
+    }
+
+    #[test]
+    fn resize_changes_screen_size() {
+        let mut term = Terminal::new(80, 24);
+        term.resize(40, 10);
+        assert_eq!(term.screen().len(), 10);
+        assert_eq!(term.screen()[0].len(), 40);
+    }
+
+    #[test]
+    fn osc_title_and_colors() {
+        let mut term = Terminal::new(80, 24);
+        term.feed(b"\x1b]2;build\x07\x1b]11;rgb:10/20/30\x07\x07");
+        assert_eq!(term.title(), Some("build"));
+        assert_eq!(term.palette().default_bg, Rgb::new(0x10, 0x20, 0x30));
+        assert_eq!(term.bell_count(), 1);
+        term.feed(b"\x1bc");
+        assert_eq!(term.title(), None);
+        assert_eq!(term.palette(), TerminalPalette::default());
+    }
+
+    #[test]
+    fn render_includes_cursor() {
+        let mut term = Terminal::new(10, 2);
+        term.feed(b"a");
+        let commands = term.render();
+        let cursor = commands.iter().find(|c| c.row == 0 && c.col == 1).unwrap();
+        assert_eq!(cursor.fg, TerminalPalette::DEFAULT.default_bg);
+        assert_eq!(cursor.bg, TerminalPalette::DEFAULT.default_fg);
+    }
+
+    #[test]
+    fn dectcem_hides_and_shows_rendered_cursor() {
+        let mut term = Terminal::new(10, 2);
+        term.feed(b"a\x1b[?25l");
+        assert!(!term.grid().cursor_visible());
+        let cursor = term.render().into_iter().find(|c| c.row == 0 && c.col == 1).unwrap();
+        assert_eq!(cursor.bg, TerminalPalette::DEFAULT.default_bg);
+
+        term.feed(b"\x1b[?25h");
+        let cursor = term.render().into_iter().find(|c| c.row == 0 && c.col == 1).unwrap();
+        assert_eq!(cursor.bg, TerminalPalette::DEFAULT.default_fg);
+    }
+}
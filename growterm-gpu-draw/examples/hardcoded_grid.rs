@@ -1,5 +1,5 @@
 use growterm_gpu_draw::GpuDrawer;
-use growterm_types::{CellFlags, RenderCommand, Rgb};
+use growterm_types::{CellFlags, RenderCommand, Rgb, UnderlineStyle};
 use std::sync::Arc;
 use winit::application::ApplicationHandler;
 use winit::event::WindowEvent;
@@ -26,6 +26,9 @@ fn build_hardcoded_commands() -> Vec<RenderCommand> {
             fg: green,
             bg: blue_bg,
             flags: empty,
+            zerowidth: None,
+            underline_style: UnderlineStyle::Single,
+            underline_color: green,
         });
     }
 
@@ -38,6 +41,9 @@ fn build_hardcoded_commands() -> Vec<RenderCommand> {
             fg: white,
             bg: black,
             flags: empty,
+            zerowidth: None,
+            underline_style: UnderlineStyle::Single,
+            underline_color: white,
         });
     }
 
@@ -53,6 +59,9 @@ fn build_hardcoded_commands() -> Vec<RenderCommand> {
             fg: red,
             bg: black,
             flags: if wide { CellFlags::WIDE_CHAR } else { empty },
+            zerowidth: None,
+            underline_style: UnderlineStyle::Single,
+            underline_color: red,
         });
         col += if wide { 2 } else { 1 };
     }
@@ -67,6 +76,9 @@ fn build_hardcoded_commands() -> Vec<RenderCommand> {
             fg: white,
             bg: black,
             flags: CellFlags::BOLD,
+            zerowidth: None,
+            underline_style: UnderlineStyle::Single,
+            underline_color: white,
         });
     }
 
@@ -107,7 +119,7 @@ impl ApplicationHandler for App {
             }
             WindowEvent::RedrawRequested => {
                 if let Some(drawer) = &mut self.drawer {
-                    drawer.draw(&self.commands, None, None, false, None, false, 0.0, 0.0, 0.0);
+                    drawer.draw(&self.commands, None, None, false, None, false, 0.0, 0.0, 0.0, 0.0, 0.0, None, None, None, None);
                 }
             }
             _ => {}
@@ -2,7 +2,7 @@
 /// Phase 0 (types) + Phase 2 (render-cmd) + Phase 1 (gpu-draw) 파이프라인 검증
 use growterm_gpu_draw::GpuDrawer;
 use growterm_render_cmd::{generate, TerminalPalette};
-use growterm_types::{Cell, CellFlags, Color, Rgb};
+use growterm_types::{Cell, CellFlags, Color, Rgb, UnderlineStyle};
 use std::sync::Arc;
 use winit::application::ApplicationHandler;
 use winit::event::WindowEvent;
@@ -20,6 +20,9 @@ fn build_grid() -> Vec<Vec<Cell>> {
             fg: Color::Rgb(Rgb::new(0, 200, 0)),
             bg: Color::Rgb(Rgb::new(30, 30, 80)),
             flags: CellFlags::empty(),
+            zerowidth: None,
+            underline_style: UnderlineStyle::Single,
+            underline_color: Color::Default,
         })
         .collect();
     grid.push(hello);
@@ -31,6 +34,9 @@ fn build_grid() -> Vec<Vec<Cell>> {
             fg: Color::Indexed(15), // bright white
             bg: Color::Indexed(i),
             flags: CellFlags::empty(),
+            zerowidth: None,
+            underline_style: UnderlineStyle::Single,
+            underline_color: Color::Default,
         })
         .collect();
     grid.push(ansi);
@@ -48,6 +54,9 @@ fn build_grid() -> Vec<Vec<Cell>> {
             } else {
                 CellFlags::empty()
             },
+            zerowidth: None,
+            underline_style: UnderlineStyle::Single,
+            underline_color: Color::Default,
         });
     }
     grid.push(korean_cells);
@@ -60,6 +69,9 @@ fn build_grid() -> Vec<Vec<Cell>> {
             fg: Color::Rgb(Rgb::new(255, 255, 255)),
             bg: Color::Rgb(Rgb::new(0, 0, 0)),
             flags: CellFlags::INVERSE,
+            zerowidth: None,
+            underline_style: UnderlineStyle::Single,
+            underline_color: Color::Default,
         })
         .collect();
     grid.push(inverse);
@@ -72,6 +84,9 @@ fn build_grid() -> Vec<Vec<Cell>> {
             fg: Color::Rgb(Rgb::new(200, 200, 200)),
             bg: Color::Default,
             flags: CellFlags::DIM,
+            zerowidth: None,
+            underline_style: UnderlineStyle::Single,
+            underline_color: Color::Default,
         })
         .collect();
     grid.push(dim);
@@ -116,7 +131,7 @@ impl ApplicationHandler for App {
                     // Integration pipeline: Cell → generate() → draw()
                     let commands =
                         generate(&self.grid, None, None, None, TerminalPalette::default());
-                    drawer.draw(&commands, None, None, false, None, false, 0.0, 0.0, 0.0);
+                    drawer.draw(&commands, None, None, false, None, false, 0.0, 0.0, 0.0, 0.0, 0.0, None, None, None, None);
                 }
             }
             _ => {}
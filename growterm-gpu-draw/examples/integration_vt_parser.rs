@@ -47,7 +47,7 @@ fn build_grid_from_escapes() -> Vec<Vec<Cell>> {
     for cmd in &cmds {
         grid.apply(cmd);
     }
-    grid.cells().to_vec()
+    grid.screen_rows().map(|row| row.to_vec()).collect()
 }
 
 struct App {
@@ -86,7 +86,7 @@ impl ApplicationHandler for App {
                 if let Some(drawer) = &mut self.drawer {
                     let commands =
                         generate(&self.grid, None, None, None, TerminalPalette::default());
-                    drawer.draw(&commands, None, None, false, None, false, 0.0, 0.0, 0.0);
+                    drawer.draw(&commands, None, None, false, None, false, 0.0, 0.0, 0.0, 0.0, 0.0, None, None, None, None);
                 }
             }
             _ => {}
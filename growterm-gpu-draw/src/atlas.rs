@@ -5,12 +5,6 @@ use std::sync::Arc;
 
 use crate::renderer::GLYPH_LOG;
 
-use core_foundation::array::CFArray;
-use core_foundation::base::TCFType;
-use core_foundation::string::CFString;
-use core_text::font as ct_font;
-use core_text::font::CTFontRef;
-
 pub struct RasterizedGlyph {
     pub width: u32,
     pub height: u32,
@@ -19,16 +13,28 @@ pub struct RasterizedGlyph {
     pub offset_y: f32,
 }
 
+/// Horizontal positions per pixel a glyph is rasterized at when subpixel
+/// positioning is on.
+pub const SUBPIXEL_STEPS: u8 = 4;
+
 pub struct GlyphAtlas {
     font: Arc<fontdue::Font>,
     fallback_font: Arc<fontdue::Font>,
     system_font_cache: HashMap<PathBuf, fontdue::Font>,
     char_to_font_path: HashMap<char, PathBuf>,
     size: f32,
-    cache: HashMap<char, RasterizedGlyph>,
+    /// Keyed by character and subpixel step (0 = pixel aligned)
+    cache: HashMap<(char, u8), RasterizedGlyph>,
     cell_width: f32,
     cell_height: f32,
     ascent: f32,
+    baseline: f32,
+    /// Keep the fractional advance as the cell width instead of rounding up
+    subpixel_positioning: bool,
+    /// Multiplier on the font's line size
+    line_height: f32,
+    /// Extra pixels per cell, half on each side of the glyph
+    cell_padding: f32,
 }
 
 impl GlyphAtlas {
@@ -39,24 +45,40 @@ impl GlyphAtlas {
     }
 
     pub fn with_shared_fonts(size: f32, font: Arc<fontdue::Font>, fallback_font: Arc<fontdue::Font>) -> Self {
-        let metrics = font.metrics('M', size);
-        let line_metrics = font.horizontal_line_metrics(size);
-        let (cell_height, ascent) = match line_metrics {
-            Some(lm) => (lm.new_line_size, lm.ascent),
-            None => (metrics.height as f32, metrics.height as f32 * 0.8),
-        };
-
-        Self {
+        let mut atlas = Self {
             font,
             fallback_font,
             system_font_cache: HashMap::new(),
             char_to_font_path: HashMap::new(),
             size,
             cache: HashMap::new(),
-            cell_width: metrics.advance_width.ceil(),
-            cell_height: cell_height.ceil(),
-            ascent,
-        }
+            cell_width: 0.0,
+            cell_height: 0.0,
+            ascent: 0.0,
+            baseline: 0.0,
+            subpixel_positioning: false,
+            line_height: 1.0,
+            cell_padding: 0.0,
+        };
+        atlas.update_metrics();
+        atlas
+    }
+
+    /// Cell size and baseline from the font's ascent/descent/line gap.
+    fn update_metrics(&mut self) {
+        let metrics = self.font.metrics('M', self.size);
+        let (ascent, descent, line_size) = match self.font.horizontal_line_metrics(self.size) {
+            Some(lm) => (lm.ascent, lm.descent, lm.new_line_size),
+            None => {
+                let h = metrics.height as f32;
+                (h * 0.8, -h * 0.2, h)
+            }
+        };
+        self.cell_height = (line_size * self.line_height).ceil();
+        let advance = metrics.advance_width + self.cell_padding;
+        self.cell_width = if self.subpixel_positioning { advance } else { advance.ceil() };
+        self.ascent = ascent;
+        self.baseline = baseline_in_cell(ascent, descent, self.cell_height);
     }
 
     pub fn load_font(size: f32, font_path: Option<&str>) -> fontdue::Font {
@@ -101,13 +123,7 @@ impl GlyphAtlas {
         self.cache.clear();
         self.system_font_cache.clear();
         self.char_to_font_path.clear();
-        let metrics = self.font.metrics('M', size);
-        let line_metrics = self.font.horizontal_line_metrics(size);
-        match line_metrics {
-            Some(lm) => { self.cell_height = lm.new_line_size.ceil(); self.ascent = lm.ascent; }
-            None => { self.cell_height = (metrics.height as f32).ceil(); self.ascent = metrics.height as f32 * 0.8; }
-        }
-        self.cell_width = metrics.advance_width.ceil();
+        self.update_metrics();
     }
 
     pub fn set_size(&mut self, size: f32) {
@@ -115,14 +131,42 @@ impl GlyphAtlas {
         self.cache.clear();
         self.system_font_cache.clear();
         self.char_to_font_path.clear();
+        self.update_metrics();
+    }
+
+    /// Off: cells are whole pixels wide and glyphs pixel aligned. On: cells
+    /// keep the font's fractional advance and glyphs are rasterized at
+    /// `SUBPIXEL_STEPS` horizontal offsets.
+    pub fn set_subpixel_positioning(&mut self, enabled: bool) {
+        if self.subpixel_positioning == enabled {
+            return;
+        }
+        self.subpixel_positioning = enabled;
+        self.cache.clear();
+        self.update_metrics();
+    }
 
-        let metrics = self.font.metrics('M', size);
-        let line_metrics = self.font.horizontal_line_metrics(size);
-        match line_metrics {
-            Some(lm) => { self.cell_height = lm.new_line_size.ceil(); self.ascent = lm.ascent; }
-            None => { self.cell_height = (metrics.height as f32).ceil(); self.ascent = metrics.height as f32 * 0.8; }
+    pub fn subpixel_positioning(&self) -> bool {
+        self.subpixel_positioning
+    }
+
+    /// Taller rows (`line_height` > 1) and wider cells (`cell_padding`
+    /// pixels) without changing the glyphs. The glyph stays centered in
+    /// the cell; box-drawing characters still fill it.
+    pub fn set_cell_spacing(&mut self, line_height: f32, cell_padding: f32) {
+        let line_height = if line_height.is_finite() { line_height.clamp(0.5, 3.0) } else { 1.0 };
+        let cell_padding = if cell_padding.is_finite() { cell_padding.max(0.0) } else { 0.0 };
+        if (line_height, cell_padding) == (self.line_height, self.cell_padding) {
+            return;
         }
-        self.cell_width = metrics.advance_width.ceil();
+        self.line_height = line_height;
+        self.cell_padding = cell_padding;
+        self.update_metrics();
+    }
+
+    /// Distance from the cell's left edge to the glyph's pen position.
+    pub fn glyph_inset(&self) -> f32 {
+        self.cell_padding / 2.0
     }
 
     pub fn cell_size(&self) -> (f32, f32) {
@@ -133,6 +177,11 @@ impl GlyphAtlas {
         self.ascent
     }
 
+    /// Baseline offset from the top of a cell, in whole pixels.
+    pub fn baseline(&self) -> f32 {
+        self.baseline
+    }
+
     fn find_system_font(&mut self, c: char) -> bool {
         if self.char_to_font_path.contains_key(&c) {
             return true;
@@ -151,74 +200,38 @@ impl GlyphAtlas {
             }
         }
 
-        let base = ct_font::new_from_name("Helvetica", self.size as f64)
-            .expect("failed to create CT font");
-        let langs: CFArray<CFString> = CFArray::from_CFTypes(&[]);
-        let cascade = ct_font::cascade_list_for_languages(&base, &langs);
-
-        let mut utf16_buf = [0u16; 2];
-        let utf16 = c.encode_utf16(&mut utf16_buf);
-        let mut glyph_buf = [0u16; 2];
-
-        for i in 0..cascade.len() {
-            let descriptor = cascade.get(i).unwrap();
-            let candidate = ct_font::new_from_descriptor(&descriptor, self.size as f64);
-
-            let found = unsafe {
-                extern "C" {
-                    fn CTFontGetGlyphsForCharacters(
-                        font: CTFontRef,
-                        characters: *const u16,
-                        glyphs: *mut u16,
-                        count: isize,
-                    ) -> bool;
+        for path_buf in crate::system_fonts::candidates(c, self.size) {
+            // Reuse already-loaded font for this path
+            if let Some(font) = self.system_font_cache.get(&path_buf) {
+                if font.lookup_glyph_index(c) != 0 {
+                    self.char_to_font_path.insert(c, path_buf);
+                    return true;
                 }
-                CTFontGetGlyphsForCharacters(
-                    candidate.as_concrete_TypeRef(),
-                    utf16.as_ptr(),
-                    glyph_buf.as_mut_ptr(),
-                    utf16.len() as isize,
-                )
-            };
-
-            if found && glyph_buf[0] != 0 {
-                if let Some(url) = candidate.url() {
-                    if let Some(path) = url.to_path() {
-                        let path_buf = path.to_path_buf();
-                        // Reuse already-loaded font for this path
-                        if let Some(font) = self.system_font_cache.get(&path_buf) {
-                            if font.lookup_glyph_index(c) != 0 {
-                                self.char_to_font_path.insert(c, path_buf);
-                                return true;
-                            }
-                            continue;
-                        }
-                        let read_start = std::time::Instant::now();
-                        if let Ok(data) = std::fs::read(&path) {
-                            let settings = fontdue::FontSettings {
-                                scale: self.size,
-                                ..Default::default()
-                            };
-                            if let Ok(font) = fontdue::Font::from_bytes(data, settings) {
-                                let has_glyph = font.lookup_glyph_index(c) != 0;
-                                if has_glyph {
-                                    self.char_to_font_path.insert(c, path_buf.clone());
-                                }
-                                // Cache font regardless of whether it has this glyph,
-                                // to avoid re-reading the same font file from disk.
-                                if let Ok(mut guard) = GLYPH_LOG.lock() {
-                                    if let Some(f) = guard.as_mut() {
-                                        let _ = writeln!(f, "[font-disk] read+parse {:?} for '{}' (U+{:04X}) glyph={} {:.1}ms",
-                                            path_buf, c, c as u32, has_glyph, read_start.elapsed().as_secs_f64() * 1000.0);
-                                    }
-                                }
-                                self.system_font_cache.insert(path_buf, font);
-                                if has_glyph {
-                                    return true;
-                                }
-                            }
+                continue;
+            }
+            let read_start = std::time::Instant::now();
+            if let Ok(data) = std::fs::read(&path_buf) {
+                let settings = fontdue::FontSettings {
+                    scale: self.size,
+                    ..Default::default()
+                };
+                if let Ok(font) = fontdue::Font::from_bytes(data, settings) {
+                    let has_glyph = font.lookup_glyph_index(c) != 0;
+                    if has_glyph {
+                        self.char_to_font_path.insert(c, path_buf.clone());
+                    }
+                    // Cache font regardless of whether it has this glyph,
+                    // to avoid re-reading the same font file from disk.
+                    if let Ok(mut guard) = GLYPH_LOG.lock() {
+                        if let Some(f) = guard.as_mut() {
+                            let _ = writeln!(f, "[font-disk] read+parse {:?} for '{}' (U+{:04X}) glyph={} {:.1}ms",
+                                path_buf, c, c as u32, has_glyph, read_start.elapsed().as_secs_f64() * 1000.0);
                         }
                     }
+                    self.system_font_cache.insert(path_buf, font);
+                    if has_glyph {
+                        return true;
+                    }
                 }
             }
         }
@@ -226,7 +239,18 @@ impl GlyphAtlas {
     }
 
     pub fn get_or_insert(&mut self, c: char) -> &RasterizedGlyph {
-        if !self.cache.contains_key(&c) {
+        self.get_or_insert_at(c, 0)
+    }
+
+    /// The glyph shifted right by `step / SUBPIXEL_STEPS` of a pixel.
+    pub fn get_or_insert_at(&mut self, c: char, step: u8) -> &RasterizedGlyph {
+        let step = step % SUBPIXEL_STEPS;
+        if step != 0 && !self.cache.contains_key(&(c, step)) {
+            let base = self.get_or_insert_at(c, 0);
+            let shifted = shift_right(base, step as f32 / SUBPIXEL_STEPS as f32);
+            self.cache.insert((c, step), shifted);
+        }
+        if !self.cache.contains_key(&(c, 0)) {
             // find_system_font borrows &mut self, so call it before taking &self refs
             let system_font_path = if self.font.lookup_glyph_index(c) != 0 || self.fallback_font.lookup_glyph_index(c) != 0 {
                 None
@@ -247,7 +271,7 @@ impl GlyphAtlas {
             };
 
             let (metrics, bitmap) = font.rasterize(c, self.size);
-            self.cache.insert(c, RasterizedGlyph {
+            self.cache.insert((c, 0), RasterizedGlyph {
                 width: metrics.width as u32,
                 height: metrics.height as u32,
                 bitmap,
@@ -255,6 +279,81 @@ impl GlyphAtlas {
                 offset_y: metrics.ymin as f32,
             });
         }
-        self.cache.get(&c).unwrap()
+        self.cache.get(&(c, step)).unwrap()
+    }
+}
+
+/// Center the font's ascent+descent box in the cell and round the baseline
+/// to a pixel so every row sits the same way.
+fn baseline_in_cell(ascent: f32, descent: f32, cell_height: f32) -> f32 {
+    let glyph_box = ascent - descent;
+    ((cell_height - glyph_box) / 2.0 + ascent).round()
+}
+
+/// Resample a coverage bitmap `fraction` of a pixel to the right. The result
+/// is one pixel wider; fontdue can't rasterize at a fractional origin.
+fn shift_right(glyph: &RasterizedGlyph, fraction: f32) -> RasterizedGlyph {
+    if glyph.width == 0 || glyph.height == 0 {
+        return RasterizedGlyph { bitmap: Vec::new(), ..*glyph };
+    }
+    let (w, h) = (glyph.width as usize, glyph.height as usize);
+    let mut bitmap = vec![0u8; (w + 1) * h];
+    for y in 0..h {
+        let src = &glyph.bitmap[y * w..(y + 1) * w];
+        let dst = &mut bitmap[y * (w + 1)..(y + 1) * (w + 1)];
+        for x in 0..=w {
+            let here = src.get(x).copied().unwrap_or(0) as f32;
+            let left = if x > 0 { src[x - 1] as f32 } else { 0.0 };
+            dst[x] = (here * (1.0 - fraction) + left * fraction).round() as u8;
+        }
+    }
+    RasterizedGlyph {
+        width: glyph.width + 1,
+        bitmap,
+        ..*glyph
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn baseline_centers_glyph_box() {
+        // ascent 20, descent -5, line gap 3 → cell 28, 1.5px above and below
+        assert_eq!(baseline_in_cell(20.0, -5.0, 28.0), 22.0);
+        assert_eq!(baseline_in_cell(20.0, -5.0, 25.0), 20.0);
+    }
+
+    #[test]
+    fn cell_spacing_grows_cell_around_glyph() {
+        let mut atlas = GlyphAtlas::new(32.0, None);
+        let (w, h) = atlas.cell_size();
+        atlas.set_cell_spacing(1.5, 4.0);
+        let (spaced_w, spaced_h) = atlas.cell_size();
+        assert_eq!(spaced_w, w + 4.0);
+        assert!(spaced_h >= h * 1.5 - 1.0 && spaced_h > h);
+        assert_eq!(atlas.glyph_inset(), 2.0);
+        // 너무 촘촘하거나 음수인 값은 잘라냄
+        atlas.set_cell_spacing(0.1, -3.0);
+        assert_eq!(atlas.glyph_inset(), 0.0);
+        assert!(atlas.cell_size().1 < h);
+    }
+
+    #[test]
+    fn shift_right_moves_coverage() {
+        let glyph = RasterizedGlyph {
+            width: 2,
+            height: 1,
+            bitmap: vec![255, 0],
+            offset_x: 1.0,
+            offset_y: 0.0,
+        };
+        let half = shift_right(&glyph, 0.5);
+        assert_eq!(half.width, 3);
+        assert_eq!(half.bitmap, vec![128, 128, 0]);
+        assert_eq!(half.offset_x, 1.0);
+        let quarter = shift_right(&glyph, 0.25);
+        assert_eq!(quarter.bitmap, vec![191, 64, 0]);
     }
 }
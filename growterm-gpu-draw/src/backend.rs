@@ -0,0 +1,293 @@
+use growterm_types::RenderCommand;
+
+use crate::renderer::{GpuDrawer, HollowCursor, TabBarInfo};
+
+/// How finished frames reach the display.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum PresentMode {
+    /// Wait for vsync: no tearing, least power
+    #[default]
+    Fifo,
+    /// Replace the waiting frame at vsync: lower latency, no tearing
+    Mailbox,
+    /// Present at once: lowest latency, may tear
+    Immediate,
+}
+
+/// Everything drawn on top of the cell grid in one frame.
+#[derive(Clone, Copy, Default)]
+pub struct Overlays<'a> {
+    /// (thumb_top, thumb_height) as fractions of the view
+    pub scrollbar: Option<(f32, f32)>,
+    pub tab_bar: Option<&'a TabBarInfo>,
+    pub is_break: bool,
+    pub break_text: Option<&'a [String]>,
+    pub transparent_tab_bar: bool,
+    /// Left edge of the cell grid (window padding)
+    pub content_x_offset: f32,
+    pub content_y_offset: f32,
+    /// Cells above this y are clipped (a row partly scrolled into view)
+    pub content_clip_top: f32,
+    pub title_bar_height: f32,
+    pub header_opacity: f32,
+    /// Debug HUD line drawn in the top-right corner of the content
+    pub hud: Option<&'a str>,
+    /// Scrollback position (e.g. "1024/10000") next to the scrollbar thumb;
+    /// drawn only while the scrollbar is
+    pub scroll_badge: Option<&'a str>,
+    /// Response time report, one box per line under the HUD
+    pub report: Option<&'a [String]>,
+    /// Outline cursor for an unfocused window
+    pub hollow_cursor: Option<HollowCursor>,
+}
+
+/// Drawing backend used by the app. `GpuDrawer` is the wgpu implementation;
+/// other backends (CPU fallback, test recorder, other platforms) plug in here.
+pub trait Renderer {
+    /// Returns true if another redraw is needed (e.g. glyph budget exceeded).
+    fn draw(&mut self, commands: &[RenderCommand], overlays: &Overlays) -> bool;
+    /// Draw a frame off screen and return it as RGBA8 at `surface_size`,
+    /// without showing it. None where the backend can't read frames back.
+    fn capture(&mut self, _commands: &[RenderCommand], _overlays: &Overlays) -> Option<Vec<u8>> {
+        None
+    }
+    fn resize(&mut self, width: u32, height: u32);
+    fn cell_size(&self) -> (f32, f32);
+    fn set_font(&mut self, font_path: Option<&str>, size: f32);
+    fn set_font_size(&mut self, size: f32);
+    fn tab_bar_height(&self) -> f32;
+    fn surface_size(&self) -> (u32, u32);
+
+    /// Number of cached glyphs and the fraction of the glyph texture in use.
+    fn atlas_usage(&self) -> (usize, f32) {
+        (0, 0.0)
+    }
+
+    /// Fractional cell width with glyphs placed at subpixel offsets.
+    fn set_subpixel_positioning(&mut self, _enabled: bool) {}
+
+    /// Row height as a multiple of the font's line size, and extra
+    /// horizontal padding per cell (scaled like font sizes). Changes the
+    /// cell size.
+    fn set_cell_spacing(&mut self, _line_height: f32, _cell_padding: f32) {}
+
+    /// Window backing scale factor; font sizes are for a 2x display.
+    fn set_scale_factor(&mut self, _scale_factor: f64) {}
+
+    /// Falls back to `Fifo` where the display doesn't support the mode.
+    fn set_present_mode(&mut self, _mode: PresentMode) {}
+}
+
+impl Renderer for GpuDrawer {
+    fn draw(&mut self, commands: &[RenderCommand], overlays: &Overlays) -> bool {
+        GpuDrawer::draw(
+            self,
+            commands,
+            overlays.scrollbar,
+            overlays.tab_bar,
+            overlays.is_break,
+            overlays.break_text,
+            overlays.transparent_tab_bar,
+            overlays.content_x_offset,
+            overlays.content_y_offset,
+            overlays.content_clip_top,
+            overlays.title_bar_height,
+            overlays.header_opacity,
+            overlays.hud,
+            overlays.scroll_badge,
+            overlays.report,
+            overlays.hollow_cursor,
+        )
+    }
+
+    fn capture(&mut self, commands: &[RenderCommand], overlays: &Overlays) -> Option<Vec<u8>> {
+        GpuDrawer::capture(self, |drawer| Renderer::draw(drawer, commands, overlays))
+    }
+
+    fn resize(&mut self, width: u32, height: u32) {
+        GpuDrawer::resize(self, width, height)
+    }
+
+    fn cell_size(&self) -> (f32, f32) {
+        GpuDrawer::cell_size(self)
+    }
+
+    fn set_font(&mut self, font_path: Option<&str>, size: f32) {
+        GpuDrawer::set_font(self, font_path, size)
+    }
+
+    fn set_font_size(&mut self, size: f32) {
+        GpuDrawer::set_font_size(self, size)
+    }
+
+    fn tab_bar_height(&self) -> f32 {
+        GpuDrawer::tab_bar_height(self)
+    }
+
+    fn surface_size(&self) -> (u32, u32) {
+        GpuDrawer::surface_size(self)
+    }
+
+    fn atlas_usage(&self) -> (usize, f32) {
+        GpuDrawer::atlas_usage(self)
+    }
+
+    fn set_subpixel_positioning(&mut self, enabled: bool) {
+        GpuDrawer::set_subpixel_positioning(self, enabled)
+    }
+
+    fn set_cell_spacing(&mut self, line_height: f32, cell_padding: f32) {
+        GpuDrawer::set_cell_spacing(self, line_height, cell_padding)
+    }
+
+    fn set_scale_factor(&mut self, scale_factor: f64) {
+        GpuDrawer::set_scale_factor(self, scale_factor)
+    }
+
+    fn set_present_mode(&mut self, mode: PresentMode) {
+        GpuDrawer::set_present_mode(self, mode)
+    }
+}
+
+/// Backend that keeps the last frame instead of drawing it. Cell size scales
+/// with the font size so layout code behaves like a real backend.
+pub struct RecordingRenderer {
+    width: u32,
+    height: u32,
+    font_size: f32,
+    spacing: (f32, f32),
+    pub frames: usize,
+    pub last_commands: Vec<RenderCommand>,
+    pub last_tab_titles: Option<Vec<String>>,
+    pub last_hollow_cursor: Option<HollowCursor>,
+}
+
+impl RecordingRenderer {
+    pub fn new(width: u32, height: u32, font_size: f32) -> Self {
+        Self {
+            width,
+            height,
+            font_size,
+            spacing: (1.0, 0.0),
+            frames: 0,
+            last_commands: Vec::new(),
+            last_tab_titles: None,
+            last_hollow_cursor: None,
+        }
+    }
+}
+
+impl Renderer for RecordingRenderer {
+    fn draw(&mut self, commands: &[RenderCommand], overlays: &Overlays) -> bool {
+        self.frames += 1;
+        self.last_commands = commands.to_vec();
+        self.last_tab_titles = overlays.tab_bar.map(|t| t.titles.clone());
+        self.last_hollow_cursor = overlays.hollow_cursor;
+        false
+    }
+
+    fn resize(&mut self, width: u32, height: u32) {
+        if width == 0 || height == 0 {
+            return;
+        }
+        self.width = width;
+        self.height = height;
+    }
+
+    fn cell_size(&self) -> (f32, f32) {
+        let (line_height, cell_padding) = self.spacing;
+        (self.font_size * 0.6 + cell_padding, self.font_size * 1.2 * line_height)
+    }
+
+    fn set_font(&mut self, _font_path: Option<&str>, size: f32) {
+        self.font_size = size;
+    }
+
+    fn set_font_size(&mut self, size: f32) {
+        self.font_size = size;
+    }
+
+    fn tab_bar_height(&self) -> f32 {
+        self.font_size * 1.2
+    }
+
+    fn set_cell_spacing(&mut self, line_height: f32, cell_padding: f32) {
+        self.spacing = (line_height, cell_padding);
+    }
+
+    fn surface_size(&self) -> (u32, u32) {
+        (self.width, self.height)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use growterm_types::{CellFlags, Rgb, UnderlineStyle};
+
+    fn command(character: char) -> RenderCommand {
+        RenderCommand {
+            col: 0,
+            row: 0,
+            character,
+            fg: Rgb::new(255, 255, 255),
+            bg: Rgb::new(0, 0, 0),
+            flags: CellFlags::empty(),
+            zerowidth: None,
+            underline_style: UnderlineStyle::Single,
+            underline_color: Rgb::new(255, 255, 255),
+        }
+    }
+
+    #[test]
+    fn recorder_keeps_last_frame() {
+        let mut r = RecordingRenderer::new(800, 600, 20.0);
+        let backend: &mut dyn Renderer = &mut r;
+        backend.draw(&[command('a')], &Overlays::default());
+        backend.draw(&[command('b'), command('c')], &Overlays::default());
+        assert_eq!(r.frames, 2);
+        assert_eq!(r.last_commands.len(), 2);
+        assert_eq!(r.last_commands[0].character, 'b');
+    }
+
+    #[test]
+    fn recorder_captures_tab_titles() {
+        let mut r = RecordingRenderer::new(800, 600, 20.0);
+        let info = TabBarInfo {
+            titles: vec!["one".into(), "two".into()],
+            active_index: 0,
+            dragging_index: None,
+            scroll_offset: 0.0,
+        };
+        let overlays = Overlays { tab_bar: Some(&info), ..Overlays::default() };
+        r.draw(&[], &overlays);
+        assert_eq!(r.last_tab_titles, Some(vec!["one".to_string(), "two".to_string()]));
+    }
+
+    #[test]
+    fn recorder_ignores_zero_resize() {
+        let mut r = RecordingRenderer::new(800, 600, 20.0);
+        r.resize(0, 100);
+        assert_eq!(r.surface_size(), (800, 600));
+        r.resize(400, 300);
+        assert_eq!(r.surface_size(), (400, 300));
+    }
+
+    #[test]
+    fn recorder_cell_size_follows_font() {
+        let mut r = RecordingRenderer::new(800, 600, 20.0);
+        let (w1, _) = r.cell_size();
+        r.set_font_size(40.0);
+        assert!(r.cell_size().0 > w1);
+    }
+
+    #[test]
+    fn recorder_cell_size_follows_spacing() {
+        let mut r = RecordingRenderer::new(800, 600, 20.0);
+        let (w, h) = r.cell_size();
+        r.set_cell_spacing(1.5, 2.0);
+        assert_eq!(r.cell_size(), (w + 2.0, h * 1.5));
+        // 탭 바는 간격과 무관
+        assert_eq!(r.tab_bar_height(), h);
+    }
+}
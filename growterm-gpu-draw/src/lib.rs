@@ -1,8 +1,15 @@
 mod atlas;
+mod backend;
 mod renderer;
+mod scene;
+mod software;
+mod system_fonts;
+pub mod tab_strip;
 
 pub use atlas::GlyphAtlas;
-pub use renderer::{GpuDrawer, TabBarInfo};
+pub use backend::{Overlays, PresentMode, RecordingRenderer, Renderer};
+pub use renderer::{GpuDrawer, HollowCursor, TabBarInfo};
+pub use software::{Presenter, SoftwareRenderer};
 
 #[cfg(test)]
 mod tests {
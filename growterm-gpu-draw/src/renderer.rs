@@ -1,9 +1,12 @@
-use growterm_types::{CellFlags, RenderCommand, Rgb};
+use growterm_types::{CellFlags, RenderCommand, Rgb, UnderlineStyle};
 use wgpu::util::DeviceExt;
 
 use unicode_width::UnicodeWidthChar;
 
-use crate::atlas::GlyphAtlas;
+use crate::atlas::{GlyphAtlas, SUBPIXEL_STEPS};
+use crate::backend::PresentMode;
+use crate::scene::{self, RowCache, RowGeometry, Scene};
+use crate::tab_strip::{TabStrip, ARROW_WIDTH};
 
 use std::io::Write;
 use std::sync::Mutex;
@@ -21,17 +24,17 @@ pub static GLYPH_LOG: std::sync::LazyLock<Mutex<Option<std::fs::File>>> = std::s
 
 #[repr(C)]
 #[derive(Copy, Clone, bytemuck::Pod, bytemuck::Zeroable)]
-struct BgVertex {
-    position: [f32; 2],
-    color: [f32; 4],
+pub(crate) struct BgVertex {
+    pub position: [f32; 2],
+    pub color: [f32; 4],
 }
 
 #[repr(C)]
 #[derive(Copy, Clone, bytemuck::Pod, bytemuck::Zeroable)]
-struct GlyphVertex {
-    position: [f32; 2],
-    tex_coords: [f32; 2],
-    color: [f32; 3],
+pub(crate) struct GlyphVertex {
+    pub position: [f32; 2],
+    pub tex_coords: [f32; 2],
+    pub color: [f32; 3],
 }
 
 #[repr(C)]
@@ -44,7 +47,9 @@ struct Uniforms {
 pub struct GpuDrawer {
     device: wgpu::Device,
     queue: wgpu::Queue,
-    surface: wgpu::Surface<'static>,
+    surface: Option<wgpu::Surface<'static>>,
+    /// Render target used instead of `surface` by headless drawers.
+    offscreen: Option<wgpu::Texture>,
     surface_config: wgpu::SurfaceConfiguration,
     render_format: wgpu::TextureFormat,
     bg_pipeline: wgpu::RenderPipeline,
@@ -60,11 +65,23 @@ pub struct GpuDrawer {
     atlas_cursor_x: u32,
     atlas_cursor_y: u32,
     atlas_row_height: u32,
-    glyph_regions: std::collections::HashMap<char, GlyphRegion>,
+    /// Keyed by character and subpixel step
+    glyph_regions: std::collections::HashMap<(char, u8), GlyphRegion>,
     tab_glyph_regions: std::collections::HashMap<char, GlyphRegion>,
     surface_dirty: bool,
     new_glyphs_this_frame: u32,
     glyph_budget_exceeded: bool,
+    /// Glyphs skipped for the per-frame budget; a row that lost one isn't cached.
+    deferred_glyphs: u64,
+    row_cache: RowCache,
+    /// Requested font size before display scaling
+    font_size: f32,
+    /// Window backing scale relative to `REFERENCE_SCALE`
+    scale: f32,
+    /// (line height multiplier, cell padding before display scaling)
+    cell_spacing: (f32, f32),
+    /// Present modes the surface supports
+    present_modes: Vec<wgpu::PresentMode>,
 }
 
 #[derive(Clone, Copy)]
@@ -82,6 +99,8 @@ struct GlyphRegion {
 const GLYPH_TEXTURE_SIZE: u32 = 1024;
 /// Maximum number of new glyphs to rasterize per frame to avoid UI freezes.
 const MAX_NEW_GLYPHS_PER_FRAME: u32 = 16;
+/// Draws `capture` allows for glyphs held back by the per-frame budget
+const CAPTURE_MAX_DRAWS: usize = 256;
 
 fn preferred_surface_alpha_mode(
     available: &[wgpu::CompositeAlphaMode],
@@ -115,20 +134,56 @@ fn push_glyph_quad(
     verts.push(GlyphVertex { position: [gx, gy + gh], tex_coords: [region.u0, region.v1], color });
 }
 
-const TAB_FONT_SIZE: f32 = 24.0;
-const TAB_BAR_PADDING: f32 = 8.0;
+pub(crate) const TAB_FONT_SIZE: f32 = 24.0;
+pub(crate) const TAB_BAR_PADDING: f32 = 8.0;
+/// Font sizes are pixel sizes at this backing scale (Retina); other
+/// displays scale them so text keeps the same size in points.
+pub(crate) const REFERENCE_SCALE: f64 = 2.0;
+pub(crate) const SCROLLBAR_WIDTH: f32 = 6.0;
+pub(crate) const LABEL_BOX_COLOR: [f32; 4] = [0.0, 0.0, 0.0, 0.7];
+pub(crate) const HUD_TEXT_COLOR: [f32; 3] = [0.4, 1.0, 0.4];
+pub(crate) const SCROLL_BADGE_TEXT_COLOR: [f32; 3] = [0.8, 0.8, 0.8];
 
 /// Tab bar rendering info passed from the app layer.
 pub struct TabBarInfo {
     pub titles: Vec<String>,
     pub active_index: usize,
     pub dragging_index: Option<usize>,
+    /// 탭이 넘칠 때 가로 스크롤 위치 (픽셀)
+    pub scroll_offset: f32,
+}
+
+/// Outline cursor drawn while the window is unfocused, in place of the
+/// filled block render-cmd produces.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct HollowCursor {
+    pub row: u16,
+    pub col: u16,
+    /// 2 on a wide character
+    pub width: u16,
+    pub color: Rgb,
 }
 
 impl GpuDrawer {
     pub fn new<W>(window: std::sync::Arc<W>, width: u32, height: u32, font_size: f32, font_path: Option<&str>) -> Self
     where
-        W: raw_window_handle::HasWindowHandle
+        W: ?Sized
+            + raw_window_handle::HasWindowHandle
+            + raw_window_handle::HasDisplayHandle
+            + Send
+            + Sync
+            + 'static,
+    {
+        Self::try_new(window, width, height, font_size, font_path).expect("failed to initialize GPU renderer")
+    }
+
+    /// Like `new`, but returns None when no surface, adapter or device can be
+    /// created (no Metal/Vulkan device, e.g. in a VM) so the caller can fall
+    /// back to `SoftwareRenderer`.
+    pub fn try_new<W>(window: std::sync::Arc<W>, width: u32, height: u32, font_size: f32, font_path: Option<&str>) -> Option<Self>
+    where
+        W: ?Sized
+            + raw_window_handle::HasWindowHandle
             + raw_window_handle::HasDisplayHandle
             + Send
             + Sync
@@ -139,14 +194,13 @@ impl GpuDrawer {
             ..Default::default()
         });
 
-        let surface = instance.create_surface(window.clone()).unwrap();
+        let surface = instance.create_surface(window.clone()).ok()?;
 
         let adapter = pollster::block_on(instance.request_adapter(&wgpu::RequestAdapterOptions {
             power_preference: wgpu::PowerPreference::default(),
             compatible_surface: Some(&surface),
             force_fallback_adapter: false,
-        }))
-        .unwrap();
+        }))?;
 
         let (device, queue) = pollster::block_on(adapter.request_device(
             &wgpu::DeviceDescriptor {
@@ -157,9 +211,9 @@ impl GpuDrawer {
             },
             None,
         ))
-        .unwrap();
+        .ok()?;
         let surface_caps = surface.get_capabilities(&adapter);
-        let surface_format = surface_caps.formats[0];
+        let surface_format = *surface_caps.formats.first()?;
 
         // Use non-sRGB view to avoid double gamma encoding of ANSI colors
         let render_format = match surface_format {
@@ -185,6 +239,87 @@ impl GpuDrawer {
         };
         surface.configure(&device, &surface_config);
 
+        let mut drawer = Self::with_device(
+            device,
+            queue,
+            Some(surface),
+            None,
+            surface_config,
+            render_format,
+            font_size,
+            font_path,
+        );
+        drawer.present_modes = surface_caps.present_modes;
+        Some(drawer)
+    }
+
+    /// Create a drawer that renders into an offscreen texture instead of a
+    /// window surface. Returns None when no GPU adapter is available.
+    /// Use `read_pixels` after `draw` to fetch the rendered frame.
+    pub fn new_headless(width: u32, height: u32, font_size: f32, font_path: Option<&str>) -> Option<Self> {
+        let instance = wgpu::Instance::new(&wgpu::InstanceDescriptor {
+            backends: wgpu::Backends::all(),
+            ..Default::default()
+        });
+
+        let adapter = pollster::block_on(instance.request_adapter(&wgpu::RequestAdapterOptions {
+            power_preference: wgpu::PowerPreference::default(),
+            compatible_surface: None,
+            force_fallback_adapter: false,
+        }))?;
+
+        let (device, queue) = pollster::block_on(adapter.request_device(
+            &wgpu::DeviceDescriptor {
+                label: Some("growterm headless device"),
+                required_features: wgpu::Features::empty(),
+                required_limits: wgpu::Limits::default(),
+                ..Default::default()
+            },
+            None,
+        ))
+        .ok()?;
+
+        // Same as the app: sRGB storage, non-sRGB render view
+        let surface_format = wgpu::TextureFormat::Bgra8UnormSrgb;
+        let render_format = wgpu::TextureFormat::Bgra8Unorm;
+        let surface_config = wgpu::SurfaceConfiguration {
+            usage: wgpu::TextureUsages::RENDER_ATTACHMENT,
+            format: surface_format,
+            width: width.max(1),
+            height: height.max(1),
+            present_mode: wgpu::PresentMode::Fifo,
+            alpha_mode: wgpu::CompositeAlphaMode::Opaque,
+            view_formats: vec![render_format],
+            desired_maximum_frame_latency: 2,
+        };
+        let offscreen = create_offscreen_texture(&device, &surface_config);
+
+        Some(Self::with_device(
+            device,
+            queue,
+            None,
+            Some(offscreen),
+            surface_config,
+            render_format,
+            font_size,
+            font_path,
+        ))
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn with_device(
+        device: wgpu::Device,
+        queue: wgpu::Queue,
+        surface: Option<wgpu::Surface<'static>>,
+        offscreen: Option<wgpu::Texture>,
+        surface_config: wgpu::SurfaceConfiguration,
+        render_format: wgpu::TextureFormat,
+        font_size: f32,
+        font_path: Option<&str>,
+    ) -> Self {
+        let width = surface_config.width;
+        let height = surface_config.height;
+
         // Uniform buffer
         let uniforms = Uniforms {
             screen_size: [width as f32, height as f32],
@@ -425,6 +560,7 @@ impl GpuDrawer {
             device,
             queue,
             surface,
+            offscreen,
             surface_config,
             render_format,
             bg_pipeline,
@@ -445,22 +581,80 @@ impl GpuDrawer {
             surface_dirty: false,
             new_glyphs_this_frame: 0,
             glyph_budget_exceeded: false,
+            deferred_glyphs: 0,
+            row_cache: RowCache::default(),
+            font_size,
+            scale: 1.0,
+            cell_spacing: (1.0, 0.0),
+            present_modes: vec![wgpu::PresentMode::Fifo],
         }
     }
 
     pub fn set_font_size(&mut self, size: f32) {
-        self.atlas.set_size(size);
-        self.glyph_regions.clear();
-        self.tab_glyph_regions.clear();
-        self.atlas_cursor_x = 0;
-        self.atlas_cursor_y = 0;
-        self.atlas_row_height = 0;
+        self.font_size = size;
+        self.atlas.set_size(size * self.scale);
+        self.reset_glyph_cache();
     }
 
     pub fn set_font(&mut self, font_path: Option<&str>, size: f32) {
-        self.atlas.set_font(font_path, size);
+        self.font_size = size;
+        self.atlas.set_font(font_path, size * self.scale);
+        self.reset_glyph_cache();
+    }
+
+    /// See `GlyphAtlas::set_subpixel_positioning`. Changes the cell width.
+    pub fn set_subpixel_positioning(&mut self, enabled: bool) {
+        if self.atlas.subpixel_positioning() == enabled {
+            return;
+        }
+        self.atlas.set_subpixel_positioning(enabled);
+        self.reset_glyph_cache();
+    }
+
+    /// See `GlyphAtlas::set_cell_spacing`. `cell_padding` is scaled with
+    /// the display like font sizes.
+    pub fn set_cell_spacing(&mut self, line_height: f32, cell_padding: f32) {
+        self.cell_spacing = (line_height, cell_padding);
+        self.atlas.set_cell_spacing(line_height, cell_padding * self.scale);
+        self.row_cache.clear();
+    }
+
+    /// The window's backing scale factor (2.0 on Retina). Glyphs are
+    /// re-rasterized at the new pixel size; cell and tab bar sizes change.
+    pub fn set_scale_factor(&mut self, scale_factor: f64) {
+        let scale = (scale_factor / REFERENCE_SCALE) as f32;
+        if !scale.is_finite() || scale <= 0.0 || scale == self.scale {
+            return;
+        }
+        self.scale = scale;
+        self.atlas.set_size(self.font_size * scale);
+        self.atlas.set_cell_spacing(self.cell_spacing.0, self.cell_spacing.1 * scale);
+        self.tab_atlas.set_size(TAB_FONT_SIZE * scale);
+        self.reset_glyph_cache();
+    }
+
+    pub fn set_present_mode(&mut self, mode: PresentMode) {
+        let wanted = match mode {
+            PresentMode::Fifo => wgpu::PresentMode::Fifo,
+            PresentMode::Mailbox => wgpu::PresentMode::Mailbox,
+            PresentMode::Immediate => wgpu::PresentMode::Immediate,
+        };
+        let mode = if self.present_modes.contains(&wanted) {
+            wanted
+        } else {
+            wgpu::PresentMode::Fifo
+        };
+        if self.surface_config.present_mode != mode {
+            self.surface_config.present_mode = mode;
+            self.surface_dirty = true;
+        }
+    }
+
+    /// Glyph metrics changed: start the texture over.
+    fn reset_glyph_cache(&mut self) {
         self.glyph_regions.clear();
         self.tab_glyph_regions.clear();
+        self.row_cache.clear();
         self.atlas_cursor_x = 0;
         self.atlas_cursor_y = 0;
         self.atlas_row_height = 0;
@@ -482,14 +676,25 @@ impl GpuDrawer {
         self.atlas.cell_size()
     }
 
+    pub fn surface_size(&self) -> (u32, u32) {
+        (self.surface_config.width, self.surface_config.height)
+    }
+
     pub fn tab_cell_size(&self) -> (f32, f32) {
         self.tab_atlas.cell_size()
     }
 
+    /// Number of cached glyphs and the fraction of the glyph texture in use.
+    pub fn atlas_usage(&self) -> (usize, f32) {
+        let used_rows = self.atlas_cursor_y + self.atlas_row_height;
+        let fill = used_rows as f32 / self.glyph_texture_size as f32;
+        (self.glyph_regions.len() + self.tab_glyph_regions.len(), fill.min(1.0))
+    }
+
     /// Fixed tab bar height in pixels (independent of body font size).
     pub fn tab_bar_height(&self) -> f32 {
         let (_, tab_ch) = self.tab_atlas.cell_size();
-        tab_ch + TAB_BAR_PADDING
+        tab_ch + TAB_BAR_PADDING * self.scale
     }
 
     /// Returns true if the glyph budget was exceeded and another redraw is needed.
@@ -501,16 +706,26 @@ impl GpuDrawer {
         is_break: bool,
         break_text: Option<&[String]>,
         transparent_tab_bar: bool,
+        content_x_offset: f32,
         content_y_offset: f32,
+        content_clip_top: f32,
         title_bar_height: f32,
         header_opacity: f32,
+        hud: Option<&str>,
+        scroll_badge: Option<&str>,
+        report: Option<&[String]>,
+        hollow_cursor: Option<HollowCursor>,
     ) -> bool {
         self.new_glyphs_this_frame = 0;
         self.glyph_budget_exceeded = false;
 
         if self.surface_dirty {
             self.surface_dirty = false;
-            self.surface.configure(&self.device, &self.surface_config);
+            if let Some(surface) = &self.surface {
+                surface.configure(&self.device, &self.surface_config);
+            } else {
+                self.offscreen = Some(create_offscreen_texture(&self.device, &self.surface_config));
+            }
             let uniforms = Uniforms {
                 screen_size: [
                     self.surface_config.width as f32,
@@ -521,72 +736,38 @@ impl GpuDrawer {
             self.queue
                 .write_buffer(&self.uniform_buffer, 0, bytemuck::bytes_of(&uniforms));
         }
-        let output = match self.surface.get_current_texture() {
-            Ok(t) => t,
-            Err(_) => return false,
+        let output = match &self.surface {
+            Some(surface) => match surface.get_current_texture() {
+                Ok(t) => Some(t),
+                Err(_) => return false,
+            },
+            None => None,
+        };
+        let target = match (&output, &self.offscreen) {
+            (Some(output), _) => &output.texture,
+            (None, Some(offscreen)) => offscreen,
+            (None, None) => return false,
         };
-        let view = output.texture.create_view(&wgpu::TextureViewDescriptor {
+        let view = target.create_view(&wgpu::TextureViewDescriptor {
             format: Some(self.render_format),
             ..Default::default()
         });
 
         let (cell_w, cell_h) = self.atlas.cell_size();
-        let y_off = content_y_offset;
-
-        // Build bg vertices
-        let mut bg_vertices: Vec<BgVertex> = Vec::new();
-
-        for cmd in commands {
-            let x = cmd.col as f32 * cell_w;
-            let y = y_off + cmd.row as f32 * cell_h;
-            let w = if cmd.flags.contains(CellFlags::WIDE_CHAR) {
-                cell_w * 2.0
-            } else {
-                cell_w
-            };
-            let color = rgb_to_f32a(cmd.bg);
+        let (x_off, y_off) = (content_x_offset.round(), content_y_offset);
 
-            bg_vertices.push(BgVertex {
-                position: [x, y],
-                color,
-            });
-            bg_vertices.push(BgVertex {
-                position: [x + w, y],
-                color,
-            });
-            bg_vertices.push(BgVertex {
-                position: [x, y + cell_h],
-                color,
-            });
-            bg_vertices.push(BgVertex {
-                position: [x + w, y],
-                color,
-            });
-            bg_vertices.push(BgVertex {
-                position: [x + w, y + cell_h],
-                color,
-            });
-            bg_vertices.push(BgVertex {
-                position: [x, y + cell_h],
-                color,
-            });
-
-            // Underline: thin rect at cell bottom using fg color
-            if cmd.flags.contains(CellFlags::UNDERLINE) {
-                let underline_h = (cell_h * 0.07).max(1.0);
-                let underline_y = y + cell_h - underline_h;
-                let fg_color = rgb_to_f32a(cmd.fg);
-                push_bg_rect(&mut bg_vertices, x, underline_y, w, underline_h, fg_color);
-            }
-        }
-
-        // Build glyph vertices
-        let mut glyph_vertices: Vec<GlyphVertex> = Vec::new();
+        // Rows whose commands hashed the same last frame reuse their vertices.
+        let rows = scene::group_rows(commands);
+        let stale: Vec<RenderCommand> = rows
+            .iter()
+            .filter(|r| self.row_cache.get(r.row, r.hash).is_none())
+            .flat_map(|r| r.indices.iter().map(|&i| commands[i]))
+            .collect();
 
         // Preload glyphs for lower rows first so the input/status area is not
         // starved by large body updates when the per-frame glyph budget is low.
-        for idx in prioritized_glyph_command_indices(commands) {
-            let cmd = &commands[idx];
+        for idx in prioritized_glyph_command_indices(&stale) {
+            let cmd = &stale[idx];
             let ch = cmd.character;
             if ch >= '\u{2500}' && ch <= '\u{257F}' {
                 continue;
@@ -597,175 +778,38 @@ impl GpuDrawer {
             let _ = self.ensure_glyph_in_atlas(ch);
         }
 
-        // Helper: push a fg-colored rectangle into bg_vertices
-        let push_rect =
-            |bg_verts: &mut Vec<BgVertex>, x: f32, y: f32, w: f32, h: f32, color: [f32; 4]| {
-                push_bg_rect(bg_verts, x, y, w, h, color);
-            };
-
-        for cmd in commands {
-            if cmd.character == ' ' {
+        let mut scene = Scene::default();
+        for row in &rows {
+            let row_y = y_off + row.row as f32 * cell_h;
+            if let Some(geometry) = self.row_cache.get(row.row, row.hash) {
+                scene.append_row(geometry, x_off, row_y);
                 continue;
             }
-            if cmd.flags.contains(CellFlags::HIDDEN) {
-                continue;
-            }
-
-            // Block elements (U+2580..U+259F, excluding shades U+2591-U+2593)
-            let ch = cmd.character;
-            if ch >= '\u{2580}' && ch <= '\u{259F}' && !(ch >= '\u{2591}' && ch <= '\u{2593}') {
-                let cx = cmd.col as f32 * cell_w;
-                let cy = y_off + cmd.row as f32 * cell_h;
-                let fg = rgb_to_f32a(cmd.fg);
-                if push_block_element_rects(&mut bg_vertices, ch, cx, cy, cell_w, cell_h, fg) {
-                    continue;
-                }
-            }
-
-            // Box drawing characters (U+2500..U+257F)
-            if ch >= '\u{2500}' && ch <= '\u{257F}' {
-                if let Some(segs) = box_drawing_segments(ch) {
-                    let cx = cmd.col as f32 * cell_w;
-                    let cy = y_off + cmd.row as f32 * cell_h;
-                    let fg = rgb_to_f32a(cmd.fg);
-                    let light_h = 1.0_f32;
-                    let heavy_h = (cell_h / 8.0).ceil().max(2.0);
-                    let light_w = 1.0_f32;
-                    let heavy_w = (cell_w / 8.0).ceil().max(2.0);
-                    let mid_x = (cell_w / 2.0).floor();
-                    let mid_y = (cell_h / 2.0).floor();
-
-                    // Horizontal segment
-                    if segs.h_weight != LineWeight::None && segs.h_weight != LineWeight::Double {
-                        let th = if segs.h_weight == LineWeight::Heavy {
-                            heavy_h
-                        } else {
-                            light_h
-                        };
-                        let x0 = if segs.left {
-                            0.0
-                        } else {
-                            mid_x - (th / 2.0).floor()
-                        };
-                        let x1 = if segs.right {
-                            cell_w
-                        } else {
-                            mid_x + (th / 2.0).ceil()
-                        };
-                        push_rect(
-                            &mut bg_vertices,
-                            cx + x0,
-                            cy + mid_y - (th / 2.0).floor(),
-                            x1 - x0,
-                            th,
-                            fg,
-                        );
-                    }
-                    // Vertical segment
-                    if segs.v_weight != LineWeight::None && segs.v_weight != LineWeight::Double {
-                        let tw = if segs.v_weight == LineWeight::Heavy {
-                            heavy_w
-                        } else {
-                            light_w
-                        };
-                        let y0 = if segs.up {
-                            0.0
-                        } else {
-                            mid_y - (tw / 2.0).floor()
-                        };
-                        let y1 = if segs.down {
-                            cell_h
-                        } else {
-                            mid_y + (tw / 2.0).ceil()
-                        };
-                        push_rect(
-                            &mut bg_vertices,
-                            cx + mid_x - (tw / 2.0).floor(),
-                            cy + y0,
-                            tw,
-                            y1 - y0,
-                            fg,
-                        );
-                    }
-                    // Double horizontal
-                    if segs.h_weight == LineWeight::Double {
-                        let gap = (cell_h / 6.0).ceil();
-                        let th = light_h;
-                        let x0 = if segs.left { 0.0 } else { mid_x };
-                        let x1 = if segs.right { cell_w } else { mid_x + light_w };
-                        push_rect(
-                            &mut bg_vertices,
-                            cx + x0,
-                            cy + mid_y - gap - th / 2.0,
-                            x1 - x0,
-                            th,
-                            fg,
-                        );
-                        push_rect(
-                            &mut bg_vertices,
-                            cx + x0,
-                            cy + mid_y + gap - th / 2.0,
-                            x1 - x0,
-                            th,
-                            fg,
-                        );
-                    }
-                    // Double vertical
-                    if segs.v_weight == LineWeight::Double {
-                        let gap = (cell_w / 6.0).ceil();
-                        let tw = light_w;
-                        let y0 = if segs.up { 0.0 } else { mid_y };
-                        let y1 = if segs.down { cell_h } else { mid_y + light_h };
-                        push_rect(
-                            &mut bg_vertices,
-                            cx + mid_x - gap - tw / 2.0,
-                            cy + y0,
-                            tw,
-                            y1 - y0,
-                            fg,
-                        );
-                        push_rect(
-                            &mut bg_vertices,
-                            cx + mid_x + gap - tw / 2.0,
-                            cy + y0,
-                            tw,
-                            y1 - y0,
-                            fg,
-                        );
-                    }
-                    continue;
-                }
-            }
-
-            let region = self.ensure_glyph_in_atlas(cmd.character);
-            if region.width == 0 || region.height == 0 {
-                continue;
+            let deferred_before = self.deferred_glyphs;
+            let geometry = self.build_row(row.indices.iter().map(|&i| &commands[i]), cell_w, cell_h);
+            scene.append_row(&geometry, x_off, row_y);
+            // 예산 초과로 빠진 글리프가 있으면 다음 프레임에 다시 빌드
+            if self.deferred_glyphs == deferred_before {
+                self.row_cache.insert(row.row, row.hash, geometry);
             }
-
-            let cell_x = cmd.col as f32 * cell_w;
-            let cell_y = y_off + cmd.row as f32 * cell_h;
-
-            // Position glyph within cell
-            let baseline_y = cell_y + cell_h * 0.8; // approximate baseline
-            let gx = cell_x + region.offset_x;
-            let gy = baseline_y - region.offset_y - region.height as f32;
-
-            let color = rgb_to_f32(cmd.fg);
-
-            push_glyph_quad(&mut glyph_vertices, &region, gx, gy, color);
         }
+        self.row_cache.retain_rows(&rows);
 
         // Scrollbar
         if let Some((thumb_top_ratio, thumb_height_ratio)) = scrollbar {
             let screen_w = self.surface_config.width as f32;
             let screen_h = self.surface_config.height as f32;
             let term_h = screen_h - y_off;
-            let bar_w = 6.0_f32;
+            let bar_w = SCROLLBAR_WIDTH;
             let x0 = screen_w - bar_w;
             let y0 = y_off + thumb_top_ratio * term_h;
             let h = thumb_height_ratio * term_h;
             let color = [0.5, 0.5, 0.5, 1.0];
-            push_rect(&mut bg_vertices, x0, y0, bar_w, h, color);
+            push_bg_rect(&mut scene.decorations, x0, y0, bar_w, h, color);
+        }
+
+        if let Some(cursor) = hollow_cursor {
+            push_hollow_cursor(&mut scene.decorations, &cursor, cell_w, cell_h, (x_off, y_off), self.scale);
         }
 
         // Title bar + Tab bar overlay
@@ -777,8 +821,7 @@ impl GpuDrawer {
             push_bg_rect(&mut tab_bg_verts, 0.0, 0.0, screen_w, title_bar_height, [0.0, 0.0, 0.0, header_opacity]);
         }
         if let Some(tab_info) = tab_bar {
-            let (tab_cw, tab_ch) = self.tab_atlas.cell_size();
-            let tab_ascent = self.tab_atlas.ascent();
+            let (tab_cw, _) = self.tab_atlas.cell_size();
             let bar_h = self.tab_bar_height();
             let screen_w = self.surface_config.width as f32;
             let tab_y = if transparent_tab_bar { title_bar_height } else { 0.0 };
@@ -795,40 +838,72 @@ impl GpuDrawer {
             }
             push_bg_rect(&mut tab_bg_verts, 0.0, tab_y, screen_w, bar_h, bar_bg);
 
-            let tab_count = tab_info.titles.len().max(1) as f32;
-            let tab_w = screen_w / tab_count;
-            let mut x = 0.0_f32;
+            let strip = TabStrip::new(tab_info.titles.len(), screen_w, tab_info.scroll_offset);
+            let (view_x, view_w) = strip.viewport();
+            let clip = (view_x, view_x + view_w);
             for (i, title) in tab_info.titles.iter().enumerate() {
+                let x = strip.tab_x(i);
+                if x + strip.tab_width <= clip.0 || x >= clip.1 {
+                    continue;
+                }
+                let tab_clip = (x.max(clip.0), (x + strip.tab_width).min(clip.1));
                 if tab_info.dragging_index == Some(i) {
-                    push_bg_rect(&mut tab_bg_verts, x, tab_y, tab_w, bar_h, dragging_bg);
+                    push_bg_rect(&mut tab_bg_verts, tab_clip.0, tab_y, tab_clip.1 - tab_clip.0, bar_h, dragging_bg);
                 }
-
+                let color: [f32; 3] = if i == tab_info.active_index {
+                    [1.0, 1.0, 1.0]
+                } else {
+                    [0.4, 0.4, 0.4]
+                };
                 let text_w = title.chars().count() as f32 * tab_cw;
-                let mut cx = x + (tab_w - text_w) / 2.0;
-                for ch in title.chars() {
-                    if ch == ' ' {
-                        cx += tab_cw;
-                        continue;
-                    }
-                    let region = self.ensure_tab_glyph_in_atlas(ch);
-                    if region.width > 0 && region.height > 0 {
-                        let baseline_y = tab_y + (bar_h - tab_ch) / 2.0 + tab_ascent;
-                        let gx = cx + region.offset_x;
-                        let gy = baseline_y - region.offset_y - region.height as f32;
-                        let color: [f32; 3] = if i == tab_info.active_index {
-                            [1.0, 1.0, 1.0]
-                        } else {
-                            [0.4, 0.4, 0.4]
-                        };
-                        push_glyph_quad(&mut tab_glyph_verts, &region, gx, gy, color);
-                    }
-                    cx += tab_cw;
-                }
+                let cx = x + (strip.tab_width - text_w) / 2.0;
+                self.push_tab_label(&mut tab_glyph_verts, title, cx, tab_y, color, tab_clip);
+            }
 
-                x += tab_w;
+            // 넘친 탭이 있는 쪽 화살표는 밝게
+            if strip.overflow {
+                let arrow_color = |active: bool| if active { [1.0, 1.0, 1.0] } else { [0.4, 0.4, 0.4] };
+                let left_x = (ARROW_WIDTH - tab_cw) / 2.0;
+                let right_x = screen_w - ARROW_WIDTH + (ARROW_WIDTH - tab_cw) / 2.0;
+                let full = (0.0, screen_w);
+                self.push_tab_label(&mut tab_glyph_verts, "<", left_x, tab_y, arrow_color(strip.scroll > 0.0), full);
+                self.push_tab_label(
+                    &mut tab_glyph_verts,
+                    ">",
+                    right_x,
+                    tab_y,
+                    arrow_color(strip.scroll < strip.max_scroll()),
+                    full,
+                );
             }
         }
 
+        let pad = TAB_BAR_PADDING * self.scale;
+        let screen_w = self.surface_config.width as f32;
+        // Debug HUD: top-right corner, just below the tab bar
+        if let Some(text) = hud {
+            let y = content_clip_top + pad;
+            self.push_label_box(&mut tab_bg_verts, &mut tab_glyph_verts, text, screen_w - pad, y, HUD_TEXT_COLOR);
+        }
+        // 응답 시간 리포트: HUD 아래에 한 줄씩 쌓음
+        if let Some(lines) = report {
+            let line_h = self.tab_bar_height();
+            let top = content_clip_top + pad + if hud.is_some() { line_h } else { 0.0 };
+            for (i, line) in lines.iter().enumerate() {
+                let y = top + i as f32 * line_h;
+                self.push_label_box(&mut tab_bg_verts, &mut tab_glyph_verts, line, screen_w - pad, y, HUD_TEXT_COLOR);
+            }
+        }
+        // 스크롤 위치 배지: 스크롤바 썸 옆, 스크롤바와 함께 사라짐
+        if let (Some(text), Some((thumb_top, _))) = (scroll_badge, scrollbar) {
+            let screen_h = self.surface_config.height as f32;
+            let y = scroll_badge_y(thumb_top, y_off, content_clip_top, screen_h, self.tab_bar_height());
+            let right = screen_w - SCROLLBAR_WIDTH - pad;
+            self.push_label_box(&mut tab_bg_verts, &mut tab_glyph_verts, text, right, y, SCROLL_BADGE_TEXT_COLOR);
+        }
+
+        let bg_vertices = scene.rect_vertices();
+        let glyph_vertices = scene.glyphs;
         let bg_buffer = self
             .device
             .create_buffer_init(&wgpu::util::BufferInitDescriptor {
@@ -871,6 +946,13 @@ impl GpuDrawer {
                 ..Default::default()
             });
 
+            let (surface_w, surface_h) = (self.surface_config.width, self.surface_config.height);
+            let clip_top = content_clip_top.max(0.0) as u32;
+            let clipped = clip_top > 0 && clip_top < surface_h;
+            if clipped {
+                pass.set_scissor_rect(0, clip_top, surface_w, surface_h - clip_top);
+            }
+
             // Pass 1: backgrounds
             if !bg_vertices.is_empty() {
                 pass.set_pipeline(&self.bg_pipeline);
@@ -888,6 +970,10 @@ impl GpuDrawer {
                 pass.draw(0..glyph_vertices.len() as u32, 0..1);
             }
 
+            if clipped {
+                pass.set_scissor_rect(0, 0, surface_w, surface_h);
+            }
+
             // Pass 2.5: tab bar (uses bg_pipeline with alpha blending)
             if !tab_bg_verts.is_empty() {
                 let tab_bg_buffer = self.device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
@@ -1078,10 +1164,218 @@ impl GpuDrawer {
         }
 
         self.queue.submit(std::iter::once(encoder.finish()));
-        output.present();
+        if let Some(output) = output {
+            output.present();
+        }
         self.glyph_budget_exceeded
     }
 
+    /// Geometry for one row's commands, with y relative to the row's top.
+    fn build_row<'a>(
+        &mut self,
+        commands: impl Iterator<Item = &'a RenderCommand>,
+        cell_w: f32,
+        cell_h: f32,
+    ) -> RowGeometry {
+        let mut geometry = RowGeometry::default();
+        let baseline_y = self.atlas.baseline();
+        let subpixel = self.atlas.subpixel_positioning();
+        let glyph_inset = self.atlas.glyph_inset();
+        for cmd in commands {
+            let cells = if cmd.flags.contains(CellFlags::WIDE_CHAR) { 2.0 } else { 1.0 };
+            // 셀 폭이 소수일 수 있으므로 사각형 가장자리는 픽셀에 맞춤 (틈 방지)
+            let glyph_x = cmd.col as f32 * cell_w;
+            let x = glyph_x.round();
+            let w = (glyph_x + cell_w * cells).round() - x;
+            push_bg_rect(&mut geometry.bg, x, 0.0, w, cell_h, rgb_to_f32a(cmd.bg));
+
+            // Underline / strikethrough: thin rects in the fg color
+            let line_h = (cell_h * 0.07).max(1.0);
+            let fg = rgb_to_f32a(cmd.fg);
+            if cmd.flags.contains(CellFlags::UNDERLINE) {
+                let color = rgb_to_f32a(cmd.underline_color);
+                push_underline_rects(&mut geometry.decorations, cmd.underline_style, x, 0.0, w, cell_h, line_h, color);
+            }
+            if cmd.flags.contains(CellFlags::STRIKETHROUGH) {
+                let strike_y = (cell_h * 0.55 - line_h / 2.0).round();
+                push_bg_rect(&mut geometry.decorations, x, strike_y, w, line_h, fg);
+            }
+
+            if cmd.character == ' ' || cmd.flags.contains(CellFlags::HIDDEN) {
+                continue;
+            }
+
+            let ch = cmd.character;
+            if push_cell_shape_rects(&mut geometry.decorations, ch, x, 0.0, cell_w, cell_h, fg) {
+                continue;
+            }
+
+            let (pen_x, step) = subpixel_split(glyph_x + glyph_inset, subpixel);
+            let region = self.ensure_glyph_at(ch, step);
+            if region.width == 0 || region.height == 0 {
+                continue;
+            }
+
+            // Position glyph within cell
+            let gx = pen_x + region.offset_x;
+            let gy = baseline_y - region.offset_y - region.height as f32;
+            let color = rgb_to_f32(cmd.fg);
+            push_glyph_quad(&mut geometry.glyphs, &region, gx, gy, color);
+
+            // No shaping: combining marks are overlaid on the base cell. Emoji
+            // ZWJ sequences keep only their first emoji.
+            if let Some(zerowidth) = cmd.zerowidth.filter(|z| !z.has_joiner()) {
+                for mark in zerowidth.as_str().chars() {
+                    if ('\u{FE00}'..='\u{FE0F}').contains(&mark) {
+                        continue;
+                    }
+                    let region = self.ensure_glyph_at(mark, step);
+                    if region.width == 0 || region.height == 0 {
+                        continue;
+                    }
+                    let gx = pen_x + region.offset_x;
+                    let gy = baseline_y - region.offset_y - region.height as f32;
+                    push_glyph_quad(&mut geometry.glyphs, &region, gx, gy, color);
+                }
+            }
+        }
+        geometry
+    }
+
+    /// Draw into an offscreen texture and read the frame back as RGBA8,
+    /// leaving the window alone. `draw` runs again while it reports
+    /// deferred glyphs so the picture comes out complete.
+    pub fn capture(&mut self, mut draw: impl FnMut(&mut Self) -> bool) -> Option<Vec<u8>> {
+        let surface = self.surface.take();
+        let surface_dirty = self.surface_dirty;
+        self.offscreen = Some(create_offscreen_texture(&self.device, &self.surface_config));
+        for _ in 0..CAPTURE_MAX_DRAWS {
+            if !draw(self) {
+                break;
+            }
+        }
+        let pixels = self.read_pixels();
+        if surface.is_some() {
+            self.surface = surface;
+            self.offscreen = None;
+            // 오프스크린 그리기가 지운 플래그를 되돌려 창 표면도 다시 설정되게
+            self.surface_dirty = surface_dirty;
+        }
+        pixels
+    }
+
+    /// Read back the last frame of a headless drawer as tightly packed RGBA8.
+    /// Returns None for window-backed drawers.
+    pub fn read_pixels(&self) -> Option<Vec<u8>> {
+        let texture = self.offscreen.as_ref()?;
+        let width = self.surface_config.width;
+        let height = self.surface_config.height;
+        let bytes_per_row = (width * 4).div_ceil(wgpu::COPY_BYTES_PER_ROW_ALIGNMENT)
+            * wgpu::COPY_BYTES_PER_ROW_ALIGNMENT;
+        let buffer = self.device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("readback"),
+            size: (bytes_per_row * height) as u64,
+            usage: wgpu::BufferUsages::COPY_DST | wgpu::BufferUsages::MAP_READ,
+            mapped_at_creation: false,
+        });
+
+        let mut encoder = self.device.create_command_encoder(&Default::default());
+        encoder.copy_texture_to_buffer(
+            wgpu::TexelCopyTextureInfo {
+                texture,
+                mip_level: 0,
+                origin: wgpu::Origin3d::ZERO,
+                aspect: wgpu::TextureAspect::All,
+            },
+            wgpu::TexelCopyBufferInfo {
+                buffer: &buffer,
+                layout: wgpu::TexelCopyBufferLayout {
+                    offset: 0,
+                    bytes_per_row: Some(bytes_per_row),
+                    rows_per_image: None,
+                },
+            },
+            wgpu::Extent3d { width, height, depth_or_array_layers: 1 },
+        );
+        self.queue.submit(std::iter::once(encoder.finish()));
+
+        let slice = buffer.slice(..);
+        let (tx, rx) = std::sync::mpsc::channel();
+        slice.map_async(wgpu::MapMode::Read, move |result| {
+            let _ = tx.send(result);
+        });
+        self.device.poll(wgpu::Maintain::Wait);
+        rx.recv().ok()?.ok()?;
+
+        let data = slice.get_mapped_range();
+        let row_bytes = (width * 4) as usize;
+        let bgra = matches!(
+            self.surface_config.format,
+            wgpu::TextureFormat::Bgra8Unorm | wgpu::TextureFormat::Bgra8UnormSrgb
+        );
+        let mut pixels = Vec::with_capacity(row_bytes * height as usize);
+        for y in 0..height as usize {
+            let start = y * bytes_per_row as usize;
+            let row = &data[start..start + row_bytes];
+            if bgra {
+                for px in row.chunks_exact(4) {
+                    pixels.extend_from_slice(&[px[2], px[1], px[0], px[3]]);
+                }
+            } else {
+                pixels.extend_from_slice(row);
+            }
+        }
+        Some(pixels)
+    }
+
+    /// 셀 그리드와 무관한 한 줄 텍스트 박스 (HUD, 스크롤 위치 배지). `right`에 오른쪽 정렬.
+    fn push_label_box(
+        &mut self,
+        bg_verts: &mut Vec<BgVertex>,
+        glyph_verts: &mut Vec<GlyphVertex>,
+        text: &str,
+        right: f32,
+        y: f32,
+        color: [f32; 3],
+    ) {
+        let (tab_cw, _) = self.tab_atlas.cell_size();
+        let pad = TAB_BAR_PADDING * self.scale;
+        let (x, w) = label_box(text, tab_cw, pad, right);
+        push_bg_rect(bg_verts, x, y, w, self.tab_bar_height(), LABEL_BOX_COLOR);
+        let screen_w = self.surface_config.width as f32;
+        self.push_tab_label(glyph_verts, text, x + pad, y, color, (0.0, screen_w));
+    }
+
+    /// 탭바 텍스트를 한 줄 그림. `clip` (x0, x1) 밖으로 나가는 글자는 생략.
+    fn push_tab_label(
+        &mut self,
+        verts: &mut Vec<GlyphVertex>,
+        text: &str,
+        x: f32,
+        tab_y: f32,
+        color: [f32; 3],
+        clip: (f32, f32),
+    ) {
+        let (tab_cw, tab_ch) = self.tab_atlas.cell_size();
+        let tab_ascent = self.tab_atlas.ascent();
+        let bar_h = self.tab_bar_height();
+        let mut cx = x;
+        for ch in text.chars() {
+            if ch == ' ' || cx < clip.0 || cx + tab_cw > clip.1 {
+                cx += tab_cw;
+                continue;
+            }
+            let region = self.ensure_tab_glyph_in_atlas(ch);
+            if region.width > 0 && region.height > 0 {
+                let baseline_y = tab_y + (bar_h - tab_ch) / 2.0 + tab_ascent;
+                let gx = cx + region.offset_x;
+                let gy = baseline_y - region.offset_y - region.height as f32;
+                push_glyph_quad(verts, &region, gx, gy, color);
+            }
+            cx += tab_cw;
+        }
+    }
+
     fn ensure_tab_glyph_in_atlas(&mut self, c: char) -> GlyphRegion {
         if let Some(&region) = self.tab_glyph_regions.get(&c) {
             return region;
@@ -1149,13 +1443,19 @@ impl GpuDrawer {
     }
 
     fn ensure_glyph_in_atlas(&mut self, c: char) -> GlyphRegion {
-        if let Some(&region) = self.glyph_regions.get(&c) {
+        self.ensure_glyph_at(c, 0)
+    }
+
+    /// Glyph region for `c` shifted right by `step / SUBPIXEL_STEPS` px.
+    fn ensure_glyph_at(&mut self, c: char, step: u8) -> GlyphRegion {
+        if let Some(&region) = self.glyph_regions.get(&(c, step)) {
             return region;
         }
 
         // Limit new glyph rasterization per frame to avoid UI freezes.
         if self.new_glyphs_this_frame >= MAX_NEW_GLYPHS_PER_FRAME {
             self.glyph_budget_exceeded = true;
+            self.deferred_glyphs += 1;
             if let Ok(mut guard) = GLYPH_LOG.lock() {
                 if let Some(f) = guard.as_mut() {
                     let _ = writeln!(f, "[glyph-budget] deferred '{}' (U+{:04X})", c, c as u32);
@@ -1168,7 +1468,7 @@ impl GpuDrawer {
         }
         self.new_glyphs_this_frame += 1;
 
-        let glyph = self.atlas.get_or_insert(c);
+        let glyph = self.atlas.get_or_insert_at(c, step);
         let w = glyph.width;
         let h = glyph.height;
 
@@ -1183,7 +1483,7 @@ impl GpuDrawer {
                 offset_x: 0.0,
                 offset_y: 0.0,
             };
-            self.glyph_regions.insert(c, region);
+            self.glyph_regions.insert((c, step), region);
             return region;
         }
 
@@ -1231,12 +1531,44 @@ impl GpuDrawer {
             offset_x: glyph.offset_x,
             offset_y: glyph.offset_y,
         };
-        self.glyph_regions.insert(c, region);
+        self.glyph_regions.insert((c, step), region);
         region
     }
 }
 
-fn rgb_to_f32(rgb: Rgb) -> [f32; 3] {
+fn create_offscreen_texture(
+    device: &wgpu::Device,
+    config: &wgpu::SurfaceConfiguration,
+) -> wgpu::Texture {
+    device.create_texture(&wgpu::TextureDescriptor {
+        label: Some("offscreen"),
+        size: wgpu::Extent3d {
+            width: config.width,
+            height: config.height,
+            depth_or_array_layers: 1,
+        },
+        mip_level_count: 1,
+        sample_count: 1,
+        dimension: wgpu::TextureDimension::D2,
+        format: config.format,
+        usage: wgpu::TextureUsages::RENDER_ATTACHMENT | wgpu::TextureUsages::COPY_SRC,
+        view_formats: &config.view_formats,
+    })
+}
+
+/// Whole-pixel origin and subpixel step for a glyph at `x`. Always pixel
+/// aligned (step 0) when subpixel positioning is off.
+pub(crate) fn subpixel_split(x: f32, subpixel: bool) -> (f32, u8) {
+    if !subpixel {
+        return (x.round(), 0);
+    }
+    let steps = SUBPIXEL_STEPS as f32;
+    let quantized = (x * steps).round();
+    let pixel = (quantized / steps).floor();
+    (pixel, (quantized - pixel * steps) as u8)
+}
+
+pub(crate) fn rgb_to_f32(rgb: Rgb) -> [f32; 3] {
     [
         rgb.r as f32 / 255.0,
         rgb.g as f32 / 255.0,
@@ -1244,12 +1576,47 @@ fn rgb_to_f32(rgb: Rgb) -> [f32; 3] {
     ]
 }
 
-fn rgb_to_f32a(rgb: Rgb) -> [f32; 4] {
+pub(crate) fn rgb_to_f32a(rgb: Rgb) -> [f32; 4] {
     let [r, g, b] = rgb_to_f32(rgb);
     [r, g, b, 1.0]
 }
 
-fn push_bg_rect(bg_verts: &mut Vec<BgVertex>, x: f32, y: f32, w: f32, h: f32, color: [f32; 4]) {
+/// Box for a one-line overlay label right-aligned at `right`.
+/// Returns (x, width); the text starts at `x + pad`.
+pub(crate) fn label_box(text: &str, char_w: f32, pad: f32, right: f32) -> (f32, f32) {
+    let w = text.chars().count() as f32 * char_w + pad * 2.0;
+    ((right - w).max(0.0), w)
+}
+
+/// Top of the scroll position badge: level with the scrollbar thumb, kept
+/// inside the content area.
+pub(crate) fn scroll_badge_y(thumb_top: f32, content_y_offset: f32, clip_top: f32, screen_h: f32, box_h: f32) -> f32 {
+    let y = content_y_offset + thumb_top * (screen_h - content_y_offset);
+    y.clamp(clip_top, (screen_h - box_h).max(clip_top))
+}
+
+/// Four thin rects around the cursor cell(s).
+pub(crate) fn push_hollow_cursor(
+    verts: &mut Vec<BgVertex>,
+    cursor: &HollowCursor,
+    cell_w: f32,
+    cell_h: f32,
+    (x_off, y_off): (f32, f32),
+    scale: f32,
+) {
+    let x = x_off + (cursor.col as f32 * cell_w).round();
+    let y = y_off + cursor.row as f32 * cell_h;
+    let w = (cursor.width.max(1) as f32 * cell_w).round();
+    // 1pt 두께
+    let t = (REFERENCE_SCALE as f32 * scale).round().max(1.0);
+    let color = rgb_to_f32a(cursor.color);
+    push_bg_rect(verts, x, y, w, t, color);
+    push_bg_rect(verts, x, y + cell_h - t, w, t, color);
+    push_bg_rect(verts, x, y + t, t, cell_h - t * 2.0, color);
+    push_bg_rect(verts, x + w - t, y + t, t, cell_h - t * 2.0, color);
+}
+
+pub(crate) fn push_bg_rect(bg_verts: &mut Vec<BgVertex>, x: f32, y: f32, w: f32, h: f32, color: [f32; 4]) {
     bg_verts.push(BgVertex {
         position: [x, y],
         color,
@@ -1276,6 +1643,200 @@ fn push_bg_rect(bg_verts: &mut Vec<BgVertex>, x: f32, y: f32, w: f32, h: f32, co
     });
 }
 
+/// Box drawing characters (U+2500..U+257F) as pixel-aligned rects.
+/// Underline of one cell `w` wide whose top edge is at `y`, `line_h` thick.
+/// Dotted, dashed and curly patterns restart every cell, so they line up
+/// across a run of cells.
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn push_underline_rects(
+    verts: &mut Vec<BgVertex>,
+    style: UnderlineStyle,
+    x: f32,
+    y: f32,
+    w: f32,
+    cell_h: f32,
+    line_h: f32,
+    color: [f32; 4],
+) {
+    let bottom = y + cell_h - line_h;
+    match style {
+        UnderlineStyle::Single => push_bg_rect(verts, x, bottom, w, line_h, color),
+        UnderlineStyle::Double => {
+            push_bg_rect(verts, x, bottom, w, line_h, color);
+            push_bg_rect(verts, x, bottom - line_h * 2.0, w, line_h, color);
+        }
+        UnderlineStyle::Dotted => {
+            let dot = line_h.round().max(1.0);
+            let mut dx = 0.0;
+            while dx < w {
+                push_bg_rect(verts, x + dx, bottom, dot.min(w - dx), line_h, color);
+                dx += dot * 2.0;
+            }
+        }
+        UnderlineStyle::Dashed => {
+            let dash = (w / 2.0).round().max(1.0);
+            push_bg_rect(verts, x + ((w - dash) / 2.0).round(), bottom, dash, line_h, color);
+        }
+        UnderlineStyle::Curly => {
+            // One sine period per cell, drawn as 1px wide columns
+            let amplitude = line_h.max(1.0);
+            let mid = bottom - amplitude;
+            let columns = w.round().max(1.0) as u32;
+            for i in 0..columns {
+                let phase = (i as f32 + 0.5) / columns as f32 * std::f32::consts::TAU;
+                let cy = (mid + amplitude * phase.sin()).round();
+                push_bg_rect(verts, x + i as f32, cy, 1.0, line_h, color);
+            }
+        }
+    }
+}
+
+/// Block elements and box drawing are drawn as rects instead of font glyphs.
+/// Returns false for any other character.
+pub(crate) fn push_cell_shape_rects(
+    verts: &mut Vec<BgVertex>,
+    ch: char,
+    x: f32,
+    y: f32,
+    cell_w: f32,
+    cell_h: f32,
+    fg: [f32; 4],
+) -> bool {
+    // Block elements (U+2580..U+259F, excluding shades U+2591-U+2593)
+    if ch >= '\u{2580}'
+        && ch <= '\u{259F}'
+        && !(ch >= '\u{2591}' && ch <= '\u{2593}')
+        && push_block_element_rects(verts, ch, x, y, cell_w, cell_h, fg)
+    {
+        return true;
+    }
+
+    // Box drawing characters (U+2500..U+257F)
+    if ch >= '\u{2500}' && ch <= '\u{257F}' {
+        if let Some(segs) = box_drawing_segments(ch) {
+            push_box_drawing_rects(verts, &segs, x, y, cell_w, cell_h, fg);
+            return true;
+        }
+    }
+    false
+}
+
+fn push_box_drawing_rects(
+    verts: &mut Vec<BgVertex>,
+    segs: &BoxSegments,
+    cx: f32,
+    cy: f32,
+    cell_w: f32,
+    cell_h: f32,
+    fg: [f32; 4],
+) {
+    let light_h = 1.0_f32;
+    let heavy_h = (cell_h / 8.0).ceil().max(2.0);
+    let light_w = 1.0_f32;
+    let heavy_w = (cell_w / 8.0).ceil().max(2.0);
+    let mid_x = (cell_w / 2.0).floor();
+    let mid_y = (cell_h / 2.0).floor();
+
+    // Horizontal segment
+    if segs.h_weight != LineWeight::None && segs.h_weight != LineWeight::Double {
+        let th = if segs.h_weight == LineWeight::Heavy {
+            heavy_h
+        } else {
+            light_h
+        };
+        let x0 = if segs.left {
+            0.0
+        } else {
+            mid_x - (th / 2.0).floor()
+        };
+        let x1 = if segs.right {
+            cell_w
+        } else {
+            mid_x + (th / 2.0).ceil()
+        };
+        push_bg_rect(
+            verts,
+            cx + x0,
+            cy + mid_y - (th / 2.0).floor(),
+            x1 - x0,
+            th,
+            fg,
+        );
+    }
+    // Vertical segment
+    if segs.v_weight != LineWeight::None && segs.v_weight != LineWeight::Double {
+        let tw = if segs.v_weight == LineWeight::Heavy {
+            heavy_w
+        } else {
+            light_w
+        };
+        let y0 = if segs.up {
+            0.0
+        } else {
+            mid_y - (tw / 2.0).floor()
+        };
+        let y1 = if segs.down {
+            cell_h
+        } else {
+            mid_y + (tw / 2.0).ceil()
+        };
+        push_bg_rect(
+            verts,
+            cx + mid_x - (tw / 2.0).floor(),
+            cy + y0,
+            tw,
+            y1 - y0,
+            fg,
+        );
+    }
+    // Double horizontal
+    if segs.h_weight == LineWeight::Double {
+        let gap = (cell_h / 6.0).ceil();
+        let th = light_h;
+        let x0 = if segs.left { 0.0 } else { mid_x };
+        let x1 = if segs.right { cell_w } else { mid_x + light_w };
+        push_bg_rect(
+            verts,
+            cx + x0,
+            cy + mid_y - gap - th / 2.0,
+            x1 - x0,
+            th,
+            fg,
+        );
+        push_bg_rect(
+            verts,
+            cx + x0,
+            cy + mid_y + gap - th / 2.0,
+            x1 - x0,
+            th,
+            fg,
+        );
+    }
+    // Double vertical
+    if segs.v_weight == LineWeight::Double {
+        let gap = (cell_w / 6.0).ceil();
+        let tw = light_w;
+        let y0 = if segs.up { 0.0 } else { mid_y };
+        let y1 = if segs.down { cell_h } else { mid_y + light_h };
+        push_bg_rect(
+            verts,
+            cx + mid_x - gap - tw / 2.0,
+            cy + y0,
+            tw,
+            y1 - y0,
+            fg,
+        );
+        push_bg_rect(
+            verts,
+            cx + mid_x + gap - tw / 2.0,
+            cy + y0,
+            tw,
+            y1 - y0,
+            fg,
+        );
+    }
+}
+
 fn push_block_element_rects(
     bg_verts: &mut Vec<BgVertex>,
     ch: char,
@@ -1435,6 +1996,54 @@ mod tests {
             fg: Rgb::new(255, 255, 255),
             bg: Rgb::new(0, 0, 0),
             flags: CellFlags::empty(),
+            zerowidth: None,
+            underline_style: UnderlineStyle::Single,
+            underline_color: Rgb::new(255, 255, 255),
+        }
+    }
+
+    #[test]
+    fn subpixel_split_quantizes_fraction() {
+        assert_eq!(subpixel_split(10.3, false), (10.0, 0));
+        assert_eq!(subpixel_split(10.0, true), (10.0, 0));
+        assert_eq!(subpixel_split(10.3, true), (10.0, 1));
+        assert_eq!(subpixel_split(10.5, true), (10.0, 2));
+        assert_eq!(subpixel_split(10.9, true), (11.0, 0));
+    }
+
+    #[test]
+    fn label_box_is_right_aligned_and_clamped() {
+        assert_eq!(label_box("10/20", 10.0, 4.0, 200.0), (142.0, 58.0));
+        assert_eq!(label_box("10/20", 10.0, 4.0, 30.0), (0.0, 58.0));
+    }
+
+    #[test]
+    fn scroll_badge_stays_inside_content() {
+        // thumb at the middle of a 100..500 content area
+        assert_eq!(scroll_badge_y(0.5, 100.0, 100.0, 500.0, 40.0), 300.0);
+        // bottom: kept above the window edge
+        assert_eq!(scroll_badge_y(1.0, 100.0, 100.0, 500.0, 40.0), 460.0);
+        // smooth scroll offset above the clip line
+        assert_eq!(scroll_badge_y(0.0, 80.0, 100.0, 500.0, 40.0), 100.0);
+    }
+
+    #[test]
+    fn underline_styles_stay_inside_the_cell() {
+        let styles = [
+            (UnderlineStyle::Single, 1),
+            (UnderlineStyle::Double, 2),
+            (UnderlineStyle::Dotted, 5),
+            (UnderlineStyle::Dashed, 1),
+            (UnderlineStyle::Curly, 10),
+        ];
+        for (style, rects) in styles {
+            let mut vertices = Vec::new();
+            push_underline_rects(&mut vertices, style, 0.0, 40.0, 10.0, 20.0, 1.0, [1.0; 4]);
+            assert_eq!(vertices.len(), rects * 6, "{style:?}");
+            for v in &vertices {
+                assert!((0.0..=10.0).contains(&v.position[0]), "{style:?}");
+                assert!((40.0..=60.0).contains(&v.position[1]), "{style:?}");
+            }
         }
     }
 
@@ -1516,4 +2125,18 @@ mod tests {
         assert_eq!(mode, wgpu::CompositeAlphaMode::Opaque);
     }
 
+    #[test]
+    fn hollow_cursor_outlines_wide_cell() {
+        let cursor = HollowCursor { row: 1, col: 2, width: 2, color: Rgb::new(255, 0, 0) };
+        let mut verts = Vec::new();
+        push_hollow_cursor(&mut verts, &cursor, 10.0, 20.0, (0.0, 5.0), 1.0);
+        assert_eq!(verts.len(), 4 * 6);
+        let xs = verts.iter().map(|v| v.position[0]);
+        let ys = verts.iter().map(|v| v.position[1]);
+        assert_eq!(xs.clone().fold(f32::MAX, f32::min), 20.0);
+        assert_eq!(xs.fold(f32::MIN, f32::max), 40.0);
+        assert_eq!(ys.clone().fold(f32::MAX, f32::min), 25.0);
+        assert_eq!(ys.fold(f32::MIN, f32::max), 45.0);
+        assert!(verts.iter().all(|v| v.color == [1.0, 0.0, 0.0, 1.0]));
+    }
 }
@@ -0,0 +1,211 @@
+//! Cell geometry batched by pipeline: backgrounds, then decoration rects
+//! (underline, strikethrough, box drawing, block elements), then glyph
+//! quads. Each row's geometry is cached by a hash of its commands, so
+//! a static screen reuses last frame's vertices instead of rebuilding them.
+
+use std::collections::{BTreeMap, HashMap};
+use std::hash::{Hash, Hasher};
+
+use growterm_types::RenderCommand;
+
+use crate::renderer::{BgVertex, GlyphVertex};
+
+/// Vertices for one row, with y relative to the row's top edge.
+#[derive(Default)]
+pub(crate) struct RowGeometry {
+    pub bg: Vec<BgVertex>,
+    pub decorations: Vec<BgVertex>,
+    pub glyphs: Vec<GlyphVertex>,
+}
+
+/// The frame's cell geometry in screen coordinates.
+#[derive(Default)]
+pub(crate) struct Scene {
+    pub bg: Vec<BgVertex>,
+    pub decorations: Vec<BgVertex>,
+    pub glyphs: Vec<GlyphVertex>,
+}
+
+impl Scene {
+    /// Copy a row's vertices in, moved right by `x` and down to `y`.
+    pub fn append_row(&mut self, row: &RowGeometry, x: f32, y: f32) {
+        let shift = |mut v: BgVertex| {
+            v.position[0] += x;
+            v.position[1] += y;
+            v
+        };
+        self.bg.extend(row.bg.iter().copied().map(shift));
+        self.decorations.extend(row.decorations.iter().copied().map(shift));
+        self.glyphs.extend(row.glyphs.iter().map(|&v| GlyphVertex {
+            position: [v.position[0] + x, v.position[1] + y],
+            ..v
+        }));
+    }
+
+    /// Backgrounds first so no cell background covers a neighbour's decoration.
+    pub fn rect_vertices(&self) -> Vec<BgVertex> {
+        let mut verts = Vec::with_capacity(self.bg.len() + self.decorations.len());
+        verts.extend_from_slice(&self.bg);
+        verts.extend_from_slice(&self.decorations);
+        verts
+    }
+}
+
+/// Commands of one screen row, in their original order.
+pub(crate) struct SceneRow {
+    pub row: u16,
+    pub hash: u64,
+    pub indices: Vec<usize>,
+}
+
+pub(crate) fn group_rows(commands: &[RenderCommand]) -> Vec<SceneRow> {
+    let mut rows: BTreeMap<u16, Vec<usize>> = BTreeMap::new();
+    for (i, cmd) in commands.iter().enumerate() {
+        rows.entry(cmd.row).or_default().push(i);
+    }
+    rows.into_iter()
+        .map(|(row, indices)| SceneRow {
+            row,
+            hash: row_hash(indices.iter().map(|&i| &commands[i])),
+            indices,
+        })
+        .collect()
+}
+
+/// Everything that affects a row's geometry except its vertical position.
+fn row_hash<'a>(commands: impl Iterator<Item = &'a RenderCommand>) -> u64 {
+    let mut hasher = std::collections::hash_map::DefaultHasher::new();
+    for cmd in commands {
+        cmd.col.hash(&mut hasher);
+        cmd.character.hash(&mut hasher);
+        (cmd.fg.r, cmd.fg.g, cmd.fg.b).hash(&mut hasher);
+        (cmd.bg.r, cmd.bg.g, cmd.bg.b).hash(&mut hasher);
+        cmd.flags.bits().hash(&mut hasher);
+        cmd.zerowidth.hash(&mut hasher);
+        cmd.underline_style.hash(&mut hasher);
+        (cmd.underline_color.r, cmd.underline_color.g, cmd.underline_color.b).hash(&mut hasher);
+    }
+    hasher.finish()
+}
+
+struct CachedRow {
+    hash: u64,
+    geometry: RowGeometry,
+}
+
+/// Row geometry from previous frames, keyed by screen row.
+#[derive(Default)]
+pub(crate) struct RowCache {
+    rows: HashMap<u16, CachedRow>,
+}
+
+impl RowCache {
+    pub fn get(&self, row: u16, hash: u64) -> Option<&RowGeometry> {
+        self.rows
+            .get(&row)
+            .filter(|cached| cached.hash == hash)
+            .map(|cached| &cached.geometry)
+    }
+
+    pub fn insert(&mut self, row: u16, hash: u64, geometry: RowGeometry) {
+        self.rows.insert(row, CachedRow { hash, geometry });
+    }
+
+    /// Forget rows that were not drawn this frame.
+    pub fn retain_rows(&mut self, rows: &[SceneRow]) {
+        self.rows
+            .retain(|row, _| rows.binary_search_by_key(row, |r| r.row).is_ok());
+    }
+
+    /// Glyph regions or cell size changed: every cached vertex is stale.
+    pub fn clear(&mut self) {
+        self.rows.clear();
+    }
+
+    #[cfg(test)]
+    fn len(&self) -> usize {
+        self.rows.len()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use growterm_types::{CellFlags, Rgb, UnderlineStyle};
+
+    fn command(row: u16, col: u16, character: char) -> RenderCommand {
+        RenderCommand {
+            col,
+            row,
+            character,
+            fg: Rgb::new(255, 255, 255),
+            bg: Rgb::new(0, 0, 0),
+            flags: CellFlags::empty(),
+            zerowidth: None,
+            underline_style: UnderlineStyle::Single,
+            underline_color: Rgb::new(255, 255, 255),
+        }
+    }
+
+    #[test]
+    fn rows_are_grouped_in_order() {
+        let commands = [command(1, 0, 'b'), command(0, 0, 'a'), command(1, 1, 'c')];
+        let rows = group_rows(&commands);
+        assert_eq!(rows.len(), 2);
+        assert_eq!((rows[0].row, rows[0].indices.clone()), (0, vec![1]));
+        assert_eq!((rows[1].row, rows[1].indices.clone()), (1, vec![0, 2]));
+    }
+
+    #[test]
+    fn hash_ignores_row_but_not_content() {
+        let a = group_rows(&[command(0, 0, 'x')]);
+        let moved = group_rows(&[command(5, 0, 'x')]);
+        let changed = group_rows(&[command(0, 0, 'y')]);
+        let mut underlined = command(0, 0, 'x');
+        underlined.flags = CellFlags::UNDERLINE;
+        let underlined = group_rows(&[underlined]);
+        assert_eq!(a[0].hash, moved[0].hash);
+        assert_ne!(a[0].hash, changed[0].hash);
+        assert_ne!(a[0].hash, underlined[0].hash);
+    }
+
+    #[test]
+    fn cache_hits_only_same_row_and_hash() {
+        let mut cache = RowCache::default();
+        cache.insert(3, 42, RowGeometry::default());
+        assert!(cache.get(3, 42).is_some());
+        assert!(cache.get(3, 7).is_none());
+        assert!(cache.get(4, 42).is_none());
+    }
+
+    #[test]
+    fn retain_drops_rows_not_drawn() {
+        let mut cache = RowCache::default();
+        cache.insert(0, 1, RowGeometry::default());
+        cache.insert(9, 1, RowGeometry::default());
+        cache.retain_rows(&group_rows(&[command(0, 0, 'a')]));
+        assert_eq!(cache.len(), 1);
+        assert!(cache.get(0, 1).is_some());
+    }
+
+    #[test]
+    fn append_row_shifts_y_and_keeps_batches_apart() {
+        let vertex = BgVertex { position: [2.0, 1.0], color: [1.0; 4] };
+        let geometry = RowGeometry {
+            bg: vec![vertex],
+            decorations: vec![vertex, vertex],
+            glyphs: vec![GlyphVertex { position: [2.0, 3.0], tex_coords: [0.0; 2], color: [1.0; 3] }],
+        };
+        let mut scene = Scene::default();
+        scene.append_row(&geometry, 0.0, 20.0);
+        assert_eq!(scene.bg[0].position, [2.0, 21.0]);
+        assert_eq!(scene.glyphs[0].position, [2.0, 23.0]);
+        let rects = scene.rect_vertices();
+        assert_eq!(rects.len(), 3);
+        assert_eq!(rects[2].position, [2.0, 21.0]);
+        // 창 여백만큼 오른쪽으로
+        scene.append_row(&geometry, 8.0, 0.0);
+        assert_eq!(scene.bg[1].position, [10.0, 1.0]);
+        assert_eq!(scene.glyphs[1].position, [10.0, 3.0]);
+    }
+}
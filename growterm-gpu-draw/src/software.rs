@@ -0,0 +1,473 @@
+//! CPU renderer for machines where wgpu can't start (no Metal device, some
+//! VMs) and for screenshots without a GPU. Cell geometry comes from the same
+//! rect helpers as `GpuDrawer`; glyphs are blended straight from the atlas
+//! bitmaps into an RGBA frame that is handed to a presenter.
+
+use growterm_types::{CellFlags, RenderCommand};
+
+use crate::atlas::{GlyphAtlas, RasterizedGlyph};
+use crate::backend::{Overlays, Renderer};
+use crate::renderer::{
+    label_box, push_bg_rect, push_cell_shape_rects, push_hollow_cursor, push_underline_rects, rgb_to_f32, rgb_to_f32a, scroll_badge_y,
+    subpixel_split, BgVertex, HUD_TEXT_COLOR, LABEL_BOX_COLOR, REFERENCE_SCALE, SCROLLBAR_WIDTH,
+    SCROLL_BADGE_TEXT_COLOR, TAB_BAR_PADDING, TAB_FONT_SIZE,
+};
+use crate::tab_strip::{TabStrip, ARROW_WIDTH};
+
+/// Receives each finished frame as tightly packed RGBA8 (width, height).
+pub type Presenter = Box<dyn FnMut(&[u8], u32, u32) + Send>;
+
+/// RGBA8 frame with alpha blending, y down.
+struct Frame {
+    width: u32,
+    height: u32,
+    pixels: Vec<u8>,
+}
+
+impl Frame {
+    fn new(width: u32, height: u32) -> Self {
+        Self { width, height, pixels: vec![0; (width * height * 4) as usize] }
+    }
+
+    fn clear(&mut self) {
+        for px in self.pixels.chunks_exact_mut(4) {
+            px.copy_from_slice(&[0, 0, 0, 255]);
+        }
+    }
+
+    fn blend(&mut self, x: u32, y: u32, color: [f32; 3], alpha: f32) {
+        let i = ((y * self.width + x) * 4) as usize;
+        for (c, dst) in color.iter().zip(&mut self.pixels[i..i + 3]) {
+            let blended = c * 255.0 * alpha + *dst as f32 * (1.0 - alpha);
+            *dst = blended.round().clamp(0.0, 255.0) as u8;
+        }
+    }
+
+    /// Rects from `push_bg_rect` (six vertices each); rows above `clip_top` are skipped.
+    fn fill_rects(&mut self, verts: &[BgVertex], clip_top: f32) {
+        for quad in verts.chunks_exact(6) {
+            let [x0, y0] = quad[0].position;
+            let [x1, y1] = quad[4].position;
+            let [r, g, b, a] = quad[0].color;
+            let xs = x0.round().max(0.0) as u32..(x1.round().max(0.0) as u32).min(self.width);
+            let ys = y0.max(clip_top).round().max(0.0) as u32..(y1.round().max(0.0) as u32).min(self.height);
+            for y in ys {
+                for x in xs.clone() {
+                    self.blend(x, y, [r, g, b], a);
+                }
+            }
+        }
+    }
+
+    /// Blend a glyph's coverage bitmap with its top-left corner at (gx, gy).
+    fn draw_glyph(&mut self, glyph: &RasterizedGlyph, gx: f32, gy: f32, color: [f32; 3], clip_top: f32) {
+        let (gx, gy) = (gx.round() as i64, gy.round() as i64);
+        for row in 0..glyph.height as i64 {
+            let y = gy + row;
+            if y < clip_top.ceil() as i64 || y < 0 || y >= self.height as i64 {
+                continue;
+            }
+            for col in 0..glyph.width as i64 {
+                let x = gx + col;
+                if x < 0 || x >= self.width as i64 {
+                    continue;
+                }
+                let coverage = glyph.bitmap[(row * glyph.width as i64 + col) as usize];
+                if coverage > 0 {
+                    self.blend(x as u32, y as u32, color, coverage as f32 / 255.0);
+                }
+            }
+        }
+    }
+}
+
+pub struct SoftwareRenderer {
+    frame: Frame,
+    atlas: GlyphAtlas,
+    tab_atlas: GlyphAtlas,
+    font_size: f32,
+    /// Backing scale relative to `REFERENCE_SCALE`
+    scale: f32,
+    /// (line height multiplier, cell padding before display scaling)
+    cell_spacing: (f32, f32),
+    presenter: Option<Presenter>,
+}
+
+impl SoftwareRenderer {
+    pub fn new(width: u32, height: u32, font_size: f32, font_path: Option<&str>) -> Self {
+        let fallback_font = std::sync::Arc::new(GlyphAtlas::load_fallback_font(font_size));
+        let font = std::sync::Arc::new(GlyphAtlas::load_font(font_size, font_path));
+        let tab_font = std::sync::Arc::new(GlyphAtlas::load_builtin_font(TAB_FONT_SIZE));
+        Self {
+            frame: Frame::new(width.max(1), height.max(1)),
+            atlas: GlyphAtlas::with_shared_fonts(font_size, font, fallback_font.clone()),
+            tab_atlas: GlyphAtlas::with_shared_fonts(TAB_FONT_SIZE, tab_font, fallback_font),
+            font_size,
+            scale: 1.0,
+            cell_spacing: (1.0, 0.0),
+            presenter: None,
+        }
+    }
+
+    /// Send every drawn frame to `presenter` (e.g. the window's layer).
+    pub fn with_presenter(mut self, presenter: Presenter) -> Self {
+        self.presenter = Some(presenter);
+        self
+    }
+
+    /// The last drawn frame as tightly packed RGBA8.
+    pub fn pixels(&self) -> &[u8] {
+        &self.frame.pixels
+    }
+
+    fn draw_cells(&mut self, commands: &[RenderCommand], (x_off, y_off): (f32, f32), clip_top: f32) {
+        let (cell_w, cell_h) = self.atlas.cell_size();
+        let baseline = self.atlas.baseline();
+        let subpixel = self.atlas.subpixel_positioning();
+        let glyph_inset = self.atlas.glyph_inset();
+        let mut bg = Vec::new();
+        let mut decorations = Vec::new();
+        let mut glyphs = Vec::new();
+        for cmd in commands {
+            let y = y_off + cmd.row as f32 * cell_h;
+            let cells = if cmd.flags.contains(CellFlags::WIDE_CHAR) { 2.0 } else { 1.0 };
+            let glyph_x = x_off + cmd.col as f32 * cell_w;
+            let x = glyph_x.round();
+            let w = (glyph_x + cell_w * cells).round() - x;
+            push_bg_rect(&mut bg, x, y, w, cell_h, rgb_to_f32a(cmd.bg));
+
+            let line_h = (cell_h * 0.07).max(1.0);
+            let fg = rgb_to_f32a(cmd.fg);
+            if cmd.flags.contains(CellFlags::UNDERLINE) {
+                let color = rgb_to_f32a(cmd.underline_color);
+                push_underline_rects(&mut decorations, cmd.underline_style, x, y, w, cell_h, line_h, color);
+            }
+            if cmd.flags.contains(CellFlags::STRIKETHROUGH) {
+                let strike_y = (cell_h * 0.55 - line_h / 2.0).round();
+                push_bg_rect(&mut decorations, x, y + strike_y, w, line_h, fg);
+            }
+
+            if cmd.character == ' ' || cmd.flags.contains(CellFlags::HIDDEN) {
+                continue;
+            }
+            if push_cell_shape_rects(&mut decorations, cmd.character, x, y, cell_w, cell_h, fg) {
+                continue;
+            }
+            let (pen_x, step) = subpixel_split(glyph_x + glyph_inset, subpixel);
+            glyphs.push((cmd.character, step, pen_x, y, rgb_to_f32(cmd.fg)));
+        }
+
+        self.frame.fill_rects(&bg, clip_top);
+        self.frame.fill_rects(&decorations, clip_top);
+        for (ch, step, pen_x, y, color) in glyphs {
+            let glyph = self.atlas.get_or_insert_at(ch, step);
+            let gx = pen_x + glyph.offset_x;
+            let gy = y + baseline - glyph.offset_y - glyph.height as f32;
+            self.frame.draw_glyph(glyph, gx, gy, color, clip_top);
+        }
+    }
+
+    /// One-line label on a translucent box, right-aligned at `right`.
+    fn draw_label_box(&mut self, text: &str, right: f32, top: f32, color: [f32; 3]) {
+        let (tab_cw, _) = self.tab_atlas.cell_size();
+        let pad = TAB_BAR_PADDING * self.scale;
+        let (x, w) = label_box(text, tab_cw, pad, right);
+        let mut rects = Vec::new();
+        push_bg_rect(&mut rects, x, top, w, self.tab_bar_height(), LABEL_BOX_COLOR);
+        self.frame.fill_rects(&rects, 0.0);
+        self.draw_label(text, x + pad, top, color, (0.0, self.frame.width as f32));
+    }
+
+    fn draw_label(&mut self, text: &str, x: f32, top: f32, color: [f32; 3], clip: (f32, f32)) {
+        let (tab_cw, tab_ch) = self.tab_atlas.cell_size();
+        let baseline = top + (self.tab_bar_height() - tab_ch) / 2.0 + self.tab_atlas.ascent();
+        let mut cx = x;
+        for ch in text.chars() {
+            if ch != ' ' && cx >= clip.0 && cx + tab_cw <= clip.1 {
+                let glyph = self.tab_atlas.get_or_insert(ch);
+                let gx = cx + glyph.offset_x;
+                let gy = baseline - glyph.offset_y - glyph.height as f32;
+                self.frame.draw_glyph(glyph, gx, gy, color, 0.0);
+            }
+            cx += tab_cw;
+        }
+    }
+
+    fn render(&mut self, commands: &[RenderCommand], overlays: &Overlays) {
+        self.frame.clear();
+        self.draw_cells(commands, (overlays.content_x_offset.round(), overlays.content_y_offset), overlays.content_clip_top.max(0.0));
+        self.draw_overlays(overlays);
+    }
+
+    fn draw_overlays(&mut self, overlays: &Overlays) {
+        let screen_w = self.frame.width as f32;
+        let screen_h = self.frame.height as f32;
+        let mut rects = Vec::new();
+
+        if let Some((thumb_top, thumb_height)) = overlays.scrollbar {
+            let term_h = screen_h - overlays.content_y_offset;
+            let y0 = overlays.content_y_offset + thumb_top * term_h;
+            push_bg_rect(&mut rects, screen_w - SCROLLBAR_WIDTH, y0, SCROLLBAR_WIDTH, thumb_height * term_h, [0.5, 0.5, 0.5, 1.0]);
+        }
+
+        if let Some(cursor) = &overlays.hollow_cursor {
+            let (cell_w, cell_h) = self.atlas.cell_size();
+            push_hollow_cursor(&mut rects, cursor, cell_w, cell_h, (overlays.content_x_offset.round(), overlays.content_y_offset), self.scale);
+        }
+
+        let header = [0.0, 0.0, 0.0, if overlays.transparent_tab_bar { overlays.header_opacity } else { 1.0 }];
+        if overlays.transparent_tab_bar && overlays.title_bar_height > 0.0 {
+            push_bg_rect(&mut rects, 0.0, 0.0, screen_w, overlays.title_bar_height, header);
+        }
+        let tab_y = if overlays.transparent_tab_bar { overlays.title_bar_height } else { 0.0 };
+        let bar_h = self.tab_bar_height();
+        if overlays.tab_bar.is_some() {
+            push_bg_rect(&mut rects, 0.0, tab_y, screen_w, bar_h, header);
+        }
+        self.frame.fill_rects(&rects, 0.0);
+
+        if let Some(tab_info) = overlays.tab_bar {
+            let (tab_cw, _) = self.tab_atlas.cell_size();
+            let strip = TabStrip::new(tab_info.titles.len(), screen_w, tab_info.scroll_offset);
+            let (view_x, view_w) = strip.viewport();
+            for (i, title) in tab_info.titles.iter().enumerate() {
+                let x = strip.tab_x(i);
+                if x + strip.tab_width <= view_x || x >= view_x + view_w {
+                    continue;
+                }
+                let clip = (x.max(view_x), (x + strip.tab_width).min(view_x + view_w));
+                if tab_info.dragging_index == Some(i) {
+                    let mut drag = Vec::new();
+                    push_bg_rect(&mut drag, clip.0, tab_y, clip.1 - clip.0, bar_h, [0.4, 0.4, 0.2, 1.0]);
+                    self.frame.fill_rects(&drag, 0.0);
+                }
+                let color = if i == tab_info.active_index { [1.0; 3] } else { [0.4; 3] };
+                let text_w = title.chars().count() as f32 * tab_cw;
+                self.draw_label(title, x + (strip.tab_width - text_w) / 2.0, tab_y, color, clip);
+            }
+            if strip.overflow {
+                let arrow_color = |active: bool| if active { [1.0; 3] } else { [0.4; 3] };
+                let left = arrow_color(strip.scroll > 0.0);
+                let right = arrow_color(strip.scroll < strip.max_scroll());
+                self.draw_label("<", (ARROW_WIDTH - tab_cw) / 2.0, tab_y, left, (0.0, screen_w));
+                self.draw_label(">", screen_w - ARROW_WIDTH + (ARROW_WIDTH - tab_cw) / 2.0, tab_y, right, (0.0, screen_w));
+            }
+        }
+
+        let pad = TAB_BAR_PADDING * self.scale;
+        if let Some(text) = overlays.hud {
+            self.draw_label_box(text, screen_w - pad, overlays.content_clip_top + pad, HUD_TEXT_COLOR);
+        }
+        if let Some(lines) = overlays.report {
+            let top = overlays.content_clip_top + pad + if overlays.hud.is_some() { bar_h } else { 0.0 };
+            for (i, line) in lines.iter().enumerate() {
+                self.draw_label_box(line, screen_w - pad, top + i as f32 * bar_h, HUD_TEXT_COLOR);
+            }
+        }
+        if let (Some(text), Some((thumb_top, _))) = (overlays.scroll_badge, overlays.scrollbar) {
+            let y = scroll_badge_y(thumb_top, overlays.content_y_offset, overlays.content_clip_top, screen_h, bar_h);
+            self.draw_label_box(text, screen_w - SCROLLBAR_WIDTH - pad, y, SCROLL_BADGE_TEXT_COLOR);
+        }
+
+        // 코칭 문구는 그리지 않고 휴식 틴트만 (overlay.wgsl과 같은 0.3 알파)
+        if overlays.is_break {
+            let mut tint = Vec::new();
+            push_bg_rect(&mut tint, 0.0, 0.0, screen_w, screen_h, [0.6, 0.0, 0.0, 0.3]);
+            self.frame.fill_rects(&tint, 0.0);
+        }
+    }
+}
+
+impl Renderer for SoftwareRenderer {
+    fn draw(&mut self, commands: &[RenderCommand], overlays: &Overlays) -> bool {
+        self.render(commands, overlays);
+        if let Some(present) = self.presenter.as_mut() {
+            present(&self.frame.pixels, self.frame.width, self.frame.height);
+        }
+        false
+    }
+
+    fn capture(&mut self, commands: &[RenderCommand], overlays: &Overlays) -> Option<Vec<u8>> {
+        self.render(commands, overlays);
+        Some(self.frame.pixels.clone())
+    }
+
+    fn resize(&mut self, width: u32, height: u32) {
+        if width == 0 || height == 0 || (width, height) == (self.frame.width, self.frame.height) {
+            return;
+        }
+        self.frame = Frame::new(width, height);
+    }
+
+    fn cell_size(&self) -> (f32, f32) {
+        self.atlas.cell_size()
+    }
+
+    fn set_font(&mut self, font_path: Option<&str>, size: f32) {
+        self.font_size = size;
+        self.atlas.set_font(font_path, size * self.scale);
+    }
+
+    fn set_font_size(&mut self, size: f32) {
+        self.font_size = size;
+        self.atlas.set_size(size * self.scale);
+    }
+
+    fn tab_bar_height(&self) -> f32 {
+        let (_, tab_ch) = self.tab_atlas.cell_size();
+        tab_ch + TAB_BAR_PADDING * self.scale
+    }
+
+    fn surface_size(&self) -> (u32, u32) {
+        (self.frame.width, self.frame.height)
+    }
+
+    fn set_subpixel_positioning(&mut self, enabled: bool) {
+        self.atlas.set_subpixel_positioning(enabled);
+    }
+
+    fn set_cell_spacing(&mut self, line_height: f32, cell_padding: f32) {
+        self.cell_spacing = (line_height, cell_padding);
+        self.atlas.set_cell_spacing(line_height, cell_padding * self.scale);
+    }
+
+    fn set_scale_factor(&mut self, scale_factor: f64) {
+        let scale = (scale_factor / REFERENCE_SCALE) as f32;
+        if !scale.is_finite() || scale <= 0.0 || scale == self.scale {
+            return;
+        }
+        self.scale = scale;
+        self.atlas.set_size(self.font_size * scale);
+        self.atlas.set_cell_spacing(self.cell_spacing.0, self.cell_spacing.1 * scale);
+        self.tab_atlas.set_size(TAB_FONT_SIZE * scale);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::renderer::HollowCursor;
+    use growterm_types::{Rgb, UnderlineStyle};
+    use std::sync::{Arc, Mutex};
+
+    fn command(col: u16, character: char, flags: CellFlags) -> RenderCommand {
+        RenderCommand {
+            col,
+            row: 0,
+            character,
+            fg: Rgb::new(255, 255, 255),
+            bg: Rgb::new(0, 0, 128),
+            flags,
+            zerowidth: None,
+            underline_style: UnderlineStyle::Single,
+            underline_color: Rgb::new(255, 255, 255),
+        }
+    }
+
+    fn pixel(r: &SoftwareRenderer, x: u32, y: u32) -> [u8; 4] {
+        let i = ((y * r.surface_size().0 + x) * 4) as usize;
+        r.pixels()[i..i + 4].try_into().unwrap()
+    }
+
+    #[test]
+    fn fills_cell_background() {
+        let mut r = SoftwareRenderer::new(200, 100, 24.0, None);
+        r.draw(&[command(0, ' ', CellFlags::empty())], &Overlays::default());
+        let (cw, ch) = r.cell_size();
+        assert_eq!(pixel(&r, 1, 1), [0, 0, 128, 255]);
+        assert_eq!(pixel(&r, (cw * 2.0) as u32, (ch / 2.0) as u32), [0, 0, 0, 255]);
+    }
+
+    #[test]
+    fn glyph_and_block_cover_pixels() {
+        let mut r = SoftwareRenderer::new(200, 100, 24.0, None);
+        r.draw(&[command(0, 'M', CellFlags::empty()), command(1, '█', CellFlags::empty())], &Overlays::default());
+        let (cw, ch) = r.cell_size();
+        let lit = |x0: u32, x1: u32| {
+            (x0..x1).flat_map(|x| (0..ch as u32).map(move |y| (x, y))).filter(|&(x, y)| pixel(&r, x, y)[0] > 128).count()
+        };
+        assert!(lit(0, cw as u32) > 0);
+        assert_eq!(pixel(&r, (cw * 1.5) as u32, (ch / 2.0) as u32), [255, 255, 255, 255]);
+    }
+
+    #[test]
+    fn clip_top_hides_partial_row() {
+        let mut r = SoftwareRenderer::new(200, 100, 24.0, None);
+        let overlays = Overlays { content_clip_top: 10.0, ..Overlays::default() };
+        r.draw(&[command(0, ' ', CellFlags::empty())], &overlays);
+        assert_eq!(pixel(&r, 1, 5), [0, 0, 0, 255]);
+        assert_eq!(pixel(&r, 1, 12), [0, 0, 128, 255]);
+    }
+
+    #[test]
+    fn content_x_offset_shifts_cells() {
+        let mut r = SoftwareRenderer::new(200, 100, 24.0, None);
+        let overlays = Overlays { content_x_offset: 8.0, ..Overlays::default() };
+        r.draw(&[command(0, ' ', CellFlags::empty())], &overlays);
+        assert_eq!(pixel(&r, 4, 5), [0, 0, 0, 255]);
+        assert_eq!(pixel(&r, 9, 5), [0, 0, 128, 255]);
+    }
+
+    #[test]
+    fn scroll_badge_follows_scrollbar() {
+        let lit = |r: &SoftwareRenderer| {
+            (0..394).flat_map(|x| (0..100).map(move |y| (x, y))).filter(|&(x, y)| pixel(r, x, y)[0] > 100).count()
+        };
+        let mut r = SoftwareRenderer::new(400, 200, 24.0, None);
+        let overlays = Overlays { scrollbar: Some((0.0, 0.5)), scroll_badge: Some("10/20"), ..Overlays::default() };
+        r.draw(&[], &overlays);
+        assert!(lit(&r) > 0);
+
+        // 스크롤바가 사라지면 배지도 같이 사라짐
+        let overlays = Overlays { scroll_badge: Some("10/20"), ..Overlays::default() };
+        r.draw(&[], &overlays);
+        assert_eq!(lit(&r), 0);
+    }
+
+    #[test]
+    fn report_lines_stack_below_hud() {
+        let lit_rows = |r: &SoftwareRenderer| {
+            (0..200).filter(|&y| (0..400).any(|x| pixel(r, x, y)[1] > 100)).count()
+        };
+        let mut r = SoftwareRenderer::new(400, 200, 24.0, None);
+        r.draw(&[], &Overlays { hud: Some("hud"), ..Overlays::default() });
+        let hud_only = lit_rows(&r);
+        assert!(hud_only > 0);
+
+        let report = vec!["line one".to_string(), "line two".to_string()];
+        r.draw(&[], &Overlays { hud: Some("hud"), report: Some(&report), ..Overlays::default() });
+        assert!(lit_rows(&r) > hud_only * 2);
+    }
+
+    #[test]
+    fn hollow_cursor_leaves_cell_inside_unpainted() {
+        let mut r = SoftwareRenderer::new(200, 100, 24.0, None);
+        let cursor = HollowCursor { row: 0, col: 1, width: 1, color: Rgb::new(255, 0, 0) };
+        r.draw(&[], &Overlays { hollow_cursor: Some(cursor), ..Overlays::default() });
+        let (cw, ch) = r.cell_size();
+        assert_eq!(pixel(&r, cw.round() as u32, (ch / 2.0) as u32), [255, 0, 0, 255]);
+        assert_eq!(pixel(&r, (cw * 1.5) as u32, (ch / 2.0) as u32), [0, 0, 0, 255]);
+    }
+
+    #[test]
+    fn capture_skips_presenter() {
+        let presented = Arc::new(Mutex::new(0));
+        let count = presented.clone();
+        let mut r = SoftwareRenderer::new(64, 32, 24.0, None)
+            .with_presenter(Box::new(move |_, _, _| *count.lock().unwrap() += 1));
+        let pixels = r.capture(&[command(0, ' ', CellFlags::empty())], &Overlays::default()).unwrap();
+        assert_eq!(pixels.len(), 64 * 32 * 4);
+        assert_eq!(pixels[..4], [0, 0, 128, 255]);
+        assert_eq!(*presented.lock().unwrap(), 0);
+    }
+
+    #[test]
+    fn presenter_receives_frame() {
+        let seen = Arc::new(Mutex::new(None));
+        let sink = seen.clone();
+        let mut r = SoftwareRenderer::new(64, 32, 24.0, None)
+            .with_presenter(Box::new(move |pixels, w, h| *sink.lock().unwrap() = Some((pixels.len(), w, h))));
+        assert!(!r.draw(&[], &Overlays::default()));
+        assert_eq!(*seen.lock().unwrap(), Some((64 * 32 * 4, 64, 32)));
+    }
+}
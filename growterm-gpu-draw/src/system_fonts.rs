@@ -0,0 +1,113 @@
+//! Locating system font files that may contain a glyph missing from the
+//! bundled fonts. Candidates are returned in preference order; the caller
+//! loads them with fontdue and checks the glyph itself.
+
+use std::path::PathBuf;
+
+#[cfg(target_os = "macos")]
+pub fn candidates(c: char, size: f32) -> Vec<PathBuf> {
+    use core_foundation::array::CFArray;
+    use core_foundation::base::TCFType;
+    use core_foundation::string::CFString;
+    use core_text::font as ct_font;
+    use core_text::font::CTFontRef;
+
+    extern "C" {
+        fn CTFontGetGlyphsForCharacters(
+            font: CTFontRef,
+            characters: *const u16,
+            glyphs: *mut u16,
+            count: isize,
+        ) -> bool;
+    }
+
+    let base = ct_font::new_from_name("Helvetica", size as f64).expect("failed to create CT font");
+    let langs: CFArray<CFString> = CFArray::from_CFTypes(&[]);
+    let cascade = ct_font::cascade_list_for_languages(&base, &langs);
+
+    let mut utf16_buf = [0u16; 2];
+    let utf16 = c.encode_utf16(&mut utf16_buf);
+    let mut glyph_buf = [0u16; 2];
+
+    let mut paths = Vec::new();
+    for i in 0..cascade.len() {
+        let descriptor = cascade.get(i).unwrap();
+        let candidate = ct_font::new_from_descriptor(&descriptor, size as f64);
+        let found = unsafe {
+            CTFontGetGlyphsForCharacters(
+                candidate.as_concrete_TypeRef(),
+                utf16.as_ptr(),
+                glyph_buf.as_mut_ptr(),
+                utf16.len() as isize,
+            )
+        };
+        if found && glyph_buf[0] != 0 {
+            if let Some(path) = candidate.url().and_then(|url| url.to_path()) {
+                paths.push(path);
+            }
+        }
+    }
+    paths
+}
+
+/// Without a font cascade API, every font file under the usual XDG font
+/// directories is a candidate.
+#[cfg(not(target_os = "macos"))]
+pub fn candidates(_c: char, _size: f32) -> Vec<PathBuf> {
+    use std::sync::OnceLock;
+
+    static FONT_FILES: OnceLock<Vec<PathBuf>> = OnceLock::new();
+    FONT_FILES
+        .get_or_init(|| {
+            let home = std::env::var("HOME").unwrap_or_default();
+            let data_home = std::env::var("XDG_DATA_HOME").unwrap_or_else(|_| format!("{home}/.local/share"));
+            let dirs = [
+                format!("{data_home}/fonts"),
+                format!("{home}/.fonts"),
+                "/usr/local/share/fonts".to_string(),
+                "/usr/share/fonts".to_string(),
+            ];
+            let mut files = Vec::new();
+            for dir in &dirs {
+                collect_font_files(std::path::Path::new(dir), &mut files);
+            }
+            files
+        })
+        .clone()
+}
+
+#[cfg(not(target_os = "macos"))]
+fn collect_font_files(dir: &std::path::Path, out: &mut Vec<PathBuf>) {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return;
+    };
+    let mut paths: Vec<PathBuf> = entries.flatten().map(|e| e.path()).collect();
+    paths.sort();
+    for path in paths {
+        if path.is_dir() {
+            collect_font_files(&path, out);
+        } else if is_font_file(&path) {
+            out.push(path);
+        }
+    }
+}
+
+#[cfg(not(target_os = "macos"))]
+fn is_font_file(path: &std::path::Path) -> bool {
+    path.extension()
+        .and_then(|ext| ext.to_str())
+        .is_some_and(|ext| matches!(ext.to_ascii_lowercase().as_str(), "ttf" | "otf" | "ttc"))
+}
+
+#[cfg(all(test, not(target_os = "macos")))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn font_file_extensions() {
+        assert!(is_font_file(std::path::Path::new("/a/DejaVuSans.ttf")));
+        assert!(is_font_file(std::path::Path::new("/a/NotoSansCJK.TTC")));
+        assert!(!is_font_file(std::path::Path::new("/a/fonts.dir")));
+        assert!(!is_font_file(std::path::Path::new("/a/README")));
+    }
+}
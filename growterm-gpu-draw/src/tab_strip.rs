@@ -0,0 +1,149 @@
+//! 탭바 가로 배치. 렌더러와 앱의 클릭 판정이 같은 계산을 쓰도록 분리.
+
+/// 탭 하나의 최소/최대 너비 (물리 픽셀)
+pub const MIN_TAB_WIDTH: f32 = 160.0;
+pub const MAX_TAB_WIDTH: f32 = 480.0;
+/// 탭이 넘칠 때 양 끝에 표시되는 스크롤 화살표 너비
+pub const ARROW_WIDTH: f32 = 48.0;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum TabStripHit {
+    Tab(usize),
+    ScrollLeft,
+    ScrollRight,
+}
+
+/// 탭 개수, 화면 너비, 스크롤 위치로 계산한 탭바 배치.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TabStrip {
+    pub tab_count: usize,
+    pub tab_width: f32,
+    pub screen_width: f32,
+    /// 탭 전체가 화면을 넘쳐 화살표가 표시되는지
+    pub overflow: bool,
+    /// 클램프된 스크롤 위치 (픽셀)
+    pub scroll: f32,
+}
+
+impl TabStrip {
+    pub fn new(tab_count: usize, screen_width: f32, scroll: f32) -> Self {
+        let tab_width = if tab_count == 0 {
+            0.0
+        } else {
+            (screen_width / tab_count as f32).clamp(MIN_TAB_WIDTH, MAX_TAB_WIDTH)
+        };
+        let overflow = tab_width * tab_count as f32 > screen_width;
+        let mut strip = Self { tab_count, tab_width, screen_width, overflow, scroll: 0.0 };
+        strip.scroll = scroll.clamp(0.0, strip.max_scroll());
+        strip
+    }
+
+    /// 탭이 그려지는 영역 (시작 x, 너비). 넘치면 화살표 사이.
+    pub fn viewport(&self) -> (f32, f32) {
+        if self.overflow {
+            (ARROW_WIDTH, (self.screen_width - 2.0 * ARROW_WIDTH).max(0.0))
+        } else {
+            (0.0, self.screen_width)
+        }
+    }
+
+    pub fn max_scroll(&self) -> f32 {
+        let (_, width) = self.viewport();
+        (self.tab_width * self.tab_count as f32 - width).max(0.0)
+    }
+
+    /// 탭 왼쪽 끝의 화면 x 좌표 (뷰포트 밖일 수 있음)
+    pub fn tab_x(&self, index: usize) -> f32 {
+        let (start, _) = self.viewport();
+        start + index as f32 * self.tab_width - self.scroll
+    }
+
+    pub fn hit(&self, x: f32) -> Option<TabStripHit> {
+        if self.tab_count == 0 || x < 0.0 || x >= self.screen_width {
+            return None;
+        }
+        let (start, width) = self.viewport();
+        if self.overflow && x < start {
+            return Some(TabStripHit::ScrollLeft);
+        }
+        if self.overflow && x >= start + width {
+            return Some(TabStripHit::ScrollRight);
+        }
+        let index = ((x - start + self.scroll) / self.tab_width) as usize;
+        (index < self.tab_count).then_some(TabStripHit::Tab(index))
+    }
+
+    /// `index` 탭이 뷰포트 안에 완전히 보이도록 하는 스크롤 위치
+    pub fn scroll_to_reveal(&self, index: usize) -> f32 {
+        let (_, width) = self.viewport();
+        let left = index as f32 * self.tab_width;
+        let right = left + self.tab_width;
+        let scroll = if left < self.scroll {
+            left
+        } else if right > self.scroll + width {
+            right - width
+        } else {
+            self.scroll
+        };
+        scroll.clamp(0.0, self.max_scroll())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn few_tabs_divide_screen_without_overflow() {
+        let strip = TabStrip::new(3, 900.0, 50.0);
+        assert_eq!(strip.tab_width, 300.0);
+        assert!(!strip.overflow);
+        assert_eq!(strip.scroll, 0.0);
+        assert_eq!(strip.hit(299.0), Some(TabStripHit::Tab(0)));
+        assert_eq!(strip.hit(300.0), Some(TabStripHit::Tab(1)));
+    }
+
+    #[test]
+    fn wide_screen_caps_tab_width() {
+        let strip = TabStrip::new(2, 2000.0, 0.0);
+        assert_eq!(strip.tab_width, MAX_TAB_WIDTH);
+        assert_eq!(strip.hit(MAX_TAB_WIDTH * 2.0 + 1.0), None);
+    }
+
+    #[test]
+    fn many_tabs_overflow_with_arrows() {
+        let strip = TabStrip::new(10, 800.0, 0.0);
+        assert_eq!(strip.tab_width, MIN_TAB_WIDTH);
+        assert!(strip.overflow);
+        assert_eq!(strip.hit(10.0), Some(TabStripHit::ScrollLeft));
+        assert_eq!(strip.hit(795.0), Some(TabStripHit::ScrollRight));
+        assert_eq!(strip.hit(ARROW_WIDTH + 1.0), Some(TabStripHit::Tab(0)));
+        assert_eq!(strip.max_scroll(), 10.0 * MIN_TAB_WIDTH - (800.0 - 2.0 * ARROW_WIDTH));
+    }
+
+    #[test]
+    fn hit_accounts_for_scroll_offset() {
+        let strip = TabStrip::new(10, 800.0, MIN_TAB_WIDTH * 2.0);
+        assert_eq!(strip.hit(ARROW_WIDTH + 1.0), Some(TabStripHit::Tab(2)));
+        assert_eq!(strip.tab_x(2), ARROW_WIDTH);
+    }
+
+    #[test]
+    fn scroll_clamps_to_content() {
+        let strip = TabStrip::new(10, 800.0, 1.0e6);
+        assert_eq!(strip.scroll, strip.max_scroll());
+        let strip = TabStrip::new(10, 800.0, -5.0);
+        assert_eq!(strip.scroll, 0.0);
+    }
+
+    #[test]
+    fn reveal_scrolls_minimally() {
+        let strip = TabStrip::new(10, 800.0, 0.0);
+        let (_, width) = strip.viewport();
+        assert_eq!(strip.scroll_to_reveal(1), 0.0);
+        assert_eq!(strip.scroll_to_reveal(9), strip.max_scroll());
+        assert_eq!(strip.scroll_to_reveal(5), 6.0 * MIN_TAB_WIDTH - width);
+        let scrolled = TabStrip::new(10, 800.0, strip.max_scroll());
+        assert_eq!(scrolled.scroll_to_reveal(0), 0.0);
+    }
+}
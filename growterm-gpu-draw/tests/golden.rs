@@ -0,0 +1,221 @@
+use std::path::PathBuf;
+
+use growterm_gpu_draw::{GpuDrawer, TabBarInfo};
+use growterm_render_cmd::TerminalPalette;
+use growterm_types::{Cell, CellFlags, RenderCommand, Rgb, UnderlineStyle};
+use unicode_width::UnicodeWidthChar;
+
+const WIDTH: u32 = 320;
+const HEIGHT: u32 = 160;
+const FONT_SIZE: f32 = 24.0;
+/// Per-channel difference allowed before a pixel counts as changed.
+const CHANNEL_TOLERANCE: u8 = 8;
+/// Fraction of pixels allowed to differ (glyph antialiasing across GPUs).
+const MAX_DIFF_RATIO: f64 = 0.002;
+
+const FG: Rgb = Rgb { r: 204, g: 204, b: 204 };
+const BG: Rgb = Rgb { r: 0, g: 0, b: 0 };
+
+fn text_commands(row: u16, text: &str, flags: CellFlags) -> Vec<RenderCommand> {
+    let mut commands = Vec::new();
+    let mut col = 0u16;
+    for c in text.chars() {
+        let wide = c.width() == Some(2);
+        let flags = if wide { flags | CellFlags::WIDE_CHAR } else { flags };
+        commands.push(RenderCommand { col, row, character: c, fg: FG, bg: BG, flags, zerowidth: None, underline_style: UnderlineStyle::Single, underline_color: FG });
+        col += if wide { 2 } else { 1 };
+    }
+    commands
+}
+
+/// Headless drawer, or None (with a loud SKIPPED line) when there's no adapter.
+/// `GROWTERM_REQUIRE_GPU=1` turns the skip into a failure for GPU CI runners.
+fn headless(name: &str) -> Option<GpuDrawer> {
+    let drawer = GpuDrawer::new_headless(WIDTH, HEIGHT, FONT_SIZE, None);
+    if drawer.is_none() {
+        if std::env::var_os("GROWTERM_REQUIRE_GPU").is_some() {
+            panic!("{name}: no GPU adapter available and GROWTERM_REQUIRE_GPU is set");
+        }
+        eprintln!("SKIPPED {name}: no GPU adapter available, golden image not compared");
+    }
+    drawer
+}
+
+/// Render until no glyphs are deferred by the per-frame budget, then read back.
+fn render(name: &str, commands: &[RenderCommand], tab_bar: Option<&TabBarInfo>, y_offset: f32) -> Option<Vec<u8>> {
+    let mut drawer = headless(name)?;
+    for _ in 0..32 {
+        if !drawer.draw(commands, None, tab_bar, false, None, false, 0.0, y_offset, 0.0, 0.0, 1.0, None, None, None, None) {
+            break;
+        }
+    }
+    drawer.read_pixels()
+}
+
+fn golden_path(name: &str) -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests/golden").join(format!("{name}.png"))
+}
+
+fn write_png(path: &PathBuf, pixels: &[u8]) {
+    let file = std::fs::File::create(path).expect("create png");
+    let mut encoder = png::Encoder::new(std::io::BufWriter::new(file), WIDTH, HEIGHT);
+    encoder.set_color(png::ColorType::Rgba);
+    encoder.set_depth(png::BitDepth::Eight);
+    encoder.write_header().unwrap().write_image_data(pixels).unwrap();
+}
+
+fn read_png(path: &PathBuf) -> (u32, u32, Vec<u8>) {
+    let decoder = png::Decoder::new(std::fs::File::open(path).expect("open golden"));
+    let mut reader = decoder.read_info().unwrap();
+    let mut buf = vec![0; reader.output_buffer_size()];
+    let info = reader.next_frame(&mut buf).unwrap();
+    buf.truncate(info.buffer_size());
+    (info.width, info.height, buf)
+}
+
+fn assert_golden(name: &str, pixels: Option<Vec<u8>>) {
+    let Some(pixels) = pixels else { return };
+    let path = golden_path(name);
+    if std::env::var_os("GROWTERM_UPDATE_GOLDEN").is_some() {
+        write_png(&path, &pixels);
+        eprintln!("wrote golden {}", path.display());
+        return;
+    }
+    if !path.exists() {
+        let actual = path.with_extension("actual.png");
+        write_png(&actual, &pixels);
+        panic!(
+            "{name}: no golden at {}; check {} and commit it, or run with GROWTERM_UPDATE_GOLDEN=1",
+            path.display(),
+            actual.display()
+        );
+    }
+
+    let (w, h, golden) = read_png(&path);
+    assert_eq!((w, h), (WIDTH, HEIGHT), "golden {name} has a different size");
+
+    let diff = pixels
+        .chunks_exact(4)
+        .zip(golden.chunks_exact(4))
+        .filter(|(a, b)| a.iter().zip(b.iter()).any(|(x, y)| x.abs_diff(*y) > CHANNEL_TOLERANCE))
+        .count();
+    let ratio = diff as f64 / (WIDTH * HEIGHT) as f64;
+    if ratio > MAX_DIFF_RATIO {
+        let actual = path.with_extension("actual.png");
+        write_png(&actual, &pixels);
+        panic!(
+            "{name}: {diff} pixels differ ({:.3}%), actual frame saved to {}",
+            ratio * 100.0,
+            actual.display()
+        );
+    }
+}
+
+#[test]
+fn golden_box_drawing() {
+    let mut commands = text_commands(0, "┌──┬──┐", CellFlags::empty());
+    commands.extend(text_commands(1, "│▀▄│█▌│", CellFlags::empty()));
+    commands.extend(text_commands(2, "╞══╪══╡", CellFlags::empty()));
+    commands.extend(text_commands(3, "╰──┴──╯", CellFlags::empty()));
+    assert_golden("box_drawing", render("box_drawing", &commands, None, 0.0));
+}
+
+#[test]
+fn golden_wide_chars() {
+    let mut commands = text_commands(0, "한글 mixed", CellFlags::empty());
+    commands.extend(text_commands(1, "ab가나다cd", CellFlags::empty()));
+    assert_golden("wide_chars", render("wide_chars", &commands, None, 0.0));
+}
+
+#[test]
+fn golden_underline_and_attributes() {
+    let mut commands = text_commands(0, "underline", CellFlags::UNDERLINE);
+    commands.extend(text_commands(1, "bold", CellFlags::BOLD));
+    commands.extend(text_commands(2, "hidden", CellFlags::HIDDEN));
+    let mut inverse = text_commands(3, "inverse", CellFlags::empty());
+    for cmd in &mut inverse {
+        std::mem::swap(&mut cmd.fg, &mut cmd.bg);
+    }
+    commands.extend(inverse);
+    assert_golden("underline_and_attributes", render("underline_and_attributes", &commands, None, 0.0));
+}
+
+#[test]
+fn golden_underline_styles() {
+    let styles = [
+        UnderlineStyle::Single,
+        UnderlineStyle::Double,
+        UnderlineStyle::Curly,
+        UnderlineStyle::Dotted,
+        UnderlineStyle::Dashed,
+    ];
+    let mut commands = Vec::new();
+    for (row, style) in styles.into_iter().enumerate() {
+        let mut line = text_commands(row as u16, "underline", CellFlags::UNDERLINE);
+        for cmd in &mut line {
+            cmd.underline_style = style;
+            cmd.underline_color = Rgb::new(255, 80, 80);
+        }
+        commands.extend(line);
+    }
+    assert_golden("underline_styles", render("underline_styles", &commands, None, 0.0));
+}
+
+#[test]
+fn golden_strikethrough() {
+    let mut commands = text_commands(0, "struck", CellFlags::STRIKETHROUGH);
+    commands.extend(text_commands(1, "both", CellFlags::STRIKETHROUGH | CellFlags::UNDERLINE));
+    commands.extend(text_commands(2, "한글", CellFlags::STRIKETHROUGH));
+    assert_golden("strikethrough", render("strikethrough", &commands, None, 0.0));
+}
+
+/// The cursor comes from render-cmd (fg/bg swap), so go through `generate`.
+#[test]
+fn golden_cursor() {
+    let grid: Vec<Vec<Cell>> = ["$ echo", "가나"]
+        .iter()
+        .map(|line| {
+            let mut row = Vec::new();
+            for c in line.chars() {
+                let wide = c.width() == Some(2);
+                let flags = if wide { CellFlags::WIDE_CHAR } else { CellFlags::empty() };
+                row.push(Cell { character: c, flags, ..Cell::default() });
+                if wide {
+                    row.push(Cell { character: ' ', ..Cell::default() });
+                }
+            }
+            row
+        })
+        .collect();
+    // 넓은 글자 위의 커서는 두 칸을 덮는다
+    let commands = growterm_render_cmd::generate(&grid, Some((1, 2)), None, None, TerminalPalette::default());
+    assert_golden("cursor", render("cursor", &commands, None, 0.0));
+}
+
+#[test]
+fn golden_tab_bar() {
+    let tab_bar = TabBarInfo {
+        titles: vec!["⌘1".to_string(), "⌘2".to_string(), "⌘3".to_string()],
+        active_index: 1,
+        dragging_index: None,
+        scroll_offset: 0.0,
+    };
+    let Some(mut drawer) = headless("tab_bar") else { return };
+    let y_offset = drawer.tab_bar_height();
+    let commands = text_commands(0, "$ ls", CellFlags::empty());
+    for _ in 0..32 {
+        if !drawer.draw(&commands, None, Some(&tab_bar), false, None, false, 0.0, y_offset, 0.0, 0.0, 1.0, None, None, None, None) {
+            break;
+        }
+    }
+    assert_golden("tab_bar", drawer.read_pixels());
+}
+
+#[test]
+fn capture_matches_drawn_frame() {
+    let commands = text_commands(0, "$ 한글", CellFlags::empty());
+    let Some(drawn) = render("capture_matches_drawn_frame", &commands, None, 0.0) else { return };
+    let mut drawer = GpuDrawer::new_headless(WIDTH, HEIGHT, FONT_SIZE, None).unwrap();
+    let captured = drawer.capture(|d| d.draw(&commands, None, None, false, None, false, 0.0, 0.0, 0.0, 0.0, 1.0, None, None, None, None));
+    assert_eq!(captured, Some(drawn));
+}
@@ -1,5 +1,8 @@
-use crate::{Grid, MAX_SCROLLBACK};
-use growterm_types::{Cell, CellFlags, Color, Rgb, TerminalCommand};
+// 기존 테스트는 cells()/scrollback()로 작성됨. 새 API는 맨 아래 Row 테스트 참고
+#![allow(deprecated)]
+
+use crate::{Grid, DEFAULT_MAX_SCROLLBACK};
+use growterm_types::{Cell, CellFlags, Color, Rgb, TerminalCommand, UnderlineStyle};
 
 // === Step 1: Grid::new + cells() ===
 
@@ -17,7 +20,7 @@ fn new_grid_has_correct_dimensions() {
 fn new_grid_all_cells_are_default() {
     let grid = Grid::new(10, 5);
     for row in grid.cells() {
-        for cell in row {
+        for cell in row.iter() {
             assert_eq!(*cell, Cell::default());
         }
     }
@@ -123,6 +126,171 @@ fn narrow_overwrite_on_spacer_clears_wide_char() {
     assert_eq!(grid.cells()[0][1].character, 'Y');
 }
 
+#[test]
+fn wide_overwrite_on_wide_first_half_clears_its_spacer() {
+    let mut grid = Grid::new(10, 2);
+    grid.apply(&TerminalCommand::Print('a'));
+    grid.apply(&TerminalCommand::Print('한')); // cols 1-2
+    grid.apply(&TerminalCommand::CursorPosition { row: 1, col: 1 });
+    grid.apply(&TerminalCommand::Print('글')); // cols 0-1, spacer lands on 한
+    assert_eq!(grid.cells()[0][0].character, '글');
+    assert_eq!(grid.cells()[0][1], Cell::default());
+    assert_eq!(grid.cells()[0][2].character, ' ');
+    assert!(!grid.cells()[0][2].flags.contains(CellFlags::WIDE_CHAR));
+}
+
+// === Combining / zero-width characters ===
+
+fn cell_text(cell: &Cell) -> String {
+    let mut text = cell.character.to_string();
+    if let Some(z) = cell.zerowidth {
+        text.push_str(z.as_str());
+    }
+    text
+}
+
+#[test]
+fn combining_mark_attaches_to_previous_cell() {
+    let mut grid = Grid::new(10, 2);
+    for c in "e\u{0301}x".chars() {
+        grid.apply(&TerminalCommand::Print(c));
+    }
+    assert_eq!(cell_text(&grid.cells()[0][0]), "e\u{0301}");
+    assert_eq!(grid.cells()[0][1].character, 'x');
+    assert_eq!(grid.cursor_pos(), (0, 2));
+}
+
+#[test]
+fn vietnamese_and_thai_stack_marks() {
+    let mut grid = Grid::new(10, 2);
+    for c in "a\u{0323}\u{0302}".chars().chain("ก\u{0E34}\u{0E49}".chars()) {
+        grid.apply(&TerminalCommand::Print(c));
+    }
+    assert_eq!(cell_text(&grid.cells()[0][0]), "a\u{0323}\u{0302}");
+    assert_eq!(cell_text(&grid.cells()[0][1]), "ก\u{0E34}\u{0E49}");
+    assert_eq!(grid.cursor_pos(), (0, 2));
+}
+
+#[test]
+fn combining_mark_after_wide_char_attaches_to_wide_cell() {
+    let mut grid = Grid::new(10, 2);
+    grid.apply(&TerminalCommand::Print('한'));
+    grid.apply(&TerminalCommand::Print('\u{0301}'));
+    assert_eq!(cell_text(&grid.cells()[0][0]), "한\u{0301}");
+    assert_eq!(grid.cells()[0][1], Cell::default());
+}
+
+#[test]
+fn combining_mark_at_pending_wrap_attaches_to_last_column() {
+    let mut grid = Grid::new(3, 2);
+    for c in "abc\u{0301}".chars() {
+        grid.apply(&TerminalCommand::Print(c));
+    }
+    assert_eq!(cell_text(&grid.cells()[0][2]), "c\u{0301}");
+    assert_eq!(grid.cursor_pos(), (0, 3));
+}
+
+#[test]
+fn combining_mark_at_line_start_is_dropped() {
+    let mut grid = Grid::new(5, 2);
+    grid.apply(&TerminalCommand::Print('\u{0301}'));
+    assert_eq!(grid.cells()[0][0], Cell::default());
+    assert_eq!(grid.cursor_pos(), (0, 0));
+}
+
+#[test]
+fn emoji_zwj_sequence_occupies_one_wide_cell() {
+    let mut grid = Grid::new(10, 2);
+    grid.apply(&TerminalCommand::SetMode(2027, true));
+    for c in "👨\u{200D}👩\u{200D}👧x".chars() {
+        grid.apply(&TerminalCommand::Print(c));
+    }
+    assert_eq!(cell_text(&grid.cells()[0][0]), "👨\u{200D}👩\u{200D}👧");
+    assert!(grid.cells()[0][0].flags.contains(CellFlags::WIDE_CHAR));
+    assert_eq!(grid.cells()[0][2].character, 'x');
+}
+
+#[test]
+fn emoji_zwj_sequence_without_mode_2027_uses_per_char_width() {
+    let mut grid = Grid::new(10, 2);
+    for c in "👨\u{200D}👩".chars() {
+        grid.apply(&TerminalCommand::Print(c));
+    }
+    assert_eq!(cell_text(&grid.cells()[0][0]), "👨\u{200D}");
+    assert_eq!(grid.cells()[0][2].character, '👩');
+    assert_eq!(grid.cursor_pos(), (0, 4));
+}
+
+#[test]
+fn flag_pair_is_one_wide_cell_in_mode_2027() {
+    let mut grid = Grid::new(10, 2);
+    grid.apply(&TerminalCommand::SetMode(2027, true));
+    for c in "🇰🇷🇯🇵".chars() {
+        grid.apply(&TerminalCommand::Print(c));
+    }
+    assert_eq!(cell_text(&grid.cells()[0][0]), "🇰🇷");
+    assert!(grid.cells()[0][0].flags.contains(CellFlags::WIDE_CHAR));
+    assert_eq!(cell_text(&grid.cells()[0][2]), "🇯🇵");
+    assert_eq!(grid.cursor_pos(), (0, 4));
+}
+
+#[test]
+fn flag_pair_is_two_narrow_cells_without_mode_2027() {
+    let mut grid = Grid::new(10, 2);
+    for c in "🇰🇷".chars() {
+        grid.apply(&TerminalCommand::Print(c));
+    }
+    assert_eq!(grid.cells()[0][0].character, '🇰');
+    assert_eq!(grid.cells()[0][1].character, '🇷');
+    assert_eq!(grid.cursor_pos(), (0, 2));
+}
+
+#[test]
+fn vs16_widens_emoji_in_mode_2027() {
+    let mut grid = Grid::new(10, 2);
+    grid.apply(&TerminalCommand::SetMode(2027, true));
+    for c in "❤\u{FE0F}x".chars() {
+        grid.apply(&TerminalCommand::Print(c));
+    }
+    assert!(grid.cells()[0][0].flags.contains(CellFlags::WIDE_CHAR));
+    assert_eq!(grid.cells()[0][2].character, 'x');
+}
+
+#[test]
+fn vs16_at_right_edge_stays_narrow() {
+    let mut grid = Grid::new(3, 2);
+    grid.apply(&TerminalCommand::SetMode(2027, true));
+    for c in "ab❤\u{FE0F}".chars() {
+        grid.apply(&TerminalCommand::Print(c));
+    }
+    assert!(!grid.cells()[0][2].flags.contains(CellFlags::WIDE_CHAR));
+    assert_eq!(grid.cursor_pos(), (0, 3));
+}
+
+#[test]
+fn private_mode_reports_grid_modes() {
+    let mut grid = Grid::new(10, 2);
+    assert_eq!(grid.private_mode(2027), Some(false));
+    grid.apply(&TerminalCommand::SetMode(2027, true));
+    assert_eq!(grid.private_mode(2027), Some(true));
+    grid.apply(&TerminalCommand::HideCursor);
+    assert_eq!(grid.private_mode(25), Some(false));
+    assert_eq!(grid.private_mode(2004), None);
+    grid.apply(&TerminalCommand::FullReset);
+    assert_eq!(grid.private_mode(2027), Some(false));
+}
+
+#[test]
+fn overwriting_cell_drops_its_marks() {
+    let mut grid = Grid::new(5, 2);
+    for c in "e\u{0301}".chars() {
+        grid.apply(&TerminalCommand::Print(c));
+    }
+    grid.apply(&TerminalCommand::CarriageReturn);
+    grid.apply(&TerminalCommand::Print('f'));
+    assert_eq!(grid.cells()[0][0].zerowidth, None);
+}
+
 // === Step 4: Attribute state ===
 
 #[test]
@@ -162,6 +330,23 @@ fn multiple_flags_combine() {
     assert!(flags.contains(CellFlags::UNDERLINE));
 }
 
+#[test]
+fn underline_style_and_color_stored_per_cell() {
+    let mut grid = Grid::new(80, 24);
+    grid.apply(&TerminalCommand::SetUnderlineStyle(UnderlineStyle::Curly));
+    grid.apply(&TerminalCommand::SetUnderlineColor(Color::Indexed(1)));
+    grid.apply(&TerminalCommand::Print('a'));
+    grid.apply(&TerminalCommand::ResetUnderline);
+    grid.apply(&TerminalCommand::Print('b'));
+    let a = grid.cells()[0][0];
+    assert!(a.flags.contains(CellFlags::UNDERLINE));
+    assert_eq!(a.underline_style, UnderlineStyle::Curly);
+    assert_eq!(a.underline_color, Color::Indexed(1));
+    let b = grid.cells()[0][1];
+    assert!(!b.flags.contains(CellFlags::UNDERLINE));
+    assert_eq!(b.underline_style, UnderlineStyle::Single);
+}
+
 #[test]
 fn reset_attributes_clears_all() {
     let mut grid = Grid::new(80, 24);
@@ -630,6 +815,132 @@ fn resize_clamps_cursor() {
     assert_eq!(grid.cells()[4][4].character, 'C');
 }
 
+// === Reflow on resize ===
+
+fn print_str(grid: &mut Grid, text: &str) {
+    for c in text.chars() {
+        grid.apply(&TerminalCommand::Print(c));
+    }
+}
+
+fn row_text(row: &[Cell]) -> String {
+    row.iter()
+        .filter(|c| c.character != '\0')
+        .map(|c| c.character)
+        .collect::<String>()
+        .trim_end()
+        .to_string()
+}
+
+#[test]
+fn autowrap_marks_row_as_wrapped() {
+    let mut grid = Grid::new(5, 3);
+    print_str(&mut grid, "abcdefg");
+    assert!(grid.cells()[0][4].flags.contains(CellFlags::WRAPLINE));
+    assert!(!grid.cells()[1][4].flags.contains(CellFlags::WRAPLINE));
+}
+
+#[test]
+fn shrinking_rewraps_long_line() {
+    let mut grid = Grid::new(10, 4);
+    print_str(&mut grid, "abcdefgh");
+    grid.apply(&TerminalCommand::CarriageReturn);
+    grid.apply(&TerminalCommand::Newline);
+    print_str(&mut grid, "$ ");
+    grid.resize(4, 4);
+    let cells = grid.cells();
+    assert_eq!(row_text(&cells[0]), "abcd");
+    assert_eq!(row_text(&cells[1]), "efgh");
+    assert_eq!(row_text(&cells[2]), "$");
+    assert!(cells[0][3].flags.contains(CellFlags::WRAPLINE));
+    assert!(!cells[1][3].flags.contains(CellFlags::WRAPLINE));
+    assert_eq!(grid.cursor_pos(), (2, 2));
+}
+
+#[test]
+fn widening_unwraps_line() {
+    let mut grid = Grid::new(4, 4);
+    print_str(&mut grid, "abcdefghij");
+    assert_eq!(grid.cursor_pos(), (2, 2));
+    grid.resize(12, 4);
+    let cells = grid.cells();
+    assert_eq!(row_text(&cells[0]), "abcdefghij");
+    assert_eq!(row_text(&cells[1]), "");
+    assert!(!cells[0][11].flags.contains(CellFlags::WRAPLINE));
+    assert_eq!(grid.cursor_pos(), (0, 10));
+}
+
+#[test]
+fn explicit_newlines_are_not_joined() {
+    let mut grid = Grid::new(4, 3);
+    print_str(&mut grid, "ab");
+    grid.apply(&TerminalCommand::CarriageReturn);
+    grid.apply(&TerminalCommand::Newline);
+    print_str(&mut grid, "cd");
+    grid.resize(10, 3);
+    assert_eq!(row_text(&grid.cells()[0]), "ab");
+    assert_eq!(row_text(&grid.cells()[1]), "cd");
+}
+
+#[test]
+fn shrinking_pushes_overflow_into_scrollback() {
+    let mut grid = Grid::new(6, 2);
+    print_str(&mut grid, "abcdef");
+    grid.apply(&TerminalCommand::CarriageReturn);
+    grid.apply(&TerminalCommand::Newline);
+    print_str(&mut grid, "xyz");
+    grid.resize(3, 2);
+    assert_eq!(grid.scrollback_len(), 1);
+    assert_eq!(row_text(&grid.scrollback()[0]), "abc");
+    assert_eq!(row_text(&grid.cells()[0]), "def");
+    assert_eq!(row_text(&grid.cells()[1]), "xyz");
+    assert_eq!(grid.cursor_pos(), (1, 3));
+    // widening re-joins the line across the scrollback/screen boundary
+    grid.resize(6, 2);
+    assert_eq!(grid.scrollback_len(), 0);
+    assert_eq!(row_text(&grid.cells()[0]), "abcdef");
+    assert_eq!(row_text(&grid.cells()[1]), "xyz");
+}
+
+#[test]
+fn reflow_keeps_wide_chars_intact() {
+    let mut grid = Grid::new(5, 3);
+    print_str(&mut grid, "a한글");
+    grid.resize(2, 3);
+    let cells = grid.cells();
+    assert_eq!(row_text(&cells[0]), "a");
+    assert_eq!(cells[1][0].character, '한');
+    assert!(cells[1][0].flags.contains(CellFlags::WIDE_CHAR));
+    assert_eq!(cells[2][0].character, '글');
+    grid.resize(5, 3);
+    assert_eq!(grid.cells()[0][0].character, 'a');
+    assert_eq!(grid.cells()[0][1].character, '한');
+    assert_eq!(grid.cells()[0][3].character, '글');
+}
+
+#[test]
+fn reflow_drops_blank_left_by_wide_char_wrap() {
+    let mut grid = Grid::new(3, 3);
+    print_str(&mut grid, "ab한");
+    assert_eq!(grid.cells()[1][0].character, '한');
+    grid.resize(6, 3);
+    assert_eq!(grid.cells()[0][2].character, '한');
+}
+
+#[test]
+fn alt_screen_is_not_reflowed_but_main_screen_is() {
+    let mut grid = Grid::new(6, 3);
+    print_str(&mut grid, "abcdef");
+    grid.apply(&TerminalCommand::EnterAltScreen);
+    print_str(&mut grid, "xyz");
+    grid.resize(3, 3);
+    assert_eq!(row_text(&grid.cells()[0]), "xyz");
+    assert_eq!(row_text(&grid.cells()[1]), "");
+    grid.apply(&TerminalCommand::LeaveAltScreen);
+    assert_eq!(row_text(&grid.cells()[0]), "abc");
+    assert_eq!(row_text(&grid.cells()[1]), "def");
+}
+
 // === Step 9: cursor_pos ===
 
 #[test]
@@ -668,12 +979,26 @@ fn reset_inverse_clears_inverse_flag() {
 }
 
 #[test]
-fn reset_bold_clears_bold_and_dim() {
+fn reset_bold_keeps_dim() {
+    let mut grid = Grid::new(80, 24);
+    grid.apply(&TerminalCommand::SetBold);
+    grid.apply(&TerminalCommand::SetDim);
+    grid.apply(&TerminalCommand::ResetBold);
+    grid.apply(&TerminalCommand::Print('A'));
+
+    let a = grid.cells()[0][0].flags;
+    assert!(!a.contains(CellFlags::BOLD));
+    assert!(a.contains(CellFlags::DIM));
+}
+
+#[test]
+fn sgr22_reset_bold_and_dim_clears_both() {
     let mut grid = Grid::new(80, 24);
     grid.apply(&TerminalCommand::SetBold);
     grid.apply(&TerminalCommand::SetDim);
     grid.apply(&TerminalCommand::Print('A'));
     grid.apply(&TerminalCommand::ResetBold);
+    grid.apply(&TerminalCommand::ResetDim);
     grid.apply(&TerminalCommand::Print('B'));
 
     let a = grid.cells()[0][0].flags;
@@ -722,13 +1047,78 @@ fn scroll_up_saves_row_to_scrollback() {
 fn scrollback_max_size_trims_oldest() {
     let mut grid = Grid::new(3, 1);
     // Each newline at bottom scrolls up, pushing the current row to scrollback
-    for i in 0..(MAX_SCROLLBACK + 100) {
+    for i in 0..(DEFAULT_MAX_SCROLLBACK + 100) {
         let c = if i % 2 == 0 { 'A' } else { 'B' };
         grid.apply(&TerminalCommand::CarriageReturn);
         grid.apply(&TerminalCommand::Print(c));
         grid.apply(&TerminalCommand::Newline);
     }
-    assert!(grid.scrollback_len() <= MAX_SCROLLBACK);
+    assert!(grid.scrollback_len() <= DEFAULT_MAX_SCROLLBACK);
+}
+
+fn push_lines(grid: &mut Grid, n: usize) {
+    for _ in 0..n {
+        grid.apply(&TerminalCommand::CarriageReturn);
+        grid.apply(&TerminalCommand::Print('A'));
+        grid.apply(&TerminalCommand::Newline);
+    }
+}
+
+#[test]
+fn max_scrollback_is_configurable() {
+    let mut grid = Grid::new(3, 1);
+    grid.set_max_scrollback(Some(5));
+    push_lines(&mut grid, 20);
+    assert_eq!(grid.scrollback_len(), 5);
+    grid.set_max_scrollback(Some(2));
+    assert_eq!(grid.scrollback_len(), 2);
+}
+
+#[test]
+fn unlimited_scrollback_keeps_everything() {
+    let mut grid = Grid::new(3, 1);
+    grid.set_max_scrollback(None);
+    push_lines(&mut grid, DEFAULT_MAX_SCROLLBACK + 10);
+    assert_eq!(grid.scrollback_len(), DEFAULT_MAX_SCROLLBACK + 10);
+}
+
+#[test]
+fn erase_saved_lines_clears_scrollback_only() {
+    let mut grid = Grid::new(3, 2);
+    push_lines(&mut grid, 5);
+    grid.apply(&TerminalCommand::CarriageReturn);
+    grid.apply(&TerminalCommand::Print('B'));
+    grid.scroll_up_view(2);
+    grid.apply(&TerminalCommand::EraseInDisplay(3));
+    assert_eq!(grid.scrollback_len(), 0);
+    assert_eq!(grid.scroll_offset(), 0);
+    assert_eq!(grid.cells()[1][0].character, 'B');
+}
+
+#[test]
+fn clear_scrollback_and_screen_keeps_cursor_line() {
+    let mut grid = Grid::new(3, 3);
+    push_lines(&mut grid, 5);
+    grid.apply(&TerminalCommand::CarriageReturn);
+    grid.apply(&TerminalCommand::Print('$'));
+    grid.clear_scrollback_and_screen();
+    assert_eq!(grid.scrollback_len(), 0);
+    assert_eq!(grid.cursor_pos(), (0, 1));
+    assert_eq!(grid.cells()[0][0].character, '$');
+    assert!(grid.cells()[1..].iter().flat_map(|row| row.iter()).all(|c| c.character == ' '));
+}
+
+#[test]
+fn clear_scrollback_and_screen_resets_view_and_damages_every_row() {
+    let mut grid = Grid::new(3, 3);
+    push_lines(&mut grid, 5);
+    grid.scroll_up_view(2);
+    let generation = grid.generation();
+    grid.clear_scrollback_and_screen();
+    assert_eq!(grid.scroll_offset(), 0);
+    let mut rows: Vec<u16> = grid.diff_since(generation).iter().map(|u| u.row).collect();
+    rows.sort();
+    assert_eq!(rows, vec![0, 1, 2]);
 }
 
 #[test]
@@ -881,6 +1271,41 @@ fn scroll_offset_increases_when_scrollback_grows() {
     assert_eq!(vis[0][0].character, 'A');
 }
 
+#[test]
+fn scroll_on_output_snaps_to_bottom() {
+    let mut grid = Grid::new(5, 2);
+    grid.set_scroll_on_output(true);
+    for line in ["AAAAA", "BBBBB"] {
+        for c in line.chars() {
+            grid.apply(&TerminalCommand::Print(c));
+        }
+        grid.apply(&TerminalCommand::CarriageReturn);
+        grid.apply(&TerminalCommand::Newline);
+    }
+    grid.scroll_up_view(1);
+    assert_eq!(grid.scroll_offset(), 1);
+
+    grid.apply(&TerminalCommand::Print('C'));
+    assert_eq!(grid.scroll_offset(), 0);
+    assert_eq!(grid.visible_cells()[1][0].character, 'C');
+}
+
+#[test]
+fn scroll_on_output_disabled_keeps_view() {
+    let mut grid = Grid::new(5, 2);
+    for c in "AAAAA".chars() {
+        grid.apply(&TerminalCommand::Print(c));
+    }
+    grid.apply(&TerminalCommand::CarriageReturn);
+    grid.apply(&TerminalCommand::Newline);
+    grid.apply(&TerminalCommand::Newline);
+    grid.scroll_up_view(1);
+    grid.apply(&TerminalCommand::Print('C'));
+    assert_eq!(grid.scroll_offset(), 1);
+    grid.reset_scroll();
+    assert_eq!(grid.scroll_offset(), 0);
+}
+
 #[test]
 fn scroll_up_view_clamps_to_scrollback_len() {
     let mut grid = Grid::new(5, 2);
@@ -899,6 +1324,25 @@ fn scroll_up_view_clamps_to_scrollback_len() {
     assert_eq!(grid.scroll_offset(), 1);
 }
 
+#[test]
+fn truncate_scrollback_drops_oldest_rows() {
+    let mut grid = Grid::new(3, 1);
+    for ch in ['A', 'B', 'C', 'D'] {
+        grid.apply(&TerminalCommand::Print(ch));
+        grid.apply(&TerminalCommand::CarriageReturn);
+        grid.apply(&TerminalCommand::Newline);
+    }
+    assert_eq!(grid.scrollback_len(), 4);
+    grid.scroll_up_view(4);
+
+    grid.truncate_scrollback(2);
+
+    assert_eq!(grid.scrollback_len(), 2);
+    assert_eq!(grid.scrollback()[0][0].character, 'C');
+    assert_eq!(grid.scrollback()[1][0].character, 'D');
+    assert_eq!(grid.scroll_offset(), 2);
+}
+
 #[test]
 fn cursor_visible_default_true() {
     let grid = Grid::new(10, 5);
@@ -954,7 +1398,7 @@ fn erase_entire_display_clears_wide_chars_without_leaving_fragments() {
     grid.apply(&TerminalCommand::EraseInDisplay(2));
 
     for row in grid.cells() {
-        for cell in row {
+        for cell in row.iter() {
             assert_eq!(cell.character, ' ');
             assert!(cell.flags.is_empty());
         }
@@ -1144,6 +1588,70 @@ fn save_and_restore_cursor_returns_to_saved_position() {
     assert_eq!(grid.cells()[1][2].character, 'X');
 }
 
+// === RIS / DECSTR ===
+
+#[test]
+fn full_reset_clears_screen_scrollback_and_state() {
+    let mut grid = Grid::new(10, 3);
+    for _ in 0..5 {
+        grid.apply(&TerminalCommand::Print('A'));
+        grid.apply(&TerminalCommand::Newline);
+    }
+    grid.apply(&TerminalCommand::SetBold);
+    grid.apply(&TerminalCommand::HideCursor);
+    grid.apply(&TerminalCommand::SetScrollRegion { top: 2, bottom: 3 });
+    grid.apply(&TerminalCommand::EnterAltScreen);
+
+    grid.apply(&TerminalCommand::FullReset);
+
+    assert_eq!(grid.cursor_pos(), (0, 0));
+    assert!(grid.cursor_visible());
+    assert_eq!(grid.scrollback_len(), 0);
+    assert_eq!(grid.cells().len(), 3);
+    assert!(grid.cells().iter().flat_map(|row| row.iter()).all(|c| *c == Cell::default()));
+    grid.apply(&TerminalCommand::Print('X'));
+    assert!(!grid.cells()[0][0].flags.contains(CellFlags::BOLD));
+    // scroll region is the full screen again
+    grid.apply(&TerminalCommand::CursorPosition { row: 3, col: 1 });
+    grid.apply(&TerminalCommand::Newline);
+    assert_eq!(grid.cells()[1][0].character, ' ');
+    assert_eq!(grid.scrollback_len(), 1);
+}
+
+#[test]
+fn full_reset_discards_main_screen_scrollback() {
+    let mut grid = Grid::new(10, 2);
+    for _ in 0..4 {
+        grid.apply(&TerminalCommand::Print('A'));
+        grid.apply(&TerminalCommand::Newline);
+    }
+    assert!(grid.scrollback_len() > 0);
+    grid.apply(&TerminalCommand::FullReset);
+    assert_eq!(grid.scrollback_len(), 0);
+}
+
+#[test]
+fn soft_reset_keeps_screen_and_cursor() {
+    let mut grid = Grid::new(10, 4);
+    grid.apply(&TerminalCommand::Print('A'));
+    grid.apply(&TerminalCommand::SetBold);
+    grid.apply(&TerminalCommand::HideCursor);
+    grid.apply(&TerminalCommand::SetScrollRegion { top: 1, bottom: 2 });
+    grid.apply(&TerminalCommand::CursorPosition { row: 3, col: 4 });
+
+    grid.apply(&TerminalCommand::SoftReset);
+
+    assert_eq!(grid.cells()[0][0].character, 'A');
+    assert_eq!(grid.cursor_pos(), (2, 3));
+    assert!(grid.cursor_visible());
+    grid.apply(&TerminalCommand::Print('B'));
+    assert!(!grid.cells()[2][3].flags.contains(CellFlags::BOLD));
+    // Newline on the last row scrolls the whole screen, not rows 1-2
+    grid.apply(&TerminalCommand::CursorPosition { row: 4, col: 1 });
+    grid.apply(&TerminalCommand::Newline);
+    assert_eq!(grid.cells()[1][3].character, 'B');
+}
+
 // === Insert/Delete Lines ===
 
 #[test]
@@ -1219,6 +1727,63 @@ fn scroll_down_content() {
     assert_eq!(grid.cells()[2][0].character, 'B');
 }
 
+fn letter_rows(grid: &mut Grid, letters: &str) {
+    for (r, ch) in letters.chars().enumerate() {
+        grid.apply(&TerminalCommand::CursorPosition { row: r as u16 + 1, col: 1 });
+        grid.apply(&TerminalCommand::Print(ch));
+    }
+}
+
+fn first_column(grid: &Grid) -> String {
+    grid.cells().iter().map(|row| row[0].character).collect()
+}
+
+#[test]
+fn scroll_up_in_region_keeps_cursor_and_outer_rows() {
+    let mut grid = Grid::new(3, 5);
+    letter_rows(&mut grid, "ABCDE");
+    grid.apply(&TerminalCommand::SetScrollRegion { top: 2, bottom: 4 });
+    grid.apply(&TerminalCommand::CursorPosition { row: 3, col: 2 });
+    grid.apply(&TerminalCommand::ScrollUp(2));
+    assert_eq!(first_column(&grid), "AD  E");
+    assert_eq!(grid.cursor_pos(), (2, 1));
+    // 중간 영역에서 밀려난 줄은 scrollback에 들어가지 않음
+    assert_eq!(grid.scrollback_len(), 0);
+}
+
+#[test]
+fn scroll_down_in_region_keeps_cursor_and_outer_rows() {
+    let mut grid = Grid::new(3, 5);
+    letter_rows(&mut grid, "ABCDE");
+    grid.apply(&TerminalCommand::SetScrollRegion { top: 2, bottom: 4 });
+    grid.apply(&TerminalCommand::CursorPosition { row: 1, col: 1 });
+    grid.apply(&TerminalCommand::ScrollDown(1));
+    assert_eq!(first_column(&grid), "A BCE");
+    assert_eq!(grid.cursor_pos(), (0, 0));
+}
+
+#[test]
+fn scroll_up_at_top_region_enters_scrollback() {
+    let mut grid = Grid::new(3, 5);
+    letter_rows(&mut grid, "ABCDE");
+    grid.apply(&TerminalCommand::SetScrollRegion { top: 1, bottom: 3 });
+    grid.apply(&TerminalCommand::ScrollUp(2));
+    assert_eq!(first_column(&grid), "C  DE");
+    assert_eq!(grid.scrollback_len(), 2);
+    assert_eq!(row_text(&grid.scrollback()[0]), "A");
+    assert_eq!(row_text(&grid.scrollback()[1]), "B");
+}
+
+#[test]
+fn scroll_down_does_not_pull_from_scrollback() {
+    let mut grid = Grid::new(3, 2);
+    letter_rows(&mut grid, "AB");
+    grid.apply(&TerminalCommand::ScrollUp(1));
+    grid.apply(&TerminalCommand::ScrollDown(1));
+    assert_eq!(first_column(&grid), " B");
+    assert_eq!(grid.scrollback_len(), 1);
+}
+
 // === Insert/Erase Characters ===
 
 #[test]
@@ -1253,6 +1818,36 @@ fn erase_chars_blanks_at_cursor() {
     assert_eq!(grid.cells()[0][4].character, 'E');
 }
 
+// === Repeat (REP) ===
+
+#[test]
+fn repeat_char_prints_last_character_with_attributes() {
+    let mut grid = Grid::new(10, 1);
+    grid.apply(&TerminalCommand::SetBold);
+    print_str(&mut grid, "ab");
+    grid.apply(&TerminalCommand::RepeatChar(3));
+    assert_eq!(row_text(&grid.cells()[0]), "abbbb");
+    assert!(grid.cells()[0][4].flags.contains(CellFlags::BOLD));
+    assert_eq!(grid.cursor_pos(), (0, 5));
+}
+
+#[test]
+fn repeat_char_wraps_and_skips_combining_marks() {
+    let mut grid = Grid::new(3, 2);
+    print_str(&mut grid, "e\u{0301}");
+    grid.apply(&TerminalCommand::RepeatChar(3));
+    assert_eq!(row_text(&grid.cells()[0]), "eee");
+    assert_eq!(row_text(&grid.cells()[1]), "e");
+    assert!(grid.cells()[0][1].zerowidth.is_none());
+}
+
+#[test]
+fn repeat_char_without_previous_character_is_noop() {
+    let mut grid = Grid::new(5, 1);
+    grid.apply(&TerminalCommand::RepeatChar(3));
+    assert_eq!(grid.cursor_pos(), (0, 0));
+}
+
 // === Scroll Region ===
 
 #[test]
@@ -1296,3 +1891,391 @@ fn reset_scroll_region_restores_full_screen() {
     assert_eq!(grid.cells()[1][0].character, 'C');
     assert_eq!(grid.cells()[2][0].character, ' ');
 }
+
+// === Minimum grid size ===
+
+#[test]
+fn zero_size_grid_is_clamped_to_minimum() {
+    let grid = Grid::new(0, 0);
+    assert_eq!(grid.cells().len(), 1);
+    assert_eq!(grid.cells()[0].len(), 1);
+}
+
+#[test]
+fn resize_to_zero_keeps_one_cell() {
+    let mut grid = Grid::new(10, 5);
+    grid.apply(&TerminalCommand::CursorPosition { row: 4, col: 8 });
+    grid.resize(0, 0);
+    assert_eq!(grid.cursor_pos(), (0, 0));
+    grid.apply(&TerminalCommand::Print('A'));
+    grid.apply(&TerminalCommand::Newline);
+    grid.apply(&TerminalCommand::CursorDown(3));
+    grid.apply(&TerminalCommand::Tab);
+    assert_eq!(grid.cursor_pos(), (0, 0));
+}
+
+#[test]
+fn wide_char_in_single_column_grid_does_not_panic() {
+    let mut grid = Grid::new(1, 2);
+    grid.apply(&TerminalCommand::Print('한'));
+    grid.apply(&TerminalCommand::Print('글'));
+    assert_eq!(grid.cells()[0].len(), 1);
+}
+
+#[test]
+fn inverted_scroll_region_is_ignored() {
+    let mut grid = Grid::new(5, 3);
+    grid.apply(&TerminalCommand::SetScrollRegion { top: 3, bottom: 2 });
+    grid.apply(&TerminalCommand::CursorPosition { row: 3, col: 1 });
+    grid.apply(&TerminalCommand::InsertLines(1));
+    grid.apply(&TerminalCommand::DeleteLines(1));
+    grid.apply(&TerminalCommand::ScrollUp(2));
+    grid.apply(&TerminalCommand::Newline);
+    assert_eq!(grid.cells().len(), 3);
+}
+
+#[test]
+fn insert_lines_below_scroll_region_is_noop() {
+    let mut grid = Grid::new(5, 4);
+    grid.apply(&TerminalCommand::SetScrollRegion { top: 1, bottom: 2 });
+    grid.apply(&TerminalCommand::CursorPosition { row: 4, col: 1 });
+    grid.apply(&TerminalCommand::InsertLines(1));
+    grid.apply(&TerminalCommand::DeleteLines(1));
+    assert_eq!(grid.cells().len(), 4);
+}
+
+#[test]
+fn leave_alt_screen_after_shrink_fits_new_size() {
+    let mut grid = Grid::new(10, 5);
+    grid.apply(&TerminalCommand::CursorPosition { row: 5, col: 10 });
+    grid.apply(&TerminalCommand::EnterAltScreen);
+    grid.resize(3, 2);
+    grid.apply(&TerminalCommand::LeaveAltScreen);
+    assert_eq!(grid.cells().len(), 2);
+    assert!(grid.cells().iter().all(|row| row.len() == 3));
+    grid.apply(&TerminalCommand::Print('X'));
+}
+
+// === Explicit spaces (whitespace debug view) ===
+
+#[test]
+fn printed_space_is_marked_explicit() {
+    let mut grid = Grid::new(5, 1);
+    grid.apply(&TerminalCommand::Print('a'));
+    grid.apply(&TerminalCommand::Print(' '));
+    assert!(grid.cells()[0][1].flags.contains(CellFlags::EXPLICIT_SPACE));
+    assert!(!grid.cells()[0][0].flags.contains(CellFlags::EXPLICIT_SPACE));
+    assert_eq!(grid.cells()[0][2], Cell::default());
+}
+
+#[test]
+fn apply_batch_matches_apply() {
+    let cmds: Vec<TerminalCommand> = "hello 한글 world\r\nsecond line that wraps past the edge"
+        .chars()
+        .map(|c| match c {
+            '\r' => TerminalCommand::CarriageReturn,
+            '\n' => TerminalCommand::Newline,
+            c => TerminalCommand::Print(c),
+        })
+        .chain([
+            TerminalCommand::SetBold,
+            TerminalCommand::Print('X'),
+            TerminalCommand::EraseInLine(0),
+        ])
+        .collect();
+    let mut one = Grid::new(10, 3);
+    for cmd in &cmds {
+        one.apply(cmd);
+    }
+    let mut batch = Grid::new(10, 3);
+    batch.apply_batch(&cmds);
+    assert_eq!(one.cells(), batch.cells());
+    assert_eq!(one.cursor_pos(), batch.cursor_pos());
+    assert_eq!(one.scrollback_len(), batch.scrollback_len());
+}
+
+#[test]
+fn apply_batch_snaps_scroll_on_output() {
+    let mut grid = Grid::new(5, 1);
+    grid.set_scroll_on_output(true);
+    grid.apply_batch(&[TerminalCommand::Print('A'), TerminalCommand::Newline]);
+    grid.scroll_up_view(1);
+    assert_eq!(grid.scroll_offset(), 1);
+    grid.apply_batch(&[]);
+    assert_eq!(grid.scroll_offset(), 1);
+    grid.apply_batch(&[TerminalCommand::Print('B')]);
+    assert_eq!(grid.scroll_offset(), 0);
+}
+
+#[test]
+fn set_mode_25_toggles_cursor_visibility() {
+    let mut grid = Grid::new(10, 3);
+    grid.apply(&TerminalCommand::SetMode(25, false));
+    assert!(!grid.cursor_visible());
+    grid.apply(&TerminalCommand::SetMode(25, true));
+    assert!(grid.cursor_visible());
+}
+
+#[test]
+fn set_mode_1049_switches_alt_screen() {
+    let mut grid = Grid::new(10, 3);
+    grid.apply(&TerminalCommand::Print('A'));
+    grid.apply(&TerminalCommand::SetMode(1049, true));
+    assert_eq!(grid.cells()[0][0].character, ' ');
+    grid.apply(&TerminalCommand::SetMode(1049, false));
+    assert_eq!(grid.cells()[0][0].character, 'A');
+}
+
+#[test]
+fn set_mode_unknown_is_ignored() {
+    let mut grid = Grid::new(10, 3);
+    grid.apply(&TerminalCommand::Print('A'));
+    grid.apply(&TerminalCommand::SetMode(2004, true));
+    assert_eq!(grid.cells()[0][0].character, 'A');
+    assert_eq!(grid.cursor_pos(), (0, 1));
+}
+
+// === Snapshot / restore ===
+
+#[test]
+fn snapshot_restore_round_trips_screen_and_cursor() {
+    let mut grid = Grid::new(10, 3);
+    grid.apply(&TerminalCommand::SetForeground(Color::Indexed(1)));
+    print_str(&mut grid, "hi");
+    grid.apply(&TerminalCommand::SetScrollRegion { top: 1, bottom: 2 });
+    grid.apply(&TerminalCommand::CursorPosition { row: 2, col: 4 });
+    grid.apply(&TerminalCommand::SetMode(25, false));
+
+    let snapshot = grid.snapshot();
+    let restored = Grid::restore(&snapshot);
+    assert_eq!(restored.cells(), grid.cells());
+    assert_eq!(restored.cursor_pos(), (1, 3));
+    assert!(!restored.cursor_visible());
+    assert_eq!(restored.snapshot(), snapshot);
+}
+
+#[test]
+fn snapshot_keeps_scrollback() {
+    let mut grid = Grid::new(5, 2);
+    print_str(&mut grid, "A");
+    grid.apply(&TerminalCommand::Newline);
+    grid.apply(&TerminalCommand::CarriageReturn);
+    print_str(&mut grid, "B");
+    grid.apply(&TerminalCommand::Newline);
+    grid.apply(&TerminalCommand::CarriageReturn);
+    print_str(&mut grid, "C");
+
+    let restored = Grid::restore(&grid.snapshot());
+    assert_eq!(restored.scrollback_len(), 1);
+    assert_eq!(restored.scrollback()[0][0].character, 'A');
+    assert_eq!(restored.cells()[1][0].character, 'C');
+}
+
+#[test]
+fn snapshot_in_alt_screen_restores_main_screen_on_exit() {
+    let mut grid = Grid::new(10, 3);
+    print_str(&mut grid, "main");
+    grid.apply(&TerminalCommand::SetMode(1049, true));
+    print_str(&mut grid, "alt");
+
+    let mut restored = Grid::restore(&grid.snapshot());
+    assert_eq!(restored.cells()[0][0].character, 'a');
+    restored.apply(&TerminalCommand::SetMode(1049, false));
+    assert_eq!(restored.cells()[0][0].character, 'm');
+    assert_eq!(restored.cursor_pos(), (0, 4));
+}
+
+#[test]
+fn restore_fits_mismatched_rows_and_clamps_cursor() {
+    let mut snapshot = Grid::new(4, 2).snapshot();
+    snapshot.cells = vec![vec![Cell { character: 'x', ..Cell::default() }; 6]];
+    snapshot.cursor = (9, 9);
+    snapshot.scroll_region = (1, 7);
+
+    let mut grid = Grid::restore(&snapshot);
+    assert_eq!(grid.cells().len(), 2);
+    assert!(grid.cells().iter().all(|row| row.len() == 4));
+    assert_eq!(grid.cells()[0][3].character, 'x');
+    assert_eq!(grid.cursor_pos(), (1, 4));
+    // Invalid scroll region falls back to the full screen
+    grid.apply(&TerminalCommand::CursorPosition { row: 2, col: 1 });
+    grid.apply(&TerminalCommand::Newline);
+    assert_eq!(grid.scrollback_len(), 1);
+    assert_eq!(grid.scrollback()[0][0].character, 'x');
+}
+
+#[test]
+fn snapshot_serializes_to_json_and_back() {
+    let mut grid = Grid::new(6, 2);
+    grid.apply(&TerminalCommand::SetBold);
+    grid.apply(&TerminalCommand::SetBackground(Color::Rgb(Rgb::new(1, 2, 3))));
+    print_str(&mut grid, "e\u{301}한");
+
+    let json = serde_json::to_string(&grid.snapshot()).unwrap();
+    let snapshot: crate::GridSnapshot = serde_json::from_str(&json).unwrap();
+    let restored = Grid::restore(&snapshot);
+    assert_eq!(restored.cells(), grid.cells());
+    assert_eq!(restored.cells()[0][0].zerowidth.unwrap().as_str(), "\u{301}");
+    assert!(restored.cells()[0][1].flags.contains(CellFlags::BOLD | CellFlags::WIDE_CHAR));
+}
+
+#[test]
+fn diff_since_zero_returns_every_row() {
+    let grid = Grid::new(4, 3);
+    let rows: Vec<u16> = grid.diff_since(0).iter().map(|u| u.row).collect();
+    assert_eq!(rows, vec![0, 1, 2]);
+    assert!(grid.diff_since(grid.generation()).is_empty());
+}
+
+#[test]
+fn diff_since_reports_only_changed_rows() {
+    let mut grid = Grid::new(4, 3);
+    let generation = grid.generation();
+    grid.apply(&TerminalCommand::CursorPosition { row: 2, col: 1 });
+    print_str(&mut grid, "ab");
+
+    let updates = grid.diff_since(generation);
+    assert_eq!(updates.len(), 1);
+    assert_eq!(updates[0].row, 1);
+    assert_eq!(updates[0].cells[0].character, 'a');
+    assert!(grid.generation() > generation);
+
+    let generation = grid.generation();
+    grid.apply(&TerminalCommand::EraseInLine(2));
+    let updates = grid.diff_since(generation);
+    assert_eq!(updates.len(), 1);
+    assert_eq!(*updates[0].cells, [Cell::default(); 4]);
+}
+
+#[test]
+fn diff_since_reports_all_rows_after_scroll_and_resize() {
+    let mut grid = Grid::new(4, 3);
+    grid.apply(&TerminalCommand::CursorPosition { row: 3, col: 1 });
+    let generation = grid.generation();
+    grid.apply(&TerminalCommand::Newline);
+    assert_eq!(grid.diff_since(generation).len(), 3);
+
+    let generation = grid.generation();
+    grid.resize(5, 4);
+    assert_eq!(grid.diff_since(generation).len(), 4);
+}
+
+#[test]
+fn generation_survives_full_reset() {
+    let mut grid = Grid::new(4, 2);
+    print_str(&mut grid, "abc");
+    let generation = grid.generation();
+    grid.apply(&TerminalCommand::FullReset);
+    assert!(grid.generation() > generation);
+    assert_eq!(grid.diff_since(generation).len(), 2);
+}
+
+#[test]
+fn erase_line_left_in_pending_wrap_does_not_panic() {
+    let mut grid = Grid::new(3, 2);
+    print_str(&mut grid, "abc");
+    assert_eq!(grid.cursor_pos(), (0, 3));
+    grid.apply(&TerminalCommand::EraseInLine(1));
+    grid.apply(&TerminalCommand::EraseInDisplay(1));
+    assert!(grid.cells()[0].iter().all(|c| c.character == ' '));
+}
+
+#[test]
+fn huge_scroll_up_is_bounded_by_screen_height() {
+    let mut grid = Grid::new(4, 3);
+    print_str(&mut grid, "abc");
+    grid.apply(&TerminalCommand::ScrollUp(u16::MAX));
+    assert_eq!(grid.scrollback_len(), 3);
+    assert_eq!(grid.scrollback()[0][0].character, 'a');
+    assert!(grid.cells().iter().flat_map(|row| row.iter()).all(|c| c.character == ' '));
+}
+
+#[test]
+fn alt_screen_resize_keeps_scrollback_rows_at_new_width() {
+    let mut grid = Grid::new(4, 2);
+    grid.apply(&TerminalCommand::EnterAltScreen);
+    print_str(&mut grid, "ab");
+    grid.apply(&TerminalCommand::ScrollUp(1));
+    assert_eq!(grid.scrollback_len(), 1);
+    grid.resize(6, 2);
+    assert!(grid.scrollback().iter().all(|row| row.len() == 6));
+    assert_eq!(grid.scrollback()[0][0].character, 'a');
+}
+
+#[test]
+fn scrolled_out_row_is_shared_not_copied() {
+    let mut grid = Grid::new(4, 2);
+    print_str(&mut grid, "ab");
+    let row = grid.screen_rows()[0].clone();
+    grid.apply(&TerminalCommand::ScrollUp(1));
+    assert!(grid.scrollback_rows()[0].shares_cells(&row));
+
+    grid.set_scroll_offset(1);
+    assert!(grid.visible_rows()[0].shares_cells(&row));
+}
+
+#[test]
+fn writing_a_shared_row_leaves_other_holders_alone() {
+    let mut grid = Grid::new(4, 2);
+    print_str(&mut grid, "ab");
+    let before = grid.diff_since(0);
+    grid.apply(&TerminalCommand::CarriageReturn);
+    print_str(&mut grid, "x");
+    assert_eq!(before[0].cells[0].character, 'a');
+    assert_eq!(grid.cells()[0][0].character, 'x');
+}
+
+#[test]
+fn blank_rows_share_one_allocation_until_written() {
+    let mut grid = Grid::new(4, 3);
+    assert!(grid.screen_rows()[1].shares_cells(&grid.screen_rows()[2]));
+    grid.apply(&TerminalCommand::CursorPosition { row: 2, col: 1 });
+    print_str(&mut grid, "a");
+    assert!(!grid.screen_rows()[1].shares_cells(&grid.screen_rows()[2]));
+    assert_eq!(grid.cells()[2][0], Cell::default());
+}
+
+// === Row / RowIter ===
+
+#[test]
+fn rows_run_from_scrollback_into_screen() {
+    let mut grid = Grid::new(4, 2);
+    print_str(&mut grid, "ab");
+    grid.apply(&TerminalCommand::ScrollUp(1));
+    print_str(&mut grid, "cd");
+    let rows = grid.rows();
+    assert_eq!(rows.len(), 3);
+    assert_eq!(rows[0][0].character, 'a');
+    assert_eq!(grid.row(0), grid.scrollback_rows().next());
+    assert_eq!(grid.row(1), grid.screen_row(0));
+    assert!(grid.row(3).is_none());
+    assert_eq!(grid.rows().rev().count(), 3);
+}
+
+#[test]
+fn visible_rows_follow_scroll_offset() {
+    let mut grid = Grid::new(4, 2);
+    for ch in ["a", "b", "c"] {
+        print_str(&mut grid, ch);
+        grid.apply(&TerminalCommand::ScrollUp(1));
+        grid.apply(&TerminalCommand::CarriageReturn);
+    }
+    assert_eq!(grid.visible_rows().len(), 2);
+    assert!(grid.visible_rows().eq(grid.screen_rows()));
+
+    grid.set_scroll_offset(2);
+    let firsts: Vec<char> = grid.visible_rows().map(|row| row[0].character).collect();
+    assert_eq!(firsts, vec!['b', 'c']);
+    assert_eq!(grid.visible_rows().collect::<Vec<_>>(), grid.visible_cells().iter().collect::<Vec<_>>());
+}
+
+#[test]
+fn row_reports_width_and_wrap() {
+    let mut grid = Grid::new(3, 2);
+    print_str(&mut grid, "abcd");
+    let row = grid.screen_row(0).unwrap();
+    assert_eq!(row.width(), 3);
+    assert!(row.wraps());
+    assert!(!grid.screen_rows()[1].wraps());
+    assert_eq!(grid.size(), (3, 2));
+}
@@ -1,8 +1,139 @@
-use growterm_types::{Cell, CellFlags, Color, TerminalCommand};
+use growterm_types::{Attributes, Cell, CellFlags, Color, TerminalCommand, UnderlineStyle, ZeroWidth};
+use serde::{Deserialize, Serialize};
 use unicode_width::UnicodeWidthChar;
 use std::io::Write;
+use std::sync::Arc;
 
-const MAX_SCROLLBACK: usize = 10_000;
+/// Scrollback limit for a new grid; see [`Grid::set_max_scrollback`].
+pub const DEFAULT_MAX_SCROLLBACK: usize = 10_000;
+
+/// Smallest grid a `Grid` will ever hold. `new`/`resize` clamp to this so
+/// cursor math like `rows - 1` can never underflow.
+pub const MIN_COLS: u16 = 1;
+pub const MIN_ROWS: u16 = 1;
+
+/// One row of cells. Rows are shared between the screen, scrollback,
+/// [`Grid::visible_rows`] and [`Grid::diff_since`] and only copied when
+/// the grid writes to a row someone else still holds. Derefs to its
+/// cells, so `row[col]` and `row.iter()` work as on a slice.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Row(Arc<[Cell]>);
+
+impl Row {
+    /// Columns in the row, wide characters counting twice
+    pub fn width(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Whether the line continues on the next row (auto-wrapped)
+    pub fn wraps(&self) -> bool {
+        is_wrapped(&self.0)
+    }
+
+    /// True if both rows share the same cells rather than equal copies.
+    pub fn shares_cells(&self, other: &Row) -> bool {
+        Arc::ptr_eq(&self.0, &other.0)
+    }
+}
+
+impl Default for Row {
+    fn default() -> Self {
+        Row(Vec::new().into())
+    }
+}
+
+impl std::ops::Deref for Row {
+    type Target = [Cell];
+
+    fn deref(&self) -> &[Cell] {
+        &self.0
+    }
+}
+
+impl AsRef<[Cell]> for Row {
+    fn as_ref(&self) -> &[Cell] {
+        &self.0
+    }
+}
+
+impl From<Vec<Cell>> for Row {
+    fn from(cells: Vec<Cell>) -> Self {
+        Row(cells.into())
+    }
+}
+
+/// Rows in order, from [`Grid::rows`], [`Grid::screen_rows`] and friends.
+/// Also indexable: `rows[i]` is the i-th row not yet iterated.
+#[derive(Debug, Clone)]
+pub struct RowIter<'a> {
+    front: &'a [Row],
+    back: &'a [Row],
+}
+
+impl<'a> RowIter<'a> {
+    fn new(front: &'a [Row], back: &'a [Row]) -> Self {
+        Self { front, back }
+    }
+
+    pub fn get(&self, index: usize) -> Option<&'a Row> {
+        match index.checked_sub(self.front.len()) {
+            None => self.front.get(index),
+            Some(index) => self.back.get(index),
+        }
+    }
+}
+
+impl<'a> Iterator for RowIter<'a> {
+    type Item = &'a Row;
+
+    fn next(&mut self) -> Option<&'a Row> {
+        if let Some((first, rest)) = self.front.split_first() {
+            self.front = rest;
+            return Some(first);
+        }
+        let (first, rest) = self.back.split_first()?;
+        self.back = rest;
+        Some(first)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.front.len() + self.back.len();
+        (len, Some(len))
+    }
+}
+
+impl DoubleEndedIterator for RowIter<'_> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if let Some((last, rest)) = self.back.split_last() {
+            self.back = rest;
+            return Some(last);
+        }
+        let (last, rest) = self.front.split_last()?;
+        self.front = rest;
+        Some(last)
+    }
+}
+
+impl ExactSizeIterator for RowIter<'_> {}
+
+impl std::ops::Index<usize> for RowIter<'_> {
+    type Output = Row;
+
+    fn index(&self, index: usize) -> &Row {
+        match self.get(index) {
+            Some(row) => row,
+            None => panic!("row {index} out of range for {} rows", self.len()),
+        }
+    }
+}
+
+fn blank_row(cols: usize) -> Row {
+    vec![Cell::default(); cols].into()
+}
+
+fn is_regional_indicator(c: char) -> bool {
+    ('\u{1F1E6}'..='\u{1F1FF}').contains(&c)
+}
 
 // 디버깅 시 /tmp/growterm-debug.log 에 로그 남길 때 사용
 #[allow(dead_code)]
@@ -17,27 +148,64 @@ fn debug_log(msg: &str) {
 }
 
 struct SavedScreen {
-    cells: Vec<Vec<Cell>>,
+    cells: Vec<Row>,
     cursor_row: usize,
     cursor_col: usize,
-    current_fg: Color,
-    current_bg: Color,
-    current_flags: CellFlags,
-    scrollback: Vec<Vec<Cell>>,
+    attrs: Attributes,
+    scrollback: Vec<Row>,
     scroll_offset: usize,
     cursor_visible: bool,
 }
 
+/// Serializable grid state from [`Grid::snapshot`], for session restore,
+/// debug dumps and golden files. The scrollback view position is not kept.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GridSnapshot {
+    pub cols: u16,
+    pub rows: u16,
+    pub cells: Vec<Vec<Cell>>,
+    #[serde(default)]
+    pub scrollback: Vec<Vec<Cell>>,
+    /// (row, col)
+    pub cursor: (u16, u16),
+    pub cursor_visible: bool,
+    pub attrs: Attributes,
+    /// (top, bottom), bottom exclusive
+    pub scroll_region: (u16, u16),
+    #[serde(default)]
+    pub saved_cursor: Option<(u16, u16)>,
+    #[serde(default)]
+    pub grapheme_clustering: bool,
+    /// The main screen, saved while the alternate screen is active.
+    #[serde(default)]
+    pub main_screen: Option<ScreenSnapshot>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ScreenSnapshot {
+    pub cells: Vec<Vec<Cell>>,
+    #[serde(default)]
+    pub scrollback: Vec<Vec<Cell>>,
+    pub cursor: (u16, u16),
+    pub cursor_visible: bool,
+    pub attrs: Attributes,
+}
+
+/// One screen row as returned by [`Grid::diff_since`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct RowUpdate {
+    pub row: u16,
+    pub cells: Row,
+}
+
 pub struct Grid {
-    cells: Vec<Vec<Cell>>,
+    cells: Vec<Row>,
     cols: usize,
     rows: usize,
     cursor_row: usize,
     cursor_col: usize,
-    current_fg: Color,
-    current_bg: Color,
-    current_flags: CellFlags,
-    scrollback: Vec<Vec<Cell>>,
+    attrs: Attributes,
+    scrollback: Vec<Row>,
     scroll_offset: usize,
     cursor_visible: bool,
     scroll_region_top: usize,
@@ -45,21 +213,33 @@ pub struct Grid {
     saved_cursor: Option<(usize, usize)>,
     saved_screen: Option<SavedScreen>,
     in_alt_screen: bool,
+    scroll_on_output: bool,
+    /// Oldest rows beyond this are dropped; `None` keeps everything
+    max_scrollback: Option<usize>,
+    /// Mode 2027: size cells by grapheme cluster instead of per code point
+    grapheme_clustering: bool,
+    /// Last base character printed, for REP. Combining marks don't count.
+    last_printed: Option<char>,
+    /// Bumped on every screen change; never goes backwards, even across RIS
+    generation: u64,
+    /// Generation at which each screen row last changed
+    row_generations: Vec<u64>,
+    /// Shared by every untouched blank row, so scrolling doesn't allocate
+    blank_row: Row,
 }
 
 impl Grid {
     pub fn new(cols: u16, rows: u16) -> Self {
-        let cols = cols as usize;
-        let rows = rows as usize;
+        let cols = cols.max(MIN_COLS) as usize;
+        let rows = rows.max(MIN_ROWS) as usize;
+        let blank = blank_row(cols);
         Self {
-            cells: vec![vec![Cell::default(); cols]; rows],
+            cells: vec![blank.clone(); rows],
             cols,
             rows,
             cursor_row: 0,
             cursor_col: 0,
-            current_fg: Color::Default,
-            current_bg: Color::Default,
-            current_flags: CellFlags::empty(),
+            attrs: Attributes::default(),
             scrollback: Vec::new(),
             scroll_offset: 0,
             cursor_visible: true,
@@ -68,13 +248,64 @@ impl Grid {
             saved_cursor: None,
             saved_screen: None,
             in_alt_screen: false,
+            scroll_on_output: false,
+            max_scrollback: Some(DEFAULT_MAX_SCROLLBACK),
+            grapheme_clustering: false,
+            last_printed: None,
+            generation: 1,
+            row_generations: vec![1; rows],
+            blank_row: blank,
         }
     }
 
-    pub fn cells(&self) -> &[Vec<Cell>] {
+    #[deprecated(note = "use `screen_rows` or `screen_row`")]
+    pub fn cells(&self) -> &[Row] {
         &self.cells
     }
 
+    /// Rows of the active screen, top to bottom.
+    pub fn screen_rows(&self) -> RowIter<'_> {
+        RowIter::new(&self.cells, &[])
+    }
+
+    pub fn screen_row(&self, row: usize) -> Option<&Row> {
+        self.cells.get(row)
+    }
+
+    /// Scrollback rows, oldest first.
+    pub fn scrollback_rows(&self) -> RowIter<'_> {
+        RowIter::new(&self.scrollback, &[])
+    }
+
+    /// Scrollback then screen: the row at absolute index `i` (as used by
+    /// selections) is `rows()[i]`.
+    pub fn rows(&self) -> RowIter<'_> {
+        RowIter::new(&self.scrollback, &self.cells)
+    }
+
+    /// Row by absolute index, counting from the oldest scrollback row.
+    pub fn row(&self, abs_row: usize) -> Option<&Row> {
+        self.rows().get(abs_row)
+    }
+
+    /// Rows in view, scrollback included when scrolled back.
+    pub fn visible_rows(&self) -> RowIter<'_> {
+        let start = self.scrollback.len().saturating_sub(self.scroll_offset);
+        let end = (start + self.rows).min(self.scrollback.len());
+        let from_scrollback = &self.scrollback[start..end];
+        RowIter::new(from_scrollback, &self.cells[..self.rows - from_scrollback.len()])
+    }
+
+    /// Screen size as (cols, rows).
+    pub fn size(&self) -> (u16, u16) {
+        (self.cols as u16, self.rows as u16)
+    }
+
+    /// Row to write into, copied first if it is still shared.
+    fn row_mut(&mut self, row: usize) -> &mut [Cell] {
+        Arc::make_mut(&mut self.cells[row].0)
+    }
+
     pub fn cursor_pos(&self) -> (u16, u16) {
         (self.cursor_row as u16, self.cursor_col as u16)
     }
@@ -84,43 +315,69 @@ impl Grid {
     }
 
     pub fn apply(&mut self, cmd: &TerminalCommand) {
+        if self.scroll_on_output {
+            self.scroll_offset = 0;
+        }
+        self.apply_command(cmd);
+    }
+
+    /// Apply a whole parsed chunk. Same result as calling `apply` for each
+    /// command, but per-chunk work is done once and runs of `Print` skip the
+    /// full command dispatch.
+    pub fn apply_batch(&mut self, cmds: &[TerminalCommand]) {
+        if cmds.is_empty() {
+            return;
+        }
+        if self.scroll_on_output {
+            self.scroll_offset = 0;
+        }
+        for cmd in cmds {
+            match cmd {
+                TerminalCommand::Print(c) => self.print(*c),
+                _ => self.apply_command(cmd),
+            }
+        }
+    }
+
+    fn apply_command(&mut self, cmd: &TerminalCommand) {
         match cmd {
             TerminalCommand::Print(c) => self.print(*c),
             TerminalCommand::CursorUp(n) => {
                 self.cursor_row = self.cursor_row.saturating_sub(*n as usize);
             }
             TerminalCommand::CursorDown(n) => {
-                self.cursor_row = (self.cursor_row + *n as usize).min(self.rows - 1);
+                self.cursor_row = (self.cursor_row + *n as usize).min(self.last_row());
             }
             TerminalCommand::CursorForward(n) => {
-                self.cursor_col = (self.cursor_col + *n as usize).min(self.cols - 1);
+                self.cursor_col = (self.cursor_col + *n as usize).min(self.last_col());
             }
             TerminalCommand::CursorBack(n) => {
                 self.cursor_col = self.cursor_col.saturating_sub(*n as usize);
             }
             TerminalCommand::CursorPosition { row, col } => {
-                self.cursor_row = (*row as usize).saturating_sub(1).min(self.rows - 1);
-                self.cursor_col = (*col as usize).saturating_sub(1).min(self.cols - 1);
-            }
-            TerminalCommand::SetForeground(c) => self.current_fg = *c,
-            TerminalCommand::SetBackground(c) => self.current_bg = *c,
-            TerminalCommand::SetBold => self.current_flags |= CellFlags::BOLD,
-            TerminalCommand::SetDim => self.current_flags |= CellFlags::DIM,
-            TerminalCommand::SetItalic => self.current_flags |= CellFlags::ITALIC,
-            TerminalCommand::SetUnderline => self.current_flags |= CellFlags::UNDERLINE,
-            TerminalCommand::SetInverse => self.current_flags |= CellFlags::INVERSE,
-            TerminalCommand::SetHidden => self.current_flags |= CellFlags::HIDDEN,
-            TerminalCommand::SetStrikethrough => self.current_flags |= CellFlags::STRIKETHROUGH,
-            TerminalCommand::ResetBold => self.current_flags.remove(CellFlags::BOLD | CellFlags::DIM),
-            TerminalCommand::ResetItalic => self.current_flags.remove(CellFlags::ITALIC),
-            TerminalCommand::ResetUnderline => self.current_flags.remove(CellFlags::UNDERLINE),
-            TerminalCommand::ResetInverse => self.current_flags.remove(CellFlags::INVERSE),
-            TerminalCommand::ResetHidden => self.current_flags.remove(CellFlags::HIDDEN),
-            TerminalCommand::ResetStrikethrough => self.current_flags.remove(CellFlags::STRIKETHROUGH),
-            TerminalCommand::ResetAttributes => {
-                self.current_fg = Color::Default;
-                self.current_bg = Color::Default;
-                self.current_flags = CellFlags::empty();
+                self.cursor_row = (*row as usize).saturating_sub(1).min(self.last_row());
+                self.cursor_col = (*col as usize).saturating_sub(1).min(self.last_col());
+            }
+            TerminalCommand::SetForeground(_)
+            | TerminalCommand::SetBackground(_)
+            | TerminalCommand::SetBold
+            | TerminalCommand::SetDim
+            | TerminalCommand::SetItalic
+            | TerminalCommand::SetUnderline
+            | TerminalCommand::SetUnderlineStyle(_)
+            | TerminalCommand::SetUnderlineColor(_)
+            | TerminalCommand::SetInverse
+            | TerminalCommand::SetHidden
+            | TerminalCommand::SetStrikethrough
+            | TerminalCommand::ResetBold
+            | TerminalCommand::ResetDim
+            | TerminalCommand::ResetItalic
+            | TerminalCommand::ResetUnderline
+            | TerminalCommand::ResetInverse
+            | TerminalCommand::ResetHidden
+            | TerminalCommand::ResetStrikethrough
+            | TerminalCommand::ResetAttributes => {
+                self.attrs.apply(cmd);
             }
             TerminalCommand::Newline => self.newline(),
             TerminalCommand::ReverseIndex => self.reverse_index(),
@@ -131,7 +388,7 @@ impl Grid {
             TerminalCommand::Tab => {
                 self.cursor_col = ((self.cursor_col / 8) + 1) * 8;
                 if self.cursor_col >= self.cols {
-                    self.cursor_col = self.cols - 1;
+                    self.cursor_col = self.last_col();
                 }
             }
             TerminalCommand::Bell => {}
@@ -140,21 +397,22 @@ impl Grid {
             TerminalCommand::DeleteChars(n) => self.delete_chars(*n),
             TerminalCommand::InsertChars(n) => self.insert_chars(*n),
             TerminalCommand::EraseChars(n) => self.erase_chars(*n),
+            TerminalCommand::RepeatChar(n) => self.repeat_char(*n),
             TerminalCommand::InsertLines(n) => self.insert_lines(*n),
             TerminalCommand::DeleteLines(n) => self.delete_lines(*n),
             TerminalCommand::ScrollUp(n) => self.scroll_up_content(*n),
             TerminalCommand::ScrollDown(n) => self.scroll_down_content(*n),
             TerminalCommand::CursorColumn(col) => {
-                self.cursor_col = (*col as usize).saturating_sub(1).min(self.cols - 1);
+                self.cursor_col = (*col as usize).saturating_sub(1).min(self.last_col());
             }
             TerminalCommand::CursorRow(row) => {
-                self.cursor_row = (*row as usize).saturating_sub(1).min(self.rows - 1);
+                self.cursor_row = (*row as usize).saturating_sub(1).min(self.last_row());
             }
             TerminalCommand::SaveCursor => self.saved_cursor = Some((self.cursor_row, self.cursor_col)),
             TerminalCommand::RestoreCursor => {
                 if let Some((row, col)) = self.saved_cursor {
-                    self.cursor_row = row.min(self.rows - 1);
-                    self.cursor_col = col.min(self.cols - 1);
+                    self.cursor_row = row.min(self.last_row());
+                    self.cursor_col = col.min(self.last_col());
                 }
             }
             TerminalCommand::SetScrollRegion { top, bottom } => {
@@ -162,37 +420,202 @@ impl Grid {
             }
             TerminalCommand::EnterAltScreen => self.enter_alt_screen(),
             TerminalCommand::LeaveAltScreen => self.leave_alt_screen(),
+            TerminalCommand::SetMode(mode, enabled) => self.set_mode(*mode, *enabled),
+            TerminalCommand::FullReset => self.full_reset(),
+            TerminalCommand::SoftReset => self.soft_reset(),
+            // 응답과 키보드 모드는 앱이 처리
+            TerminalCommand::QueryMode(_)
+            | TerminalCommand::QueryCursorPosition
+            | TerminalCommand::QueryPrimaryDeviceAttributes
+            | TerminalCommand::QuerySecondaryDeviceAttributes
+            | TerminalCommand::QueryKittyKeyboard
+            | TerminalCommand::PushKittyKeyboard(_)
+            | TerminalCommand::PopKittyKeyboard(_)
+            | TerminalCommand::SetKittyKeyboard(..)
+            | TerminalCommand::Osc(_)
+            | TerminalCommand::Dcs { .. } => {}
             TerminalCommand::EraseInLine(mode) => self.erase_in_line(*mode),
             TerminalCommand::EraseInDisplay(mode) => self.erase_in_display(*mode),
         }
     }
 
+    /// DEC private modes that affect the screen. Modes handled elsewhere
+    /// (bracketed paste, mouse, synchronized output) are ignored here.
+    fn set_mode(&mut self, mode: u16, enabled: bool) {
+        match (mode, enabled) {
+            (25, _) => self.cursor_visible = enabled,
+            (47 | 1047 | 1049, true) => self.enter_alt_screen(),
+            (47 | 1047 | 1049, false) => self.leave_alt_screen(),
+            (2027, _) => self.grapheme_clustering = enabled,
+            _ => {}
+        }
+    }
+
+    /// Current state of a private mode the grid owns, for DECRQM.
+    pub fn private_mode(&self, mode: u16) -> Option<bool> {
+        match mode {
+            25 => Some(self.cursor_visible),
+            47 | 1047 | 1049 => Some(self.in_alt_screen),
+            2027 => Some(self.grapheme_clustering),
+            _ => None,
+        }
+    }
+
+    /// RIS. Tab stops are fixed every 8 columns, so there is nothing to
+    /// restore for them.
+    fn full_reset(&mut self) {
+        *self = Grid {
+            scroll_on_output: self.scroll_on_output,
+            max_scrollback: self.max_scrollback,
+            generation: self.generation,
+            ..Grid::new(self.cols as u16, self.rows as u16)
+        };
+        self.touch_all();
+    }
+
+    /// DECSTR. Screen contents and cursor position are left alone.
+    fn soft_reset(&mut self) {
+        self.attrs = Attributes::default();
+        self.cursor_visible = true;
+        self.scroll_region_top = 0;
+        self.scroll_region_bottom = self.rows;
+        self.saved_cursor = None;
+    }
+
+    /// Resize the screen, re-wrapping the main screen and scrollback to the
+    /// new width. The alt screen is only truncated/padded; full-screen apps
+    /// redraw it on SIGWINCH anyway.
     pub fn resize(&mut self, cols: u16, rows: u16) {
-        let new_cols = cols as usize;
-        let new_rows = rows as usize;
+        let new_cols = cols.max(MIN_COLS) as usize;
+        let new_rows = rows.max(MIN_ROWS) as usize;
 
-        // Adjust existing rows' width
-        for row in &mut self.cells {
-            row.resize(new_cols, Cell::default());
+        self.blank_row = blank_row(new_cols);
+        if self.in_alt_screen {
+            for row in self.cells.iter_mut().chain(&mut self.scrollback) {
+                *row = fit_row(row, new_cols);
+            }
+            self.cells.resize(new_rows, self.blank_row.clone());
+            if let Some(saved) = self.saved_screen.as_mut() {
+                let cursor = (saved.cursor_row, saved.cursor_col);
+                let (cells, scrollback, cursor) = reflow(
+                    std::mem::take(&mut saved.cells),
+                    std::mem::take(&mut saved.scrollback),
+                    cursor,
+                    self.cols,
+                    new_cols,
+                    new_rows,
+                    self.max_scrollback,
+                );
+                saved.cells = cells;
+                saved.scrollback = scrollback;
+                (saved.cursor_row, saved.cursor_col) = cursor;
+                saved.scroll_offset = saved.scroll_offset.min(saved.scrollback.len());
+            }
+        } else {
+            let (cells, scrollback, cursor) = reflow(
+                std::mem::take(&mut self.cells),
+                std::mem::take(&mut self.scrollback),
+                (self.cursor_row, self.cursor_col),
+                self.cols,
+                new_cols,
+                new_rows,
+                self.max_scrollback,
+            );
+            self.cells = cells;
+            self.scrollback = scrollback;
+            (self.cursor_row, self.cursor_col) = cursor;
+            self.scroll_offset = self.scroll_offset.min(self.scrollback.len());
         }
-        // Adjust row count
-        self.cells.resize(new_rows, vec![Cell::default(); new_cols]);
 
         self.cols = new_cols;
         self.rows = new_rows;
-        self.cursor_row = self.cursor_row.min(self.rows - 1);
-        self.cursor_col = self.cursor_col.min(self.cols - 1);
+        self.cursor_row = self.cursor_row.min(self.last_row());
+        // cursor_col == cols is a pending wrap, as after printing in the last column
+        self.cursor_col = self.cursor_col.min(self.cols);
         // Reset scroll region on resize
         self.scroll_region_top = 0;
         self.scroll_region_bottom = self.rows;
+        self.row_generations.resize(self.rows, 0);
+        self.touch_all();
+    }
+
+    /// Current change counter. Read it together with [`Grid::diff_since`] and
+    /// pass it to the next call to get only what changed in between.
+    pub fn generation(&self) -> u64 {
+        self.generation
+    }
+
+    /// Screen rows changed after `generation`. `diff_since(0)` returns every
+    /// row. A resize or alt-screen switch reports every row, so a mirror
+    /// only needs to resize itself when `cells().len()` or the row width differs.
+    pub fn diff_since(&self, generation: u64) -> Vec<RowUpdate> {
+        self.row_generations
+            .iter()
+            .enumerate()
+            .filter(|(_, changed)| **changed > generation)
+            .map(|(row, _)| RowUpdate { row: row as u16, cells: self.cells[row].clone() })
+            .collect()
+    }
+
+    fn touch_rows(&mut self, rows: std::ops::Range<usize>) {
+        self.generation += 1;
+        let end = rows.end.min(self.row_generations.len());
+        let start = rows.start.min(end);
+        for changed in &mut self.row_generations[start..end] {
+            *changed = self.generation;
+        }
+    }
+
+    fn touch_row(&mut self, row: usize) {
+        self.touch_rows(row..row + 1);
+    }
+
+    fn touch_all(&mut self) {
+        self.touch_rows(0..self.rows);
+    }
+
+    fn last_row(&self) -> usize {
+        self.rows.saturating_sub(1)
+    }
+
+    fn last_col(&self) -> usize {
+        self.cols.saturating_sub(1)
     }
 
     fn print(&mut self, c: char) {
         let width = UnicodeWidthChar::width(c).unwrap_or(1);
 
+        // Combining marks, variation selectors and ZWJ attach to the previous
+        // cell. In mode 2027 whole grapheme clusters share one cell: whatever
+        // follows a ZWJ, the second half of a flag, and VS16 widens the cell.
+        if let Some((row, col)) = self.previous_cell() {
+            let prev = self.cells[row][col];
+            let joins = self.grapheme_clustering
+                && (prev.zerowidth.is_some_and(|z| z.ends_with_joiner())
+                    || (is_regional_indicator(c)
+                        && is_regional_indicator(prev.character)
+                        && prev.zerowidth.is_none()));
+            if width == 0 || joins {
+                let cell = &mut self.row_mut(row)[col];
+                cell.zerowidth = ZeroWidth::push(cell.zerowidth, c);
+                let widen = is_regional_indicator(c) || c == '\u{FE0F}';
+                if self.grapheme_clustering && widen {
+                    self.widen_cell(row, col);
+                }
+                self.touch_row(row);
+                return;
+            }
+        }
+        if width == 0 {
+            // Nothing to attach to (start of line)
+            return;
+        }
+        self.last_printed = Some(c);
+
         if width == 2 {
             // Wide char: need 2 cols. If only 1 remaining, wrap.
-            if self.cursor_col + 1 >= self.cols {
+            // A 1-column grid can never fit it, so don't wrap forever at col 0.
+            if self.cursor_col + 1 >= self.cols && self.cursor_col > 0 {
                 self.wrap_cursor();
             }
         }
@@ -203,43 +626,92 @@ impl Grid {
 
         // Clean up wide char pairs if overwriting
         self.cleanup_overwrite(self.cursor_row, self.cursor_col);
+        if width == 2 && self.cursor_col + 1 < self.cols {
+            // The spacer may land on the first half of another wide char
+            self.cleanup_overwrite(self.cursor_row, self.cursor_col + 1);
+        }
 
         let flags = if width == 2 {
-            self.current_flags | CellFlags::WIDE_CHAR
+            self.attrs.flags | CellFlags::WIDE_CHAR
+        } else if c == ' ' {
+            self.attrs.flags | CellFlags::EXPLICIT_SPACE
         } else {
-            self.current_flags
+            self.attrs.flags
         };
 
-        self.cells[self.cursor_row][self.cursor_col] = Cell {
+        let (row, col) = (self.cursor_row, self.cursor_col);
+        self.row_mut(row)[col] = Cell {
             character: c,
-            fg: self.current_fg,
-            bg: self.current_bg,
+            fg: self.attrs.fg,
+            bg: self.attrs.bg,
             flags,
+            zerowidth: None,
+            underline_style: self.attrs.underline_style,
+            underline_color: self.attrs.underline_color,
         };
         self.cursor_col += 1;
 
         if width == 2 {
             // Place spacer cell
             if self.cursor_col < self.cols {
-                self.cells[self.cursor_row][self.cursor_col] = Cell::default();
+                self.row_mut(row)[col + 1] = Cell::default();
                 self.cursor_col += 1;
+            } else {
+                // 1-column grid: no room for the spacer, keep the cell narrow
+                // so the next print doesn't treat column 1 as its second half
+                self.row_mut(row)[col].flags.remove(CellFlags::WIDE_CHAR);
             }
         }
+        self.touch_row(self.cursor_row);
+    }
+
+    /// Turn the narrow cell left of the cursor into a wide one (emoji
+    /// presentation, flags). Stays narrow if there's no room for the spacer.
+    fn widen_cell(&mut self, row: usize, col: usize) {
+        if self.cells[row][col].flags.contains(CellFlags::WIDE_CHAR)
+            || col + 1 != self.cursor_col
+            || self.cursor_col >= self.cols
+        {
+            return;
+        }
+        self.cleanup_overwrite(row, self.cursor_col);
+        let line = self.row_mut(row);
+        line[col].flags.insert(CellFlags::WIDE_CHAR);
+        line[col + 1] = Cell::default();
+        self.cursor_col += 1;
+    }
+
+    /// Cell the last printed character went into, if it's directly left of
+    /// the cursor (the wide char itself when left of the cursor is a spacer).
+    fn previous_cell(&self) -> Option<(usize, usize)> {
+        if self.cursor_col == 0 {
+            return None;
+        }
+        let row = self.cursor_row;
+        let col = self.cursor_col - 1;
+        if col > 0 && self.cells[row][col - 1].flags.contains(CellFlags::WIDE_CHAR) {
+            return Some((row, col - 1));
+        }
+        Some((row, col))
     }
 
     fn cleanup_overwrite(&mut self, row: usize, col: usize) {
         let cell = self.cells[row][col];
         // Overwriting the first half of a wide char → clear its spacer
         if cell.flags.contains(CellFlags::WIDE_CHAR) && col + 1 < self.cols {
-            self.cells[row][col + 1] = Cell::default();
+            self.row_mut(row)[col + 1] = Cell::default();
         }
         // Overwriting a spacer (second half of wide char) → clear the wide char
         if col > 0 && self.cells[row][col - 1].flags.contains(CellFlags::WIDE_CHAR) {
-            self.cells[row][col - 1] = Cell::default();
+            self.row_mut(row)[col - 1] = Cell::default();
         }
     }
 
     fn wrap_cursor(&mut self) {
+        let last_col = self.last_col();
+        let row = self.cursor_row;
+        self.row_mut(row)[last_col].flags.insert(CellFlags::WRAPLINE);
+        self.touch_row(self.cursor_row);
         self.cursor_col = 0;
         if self.cursor_row + 1 >= self.rows {
             self.scroll_up();
@@ -249,7 +721,7 @@ impl Grid {
     }
 
     fn newline(&mut self) {
-        let bottom = self.scroll_region_bottom - 1; // 0-indexed
+        let bottom = self.scroll_region_bottom.saturating_sub(1); // 0-indexed
         if self.cursor_row == bottom {
             self.scroll_region_up(1);
         } else if self.cursor_row + 1 < self.rows {
@@ -269,11 +741,9 @@ impl Grid {
     fn scroll_up(&mut self) {
         let row = self.cells.remove(0);
         self.scrollback.push(row);
-        if self.scrollback.len() > MAX_SCROLLBACK {
-            self.scrollback.remove(0);
-            self.scroll_offset = self.scroll_offset.min(self.scrollback.len());
-        }
-        self.cells.push(vec![Cell::default(); self.cols]);
+        self.trim_scrollback();
+        self.cells.push(self.blank_row.clone());
+        self.touch_all();
         if self.scroll_offset > 0 {
             self.scroll_offset += 1;
             self.scroll_offset = self.scroll_offset.min(self.scrollback.len());
@@ -285,22 +755,20 @@ impl Grid {
         let bottom = self.scroll_region_bottom; // exclusive
         if top == 0 && bottom == self.rows {
             // Full screen scroll - use scrollback
-            for _ in 0..n {
+            // 화면 높이 이상은 빈 줄만 쌓이므로 rows로 제한 (CSI 65535 S 방지)
+            for _ in 0..(n as usize).min(self.rows) {
                 self.scroll_up();
             }
             return;
         }
-        let n = (n as usize).min(bottom - top);
-        let blank = vec![Cell::default(); self.cols];
+        let n = (n as usize).min(bottom.saturating_sub(top));
+        let blank = self.blank_row.clone();
         for _ in 0..n {
             let removed = self.cells.remove(top);
             if top == 0 || self.in_alt_screen {
                 // Line scrolled off the top of screen (or alt screen) - save to scrollback
                 self.scrollback.push(removed);
-                if self.scrollback.len() > MAX_SCROLLBACK {
-                    self.scrollback.remove(0);
-                    self.scroll_offset = self.scroll_offset.min(self.scrollback.len());
-                }
+                self.trim_scrollback();
                 if self.scroll_offset > 0 {
                     self.scroll_offset += 1;
                     self.scroll_offset = self.scroll_offset.min(self.scrollback.len());
@@ -308,17 +776,19 @@ impl Grid {
             }
             self.cells.insert(bottom - 1, blank.clone());
         }
+        self.touch_rows(top..bottom);
     }
 
     fn scroll_region_down(&mut self, n: u16) {
         let top = self.scroll_region_top;
         let bottom = self.scroll_region_bottom; // exclusive
-        let n = (n as usize).min(bottom - top);
-        let blank = vec![Cell::default(); self.cols];
+        let n = (n as usize).min(bottom.saturating_sub(top));
+        let blank = self.blank_row.clone();
         for _ in 0..n {
             self.cells.remove(bottom - 1);
             self.cells.insert(top, blank.clone());
         }
+        self.touch_rows(top..bottom);
     }
 
     fn set_scroll_region(&mut self, top: u16, bottom: u16) {
@@ -327,8 +797,14 @@ impl Grid {
             self.scroll_region_top = 0;
             self.scroll_region_bottom = self.rows;
         } else {
-            self.scroll_region_top = (top as usize).saturating_sub(1);
-            self.scroll_region_bottom = (bottom as usize).min(self.rows);
+            let top = (top as usize).saturating_sub(1);
+            let bottom = if bottom == 0 { self.rows } else { (bottom as usize).min(self.rows) };
+            // DECSTBM requires top < bottom; ignore invalid regions
+            if top + 1 >= bottom {
+                return;
+            }
+            self.scroll_region_top = top;
+            self.scroll_region_bottom = bottom;
         }
     }
 
@@ -337,18 +813,17 @@ impl Grid {
             cells: self.cells.clone(),
             cursor_row: self.cursor_row,
             cursor_col: self.cursor_col,
-            current_fg: self.current_fg,
-            current_bg: self.current_bg,
-            current_flags: self.current_flags,
+            attrs: self.attrs,
             scrollback: std::mem::take(&mut self.scrollback),
             scroll_offset: self.scroll_offset,
             cursor_visible: self.cursor_visible,
         });
-        self.cells = vec![vec![Cell::default(); self.cols]; self.rows];
+        self.cells = vec![self.blank_row.clone(); self.rows];
         self.cursor_row = 0;
         self.cursor_col = 0;
         self.scroll_offset = 0;
         self.in_alt_screen = true;
+        self.touch_all();
     }
 
     fn leave_alt_screen(&mut self) {
@@ -357,13 +832,21 @@ impl Grid {
             self.cells = saved.cells;
             self.cursor_row = saved.cursor_row;
             self.cursor_col = saved.cursor_col;
-            self.current_fg = saved.current_fg;
-            self.current_bg = saved.current_bg;
-            self.current_flags = saved.current_flags;
+            self.attrs = saved.attrs;
             self.scrollback = saved.scrollback;
             self.scrollback.extend(alt_scrollback);
             self.scroll_offset = saved.scroll_offset;
             self.cursor_visible = saved.cursor_visible;
+            // The window may have been resized while the alt screen was active
+            for row in &mut self.cells {
+                if row.len() != self.cols {
+                    *row = fit_row(row, self.cols);
+                }
+            }
+            self.cells.resize(self.rows, self.blank_row.clone());
+            self.cursor_row = self.cursor_row.min(self.last_row());
+            self.cursor_col = self.cursor_col.min(self.last_col());
+            self.touch_all();
         }
         self.in_alt_screen = false;
     }
@@ -372,24 +855,26 @@ impl Grid {
         let n = n as usize;
         let bottom = self.scroll_region_bottom;
         let row = self.cursor_row;
-        let blank = vec![Cell::default(); self.cols];
-        for _ in 0..n.min(bottom - row) {
+        let blank = self.blank_row.clone();
+        for _ in 0..n.min(bottom.saturating_sub(row)) {
             if bottom <= self.cells.len() {
                 self.cells.remove(bottom - 1);
             }
             self.cells.insert(row, blank.clone());
         }
+        self.touch_rows(row..bottom);
     }
 
     fn delete_lines(&mut self, n: u16) {
         let n = n as usize;
         let bottom = self.scroll_region_bottom;
         let row = self.cursor_row;
-        let blank = vec![Cell::default(); self.cols];
-        for _ in 0..n.min(bottom - row) {
+        let blank = self.blank_row.clone();
+        for _ in 0..n.min(bottom.saturating_sub(row)) {
             self.cells.remove(row);
             self.cells.insert(bottom - 1, blank.clone());
         }
+        self.touch_rows(row..bottom);
     }
 
     fn scroll_up_content(&mut self, n: u16) {
@@ -406,13 +891,15 @@ impl Grid {
         let col = self.cursor_col;
         let blank = self.blank_cell();
         // Shift right from end
-        for i in (col..self.cols).rev() {
+        let line = self.row_mut(row);
+        for i in (col..line.len()).rev() {
             if i >= col + n {
-                self.cells[row][i] = self.cells[row][i - n];
+                line[i] = line[i - n];
             } else {
-                self.cells[row][i] = blank;
+                line[i] = blank;
             }
         }
+        self.touch_row(row);
     }
 
     fn erase_chars(&mut self, n: u16) {
@@ -420,8 +907,18 @@ impl Grid {
         let row = self.cursor_row;
         let col = self.cursor_col;
         let blank = self.blank_cell();
-        for i in col..(col + n).min(self.cols) {
-            self.cells[row][i] = blank;
+        let end = (col + n).min(self.cols);
+        if col < end {
+            self.row_mut(row)[col..end].fill(blank);
+        }
+        self.touch_row(row);
+    }
+
+    fn repeat_char(&mut self, n: u16) {
+        if let Some(c) = self.last_printed {
+            for _ in 0..n {
+                self.print(c);
+            }
         }
     }
 
@@ -437,6 +934,12 @@ impl Grid {
         self.scroll_offset = 0;
     }
 
+    /// When enabled, any applied output snaps the view back to the bottom.
+    /// Otherwise a scrolled-back view stays on the same content.
+    pub fn set_scroll_on_output(&mut self, enabled: bool) {
+        self.scroll_on_output = enabled;
+    }
+
     pub fn scroll_offset(&self) -> usize {
         self.scroll_offset
     }
@@ -449,17 +952,130 @@ impl Grid {
         self.scrollback.len()
     }
 
-    pub fn scrollback(&self) -> &[Vec<Cell>] {
+    #[deprecated(note = "use `scrollback_rows` or `row`")]
+    pub fn scrollback(&self) -> &[Row] {
         &self.scrollback
     }
 
-    pub fn visible_cells(&self) -> std::borrow::Cow<'_, Vec<Vec<Cell>>> {
+    /// Drop the oldest scrollback rows, keeping at most `keep`.
+    pub fn truncate_scrollback(&mut self, keep: usize) {
+        cap_scrollback(&mut self.scrollback, Some(keep));
+        self.scroll_offset = self.scroll_offset.min(self.scrollback.len());
+    }
+
+    /// Change how many scrollback rows are kept (`None` for unlimited).
+    /// Rows already over the new limit are dropped right away.
+    pub fn set_max_scrollback(&mut self, max: Option<usize>) {
+        self.max_scrollback = max;
+        self.trim_scrollback();
+        if let Some(saved) = self.saved_screen.as_mut() {
+            cap_scrollback(&mut saved.scrollback, max);
+            saved.scroll_offset = saved.scroll_offset.min(saved.scrollback.len());
+        }
+    }
+
+    pub fn max_scrollback(&self) -> Option<usize> {
+        self.max_scrollback
+    }
+
+    /// ED 3 and Cmd+K: forget all saved lines, including the main screen's
+    /// while the alt screen is active.
+    pub fn clear_scrollback(&mut self) {
+        self.scrollback.clear();
+        self.scroll_offset = 0;
+        if let Some(saved) = self.saved_screen.as_mut() {
+            saved.scrollback.clear();
+            saved.scroll_offset = 0;
+        }
+        self.touch_all();
+    }
+
+    /// Cmd+K: clear scrollback and screen, keeping the cursor's line at the
+    /// top so the shell prompt stays visible without redrawing it.
+    pub fn clear_scrollback_and_screen(&mut self) {
+        self.clear_scrollback();
+        let line = std::mem::replace(&mut self.cells[self.cursor_row], self.blank_row.clone());
+        for row in &mut self.cells {
+            *row = self.blank_row.clone();
+        }
+        self.cells[0] = line;
+        self.cursor_row = 0;
+        self.touch_all();
+    }
+
+    fn trim_scrollback(&mut self) {
+        cap_scrollback(&mut self.scrollback, self.max_scrollback);
+        self.scroll_offset = self.scroll_offset.min(self.scrollback.len());
+    }
+
+    pub fn snapshot(&self) -> GridSnapshot {
+        GridSnapshot {
+            cols: self.cols as u16,
+            rows: self.rows as u16,
+            cells: to_vecs(&self.cells),
+            scrollback: to_vecs(&self.scrollback),
+            cursor: (self.cursor_row as u16, self.cursor_col as u16),
+            cursor_visible: self.cursor_visible,
+            attrs: self.attrs,
+            scroll_region: (self.scroll_region_top as u16, self.scroll_region_bottom as u16),
+            saved_cursor: self.saved_cursor.map(|(r, c)| (r as u16, c as u16)),
+            grapheme_clustering: self.grapheme_clustering,
+            main_screen: self.saved_screen.as_ref().map(|saved| ScreenSnapshot {
+                cells: to_vecs(&saved.cells),
+                scrollback: to_vecs(&saved.scrollback),
+                cursor: (saved.cursor_row as u16, saved.cursor_col as u16),
+                cursor_visible: saved.cursor_visible,
+                attrs: saved.attrs,
+            }),
+        }
+    }
+
+    /// Rebuild a grid from a snapshot. Rows are padded or cut to the
+    /// snapshot's size and positions clamped, so a hand-edited or truncated
+    /// snapshot still gives a consistent grid.
+    pub fn restore(snapshot: &GridSnapshot) -> Self {
+        let mut grid = Grid::new(snapshot.cols, snapshot.rows);
+        let (cols, rows) = (grid.cols, grid.rows);
+        grid.cells = fit_screen(&snapshot.cells, cols, rows);
+        grid.scrollback = fit_scrollback(&snapshot.scrollback, cols, grid.max_scrollback);
+        grid.cursor_row = (snapshot.cursor.0 as usize).min(rows - 1);
+        grid.cursor_col = (snapshot.cursor.1 as usize).min(cols);
+        grid.cursor_visible = snapshot.cursor_visible;
+        grid.attrs = snapshot.attrs;
+        let (top, bottom) = (snapshot.scroll_region.0 as usize, snapshot.scroll_region.1 as usize);
+        if top < bottom && bottom <= rows {
+            grid.scroll_region_top = top;
+            grid.scroll_region_bottom = bottom;
+        }
+        grid.saved_cursor = snapshot
+            .saved_cursor
+            .map(|(r, c)| ((r as usize).min(rows - 1), (c as usize).min(cols - 1)));
+        grid.grapheme_clustering = snapshot.grapheme_clustering;
+        if let Some(main) = &snapshot.main_screen {
+            grid.saved_screen = Some(SavedScreen {
+                cells: fit_screen(&main.cells, cols, rows),
+                cursor_row: (main.cursor.0 as usize).min(rows - 1),
+                cursor_col: (main.cursor.1 as usize).min(cols),
+                attrs: main.attrs,
+                scrollback: fit_scrollback(&main.scrollback, cols, grid.max_scrollback),
+                scroll_offset: 0,
+                cursor_visible: main.cursor_visible,
+            });
+            grid.in_alt_screen = true;
+        }
+        grid
+    }
+
+    /// Rows in view. Scrolled back, the result shares its rows with the
+    /// scrollback and screen instead of copying cells.
+    #[deprecated(note = "use `visible_rows`")]
+    pub fn visible_cells(&self) -> std::borrow::Cow<'_, Vec<Row>> {
         if self.scroll_offset == 0 {
             return std::borrow::Cow::Borrowed(&self.cells);
         }
         let sb_len = self.scrollback.len();
         let sb_start = sb_len.saturating_sub(self.scroll_offset);
-        let mut result: Vec<Vec<Cell>> = self.scrollback[sb_start..].to_vec();
+        let mut result: Vec<Row> = self.scrollback[sb_start..].to_vec();
         let screen_rows_needed = self.rows - result.len().min(self.rows);
         result.extend_from_slice(&self.cells[..screen_rows_needed]);
         result.truncate(self.rows);
@@ -470,8 +1086,11 @@ impl Grid {
         Cell {
             character: ' ',
             fg: Color::Default,
-            bg: self.current_bg,
+            bg: self.attrs.bg,
             flags: CellFlags::empty(),
+            zerowidth: None,
+            underline_style: UnderlineStyle::Single,
+            underline_color: Color::Default,
         }
     }
 
@@ -480,13 +1099,15 @@ impl Grid {
         let row = self.cursor_row;
         let col = self.cursor_col;
         let blank = self.blank_cell();
-        for i in col..self.cols {
-            if i + n < self.cols {
-                self.cells[row][i] = self.cells[row][i + n];
+        let line = self.row_mut(row);
+        for i in col..line.len() {
+            if i + n < line.len() {
+                line[i] = line[i + n];
             } else {
-                self.cells[row][i] = blank;
+                line[i] = blank;
             }
         }
+        self.touch_row(row);
     }
 
     fn erase_in_line(&mut self, mode: u16) {
@@ -494,22 +1115,18 @@ impl Grid {
         let blank = self.blank_cell();
         match mode {
             0 => {
-                for col in self.cursor_col..self.cols {
-                    self.cells[row][col] = blank;
-                }
+                let start = self.cursor_col.min(self.cols);
+                self.row_mut(row)[start..].fill(blank);
             }
             1 => {
-                for col in 0..=self.cursor_col {
-                    self.cells[row][col] = blank;
-                }
+                // pending wrap 상태(cursor_col == cols)에서도 마지막 열까지만
+                let end = self.cursor_col.min(self.last_col());
+                self.row_mut(row)[..=end].fill(blank);
             }
-            2 => {
-                for col in 0..self.cols {
-                    self.cells[row][col] = blank;
-                }
-            }
-            _ => {}
+            2 => self.cells[row] = blank_row_with(blank, self.cols),
+            _ => return,
         }
+        self.touch_row(row);
     }
 
     fn erase_in_display(&mut self, mode: u16) {
@@ -518,33 +1135,193 @@ impl Grid {
             0 => {
                 // Erase from cursor to end
                 self.erase_in_line(0);
+                let line = blank_row_with(blank, self.cols);
                 for row in (self.cursor_row + 1)..self.rows {
-                    for col in 0..self.cols {
-                        self.cells[row][col] = blank;
-                    }
+                    self.cells[row] = line.clone();
                 }
+                self.touch_rows(self.cursor_row + 1..self.rows);
             }
             1 => {
                 // Erase from start to cursor
+                let line = blank_row_with(blank, self.cols);
                 for row in 0..self.cursor_row {
-                    for col in 0..self.cols {
-                        self.cells[row][col] = blank;
-                    }
+                    self.cells[row] = line.clone();
                 }
+                self.touch_rows(0..self.cursor_row);
                 self.erase_in_line(1);
             }
             2 => {
-                for row in 0..self.rows {
-                    for col in 0..self.cols {
-                        self.cells[row][col] = blank;
-                    }
-                }
+                self.cells.fill(blank_row_with(blank, self.cols));
+                self.touch_all();
             }
+            3 => self.clear_scrollback(),
             _ => {}
         }
     }
 }
 
+// --- Reflow ---
+
+fn is_wrapped(row: &[Cell]) -> bool {
+    row.last().is_some_and(|c| c.flags.contains(CellFlags::WRAPLINE))
+}
+
+/// Re-wrap scrollback + screen from `old_cols` to `cols` columns.
+/// Returns the new screen (`rows` rows), scrollback and cursor position.
+///
+/// Rows joined by `WRAPLINE` form one logical line. Trailing blank cells are
+/// dropped before re-wrapping; a cursor past the end of its line keeps its
+/// column on the line's last row. Rows move into scrollback from the top only
+/// when the screen (minus blank rows below the cursor) no longer fits.
+fn reflow(
+    screen: Vec<Row>,
+    scrollback: Vec<Row>,
+    cursor: (usize, usize),
+    old_cols: usize,
+    cols: usize,
+    rows: usize,
+    max_scrollback: Option<usize>,
+) -> (Vec<Row>, Vec<Row>, (usize, usize)) {
+    let scrollback_len = scrollback.len();
+    let cursor_abs = scrollback_len + cursor.0;
+    let pending_wrap = cursor.1 >= old_cols;
+
+    // 1. Unwrap into logical lines, remembering where the cursor and the
+    //    first screen row ended up
+    let mut lines: Vec<Vec<Cell>> = Vec::new();
+    let mut cursor_logical = (0, 0);
+    let mut first_screen_line = 0;
+    let mut current: Vec<Cell> = Vec::new();
+    for (abs, row) in scrollback.into_iter().chain(screen).enumerate() {
+        let mut row = row.to_vec();
+        if abs == scrollback_len {
+            first_screen_line = lines.len();
+        }
+        let wrapped = is_wrapped(&row);
+        if let Some(last) = row.last_mut() {
+            last.flags.remove(CellFlags::WRAPLINE);
+        }
+        if abs == cursor_abs {
+            cursor_logical = (lines.len(), current.len() + cursor.1.min(row.len()));
+        }
+        // A wide char that didn't fit left a blank in the last column
+        // (a wide char's own spacer is blank too, so check the cell before)
+        let n = row.len();
+        if wrapped
+            && row[n - 1] == Cell::default()
+            && (n < 2 || !row[n - 2].flags.contains(CellFlags::WIDE_CHAR))
+        {
+            row.pop();
+        }
+        current.extend(row);
+        if !wrapped {
+            lines.push(std::mem::take(&mut current));
+        }
+    }
+    if !current.is_empty() {
+        lines.push(current);
+    }
+
+    // 2. Re-wrap each logical line
+    let mut out: Vec<Vec<Cell>> = Vec::new();
+    let mut new_cursor = (0, 0);
+    let mut first_screen_row = 0;
+    for (index, mut line) in lines.into_iter().enumerate() {
+        if index == first_screen_line {
+            first_screen_row = out.len();
+        }
+        let content_len = line.iter().rposition(|c| *c != Cell::default()).map_or(0, |i| i + 1);
+        line.truncate(content_len);
+        let first_row = out.len();
+        let is_cursor_line = index == cursor_logical.0;
+        let mut row: Vec<Cell> = Vec::with_capacity(cols);
+        let mut i = 0;
+        while i < line.len() {
+            let mut cell = line[i];
+            let wide = cell.flags.contains(CellFlags::WIDE_CHAR);
+            let width = if wide && cols > 1 { 2 } else { 1 };
+            if row.len() + width > cols {
+                row.resize(cols, Cell::default());
+                row[cols - 1].flags.insert(CellFlags::WRAPLINE);
+                out.push(std::mem::replace(&mut row, Vec::with_capacity(cols)));
+            }
+            if is_cursor_line && i == cursor_logical.1 {
+                new_cursor = (out.len(), row.len());
+            } else if is_cursor_line && wide && i + 1 == cursor_logical.1 {
+                new_cursor = (out.len(), row.len() + width - 1);
+            }
+            if width == 1 {
+                cell.flags.remove(CellFlags::WIDE_CHAR);
+            }
+            row.push(cell);
+            if width == 2 {
+                row.push(line.get(i + 1).copied().unwrap_or_default());
+            }
+            i += if wide { 2 } else { 1 };
+        }
+        if is_cursor_line && cursor_logical.1 >= line.len() {
+            // Cursor at or past the end of the text: stays on the last row
+            let offset = cursor_logical.1.saturating_sub((out.len() - first_row) * cols);
+            let max_col = if pending_wrap || row.len() == cols { cols } else { cols - 1 };
+            new_cursor = (out.len(), offset.max(row.len()).min(max_col));
+        }
+        row.resize(cols, Cell::default());
+        out.push(row);
+    }
+
+    // 3. Split into scrollback and screen
+    let mut end = out.len();
+    while end > first_screen_row + rows
+        && end > new_cursor.0 + 1
+        && out[end - 1].iter().all(|c| *c == Cell::default())
+    {
+        end -= 1;
+    }
+    out.truncate(end);
+    let screen_start = first_screen_row
+        .max(out.len().saturating_sub(rows))
+        .min(new_cursor.0);
+    let mut screen: Vec<Row> = out.split_off(screen_start).into_iter().map(Row::from).collect();
+    screen.resize(rows, blank_row(cols));
+    let mut scrollback: Vec<Row> = out.into_iter().map(Row::from).collect();
+    cap_scrollback(&mut scrollback, max_scrollback);
+    (screen, scrollback, (new_cursor.0 - screen_start, new_cursor.1))
+}
+
+fn fit_row(row: &[Cell], cols: usize) -> Row {
+    let mut row = row.to_vec();
+    row.resize(cols, Cell::default());
+    row.into()
+}
+
+fn blank_row_with(blank: Cell, cols: usize) -> Row {
+    vec![blank; cols].into()
+}
+
+fn to_vecs(rows: &[Row]) -> Vec<Vec<Cell>> {
+    rows.iter().map(|row| row.to_vec()).collect()
+}
+
+fn fit_screen(cells: &[Vec<Cell>], cols: usize, rows: usize) -> Vec<Row> {
+    let mut screen: Vec<Row> = cells.iter().take(rows).map(|row| fit_row(row, cols)).collect();
+    screen.resize(rows, blank_row(cols));
+    screen
+}
+
+fn fit_scrollback(scrollback: &[Vec<Cell>], cols: usize, max: Option<usize>) -> Vec<Row> {
+    let start = max.map_or(0, |max| scrollback.len().saturating_sub(max));
+    scrollback[start..].iter().map(|row| fit_row(row, cols)).collect()
+}
+
+/// Drop the oldest rows so at most `max` remain.
+fn cap_scrollback(scrollback: &mut Vec<Row>, max: Option<usize>) {
+    if let Some(max) = max {
+        if scrollback.len() > max {
+            scrollback.drain(..scrollback.len() - max);
+        }
+    }
+}
+
 #[cfg(test)]
 #[path = "grid_tests.rs"]
 mod grid_tests;
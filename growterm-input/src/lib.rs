@@ -1,4 +1,18 @@
-use growterm_types::{Key, KeyEvent, Modifiers};
+use growterm_types::{Key, KeyEvent, KeypadKey, Modifiers};
+
+/// Kitty keyboard protocol progressive enhancement flags (`CSI > flags u`).
+pub const KITTY_DISAMBIGUATE: u16 = 0b1;
+pub const KITTY_REPORT_EVENT_TYPES: u16 = 0b10;
+pub const KITTY_REPORT_ALTERNATE_KEYS: u16 = 0b100;
+pub const KITTY_REPORT_ALL_KEYS: u16 = 0b1000;
+pub const KITTY_REPORT_TEXT: u16 = 0b10000;
+/// All flags this encoder honors; pushed flags are masked to these and the
+/// `CSI ? u` query reports only them.
+pub const KITTY_SUPPORTED_FLAGS: u16 = KITTY_DISAMBIGUATE
+    | KITTY_REPORT_EVENT_TYPES
+    | KITTY_REPORT_ALTERNATE_KEYS
+    | KITTY_REPORT_ALL_KEYS
+    | KITTY_REPORT_TEXT;
 
 /// Convert a KeyEvent to the byte sequence a terminal PTY expects.
 pub fn encode(event: KeyEvent) -> Vec<u8> {
@@ -42,6 +56,154 @@ pub fn encode(event: KeyEvent) -> Vec<u8> {
         Key::End => encode_cursor(b'F', has_shift, has_alt, has_ctrl),
         Key::PageUp => encode_tilde(5, has_shift, has_alt, has_ctrl),
         Key::PageDown => encode_tilde(6, has_shift, has_alt, has_ctrl),
+        Key::Insert => encode_tilde(2, has_shift, has_alt, has_ctrl),
+        Key::F(n @ 1..=4) => encode_ss3(b'P' + n - 1, has_shift, has_alt, has_ctrl),
+        Key::F(n) => match function_key_tilde(n) {
+            Some(code) => encode_tilde(code, has_shift, has_alt, has_ctrl),
+            None => Vec::new(),
+        },
+        // Numeric keypad mode: keypad keys send the same bytes as the main keys
+        Key::Keypad(KeypadKey::Enter) => encode(KeyEvent { key: Key::Enter, ..event }),
+        Key::Keypad(k) => encode(KeyEvent { key: Key::Char(k.as_char()), ..event }),
+    }
+}
+
+/// Encode a key press honoring the active kitty keyboard flags.
+/// With no flags set this is identical to `encode`.
+pub fn encode_with_flags(event: KeyEvent, kitty_flags: u16) -> Vec<u8> {
+    encode_kitty(event, kitty_flags, false).unwrap_or_else(|| encode(event))
+}
+
+/// Encode a key release. Only reported when the application requested
+/// event types; keys that still use legacy bytes have no release event.
+pub fn encode_release(event: KeyEvent, kitty_flags: u16) -> Option<Vec<u8>> {
+    if kitty_flags & KITTY_REPORT_EVENT_TYPES == 0 {
+        return None;
+    }
+    encode_kitty(event, kitty_flags, true)
+}
+
+/// CSI-u encoding per the kitty keyboard protocol. Returns None when the key
+/// keeps its legacy encoding under the given flags.
+fn encode_kitty(event: KeyEvent, flags: u16, release: bool) -> Option<Vec<u8>> {
+    if flags & (KITTY_DISAMBIGUATE | KITTY_REPORT_ALL_KEYS) == 0 {
+        return None;
+    }
+    let all_keys = flags & KITTY_REPORT_ALL_KEYS != 0;
+    let has_alt = event.modifiers.contains(Modifiers::ALT);
+    let has_ctrl = event.modifiers.contains(Modifiers::CTRL);
+    let has_shift = event.modifiers.contains(Modifiers::SHIFT);
+    let m = modifier_param(has_shift, has_alt, has_ctrl).unwrap_or(1);
+    let event_type = if release { ":3" } else { "" };
+
+    let seq = match event.key {
+        Key::Char(c) => {
+            // Text-producing keys stay plain text unless all keys are reported
+            if !all_keys && !has_alt && !has_ctrl {
+                return None;
+            }
+            let code = c.to_lowercase().next().unwrap_or(c);
+            // 시프트로 바뀐 글자는 alternate key로 (CSI 97:65;2u)
+            let shifted = (flags & KITTY_REPORT_ALTERNATE_KEYS != 0 && has_shift && code != c).then_some(c as u32);
+            // 텍스트는 누를 때만, 모든 키를 보고할 때만 실림
+            let text = (flags & KITTY_REPORT_TEXT != 0 && all_keys && !release && !has_ctrl && !has_alt && !c.is_control())
+                .then_some(c as u32);
+            csi_u_with(code as u32, shifted, m, event_type, text)
+        }
+        Key::Enter | Key::Tab | Key::Backspace => {
+            if !all_keys && event.modifiers.is_empty() {
+                return None;
+            }
+            let code = match event.key {
+                Key::Enter => 13,
+                Key::Tab => 9,
+                _ => 127,
+            };
+            csi_u(code, m, event_type)
+        }
+        Key::Escape => csi_u(27, m, event_type),
+        Key::ArrowUp => csi_letter(b'A', m, event_type),
+        Key::ArrowDown => csi_letter(b'B', m, event_type),
+        Key::ArrowRight => csi_letter(b'C', m, event_type),
+        Key::ArrowLeft => csi_letter(b'D', m, event_type),
+        Key::Home => csi_letter(b'H', m, event_type),
+        Key::End => csi_letter(b'F', m, event_type),
+        Key::Delete => csi_tilde(3, m, event_type),
+        Key::PageUp => csi_tilde(5, m, event_type),
+        Key::PageDown => csi_tilde(6, m, event_type),
+        Key::Insert => csi_tilde(2, m, event_type),
+        Key::F(n @ 1..=4) => csi_letter(b'P' + n - 1, m, event_type),
+        Key::F(n) => csi_tilde(function_key_tilde(n)?, m, event_type),
+        Key::Keypad(k) => csi_u(kitty_keypad_code(k), m, event_type),
+    };
+    Some(seq)
+}
+
+/// CSI {n}~ number for F5–F12 (F1–F4 use SS3 P–S).
+fn function_key_tilde(n: u8) -> Option<u8> {
+    match n {
+        5 => Some(15),
+        6 => Some(17),
+        7 => Some(18),
+        8 => Some(19),
+        9 => Some(20),
+        10 => Some(21),
+        11 => Some(23),
+        12 => Some(24),
+        _ => None,
+    }
+}
+
+/// Kitty functional key codes for the keypad (KP_0 = 57399 ...).
+fn kitty_keypad_code(key: KeypadKey) -> u32 {
+    match key {
+        KeypadKey::Digit(d) => 57399 + d.min(9) as u32,
+        KeypadKey::Decimal => 57409,
+        KeypadKey::Divide => 57410,
+        KeypadKey::Multiply => 57411,
+        KeypadKey::Minus => 57412,
+        KeypadKey::Plus => 57413,
+        KeypadKey::Enter => 57414,
+        KeypadKey::Equals => 57415,
+    }
+}
+
+fn csi_u(code: u32, m: u8, event_type: &str) -> Vec<u8> {
+    csi_u_with(code, None, m, event_type, None)
+}
+
+/// `CSI code[:shifted] ; m[:event] ; text u`, dropping trailing default fields.
+fn csi_u_with(code: u32, shifted: Option<u32>, m: u8, event_type: &str, text: Option<u32>) -> Vec<u8> {
+    let mut seq = format!("\x1b[{code}");
+    if let Some(shifted) = shifted {
+        seq.push_str(&format!(":{shifted}"));
+    }
+    if m != 1 || !event_type.is_empty() {
+        seq.push_str(&format!(";{m}{event_type}"));
+    } else if text.is_some() {
+        seq.push(';');
+    }
+    if let Some(text) = text {
+        seq.push_str(&format!(";{text}"));
+    }
+    seq.push('u');
+    seq.into_bytes()
+}
+
+fn csi_letter(letter: u8, m: u8, event_type: &str) -> Vec<u8> {
+    let letter = letter as char;
+    if m == 1 && event_type.is_empty() {
+        format!("\x1b[{letter}").into_bytes()
+    } else {
+        format!("\x1b[1;{m}{event_type}{letter}").into_bytes()
+    }
+}
+
+fn csi_tilde(n: u8, m: u8, event_type: &str) -> Vec<u8> {
+    if m == 1 && event_type.is_empty() {
+        format!("\x1b[{n}~").into_bytes()
+    } else {
+        format!("\x1b[{n};{m}{event_type}~").into_bytes()
     }
 }
 
@@ -67,15 +229,16 @@ fn encode_cursor(letter: u8, shift: bool, alt: bool, ctrl: bool) -> Vec<u8> {
 /// Encode tilde-style sequences: \x1b[{n}~ or \x1b[{n};{mod}~
 fn encode_tilde(n: u8, shift: bool, alt: bool, ctrl: bool) -> Vec<u8> {
     match modifier_param(shift, alt, ctrl) {
-        Some(m) => {
-            let mut v = vec![0x1b, b'['];
-            v.push(b'0' + n);
-            v.push(b';');
-            v.push(b'0' + m);
-            v.push(b'~');
-            v
-        }
-        None => vec![0x1b, b'[', b'0' + n, b'~'],
+        Some(m) => format!("\x1b[{n};{m}~").into_bytes(),
+        None => format!("\x1b[{n}~").into_bytes(),
+    }
+}
+
+/// Encode SS3 sequences (F1–F4): \x1bO{letter}, or \x1b[1;{mod}{letter} with modifiers
+fn encode_ss3(letter: u8, shift: bool, alt: bool, ctrl: bool) -> Vec<u8> {
+    match modifier_param(shift, alt, ctrl) {
+        Some(_) => encode_cursor(letter, shift, alt, ctrl),
+        None => vec![0x1b, b'O', letter],
     }
 }
 
@@ -315,4 +478,224 @@ mod tests {
         let event = KeyEvent { key: Key::Char('1'), modifiers: Modifiers::CTRL };
         assert_eq!(encode(event), b"1");
     }
+
+    // --- Kitty keyboard protocol ---
+
+    #[test]
+    fn kitty_no_flags_matches_legacy() {
+        let event = KeyEvent { key: Key::Char('a'), modifiers: Modifiers::CTRL };
+        assert_eq!(encode_with_flags(event, 0), encode(event));
+    }
+
+    #[test]
+    fn kitty_disambiguate_escape_is_csi_27u() {
+        let event = KeyEvent { key: Key::Escape, modifiers: Modifiers::empty() };
+        assert_eq!(encode_with_flags(event, KITTY_DISAMBIGUATE), b"\x1b[27u");
+    }
+
+    #[test]
+    fn kitty_disambiguate_plain_text_stays_text() {
+        let event = KeyEvent { key: Key::Char('A'), modifiers: Modifiers::SHIFT };
+        assert_eq!(encode_with_flags(event, KITTY_DISAMBIGUATE), b"A");
+    }
+
+    #[test]
+    fn kitty_disambiguate_ctrl_char() {
+        let event = KeyEvent { key: Key::Char('c'), modifiers: Modifiers::CTRL };
+        assert_eq!(encode_with_flags(event, KITTY_DISAMBIGUATE), b"\x1b[99;5u");
+    }
+
+    #[test]
+    fn kitty_ctrl_shift_char_uses_lowercase_code() {
+        let event = KeyEvent { key: Key::Char('A'), modifiers: Modifiers::CTRL | Modifiers::SHIFT };
+        assert_eq!(encode_with_flags(event, KITTY_DISAMBIGUATE), b"\x1b[97;6u");
+    }
+
+    #[test]
+    fn kitty_alt_char() {
+        let event = KeyEvent { key: Key::Char('x'), modifiers: Modifiers::ALT };
+        assert_eq!(encode_with_flags(event, KITTY_DISAMBIGUATE), b"\x1b[120;3u");
+    }
+
+    #[test]
+    fn kitty_disambiguate_plain_enter_stays_legacy() {
+        let event = KeyEvent { key: Key::Enter, modifiers: Modifiers::empty() };
+        assert_eq!(encode_with_flags(event, KITTY_DISAMBIGUATE), b"\r");
+    }
+
+    #[test]
+    fn kitty_ctrl_enter() {
+        let event = KeyEvent { key: Key::Enter, modifiers: Modifiers::CTRL };
+        assert_eq!(encode_with_flags(event, KITTY_DISAMBIGUATE), b"\x1b[13;5u");
+    }
+
+    #[test]
+    fn kitty_report_all_keys_encodes_plain_text() {
+        let event = KeyEvent { key: Key::Char('a'), modifiers: Modifiers::empty() };
+        assert_eq!(encode_with_flags(event, KITTY_REPORT_ALL_KEYS), b"\x1b[97u");
+        let event = KeyEvent { key: Key::Enter, modifiers: Modifiers::empty() };
+        assert_eq!(encode_with_flags(event, KITTY_REPORT_ALL_KEYS), b"\x1b[13u");
+    }
+
+    #[test]
+    fn kitty_arrow_keys_keep_letter_form() {
+        let event = KeyEvent { key: Key::ArrowUp, modifiers: Modifiers::empty() };
+        assert_eq!(encode_with_flags(event, KITTY_DISAMBIGUATE), b"\x1b[A");
+        let event = KeyEvent { key: Key::PageUp, modifiers: Modifiers::CTRL };
+        assert_eq!(encode_with_flags(event, KITTY_DISAMBIGUATE), b"\x1b[5;5~");
+    }
+
+    #[test]
+    fn kitty_release_requires_event_types_flag() {
+        let event = KeyEvent { key: Key::Escape, modifiers: Modifiers::empty() };
+        assert_eq!(encode_release(event, KITTY_DISAMBIGUATE), None);
+        assert_eq!(
+            encode_release(event, KITTY_DISAMBIGUATE | KITTY_REPORT_EVENT_TYPES),
+            Some(b"\x1b[27;1:3u".to_vec())
+        );
+    }
+
+    #[test]
+    fn kitty_release_of_modified_arrow() {
+        let event = KeyEvent { key: Key::ArrowLeft, modifiers: Modifiers::SHIFT };
+        assert_eq!(
+            encode_release(event, KITTY_DISAMBIGUATE | KITTY_REPORT_EVENT_TYPES),
+            Some(b"\x1b[1;2:3D".to_vec())
+        );
+    }
+
+    #[test]
+    fn kitty_release_skipped_for_text_keys_without_all_keys() {
+        let event = KeyEvent { key: Key::Char('a'), modifiers: Modifiers::empty() };
+        assert_eq!(encode_release(event, KITTY_DISAMBIGUATE | KITTY_REPORT_EVENT_TYPES), None);
+    }
+
+    #[test]
+    fn kitty_alternate_keys_report_shifted_key() {
+        // 스펙 예: shift+a → CSI 97:65;2u, ctrl+shift+a → CSI 97:65;6u
+        let flags = KITTY_REPORT_ALL_KEYS | KITTY_REPORT_ALTERNATE_KEYS;
+        let event = KeyEvent { key: Key::Char('A'), modifiers: Modifiers::SHIFT };
+        assert_eq!(encode_with_flags(event, flags), b"\x1b[97:65;2u");
+        let event = KeyEvent { key: Key::Char('A'), modifiers: Modifiers::CTRL | Modifiers::SHIFT };
+        assert_eq!(
+            encode_with_flags(event, KITTY_DISAMBIGUATE | KITTY_REPORT_ALTERNATE_KEYS),
+            b"\x1b[97:65;6u"
+        );
+        // alternate 플래그가 없으면 기본 키만
+        assert_eq!(encode_with_flags(event, KITTY_DISAMBIGUATE), b"\x1b[97;6u");
+    }
+
+    #[test]
+    fn kitty_alternate_keys_skip_unshifted_keys() {
+        let event = KeyEvent { key: Key::Char('a'), modifiers: Modifiers::empty() };
+        assert_eq!(encode_with_flags(event, KITTY_REPORT_ALL_KEYS | KITTY_REPORT_ALTERNATE_KEYS), b"\x1b[97u");
+    }
+
+    #[test]
+    fn kitty_report_text_appends_codepoints() {
+        // 스펙 예: a → CSI 97;;97u, shift+a → CSI 97:65;2;65u
+        let event = KeyEvent { key: Key::Char('a'), modifiers: Modifiers::empty() };
+        assert_eq!(encode_with_flags(event, KITTY_REPORT_ALL_KEYS | KITTY_REPORT_TEXT), b"\x1b[97;;97u");
+        let event = KeyEvent { key: Key::Char('A'), modifiers: Modifiers::SHIFT };
+        assert_eq!(
+            encode_with_flags(event, KITTY_REPORT_ALL_KEYS | KITTY_REPORT_ALTERNATE_KEYS | KITTY_REPORT_TEXT),
+            b"\x1b[97:65;2;65u"
+        );
+    }
+
+    #[test]
+    fn kitty_report_text_needs_all_keys_and_no_ctrl() {
+        let event = KeyEvent { key: Key::Char('a'), modifiers: Modifiers::empty() };
+        assert_eq!(encode_with_flags(event, KITTY_DISAMBIGUATE | KITTY_REPORT_TEXT), b"a");
+        let event = KeyEvent { key: Key::Char('a'), modifiers: Modifiers::CTRL };
+        assert_eq!(encode_with_flags(event, KITTY_REPORT_ALL_KEYS | KITTY_REPORT_TEXT), b"\x1b[97;5u");
+    }
+
+    #[test]
+    fn kitty_release_of_text_key_with_all_keys() {
+        // 해제에는 텍스트가 실리지 않음: CSI 97;1:3u
+        let event = KeyEvent { key: Key::Char('a'), modifiers: Modifiers::empty() };
+        let flags = KITTY_REPORT_ALL_KEYS | KITTY_REPORT_EVENT_TYPES | KITTY_REPORT_TEXT;
+        assert_eq!(encode_release(event, flags), Some(b"\x1b[97;1:3u".to_vec()));
+        let event = KeyEvent { key: Key::Enter, modifiers: Modifiers::empty() };
+        assert_eq!(encode_release(event, flags), Some(b"\x1b[13;1:3u".to_vec()));
+    }
+
+    #[test]
+    fn kitty_release_skips_legacy_enter_without_all_keys() {
+        let event = KeyEvent { key: Key::Enter, modifiers: Modifiers::empty() };
+        assert_eq!(encode_release(event, KITTY_DISAMBIGUATE | KITTY_REPORT_EVENT_TYPES), None);
+    }
+
+    // --- Function keys / Insert / keypad ---
+
+    #[test]
+    fn f1_to_f4_use_ss3() {
+        let event = KeyEvent { key: Key::F(1), modifiers: Modifiers::empty() };
+        assert_eq!(encode(event), b"\x1bOP");
+        let event = KeyEvent { key: Key::F(4), modifiers: Modifiers::empty() };
+        assert_eq!(encode(event), b"\x1bOS");
+    }
+
+    #[test]
+    fn f2_with_shift_uses_csi() {
+        let event = KeyEvent { key: Key::F(2), modifiers: Modifiers::SHIFT };
+        assert_eq!(encode(event), b"\x1b[1;2Q");
+    }
+
+    #[test]
+    fn f5_to_f12_use_tilde() {
+        let expected: [&[u8]; 8] = [
+            b"\x1b[15~", b"\x1b[17~", b"\x1b[18~", b"\x1b[19~",
+            b"\x1b[20~", b"\x1b[21~", b"\x1b[23~", b"\x1b[24~",
+        ];
+        for (i, bytes) in expected.iter().enumerate() {
+            let event = KeyEvent { key: Key::F(5 + i as u8), modifiers: Modifiers::empty() };
+            assert_eq!(encode(event), *bytes);
+        }
+    }
+
+    #[test]
+    fn f12_with_ctrl() {
+        let event = KeyEvent { key: Key::F(12), modifiers: Modifiers::CTRL };
+        assert_eq!(encode(event), b"\x1b[24;5~");
+    }
+
+    #[test]
+    fn unknown_function_key_is_empty() {
+        let event = KeyEvent { key: Key::F(20), modifiers: Modifiers::empty() };
+        assert!(encode(event).is_empty());
+    }
+
+    #[test]
+    fn insert_key() {
+        let event = KeyEvent { key: Key::Insert, modifiers: Modifiers::empty() };
+        assert_eq!(encode(event), b"\x1b[2~");
+    }
+
+    #[test]
+    fn keypad_keys_send_their_characters() {
+        let event = KeyEvent { key: Key::Keypad(KeypadKey::Digit(5)), modifiers: Modifiers::empty() };
+        assert_eq!(encode(event), b"5");
+        let event = KeyEvent { key: Key::Keypad(KeypadKey::Plus), modifiers: Modifiers::empty() };
+        assert_eq!(encode(event), b"+");
+        let event = KeyEvent { key: Key::Keypad(KeypadKey::Enter), modifiers: Modifiers::empty() };
+        assert_eq!(encode(event), b"\r");
+    }
+
+    #[test]
+    fn kitty_keypad_uses_dedicated_codes() {
+        let event = KeyEvent { key: Key::Keypad(KeypadKey::Digit(0)), modifiers: Modifiers::empty() };
+        assert_eq!(encode_with_flags(event, KITTY_DISAMBIGUATE), b"\x1b[57399u");
+        let event = KeyEvent { key: Key::Keypad(KeypadKey::Enter), modifiers: Modifiers::empty() };
+        assert_eq!(encode_with_flags(event, KITTY_DISAMBIGUATE), b"\x1b[57414u");
+    }
+
+    #[test]
+    fn kitty_function_keys() {
+        let event = KeyEvent { key: Key::F(1), modifiers: Modifiers::CTRL };
+        assert_eq!(encode_with_flags(event, KITTY_DISAMBIGUATE), b"\x1b[1;5P");
+        let event = KeyEvent { key: Key::F(5), modifiers: Modifiers::empty() };
+        assert_eq!(encode_with_flags(event, KITTY_DISAMBIGUATE), b"\x1b[15~");
+    }
 }
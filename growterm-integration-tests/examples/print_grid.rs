@@ -13,7 +13,7 @@ fn parse_and_apply(input: &[u8], cols: u16, rows: u16) -> Grid {
 
 fn print_grid(grid: &Grid, rows: usize, cols: usize) {
     for r in 0..rows {
-        let line: String = grid.cells()[r][..cols]
+        let line: String = grid.screen_rows()[r][..cols]
             .iter()
             .map(|c| c.character)
             .collect();
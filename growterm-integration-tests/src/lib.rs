@@ -1,3 +1,5 @@
+pub mod scenario;
+
 use std::collections::HashSet;
 use std::process::{Command, Stdio};
 use std::time::Duration;
@@ -0,0 +1,304 @@
+//! Small DSL for scripted terminal interactions.
+//!
+//! A `Scenario` drives a real shell through the headless pipeline
+//! (PTY → VtParser → Grid) and synchronizes on grid contents instead of
+//! fixed sleeps, so each step proceeds as soon as the terminal is ready.
+//!
+//! ```ignore
+//! Scenario::new(80, 24)
+//!     .send("printf '\\033[1mBOLD\\033[0m\\n'\n")
+//!     .wait_for_text("BOLD")
+//!     .assert_flags_at_text("BOLD", CellFlags::BOLD)
+//!     .resize(40, 10)
+//!     .run();
+//! ```
+
+use std::io::{Read, Write};
+use std::sync::mpsc;
+use std::thread;
+use std::time::{Duration, Instant};
+
+use growterm_grid::Grid;
+use growterm_types::{Cell, CellFlags, KeyEvent};
+use growterm_vt_parser::VtParser;
+
+const DEFAULT_TIMEOUT: Duration = Duration::from_secs(5);
+
+enum Step {
+    Send(Vec<u8>),
+    WaitForText(String),
+    WaitUntil(String, Box<dyn Fn(&Grid) -> bool>),
+    AssertCell {
+        row: u16,
+        col: u16,
+        check: Box<dyn Fn(&Cell) -> bool>,
+        what: String,
+    },
+    AssertFlagsAtText(String, CellFlags),
+    Resize { cols: u16, rows: u16 },
+}
+
+pub struct Scenario {
+    cols: u16,
+    rows: u16,
+    timeout: Duration,
+    steps: Vec<Step>,
+}
+
+impl Scenario {
+    pub fn new(cols: u16, rows: u16) -> Self {
+        Self {
+            cols,
+            rows,
+            timeout: DEFAULT_TIMEOUT,
+            steps: Vec::new(),
+        }
+    }
+
+    /// Timeout applied to every wait step.
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// Write raw text to the shell.
+    pub fn send(mut self, text: &str) -> Self {
+        self.steps.push(Step::Send(text.as_bytes().to_vec()));
+        self
+    }
+
+    /// Encode a key event with growterm-input and write it to the shell.
+    pub fn key(mut self, event: KeyEvent) -> Self {
+        self.steps.push(Step::Send(growterm_input::encode(event)));
+        self
+    }
+
+    /// Block until `needle` appears anywhere on screen.
+    pub fn wait_for_text(mut self, needle: &str) -> Self {
+        self.steps.push(Step::WaitForText(needle.to_string()));
+        self
+    }
+
+    /// Block until `predicate` holds for the grid.
+    pub fn wait_until(
+        mut self,
+        description: &str,
+        predicate: impl Fn(&Grid) -> bool + 'static,
+    ) -> Self {
+        self.steps
+            .push(Step::WaitUntil(description.to_string(), Box::new(predicate)));
+        self
+    }
+
+    /// Assert the cell at (row, col) satisfies `check`.
+    pub fn assert_cell(
+        mut self,
+        row: u16,
+        col: u16,
+        what: &str,
+        check: impl Fn(&Cell) -> bool + 'static,
+    ) -> Self {
+        self.steps.push(Step::AssertCell {
+            row,
+            col,
+            check: Box::new(check),
+            what: what.to_string(),
+        });
+        self
+    }
+
+    /// Assert every cell of the first on-screen occurrence of `needle` has `flags`.
+    pub fn assert_flags_at_text(mut self, needle: &str, flags: CellFlags) -> Self {
+        self.steps
+            .push(Step::AssertFlagsAtText(needle.to_string(), flags));
+        self
+    }
+
+    /// Resize both the PTY and the grid.
+    pub fn resize(mut self, cols: u16, rows: u16) -> Self {
+        self.steps.push(Step::Resize { cols, rows });
+        self
+    }
+
+    /// Run all steps in order, panicking with a screen dump on the first failure.
+    /// Returns the final grid for further inspection.
+    pub fn run(self) -> Grid {
+        let (reader, mut writer) =
+            growterm_pty::spawn(self.rows, self.cols).expect("failed to spawn PTY");
+        let rx = spawn_reader(reader);
+        let mut term = HeadlessTerm {
+            grid: Grid::new(self.cols, self.rows),
+            parser: VtParser::new(),
+            rx,
+        };
+
+        for (idx, step) in self.steps.into_iter().enumerate() {
+            match step {
+                Step::Send(bytes) => {
+                    writer.write_all(&bytes).expect("failed to write to PTY");
+                    writer.flush().expect("failed to flush PTY");
+                }
+                Step::WaitForText(needle) => {
+                    if !term.wait(self.timeout, |g| screen_text(g).contains(&needle)) {
+                        fail(idx, &format!("timed out waiting for {needle:?}"), &term.grid);
+                    }
+                }
+                Step::WaitUntil(description, predicate) => {
+                    if !term.wait(self.timeout, |g| predicate(g)) {
+                        fail(idx, &format!("timed out waiting until {description}"), &term.grid);
+                    }
+                }
+                Step::AssertCell {
+                    row,
+                    col,
+                    check,
+                    what,
+                } => {
+                    term.drain();
+                    let cell = term
+                        .grid
+                        .screen_row(row as usize)
+                        .and_then(|r| r.get(col as usize));
+                    match cell {
+                        Some(cell) if check(cell) => {}
+                        Some(cell) => fail(
+                            idx,
+                            &format!("cell ({row},{col}) is not {what}: {cell:?}"),
+                            &term.grid,
+                        ),
+                        None => fail(idx, &format!("cell ({row},{col}) out of bounds"), &term.grid),
+                    }
+                }
+                Step::AssertFlagsAtText(needle, flags) => {
+                    term.drain();
+                    match find_text(&term.grid, &needle) {
+                        Some((row, col)) => {
+                            let cells = term.grid.screen_row(row).expect("find_text row is on screen");
+                            let len = needle.chars().count();
+                            let missing = cells[col..col + len]
+                                .iter()
+                                .any(|c| !c.flags.contains(flags));
+                            if missing {
+                                fail(
+                                    idx,
+                                    &format!("{needle:?} at ({row},{col}) lacks {flags:?}"),
+                                    &term.grid,
+                                );
+                            }
+                        }
+                        None => fail(idx, &format!("{needle:?} not on screen"), &term.grid),
+                    }
+                }
+                Step::Resize { cols, rows } => {
+                    writer.resize(rows, cols).expect("failed to resize PTY");
+                    term.grid.resize(cols, rows);
+                }
+            }
+        }
+
+        term.drain();
+        term.grid
+    }
+}
+
+struct HeadlessTerm {
+    grid: Grid,
+    parser: VtParser,
+    rx: mpsc::Receiver<Vec<u8>>,
+}
+
+impl HeadlessTerm {
+    fn feed(&mut self, data: &[u8]) {
+        for cmd in &self.parser.parse(data) {
+            self.grid.apply(cmd);
+        }
+    }
+
+    /// Apply everything already read without blocking.
+    fn drain(&mut self) {
+        while let Ok(data) = self.rx.try_recv() {
+            self.feed(&data);
+        }
+    }
+
+    /// Apply output until `predicate` holds. Returns false on timeout or EOF.
+    fn wait(&mut self, timeout: Duration, predicate: impl Fn(&Grid) -> bool) -> bool {
+        self.drain();
+        if predicate(&self.grid) {
+            return true;
+        }
+        let deadline = Instant::now() + timeout;
+        loop {
+            let remaining = deadline.saturating_duration_since(Instant::now());
+            if remaining.is_zero() {
+                return false;
+            }
+            match self.rx.recv_timeout(remaining) {
+                Ok(data) => {
+                    self.feed(&data);
+                    if predicate(&self.grid) {
+                        return true;
+                    }
+                }
+                Err(_) => return false,
+            }
+        }
+    }
+}
+
+fn spawn_reader(reader: growterm_pty::PtyReader) -> mpsc::Receiver<Vec<u8>> {
+    let (tx, rx) = mpsc::channel();
+    thread::spawn(move || {
+        let mut reader = reader;
+        let mut buf = [0u8; 4096];
+        loop {
+            match reader.read(&mut buf) {
+                Ok(0) | Err(_) => break,
+                Ok(n) => {
+                    if tx.send(buf[..n].to_vec()).is_err() {
+                        break;
+                    }
+                }
+            }
+        }
+    });
+    rx
+}
+
+/// Visible rows joined with newlines, wide-char spacer cells removed.
+pub fn screen_text(grid: &Grid) -> String {
+    grid.screen_rows()
+        .map(|row| row_string(row).trim_end().to_string())
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+fn row_string(row: &[Cell]) -> String {
+    let mut text = String::new();
+    let mut skip_spacer = false;
+    for cell in row {
+        if skip_spacer {
+            skip_spacer = false;
+            continue;
+        }
+        skip_spacer = cell.flags.contains(CellFlags::WIDE_CHAR);
+        text.push(cell.character);
+    }
+    text
+}
+
+/// (row, col) of the first occurrence of `needle`, in cell coordinates.
+fn find_text(grid: &Grid, needle: &str) -> Option<(usize, usize)> {
+    let needle: Vec<char> = needle.chars().collect();
+    for (r, row) in grid.screen_rows().enumerate() {
+        let chars: Vec<char> = row.iter().map(|c| c.character).collect();
+        if let Some(col) = chars.windows(needle.len()).position(|w| w == needle.as_slice()) {
+            return Some((r, col));
+        }
+    }
+    None
+}
+
+fn fail(step: usize, message: &str, grid: &Grid) -> ! {
+    panic!("scenario step {step}: {message}\nscreen:\n{}", screen_text(grid));
+}
@@ -63,7 +63,7 @@ fn infer_rows_from_fixture(bytes: &[u8]) -> u16 {
 }
 
 fn visible_row_text(grid: &Grid, row: usize) -> String {
-    grid.visible_cells()[row]
+    grid.visible_rows()[row]
         .iter()
         .map(|cell| cell.character)
         .collect::<String>()
@@ -73,7 +73,7 @@ fn visible_row_text(grid: &Grid, row: usize) -> String {
 
 fn dump_visible_rows(grid: &Grid) -> String {
     let mut out = String::new();
-    for row in 0..grid.visible_cells().len() {
+    for row in 0..grid.visible_rows().len() {
         let line = visible_row_text(grid, row);
         out.push_str(&format!("{row:02}: {line}\n"));
     }
@@ -90,8 +90,7 @@ fn codex_resume_fixture_replays_without_parser_panics() {
     let grid = replay_fixture(&bytes, DEFAULT_COLS, rows);
 
     assert!(
-        grid.visible_cells()
-            .iter()
+        grid.visible_rows()
             .flat_map(|row| row.iter())
             .any(|cell| cell.character != ' ' && cell.character != '\0'),
         "fixture replay produced an empty grid\n{}",
@@ -105,7 +104,7 @@ fn codex_resume_fixture_status_bar_stays_at_bottom() {
     let bytes = load_fixture_bytes();
     let rows = infer_rows_from_fixture(&bytes);
     let grid = replay_fixture(&bytes, DEFAULT_COLS, rows);
-    let rows = grid.visible_cells().len();
+    let rows = grid.visible_rows().len();
 
     let bottom = visible_row_text(&grid, rows - 1);
     let above_bottom = visible_row_text(&grid, rows.saturating_sub(2));
@@ -10,7 +10,8 @@ fn render(input: &[u8], palette: TerminalPalette) -> Vec<growterm_types::RenderC
     for command in &commands {
         grid.apply(command);
     }
-    generate(grid.cells(), None, None, None, palette)
+    let rows: Vec<_> = grid.screen_rows().collect();
+    generate(&rows, None, None, None, palette)
 }
 
 #[test]
@@ -18,6 +19,7 @@ fn default_colors_follow_injected_palette() {
     let palette = TerminalPalette {
         default_fg: Rgb::new(12, 34, 56),
         default_bg: Rgb::new(65, 43, 21),
+        ..TerminalPalette::DEFAULT
     };
 
     // A: explicit FG, B: default FG, C: explicit BG, D: default BG
@@ -1,4 +1,4 @@
-use growterm_types::{CellFlags, RenderCommand, Rgb};
+use growterm_types::{CellFlags, RenderCommand, Rgb, UnderlineStyle};
 
 const FONT_SIZE: f32 = 32.0;
 const WIDTH: u32 = 800;
@@ -26,6 +26,9 @@ fn no_gap_between_same_color_cells() {
                 fg: black,
                 bg: white,
                 flags: CellFlags::empty(),
+                zerowidth: None,
+                underline_style: UnderlineStyle::Single,
+                underline_color: black,
             });
         }
     }
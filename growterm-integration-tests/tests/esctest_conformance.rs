@@ -0,0 +1,561 @@
+//! Curated subset of esctest (https://github.com/migueldeicaza/esctest)
+//! run against the headless emulator (VtParser + Grid).
+//!
+//! esctest talks to a live terminal and reads state back with CPR/DECRQCRA;
+//! here the same checks read the grid directly. Coordinates are 1-based
+//! `(x, y)` like esctest's `Point`.
+//!
+//! Cases that fail today are listed in `fixtures/esctest-known-failures.txt`.
+//! The test fails on a new failure (regression) and on a listed case that
+//! now passes, so the list always matches reality. See the report with
+//! `cargo test --manifest-path growterm-integration-tests/Cargo.toml --test esctest_conformance -- --nocapture`.
+
+use std::collections::BTreeSet;
+use std::panic::{self, AssertUnwindSafe};
+
+use growterm_emulator::Terminal;
+use growterm_types::CellFlags;
+
+const KNOWN_FAILURES: &str = include_str!("../fixtures/esctest-known-failures.txt");
+const COLS: u16 = 80;
+const ROWS: u16 = 24;
+
+type Check = Result<(), String>;
+
+struct Case {
+    name: &'static str,
+    run: fn(&mut Terminal) -> Check,
+}
+
+// --- helpers ---
+
+fn cup(t: &mut Terminal, x: u16, y: u16) {
+    t.feed(format!("\x1b[{y};{x}H").as_bytes());
+}
+
+fn decstbm(t: &mut Terminal, top: u16, bottom: u16) {
+    t.feed(format!("\x1b[{top};{bottom}r").as_bytes());
+}
+
+fn expect_cursor(t: &Terminal, x: u16, y: u16) -> Check {
+    let (row, col) = t.cursor();
+    let actual = (col + 1, row + 1);
+    if actual == (x, y) {
+        Ok(())
+    } else {
+        Err(format!("cursor at {actual:?}, expected {:?}", (x, y)))
+    }
+}
+
+/// Row texts starting at line `top` (trailing blanks trimmed).
+fn expect_lines(t: &Terminal, top: u16, expected: &[&str]) -> Check {
+    for (i, want) in expected.iter().enumerate() {
+        let y = top as usize + i;
+        let got = t.row_text(y - 1);
+        if got != *want {
+            return Err(format!("line {y} is {got:?}, expected {want:?}"));
+        }
+    }
+    Ok(())
+}
+
+fn expect_char(t: &Terminal, x: u16, y: u16, want: char) -> Check {
+    let got = t.screen()[y as usize - 1][x as usize - 1].character;
+    if got == want {
+        Ok(())
+    } else {
+        Err(format!("char at ({x}, {y}) is {got:?}, expected {want:?}"))
+    }
+}
+
+/// Lines "a".."e" at rows 1..5, cursor back home.
+fn write_letter_lines(t: &mut Terminal) {
+    t.feed(b"a\r\nb\r\nc\r\nd\r\ne");
+    cup(t, 1, 1);
+}
+
+/// esctest's ED/EL fixture: "a" on line 1, "bcd" on line 3, "e" on line 5.
+fn prepare_ed(t: &mut Terminal) {
+    cup(t, 1, 1);
+    t.feed(b"a");
+    cup(t, 1, 3);
+    t.feed(b"bcd");
+    cup(t, 1, 5);
+    t.feed(b"e");
+    cup(t, 2, 3);
+}
+
+fn prepare_el(t: &mut Terminal) {
+    cup(t, 1, 1);
+    t.feed(b"abcdefghij");
+    cup(t, 5, 1);
+}
+
+// --- cases ---
+
+const CASES: &[Case] = &[
+    // CUP / HVP
+    Case { name: "CUPTests.test_CUP_DefaultParams", run: |t| {
+        cup(t, 6, 3);
+        t.feed(b"\x1b[H");
+        expect_cursor(t, 1, 1)
+    }},
+    Case { name: "CUPTests.test_CUP_RowOnly", run: |t| {
+        cup(t, 6, 3);
+        t.feed(b"\x1b[2H");
+        expect_cursor(t, 1, 2)
+    }},
+    Case { name: "CUPTests.test_CUP_ColumnOnly", run: |t| {
+        cup(t, 6, 3);
+        t.feed(b"\x1b[;2H");
+        expect_cursor(t, 2, 1)
+    }},
+    Case { name: "CUPTests.test_CUP_ZeroIsTreatedAsOne", run: |t| {
+        cup(t, 6, 3);
+        t.feed(b"\x1b[0;0H");
+        expect_cursor(t, 1, 1)
+    }},
+    Case { name: "CUPTests.test_CUP_OutOfBoundsParams", run: |t| {
+        t.feed(b"\x1b[999;999H");
+        expect_cursor(t, COLS, ROWS)
+    }},
+    Case { name: "CUPTests.test_CUP_RespectsOriginMode", run: |t| {
+        decstbm(t, 6, 11);
+        t.feed(b"\x1b[?6h");
+        cup(t, 1, 1);
+        expect_cursor(t, 1, 6)
+    }},
+    Case { name: "HVPTests.test_HVP_DefaultParams", run: |t| {
+        cup(t, 6, 3);
+        t.feed(b"\x1b[f");
+        expect_cursor(t, 1, 1)
+    }},
+    // CUU / CUD / CUF / CUB
+    Case { name: "CUUTests.test_CUU_DefaultParam", run: |t| {
+        cup(t, 5, 3);
+        t.feed(b"\x1b[A");
+        expect_cursor(t, 5, 2)
+    }},
+    Case { name: "CUUTests.test_CUU_StopsAtTopLine", run: |t| {
+        cup(t, 5, 3);
+        t.feed(b"\x1b[99A");
+        expect_cursor(t, 5, 1)
+    }},
+    Case { name: "CUUTests.test_CUU_StopsAtTopMarginInScrollRegion", run: |t| {
+        decstbm(t, 2, 4);
+        cup(t, 5, 3);
+        t.feed(b"\x1b[99A");
+        expect_cursor(t, 5, 2)
+    }},
+    Case { name: "CUDTests.test_CUD_StopsAtBottomLine", run: |t| {
+        cup(t, 5, 3);
+        t.feed(b"\x1b[99B");
+        expect_cursor(t, 5, ROWS)
+    }},
+    Case { name: "CUDTests.test_CUD_StopsAtBottomMarginInScrollRegion", run: |t| {
+        decstbm(t, 2, 4);
+        cup(t, 5, 3);
+        t.feed(b"\x1b[99B");
+        expect_cursor(t, 5, 4)
+    }},
+    Case { name: "CUFTests.test_CUF_StopsAtRightSide", run: |t| {
+        cup(t, 5, 1);
+        t.feed(b"\x1b[999C");
+        expect_cursor(t, COLS, 1)
+    }},
+    Case { name: "CUBTests.test_CUB_StopsAtLeftEdge", run: |t| {
+        cup(t, 5, 3);
+        t.feed(b"\x1b[99D");
+        expect_cursor(t, 1, 3)
+    }},
+    Case { name: "CNLTests.test_CNL_DefaultParam", run: |t| {
+        cup(t, 5, 3);
+        t.feed(b"\x1b[E");
+        expect_cursor(t, 1, 4)
+    }},
+    Case { name: "CPLTests.test_CPL_DefaultParam", run: |t| {
+        cup(t, 5, 3);
+        t.feed(b"\x1b[F");
+        expect_cursor(t, 1, 2)
+    }},
+    // CHA / HPA / VPA
+    Case { name: "CHATests.test_CHA_ZeroParam", run: |t| {
+        cup(t, 5, 3);
+        t.feed(b"\x1b[0G");
+        expect_cursor(t, 1, 3)
+    }},
+    Case { name: "CHATests.test_CHA_OutOfBoundsLarge", run: |t| {
+        cup(t, 5, 3);
+        t.feed(b"\x1b[999G");
+        expect_cursor(t, COLS, 3)
+    }},
+    Case { name: "HPATests.test_HPA_DefaultParams", run: |t| {
+        cup(t, 6, 1);
+        t.feed(b"\x1b[`");
+        expect_cursor(t, 1, 1)
+    }},
+    Case { name: "VPATests.test_VPA_StopsAtBottomEdge", run: |t| {
+        cup(t, 5, 3);
+        t.feed(b"\x1b[999d");
+        expect_cursor(t, 5, ROWS)
+    }},
+    // ED
+    Case { name: "EDTests.test_ED_0", run: |t| {
+        prepare_ed(t);
+        t.feed(b"\x1b[0J");
+        expect_lines(t, 1, &["a", "", "b", "", ""])
+    }},
+    Case { name: "EDTests.test_ED_1", run: |t| {
+        prepare_ed(t);
+        t.feed(b"\x1b[1J");
+        expect_lines(t, 1, &["", "", "  d", "", "e"])
+    }},
+    Case { name: "EDTests.test_ED_2", run: |t| {
+        prepare_ed(t);
+        t.feed(b"\x1b[2J");
+        expect_lines(t, 1, &["", "", "", "", ""])?;
+        expect_cursor(t, 2, 3)
+    }},
+    Case { name: "EDTests.test_ED_3", run: |t| {
+        prepare_ed(t);
+        t.feed(b"\x1b[3J");
+        expect_lines(t, 1, &["a", "", "bcd", "", "e"])
+    }},
+    // EL
+    Case { name: "ELTests.test_EL_Default", run: |t| {
+        prepare_el(t);
+        t.feed(b"\x1b[K");
+        expect_lines(t, 1, &["abcd"])
+    }},
+    Case { name: "ELTests.test_EL_1", run: |t| {
+        prepare_el(t);
+        t.feed(b"\x1b[1K");
+        expect_lines(t, 1, &["     fghij"])
+    }},
+    Case { name: "ELTests.test_EL_2", run: |t| {
+        prepare_el(t);
+        t.feed(b"\x1b[2K");
+        expect_lines(t, 1, &[""])?;
+        expect_cursor(t, 5, 1)
+    }},
+    // ECH / DCH / ICH / REP
+    Case { name: "ECHTests.test_ECH_DefaultParam", run: |t| {
+        t.feed(b"abcdefg");
+        cup(t, 2, 1);
+        t.feed(b"\x1b[X");
+        expect_lines(t, 1, &["a cdefg"])?;
+        expect_cursor(t, 2, 1)
+    }},
+    Case { name: "ECHTests.test_ECH_StopsAtRightEdge", run: |t| {
+        t.feed(b"abcdefg");
+        cup(t, 2, 1);
+        t.feed(b"\x1b[999X");
+        expect_lines(t, 1, &["a"])
+    }},
+    Case { name: "DCHTests.test_DCH_DefaultParam", run: |t| {
+        t.feed(b"abcd");
+        cup(t, 2, 1);
+        t.feed(b"\x1b[P");
+        expect_lines(t, 1, &["acd"])
+    }},
+    Case { name: "DCHTests.test_DCH_ExplicitParam", run: |t| {
+        t.feed(b"abcd");
+        cup(t, 2, 1);
+        t.feed(b"\x1b[2P");
+        expect_lines(t, 1, &["ad"])
+    }},
+    Case { name: "ICHTests.test_ICH_DefaultParam", run: |t| {
+        t.feed(b"abcd");
+        cup(t, 2, 1);
+        t.feed(b"\x1b[@");
+        expect_lines(t, 1, &["a bcd"])?;
+        expect_cursor(t, 2, 1)
+    }},
+    Case { name: "ICHTests.test_ICH_ScrollOffRightEdge", run: |t| {
+        cup(t, COLS - 1, 1);
+        t.feed(b"ab");
+        cup(t, COLS - 1, 1);
+        t.feed(b"\x1b[@");
+        expect_char(t, COLS - 1, 1, ' ')?;
+        expect_char(t, COLS, 1, 'a')
+    }},
+    Case { name: "REPTests.test_REP_DefaultParam", run: |t| {
+        t.feed(b"a\x1b[b");
+        expect_lines(t, 1, &["aa"])
+    }},
+    // IL / DL
+    Case { name: "ILTests.test_IL_DefaultParam", run: |t| {
+        write_letter_lines(t);
+        cup(t, 1, 2);
+        t.feed(b"\x1b[L");
+        expect_lines(t, 1, &["a", "", "b", "c", "d", "e"])
+    }},
+    Case { name: "ILTests.test_IL_RespectsScrollRegion", run: |t| {
+        write_letter_lines(t);
+        decstbm(t, 1, 3);
+        cup(t, 1, 2);
+        t.feed(b"\x1b[L");
+        expect_lines(t, 1, &["a", "", "b", "d"])
+    }},
+    Case { name: "DLTests.test_DL_DefaultParam", run: |t| {
+        write_letter_lines(t);
+        cup(t, 1, 2);
+        t.feed(b"\x1b[M");
+        expect_lines(t, 1, &["a", "c", "d", "e", ""])
+    }},
+    Case { name: "DLTests.test_DL_RespectsScrollRegion", run: |t| {
+        write_letter_lines(t);
+        decstbm(t, 1, 3);
+        cup(t, 1, 2);
+        t.feed(b"\x1b[M");
+        expect_lines(t, 1, &["a", "c", "", "d"])
+    }},
+    // SU / SD
+    Case { name: "SUTests.test_SU_DefaultParam", run: |t| {
+        write_letter_lines(t);
+        t.feed(b"\x1b[S");
+        expect_lines(t, 1, &["b", "c", "d", "e", ""])
+    }},
+    Case { name: "SUTests.test_SU_RespectsScrollRegion", run: |t| {
+        write_letter_lines(t);
+        decstbm(t, 2, 4);
+        t.feed(b"\x1b[S");
+        expect_lines(t, 1, &["a", "c", "d", "", "e"])
+    }},
+    Case { name: "SDTests.test_SD_DefaultParam", run: |t| {
+        write_letter_lines(t);
+        t.feed(b"\x1b[T");
+        expect_lines(t, 1, &["", "a", "b", "c", "d", "e"])
+    }},
+    // IND / RI / NEL / LF / VT / FF
+    Case { name: "INDTests.test_IND_Basic", run: |t| {
+        cup(t, 5, 3);
+        t.feed(b"\x1bD");
+        expect_cursor(t, 5, 4)
+    }},
+    Case { name: "RITests.test_RI_Basic", run: |t| {
+        cup(t, 5, 3);
+        t.feed(b"\x1bM");
+        expect_cursor(t, 5, 2)
+    }},
+    Case { name: "RITests.test_RI_Scrolls", run: |t| {
+        write_letter_lines(t);
+        t.feed(b"\x1bM");
+        expect_lines(t, 1, &["", "a", "b"])?;
+        expect_cursor(t, 1, 1)
+    }},
+    Case { name: "RITests.test_RI_StopsAtTopMarginAndScrolls", run: |t| {
+        write_letter_lines(t);
+        decstbm(t, 2, 4);
+        cup(t, 1, 2);
+        t.feed(b"\x1bM");
+        expect_lines(t, 1, &["a", "", "b", "c", "e"])
+    }},
+    Case { name: "NELTests.test_NEL_Basic", run: |t| {
+        cup(t, 5, 3);
+        t.feed(b"\x1bE");
+        expect_cursor(t, 1, 4)
+    }},
+    Case { name: "LFTests.test_LF_ScrollsAtBottomMargin", run: |t| {
+        write_letter_lines(t);
+        decstbm(t, 2, 3);
+        cup(t, 1, 3);
+        t.feed(b"\n");
+        expect_lines(t, 1, &["a", "c", "", "d"])?;
+        expect_cursor(t, 1, 3)
+    }},
+    Case { name: "VTTests.test_VT_Basic", run: |t| {
+        cup(t, 5, 3);
+        t.feed(b"\x0b");
+        expect_cursor(t, 5, 4)
+    }},
+    Case { name: "FFTests.test_FF_Basic", run: |t| {
+        cup(t, 5, 3);
+        t.feed(b"\x0c");
+        expect_cursor(t, 5, 4)
+    }},
+    // DECSTBM
+    Case { name: "DECSTBMTests.test_DECSTBM_MovesCursorToOrigin", run: |t| {
+        cup(t, 5, 5);
+        decstbm(t, 2, 4);
+        expect_cursor(t, 1, 1)
+    }},
+    Case { name: "DECSTBMTests.test_DECSTBM_DefaultRestores", run: |t| {
+        write_letter_lines(t);
+        decstbm(t, 2, 3);
+        t.feed(b"\x1b[r");
+        cup(t, 1, ROWS);
+        t.feed(b"\n");
+        expect_lines(t, 1, &["b", "c", "d", "e"])
+    }},
+    Case { name: "DECSTBMTests.test_DECSTBM_TopBelowBottomIgnored", run: |t| {
+        write_letter_lines(t);
+        decstbm(t, 3, 2);
+        cup(t, 1, ROWS);
+        t.feed(b"\n");
+        expect_lines(t, 1, &["b", "c"])
+    }},
+    // Tabs, BS, CR
+    Case { name: "HTTests.test_HT_Basic", run: |t| {
+        t.feed(b"\t");
+        expect_cursor(t, 9, 1)
+    }},
+    Case { name: "HTTests.test_HT_StopsAtRightEdge", run: |t| {
+        cup(t, COLS - 5, 1);
+        t.feed(b"\t\t");
+        expect_cursor(t, COLS, 1)
+    }},
+    Case { name: "CBTTests.test_CBT_Basic", run: |t| {
+        cup(t, 12, 1);
+        t.feed(b"\x1b[Z");
+        expect_cursor(t, 9, 1)
+    }},
+    Case { name: "HTSTests.test_HTS_Basic", run: |t| {
+        cup(t, 3, 1);
+        t.feed(b"\x1bH");
+        cup(t, 1, 1);
+        t.feed(b"\t");
+        expect_cursor(t, 3, 1)
+    }},
+    Case { name: "BSTests.test_BS_Basic", run: |t| {
+        cup(t, 3, 3);
+        t.feed(b"\x08");
+        expect_cursor(t, 2, 3)
+    }},
+    Case { name: "BSTests.test_BS_StopsAtLeftEdge", run: |t| {
+        cup(t, 1, 3);
+        t.feed(b"\x08");
+        expect_cursor(t, 1, 3)
+    }},
+    Case { name: "CRTests.test_CR_Basic", run: |t| {
+        cup(t, 5, 3);
+        t.feed(b"\r");
+        expect_cursor(t, 1, 3)
+    }},
+    // Save/restore cursor
+    Case { name: "DECSCTests.test_SaveRestoreCursor_Basic", run: |t| {
+        cup(t, 5, 6);
+        t.feed(b"\x1b7");
+        cup(t, 1, 1);
+        t.feed(b"\x1b8");
+        expect_cursor(t, 5, 6)
+    }},
+    Case { name: "SCOSCTests.test_SaveRestoreCursor_Basic", run: |t| {
+        cup(t, 5, 6);
+        t.feed(b"\x1b[s");
+        cup(t, 1, 1);
+        t.feed(b"\x1b[u");
+        expect_cursor(t, 5, 6)
+    }},
+    Case { name: "DECSCTests.test_SaveRestoreCursor_ResetsAttributes", run: |t| {
+        t.feed(b"\x1b7\x1b[1m\x1b8x");
+        if t.screen()[0][0].flags.contains(CellFlags::BOLD) {
+            return Err("attributes were not restored".into());
+        }
+        Ok(())
+    }},
+    // Modes
+    Case { name: "DECSETTests.test_DECSET_DECAWM", run: |t| {
+        cup(t, COLS, 1);
+        t.feed(b"ab");
+        expect_char(t, COLS, 1, 'a')?;
+        expect_char(t, 1, 2, 'b')
+    }},
+    Case { name: "DECSETTests.test_DECSET_DECAWM_Off", run: |t| {
+        t.feed(b"\x1b[?7l");
+        cup(t, COLS, 1);
+        t.feed(b"ab");
+        expect_char(t, COLS, 1, 'b')?;
+        expect_cursor(t, COLS, 1)
+    }},
+    Case { name: "DECSETTests.test_DECSET_DECTCEM", run: |t| {
+        t.feed(b"\x1b[?25l");
+        if t.grid().cursor_visible() {
+            return Err("cursor still visible after ?25l".into());
+        }
+        t.feed(b"\x1b[?25h");
+        if !t.grid().cursor_visible() {
+            return Err("cursor hidden after ?25h".into());
+        }
+        Ok(())
+    }},
+    Case { name: "DECSETTests.test_DECSET_ALTBUF", run: |t| {
+        t.feed(b"a\x1b[?1049h");
+        expect_lines(t, 1, &[""])?;
+        t.feed(b"b\x1b[?1049l");
+        expect_lines(t, 1, &["a"])
+    }},
+    Case { name: "SMTests.test_SM_IRM", run: |t| {
+        t.feed(b"abc");
+        cup(t, 1, 1);
+        t.feed(b"\x1b[4hX");
+        expect_lines(t, 1, &["Xabc"])
+    }},
+    // Resets
+    Case { name: "RISTests.test_RIS_ClearsScreen", run: |t| {
+        t.feed(b"x");
+        cup(t, 5, 3);
+        t.feed(b"\x1bc");
+        expect_lines(t, 1, &[""])?;
+        expect_cursor(t, 1, 1)
+    }},
+    Case { name: "DECSTRTests.test_DECSTR_STBM", run: |t| {
+        write_letter_lines(t);
+        decstbm(t, 2, 3);
+        t.feed(b"\x1b[!p");
+        cup(t, 1, ROWS);
+        t.feed(b"\n");
+        expect_lines(t, 1, &["b", "c"])
+    }},
+    Case { name: "DECALNTests.test_DECALN_FillsScreen", run: |t| {
+        t.feed(b"\x1b#8");
+        expect_char(t, 1, 1, 'E')?;
+        expect_char(t, COLS, ROWS, 'E')
+    }},
+];
+
+fn known_failures() -> BTreeSet<&'static str> {
+    KNOWN_FAILURES
+        .lines()
+        .map(|line| line.split('#').next().unwrap().trim())
+        .filter(|name| !name.is_empty())
+        .collect()
+}
+
+fn run_case(case: &Case) -> Check {
+    let mut term = Terminal::new(COLS, ROWS);
+    panic::catch_unwind(AssertUnwindSafe(|| (case.run)(&mut term)))
+        .unwrap_or_else(|_| Err("panicked".into()))
+}
+
+#[test]
+fn esctest_conformance() {
+    let known = known_failures();
+    let mut regressions = Vec::new();
+    let mut fixed = Vec::new();
+    let mut passed = 0;
+
+    for case in CASES {
+        let result = run_case(case);
+        match (&result, known.contains(case.name)) {
+            (Ok(()), false) => passed += 1,
+            (Ok(()), true) => fixed.push(case.name),
+            (Err(e), false) => regressions.push(format!("{}: {e}", case.name)),
+            (Err(_), true) => {}
+        }
+        match result {
+            Ok(()) => println!("PASS {}", case.name),
+            Err(e) => println!("FAIL {} ({e})", case.name),
+        }
+    }
+    println!("{passed}/{} passed, {} known failures", CASES.len(), known.len());
+
+    let names: BTreeSet<_> = CASES.iter().map(|c| c.name).collect();
+    let stale: Vec<_> = known.difference(&names).collect();
+    assert!(stale.is_empty(), "known failures list names unknown cases: {stale:?}");
+    assert!(regressions.is_empty(), "unexpected failures:\n{}", regressions.join("\n"));
+    assert!(
+        fixed.is_empty(),
+        "now passing, remove from fixtures/esctest-known-failures.txt: {fixed:?}"
+    );
+}
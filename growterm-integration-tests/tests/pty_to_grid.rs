@@ -3,8 +3,8 @@ use std::sync::mpsc;
 use std::thread;
 use std::time::{Duration, Instant};
 
+use growterm_emulator::Terminal;
 use growterm_grid::Grid;
-use growterm_vt_parser::VtParser;
 
 /// Read PTY output, parse VT sequences, apply to grid until `predicate` returns true or timeout.
 fn pty_grid_wait(
@@ -32,20 +32,16 @@ fn pty_grid_wait(
         }
     });
 
-    let mut grid = Grid::new(cols, rows);
-    let mut parser = VtParser::new();
+    let mut term = Terminal::new(cols, rows);
     let deadline = Instant::now() + timeout;
 
     while Instant::now() < deadline {
         let remaining = deadline.saturating_duration_since(Instant::now());
         match rx.recv_timeout(remaining.min(Duration::from_millis(100))) {
             Ok(data) => {
-                let commands = parser.parse(&data);
-                for cmd in &commands {
-                    grid.apply(cmd);
-                }
-                if predicate(&grid) {
-                    return grid;
+                term.feed(&data);
+                if predicate(term.grid()) {
+                    return term.into_grid();
                 }
             }
             Err(mpsc::RecvTimeoutError::Timeout) => continue,
@@ -53,12 +49,12 @@ fn pty_grid_wait(
         }
     }
 
-    grid
+    term.into_grid()
 }
 
 /// Extract text from a grid row, trimming trailing spaces.
 fn row_text(grid: &Grid, row: usize) -> String {
-    grid.cells()[row]
+    grid.screen_rows()[row]
         .iter()
         .map(|c| c.character)
         .collect::<String>()
@@ -68,7 +64,7 @@ fn row_text(grid: &Grid, row: usize) -> String {
 
 /// Check if any row in the grid contains non-empty text (shell prompt appeared).
 fn has_any_content(grid: &Grid) -> bool {
-    grid.cells().iter().any(|row| {
+    grid.screen_rows().any(|row| {
         row.iter().any(|cell| cell.character != '\0' && cell.character != ' ')
     })
 }
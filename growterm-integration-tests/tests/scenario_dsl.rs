@@ -0,0 +1,56 @@
+use growterm_integration_tests::scenario::{screen_text, Scenario};
+use growterm_types::{CellFlags, Key, KeyEvent, Modifiers};
+
+/// The waited-for text must differ from the typed line, or the tty's echo
+/// of the input would satisfy the wait before the shell runs anything.
+#[test]
+fn echo_then_wait_for_output() {
+    let grid = Scenario::new(80, 24)
+        .send("echo SCENARIO_$((1+1))\n")
+        .wait_for_text("SCENARIO_2")
+        .run();
+
+    assert!(screen_text(&grid).lines().any(|line| line == "SCENARIO_2"));
+}
+
+#[test]
+fn bold_output_has_bold_cells() {
+    Scenario::new(80, 24)
+        .send("printf 'x\\033[1mBOLDTEXT\\033[0m\\n'\n")
+        .wait_for_text("xBOLDTEXT")
+        .assert_flags_at_text("BOLDTEXT", CellFlags::BOLD)
+        .run();
+}
+
+#[test]
+fn ctrl_c_interrupts_running_command() {
+    Scenario::new(80, 24)
+        .send("printf 'SLEEP%s\\n' ING; sleep 30\n")
+        .wait_for_text("SLEEPING")
+        .key(KeyEvent {
+            key: Key::Char('c'),
+            modifiers: Modifiers::CTRL,
+        })
+        // 130 = 128 + SIGINT: only the interrupt can end `sleep 30` this way
+        .send("echo \"rc=$?\"\n")
+        .wait_for_text("rc=130")
+        .run();
+}
+
+#[test]
+fn resize_reaches_the_shell() {
+    Scenario::new(80, 24)
+        .resize(40, 10)
+        .send("stty size\n")
+        .wait_for_text("10 40")
+        .run();
+}
+
+#[test]
+fn assert_cell_checks_character() {
+    Scenario::new(80, 24)
+        .send("clear; printf 'Z'\n")
+        .wait_until("Z at top-left", |g| g.screen_rows()[0][0].character == 'Z')
+        .assert_cell(0, 0, "'Z'", |c| c.character == 'Z')
+        .run();
+}
@@ -0,0 +1,67 @@
+//! SGR attribute set/reset behavior as exercised by vttest's
+//! "Test of graphic rendition" screens (menu 11.6 / character attributes).
+
+use growterm_grid::Grid;
+use growterm_types::{CellFlags, Color};
+use growterm_vt_parser::VtParser;
+
+fn parse_and_apply(input: &[u8]) -> Grid {
+    let mut parser = VtParser::new();
+    let mut grid = Grid::new(40, 4);
+    for cmd in parser.parse(input) {
+        grid.apply(&cmd);
+    }
+    grid
+}
+
+fn flags_at(grid: &Grid, col: usize) -> CellFlags {
+    grid.screen_rows()[0][col].flags
+}
+
+#[test]
+fn sgr22_clears_bold_and_faint() {
+    let grid = parse_and_apply(b"\x1b[1;2mA\x1b[22mB");
+    assert!(flags_at(&grid, 0).contains(CellFlags::BOLD | CellFlags::DIM));
+    assert!(!flags_at(&grid, 1).intersects(CellFlags::BOLD | CellFlags::DIM));
+}
+
+#[test]
+fn sgr22_keeps_other_attributes() {
+    let grid = parse_and_apply(b"\x1b[1;3;4;7mA\x1b[22mB");
+    let b = flags_at(&grid, 1);
+    assert!(b.contains(CellFlags::ITALIC | CellFlags::UNDERLINE | CellFlags::INVERSE));
+}
+
+#[test]
+fn bold_after_faint_reset_is_independent() {
+    // 2 → 22 → 1: the second character is bold only
+    let grid = parse_and_apply(b"\x1b[2mA\x1b[22;1mB");
+    assert_eq!(flags_at(&grid, 1), CellFlags::BOLD);
+}
+
+#[test]
+fn each_reset_touches_one_attribute() {
+    let grid = parse_and_apply(b"\x1b[1;3;4;7;8;9m\x1b[23mA\x1b[24mB\x1b[27mC\x1b[28mD\x1b[29mE");
+    assert!(!flags_at(&grid, 0).contains(CellFlags::ITALIC));
+    assert!(flags_at(&grid, 0).contains(CellFlags::UNDERLINE));
+    assert!(!flags_at(&grid, 1).contains(CellFlags::UNDERLINE));
+    assert!(!flags_at(&grid, 2).contains(CellFlags::INVERSE));
+    assert!(!flags_at(&grid, 3).contains(CellFlags::HIDDEN));
+    assert_eq!(flags_at(&grid, 4), CellFlags::BOLD);
+}
+
+#[test]
+fn sgr0_resets_colors_and_flags() {
+    let grid = parse_and_apply(b"\x1b[1;31;42mA\x1b[0mB");
+    let b = grid.screen_rows()[0][1];
+    assert_eq!(b.fg, Color::Default);
+    assert_eq!(b.bg, Color::Default);
+    assert!(b.flags.is_empty());
+}
+
+#[test]
+fn sgr21_double_underline_renders_as_underline() {
+    let grid = parse_and_apply(b"\x1b[21mA\x1b[24mB");
+    assert!(flags_at(&grid, 0).contains(CellFlags::UNDERLINE));
+    assert!(!flags_at(&grid, 1).contains(CellFlags::UNDERLINE));
+}
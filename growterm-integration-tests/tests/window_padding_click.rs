@@ -0,0 +1,90 @@
+use growterm_app::config::WindowPadding;
+use growterm_app::selection::{self, Selection};
+use growterm_app::tab::TabManager;
+use growterm_grid::Grid;
+use growterm_types::TerminalCommand;
+
+const CELL_W: f32 = 10.0;
+const CELL_H: f32 = 20.0;
+const TAB_BAR_H: f32 = 30.0;
+
+fn fill_grid(grid: &mut Grid, lines: &[&str]) {
+    for (i, line) in lines.iter().enumerate() {
+        for ch in line.chars() {
+            grid.apply(&TerminalCommand::Print(ch));
+        }
+        if i + 1 < lines.len() {
+            grid.apply(&TerminalCommand::Newline);
+            grid.apply(&TerminalCommand::CarriageReturn);
+        }
+    }
+}
+
+/// 2x 배율 창, 여백 top 10 / right 5 / bottom 10 / left 15 (논리 픽셀)
+fn padded_tabs() -> TabManager {
+    let mut tabs = TabManager::new();
+    tabs.set_window_padding(WindowPadding { top: 10.0, right: 5.0, bottom: 10.0, left: 15.0 }, 2.0);
+    tabs
+}
+
+/// Pixel at the middle of a cell, the way the renderer places it.
+fn cell_center(tabs: &TabManager, row: u16, col: u16) -> (f32, f32) {
+    let (x, y) = tabs.content_origin(TAB_BAR_H, 0.0, false);
+    (x + (col as f32 + 0.5) * CELL_W, y + (row as f32 + 0.5) * CELL_H)
+}
+
+fn click(tabs: &TabManager, x: f32, y: f32) -> (u16, u16) {
+    let origin = tabs.content_origin(TAB_BAR_H, 0.0, false);
+    selection::mouse_pixel_to_cell(x, y, CELL_W, CELL_H, origin)
+}
+
+#[test]
+fn padding_shrinks_grid_size() {
+    let tabs = padded_tabs();
+    // (800 - 30 - 10) / 10 = 76, (600 - 20 - 20) / 20 = 28
+    assert_eq!(tabs.term_cols(800, CELL_W), 76);
+    assert_eq!(tabs.term_rows(600, CELL_H, TAB_BAR_H, 0.0, false), 28);
+}
+
+#[test]
+fn click_maps_to_drawn_cell() {
+    let tabs = padded_tabs();
+    for (row, col) in [(0, 0), (0, 6), (1, 3), (27, 75)] {
+        let (x, y) = cell_center(&tabs, row, col);
+        assert_eq!(click(&tabs, x, y), (row, col), "cell ({row}, {col}) at ({x}, {y})");
+    }
+}
+
+#[test]
+fn click_in_padding_clamps_to_first_cell() {
+    let tabs = padded_tabs();
+    // 왼쪽/위 여백 안 (30px, 20px 안쪽)
+    assert_eq!(click(&tabs, 10.0, 10.0), (0, 0));
+    assert_eq!(click(&tabs, 29.0, 45.0), (1, 0));
+}
+
+#[test]
+fn drag_selects_text_under_padded_cells() {
+    let tabs = padded_tabs();
+    let mut grid = Grid::new(tabs.term_cols(800, CELL_W), tabs.term_rows(600, CELL_H, TAB_BAR_H, 0.0, false));
+    fill_grid(&mut grid, &["Hello World", "Rust Lang"]);
+
+    let (x0, y0) = cell_center(&tabs, 0, 6);
+    let (x1, y1) = cell_center(&tabs, 1, 3);
+    let mut sel = Selection::default();
+    let (row, col) = click(&tabs, x0, y0);
+    sel.begin(row as u32, col);
+    let (row, col) = click(&tabs, x1, y1);
+    sel.update(row as u32, col);
+    sel.finish();
+
+    assert_eq!(selection::extract_text_absolute(&grid, &sel), "World\nRust");
+}
+
+#[test]
+fn without_padding_same_pixel_hits_another_cell() {
+    let tabs = padded_tabs();
+    let (x, y) = cell_center(&tabs, 0, 6);
+    let plain = TabManager::new();
+    assert_eq!(click(&plain, x, y), (1, 9));
+}
@@ -0,0 +1,162 @@
+//! IME 조합 상태 머신. NSTextInputClient 콜백이 여기로 위임하고,
+//! 범위와 후보창 위치 계산은 AppKit 없이 테스트한다.
+//!
+//! 범위는 모두 조합 문자열 안의 UTF-16 오프셋 (NSRange 단위).
+
+use unicode_width::UnicodeWidthChar;
+
+/// Marked text of an in-progress composition: a dead key (`´`), Korean
+/// jamo being assembled, or Japanese/Chinese reading with the clause the
+/// IME is converting selected.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct Composition {
+    marked: String,
+    /// (location, length) of the selected clause or caret
+    selected: (usize, usize),
+}
+
+impl Composition {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn marked_text(&self) -> &str {
+        &self.marked
+    }
+
+    pub fn is_composing(&self) -> bool {
+        !self.marked.is_empty()
+    }
+
+    /// `setMarkedText:selectedRange:`. An empty `text` cancels the
+    /// composition. `selected` is clamped to the new text.
+    pub fn set_marked(&mut self, text: &str, selected: (usize, usize)) {
+        self.marked = text.to_string();
+        let len = utf16_len(&self.marked);
+        let location = selected.0.min(len);
+        self.selected = (location, selected.1.min(len - location));
+    }
+
+    /// `insertText:`. Returns true if the text ended a composition, so
+    /// the app has a preedit to clear.
+    pub fn commit(&mut self) -> bool {
+        self.clear()
+    }
+
+    /// `unmarkText`, or dropping marked text the input context forgot.
+    /// Returns true if anything was marked.
+    pub fn clear(&mut self) -> bool {
+        let was_composing = self.is_composing();
+        self.marked.clear();
+        self.selected = (0, 0);
+        was_composing
+    }
+
+    /// `markedRange`: None (NSNotFound) while nothing is marked.
+    pub fn marked_range(&self) -> Option<(usize, usize)> {
+        self.is_composing().then(|| (0, utf16_len(&self.marked)))
+    }
+
+    /// `selectedRange`: the selected clause or caret inside the marked
+    /// text, None (NSNotFound) while nothing is marked.
+    pub fn selected_range(&self) -> Option<(usize, usize)> {
+        self.is_composing().then_some(self.selected)
+    }
+
+    /// `firstRectForCharacterRange:`: rect of the character at `location`
+    /// given the rect of the cell the preedit starts in. Each character
+    /// before it moves the rect one cell (two for wide characters), so
+    /// candidate windows follow the clause being converted.
+    pub fn char_rect(&self, start_cell: (f64, f64, f64, f64), location: usize) -> (f64, f64, f64, f64) {
+        let (x, y, cell_w, cell_h) = start_cell;
+        let mut units = 0;
+        let mut cells = 0;
+        for ch in self.marked.chars() {
+            if units >= location {
+                break;
+            }
+            units += ch.len_utf16();
+            cells += ch.width().unwrap_or(1);
+        }
+        (x + cells as f64 * cell_w, y, cell_w, cell_h)
+    }
+}
+
+fn utf16_len(text: &str) -> usize {
+    text.encode_utf16().count()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CELL: (f64, f64, f64, f64) = (100.0, 40.0, 10.0, 20.0);
+
+    #[test]
+    fn dead_key_is_marked_then_committed() {
+        let mut comp = Composition::new();
+        // Option+E → ´, then E → é
+        comp.set_marked("´", (1, 0));
+        assert_eq!(comp.marked_range(), Some((0, 1)));
+        assert_eq!(comp.selected_range(), Some((1, 0)));
+        assert!(comp.commit());
+        assert_eq!(comp.marked_range(), None);
+        assert_eq!(comp.selected_range(), None);
+    }
+
+    #[test]
+    fn commit_without_composition_reports_nothing_to_clear() {
+        let mut comp = Composition::new();
+        assert!(!comp.commit());
+    }
+
+    #[test]
+    fn japanese_clause_selection_moves_candidate_rect() {
+        let mut comp = Composition::new();
+        comp.set_marked("にほんご", (4, 0));
+        comp.set_marked("日本語を", (3, 1));
+        assert_eq!(comp.selected_range(), Some((3, 1)));
+        // 앞의 세 글자가 각각 두 칸
+        assert_eq!(comp.char_rect(CELL, 3), (160.0, 40.0, 10.0, 20.0));
+        assert_eq!(comp.char_rect(CELL, 0), CELL);
+    }
+
+    #[test]
+    fn ranges_count_utf16_units() {
+        let mut comp = Composition::new();
+        comp.set_marked("a😀", (3, 0));
+        assert_eq!(comp.marked_range(), Some((0, 3)));
+        assert_eq!(comp.char_rect(CELL, 3), (130.0, 40.0, 10.0, 20.0));
+    }
+
+    #[test]
+    fn selected_range_is_clamped_to_marked_text() {
+        let mut comp = Composition::new();
+        comp.set_marked("ni", (5, 9));
+        assert_eq!(comp.selected_range(), Some((2, 0)));
+    }
+
+    #[test]
+    fn empty_marked_text_cancels() {
+        let mut comp = Composition::new();
+        comp.set_marked("한", (1, 0));
+        comp.set_marked("", (0, 0));
+        assert!(!comp.is_composing());
+        assert_eq!(comp.marked_range(), None);
+    }
+
+    #[test]
+    fn clear_noop_when_empty() {
+        let mut comp = Composition::new();
+        assert!(!comp.clear());
+        assert!(comp.marked_text().is_empty());
+    }
+
+    #[test]
+    fn clear_drops_existing_text() {
+        let mut comp = Composition::new();
+        comp.set_marked("한글", (2, 0));
+        assert!(comp.clear());
+        assert!(comp.marked_text().is_empty());
+    }
+}
@@ -1,15 +1,26 @@
 use std::cell::RefCell;
+use std::rc::Rc;
 use std::sync::{mpsc, Arc};
 
 use objc2::rc::Retained;
-use objc2::{define_class, DefinedClass, MainThreadMarker, MainThreadOnly};
-use objc2_app_kit::{NSApplication, NSApplicationDelegate};
-use objc2_foundation::{NSNotification, NSObject, NSObjectProtocol};
+use objc2::runtime::AnyObject;
+use objc2::{define_class, msg_send, sel, DefinedClass, MainThreadMarker, MainThreadOnly};
+use objc2_app_kit::{
+    NSApplication, NSApplicationDelegate, NSMenu, NSMenuItem, NSPasteboard, NSWindow, NSWindowOrderingMode,
+    NSWindowTabbingMode,
+};
+use objc2_foundation::{NSNotification, NSObject, NSObjectProtocol, NSString};
 
 use crate::event::AppEvent;
+use crate::view::TerminalView;
 use crate::window::MacWindow;
 
-type SetupFn = Box<dyn FnOnce(Arc<MacWindow>, mpsc::Receiver<AppEvent>) + 'static>;
+type SetupFn = Box<dyn Fn(Arc<MacWindow>, mpsc::Receiver<AppEvent>) + 'static>;
+
+thread_local! {
+    /// 윈도우마다 호출되는 setup 콜백 (메인 스레드 전용)
+    static SETUP: RefCell<Option<Rc<SetupFn>>> = const { RefCell::new(None) };
+}
 
 pub(crate) struct DelegateIvars {
     setup: RefCell<Option<SetupFn>>,
@@ -24,6 +35,44 @@ define_class! {
     #[ivars = DelegateIvars]
     pub(crate) struct AppDelegate;
 
+    impl AppDelegate {
+        #[unsafe(method(newTabFromDock:))]
+        fn new_tab_from_dock(&self, _sender: &AnyObject) {
+            let mtm = MainThreadMarker::new().unwrap();
+            NSApplication::sharedApplication(mtm).activate();
+            match front_terminal_view(mtm) {
+                Some(view) => view.send_event(AppEvent::NewTab),
+                None => open_window(mtm, self.ivars().window_size, None, None),
+            }
+        }
+
+        #[unsafe(method(newWindowFromDock:))]
+        fn new_window_from_dock(&self, _sender: &AnyObject) {
+            let mtm = MainThreadMarker::new().unwrap();
+            NSApplication::sharedApplication(mtm).activate();
+            open_window(mtm, self.ivars().window_size, None, None);
+        }
+
+        /// Finder 서비스 "New growTerm Tab Here" (Info.plist의 NSServices)
+        #[unsafe(method(openTabHere:userData:error:))]
+        fn open_tab_here(&self, pasteboard: &NSPasteboard, _user_data: Option<&NSString>, _error: *mut *mut NSString) {
+            let mtm = MainThreadMarker::new().unwrap();
+            let Some(paths) = crate::view::extract_dropped_paths(pasteboard) else {
+                return;
+            };
+            NSApplication::sharedApplication(mtm).activate();
+            let view = front_terminal_view(mtm).or_else(|| {
+                open_window(mtm, self.ivars().window_size, None, None);
+                front_terminal_view(mtm)
+            });
+            if let Some(view) = view {
+                for path in paths {
+                    view.send_event(AppEvent::NewTabAt(service_dir(&path)));
+                }
+            }
+        }
+    }
+
     unsafe impl NSObjectProtocol for AppDelegate {}
 
     unsafe impl NSApplicationDelegate for AppDelegate {
@@ -32,30 +81,48 @@ define_class! {
             let mtm = MainThreadMarker::new().unwrap();
             let app = NSApplication::sharedApplication(mtm);
             app.activate();
+            let _: () = unsafe { msg_send![&app, setServicesProvider: self] };
 
             // 윈도우 생성을 다음 런루프 틱으로 지연.
             // didFinishLaunching 시점에는 IMK 입력 서버의 mach port 연결이
             // 아직 완료되지 않아, 즉시 윈도우를 만들면 자소 분리가 발생함.
             let setup = self.ivars().setup.borrow_mut().take();
-            let (w, h) = self.ivars().window_size;
+            let size = self.ivars().window_size;
             let pos = self.ivars().window_position;
             if let Some(setup) = setup {
+                SETUP.with(|cell| *cell.borrow_mut() = Some(Rc::new(setup)));
                 dispatch_async_main(move || {
                     let mtm = MainThreadMarker::new().unwrap();
-                    let mac_window = MacWindow::new(mtm, "growterm", w, h, pos);
-                    let (tx, rx) = mpsc::channel();
-                    mac_window.set_sender(tx);
-                    mac_window.show();
-                    if let Some((x, y)) = pos {
-                        mac_window.set_position(x, y);
-                    }
-
-                    let mac_window = Arc::new(mac_window);
-                    setup(mac_window, rx);
+                    open_window(mtm, size, pos, None);
                 });
             }
         }
 
+        #[unsafe(method_id(applicationDockMenu:))]
+        fn application_dock_menu(&self, _app: &NSApplication) -> Option<Retained<NSMenu>> {
+            let mtm = MainThreadMarker::new().unwrap();
+            let menu = NSMenu::new(mtm);
+            for (title, action) in [("New Tab", sel!(newTabFromDock:)), ("New Window", sel!(newWindowFromDock:))] {
+                let item = unsafe {
+                    NSMenuItem::initWithTitle_action_keyEquivalent(
+                        mtm.alloc(),
+                        &NSString::from_str(title),
+                        Some(action),
+                        &NSString::from_str(""),
+                    )
+                };
+                unsafe { item.setTarget(Some(self)) };
+                menu.addItem(&item);
+            }
+            Some(menu)
+        }
+
+        /// Cmd+Q, Dock 종료, 마지막 윈도우 닫기 모두 여기를 지남
+        #[unsafe(method(applicationWillTerminate:))]
+        fn will_terminate(&self, _notification: &NSNotification) {
+            growterm_window::run_before_exit();
+        }
+
         #[unsafe(method(applicationShouldTerminateAfterLastWindowClosed:))]
         fn should_terminate_after_last_window_closed(&self, _app: &NSApplication) -> bool {
             true
@@ -65,6 +132,58 @@ define_class! {
 
 use crate::dispatch::dispatch_async_main;
 
+/// 윈도우를 만들어 setup 콜백에 넘김. `tab_of`가 있으면 그 윈도우의 네이티브 탭으로 붙임.
+pub(crate) fn open_window(
+    mtm: MainThreadMarker,
+    size: (f64, f64),
+    pos: Option<(f64, f64)>,
+    tab_of: Option<&NSWindow>,
+) {
+    let Some(setup) = SETUP.with(|cell| cell.borrow().clone()) else {
+        return;
+    };
+    let (w, h) = size;
+    let mac_window = MacWindow::new(mtm, "growterm", w, h, pos);
+    let (tx, rx) = mpsc::channel();
+    mac_window.set_sender(tx);
+    match tab_of {
+        Some(parent) => {
+            parent.setTabbingMode(NSWindowTabbingMode::Preferred);
+            mac_window.ns_window().setTabbingMode(NSWindowTabbingMode::Preferred);
+            parent.addTabbedWindow_ordered(mac_window.ns_window(), NSWindowOrderingMode::Above);
+            mac_window.show();
+        }
+        None => {
+            mac_window.show();
+            if let Some((x, y)) = pos {
+                mac_window.set_position(x, y);
+            }
+        }
+    }
+
+    setup(Arc::new(mac_window), rx);
+}
+
+/// 맨 앞 터미널 윈도우의 뷰. 경고창 같은 다른 윈도우는 건너뜀
+fn front_terminal_view(mtm: MainThreadMarker) -> Option<Retained<TerminalView>> {
+    NSApplication::sharedApplication(mtm)
+        .orderedWindows()
+        .iter()
+        .filter_map(|window| window.contentView())
+        .find_map(|view| view.downcast::<TerminalView>().ok())
+}
+
+/// 서비스로 받은 경로가 파일이면 그 파일이 있는 폴더에서 연다
+fn service_dir(path: &str) -> String {
+    let p = std::path::Path::new(path);
+    if p.is_dir() {
+        return path.to_string();
+    }
+    p.parent()
+        .map(|parent| parent.to_string_lossy().into_owned())
+        .unwrap_or_else(|| path.to_string())
+}
+
 impl AppDelegate {
     pub(crate) fn new(mtm: MainThreadMarker, window_size: (f64, f64), window_position: Option<(f64, f64)>, setup: SetupFn) -> Retained<Self> {
         let this = mtm.alloc::<Self>().set_ivars(DelegateIvars {
@@ -76,3 +195,21 @@ impl AppDelegate {
     }
 }
 
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn service_dir_keeps_folders_and_uses_parent_of_files() {
+        let dir = std::env::temp_dir().join(format!("growterm-service-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let file = dir.join("notes.txt");
+        std::fs::write(&file, b"").unwrap();
+        let dir_str = dir.to_string_lossy().into_owned();
+
+        assert_eq!(service_dir(&dir_str), dir_str);
+        assert_eq!(service_dir(&file.to_string_lossy()), dir_str);
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+}
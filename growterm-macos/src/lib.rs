@@ -1,13 +1,14 @@
+pub mod composition;
 mod delegate;
 mod dispatch;
-pub mod event;
-pub mod key_convert;
+mod present;
+mod quick_look;
 #[doc(hidden)]
 pub mod view;
 mod window;
 
-pub use event::{AppEvent, Modifiers};
-pub use key_convert::convert_key;
+pub use growterm_window::{convert_key, event, key_convert, AppEvent, Modifiers, MouseCursor, TerminalWindow};
+pub use composition::Composition;
 pub use window::MacWindow;
 
 /// 통합 테스트용 헬퍼. 프로덕션 코드에서 사용하지 않음.
@@ -34,7 +35,10 @@ use std::sync::mpsc;
 
 use objc2::runtime::ProtocolObject;
 use objc2::MainThreadMarker;
-use objc2_app_kit::{NSApplication, NSApplicationActivationPolicy, NSApplicationDelegate, NSMenu, NSMenuItem};
+use objc2_app_kit::{
+    NSApplication, NSApplicationActivationPolicy, NSApplicationDelegate, NSEventModifierFlags, NSMenu,
+    NSMenuItem,
+};
 use objc2_foundation::NSString;
 
 use delegate::AppDelegate;
@@ -44,10 +48,11 @@ use delegate::AppDelegate;
 /// 이 함수는 메인 스레드에서 호출되어야 하며 반환하지 않음.
 /// `setup` 콜백은 applicationDidFinishLaunching에서 호출되어
 /// IMK 입력 서버 연결이 완료된 상태에서 윈도우가 생성됨.
+/// 이후 `TerminalWindow::open_window`로 연 윈도우마다 다시 호출됨.
 pub fn run(
     window_size: (f64, f64),
     window_position: Option<(f64, f64)>,
-    setup: impl FnOnce(std::sync::Arc<MacWindow>, mpsc::Receiver<AppEvent>) + 'static,
+    setup: impl Fn(std::sync::Arc<MacWindow>, mpsc::Receiver<AppEvent>) + 'static,
 ) -> ! {
     // bare 바이너리(cargo run)에서도 IMK 입력 서버가 연결되도록
     // 번들 ID를 런타임에 설정. .app 번들로 실행 시에는 Info.plist 값이 이미 있으므로 무해.
@@ -83,7 +88,7 @@ fn setup_main_menu(app: &NSApplication) {
         let quit_item = NSMenuItem::initWithTitle_action_keyEquivalent(
             mtm.alloc(),
             &quit_title,
-            Some(objc2::sel!(terminate:)),
+            Some(objc2::sel!(quitRequested:)),
             &quit_key,
         );
         app_menu.addItem(&quit_item);
@@ -147,8 +152,151 @@ fn setup_main_menu(app: &NSApplication) {
             &reload_key,
         );
         view_menu.addItem(&reload_item);
+
+        let diagnostics_title = NSString::from_str("Diagnostics");
+        let diagnostics_key = NSString::from_str("");
+        let diagnostics_item = NSMenuItem::initWithTitle_action_keyEquivalent(
+            mtm.alloc(),
+            &diagnostics_title,
+            Some(objc2::sel!(showDiagnostics:)),
+            &diagnostics_key,
+        );
+        view_menu.addItem(&diagnostics_item);
+
+        let debug_view_title = NSString::from_str("Show Whitespace & Controls");
+        let debug_view_key = NSString::from_str("");
+        let debug_view_item = NSMenuItem::initWithTitle_action_keyEquivalent(
+            mtm.alloc(),
+            &debug_view_title,
+            Some(objc2::sel!(toggleDebugView:)),
+            &debug_view_key,
+        );
+        view_menu.addItem(&debug_view_item);
+
+        // 위 항목들은 window.rs에서 인덱스로 체크 표시하므로, 새 항목은 여기 아래에 추가
+        let skip_break_title = NSString::from_str("Skip Break");
+        let skip_break_key = NSString::from_str("");
+        let skip_break_item = NSMenuItem::initWithTitle_action_keyEquivalent(
+            mtm.alloc(),
+            &skip_break_title,
+            Some(objc2::sel!(skipBreak:)),
+            &skip_break_key,
+        );
+        view_menu.addItem(&skip_break_item);
+
+        let extend_break_title = NSString::from_str("Extend Break (+5 min)");
+        let extend_break_key = NSString::from_str("");
+        let extend_break_item = NSMenuItem::initWithTitle_action_keyEquivalent(
+            mtm.alloc(),
+            &extend_break_title,
+            Some(objc2::sel!(extendBreak:)),
+            &extend_break_key,
+        );
+        view_menu.addItem(&extend_break_item);
+
+        // Cmd+Shift+R은 Reload Config이 쓰므로 Cmd+Option+R
+        let response_report_title = NSString::from_str("Response Report");
+        let response_report_key = NSString::from_str("r");
+        let response_report_item = NSMenuItem::initWithTitle_action_keyEquivalent(
+            mtm.alloc(),
+            &response_report_title,
+            Some(objc2::sel!(toggleResponseReport:)),
+            &response_report_key,
+        );
+        response_report_item.setKeyEquivalentModifierMask(
+            NSEventModifierFlags::Command | NSEventModifierFlags::Option,
+        );
+        view_menu.addItem(&response_report_item);
+
+        let export_times_title = NSString::from_str("Export Response Times…");
+        let export_times_key = NSString::from_str("");
+        let export_times_item = NSMenuItem::initWithTitle_action_keyEquivalent(
+            mtm.alloc(),
+            &export_times_title,
+            Some(objc2::sel!(exportResponseTimes:)),
+            &export_times_key,
+        );
+        view_menu.addItem(&export_times_item);
+
+        let latency_hud_title = NSString::from_str("Latency HUD");
+        let latency_hud_key = NSString::from_str("");
+        let latency_hud_item = NSMenuItem::initWithTitle_action_keyEquivalent(
+            mtm.alloc(),
+            &latency_hud_title,
+            Some(objc2::sel!(toggleLatencyHud:)),
+            &latency_hud_key,
+        );
+        view_menu.addItem(&latency_hud_item);
+
+        view_menu.addItem(&NSMenuItem::separatorItem(mtm));
+
+        // NSWindow가 처리하는 표준 액션. 제목(Enter/Exit)도 AppKit이 바꿔줌
+        let fullscreen_title = NSString::from_str("Enter Full Screen");
+        let fullscreen_key = NSString::from_str("f");
+        let fullscreen_item = NSMenuItem::initWithTitle_action_keyEquivalent(
+            mtm.alloc(),
+            &fullscreen_title,
+            Some(objc2::sel!(toggleFullScreen:)),
+            &fullscreen_key,
+        );
+        fullscreen_item.setKeyEquivalentModifierMask(
+            NSEventModifierFlags::Command | NSEventModifierFlags::Control,
+        );
+        view_menu.addItem(&fullscreen_item);
         view_menu_item.setSubmenu(Some(&view_menu));
 
+        // Shell menu — 프로필 항목은 앱이 set_profiles로 채움
+        let shell_menu_item = NSMenuItem::new(mtm);
+        menubar.addItem(&shell_menu_item);
+
+        let shell_menu = NSMenu::initWithTitle(mtm.alloc(), &NSString::from_str("Shell"));
+        let profile_item = NSMenuItem::new(mtm);
+        profile_item.setTitle(&NSString::from_str("New Tab With Profile"));
+        let profile_menu = NSMenu::initWithTitle(mtm.alloc(), &NSString::from_str("New Tab With Profile"));
+        profile_item.setSubmenu(Some(&profile_menu));
+        shell_menu.addItem(&profile_item);
+
+        let clear_title = NSString::from_str("Clear Scrollback & Screen");
+        let clear_key = NSString::from_str("k");
+        let clear_item = NSMenuItem::initWithTitle_action_keyEquivalent(
+            mtm.alloc(),
+            &clear_title,
+            Some(objc2::sel!(clearScrollback:)),
+            &clear_key,
+        );
+        shell_menu.addItem(&clear_item);
+
+        let export_title = NSString::from_str("Export Buffer…");
+        let export_key = NSString::from_str("s");
+        let export_item = NSMenuItem::initWithTitle_action_keyEquivalent(
+            mtm.alloc(),
+            &export_title,
+            Some(objc2::sel!(exportBuffer:)),
+            &export_key,
+        );
+        shell_menu.addItem(&export_item);
+
+        let copy_screen_title = NSString::from_str("Copy Screen as Text");
+        let copy_screen_key = NSString::from_str("");
+        let copy_screen_item = NSMenuItem::initWithTitle_action_keyEquivalent(
+            mtm.alloc(),
+            &copy_screen_title,
+            Some(objc2::sel!(copyScreenText:)),
+            &copy_screen_key,
+        );
+        shell_menu.addItem(&copy_screen_item);
+
+        let screenshot_title = NSString::from_str("Save Screenshot (PNG)…");
+        let screenshot_key = NSString::from_str("");
+        let screenshot_item = NSMenuItem::initWithTitle_action_keyEquivalent(
+            mtm.alloc(),
+            &screenshot_title,
+            Some(objc2::sel!(saveScreenshot:)),
+            &screenshot_key,
+        );
+        shell_menu.addItem(&screenshot_item);
+        shell_menu_item.setSubmenu(Some(&shell_menu));
+
         app.setMainMenu(Some(&menubar));
     }
 }
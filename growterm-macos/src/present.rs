@@ -0,0 +1,73 @@
+//! GPU 없이 그린 프레임(RGBA8)을 뷰 레이어의 contents로 표시.
+
+use std::ffi::c_void;
+
+use objc2::runtime::AnyObject;
+use objc2_app_kit::NSView;
+
+type CFTypeRef = *const c_void;
+
+/// kCGImageAlphaNoneSkipLast: RGBX, 알파 무시
+const ALPHA_NONE_SKIP_LAST: u32 = 5;
+const RENDERING_INTENT_DEFAULT: i32 = 0;
+
+#[link(name = "CoreFoundation", kind = "framework")]
+extern "C" {
+    fn CFDataCreate(allocator: CFTypeRef, bytes: *const u8, length: isize) -> CFTypeRef;
+    fn CFRelease(cf: CFTypeRef);
+}
+
+#[link(name = "CoreGraphics", kind = "framework")]
+extern "C" {
+    fn CGColorSpaceCreateDeviceRGB() -> CFTypeRef;
+    fn CGDataProviderCreateWithCFData(data: CFTypeRef) -> CFTypeRef;
+    #[allow(clippy::too_many_arguments)]
+    fn CGImageCreate(
+        width: usize,
+        height: usize,
+        bits_per_component: usize,
+        bits_per_pixel: usize,
+        bytes_per_row: usize,
+        space: CFTypeRef,
+        bitmap_info: u32,
+        provider: CFTypeRef,
+        decode: *const f64,
+        should_interpolate: bool,
+        intent: i32,
+    ) -> CFTypeRef;
+}
+
+/// 메인 스레드에서 호출. 레이어가 이미지를 retain하므로 여기서 만든 객체는 모두 해제.
+pub(crate) fn set_layer_image(view: &NSView, rgba: &[u8], width: u32, height: u32) {
+    let Some(layer) = view.layer() else { return };
+    let (width, height) = (width as usize, height as usize);
+    if rgba.len() < width * height * 4 {
+        return;
+    }
+    unsafe {
+        let data = CFDataCreate(std::ptr::null(), rgba.as_ptr(), (width * height * 4) as isize);
+        let provider = CGDataProviderCreateWithCFData(data);
+        let space = CGColorSpaceCreateDeviceRGB();
+        let image = CGImageCreate(
+            width,
+            height,
+            8,
+            32,
+            width * 4,
+            space,
+            ALPHA_NONE_SKIP_LAST,
+            provider,
+            std::ptr::null(),
+            false,
+            RENDERING_INTENT_DEFAULT,
+        );
+        if !image.is_null() {
+            let _: () = objc2::msg_send![&*layer, setContents: image as *mut AnyObject];
+        }
+        for object in [image, space, provider, data] {
+            if !object.is_null() {
+                CFRelease(object);
+            }
+        }
+    }
+}
@@ -0,0 +1,35 @@
+//! QLPreviewPanel 브리지. 패널은 응답자 체인에서 컨트롤러를 찾으므로
+//! 데이터 소스 역할은 TerminalView가 맡음 (view.rs).
+
+use objc2::msg_send;
+use objc2::runtime::{AnyClass, AnyObject};
+use objc2_foundation::{NSString, NSURL};
+
+use crate::view::TerminalView;
+
+// QLPreviewPanel은 Quartz(QuickLookUI)에 있음
+#[link(name = "Quartz", kind = "framework")]
+extern "C" {}
+
+/// `path`를 Quick Look 패널로 보여줌. 패널이 이미 떠 있으면 닫음. 메인 스레드 전용.
+pub(crate) fn toggle(view: &TerminalView, path: &str) {
+    let Some(class) = AnyClass::get(c"QLPreviewPanel") else {
+        return;
+    };
+    let nil: *const AnyObject = std::ptr::null();
+    unsafe {
+        let panel: *mut AnyObject = msg_send![class, sharedPreviewPanel];
+        if panel.is_null() {
+            return;
+        }
+        let visible: bool = msg_send![panel, isVisible];
+        if visible {
+            let _: () = msg_send![panel, orderOut: nil];
+            return;
+        }
+        view.set_quick_look_url(Some(NSURL::fileURLWithPath(&NSString::from_str(path))));
+        let _: () = msg_send![panel, updateController];
+        let _: () = msg_send![panel, reloadData];
+        let _: () = msg_send![panel, makeKeyAndOrderFront: nil];
+    }
+}
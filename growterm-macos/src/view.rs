@@ -1,5 +1,5 @@
 use std::cell::{Cell, RefCell};
-use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::atomic::{AtomicU8, Ordering};
 use std::sync::mpsc::Sender;
 
 use objc2::rc::Retained;
@@ -7,17 +7,29 @@ use objc2::runtime::{AnyObject, NSObjectProtocol, Sel};
 use objc2::{define_class, msg_send, DefinedClass, MainThreadMarker, MainThreadOnly};
 use objc2_app_kit::{
     NSDragOperation, NSDraggingDestination, NSDraggingInfo, NSEvent, NSEventModifierFlags,
-    NSTextInputClient, NSView,
+    NSTextInputClient, NSView, NSWindowDelegate,
 };
 use objc2_foundation::{
-    NSArray, NSAttributedString, NSAttributedStringKey, NSCopying, NSPoint, NSRange,
+    NSArray, NSAttributedString, NSAttributedStringKey, NSCopying, NSNotification, NSPoint, NSRange,
     NSRangePointer, NSRect, NSString, NSURL, NSUInteger,
 };
 
+use crate::composition::Composition;
 use crate::event::{AppEvent, Modifiers};
-
-/// App thread sets this; view reads it on mouseMoved to apply cursor synchronously.
-pub static POINTING_HAND_CURSOR: AtomicBool = AtomicBool::new(false);
+use crate::MouseCursor;
+
+/// App thread sets this (`MouseCursor as u8`); view reads it on mouseMoved to apply cursor synchronously.
+pub static MOUSE_CURSOR: AtomicU8 = AtomicU8::new(MouseCursor::Arrow as u8);
+
+/// Set the NSCursor matching `MOUSE_CURSOR`. Main thread only.
+pub(crate) fn apply_mouse_cursor() {
+    let cursor = match MOUSE_CURSOR.load(Ordering::Relaxed) {
+        c if c == MouseCursor::IBeam as u8 => objc2_app_kit::NSCursor::IBeamCursor(),
+        c if c == MouseCursor::PointingHand as u8 => objc2_app_kit::NSCursor::pointingHandCursor(),
+        _ => objc2_app_kit::NSCursor::arrowCursor(),
+    };
+    cursor.set();
+}
 
 /// IME 상태 머신 (WezTerm 방식)
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -34,7 +46,7 @@ enum ImeState {
 pub struct Ivars {
     sender: RefCell<Option<Sender<AppEvent>>>,
     ime_state: Cell<ImeState>,
-    marked_text: RefCell<String>,
+    composition: RefCell<Composition>,
     ime_cursor_rect: RefCell<Option<NSRect>>,
     current_event: RefCell<Option<Retained<NSEvent>>>,
     pending_resize: Cell<Option<(u32, u32)>>,
@@ -42,6 +54,8 @@ pub struct Ivars {
     copy_mode_bypass_ime: Cell<bool>,
     /// insertText:가 조합 중인 텍스트를 확정했는지 추적
     ime_committed_from_composition: Cell<bool>,
+    /// Quick Look 패널에 보여줄 파일
+    quick_look_url: RefCell<Option<Retained<NSURL>>>,
 }
 
 define_class! {
@@ -104,13 +118,12 @@ define_class! {
                 return;
             }
 
-            let has_local_marked_text = !self.ivars().marked_text.borrow().is_empty();
+            let has_local_marked_text = self.ivars().composition.borrow().is_composing();
             let cleared_stale_marked_text = if should_clear_stale_marked_text(
                 has_local_marked_text,
                 self.input_context_has_marked_text(),
             ) {
-                let mut marked = self.ivars().marked_text.borrow_mut();
-                clear_stale_marked_text(&mut marked)
+                self.ivars().composition.borrow_mut().clear()
             } else {
                 false
             };
@@ -144,6 +157,26 @@ define_class! {
             self.ivars().current_event.replace(None);
         }
 
+        #[unsafe(method(keyUp:))]
+        fn key_up(&self, event: &NSEvent) {
+            // IME 조합 중이거나 Cmd 조합이면 키 해제를 보고하지 않음
+            if self.ivars().composition.borrow().is_composing() {
+                return;
+            }
+            let modifiers = convert_modifier_flags(event.modifierFlags());
+            if modifiers.contains(Modifiers::SUPER) {
+                return;
+            }
+            let characters = event
+                .charactersIgnoringModifiers()
+                .map(|s| s.to_string());
+            self.send_event(AppEvent::KeyRelease {
+                keycode: event.keyCode(),
+                characters,
+                modifiers,
+            });
+        }
+
         #[unsafe(method(mouseMoved:))]
         fn mouse_moved(&self, event: &NSEvent) {
             let (x, y) = self.event_location_in_backing(event);
@@ -151,9 +184,7 @@ define_class! {
             let modifiers = convert_modifier_flags(event.modifierFlags());
             self.send_event(AppEvent::MouseMoved(x, y, modifiers));
             // Apply cursor synchronously on main thread to prevent macOS from resetting it
-            if POINTING_HAND_CURSOR.load(Ordering::Relaxed) {
-                objc2_app_kit::NSCursor::pointingHandCursor().set();
-            }
+            apply_mouse_cursor();
         }
 
         #[unsafe(method(flagsChanged:))]
@@ -162,9 +193,7 @@ define_class! {
             let (x, y) = self.ivars().last_mouse_pos.get();
             let modifiers = convert_modifier_flags(event.modifierFlags());
             self.send_event(AppEvent::MouseMoved(x, y, modifiers));
-            if POINTING_HAND_CURSOR.load(Ordering::Relaxed) {
-                objc2_app_kit::NSCursor::pointingHandCursor().set();
-            }
+            apply_mouse_cursor();
         }
 
         #[unsafe(method(mouseDown:))]
@@ -188,16 +217,18 @@ define_class! {
 
         #[unsafe(method(scrollWheel:))]
         fn scroll_wheel(&self, event: &NSEvent) {
-            let delta_y = if event.hasPreciseScrollingDeltas() {
+            // 관성 스크롤은 macOS가 momentumPhase 이벤트로 이어서 보내 줌
+            let precise = event.hasPreciseScrollingDeltas();
+            let delta_y = if precise {
                 // 트랙패드: 픽셀 단위 → 그대로 전달 (app에서 누적)
-                event.scrollingDeltaY()
+                event.scrollingDeltaY() * self.backing_scale_factor()
             } else {
                 // 마우스 휠: line 단위 → 셀 높이를 곱해서 픽셀 단위로 변환
                 let scale = self.backing_scale_factor();
                 event.scrollingDeltaY() * 40.0 * scale
             };
             if delta_y != 0.0 {
-                self.send_event(AppEvent::ScrollWheel(delta_y));
+                self.send_event(AppEvent::ScrollWheel(delta_y, precise));
             }
         }
 
@@ -229,16 +260,42 @@ define_class! {
             }
         }
 
+        /// Cmd+Q: 바로 terminate:하지 않고 앱이 실행 중인 프로세스를 확인
+        #[unsafe(method(quitRequested:))]
+        fn quit_requested(&self, _sender: &AnyObject) {
+            self.send_event(AppEvent::QuitRequested);
+        }
+
         #[unsafe(method(togglePomodoro:))]
         fn toggle_pomodoro(&self, _sender: &AnyObject) {
             self.send_event(AppEvent::TogglePomodoro);
         }
 
+        #[unsafe(method(skipBreak:))]
+        fn skip_break(&self, _sender: &AnyObject) {
+            self.send_event(AppEvent::SkipBreak);
+        }
+
+        #[unsafe(method(extendBreak:))]
+        fn extend_break(&self, _sender: &AnyObject) {
+            self.send_event(AppEvent::ExtendBreak);
+        }
+
         #[unsafe(method(toggleResponseTimer:))]
         fn toggle_response_timer(&self, _sender: &AnyObject) {
             self.send_event(AppEvent::ToggleResponseTimer);
         }
 
+        #[unsafe(method(toggleResponseReport:))]
+        fn toggle_response_report(&self, _sender: &AnyObject) {
+            self.send_event(AppEvent::ToggleResponseReport);
+        }
+
+        #[unsafe(method(exportResponseTimes:))]
+        fn export_response_times(&self, _sender: &AnyObject) {
+            self.send_event(AppEvent::ExportResponseTimes);
+        }
+
         #[unsafe(method(toggleCoaching:))]
         fn toggle_coaching(&self, _sender: &AnyObject) {
             self.send_event(AppEvent::ToggleCoaching);
@@ -254,17 +311,129 @@ define_class! {
             self.send_event(AppEvent::ReloadConfig);
         }
 
+        #[unsafe(method(toggleDebugView:))]
+        fn toggle_debug_view(&self, _sender: &AnyObject) {
+            self.send_event(AppEvent::ToggleDebugView);
+        }
+
+        #[unsafe(method(toggleLatencyHud:))]
+        fn toggle_latency_hud(&self, _sender: &AnyObject) {
+            self.send_event(AppEvent::ToggleLatencyHud);
+        }
+
+        #[unsafe(method(newTabWithProfile:))]
+        fn new_tab_with_profile(&self, sender: &AnyObject) {
+            let tag: isize = unsafe { msg_send![sender, tag] };
+            self.send_event(AppEvent::NewTabWithProfile(tag as usize));
+        }
+
+        #[unsafe(method(clearScrollback:))]
+        fn clear_scrollback(&self, _sender: &AnyObject) {
+            self.send_event(AppEvent::ClearScrollback);
+        }
+
+        #[unsafe(method(exportBuffer:))]
+        fn export_buffer(&self, _sender: &AnyObject) {
+            self.send_event(AppEvent::ExportBuffer);
+        }
+
+        #[unsafe(method(copyScreenText:))]
+        fn copy_screen_text(&self, _sender: &AnyObject) {
+            self.send_event(AppEvent::CopyScreenText);
+        }
+
+        #[unsafe(method(saveScreenshot:))]
+        fn save_screenshot(&self, _sender: &AnyObject) {
+            self.send_event(AppEvent::SaveScreenshot);
+        }
+
+        #[unsafe(method(showDiagnostics:))]
+        fn show_diagnostics(&self, _sender: &AnyObject) {
+            self.send_event(AppEvent::ShowDiagnostics);
+        }
+
+        // --- QLPreviewPanel 컨트롤러 (응답자 체인에서 패널이 찾음) ---
+
+        #[unsafe(method(acceptsPreviewPanelControl:))]
+        fn accepts_preview_panel_control(&self, _panel: &AnyObject) -> bool {
+            self.ivars().quick_look_url.borrow().is_some()
+        }
+
+        #[unsafe(method(beginPreviewPanelControl:))]
+        fn begin_preview_panel_control(&self, panel: &AnyObject) {
+            let _: () = unsafe { msg_send![panel, setDataSource: self] };
+        }
+
+        #[unsafe(method(endPreviewPanelControl:))]
+        fn end_preview_panel_control(&self, panel: &AnyObject) {
+            let nil: *const AnyObject = std::ptr::null();
+            let _: () = unsafe { msg_send![panel, setDataSource: nil] };
+            self.ivars().quick_look_url.replace(None);
+        }
+
+        #[unsafe(method(numberOfPreviewItemsInPreviewPanel:))]
+        fn number_of_preview_items(&self, _panel: &AnyObject) -> isize {
+            self.ivars().quick_look_url.borrow().is_some() as isize
+        }
+
+        #[unsafe(method_id(previewPanel:previewItemAtIndex:))]
+        fn preview_item(&self, _panel: &AnyObject, _index: isize) -> Option<Retained<NSURL>> {
+            self.ivars().quick_look_url.borrow().clone()
+        }
+
+        #[unsafe(method(viewDidChangeEffectiveAppearance))]
+        fn view_did_change_effective_appearance(&self) {
+            let _: () = unsafe { msg_send![super(self), viewDidChangeEffectiveAppearance] };
+            self.send_event(AppEvent::AppearanceChanged(self.is_dark_appearance()));
+        }
+
         #[unsafe(method(viewDidChangeBackingProperties))]
         fn view_did_change_backing_properties(&self) {
             let _: () = unsafe { msg_send![super(self), viewDidChangeBackingProperties] };
             if let Some(layer) = self.layer() {
                 layer.setContentsScale(self.backing_scale_factor());
             }
+            self.send_event(AppEvent::ScaleFactorChanged(self.backing_scale_factor()));
         }
     }
 
     unsafe impl NSObjectProtocol for TerminalView {}
 
+    // --- NSWindowDelegate ---
+
+    unsafe impl NSWindowDelegate for TerminalView {
+        /// 닫기 버튼·네이티브 탭 닫기 → 이 윈도우의 앱 루프 종료
+        #[unsafe(method(windowWillClose:))]
+        fn window_will_close(&self, _notification: &NSNotification) {
+            self.send_event(AppEvent::CloseRequested);
+        }
+
+        #[unsafe(method(windowDidMove:))]
+        fn window_did_move(&self, _notification: &NSNotification) {
+            self.send_event(AppEvent::WindowMoved);
+        }
+
+        #[unsafe(method(windowDidEnterFullScreen:))]
+        fn window_did_enter_full_screen(&self, _notification: &NSNotification) {
+            self.send_event(AppEvent::FullScreenChanged(true));
+        }
+
+        #[unsafe(method(windowDidExitFullScreen:))]
+        fn window_did_exit_full_screen(&self, _notification: &NSNotification) {
+            self.send_event(AppEvent::FullScreenChanged(false));
+        }
+
+        #[unsafe(method(windowDidBecomeKey:))]
+        fn window_did_become_key(&self, _notification: &NSNotification) {
+            self.send_event(AppEvent::FocusChanged(true));
+        }
+
+        #[unsafe(method(windowDidResignKey:))]
+        fn window_did_resign_key(&self, _notification: &NSNotification) {
+            self.send_event(AppEvent::FocusChanged(false));
+        }
+    }
+
     // --- NSDraggingDestination ---
 
     unsafe impl NSDraggingDestination for TerminalView {
@@ -314,14 +483,9 @@ define_class! {
             self.ivars().ime_state.set(ImeState::Acted);
 
             let text = nsobj_to_string(string);
-            let was_composing;
-            {
-                let mut marked = self.ivars().marked_text.borrow_mut();
-                was_composing = !marked.is_empty();
-                if was_composing {
-                    marked.clear();
-                    self.send_event(AppEvent::Preedit(String::new()));
-                }
+            let was_composing = self.ivars().composition.borrow_mut().commit();
+            if was_composing {
+                self.send_event(AppEvent::Preedit(String::new()));
             }
             self.ivars().ime_committed_from_composition.set(was_composing);
             self.send_event(AppEvent::TextCommit(text));
@@ -336,22 +500,22 @@ define_class! {
         fn set_marked_text(
             &self,
             string: &AnyObject,
-            _selected_range: NSRange,
+            selected_range: NSRange,
             _replacement_range: NSRange,
         ) {
             self.ivars().ime_state.set(ImeState::Acted);
 
             let text = nsobj_to_string(string);
-            self.ivars().marked_text.replace(text.clone());
+            self.ivars()
+                .composition
+                .borrow_mut()
+                .set_marked(&text, (selected_range.location, selected_range.length));
             self.send_event(AppEvent::Preedit(text));
         }
 
         #[unsafe(method(unmarkText))]
         fn unmark_text(&self) {
-            let cleared = {
-                let mut marked = self.ivars().marked_text.borrow_mut();
-                clear_stale_marked_text(&mut marked)
-            };
+            let cleared = self.ivars().composition.borrow_mut().clear();
             if cleared {
                 self.send_event(AppEvent::Preedit(String::new()));
             }
@@ -359,22 +523,17 @@ define_class! {
 
         #[unsafe(method(hasMarkedText))]
         fn has_marked_text(&self) -> bool {
-            !self.ivars().marked_text.borrow().is_empty()
+            self.ivars().composition.borrow().is_composing()
         }
 
         #[unsafe(method(markedRange))]
         fn marked_range(&self) -> NSRange {
-            let marked = self.ivars().marked_text.borrow();
-            if marked.is_empty() {
-                NSRange::new(NSUInteger::MAX, 0)
-            } else {
-                NSRange::new(0, marked.len())
-            }
+            to_ns_range(self.ivars().composition.borrow().marked_range())
         }
 
         #[unsafe(method(selectedRange))]
         fn selected_range(&self) -> NSRange {
-            NSRange::new(NSUInteger::MAX, 0)
+            to_ns_range(self.ivars().composition.borrow().selected_range())
         }
 
         #[unsafe(method_id(attributedSubstringForProposedRange:actualRange:))]
@@ -389,11 +548,25 @@ define_class! {
         #[unsafe(method(firstRectForCharacterRange:actualRange:))]
         fn first_rect(
             &self,
-            _range: NSRange,
-            _actual_range: NSRangePointer,
+            range: NSRange,
+            actual_range: NSRangePointer,
         ) -> NSRect {
             if let Some(window) = self.window() {
-                if let Some(backing_rect) = *self.ivars().ime_cursor_rect.borrow() {
+                if let Some(cursor_rect) = *self.ivars().ime_cursor_rect.borrow() {
+                    // 후보창이 변환 중인 문절 아래에 뜨도록 커서 셀에서 그만큼 이동
+                    let composition = self.ivars().composition.borrow();
+                    let location = if range.location == NSUInteger::MAX { 0 } else { range.location };
+                    let (x, y, w, h) = composition.char_rect(
+                        (cursor_rect.origin.x, cursor_rect.origin.y, cursor_rect.size.width, cursor_rect.size.height),
+                        location,
+                    );
+                    if !actual_range.is_null() {
+                        if let Some((start, len)) = composition.marked_range() {
+                            let location = location.min(start + len);
+                            unsafe { *actual_range = NSRange::new(location, range.length.min(start + len - location)) };
+                        }
+                    }
+                    let backing_rect = NSRect::new(NSPoint::new(x, y), objc2_foundation::NSSize::new(w, h));
                     let scale = self.backing_scale_factor();
                     let local_rect = NSRect::new(
                         NSPoint::new(backing_rect.origin.x / scale, backing_rect.origin.y / scale),
@@ -438,13 +611,14 @@ impl TerminalView {
         let this = this.set_ivars(Ivars {
             sender: RefCell::new(None),
             ime_state: Cell::new(ImeState::None),
-            marked_text: RefCell::new(String::new()),
+            composition: RefCell::new(Composition::new()),
             ime_cursor_rect: RefCell::new(None),
             current_event: RefCell::new(None),
             pending_resize: Cell::new(None),
             last_mouse_pos: Cell::new((0.0, 0.0)),
             copy_mode_bypass_ime: Cell::new(false),
             ime_committed_from_composition: Cell::new(false),
+            quick_look_url: RefCell::new(None),
         });
         let this: Retained<Self> = unsafe { msg_send![super(this), init] };
         this.setWantsLayer(true);
@@ -484,11 +658,27 @@ impl TerminalView {
         self.ivars().copy_mode_bypass_ime.set(enabled);
     }
 
+    pub(crate) fn set_quick_look_url(&self, url: Option<Retained<NSURL>>) {
+        self.ivars().quick_look_url.replace(url);
+    }
+
     pub(crate) fn set_ime_cursor_rect(&self, rect: Option<NSRect>) {
         self.ivars().ime_cursor_rect.replace(rect);
     }
 
-    fn send_event(&self, event: AppEvent) {
+    /// effectiveAppearance 이름이 Dark 계열(DarkAqua, VibrantDark 등)인지
+    pub(crate) fn is_dark_appearance(&self) -> bool {
+        unsafe {
+            let appearance: *mut AnyObject = msg_send![self, effectiveAppearance];
+            if appearance.is_null() {
+                return true;
+            }
+            let name: *mut NSString = msg_send![appearance, name];
+            !name.is_null() && (*name).to_string().contains("Dark")
+        }
+    }
+
+    pub(crate) fn send_event(&self, event: AppEvent) {
         if let Some(ref sender) = *self.ivars().sender.borrow() {
             let _ = sender.send(event);
         }
@@ -607,7 +797,7 @@ fn resolve_file_url(url_str: &str) -> String {
     url_string_to_path(url_str)
 }
 
-fn extract_dropped_paths(pasteboard: &objc2_app_kit::NSPasteboard) -> Option<Vec<String>> {
+pub(crate) fn extract_dropped_paths(pasteboard: &objc2_app_kit::NSPasteboard) -> Option<Vec<String>> {
     let file_url_type = unsafe { objc2_app_kit::NSPasteboardTypeFileURL };
 
     if let Some(items) = pasteboard.pasteboardItems() {
@@ -635,13 +825,9 @@ fn parse_file_url_lines(input: &str) -> Vec<String> {
         .collect()
 }
 
-fn clear_stale_marked_text(marked_text: &mut String) -> bool {
-    if marked_text.is_empty() {
-        false
-    } else {
-        marked_text.clear();
-        true
-    }
+/// None → NSNotFound
+fn to_ns_range(range: Option<(usize, usize)>) -> NSRange {
+    range.map_or(NSRange::new(NSUInteger::MAX, 0), |(location, length)| NSRange::new(location, length))
 }
 
 fn should_clear_stale_marked_text(
@@ -756,23 +942,10 @@ mod tests {
     }
 
     #[test]
-    fn clear_stale_marked_text_noop_when_empty() {
-        let mut marked = String::new();
-
-        let cleared = clear_stale_marked_text(&mut marked);
-
-        assert!(!cleared);
-        assert!(marked.is_empty());
-    }
-
-    #[test]
-    fn clear_stale_marked_text_clears_existing_text() {
-        let mut marked = "한글".to_string();
-
-        let cleared = clear_stale_marked_text(&mut marked);
-
-        assert!(cleared);
-        assert!(marked.is_empty());
+    fn empty_range_maps_to_not_found() {
+        assert_eq!(to_ns_range(None).location, NSUInteger::MAX);
+        let range = to_ns_range(Some((2, 1)));
+        assert_eq!((range.location, range.length), (2, 1));
     }
 
     #[test]
@@ -3,12 +3,17 @@ use std::ptr::NonNull;
 use std::sync::mpsc::Sender;
 
 use objc2::rc::Retained;
+use objc2::runtime::ProtocolObject;
 use objc2::MainThreadMarker;
 use objc2_app_kit::{NSBackingStoreType, NSColor, NSWindow, NSWindowStyleMask};
 use objc2_foundation::{NSPoint, NSRect, NSSize, NSString};
 
 use crate::dispatch::dispatch_async_main;
 
+extern "C" {
+    fn NSBeep();
+}
+
 extern "C" fn set_needs_display_on_main(ctx: *mut c_void) {
     unsafe {
         let view: *mut objc2::runtime::AnyObject = ctx as *mut _;
@@ -22,10 +27,13 @@ use raw_window_handle::{
 
 use crate::event::AppEvent;
 use crate::view::TerminalView;
+use crate::{MouseCursor, TerminalWindow};
 
 pub struct MacWindow {
     ns_window: Retained<NSWindow>,
     view: Retained<TerminalView>,
+    /// 뷰의 sender와 같은 채널. 메인 스레드 밖에서 `post_event`로 씀
+    sender: std::sync::OnceLock<Sender<AppEvent>>,
 }
 
 impl MacWindow {
@@ -51,8 +59,12 @@ impl MacWindow {
         ns_window.setTitlebarAppearsTransparent(true);
         ns_window.setBackgroundColor(Some(&NSColor::blackColor()));
         ns_window.setTabbingMode(objc2_app_kit::NSWindowTabbingMode::Disallowed);
+        ns_window.setTabbingIdentifier(&NSString::from_str("growterm"));
         ns_window.setContentView(Some(&view));
         ns_window.makeFirstResponder(Some(&view));
+        // 닫힘은 windowWillClose:로 앱에 알리고, 해제는 Retained가 관리
+        unsafe { ns_window.setReleasedWhenClosed(false) };
+        ns_window.setDelegate(Some(ProtocolObject::from_ref(&*view)));
 
         let title_str = NSString::from_str(title);
         ns_window.setTitle(&title_str);
@@ -60,14 +72,36 @@ impl MacWindow {
             ns_window.center();
         }
 
-        Self { ns_window, view }
+        Self { ns_window, view, sender: std::sync::OnceLock::new() }
     }
 
     pub fn set_sender(&self, sender: Sender<AppEvent>) {
+        let _ = self.sender.set(sender.clone());
         self.view.set_sender(sender);
     }
 
-    pub fn inner_size(&self) -> (u32, u32) {
+    /// 화면 좌상단 기준 좌표(x, y)로 윈도우를 이동.
+    /// macOS 좌표계(좌하단 원점)로 변환하여 적용.
+    pub fn set_position(&self, x: f64, y: f64) {
+        let screen_height = self.ns_window.screen()
+            .map(|s| s.frame().size.height)
+            .unwrap_or(900.0);
+        let window_height = self.ns_window.frame().size.height;
+        let flipped_y = screen_height - y - window_height;
+        self.ns_window.setFrameOrigin(NSPoint::new(x, flipped_y));
+    }
+
+    pub fn show(&self) {
+        self.ns_window.makeKeyAndOrderFront(None);
+    }
+
+    pub fn ns_window(&self) -> &NSWindow {
+        &self.ns_window
+    }
+}
+
+impl TerminalWindow for MacWindow {
+    fn inner_size(&self) -> (u32, u32) {
         let frame = self.view.frame();
         let scale = self.backing_scale_factor();
         let w = (frame.size.width * scale) as u32;
@@ -75,18 +109,24 @@ impl MacWindow {
         (w.max(1), h.max(1))
     }
 
-    pub fn backing_scale_factor(&self) -> f64 {
+    fn backing_scale_factor(&self) -> f64 {
         self.ns_window.backingScaleFactor()
     }
 
-    pub fn request_redraw(&self) {
+    fn request_redraw(&self) {
         let ptr = Retained::as_ptr(&self.view) as *mut c_void;
         unsafe {
             crate::dispatch::dispatch_raw_main(ptr, set_needs_display_on_main);
         }
     }
 
-    pub fn set_title(&self, title: &str) {
+    fn post_event(&self, event: AppEvent) {
+        if let Some(sender) = self.sender.get() {
+            let _ = sender.send(event);
+        }
+    }
+
+    fn set_title(&self, title: &str) {
         let raw: *const NSWindow = Retained::as_ptr(&self.ns_window);
         let title = title.to_owned();
         unsafe {
@@ -99,7 +139,7 @@ impl MacWindow {
         }
     }
 
-    pub fn set_copy_mode(&self, enabled: bool) {
+    fn set_copy_mode(&self, enabled: bool) {
         let raw = Retained::as_ptr(&self.view) as usize;
         dispatch_async_main(move || {
             let view = unsafe { &*(raw as *const TerminalView) };
@@ -107,7 +147,7 @@ impl MacWindow {
         });
     }
 
-    pub fn set_ime_cursor_rect(&self, rect: Option<(f32, f32, f32, f32)>) {
+    fn set_ime_cursor_rect(&self, rect: Option<(f32, f32, f32, f32)>) {
         let raw = Retained::as_ptr(&self.view) as usize;
         dispatch_async_main(move || {
             let view = unsafe { &*(raw as *const TerminalView) };
@@ -121,7 +161,7 @@ impl MacWindow {
         });
     }
 
-    pub fn discard_marked_text(&self) {
+    fn discard_marked_text(&self) {
         let raw = Retained::as_ptr(&self.view) as usize;
         dispatch_async_main(move || {
             let view = raw as *const objc2::runtime::AnyObject;
@@ -134,27 +174,66 @@ impl MacWindow {
         });
     }
 
-    pub fn set_pomodoro_checked(&self, checked: bool) {
+    fn set_pomodoro_checked(&self, checked: bool) {
         set_view_menu_item_checked(0, checked);
     }
 
-    pub fn set_response_timer_checked(&self, checked: bool) {
+    fn set_response_timer_checked(&self, checked: bool) {
         set_view_menu_item_checked(1, checked);
     }
 
-    pub fn set_coaching_checked(&self, checked: bool) {
+    fn set_coaching_checked(&self, checked: bool) {
         set_view_menu_item_checked(2, checked);
     }
 
-    pub fn set_coaching_menu_enabled(&self, enabled: bool) {
+    fn set_coaching_menu_enabled(&self, enabled: bool) {
         set_view_menu_item_enabled(2, enabled);
     }
 
-    pub fn set_transparent_tab_bar_checked(&self, checked: bool) {
+    fn set_transparent_tab_bar_checked(&self, checked: bool) {
         set_view_menu_item_checked(3, checked);
     }
 
-    pub fn set_transparent_mode(&self, enabled: bool) {
+    fn set_debug_view_checked(&self, checked: bool) {
+        set_view_menu_item_checked(7, checked);
+    }
+
+    fn set_latency_hud_checked(&self, checked: bool) {
+        set_view_menu_item_checked(12, checked);
+    }
+
+    fn set_profiles(&self, names: &[String]) {
+        let names = names.to_vec();
+        dispatch_async_main(move || {
+            let mtm = MainThreadMarker::new().unwrap();
+            let app = objc2_app_kit::NSApplication::sharedApplication(mtm);
+            // Shell 메뉴(2) > New Tab With Profile(0)
+            let Some(profile_menu) = app
+                .mainMenu()
+                .and_then(|menu| menu.itemAtIndex(2))
+                .and_then(|item| item.submenu())
+                .and_then(|menu| menu.itemAtIndex(0))
+                .and_then(|item| item.submenu())
+            else {
+                return;
+            };
+            profile_menu.removeAllItems();
+            for (index, name) in names.iter().enumerate() {
+                let item = unsafe {
+                    objc2_app_kit::NSMenuItem::initWithTitle_action_keyEquivalent(
+                        mtm.alloc(),
+                        &NSString::from_str(name),
+                        Some(objc2::sel!(newTabWithProfile:)),
+                        &NSString::from_str(""),
+                    )
+                };
+                item.setTag(index as isize);
+                profile_menu.addItem(&item);
+            }
+        });
+    }
+
+    fn set_transparent_mode(&self, enabled: bool) {
         let raw = Retained::as_ptr(&self.ns_window) as usize;
         dispatch_async_main(move || {
             let window = unsafe { &*(raw as *const NSWindow) };
@@ -170,39 +249,218 @@ impl MacWindow {
         });
     }
 
-    pub fn title_bar_height(&self) -> f64 {
+    fn title_bar_height(&self) -> f64 {
         let frame = self.ns_window.frame();
         let content_rect = self.ns_window.contentLayoutRect();
         (frame.size.height - content_rect.size.height) * self.backing_scale_factor()
     }
 
-    /// 화면 좌상단 기준 좌표(x, y)로 윈도우를 이동.
-    /// macOS 좌표계(좌하단 원점)로 변환하여 적용.
-    pub fn set_position(&self, x: f64, y: f64) {
+    /// 모달 알림 창을 띄움.
+    /// `confirm`이 있으면 (버튼 제목, 이벤트) 버튼과 "Ignore" 버튼을 추가하고,
+    /// 첫 번째 버튼을 누르면 해당 이벤트를 앱으로 보냄.
+    fn show_alert(&self, title: &str, message: &str, confirm: Option<(&str, AppEvent)>) {
+        let raw = Retained::as_ptr(&self.view) as usize;
+        let title = title.to_owned();
+        let message = message.to_owned();
+        let confirm = confirm.map(|(label, event)| (label.to_owned(), event));
+        dispatch_async_main(move || {
+            let view = unsafe { &*(raw as *const TerminalView) };
+            unsafe {
+                let alert: Retained<objc2::runtime::AnyObject> =
+                    objc2::msg_send![objc2::class!(NSAlert), new];
+                let _: () = objc2::msg_send![&alert, setMessageText: &*NSString::from_str(&title)];
+                let _: () =
+                    objc2::msg_send![&alert, setInformativeText: &*NSString::from_str(&message)];
+                if let Some((label, _)) = &confirm {
+                    let _: *mut objc2::runtime::AnyObject =
+                        objc2::msg_send![&alert, addButtonWithTitle: &*NSString::from_str(label)];
+                    let _: *mut objc2::runtime::AnyObject =
+                        objc2::msg_send![&alert, addButtonWithTitle: &*NSString::from_str("Ignore")];
+                }
+                let response: isize = objc2::msg_send![&alert, runModal];
+                // NSAlertFirstButtonReturn
+                if response == 1000 {
+                    if let Some((_, event)) = confirm {
+                        view.send_event(event);
+                    }
+                }
+            }
+        });
+    }
+
+    fn show_save_panel(&self, default_name: &str, on_save: fn(String) -> AppEvent) {
+        let raw = Retained::as_ptr(&self.view) as usize;
+        let default_name = default_name.to_owned();
+        dispatch_async_main(move || {
+            let view = unsafe { &*(raw as *const TerminalView) };
+            unsafe {
+                let panel: Retained<objc2::runtime::AnyObject> =
+                    objc2::msg_send![objc2::class!(NSSavePanel), savePanel];
+                let _: () = objc2::msg_send![
+                    &panel,
+                    setNameFieldStringValue: &*NSString::from_str(&default_name)
+                ];
+                let response: isize = objc2::msg_send![&panel, runModal];
+                // NSModalResponseOK
+                if response != 1 {
+                    return;
+                }
+                let url: Option<Retained<objc2::runtime::AnyObject>> = objc2::msg_send![&panel, URL];
+                let path: Option<Retained<NSString>> = url.and_then(|url| objc2::msg_send![&url, path]);
+                if let Some(path) = path {
+                    view.send_event(on_save(path.to_string()));
+                }
+            }
+        });
+    }
+
+    /// 붙여넣는 앱이 고를 수 있도록 세 가지 형식을 한 번에 올림
+    fn set_rich_clipboard(&self, text: &str, html: &str, rtf: &str) -> bool {
+        let pasteboard = objc2_app_kit::NSPasteboard::generalPasteboard();
+        pasteboard.clearContents();
+        unsafe {
+            pasteboard.setString_forType(&NSString::from_str(text), objc2_app_kit::NSPasteboardTypeString);
+            pasteboard.setString_forType(&NSString::from_str(html), objc2_app_kit::NSPasteboardTypeHTML);
+            pasteboard.setString_forType(&NSString::from_str(rtf), objc2_app_kit::NSPasteboardTypeRTF);
+        }
+        true
+    }
+
+    /// 시스템 경고음 재생
+    fn beep(&self) {
+        dispatch_async_main(|| unsafe { NSBeep() });
+    }
+
+    fn request_attention(&self) {
+        dispatch_async_main(|| {
+            let mtm = MainThreadMarker::new().unwrap();
+            let app = objc2_app_kit::NSApplication::sharedApplication(mtm);
+            // NSInformationalRequest: 한 번만 튐. 앱이 활성이면 무시됨
+            let _: isize = unsafe { objc2::msg_send![&app, requestUserAttention: 10isize] };
+        });
+    }
+
+    /// Dock 아이콘 배지 설정 (None이면 제거)
+    fn set_dock_badge(&self, label: Option<&str>) {
+        let label = label.map(str::to_owned);
+        dispatch_async_main(move || {
+            let mtm = MainThreadMarker::new().unwrap();
+            let app = objc2_app_kit::NSApplication::sharedApplication(mtm);
+            unsafe {
+                let tile: *mut objc2::runtime::AnyObject = objc2::msg_send![&app, dockTile];
+                if tile.is_null() {
+                    return;
+                }
+                match &label {
+                    Some(text) => {
+                        let _: () = objc2::msg_send![tile, setBadgeLabel: &*NSString::from_str(text)];
+                    }
+                    None => {
+                        let none: *const NSString = std::ptr::null();
+                        let _: () = objc2::msg_send![tile, setBadgeLabel: none];
+                    }
+                }
+            }
+        });
+    }
+
+    fn is_dark_appearance(&self) -> bool {
+        self.view.is_dark_appearance()
+    }
+
+    /// set_position과 같은 좌상단 기준 좌표. 크기는 타이틀바를 뺀 콘텐츠 영역
+    fn window_frame(&self) -> Option<(f64, f64, f64, f64)> {
+        if self.ns_window.styleMask().contains(NSWindowStyleMask::FullScreen) {
+            return None;
+        }
+        let frame = self.ns_window.frame();
         let screen_height = self.ns_window.screen()
             .map(|s| s.frame().size.height)
             .unwrap_or(900.0);
-        let window_height = self.ns_window.frame().size.height;
-        let flipped_y = screen_height - y - window_height;
-        self.ns_window.setFrameOrigin(NSPoint::new(x, flipped_y));
+        let y = screen_height - frame.origin.y - frame.size.height;
+        let content = self.ns_window.contentLayoutRect().size;
+        Some((frame.origin.x, y, content.width, content.height))
     }
 
-    pub fn show(&self) {
-        self.ns_window.makeKeyAndOrderFront(None);
+    fn set_fullscreen(&self, enabled: bool) {
+        let raw = Retained::as_ptr(&self.ns_window) as usize;
+        dispatch_async_main(move || {
+            let window = unsafe { &*(raw as *const NSWindow) };
+            if window.styleMask().contains(NSWindowStyleMask::FullScreen) != enabled {
+                window.toggleFullScreen(None);
+            }
+        });
     }
 
-    pub fn ns_window(&self) -> &NSWindow {
-        &self.ns_window
+    fn focus(&self) {
+        let raw = Retained::as_ptr(&self.ns_window) as usize;
+        dispatch_async_main(move || {
+            let mtm = MainThreadMarker::new().unwrap();
+            let window = unsafe { &*(raw as *const NSWindow) };
+            objc2_app_kit::NSApplication::sharedApplication(mtm).activate();
+            window.makeKeyAndOrderFront(None);
+        });
+    }
+
+    /// 새 윈도우는 현재 윈도우와 같은 크기로 열림
+    fn open_window(&self, as_tab: bool) -> bool {
+        let raw = Retained::as_ptr(&self.ns_window) as usize;
+        dispatch_async_main(move || {
+            let mtm = MainThreadMarker::new().unwrap();
+            let parent = unsafe { &*(raw as *const NSWindow) };
+            let size = parent.contentLayoutRect().size;
+            let tab_of = as_tab.then_some(parent);
+            crate::delegate::open_window(mtm, (size.width, size.height), None, tab_of);
+        });
+        true
+    }
+
+    fn toggle_quick_look(&self, path: &str) -> bool {
+        let raw = Retained::as_ptr(&self.view) as usize;
+        let path = path.to_string();
+        dispatch_async_main(move || {
+            let view = unsafe { &*(raw as *const TerminalView) };
+            crate::quick_look::toggle(view, &path);
+        });
+        true
+    }
+
+    fn close(&self) {
+        let raw = Retained::as_ptr(&self.ns_window) as usize;
+        dispatch_async_main(move || {
+            let window = unsafe { &*(raw as *const NSWindow) };
+            if window.isVisible() {
+                window.close();
+            }
+        });
+    }
+
+    fn quit(&self) {
+        dispatch_async_main(|| {
+            let mtm = MainThreadMarker::new().unwrap();
+            let app = objc2_app_kit::NSApplication::sharedApplication(mtm);
+            let none: *const objc2::runtime::AnyObject = std::ptr::null();
+            let _: () = unsafe { objc2::msg_send![&app, terminate: none] };
+        });
+    }
+
+    fn present_pixels(&self, rgba: &[u8], width: u32, height: u32) {
+        let raw = Retained::as_ptr(&self.view) as usize;
+        let rgba = rgba.to_vec();
+        dispatch_async_main(move || {
+            let view = unsafe { &*(raw as *const TerminalView) };
+            crate::present::set_layer_image(view, &rgba, width, height);
+        });
     }
 
-    pub fn set_pointing_hand_cursor(&self, enabled: bool) {
+    fn can_present_pixels(&self) -> bool {
+        true
+    }
+
+    fn set_mouse_cursor(&self, cursor: MouseCursor) {
         use std::sync::atomic::Ordering;
-        crate::view::POINTING_HAND_CURSOR.store(enabled, Ordering::Relaxed);
-        if !enabled {
-            dispatch_async_main(|| {
-                objc2_app_kit::NSCursor::arrowCursor().set();
-            });
-        }
+        crate::view::MOUSE_CURSOR.store(cursor as u8, Ordering::Relaxed);
+        dispatch_async_main(crate::view::apply_mouse_cursor);
     }
 }
 
@@ -1,11 +1,26 @@
 use portable_pty::{CommandBuilder, NativePtySystem, PtySize, PtySystem};
+use std::collections::{BTreeMap, BTreeSet};
 use std::io;
 use std::path::PathBuf;
-use std::sync::{Arc, Mutex};
+use std::sync::{mpsc, Arc, Mutex};
 
-/// PTY read end. Moved to IO thread in Phase 7.
+mod reactor;
+mod terminfo;
+
+pub use reactor::{register, register_with_options, PtyHandler, ReadFlow, ReadOptions, TICK};
+pub use terminfo::{install_terminfo, term_name};
+
+/// PTY read end. Read it directly, or hand it to [`register`] to have the
+/// shared reactor thread read it.
 pub struct PtyReader {
-    inner: Box<dyn io::Read + Send>,
+    inner: std::fs::File,
+    replies: reactor::ReplyQueue,
+}
+
+impl PtyReader {
+    fn into_parts(self) -> (std::fs::File, reactor::ReplyQueue) {
+        (self.inner, self.replies)
+    }
 }
 
 impl io::Read for PtyReader {
@@ -18,16 +33,48 @@ impl io::Read for PtyReader {
 pub struct PtyWriter {
     writer: Arc<Mutex<Box<dyn io::Write + Send>>>,
     master: Box<dyn portable_pty::MasterPty + Send>,
-    _child: Box<dyn portable_pty::Child + Send + Sync>,
+    child_pid: Option<u32>,
+    /// Until someone asks for its exit; then the reactor reaps it
+    child: Option<Box<dyn portable_pty::Child + Send + Sync>>,
+    replies: reactor::ReplyQueue,
+}
+
+/// How the child process ended.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ChildExit {
+    /// Exit code; 1 if the process was killed by a signal or couldn't be waited on.
+    pub code: u32,
+}
+
+/// A process other than the shell in the terminal's foreground.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RunningProcess {
+    pub pid: u32,
+    /// Program name without its directory, e.g. `vim`
+    pub name: String,
+}
+
+impl ChildExit {
+    pub fn success(&self) -> bool {
+        self.code == 0
+    }
 }
 
 impl io::Write for PtyWriter {
+    /// Blocks until the child has room, even once [`register`] has made
+    /// the shared master fd non-blocking.
     fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
-        let mut writer = self
-            .writer
-            .lock()
-            .map_err(|_| io::Error::new(io::ErrorKind::Other, "pty writer lock poisoned"))?;
-        writer.write(buf)
+        loop {
+            let result = self
+                .writer
+                .lock()
+                .map_err(|_| io::Error::new(io::ErrorKind::Other, "pty writer lock poisoned"))?
+                .write(buf);
+            match result {
+                Err(e) if e.kind() == io::ErrorKind::WouldBlock => self.wait_writable()?,
+                result => return result,
+            }
+        }
     }
 
     fn flush(&mut self) -> io::Result<()> {
@@ -39,19 +86,23 @@ impl io::Write for PtyWriter {
     }
 }
 
+/// Replies to terminal queries (DA, CPR, ...) from a [`PtyHandler`]. They
+/// are queued and the reactor writes them when the PTY has room, so a
+/// child that stops reading stdin never stalls the reactor thread.
 #[derive(Clone)]
 pub struct PtyResponder {
-    writer: Arc<Mutex<Box<dyn io::Write + Send>>>,
+    replies: reactor::ReplyQueue,
 }
 
 impl PtyResponder {
-    pub fn write_all_flush(&self, bytes: &[u8]) -> io::Result<()> {
-        let mut writer = self
-            .writer
-            .lock()
-            .map_err(|_| io::Error::new(io::ErrorKind::Other, "pty writer lock poisoned"))?;
-        writer.write_all(bytes)?;
-        writer.flush()
+    /// Queue `bytes` for the child's stdin. Never blocks. Written only while
+    /// the PTY is registered with the reactor.
+    pub fn respond(&self, bytes: &[u8]) {
+        if let Ok(mut pending) = self.replies.lock() {
+            if pending.len() + bytes.len() <= reactor::MAX_PENDING_REPLIES {
+                pending.extend_from_slice(bytes);
+            }
+        }
     }
 }
 
@@ -68,20 +119,190 @@ impl PtyWriter {
     }
 
     pub fn child_pid(&self) -> Option<u32> {
-        self._child.process_id()
+        self.child_pid
+    }
+
+    /// Process group currently in the foreground of the terminal
+    /// (the shell itself when idle, `ssh`, `vim`, ... while they run).
+    pub fn foreground_pid(&self) -> Option<u32> {
+        self.master
+            .process_group_leader()
+            .and_then(|pid| u32::try_from(pid).ok())
+    }
+
+    /// Processes in the foreground process group when it isn't the shell
+    /// itself, e.g. `vim` or `ssh host` — what closing the PTY would kill.
+    pub fn running_processes(&self) -> Vec<RunningProcess> {
+        let Some(fg) = self.foreground_pid() else {
+            return Vec::new();
+        };
+        if fg == 0 || Some(fg) == self.child_pid {
+            return Vec::new();
+        }
+        let mut pids = process_group_pids(fg);
+        pids.sort_unstable();
+        pids.dedup();
+        pids.into_iter()
+            .map(|pid| {
+                let name = process_args(pid)
+                    .and_then(|args| args.into_iter().next())
+                    .map(|arg0| arg0.rsplit('/').next().unwrap_or(&arg0).trim_start_matches('-').to_string())
+                    .unwrap_or_else(|| format!("pid {pid}"));
+                RunningProcess { pid, name }
+            })
+            .collect()
+    }
+
+    /// Receiver that gets one `ChildExit` when the child terminates.
+    /// Can only be taken once, and not after [`PtyWriter::on_exit`].
+    pub fn take_exit_receiver(&mut self) -> Option<mpsc::Receiver<ChildExit>> {
+        let (tx, rx) = mpsc::channel();
+        self.on_exit(move |exit| {
+            let _ = tx.send(exit);
+        })
+        .then_some(rx)
+    }
+
+    /// Call `on_exit` on the reactor thread once the child terminates, so
+    /// keep it short. False if the exit is already watched.
+    pub fn on_exit(&mut self, on_exit: impl FnOnce(ChildExit) + Send + 'static) -> bool {
+        self.child
+            .take()
+            .is_some_and(|child| reactor::watch_child(child, Box::new(on_exit)).is_ok())
     }
 
     pub fn responder(&self) -> PtyResponder {
         PtyResponder {
-            writer: Arc::clone(&self.writer),
+            replies: Arc::clone(&self.replies),
+        }
+    }
+
+    fn wait_writable(&self) -> io::Result<()> {
+        let Some(fd) = self.master.as_raw_fd() else {
+            return Err(io::Error::new(io::ErrorKind::Other, "pty master has no fd"));
+        };
+        let mut pollfd = libc::pollfd { fd, events: libc::POLLOUT, revents: 0 };
+        loop {
+            if unsafe { libc::poll(&mut pollfd, 1, -1) } >= 0 {
+                return Ok(());
+            }
+            let err = io::Error::last_os_error();
+            if err.kind() != io::ErrorKind::Interrupted {
+                return Err(err);
+            }
+        }
+    }
+}
+
+impl Drop for PtyWriter {
+    fn drop(&mut self) {
+        // 아무도 종료를 기다리지 않았어도 좀비로 남지 않게 거둠
+        self.on_exit(|_| {});
+    }
+}
+
+/// What to run in a new PTY. The default is the user's `$SHELL -l`,
+/// started in HOME, at 24x80.
+///
+/// ```ignore
+/// let opts = PtyOptions::new().program("ssh").arg("host").size(40, 120);
+/// let (reader, writer) = growterm_pty::spawn_with_options(&opts)?;
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PtyOptions {
+    program: Option<String>,
+    args: Vec<String>,
+    env: BTreeMap<String, String>,
+    env_remove: BTreeSet<String>,
+    cwd: Option<PathBuf>,
+    rows: u16,
+    cols: u16,
+    login_shell: bool,
+}
+
+impl Default for PtyOptions {
+    fn default() -> Self {
+        Self {
+            program: None,
+            args: Vec::new(),
+            env: BTreeMap::new(),
+            env_remove: BTreeSet::new(),
+            cwd: None,
+            rows: 24,
+            cols: 80,
+            login_shell: true,
         }
     }
 }
 
+impl PtyOptions {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Run `program` instead of `$SHELL`. Also turns off `login_shell`, so
+    /// call `login_shell(true)` afterwards to pass `-l` to it anyway.
+    pub fn program(mut self, program: impl Into<String>) -> Self {
+        self.program = Some(program.into());
+        self.login_shell = false;
+        self
+    }
+
+    pub fn arg(mut self, arg: impl Into<String>) -> Self {
+        self.args.push(arg.into());
+        self
+    }
+
+    pub fn args<I, S>(mut self, args: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.args.extend(args.into_iter().map(Into::into));
+        self
+    }
+
+    /// Set an environment variable for the child. Overrides the defaults
+    /// (`TERM`, `COLORTERM`, `LANG`).
+    pub fn env(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
+        let key = key.into();
+        self.env_remove.remove(&key);
+        self.env.insert(key, value.into());
+        self
+    }
+
+    /// Keep `key` out of the child's environment, whether inherited from
+    /// growterm, one of the defaults, or set by an earlier `env` call.
+    pub fn env_remove(mut self, key: impl Into<String>) -> Self {
+        let key = key.into();
+        self.env.remove(&key);
+        self.env_remove.insert(key);
+        self
+    }
+
+    /// Working directory. `None` (the default) starts in HOME.
+    pub fn cwd(mut self, cwd: Option<impl Into<PathBuf>>) -> Self {
+        self.cwd = cwd.map(Into::into);
+        self
+    }
+
+    pub fn size(mut self, rows: u16, cols: u16) -> Self {
+        self.rows = rows;
+        self.cols = cols;
+        self
+    }
+
+    /// Pass `-l` so zprofile/login PATH setup is applied.
+    pub fn login_shell(mut self, login_shell: bool) -> Self {
+        self.login_shell = login_shell;
+        self
+    }
+}
+
 /// Spawn a shell process in a PTY.
 /// Returns (reader, writer) for use on separate threads.
 pub fn spawn(rows: u16, cols: u16) -> io::Result<(PtyReader, PtyWriter)> {
-    spawn_with_cwd(rows, cols, None)
+    spawn_with_options(&PtyOptions::new().size(rows, cols))
 }
 
 /// Spawn a shell process in a PTY with an optional working directory.
@@ -91,60 +312,68 @@ pub fn spawn_with_cwd(
     cols: u16,
     cwd: Option<&std::path::Path>,
 ) -> io::Result<(PtyReader, PtyWriter)> {
+    spawn_with_options(&PtyOptions::new().size(rows, cols).cwd(cwd))
+}
+
+/// Spawn the process described by `options` in a PTY.
+pub fn spawn_with_options(options: &PtyOptions) -> io::Result<(PtyReader, PtyWriter)> {
     let pty_system = NativePtySystem::default();
     let pair = pty_system
         .openpty(PtySize {
-            rows,
-            cols,
+            rows: options.rows,
+            cols: options.cols,
             pixel_width: 0,
             pixel_height: 0,
         })
         .map_err(|e| io::Error::new(io::ErrorKind::Other, e))?;
 
-    let shell = std::env::var("SHELL").unwrap_or_else(|_| "/bin/sh".to_string());
-    let mut cmd = build_shell_command(&shell);
-    cmd.env("TERM", "xterm-256color");
-    cmd.env("COLORTERM", "truecolor");
-    // .app 번들로 실행 시 launchd 환경에는 LANG이 없어 한글이 깨짐.
-    // 터미널 환경에 이미 있으면 그대로 쓰고, 없으면 UTF-8로 설정.
-    if std::env::var("LANG").unwrap_or_default().is_empty() {
-        cmd.env("LANG", "en_US.UTF-8");
-    }
-    if let Some(dir) = cwd {
-        cmd.cwd(dir);
-    } else if let Some(home) = std::env::var_os("HOME") {
-        // .app 번들에서 실행 시 cwd가 / 등이 되어 쉘 시작 스크립트가
-        // 보호된 폴더에 접근하면 TCC 다이얼로그가 반복 발생함.
-        cmd.cwd(home);
-    }
+    let cmd = build_command(options);
 
     let child = pair
         .slave
         .spawn_command(cmd)
         .map_err(|e| io::Error::new(io::ErrorKind::Other, e))?;
+    let child_pid = child.process_id();
 
-    let reader = pair
-        .master
-        .try_clone_reader()
-        .map_err(|e| io::Error::new(io::ErrorKind::Other, e))?;
+    let reader = clone_master_fd(pair.master.as_ref())?;
 
     let writer = pair
         .master
         .take_writer()
         .map_err(|e| io::Error::new(io::ErrorKind::Other, e))?;
     let shared_writer = Arc::new(Mutex::new(writer));
+    let replies = reactor::ReplyQueue::default();
 
     Ok((
-        PtyReader { inner: reader },
+        PtyReader {
+            inner: reader,
+            replies: Arc::clone(&replies),
+        },
         PtyWriter {
             writer: shared_writer,
             master: pair.master,
-            _child: child,
+            child_pid,
+            child: Some(child),
+            replies,
         },
     ))
 }
 
-/// Get the current working directory of a process by PID (macOS only).
+/// Own a dup of the master fd so the reactor can poll(2) it.
+fn clone_master_fd(master: &dyn portable_pty::MasterPty) -> io::Result<std::fs::File> {
+    use std::os::unix::io::FromRawFd;
+
+    let fd = master
+        .as_raw_fd()
+        .ok_or_else(|| io::Error::new(io::ErrorKind::Other, "pty master has no fd"))?;
+    let dup = unsafe { libc::fcntl(fd, libc::F_DUPFD_CLOEXEC, 0) };
+    if dup < 0 {
+        return Err(io::Error::last_os_error());
+    }
+    Ok(unsafe { std::fs::File::from_raw_fd(dup) })
+}
+
+/// Get the current working directory of a process by PID (macOS, Linux).
 pub fn child_cwd(pid: u32) -> Option<PathBuf> {
     #[cfg(target_os = "macos")]
     {
@@ -186,22 +415,146 @@ pub fn child_cwd(pid: u32) -> Option<PathBuf> {
             }
         }
     }
-    #[cfg(not(target_os = "macos"))]
+    #[cfg(target_os = "linux")]
+    {
+        let path = std::fs::read_link(format!("/proc/{pid}/cwd")).ok()?;
+        path.is_dir().then_some(path)
+    }
+    #[cfg(not(any(target_os = "macos", target_os = "linux")))]
     {
         let _ = pid;
         None
     }
 }
 
-fn build_shell_command(shell: &str) -> CommandBuilder {
-    let mut cmd = CommandBuilder::new(shell);
-    // Start an interactive login shell so zprofile/login PATH setup is applied.
-    cmd.arg("-l");
+/// Command-line arguments of a process by PID, program name first.
+pub fn process_args(pid: u32) -> Option<Vec<String>> {
+    #[cfg(target_os = "macos")]
+    {
+        // KERN_PROCARGS2: argc(i32), exec path, NUL 패딩, argv[0..argc], envp...
+        let mut mib = [libc::CTL_KERN, libc::KERN_PROCARGS2, pid as libc::c_int];
+        let mut size: libc::size_t = 0;
+        unsafe {
+            if libc::sysctl(mib.as_mut_ptr(), 3, std::ptr::null_mut(), &mut size, std::ptr::null_mut(), 0) != 0 {
+                return None;
+            }
+            let mut buf = vec![0u8; size];
+            if libc::sysctl(mib.as_mut_ptr(), 3, buf.as_mut_ptr().cast(), &mut size, std::ptr::null_mut(), 0) != 0 {
+                return None;
+            }
+            buf.truncate(size);
+            parse_procargs2(&buf)
+        }
+    }
+    #[cfg(not(target_os = "macos"))]
+    {
+        let cmdline = std::fs::read(format!("/proc/{pid}/cmdline")).ok()?;
+        let args: Vec<String> = cmdline
+            .split(|&b| b == 0)
+            .filter(|a| !a.is_empty())
+            .map(|a| String::from_utf8_lossy(a).into_owned())
+            .collect();
+        (!args.is_empty()).then_some(args)
+    }
+}
+
+/// PIDs of every process in process group `pgid`.
+pub fn process_group_pids(pgid: u32) -> Vec<u32> {
+    #[cfg(target_os = "macos")]
+    {
+        let mut buf = vec![0 as libc::pid_t; 256];
+        let bytes = unsafe {
+            libc::proc_listpgrppids(
+                pgid as libc::pid_t,
+                buf.as_mut_ptr().cast(),
+                (buf.len() * std::mem::size_of::<libc::pid_t>()) as libc::c_int,
+            )
+        };
+        if bytes <= 0 {
+            return Vec::new();
+        }
+        buf.truncate(bytes as usize / std::mem::size_of::<libc::pid_t>());
+        buf.into_iter()
+            .filter_map(|pid| u32::try_from(pid).ok())
+            .filter(|&pid| pid != 0)
+            .collect()
+    }
+    #[cfg(not(target_os = "macos"))]
+    {
+        let Ok(entries) = std::fs::read_dir("/proc") else {
+            return Vec::new();
+        };
+        entries
+            .filter_map(|entry| entry.ok()?.file_name().to_str()?.parse::<u32>().ok())
+            .filter(|pid| {
+                std::fs::read_to_string(format!("/proc/{pid}/stat"))
+                    .ok()
+                    .and_then(|stat| parse_stat_pgrp(&stat))
+                    == Some(pgid)
+            })
+            .collect()
+    }
+}
+
+/// Process group from `/proc/<pid>/stat`. The command name in parens may
+/// itself contain spaces or parens, so fields are counted from the last `)`.
+#[cfg(any(not(target_os = "macos"), test))]
+fn parse_stat_pgrp(stat: &str) -> Option<u32> {
+    let rest = &stat[stat.rfind(')')? + 1..];
+    // state, ppid, pgrp
+    rest.split_whitespace().nth(2)?.parse().ok()
+}
+
+#[cfg(any(target_os = "macos", test))]
+fn parse_procargs2(buf: &[u8]) -> Option<Vec<String>> {
+    let argc = i32::from_ne_bytes(buf.get(..4)?.try_into().ok()?);
+    let rest = &buf[4..];
+    let exec_end = rest.iter().position(|&b| b == 0)?;
+    let mut args = rest[exec_end..]
+        .split(|&b| b == 0)
+        .filter(|a| !a.is_empty())
+        .map(|a| String::from_utf8_lossy(a).into_owned());
+    let args: Vec<String> = (&mut args).take(usize::try_from(argc).ok()?).collect();
+    (!args.is_empty()).then_some(args)
+}
+
+fn build_command(options: &PtyOptions) -> CommandBuilder {
+    let program = options
+        .program
+        .clone()
+        .unwrap_or_else(|| std::env::var("SHELL").unwrap_or_else(|_| "/bin/sh".to_string()));
+    let mut cmd = CommandBuilder::new(program);
+    if options.login_shell {
+        // Start an interactive login shell so zprofile/login PATH setup is applied.
+        cmd.arg("-l");
+    }
+    cmd.args(&options.args);
+    cmd.env("TERM", terminfo::term_name());
+    cmd.env("COLORTERM", "truecolor");
+    // .app 번들로 실행 시 launchd 환경에는 LANG이 없어 한글이 깨짐.
+    // 터미널 환경에 이미 있으면 그대로 쓰고, 없으면 UTF-8로 설정.
+    if std::env::var("LANG").unwrap_or_default().is_empty() {
+        cmd.env("LANG", "en_US.UTF-8");
+    }
+    for key in &options.env_remove {
+        cmd.env_remove(key);
+    }
+    for (key, value) in &options.env {
+        cmd.env(key, value);
+    }
+    if let Some(dir) = &options.cwd {
+        cmd.cwd(dir);
+    } else if let Some(home) = std::env::var_os("HOME") {
+        // .app 번들에서 실행 시 cwd가 / 등이 되어 쉘 시작 스크립트가
+        // 보호된 폴더에 접근하면 TCC 다이얼로그가 반복 발생함.
+        cmd.cwd(home);
+    }
     cmd
 }
 
 #[cfg(test)]
 mod tests {
+    use super::PtyOptions;
     use std::ffi::OsStr;
 
     #[test]
@@ -214,18 +567,148 @@ mod tests {
         assert!(cwd.unwrap().is_dir());
     }
 
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn child_cwd_reads_proc_on_linux() {
+        assert_eq!(super::child_cwd(std::process::id()), std::env::current_dir().ok());
+    }
+
     #[test]
     fn child_cwd_returns_none_for_invalid_pid() {
         assert!(super::child_cwd(0).is_none());
     }
 
+    #[test]
+    fn process_args_of_spawned_program() {
+        let opts = PtyOptions::new().program("/bin/sleep").arg("5");
+        let (_reader, writer) = super::spawn_with_options(&opts).unwrap();
+        let pid = writer.child_pid().expect("should have child PID");
+        // fork 직후에는 아직 exec 전일 수 있음
+        let mut args = None;
+        for _ in 0..50 {
+            args = super::process_args(pid);
+            if args.as_ref().and_then(|a| a.last()).is_some_and(|a| a == "5") {
+                break;
+            }
+            std::thread::sleep(std::time::Duration::from_millis(20));
+        }
+        assert_eq!(args.unwrap().last().map(String::as_str), Some("5"));
+    }
+
+    #[test]
+    fn procargs2_skips_exec_path_and_padding() {
+        let mut buf = 2i32.to_ne_bytes().to_vec();
+        buf.extend_from_slice(b"/usr/bin/ssh\0\0\0ssh\0me@host\0PATH=/bin\0");
+        assert_eq!(
+            super::parse_procargs2(&buf),
+            Some(vec!["ssh".to_string(), "me@host".to_string()])
+        );
+    }
+
+    #[test]
+    fn stat_pgrp_counts_fields_after_command_name() {
+        assert_eq!(super::parse_stat_pgrp("42 (vim) S 1 42 42 34816"), Some(42));
+        assert_eq!(super::parse_stat_pgrp("7 (a (b) c) R 3 9 9 0"), Some(9));
+        assert_eq!(super::parse_stat_pgrp("garbage"), None);
+    }
+
+    #[test]
+    fn process_group_of_spawned_program_contains_it() {
+        let opts = PtyOptions::new().program("/bin/sleep").arg("5");
+        let (_reader, writer) = super::spawn_with_options(&opts).unwrap();
+        let pid = writer.child_pid().expect("should have child PID");
+        // 자식은 setsid로 새 세션·프로세스 그룹의 리더가 됨 (fork 직후엔 아직일 수 있음)
+        let mut found = false;
+        for _ in 0..50 {
+            found = super::process_group_pids(pid).contains(&pid);
+            if found {
+                break;
+            }
+            std::thread::sleep(std::time::Duration::from_millis(20));
+        }
+        assert!(found);
+        // 포그라운드가 자식 자신이면 확인할 프로세스가 없음
+        assert!(writer.running_processes().is_empty());
+    }
+
     #[test]
     fn shell_command_includes_login_flag() {
-        let cmd = super::build_shell_command("/bin/zsh");
+        let cmd = super::build_command(&PtyOptions::new());
         let argv = cmd.get_argv();
         assert!(
             argv.iter().any(|arg| arg == OsStr::new("-l")),
             "expected shell command argv to include '-l', got: {argv:?}"
         );
     }
+
+    #[test]
+    fn custom_program_gets_args_without_login_flag() {
+        let opts = PtyOptions::new().program("ssh").args(["-p", "22", "host"]);
+        let cmd = super::build_command(&opts);
+        assert_eq!(cmd.get_argv(), &["ssh", "-p", "22", "host"]);
+    }
+
+    #[test]
+    fn login_flag_can_be_requested_for_custom_program() {
+        let opts = PtyOptions::new().program("/bin/zsh").login_shell(true).arg("-i");
+        let cmd = super::build_command(&opts);
+        assert_eq!(cmd.get_argv(), &["/bin/zsh", "-l", "-i"]);
+    }
+
+    #[test]
+    fn env_and_cwd_are_applied() {
+        let opts = PtyOptions::new()
+            .env("TERM", "xterm-kitty")
+            .env("GROWTERM_PROFILE", "work")
+            .cwd(Some("/tmp"));
+        let cmd = super::build_command(&opts);
+        assert_eq!(cmd.get_env("TERM"), Some(OsStr::new("xterm-kitty")));
+        assert_eq!(cmd.get_env("GROWTERM_PROFILE"), Some(OsStr::new("work")));
+        assert_eq!(cmd.get_env("COLORTERM"), Some(OsStr::new("truecolor")));
+        assert_eq!(cmd.get_cwd().map(|p| p.as_os_str()), Some(OsStr::new("/tmp")));
+    }
+
+    #[test]
+    fn env_remove_drops_inherited_and_default_vars() {
+        let opts = PtyOptions::new()
+            .env("HTTP_PROXY", "http://proxy:3128")
+            .env_remove("HTTP_PROXY")
+            .env_remove("COLORTERM")
+            .env_remove("HOME");
+        let cmd = super::build_command(&opts);
+        assert_eq!(cmd.get_env("HTTP_PROXY"), None);
+        assert_eq!(cmd.get_env("COLORTERM"), None);
+        assert_eq!(cmd.get_env("HOME"), None);
+
+        let opts = PtyOptions::new().env_remove("LANG").env("LANG", "ko_KR.UTF-8");
+        let cmd = super::build_command(&opts);
+        assert_eq!(cmd.get_env("LANG"), Some(OsStr::new("ko_KR.UTF-8")));
+    }
+
+    #[test]
+    fn exit_receiver_reports_status() {
+        let opts = PtyOptions::new().program("/bin/sh").args(["-c", "exit 3"]);
+        let (_reader, mut writer) = super::spawn_with_options(&opts).unwrap();
+        let rx = writer.take_exit_receiver().expect("receiver available once");
+        assert!(writer.take_exit_receiver().is_none());
+        let exit = rx.recv_timeout(std::time::Duration::from_secs(5)).unwrap();
+        assert_eq!(exit.code, 3);
+        assert!(!exit.success());
+    }
+
+    #[test]
+    fn spawn_with_options_runs_program() {
+        use std::io::Read;
+        let opts = PtyOptions::new().program("/bin/echo").arg("hello-pty");
+        let (mut reader, _writer) = super::spawn_with_options(&opts).unwrap();
+        let mut out = String::new();
+        let mut buf = [0u8; 256];
+        while !out.contains("hello-pty") {
+            match reader.read(&mut buf) {
+                Ok(0) | Err(_) => break,
+                Ok(n) => out.push_str(&String::from_utf8_lossy(&buf[..n])),
+            }
+        }
+        assert!(out.contains("hello-pty"), "got: {out:?}");
+    }
 }
@@ -0,0 +1,740 @@
+//! One poll(2) thread that reads every tab's PTY, writes its query
+//! replies and reaps its child, instead of blocking threads per tab.
+
+use std::fs::File;
+use std::io::{self, Read, Write};
+use std::os::unix::io::{AsRawFd, FromRawFd, RawFd};
+use std::sync::atomic::{AtomicI32, Ordering};
+use std::sync::{mpsc, Arc, Mutex, OnceLock};
+use std::time::{Duration, Instant};
+
+use crate::{ChildExit, PtyReader};
+
+/// Receives output of a PTY registered with [`register`].
+/// Called on the reactor thread, so keep it short: a slow handler
+/// delays every other tab.
+pub trait PtyHandler: Send {
+    fn on_data(&mut self, data: &[u8]) -> ReadFlow;
+    /// Called every [`TICK`] while the last call returned something other
+    /// than [`ReadFlow::Continue`], so buffered output can be drained even
+    /// when the PTY is quiet or paused.
+    fn on_tick(&mut self) -> ReadFlow {
+        ReadFlow::Continue
+    }
+    /// The PTY hit EOF/EIO (the child exited). Called once, then dropped.
+    fn on_close(&mut self);
+}
+
+/// What the handler wants after taking some output.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReadFlow {
+    /// Nothing buffered; wake only for new output.
+    Continue,
+    /// Output is buffered; keep reading and tick.
+    Drain,
+    /// Too much is buffered; stop reading so the kernel blocks the
+    /// writer, and tick until the handler catches up.
+    Pause,
+}
+
+/// How often backlogged handlers get [`PtyHandler::on_tick`].
+pub const TICK: Duration = Duration::from_millis(8);
+
+const MIN_READ: usize = 4 * 1024;
+const MAX_READ: usize = 64 * 1024;
+const COALESCE: Duration = Duration::from_micros(500);
+
+/// How a source registered with [`register_with_options`] is read.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ReadOptions {
+    /// Smallest read size; also how much held output is handed over at once
+    pub min_read: usize,
+    /// Largest read size, i.e. the most one source gets per poll round
+    pub max_read: usize,
+    /// How long small reads that arrive back to back are held so the
+    /// handler gets them as one chunk. Zero hands every read over as is.
+    /// poll(2) waits in whole milliseconds, so the hold can run up to 1ms.
+    pub coalesce: Duration,
+}
+
+impl Default for ReadOptions {
+    fn default() -> Self {
+        Self { min_read: MIN_READ, max_read: MAX_READ, coalesce: COALESCE }
+    }
+}
+
+/// Read size per source: grows while reads fill the buffer (bulk output
+/// such as `cat`), shrinks back when they don't, so one flooding tab
+/// gets at most `max` per poll round before the others are served.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct ReadSizer {
+    size: usize,
+    min: usize,
+    max: usize,
+}
+
+impl ReadSizer {
+    pub(crate) fn new(min: usize, max: usize) -> Self {
+        let min = min.max(1);
+        Self { size: min, min, max: max.max(min) }
+    }
+
+    pub(crate) fn size(&self) -> usize {
+        self.size
+    }
+
+    pub(crate) fn record(&mut self, n: usize) {
+        if n >= self.size {
+            self.size = (self.size * 2).min(self.max);
+        } else if n < self.size / 4 {
+            self.size = (self.size / 2).max(self.min);
+        }
+    }
+}
+
+/// Holds small reads that arrive in quick succession (a build printing
+/// line by line) so the handler parses one chunk instead of dozens.
+/// A read after a quiet spell, like the echo of a keystroke, goes
+/// straight through.
+#[derive(Debug)]
+pub(crate) struct Coalescer {
+    window: Duration,
+    threshold: usize,
+    pending: Vec<u8>,
+    deadline: Option<Instant>,
+    last_read: Option<Instant>,
+}
+
+impl Coalescer {
+    pub(crate) fn new(window: Duration, threshold: usize) -> Self {
+        Self { window, threshold, pending: Vec::new(), deadline: None, last_read: None }
+    }
+
+    /// Add a read made at `now`. True when the held bytes should be
+    /// handed over now rather than waiting for more.
+    pub(crate) fn push(&mut self, data: &[u8], now: Instant) -> bool {
+        let burst = self
+            .last_read
+            .is_some_and(|last| now.saturating_duration_since(last) < self.window);
+        self.last_read = Some(now);
+        self.pending.extend_from_slice(data);
+        if !burst || self.pending.len() >= self.threshold {
+            return true;
+        }
+        self.deadline.get_or_insert(now + self.window);
+        false
+    }
+
+    /// When held bytes must go out even if nothing more arrives
+    pub(crate) fn deadline(&self) -> Option<Instant> {
+        self.deadline
+    }
+
+    pub(crate) fn pending(&self) -> &[u8] {
+        &self.pending
+    }
+
+    pub(crate) fn clear(&mut self) {
+        self.pending.clear();
+        self.deadline = None;
+    }
+}
+
+/// Bytes queued for the child's stdin by a [`crate::PtyResponder`],
+/// written by the reactor whenever the PTY has room.
+pub(crate) type ReplyQueue = Arc<Mutex<Vec<u8>>>;
+
+/// Replies beyond this are dropped: a child that lets this much pile up
+/// isn't reading its input.
+pub(crate) const MAX_PENDING_REPLIES: usize = 64 * 1024;
+
+struct Source {
+    file: File,
+    handler: Box<dyn PtyHandler>,
+    sizer: ReadSizer,
+    coalescer: Coalescer,
+    flow: ReadFlow,
+    replies: ReplyQueue,
+}
+
+impl Source {
+    /// Give the handler whatever the coalescer holds.
+    fn deliver(&mut self) {
+        if !self.coalescer.pending().is_empty() {
+            self.flow = self.handler.on_data(self.coalescer.pending());
+            self.coalescer.clear();
+        }
+    }
+
+    fn has_replies(&self) -> bool {
+        self.replies.lock().is_ok_and(|pending| !pending.is_empty())
+    }
+
+    /// Write queued replies until the PTY would block. The fd is
+    /// non-blocking, so a child that stops reading never stalls the reactor.
+    fn flush_replies(&mut self) {
+        let Ok(mut pending) = self.replies.lock() else {
+            return;
+        };
+        while !pending.is_empty() {
+            match self.file.write(&pending) {
+                Ok(0) => break,
+                Ok(n) => {
+                    pending.drain(..n);
+                }
+                Err(e) if e.kind() == io::ErrorKind::Interrupted => {}
+                Err(e) if e.kind() == io::ErrorKind::WouldBlock => break,
+                // PTY가 닫힘: 응답을 받을 자식이 없음
+                Err(_) => pending.clear(),
+            }
+        }
+    }
+}
+
+/// A child process the reactor waits for.
+struct Watched {
+    child: Box<dyn portable_pty::Child + Send + Sync>,
+    on_exit: Box<dyn FnOnce(ChildExit) + Send>,
+}
+
+enum Request {
+    Read(Source),
+    Reap(Watched),
+}
+
+struct Reactor {
+    tx: Mutex<mpsc::Sender<Request>>,
+    /// Write end of the self-pipe that interrupts poll(2) on registration
+    /// and on SIGCHLD
+    wake: File,
+}
+
+/// `wake`'s fd for the SIGCHLD handler, which can't reach `REACTOR`
+static SIGCHLD_WAKE_FD: AtomicI32 = AtomicI32::new(-1);
+
+static REACTOR: OnceLock<io::Result<Reactor>> = OnceLock::new();
+
+/// Hand `reader` to the shared reactor thread; `handler` gets its output
+/// until the PTY closes. The reactor thread starts on first use.
+pub fn register(reader: PtyReader, handler: Box<dyn PtyHandler>) -> io::Result<()> {
+    register_with_options(reader, handler, ReadOptions::default())
+}
+
+/// [`register`] with explicit read sizes and coalescing.
+pub fn register_with_options(reader: PtyReader, handler: Box<dyn PtyHandler>, options: ReadOptions) -> io::Result<()> {
+    let (file, replies) = reader.into_parts();
+    // The fd shares its open file description with the PTY writer, which
+    // waits out EAGAIN itself (see `PtyWriter::write`).
+    set_nonblocking(file.as_raw_fd())?;
+    let sizer = ReadSizer::new(options.min_read, options.max_read);
+    send(Request::Read(Source {
+        file,
+        handler,
+        sizer,
+        coalescer: Coalescer::new(options.coalesce, sizer.size()),
+        flow: ReadFlow::Continue,
+        replies,
+    }))
+}
+
+/// Have the reactor thread reap `child` and call `on_exit` (on that
+/// thread) once it has exited.
+pub(crate) fn watch_child(
+    child: Box<dyn portable_pty::Child + Send + Sync>,
+    on_exit: Box<dyn FnOnce(ChildExit) + Send>,
+) -> io::Result<()> {
+    send(Request::Reap(Watched { child, on_exit }))
+}
+
+fn send(request: Request) -> io::Result<()> {
+    let reactor = REACTOR
+        .get_or_init(start)
+        .as_ref()
+        .map_err(|e| io::Error::new(e.kind(), e.to_string()))?;
+    reactor
+        .tx
+        .lock()
+        .map_err(|_| io::Error::new(io::ErrorKind::Other, "pty reactor lock poisoned"))?
+        .send(request)
+        .map_err(|_| io::Error::new(io::ErrorKind::BrokenPipe, "pty reactor stopped"))?;
+    io::Write::write_all(&mut &reactor.wake, &[1])
+}
+
+fn set_nonblocking(fd: RawFd) -> io::Result<()> {
+    let flags = unsafe { libc::fcntl(fd, libc::F_GETFL) };
+    if flags < 0 || unsafe { libc::fcntl(fd, libc::F_SETFL, flags | libc::O_NONBLOCK) } < 0 {
+        return Err(io::Error::last_os_error());
+    }
+    Ok(())
+}
+
+/// Wake the reactor so it reaps whichever child just exited.
+/// Only async-signal-safe calls here.
+extern "C" fn on_sigchld(_: libc::c_int) {
+    let fd = SIGCHLD_WAKE_FD.load(Ordering::Relaxed);
+    if fd >= 0 {
+        unsafe {
+            let errno = *errno_location();
+            libc::write(fd, [1u8].as_ptr().cast(), 1);
+            *errno_location() = errno;
+        }
+    }
+}
+
+#[cfg(target_os = "macos")]
+unsafe fn errno_location() -> *mut libc::c_int {
+    libc::__error()
+}
+
+#[cfg(not(target_os = "macos"))]
+unsafe fn errno_location() -> *mut libc::c_int {
+    libc::__errno_location()
+}
+
+fn install_sigchld_handler(wake_fd: RawFd) -> io::Result<()> {
+    SIGCHLD_WAKE_FD.store(wake_fd, Ordering::Relaxed);
+    unsafe {
+        let mut action: libc::sigaction = std::mem::zeroed();
+        action.sa_sigaction = on_sigchld as extern "C" fn(libc::c_int) as libc::sighandler_t;
+        action.sa_flags = libc::SA_RESTART | libc::SA_NOCLDSTOP;
+        libc::sigemptyset(&mut action.sa_mask);
+        if libc::sigaction(libc::SIGCHLD, &action, std::ptr::null_mut()) != 0 {
+            return Err(io::Error::last_os_error());
+        }
+    }
+    Ok(())
+}
+
+fn start() -> io::Result<Reactor> {
+    let mut fds = [0 as RawFd; 2];
+    if unsafe { libc::pipe(fds.as_mut_ptr()) } != 0 {
+        return Err(io::Error::last_os_error());
+    }
+    let (wake_rx, wake_tx) = unsafe { (File::from_raw_fd(fds[0]), File::from_raw_fd(fds[1])) };
+    for fd in fds {
+        unsafe {
+            libc::fcntl(fd, libc::F_SETFD, libc::FD_CLOEXEC);
+            // 깨우기 바이트가 쌓여도 register()가 막히지 않도록
+            libc::fcntl(fd, libc::F_SETFL, libc::O_NONBLOCK);
+        }
+    }
+    install_sigchld_handler(wake_tx.as_raw_fd())?;
+    let (tx, rx) = mpsc::channel();
+    std::thread::Builder::new()
+        .name("pty-reactor".into())
+        .spawn(move || run(wake_rx, rx))?;
+    Ok(Reactor {
+        tx: Mutex::new(tx),
+        wake: wake_tx,
+    })
+}
+
+/// Report every watched child that has exited.
+fn reap(children: &mut Vec<Watched>) {
+    let mut i = 0;
+    while i < children.len() {
+        let code = match children[i].child.try_wait() {
+            Ok(None) => {
+                i += 1;
+                continue;
+            }
+            Ok(Some(status)) => status.exit_code(),
+            Err(_) => 1,
+        };
+        let watched = children.swap_remove(i);
+        (watched.on_exit)(ChildExit { code });
+    }
+}
+
+fn run(mut wake: File, rx: mpsc::Receiver<Request>) {
+    let mut sources: Vec<Source> = Vec::new();
+    let mut children: Vec<Watched> = Vec::new();
+    let mut buf = Vec::new();
+    let mut drain = [0u8; 64];
+    let mut last_tick = Instant::now();
+    loop {
+        let mut pollfds: Vec<libc::pollfd> = std::iter::once(libc::pollfd {
+            fd: wake.as_raw_fd(),
+            events: libc::POLLIN,
+            revents: 0,
+        })
+        .chain(sources.iter().map(|s| {
+            let mut events = 0;
+            if s.flow != ReadFlow::Pause {
+                events |= libc::POLLIN;
+            }
+            if s.has_replies() {
+                events |= libc::POLLOUT;
+            }
+            // poll(2) ignores negative fds: a paused PTY with nothing to
+            // write isn't watched at all
+            libc::pollfd {
+                fd: if events == 0 { -1 } else { s.file.as_raw_fd() },
+                events,
+                revents: 0,
+            }
+        }))
+        .collect();
+        let ticking = sources.iter().any(|s| s.flow != ReadFlow::Continue);
+        let tick_timeout = if ticking {
+            TICK.saturating_sub(last_tick.elapsed()).as_millis() as libc::c_int
+        } else {
+            -1
+        };
+        // 붙잡아 둔 출력의 마감. 0ms로 돌지 않도록 올림
+        let hold_timeout = sources
+            .iter()
+            .filter_map(|s| s.coalescer.deadline())
+            .min()
+            .map(|deadline| deadline.saturating_duration_since(Instant::now()).as_micros().div_ceil(1000) as libc::c_int);
+        let timeout = match hold_timeout {
+            Some(hold) if tick_timeout < 0 || hold < tick_timeout => hold,
+            _ => tick_timeout,
+        };
+        let ret =
+            unsafe { libc::poll(pollfds.as_mut_ptr(), pollfds.len() as libc::nfds_t, timeout) };
+        if ret < 0 {
+            if io::Error::last_os_error().kind() == io::ErrorKind::Interrupted {
+                continue;
+            }
+            break;
+        }
+
+        // Read before accepting new sources so indices still match `pollfds`.
+        let mut closed = Vec::new();
+        for (i, source) in sources.iter_mut().enumerate() {
+            let revents = pollfds[i + 1].revents;
+            if revents & libc::POLLOUT != 0 {
+                source.flush_replies();
+            }
+            if source.flow == ReadFlow::Pause
+                || revents & (libc::POLLIN | libc::POLLHUP | libc::POLLERR | libc::POLLNVAL) == 0
+            {
+                continue;
+            }
+            // poll said readable (or hung up), so this read won't block.
+            let size = source.sizer.size();
+            if buf.len() < size {
+                buf.resize(size, 0);
+            }
+            match source.file.read(&mut buf[..size]) {
+                Ok(0) => closed.push(i),
+                Ok(n) => {
+                    source.sizer.record(n);
+                    if source.coalescer.push(&buf[..n], Instant::now()) {
+                        source.deliver();
+                    }
+                }
+                Err(e) if matches!(e.kind(), io::ErrorKind::Interrupted | io::ErrorKind::WouldBlock) => {}
+                // macOS/Linux report EIO once the slave side is gone
+                Err(_) => closed.push(i),
+            }
+        }
+        let any_closed = !closed.is_empty();
+        for i in closed.into_iter().rev() {
+            let mut source = sources.swap_remove(i);
+            source.deliver();
+            source.handler.on_close();
+        }
+        let now = Instant::now();
+        for source in sources.iter_mut() {
+            if source.coalescer.deadline().is_some_and(|deadline| now >= deadline) {
+                source.deliver();
+            }
+        }
+
+        if ticking && last_tick.elapsed() >= TICK {
+            last_tick = Instant::now();
+            for source in sources.iter_mut().filter(|s| s.flow != ReadFlow::Continue) {
+                source.flow = source.handler.on_tick();
+            }
+        } else if !ticking {
+            last_tick = Instant::now();
+        }
+
+        if pollfds[0].revents != 0 {
+            while matches!(wake.read(&mut drain), Ok(n) if n > 0) {}
+            for request in rx.try_iter() {
+                match request {
+                    Request::Read(source) => sources.push(source),
+                    Request::Reap(watched) => children.push(watched),
+                }
+            }
+        }
+        // SIGCHLD이 다른 스레드에서 소비됐어도 PTY가 닫히면 한 번 더 확인
+        if pollfds[0].revents != 0 || any_closed {
+            reap(&mut children);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::PtyOptions;
+
+    #[test]
+    fn read_size_grows_on_full_reads_and_is_capped() {
+        let mut sizer = ReadSizer::new(MIN_READ, MAX_READ);
+        for _ in 0..10 {
+            let n = sizer.size();
+            sizer.record(n);
+        }
+        assert_eq!(sizer.size(), MAX_READ);
+    }
+
+    #[test]
+    fn read_size_shrinks_on_small_reads() {
+        let mut sizer = ReadSizer::new(MIN_READ, MAX_READ);
+        sizer.record(MIN_READ);
+        sizer.record(MIN_READ * 2);
+        assert_eq!(sizer.size(), MIN_READ * 4);
+        sizer.record(10);
+        assert_eq!(sizer.size(), MIN_READ * 2);
+        sizer.record(10);
+        sizer.record(10);
+        assert_eq!(sizer.size(), MIN_READ);
+    }
+
+    #[test]
+    fn read_size_follows_options() {
+        let mut sizer = ReadSizer::new(1024, 2048);
+        assert_eq!(sizer.size(), 1024);
+        for _ in 0..4 {
+            let n = sizer.size();
+            sizer.record(n);
+        }
+        assert_eq!(sizer.size(), 2048);
+    }
+
+    #[test]
+    fn read_after_quiet_spell_goes_straight_through() {
+        let mut coalescer = Coalescer::new(Duration::from_millis(1), 4096);
+        let t0 = Instant::now();
+        assert!(coalescer.push(b"a", t0));
+        coalescer.clear();
+        assert!(coalescer.push(b"b", t0 + Duration::from_millis(5)));
+    }
+
+    #[test]
+    fn back_to_back_small_reads_are_held_until_deadline_or_threshold() {
+        let mut coalescer = Coalescer::new(Duration::from_millis(1), 8);
+        let t0 = Instant::now();
+        assert!(coalescer.push(b"one\n", t0));
+        coalescer.clear();
+        let t1 = t0 + Duration::from_micros(100);
+        assert!(!coalescer.push(b"two\n", t1));
+        assert_eq!(coalescer.deadline(), Some(t1 + Duration::from_millis(1)));
+        // 임계값을 넘으면 바로 넘김
+        assert!(coalescer.push(b"three\n", t1 + Duration::from_micros(100)));
+        assert_eq!(coalescer.pending(), b"two\nthree\n");
+        coalescer.clear();
+        assert_eq!(coalescer.deadline(), None);
+    }
+
+    #[test]
+    fn zero_window_never_holds() {
+        let mut coalescer = Coalescer::new(Duration::ZERO, 4096);
+        let t0 = Instant::now();
+        assert!(coalescer.push(b"a", t0));
+        assert!(coalescer.push(b"b", t0));
+    }
+
+    enum Event {
+        Data(Vec<u8>),
+        Closed,
+        Tick,
+    }
+
+    struct ChannelHandler(mpsc::Sender<Event>);
+
+    impl PtyHandler for ChannelHandler {
+        fn on_data(&mut self, data: &[u8]) -> ReadFlow {
+            let _ = self.0.send(Event::Data(data.to_vec()));
+            ReadFlow::Continue
+        }
+
+        fn on_close(&mut self) {
+            let _ = self.0.send(Event::Closed);
+        }
+    }
+
+    #[test]
+    fn multiplexes_several_ptys_until_close() {
+        let mut receivers = Vec::new();
+        let mut writers = Vec::new();
+        for word in ["reactor-a", "reactor-b", "reactor-c"] {
+            let opts = PtyOptions::new().program("/bin/echo").arg(word);
+            let (reader, writer) = crate::spawn_with_options(&opts).unwrap();
+            let (tx, rx) = mpsc::channel();
+            register(reader, Box::new(ChannelHandler(tx))).unwrap();
+            receivers.push((word, rx));
+            writers.push(writer);
+        }
+        for (word, rx) in receivers {
+            let mut out = String::new();
+            loop {
+                match rx
+                    .recv_timeout(Duration::from_secs(5))
+                    .expect("reactor event")
+                {
+                    Event::Data(bytes) => out.push_str(&String::from_utf8_lossy(&bytes)),
+                    Event::Closed => break,
+                    Event::Tick => {}
+                }
+            }
+            assert!(out.contains(word), "got: {out:?}");
+        }
+    }
+
+    /// Answers every chunk with more input than the child will ever read.
+    struct FloodingResponder {
+        tx: mpsc::Sender<Event>,
+        responder: crate::PtyResponder,
+    }
+
+    impl PtyHandler for FloodingResponder {
+        fn on_data(&mut self, data: &[u8]) -> ReadFlow {
+            let _ = self.tx.send(Event::Data(data.to_vec()));
+            for _ in 0..64 {
+                self.responder.respond(&[b'x'; 4096]);
+            }
+            ReadFlow::Continue
+        }
+
+        fn on_close(&mut self) {
+            let _ = self.tx.send(Event::Closed);
+        }
+    }
+
+    #[test]
+    fn child_not_reading_stdin_does_not_stall_other_ptys() {
+        // raw 모드라 줄 단위 버퍼 없이 입력이 쌓이다 커널 버퍼가 차서 막힘
+        let opts = PtyOptions::new()
+            .program("/bin/sh")
+            .args(["-c", "stty raw -echo; echo stuck; sleep 5"]);
+        let (reader, stuck_writer) = crate::spawn_with_options(&opts).unwrap();
+        let (tx, stuck_rx) = mpsc::channel();
+        let responder = stuck_writer.responder();
+        register(reader, Box::new(FloodingResponder { tx, responder })).unwrap();
+        match stuck_rx.recv_timeout(Duration::from_secs(5)).expect("first output") {
+            Event::Data(_) => {}
+            _ => panic!("stuck pty closed early"),
+        }
+
+        let opts = PtyOptions::new().program("/bin/echo").arg("still-flowing");
+        let (reader, _writer) = crate::spawn_with_options(&opts).unwrap();
+        let (tx, rx) = mpsc::channel();
+        register(reader, Box::new(ChannelHandler(tx))).unwrap();
+        let mut out = String::new();
+        loop {
+            match rx.recv_timeout(Duration::from_secs(3)).expect("second pty output") {
+                Event::Data(bytes) => out.push_str(&String::from_utf8_lossy(&bytes)),
+                Event::Closed => break,
+                Event::Tick => {}
+            }
+        }
+        assert!(out.contains("still-flowing"), "got: {out:?}");
+    }
+
+    /// Answers the child's prompt the way a terminal answers a query.
+    struct AnsweringHandler {
+        tx: mpsc::Sender<Event>,
+        responder: crate::PtyResponder,
+    }
+
+    impl PtyHandler for AnsweringHandler {
+        fn on_data(&mut self, data: &[u8]) -> ReadFlow {
+            if String::from_utf8_lossy(data).contains("ask?") {
+                self.responder.respond(b"answer\n");
+            }
+            let _ = self.tx.send(Event::Data(data.to_vec()));
+            ReadFlow::Continue
+        }
+
+        fn on_close(&mut self) {
+            let _ = self.tx.send(Event::Closed);
+        }
+    }
+
+    #[test]
+    fn replies_are_written_to_the_child() {
+        let opts = PtyOptions::new()
+            .program("/bin/sh")
+            .args(["-c", "printf 'ask?'; read reply; echo \"got:$reply\""]);
+        let (reader, mut writer) = crate::spawn_with_options(&opts).unwrap();
+        let exit_rx = writer.take_exit_receiver().unwrap();
+        let (tx, rx) = mpsc::channel();
+        let responder = writer.responder();
+        register(reader, Box::new(AnsweringHandler { tx, responder })).unwrap();
+        let mut out = String::new();
+        loop {
+            match rx.recv_timeout(Duration::from_secs(5)).expect("reactor event") {
+                Event::Data(bytes) => out.push_str(&String::from_utf8_lossy(&bytes)),
+                Event::Closed => break,
+                Event::Tick => {}
+            }
+        }
+        assert!(out.contains("got:answer"), "got: {out:?}");
+        assert_eq!(exit_rx.recv_timeout(Duration::from_secs(5)).unwrap().code, 0);
+    }
+
+    /// Pauses after the first chunk and resumes on the third tick.
+    struct PausingHandler {
+        tx: mpsc::Sender<Event>,
+        ticks: u32,
+    }
+
+    impl PtyHandler for PausingHandler {
+        fn on_data(&mut self, data: &[u8]) -> ReadFlow {
+            let _ = self.tx.send(Event::Data(data.to_vec()));
+            if self.ticks < 3 {
+                ReadFlow::Pause
+            } else {
+                ReadFlow::Continue
+            }
+        }
+
+        fn on_tick(&mut self) -> ReadFlow {
+            self.ticks += 1;
+            let _ = self.tx.send(Event::Tick);
+            if self.ticks < 3 {
+                ReadFlow::Pause
+            } else {
+                ReadFlow::Continue
+            }
+        }
+
+        fn on_close(&mut self) {
+            let _ = self.tx.send(Event::Closed);
+        }
+    }
+
+    #[test]
+    fn paused_source_is_ticked_and_not_read() {
+        let opts = PtyOptions::new()
+            .program("/bin/sh")
+            .args(["-c", "echo first; sleep 0.1; echo second"]);
+        let (reader, _writer) = crate::spawn_with_options(&opts).unwrap();
+        let (tx, rx) = mpsc::channel();
+        register(reader, Box::new(PausingHandler { tx, ticks: 0 })).unwrap();
+        let mut events = Vec::new();
+        loop {
+            match rx
+                .recv_timeout(Duration::from_secs(5))
+                .expect("reactor event")
+            {
+                Event::Closed => break,
+                event => events.push(event),
+            }
+        }
+        let ticks_before_second = events
+            .iter()
+            .take_while(
+                |e| !matches!(e, Event::Data(d) if String::from_utf8_lossy(d).contains("second")),
+            )
+            .filter(|e| matches!(e, Event::Tick))
+            .count();
+        assert_eq!(ticks_before_second, 3);
+    }
+}
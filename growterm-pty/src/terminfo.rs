@@ -0,0 +1,97 @@
+//! The bundled `growterm` terminfo entry and the `TERM` it implies.
+
+use std::io;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+/// Source of the `growterm` entry, compiled with `tic` on install.
+pub const TERMINFO_SOURCE: &str = include_str!("../terminfo/growterm.terminfo");
+
+pub const TERM_NAME: &str = "growterm";
+/// Used while the `growterm` entry isn't installed, so programs still
+/// find a description they understand.
+pub const FALLBACK_TERM: &str = "xterm-256color";
+
+/// `TERM` for new PTYs: `growterm` when its entry can be found,
+/// `xterm-256color` otherwise.
+pub fn term_name() -> &'static str {
+    if find_entry(&search_dirs()).is_some() {
+        TERM_NAME
+    } else {
+        FALLBACK_TERM
+    }
+}
+
+/// Compile the bundled entry into `~/.terminfo` unless one is already
+/// installed. Returns whether it installed anything.
+pub fn install_terminfo() -> io::Result<bool> {
+    if find_entry(&search_dirs()).is_some() {
+        return Ok(false);
+    }
+    let home = std::env::var_os("HOME")
+        .ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, "HOME is not set"))?;
+    let out_dir = Path::new(&home).join(".terminfo");
+    std::fs::create_dir_all(&out_dir)?;
+    let source = std::env::temp_dir().join(format!("growterm-{}.terminfo", std::process::id()));
+    std::fs::write(&source, TERMINFO_SOURCE)?;
+    let status = Command::new("tic").arg("-x").arg("-o").arg(&out_dir).arg(&source).status();
+    let _ = std::fs::remove_file(&source);
+    if !status?.success() {
+        return Err(io::Error::new(io::ErrorKind::Other, "tic failed to compile the growterm entry"));
+    }
+    Ok(true)
+}
+
+/// Directories ncurses looks in, in order.
+fn search_dirs() -> Vec<PathBuf> {
+    let mut dirs = Vec::new();
+    if let Some(dir) = std::env::var_os("TERMINFO") {
+        dirs.push(PathBuf::from(dir));
+    }
+    if let Some(home) = std::env::var_os("HOME") {
+        dirs.push(Path::new(&home).join(".terminfo"));
+    }
+    if let Some(list) = std::env::var_os("TERMINFO_DIRS") {
+        dirs.extend(std::env::split_paths(&list).filter(|p| !p.as_os_str().is_empty()));
+    }
+    for dir in ["/etc/terminfo", "/lib/terminfo", "/usr/share/terminfo", "/usr/lib/terminfo"] {
+        dirs.push(PathBuf::from(dir));
+    }
+    dirs
+}
+
+/// Compiled entry in one of `dirs`. ncurses files it under the first
+/// letter (`g/growterm`), macOS's under its hex code (`67/growterm`).
+fn find_entry(dirs: &[PathBuf]) -> Option<PathBuf> {
+    dirs.iter()
+        .flat_map(|dir| [dir.join("g").join(TERM_NAME), dir.join("67").join(TERM_NAME)])
+        .find(|path| path.is_file())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn finds_entry_in_letter_or_hex_layout() {
+        let root = std::env::temp_dir().join(format!("growterm-terminfo-test-{}", std::process::id()));
+        let letter = root.join("letter");
+        let hex = root.join("hex");
+        std::fs::create_dir_all(letter.join("g")).unwrap();
+        std::fs::create_dir_all(hex.join("67")).unwrap();
+        std::fs::write(hex.join("67").join(TERM_NAME), b"").unwrap();
+
+        assert_eq!(find_entry(&[letter.clone()]), None);
+        assert_eq!(find_entry(&[letter.clone(), hex.clone()]), Some(hex.join("67").join(TERM_NAME)));
+
+        std::fs::write(letter.join("g").join(TERM_NAME), b"").unwrap();
+        assert_eq!(find_entry(&[letter.clone(), hex]), Some(letter.join("g").join(TERM_NAME)));
+        std::fs::remove_dir_all(root).unwrap();
+    }
+
+    #[test]
+    fn source_describes_growterm() {
+        assert!(TERMINFO_SOURCE.contains("\ngrowterm|"));
+        assert!(TERMINFO_SOURCE.contains("smcup=\\E[?1049h"));
+    }
+}
@@ -86,10 +86,12 @@ fn term_env_is_set() {
         .write_all(b"printf 'TERMVAL=%s\\n' \"$TERM\"\n")
         .expect("write failed");
 
-    let output = read_until(reader, "TERMVAL=xterm-256color", Duration::from_secs(20));
+    // growterm 항목이 설치돼 있지 않으면 xterm-256color
+    let expected = format!("TERMVAL={}", growterm_pty::term_name());
+    let output = read_until(reader, &expected, Duration::from_secs(20));
     assert!(
-        output.contains("TERMVAL=xterm-256color"),
-        "expected TERM=xterm-256color in output, got: {output}"
+        output.contains(&expected),
+        "expected {expected} in output, got: {output}"
     );
 }
 
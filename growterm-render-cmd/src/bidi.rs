@@ -0,0 +1,278 @@
+//! Right-to-left rows: the grid keeps cells in logical (typed) order, this
+//! works out where each cell is drawn. Rows run left-to-right as a whole
+//! and only RTL runs inside them are reversed, so prompts and columns of
+//! Latin text stay where they are. Arabic letters also get their joined
+//! (initial/medial/final) forms, since every cell is drawn on its own.
+
+use growterm_types::{Cell, CellFlags};
+use unicode_bidi::{bidi_class, BidiClass, BidiInfo, Level};
+
+/// Where the cells of one RTL row are drawn, and with which character.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VisualRow {
+    /// logical col → visual col
+    visual: Vec<u16>,
+    /// visual col → logical col
+    logical: Vec<u16>,
+    chars: Vec<char>,
+}
+
+impl VisualRow {
+    /// Screen column of the cell at `col`. Wide characters are drawn from
+    /// the visual column of their first cell.
+    pub fn visual_col(&self, col: u16) -> u16 {
+        self.visual.get(col as usize).copied().unwrap_or(col)
+    }
+
+    /// Cell under screen column `col`, e.g. for a mouse click.
+    pub fn logical_col(&self, col: u16) -> u16 {
+        self.logical.get(col as usize).copied().unwrap_or(col)
+    }
+
+    /// Character to draw for the cell at `col`: mirrored brackets in RTL
+    /// runs, joined forms of Arabic letters.
+    pub fn character(&self, col: u16) -> Option<char> {
+        self.chars.get(col as usize).copied()
+    }
+}
+
+fn is_rtl(c: char) -> bool {
+    c >= '\u{0590}' && matches!(bidi_class(c), BidiClass::R | BidiClass::AL)
+}
+
+/// Visual layout of `line`, or None when it has no RTL text and is drawn
+/// as stored.
+pub fn layout(line: &[Cell]) -> Option<VisualRow> {
+    if !line.iter().any(|cell| is_rtl(cell.character)) {
+        return None;
+    }
+
+    // 한 셀(넓은 문자는 두 셀)이 문자 하나
+    let mut units: Vec<(usize, usize)> = Vec::new(); // (first col, width)
+    let mut text = String::new();
+    let mut starts = Vec::new();
+    let mut col = 0;
+    while col < line.len() {
+        let cell = &line[col];
+        let width = if cell.flags.contains(CellFlags::WIDE_CHAR) { 2 } else { 1 };
+        let width = width.min(line.len() - col);
+        starts.push(text.len());
+        text.push(if cell.character == '\0' { ' ' } else { cell.character });
+        units.push((col, width));
+        col += width;
+    }
+
+    let info = BidiInfo::new(&text, Some(Level::ltr()));
+    let [para] = info.paragraphs.as_slice() else {
+        return None;
+    };
+    let (levels, runs) = info.visual_runs(para, para.range.clone());
+
+    let mut chars: Vec<char> = line.iter().map(|cell| cell.character).collect();
+    shape_arabic(line, &units, &mut chars);
+
+    let mut visual = vec![0u16; line.len()];
+    let mut logical = vec![0u16; line.len()];
+    let mut next_col = 0usize;
+    for run in runs {
+        let first = starts.partition_point(|&start| start < run.start);
+        let last = starts.partition_point(|&start| start < run.end);
+        let rtl = levels[run.start].is_rtl();
+        let mut order: Vec<usize> = (first..last).collect();
+        if rtl {
+            order.reverse();
+        }
+        for unit in order {
+            let (start, width) = units[unit];
+            for offset in 0..width {
+                visual[start + offset] = (next_col + offset) as u16;
+                logical[next_col + offset] = start as u16;
+            }
+            if rtl {
+                chars[start] = mirror(chars[start]);
+            }
+            next_col += width;
+        }
+    }
+
+    Some(VisualRow { visual, logical, chars })
+}
+
+/// Paired punctuation drawn mirrored inside RTL runs
+fn mirror(c: char) -> char {
+    match c {
+        '(' => ')',
+        ')' => '(',
+        '[' => ']',
+        ']' => '[',
+        '{' => '}',
+        '}' => '{',
+        '<' => '>',
+        '>' => '<',
+        '«' => '»',
+        '»' => '«',
+        other => other,
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Joining {
+    /// Joins on both sides (بـ)
+    Dual,
+    /// Joins only to the preceding letter (ا، د، و)
+    Right,
+    /// Tatweel and ZWJ: join whatever is next to them
+    Causing,
+    None,
+}
+
+/// (letter, isolated presentation form, joining type). The final, initial
+/// and medial forms follow the isolated one in that order.
+const ARABIC_FORMS: &[(char, u32, Joining)] = &[
+    ('\u{0622}', 0xFE81, Joining::Right),
+    ('\u{0623}', 0xFE83, Joining::Right),
+    ('\u{0624}', 0xFE85, Joining::Right),
+    ('\u{0625}', 0xFE87, Joining::Right),
+    ('\u{0626}', 0xFE89, Joining::Dual),
+    ('\u{0627}', 0xFE8D, Joining::Right),
+    ('\u{0628}', 0xFE8F, Joining::Dual),
+    ('\u{0629}', 0xFE93, Joining::Right),
+    ('\u{062A}', 0xFE95, Joining::Dual),
+    ('\u{062B}', 0xFE99, Joining::Dual),
+    ('\u{062C}', 0xFE9D, Joining::Dual),
+    ('\u{062D}', 0xFEA1, Joining::Dual),
+    ('\u{062E}', 0xFEA5, Joining::Dual),
+    ('\u{062F}', 0xFEA9, Joining::Right),
+    ('\u{0630}', 0xFEAB, Joining::Right),
+    ('\u{0631}', 0xFEAD, Joining::Right),
+    ('\u{0632}', 0xFEAF, Joining::Right),
+    ('\u{0633}', 0xFEB1, Joining::Dual),
+    ('\u{0634}', 0xFEB5, Joining::Dual),
+    ('\u{0635}', 0xFEB9, Joining::Dual),
+    ('\u{0636}', 0xFEBD, Joining::Dual),
+    ('\u{0637}', 0xFEC1, Joining::Dual),
+    ('\u{0638}', 0xFEC5, Joining::Dual),
+    ('\u{0639}', 0xFEC9, Joining::Dual),
+    ('\u{063A}', 0xFECD, Joining::Dual),
+    ('\u{0641}', 0xFED1, Joining::Dual),
+    ('\u{0642}', 0xFED5, Joining::Dual),
+    ('\u{0643}', 0xFED9, Joining::Dual),
+    ('\u{0644}', 0xFEDD, Joining::Dual),
+    ('\u{0645}', 0xFEE1, Joining::Dual),
+    ('\u{0646}', 0xFEE5, Joining::Dual),
+    ('\u{0647}', 0xFEE9, Joining::Dual),
+    ('\u{0648}', 0xFEED, Joining::Right),
+    ('\u{0649}', 0xFEEF, Joining::Right),
+    ('\u{064A}', 0xFEF1, Joining::Dual),
+    // 페르시아어
+    ('\u{067E}', 0xFB56, Joining::Dual),
+    ('\u{0686}', 0xFB7A, Joining::Dual),
+    ('\u{0698}', 0xFB8A, Joining::Right),
+    ('\u{06A9}', 0xFB8E, Joining::Dual),
+    ('\u{06AF}', 0xFB92, Joining::Dual),
+    ('\u{06CC}', 0xFBFC, Joining::Dual),
+];
+
+fn forms(c: char) -> Option<(u32, Joining)> {
+    let i = ARABIC_FORMS.binary_search_by_key(&c, |&(letter, _, _)| letter).ok()?;
+    let (_, isolated, joining) = ARABIC_FORMS[i];
+    Some((isolated, joining))
+}
+
+fn joining(c: char) -> Joining {
+    match c {
+        '\u{0640}' | '\u{200D}' => Joining::Causing,
+        c => forms(c).map_or(Joining::None, |(_, joining)| joining),
+    }
+}
+
+/// Replace Arabic letters in `chars` (indexed by col) with the form that
+/// joins them to their neighbours in logical order. Lam-alef ligatures
+/// are left as two letters: they would not fill two cells.
+fn shape_arabic(line: &[Cell], units: &[(usize, usize)], chars: &mut [char]) {
+    let kinds: Vec<Joining> = units.iter().map(|&(col, _)| joining(line[col].character)).collect();
+    for (i, &(col, _)) in units.iter().enumerate() {
+        let Some((isolated, kind)) = forms(line[col].character) else {
+            continue;
+        };
+        let prev = i.checked_sub(1).map_or(Joining::None, |p| kinds[p]);
+        let next = kinds.get(i + 1).copied().unwrap_or(Joining::None);
+        let joins_prev = matches!(prev, Joining::Dual | Joining::Causing);
+        let joins_next = kind == Joining::Dual && matches!(next, Joining::Dual | Joining::Right | Joining::Causing);
+        let offset = match (joins_prev, joins_next) {
+            (false, false) => 0,
+            (true, false) => 1,
+            (false, true) => 2,
+            (true, true) => 3,
+        };
+        if let Some(shaped) = char::from_u32(isolated + offset) {
+            chars[col] = shaped;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn row(text: &str) -> Vec<Cell> {
+        text.chars().map(|c| Cell { character: c, ..Cell::default() }).collect()
+    }
+
+    fn drawn(line: &[Cell]) -> String {
+        let Some(visual) = layout(line) else {
+            return line.iter().map(|cell| cell.character).collect();
+        };
+        let mut out = vec![' '; line.len()];
+        for col in 0..line.len() as u16 {
+            out[visual.visual_col(col) as usize] = visual.character(col).unwrap();
+        }
+        out.into_iter().collect()
+    }
+
+    #[test]
+    fn ltr_rows_have_no_layout() {
+        assert!(layout(&row("hello 한글")).is_none());
+    }
+
+    #[test]
+    fn hebrew_run_is_reversed_in_place() {
+        let line = row("ab שלום cd");
+        assert_eq!(drawn(&line), "ab םולש cd");
+        let visual = layout(&line).unwrap();
+        assert_eq!(visual.visual_col(3), 6);
+        assert_eq!(visual.logical_col(6), 3);
+        assert_eq!(visual.visual_col(0), 0);
+    }
+
+    #[test]
+    fn numbers_inside_rtl_keep_their_order() {
+        assert_eq!(drawn(&row("אב 12 גד")), "דג 12 בא");
+    }
+
+    #[test]
+    fn brackets_mirror_inside_rtl_run() {
+        assert_eq!(drawn(&row("א(ב)ג")), "ג(ב)א");
+    }
+
+    #[test]
+    fn arabic_letters_take_joined_forms() {
+        // بيت: 시작형 ب, 중간형 ي, 끝형 ت — 오른쪽에서 왼쪽으로
+        assert_eq!(drawn(&row("بيت")), "\u{FE96}\u{FEF4}\u{FE91}");
+        // د는 뒤 글자와 잇지 않음
+        assert_eq!(drawn(&row("دب")), "\u{FE8F}\u{FEA9}");
+    }
+
+    #[test]
+    fn wide_cells_move_together() {
+        let mut line = row("א");
+        line.push(Cell { character: '한', flags: CellFlags::WIDE_CHAR, ..Cell::default() });
+        line.push(Cell::default());
+        line.extend(row("ב"));
+        let visual = layout(&line).unwrap();
+        // LTR 문단: 한 글자 사이의 두 RTL 글자는 각자 자리
+        assert_eq!(visual.visual_col(1), 1);
+        assert_eq!(visual.visual_col(2), 2);
+        assert_eq!(visual.logical_col(2), 1);
+    }
+}
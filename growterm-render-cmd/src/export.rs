@@ -0,0 +1,345 @@
+//! Cells as plain text, HTML or RTF, for "Export Buffer…", "Copy Screen as
+//! Text" and rich copy.
+
+use growterm_types::{Cell, CellFlags, Rgb};
+
+use crate::{generate, TerminalPalette};
+
+fn is_wrapped(row: &[Cell]) -> bool {
+    row.last().is_some_and(|c| c.flags.contains(CellFlags::WRAPLINE))
+}
+
+fn push_char(out: &mut String, character: char, zerowidth: Option<growterm_types::ZeroWidth>) {
+    out.push(if character == '\0' { ' ' } else { character });
+    if let Some(zw) = zerowidth {
+        out.push_str(zw.as_str());
+    }
+}
+
+/// Drop blank lines at the end of the buffer, keeping one final newline.
+fn finish_lines(mut out: String) -> String {
+    let trimmed = out.trim_end_matches('\n').len();
+    out.truncate(trimmed);
+    if !out.is_empty() {
+        out.push('\n');
+    }
+    out
+}
+
+/// Plain text, one line per logical line: auto-wrapped rows are joined and
+/// trailing blanks trimmed so the result greps like the original output.
+pub fn to_text<'a>(rows: impl IntoIterator<Item = &'a [Cell]>) -> String {
+    let mut out = String::new();
+    let mut line = String::new();
+    for row in rows {
+        let mut col = 0;
+        while col < row.len() {
+            let cell = &row[col];
+            push_char(&mut line, cell.character, cell.zerowidth);
+            col += if cell.flags.contains(CellFlags::WIDE_CHAR) { 2 } else { 1 };
+        }
+        if !is_wrapped(row) {
+            out.push_str(line.trim_end());
+            out.push('\n');
+            line.clear();
+        }
+    }
+    if !line.is_empty() {
+        out.push_str(line.trim_end());
+        out.push('\n');
+    }
+    finish_lines(out)
+}
+
+/// The screen as it looks: one line per row, wrapped rows kept apart and
+/// only trailing blanks trimmed, for "Copy Screen as Text".
+pub fn to_screen_text<'a>(rows: impl IntoIterator<Item = &'a [Cell]>) -> String {
+    let mut out = String::new();
+    for row in rows {
+        let mut line = String::new();
+        let mut col = 0;
+        while col < row.len() {
+            let cell = &row[col];
+            push_char(&mut line, cell.character, cell.zerowidth);
+            col += if cell.flags.contains(CellFlags::WIDE_CHAR) { 2 } else { 1 };
+        }
+        out.push_str(line.trim_end());
+        out.push('\n');
+    }
+    finish_lines(out)
+}
+
+fn hex(rgb: Rgb) -> String {
+    format!("#{:02x}{:02x}{:02x}", rgb.r, rgb.g, rgb.b)
+}
+
+fn escape_into(out: &mut String, text: &str) {
+    for c in text.chars() {
+        match c {
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '&' => out.push_str("&amp;"),
+            _ => out.push(c),
+        }
+    }
+}
+
+/// Consecutive cells of one row that share colors and text style.
+struct Run {
+    fg: Rgb,
+    bg: Rgb,
+    flags: CellFlags,
+    text: String,
+}
+
+const RUN_FLAGS: [CellFlags; 4] =
+    [CellFlags::BOLD, CellFlags::ITALIC, CellFlags::UNDERLINE, CellFlags::STRIKETHROUGH];
+
+/// Split each row into styled runs with colors resolved through `palette`
+/// the same way the renderer does (bold-is-bright, inverse, dim, hidden).
+/// The bool is true when the row wrapped onto the next one.
+fn styled_rows<'a>(
+    rows: impl IntoIterator<Item = &'a [Cell]>,
+    palette: TerminalPalette,
+) -> Vec<(Vec<Run>, bool)> {
+    let mut out = Vec::new();
+    for row in rows {
+        let mut commands = generate(&[row.to_vec()], None, None, None, palette);
+        // 기본 배경의 줄 끝 공백은 내보내지 않음
+        while commands
+            .last()
+            .is_some_and(|c| matches!(c.character, ' ' | '\0') && c.zerowidth.is_none() && c.bg == palette.default_bg)
+        {
+            commands.pop();
+        }
+        let mut runs: Vec<Run> = Vec::new();
+        for cmd in &commands {
+            let flags = RUN_FLAGS
+                .iter()
+                .fold(CellFlags::empty(), |acc, &f| if cmd.flags.contains(f) { acc | f } else { acc });
+            match runs.last_mut() {
+                Some(run) if run.fg == cmd.fg && run.bg == cmd.bg && run.flags == flags => {
+                    push_char(&mut run.text, cmd.character, cmd.zerowidth);
+                }
+                _ => {
+                    let mut text = String::new();
+                    push_char(&mut text, cmd.character, cmd.zerowidth);
+                    runs.push(Run { fg: cmd.fg, bg: cmd.bg, flags, text });
+                }
+            }
+        }
+        out.push((runs, is_wrapped(row)));
+    }
+    while out.last().is_some_and(|(runs, wrapped)| runs.is_empty() && !wrapped) {
+        out.pop();
+    }
+    out
+}
+
+fn span_style(run: &Run, palette: &TerminalPalette) -> String {
+    let mut style = String::new();
+    if run.fg != palette.default_fg {
+        style.push_str(&format!("color:{};", hex(run.fg)));
+    }
+    if run.bg != palette.default_bg {
+        style.push_str(&format!("background:{};", hex(run.bg)));
+    }
+    if run.flags.contains(CellFlags::BOLD) {
+        style.push_str("font-weight:bold;");
+    }
+    if run.flags.contains(CellFlags::ITALIC) {
+        style.push_str("font-style:italic;");
+    }
+    match (run.flags.contains(CellFlags::UNDERLINE), run.flags.contains(CellFlags::STRIKETHROUGH)) {
+        (true, true) => style.push_str("text-decoration:underline line-through;"),
+        (true, false) => style.push_str("text-decoration:underline;"),
+        (false, true) => style.push_str("text-decoration:line-through;"),
+        (false, false) => {}
+    }
+    style
+}
+
+/// A `<pre>` block, for the clipboard or embedding in a page.
+pub fn to_html_fragment<'a>(rows: impl IntoIterator<Item = &'a [Cell]>, palette: TerminalPalette) -> String {
+    let mut out = format!(
+        "<pre style=\"margin:0;padding:8px;color:{};background:{};font-family:monospace\">",
+        hex(palette.default_fg),
+        hex(palette.default_bg),
+    );
+    let rows = styled_rows(rows, palette);
+    let last = rows.len().saturating_sub(1);
+    for (i, (runs, wrapped)) in rows.iter().enumerate() {
+        for run in runs {
+            let style = span_style(run, &palette);
+            if style.is_empty() {
+                escape_into(&mut out, &run.text);
+            } else {
+                out.push_str("<span style=\"");
+                out.push_str(&style);
+                out.push_str("\">");
+                escape_into(&mut out, &run.text);
+                out.push_str("</span>");
+            }
+        }
+        if !wrapped || i == last {
+            out.push('\n');
+        }
+    }
+    out.push_str("</pre>");
+    out
+}
+
+/// A standalone HTML page around [`to_html_fragment`].
+pub fn to_html<'a>(rows: impl IntoIterator<Item = &'a [Cell]>, palette: TerminalPalette) -> String {
+    format!(
+        "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n<title>growTerm</title>\n</head>\n\
+         <body style=\"margin:0;background:{}\">\n{}\n</body>\n</html>\n",
+        hex(palette.default_bg),
+        to_html_fragment(rows, palette),
+    )
+}
+
+/// 1-based index into the RTF color table, adding `rgb` if missing.
+fn color_index(rgb: Rgb, colors: &mut Vec<Rgb>) -> usize {
+    match colors.iter().position(|&c| c == rgb) {
+        Some(i) => i + 1,
+        None => {
+            colors.push(rgb);
+            colors.len()
+        }
+    }
+}
+
+fn rtf_escape_into(out: &mut String, text: &str) {
+    for c in text.chars() {
+        match c {
+            '\\' | '{' | '}' => {
+                out.push('\\');
+                out.push(c);
+            }
+            c if c.is_ascii() => out.push(c),
+            c => {
+                let mut buf = [0u16; 2];
+                for unit in c.encode_utf16(&mut buf) {
+                    out.push_str(&format!("\\u{}?", *unit as i16));
+                }
+            }
+        }
+    }
+}
+
+/// RTF with a color table, for pasting into Keynote, Pages or Mail.
+/// Each run is its own group so formatting never leaks into the next one.
+pub fn to_rtf<'a>(rows: impl IntoIterator<Item = &'a [Cell]>, palette: TerminalPalette) -> String {
+    let rows = styled_rows(rows, palette);
+    let mut colors = vec![palette.default_fg, palette.default_bg];
+    let mut body = String::new();
+    for (runs, wrapped) in &rows {
+        for run in runs {
+            let fg = color_index(run.fg, &mut colors);
+            let bg = color_index(run.bg, &mut colors);
+            body.push_str(&format!("{{\\cf{fg}\\cb{bg}"));
+            for (flag, word) in [
+                (CellFlags::BOLD, "\\b"),
+                (CellFlags::ITALIC, "\\i"),
+                (CellFlags::UNDERLINE, "\\ul"),
+                (CellFlags::STRIKETHROUGH, "\\strike"),
+            ] {
+                if run.flags.contains(flag) {
+                    body.push_str(word);
+                }
+            }
+            body.push(' ');
+            rtf_escape_into(&mut body, &run.text);
+            body.push('}');
+        }
+        if !wrapped {
+            body.push_str("\\line\n");
+        }
+    }
+    let mut out = String::from("{\\rtf1\\ansi\\deff0{\\fonttbl{\\f0\\fmodern Menlo;}}\n{\\colortbl;");
+    for c in &colors {
+        out.push_str(&format!("\\red{}\\green{}\\blue{};", c.r, c.g, c.b));
+    }
+    out.push_str("}\n\\f0\\fs24\n");
+    out.push_str(&body);
+    out.push('}');
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use growterm_types::Color;
+
+    fn row(text: &str, cols: usize) -> Vec<Cell> {
+        let mut cells: Vec<Cell> = text
+            .chars()
+            .map(|c| Cell { character: c, ..Cell::default() })
+            .collect();
+        cells.resize(cols, Cell::default());
+        cells
+    }
+
+    fn rows(rows: &[Vec<Cell>]) -> impl Iterator<Item = &[Cell]> {
+        rows.iter().map(Vec::as_slice)
+    }
+
+    #[test]
+    fn text_trims_blanks_and_trailing_empty_rows() {
+        let buffer = vec![row("ls", 6), row("a  b", 6), row("", 6), row("", 6)];
+        assert_eq!(to_text(rows(&buffer)), "ls\na  b\n");
+    }
+
+    #[test]
+    fn text_joins_wrapped_rows() {
+        let mut first = row("abc", 3);
+        first[2].flags.insert(CellFlags::WRAPLINE);
+        let buffer = vec![first, row("de", 3)];
+        assert_eq!(to_text(rows(&buffer)), "abcde\n");
+    }
+
+    #[test]
+    fn screen_text_keeps_wrapped_rows_apart() {
+        let mut first = row("abc", 3);
+        first[2].flags.insert(CellFlags::WRAPLINE);
+        let mut wide = row("가", 3);
+        wide[0].flags.insert(CellFlags::WIDE_CHAR);
+        wide[2].character = 'x';
+        let buffer = vec![first, row("de", 3), row("", 3), wide, row("", 3)];
+        assert_eq!(to_screen_text(rows(&buffer)), "abc\nde\n\n가x\n");
+    }
+
+    #[test]
+    fn text_skips_wide_char_spacer() {
+        let mut cells = row("가", 4);
+        cells[0].flags.insert(CellFlags::WIDE_CHAR);
+        cells[1] = Cell { character: '\0', ..Cell::default() };
+        cells[2].character = 'x';
+        assert_eq!(to_text(rows(&[cells])), "가x\n");
+    }
+
+    #[test]
+    fn html_escapes_and_colors_runs() {
+        let mut cells = row("a<b", 5);
+        cells[1].fg = Color::Indexed(1);
+        cells[2].fg = Color::Indexed(1);
+        cells[2].flags.insert(CellFlags::BOLD);
+        let html = to_html(rows(&[cells]), TerminalPalette::DEFAULT);
+        assert!(html.contains(">a<span style=\"color:#cc0000;\">&lt;</span>"));
+        assert!(html.contains("<span style=\"color:#ff0000;font-weight:bold;\">b</span>\n</pre>"));
+    }
+
+    #[test]
+    fn rtf_has_color_table_and_escapes() {
+        let mut cells = row("{가}", 4);
+        cells[0].fg = Color::Indexed(2);
+        cells[1].flags.insert(CellFlags::WIDE_CHAR);
+        cells[2] = Cell { character: '\0', ..Cell::default() };
+        cells[3].character = '}';
+        let rtf = to_rtf(rows(&[cells]), TerminalPalette::DEFAULT);
+        assert!(rtf.starts_with("{\\rtf1"));
+        assert!(rtf.contains("{\\colortbl;\\red204\\green204\\blue204;\\red0\\green0\\blue0;\\red0\\green204\\blue0;}"));
+        assert!(rtf.contains("{\\cf3\\cb2 \\{}{\\cf1\\cb2 \\u-21504?\\}}\\line"));
+    }
+}
@@ -1,10 +1,18 @@
-use growterm_types::{Cell, CellFlags, Color, RenderCommand, Rgb};
+pub mod bidi;
+pub mod export;
+
+use growterm_types::{Cell, CellFlags, Color, RenderCommand, Rgb, UnderlineStyle};
 use unicode_width::UnicodeWidthChar;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub struct TerminalPalette {
     pub default_fg: Rgb,
     pub default_bg: Rgb,
+    pub selection: SelectionColors,
+    pub cursor: CursorColors,
+    pub dim: DimStyle,
+    /// BOLD + standard color (0-7) → bright color (8-15)
+    pub bold_is_bright: bool,
 }
 
 impl TerminalPalette {
@@ -15,9 +23,88 @@ impl TerminalPalette {
             b: 204,
         },
         default_bg: Rgb { r: 0, g: 0, b: 0 },
+        selection: SelectionColors::SWAP,
+        cursor: CursorColors::SWAP,
+        dim: DimStyle::DEFAULT,
+        bold_is_bright: true,
     };
 }
 
+/// Selection highlight colors. Without a background the selection swaps
+/// fg/bg; with one, the background is blended over the cell's own
+/// background using `alpha` (255 = replace).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SelectionColors {
+    pub fg: Option<Rgb>,
+    pub bg: Option<Rgb>,
+    pub alpha: u8,
+}
+
+impl SelectionColors {
+    pub const SWAP: Self = Self { fg: None, bg: None, alpha: 255 };
+}
+
+impl Default for SelectionColors {
+    fn default() -> Self {
+        Self::SWAP
+    }
+}
+
+/// Block cursor colors. Without a background the cursor cell swaps
+/// fg/bg; with one, text under the cursor takes `fg`, or the cell's own
+/// background when `fg` is unset.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CursorColors {
+    pub fg: Option<Rgb>,
+    pub bg: Option<Rgb>,
+}
+
+impl CursorColors {
+    pub const SWAP: Self = Self { fg: None, bg: None };
+}
+
+impl Default for CursorColors {
+    fn default() -> Self {
+        Self::SWAP
+    }
+}
+
+/// How DIM (SGR 2) text is drawn. Percentages are the share of the
+/// original fg brightness that is kept.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DimStyle {
+    /// Scale fg toward black
+    Scale(u8),
+    /// Blend fg toward the cell background, so faint text stays readable on
+    /// light and colored backgrounds
+    BlendToBackground(u8),
+}
+
+impl DimStyle {
+    pub const DEFAULT: Self = Self::Scale(50);
+
+    fn apply(self, fg: Rgb, bg: Rgb) -> Rgb {
+        let (keep, target) = match self {
+            DimStyle::Scale(keep) => (keep, Rgb::new(0, 0, 0)),
+            DimStyle::BlendToBackground(keep) => (keep, bg),
+        };
+        let keep = keep.min(100) as u16;
+        let mix = |f: u8, t: u8| ((f as u16 * keep + t as u16 * (100 - keep)) / 100) as u8;
+        Rgb::new(mix(fg.r, target.r), mix(fg.g, target.g), mix(fg.b, target.b))
+    }
+}
+
+impl Default for DimStyle {
+    fn default() -> Self {
+        Self::DEFAULT
+    }
+}
+
+fn blend(under: Rgb, over: Rgb, alpha: u8) -> Rgb {
+    let mix = |u: u8, o: u8| ((u as u16 * (255 - alpha as u16) + o as u16 * alpha as u16) / 255) as u8;
+    Rgb::new(mix(under.r, over.r), mix(under.g, over.g), mix(under.b, over.b))
+}
+
 impl Default for TerminalPalette {
     fn default() -> Self {
         Self::DEFAULT
@@ -104,126 +191,362 @@ fn resolve_color(color: Color, default: Rgb) -> Rgb {
     }
 }
 
-pub fn generate(
-    cells: &[Vec<Cell>],
-    cursor_pos: Option<(u16, u16)>,
-    preedit: Option<&str>,
-    selection: Option<((u16, u16), (u16, u16))>,
-    palette: TerminalPalette,
-) -> Vec<RenderCommand> {
-    generate_with_offset(cells, cursor_pos, preedit, selection, 0, palette, None, cursor_pos)
+/// How a highlight span is drawn.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HighlightStyle {
+    /// The palette's selection colors
+    Selection,
+    /// Own colors, e.g. search matches
+    Colors(SelectionColors),
+    /// Underlined, colors untouched (Cmd+hovered link)
+    Underline,
 }
 
-pub fn generate_with_offset(
-    cells: &[Vec<Cell>],
-    cursor_pos: Option<(u16, u16)>,
-    preedit: Option<&str>,
-    selection: Option<((u16, u16), (u16, u16))>,
-    row_offset: u16,
+/// A run of cells drawn highlighted: the selection, a search match, a
+/// hovered link. `start`/`end` are (row, col), both inclusive; rows in
+/// between are covered whole.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Highlight {
+    pub start: (u16, u16),
+    pub end: (u16, u16),
+    pub style: HighlightStyle,
+}
+
+impl Highlight {
+    pub fn selection(start: (u16, u16), end: (u16, u16)) -> Self {
+        Self { start, end, style: HighlightStyle::Selection }
+    }
+
+    /// Columns `start_col..end_col` of one row underlined
+    pub fn underline(row: u16, start_col: u16, end_col: u16) -> Option<Self> {
+        if end_col <= start_col {
+            return None;
+        }
+        Some(Self {
+            start: (row, start_col),
+            end: (row, end_col - 1),
+            style: HighlightStyle::Underline,
+        })
+    }
+
+    pub fn contains(&self, row: u16, col: u16) -> bool {
+        let (start, end) = (self.start, self.end);
+        if start.0 == end.0 {
+            row == start.0 && col >= start.1 && col <= end.1
+        } else if row == start.0 {
+            col >= start.1
+        } else if row == end.0 {
+            col <= end.1
+        } else {
+            row > start.0 && row < end.0
+        }
+    }
+
+    fn colors(&self, palette: &TerminalPalette) -> Option<SelectionColors> {
+        match self.style {
+            HighlightStyle::Selection => Some(palette.selection),
+            HighlightStyle::Colors(colors) => Some(colors),
+            HighlightStyle::Underline => None,
+        }
+    }
+}
+
+/// One frame's worth of input to command generation. Start from the
+/// rows and palette and add only what the frame has:
+///
+/// ```ignore
+/// RenderPlan::new(&rows, palette).cursor(Some(cursor)).highlights(&spans).render()
+/// ```
+///
+/// `cells` can be any row type that derefs to cells: `Vec<Cell>` in
+/// tests, the grid's `Row`s (collected from a `RowIter`) in the app.
+#[derive(Debug, Clone)]
+pub struct RenderPlan<'a, R> {
+    cells: &'a [R],
     palette: TerminalPalette,
-    preedit_pos_override: Option<(u16, u16)>,
-    preedit_cursor: Option<(u16, u16)>,
-) -> Vec<RenderCommand> {
-    let mut commands = Vec::new();
-    for (row, line) in cells.iter().enumerate() {
-        let mut skip_next = false;
-        for (col, cell) in line.iter().enumerate() {
-            if skip_next {
-                skip_next = false;
-                continue;
-            }
+    cursor: Option<(u16, u16)>,
+    preedit: Option<&'a str>,
+    preedit_anchor: Option<(u16, u16)>,
+    highlights: &'a [Highlight],
+    row_offset: u16,
+    bidi: bool,
+}
 
-            // BOLD + standard color (0-7) → bright color (8-15)
-            let fg_color = if cell.flags.contains(CellFlags::BOLD) {
-                match cell.fg {
-                    Color::Indexed(idx) if idx < 8 => Color::Indexed(idx + 8),
-                    other => other,
+impl<'a, R: AsRef<[Cell]>> RenderPlan<'a, R> {
+    pub fn new(cells: &'a [R], palette: TerminalPalette) -> Self {
+        Self {
+            cells,
+            palette,
+            cursor: None,
+            preedit: None,
+            preedit_anchor: None,
+            highlights: &[],
+            row_offset: 0,
+            bidi: false,
+        }
+    }
+
+    /// Block cursor cell (row, col)
+    pub fn cursor(mut self, cursor: Option<(u16, u16)>) -> Self {
+        self.cursor = cursor;
+        self
+    }
+
+    /// IME composition text, drawn at the cursor unless `preedit_anchor`
+    /// says otherwise.
+    pub fn preedit(mut self, text: Option<&'a str>) -> Self {
+        self.preedit = text;
+        self
+    }
+
+    pub fn preedit_anchor(mut self, anchor: Option<(u16, u16)>) -> Self {
+        self.preedit_anchor = anchor;
+        self
+    }
+
+    /// Highlight spans; where colored spans overlap the later one wins
+    pub fn highlights(mut self, highlights: &'a [Highlight]) -> Self {
+        self.highlights = highlights;
+        self
+    }
+
+    /// Added to every command's row, e.g. below a partly shown row
+    pub fn row_offset(mut self, row_offset: u16) -> Self {
+        self.row_offset = row_offset;
+        self
+    }
+
+    /// Draw rows with RTL text in visual order (see [`bidi`]). Cursor and
+    /// highlights stay in cell coordinates and move with their cells.
+    pub fn bidi(mut self, bidi: bool) -> Self {
+        self.bidi = bidi;
+        self
+    }
+
+    pub fn render(&self) -> Vec<RenderCommand> {
+        let (cells, palette, cursor_pos, row_offset) = (self.cells, self.palette, self.cursor, self.row_offset);
+        let mut commands = Vec::new();
+        for (row, line) in cells.iter().enumerate() {
+            let line = line.as_ref();
+            let visual = if self.bidi { bidi::layout(line) } else { None };
+            let mut skip_next = false;
+            for (col, cell) in line.iter().enumerate() {
+                if skip_next {
+                    skip_next = false;
+                    continue;
                 }
-            } else {
-                cell.fg
-            };
-            let mut fg = resolve_color(fg_color, palette.default_fg);
-            let mut bg = resolve_color(cell.bg, palette.default_bg);
-
-            // Cursor: swap fg/bg at cursor position
-            let is_cursor = cursor_pos == Some((row as u16, col as u16));
-            if is_cursor {
-                std::mem::swap(&mut fg, &mut bg);
-            }
 
-            // Selection highlight: swap fg/bg
-            if let Some((start, end)) = selection {
-                let r = row as u16;
-                let c = col as u16;
-                let in_sel = if start.0 == end.0 {
-                    r == start.0 && c >= start.1 && c <= end.1
-                } else if r == start.0 {
-                    c >= start.1
-                } else if r == end.0 {
-                    c <= end.1
+                // BOLD + standard color (0-7) → bright color (8-15)
+                let fg_color = if palette.bold_is_bright && cell.flags.contains(CellFlags::BOLD) {
+                    match cell.fg {
+                        Color::Indexed(idx) if idx < 8 => Color::Indexed(idx + 8),
+                        other => other,
+                    }
                 } else {
-                    r > start.0 && r < end.0
+                    cell.fg
                 };
-                if in_sel {
+                let mut fg = resolve_color(fg_color, palette.default_fg);
+                let mut bg = resolve_color(cell.bg, palette.default_bg);
+
+                // Cursor: swap fg/bg at cursor position
+                let is_cursor = cursor_pos == Some((row as u16, col as u16));
+                if is_cursor && palette.cursor.bg.is_none() {
                     std::mem::swap(&mut fg, &mut bg);
                 }
-            }
 
-            // INVERSE: swap fg/bg
-            if cell.flags.contains(CellFlags::INVERSE) {
-                std::mem::swap(&mut fg, &mut bg);
-            }
+                let (r, c) = (row as u16, col as u16);
+                let mut covering = self.highlights.iter().filter(|h| h.contains(r, c));
+                let highlight = covering.clone().rev().find_map(|h| h.colors(&palette));
+                let underline = covering.any(|h| h.style == HighlightStyle::Underline);
 
-            // DIM: halve fg brightness
-            if cell.flags.contains(CellFlags::DIM) {
-                fg = Rgb::new(fg.r / 2, fg.g / 2, fg.b / 2);
-            }
+                // Highlight without explicit colors: swap fg/bg
+                if highlight.is_some_and(|colors| colors.bg.is_none()) {
+                    std::mem::swap(&mut fg, &mut bg);
+                }
+
+                // INVERSE: swap fg/bg
+                if cell.flags.contains(CellFlags::INVERSE) {
+                    std::mem::swap(&mut fg, &mut bg);
+                }
+
+                // Highlight with explicit colors: applied after INVERSE so the
+                // highlight looks the same on every cell
+                if let Some(colors) = highlight {
+                    if let Some(sel_bg) = colors.bg {
+                        bg = blend(bg, sel_bg, colors.alpha);
+                    }
+                    if let Some(sel_fg) = colors.fg {
+                        fg = sel_fg;
+                    }
+                }
+
+                // Cursor with explicit colors: after INVERSE and selection so the
+                // cursor looks the same everywhere
+                if let (true, Some(cursor_bg)) = (is_cursor, palette.cursor.bg) {
+                    fg = palette.cursor.fg.unwrap_or(bg);
+                    bg = cursor_bg;
+                }
+
+                // DIM: reduce fg brightness (default: halve)
+                if cell.flags.contains(CellFlags::DIM) {
+                    fg = palette.dim.apply(fg, bg);
+                }
+
+                // HIDDEN: fg = bg
+                if cell.flags.contains(CellFlags::HIDDEN) {
+                    fg = bg;
+                }
+
+                // SGR 58 color stays put under inverse/selection; default follows fg
+                let underline_color = match cell.underline_color {
+                    Color::Default => fg,
+                    _ if cell.flags.contains(CellFlags::HIDDEN) => bg,
+                    color => resolve_color(color, palette.default_fg),
+                };
 
-            // HIDDEN: fg = bg
-            if cell.flags.contains(CellFlags::HIDDEN) {
-                fg = bg;
+                let mut flags = cell.flags;
+                if underline {
+                    flags |= CellFlags::UNDERLINE;
+                }
+
+                commands.push(RenderCommand {
+                    col: visual.as_ref().map_or(col as u16, |v| v.visual_col(col as u16)),
+                    row: row as u16 + row_offset,
+                    character: visual.as_ref().and_then(|v| v.character(col as u16)).unwrap_or(cell.character),
+                    fg,
+                    bg,
+                    flags,
+                    zerowidth: cell.zerowidth,
+                    underline_style: cell.underline_style,
+                    underline_color,
+                });
+
+                if cell.flags.contains(CellFlags::WIDE_CHAR) {
+                    skip_next = true;
+                }
             }
+        }
 
-            commands.push(RenderCommand {
-                col: col as u16,
-                row: row as u16 + row_offset,
-                character: cell.character,
-                fg,
-                bg,
-                flags: cell.flags,
-            });
-
-            if cell.flags.contains(CellFlags::WIDE_CHAR) {
-                skip_next = true;
+        // Preedit overlay: 커서 위치에 조합 중인 텍스트를 밑줄 + 색반전으로 표시
+        if let (Some(text), Some(anchor)) = (self.preedit, self.preedit_anchor.or(cursor_pos)) {
+            let cols = cells.first().map_or(0, |line| line.as_ref().len()) as u16;
+            for (preedit_row, col, ch) in preedit_layout(text, anchor, cols, cells.len() as u16) {
+                let width = ch.width().unwrap_or(1) as u16;
+                let flags = CellFlags::UNDERLINE
+                    | if width > 1 {
+                        CellFlags::WIDE_CHAR
+                    } else {
+                        CellFlags::empty()
+                    };
+                commands.push(RenderCommand {
+                    col,
+                    row: preedit_row + row_offset,
+                    character: ch,
+                    fg: palette.default_bg,
+                    bg: palette.default_fg,
+                    flags,
+                    zerowidth: None,
+                    underline_style: UnderlineStyle::Single,
+                    underline_color: palette.default_bg,
+                });
             }
         }
+
+        commands
     }
+}
 
-    // Preedit overlay: 커서 위치에 조합 중인 텍스트를 밑줄 + 색반전으로 표시
-    if let (Some(text), Some((cursor_row, cursor_col))) = (preedit, preedit_cursor) {
-        let (preedit_row, preedit_col) = preedit_pos_override.unwrap_or((cursor_row, cursor_col));
-        let mut col = preedit_col;
-        for ch in text.chars() {
-            let width = ch.width().unwrap_or(1) as u16;
-            let flags = CellFlags::UNDERLINE
-                | if width > 1 {
-                    CellFlags::WIDE_CHAR
-                } else {
-                    CellFlags::empty()
-                };
-            commands.push(RenderCommand {
-                col,
-                row: preedit_row + row_offset,
-                character: ch,
-                fg: palette.default_bg,
-                bg: palette.default_fg,
-                flags,
-            });
-            col += width;
+/// `RenderPlan` with `selection` as the only highlight
+pub fn generate<R: AsRef<[Cell]>>(
+    cells: &[R],
+    cursor_pos: Option<(u16, u16)>,
+    preedit: Option<&str>,
+    selection: Option<((u16, u16), (u16, u16))>,
+    palette: TerminalPalette,
+) -> Vec<RenderCommand> {
+    let highlights: Vec<Highlight> = selection.map(|(start, end)| Highlight::selection(start, end)).into_iter().collect();
+    RenderPlan::new(cells, palette)
+        .cursor(cursor_pos)
+        .preedit(preedit)
+        .highlights(&highlights)
+        .render()
+}
+
+/// Positional form of `RenderPlan`, kept for existing callers. The
+/// preedit is drawn only with a `preedit_cursor`, at
+/// `preedit_pos_override` if given.
+#[allow(clippy::too_many_arguments)]
+pub fn generate_with_offset<R: AsRef<[Cell]>>(
+    cells: &[R],
+    cursor_pos: Option<(u16, u16)>,
+    preedit: Option<&str>,
+    selection: Option<((u16, u16), (u16, u16))>,
+    row_offset: u16,
+    palette: TerminalPalette,
+    preedit_pos_override: Option<(u16, u16)>,
+    preedit_cursor: Option<(u16, u16)>,
+) -> Vec<RenderCommand> {
+    let highlights: Vec<Highlight> = selection.map(|(start, end)| Highlight::selection(start, end)).into_iter().collect();
+    RenderPlan::new(cells, palette)
+        .cursor(cursor_pos)
+        .preedit(preedit.filter(|_| preedit_cursor.is_some()))
+        .preedit_anchor(preedit_cursor.map(|cursor| preedit_pos_override.unwrap_or(cursor)))
+        .highlights(&highlights)
+        .row_offset(row_offset)
+        .render()
+}
+
+/// Screen position of each preedit character starting at `anchor`.
+/// Text that reaches the right edge wraps to the next row (a wide char
+/// never straddles it), and if that runs past the bottom row the whole
+/// preedit moves up so it stays on screen.
+pub fn preedit_layout(text: &str, anchor: (u16, u16), cols: u16, rows: u16) -> Vec<(u16, u16, char)> {
+    if cols == 0 || rows == 0 {
+        return Vec::new();
+    }
+    let (mut row, mut col) = (anchor.0.min(rows - 1), anchor.1.min(cols - 1));
+    let mut cells = Vec::new();
+    for ch in text.chars() {
+        let width = (ch.width().unwrap_or(1) as u16).min(cols);
+        if col + width > cols {
+            row += 1;
+            col = 0;
         }
+        cells.push((row, col, ch));
+        col += width;
     }
+    let overflow = (row + 1).saturating_sub(rows);
+    cells
+        .into_iter()
+        .filter(|&(r, _, _)| r >= overflow)
+        .map(|(r, c, ch)| (r - overflow, c, ch))
+        .collect()
+}
 
-    commands
+/// Debug view: replace spaces the program printed after the last visible
+/// character of each row with a faint middle dot.
+pub fn mark_trailing_whitespace(commands: &mut [RenderCommand]) {
+    let mut last_text_col: std::collections::HashMap<u16, u16> = std::collections::HashMap::new();
+    for cmd in commands.iter() {
+        if cmd.character != ' ' {
+            let last = last_text_col.entry(cmd.row).or_insert(cmd.col);
+            *last = (*last).max(cmd.col);
+        }
+    }
+    for cmd in commands.iter_mut() {
+        if cmd.character != ' ' || !cmd.flags.contains(CellFlags::EXPLICIT_SPACE) {
+            continue;
+        }
+        let trailing = last_text_col.get(&cmd.row).map_or(true, |&last| cmd.col > last);
+        if trailing {
+            cmd.character = '·';
+            cmd.fg = Rgb::new(
+                ((cmd.fg.r as u16 + cmd.bg.r as u16) / 2) as u8,
+                ((cmd.fg.g as u16 + cmd.bg.g as u16) / 2) as u8,
+                ((cmd.fg.b as u16 + cmd.bg.b as u16) / 2) as u8,
+            );
+        }
+    }
 }
 
 #[cfg(test)]
@@ -267,6 +590,14 @@ mod tests {
         assert_eq!(cmds[0].bg, DEFAULT_BG);
     }
 
+    #[test]
+    fn zerowidth_passthrough() {
+        let zerowidth = growterm_types::ZeroWidth::intern("\u{0301}");
+        let cell = Cell { character: 'e', zerowidth, ..Cell::default() };
+        let cmds = generate(&vec![vec![cell]], None, None, None);
+        assert_eq!(cmds[0].zerowidth, zerowidth);
+    }
+
     #[test]
     fn rgb_color_passthrough() {
         let cell = Cell {
@@ -274,6 +605,9 @@ mod tests {
             fg: Color::Rgb(Rgb::new(100, 150, 200)),
             bg: Color::Rgb(Rgb::new(10, 20, 30)),
             flags: CellFlags::empty(),
+            zerowidth: None,
+            underline_style: UnderlineStyle::Single,
+            underline_color: Color::Default,
         };
         let cmds = generate(&vec![vec![cell]], None, None, None);
         assert_eq!(cmds[0].fg, Rgb::new(100, 150, 200));
@@ -287,6 +621,9 @@ mod tests {
             fg: Color::Indexed(1), // red
             bg: Color::Indexed(4), // blue
             flags: CellFlags::empty(),
+            zerowidth: None,
+            underline_style: UnderlineStyle::Single,
+            underline_color: Color::Default,
         };
         let cmds = generate(&vec![vec![cell]], None, None, None);
         assert_eq!(cmds[0].fg, Rgb::new(204, 0, 0));
@@ -301,6 +638,9 @@ mod tests {
             fg: Color::Indexed(196),
             bg: Color::Default,
             flags: CellFlags::empty(),
+            zerowidth: None,
+            underline_style: UnderlineStyle::Single,
+            underline_color: Color::Default,
         };
         let cmds = generate(&vec![vec![cell]], None, None, None);
         assert_eq!(cmds[0].fg, Rgb::new(255, 0, 0));
@@ -314,6 +654,9 @@ mod tests {
             fg: Color::Indexed(232),
             bg: Color::Indexed(255),
             flags: CellFlags::empty(),
+            zerowidth: None,
+            underline_style: UnderlineStyle::Single,
+            underline_color: Color::Default,
         };
         let cmds = generate(&vec![vec![cell]], None, None, None);
         assert_eq!(cmds[0].fg, Rgb::new(8, 8, 8));
@@ -327,6 +670,9 @@ mod tests {
             fg: Color::Rgb(Rgb::new(255, 255, 255)),
             bg: Color::Rgb(Rgb::new(0, 0, 0)),
             flags: CellFlags::INVERSE,
+            zerowidth: None,
+            underline_style: UnderlineStyle::Single,
+            underline_color: Color::Default,
         };
         let cmds = generate(&vec![vec![cell]], None, None, None);
         assert_eq!(cmds[0].fg, Rgb::new(0, 0, 0));
@@ -340,6 +686,9 @@ mod tests {
             fg: Color::Rgb(Rgb::new(200, 100, 50)),
             bg: Color::Default,
             flags: CellFlags::DIM,
+            zerowidth: None,
+            underline_style: UnderlineStyle::Single,
+            underline_color: Color::Default,
         };
         let cmds = generate(&vec![vec![cell]], None, None, None);
         assert_eq!(cmds[0].fg, Rgb::new(100, 50, 25));
@@ -352,6 +701,9 @@ mod tests {
             fg: Color::Rgb(Rgb::new(255, 255, 255)),
             bg: Color::Rgb(Rgb::new(0, 0, 0)),
             flags: CellFlags::HIDDEN,
+            zerowidth: None,
+            underline_style: UnderlineStyle::Single,
+            underline_color: Color::Default,
         };
         let cmds = generate(&vec![vec![cell]], None, None, None);
         assert_eq!(cmds[0].fg, cmds[0].bg);
@@ -366,6 +718,9 @@ mod tests {
                 fg: Color::Default,
                 bg: Color::Default,
                 flags: CellFlags::WIDE_CHAR,
+                zerowidth: None,
+                underline_style: UnderlineStyle::Single,
+                underline_color: Color::Default,
             },
             Cell::default(), // spacer
             Cell {
@@ -373,6 +728,9 @@ mod tests {
                 fg: Color::Default,
                 bg: Color::Default,
                 flags: CellFlags::WIDE_CHAR,
+                zerowidth: None,
+                underline_style: UnderlineStyle::Single,
+                underline_color: Color::Default,
             },
             Cell::default(), // spacer
         ]];
@@ -414,6 +772,9 @@ mod tests {
             fg: Color::Default,
             bg: Color::Default,
             flags: CellFlags::empty(),
+            zerowidth: None,
+            underline_style: UnderlineStyle::Single,
+            underline_color: Color::Default,
         };
         let cells = vec![vec![cell]];
         let cmds = generate(&cells, Some((0, 0)), None, None);
@@ -450,12 +811,40 @@ mod tests {
             fg: Color::Rgb(Rgb::new(100, 150, 200)),
             bg: Color::Rgb(Rgb::new(10, 20, 30)),
             flags: CellFlags::empty(),
+            zerowidth: None,
+            underline_style: UnderlineStyle::Single,
+            underline_color: Color::Default,
         };
         let cmds = generate(&vec![vec![cell]], Some((0, 0)), None, None);
         assert_eq!(cmds[0].fg, Rgb::new(10, 20, 30));
         assert_eq!(cmds[0].bg, Rgb::new(100, 150, 200));
     }
 
+    #[test]
+    fn cursor_colors_replace_swap() {
+        let cells = vec![vec![Cell { character: 'A', ..Cell::default() }]];
+        let palette = TerminalPalette {
+            cursor: CursorColors { fg: Some(Rgb::new(1, 2, 3)), bg: Some(Rgb::new(250, 200, 0)) },
+            ..TerminalPalette::DEFAULT
+        };
+        let cmds = super::generate(&cells, Some((0, 0)), None, None, palette);
+        assert_eq!(cmds[0].fg, Rgb::new(1, 2, 3));
+        assert_eq!(cmds[0].bg, Rgb::new(250, 200, 0));
+    }
+
+    #[test]
+    fn cursor_bg_only_draws_text_in_cell_background() {
+        let cell = Cell { character: 'I', flags: CellFlags::INVERSE, ..Cell::default() };
+        let palette = TerminalPalette {
+            cursor: CursorColors { fg: None, bg: Some(Rgb::new(250, 200, 0)) },
+            ..TerminalPalette::DEFAULT
+        };
+        let cmds = super::generate(&vec![vec![cell]], Some((0, 0)), None, None, palette);
+        // INVERSE 셀 배경은 기본 fg
+        assert_eq!(cmds[0].fg, DEFAULT_FG);
+        assert_eq!(cmds[0].bg, Rgb::new(250, 200, 0));
+    }
+
     #[test]
     fn cursor_plus_inverse_cancels_out() {
         // cursor swaps, then INVERSE swaps again → back to original
@@ -464,12 +853,42 @@ mod tests {
             fg: Color::Rgb(Rgb::new(255, 255, 255)),
             bg: Color::Rgb(Rgb::new(0, 0, 0)),
             flags: CellFlags::INVERSE,
+            zerowidth: None,
+            underline_style: UnderlineStyle::Single,
+            underline_color: Color::Default,
         };
         let cmds = generate(&vec![vec![cell]], Some((0, 0)), None, None);
         assert_eq!(cmds[0].fg, Rgb::new(255, 255, 255));
         assert_eq!(cmds[0].bg, Rgb::new(0, 0, 0));
     }
 
+    #[test]
+    fn underline_color_defaults_to_final_fg() {
+        let cell = Cell {
+            character: 'u',
+            fg: Color::Rgb(Rgb::new(200, 0, 0)),
+            flags: CellFlags::UNDERLINE | CellFlags::INVERSE,
+            underline_style: UnderlineStyle::Curly,
+            ..Cell::default()
+        };
+        let cmds = generate(&vec![vec![cell]], None, None, None);
+        assert_eq!(cmds[0].underline_style, UnderlineStyle::Curly);
+        assert_eq!(cmds[0].underline_color, cmds[0].fg);
+        assert_eq!(cmds[0].underline_color, DEFAULT_BG);
+    }
+
+    #[test]
+    fn explicit_underline_color_ignores_inverse() {
+        let cell = Cell {
+            character: 'u',
+            flags: CellFlags::UNDERLINE | CellFlags::INVERSE,
+            underline_color: Color::Indexed(1),
+            ..Cell::default()
+        };
+        let cmds = generate(&vec![vec![cell]], None, None, None);
+        assert_eq!(cmds[0].underline_color, Rgb::new(204, 0, 0));
+    }
+
     #[test]
     fn cursor_on_wide_char() {
         let cells = vec![vec![
@@ -478,6 +897,9 @@ mod tests {
                 fg: Color::Default,
                 bg: Color::Default,
                 flags: CellFlags::WIDE_CHAR,
+                zerowidth: None,
+                underline_style: UnderlineStyle::Single,
+                underline_color: Color::Default,
             },
             Cell::default(), // spacer
         ]];
@@ -511,6 +933,9 @@ mod tests {
             fg: Color::Rgb(Rgb::new(200, 100, 50)),
             bg: Color::Rgb(Rgb::new(40, 60, 80)),
             flags: CellFlags::DIM,
+            zerowidth: None,
+            underline_style: UnderlineStyle::Single,
+            underline_color: Color::Default,
         };
         let cmds = generate(&vec![vec![cell]], Some((0, 0)), None, None);
         // cursor swaps: fg=40,60,80 bg=200,100,50
@@ -547,6 +972,9 @@ mod tests {
             fg: Color::Default,
             bg: Color::Default,
             flags: CellFlags::BOLD | CellFlags::UNDERLINE,
+            zerowidth: None,
+            underline_style: UnderlineStyle::Single,
+            underline_color: Color::Default,
         };
         let cmds = generate(&vec![vec![cell]], None, None, None);
         assert!(cmds[0].flags.contains(CellFlags::BOLD));
@@ -565,14 +993,7 @@ mod tests {
 
     #[test]
     fn preedit_korean_at_cursor() {
-        let cells = vec![vec![
-            Cell::default(),
-            Cell::default(),
-            Cell::default(),
-            Cell::default(),
-            Cell::default(),
-            Cell::default(),
-        ]];
+        let cells = vec![vec![Cell::default(); 7]];
         let cmds = generate(&cells, Some((0, 5)), Some("한"), None);
         // Last command should be the preedit overlay
         let preedit_cmd = cmds.last().unwrap();
@@ -623,6 +1044,74 @@ mod tests {
         assert_eq!(cmds_no_cursor.len(), cmds_no_preedit.len());
     }
 
+    #[test]
+    fn plan_underlines_hover_range_only() {
+        let cells = vec![vec![Cell::default(); 4], vec![Cell::default(); 4]];
+        let hover: Vec<_> = Highlight::underline(1, 1, 3).into_iter().collect();
+        let cmds = RenderPlan::new(&cells, TerminalPalette::default()).highlights(&hover).render();
+        let underlined: Vec<_> = cmds
+            .iter()
+            .filter(|c| c.flags.contains(CellFlags::UNDERLINE))
+            .map(|c| (c.row, c.col))
+            .collect();
+        assert_eq!(underlined, vec![(1, 1), (1, 2)]);
+    }
+
+    #[test]
+    fn later_highlight_wins_where_spans_overlap() {
+        let cells = vec![vec![Cell::default(); 4]];
+        let search = SelectionColors { fg: None, bg: Some(Rgb::new(200, 200, 0)), alpha: 255 };
+        let highlights = [
+            Highlight::selection((0, 0), (0, 2)),
+            Highlight { start: (0, 2), end: (0, 3), style: HighlightStyle::Colors(search) },
+        ];
+        let cmds = RenderPlan::new(&cells, TerminalPalette::default()).highlights(&highlights).render();
+        // 선택은 fg/bg 교환, 검색 색이 겹친 칸을 덮음
+        assert_eq!(cmds[0].bg, DEFAULT_FG);
+        assert_eq!(cmds[2].bg, Rgb::new(200, 200, 0));
+        assert_eq!(cmds[3].bg, Rgb::new(200, 200, 0));
+    }
+
+    #[test]
+    fn underline_highlight_keeps_selection_colors() {
+        let cells = vec![vec![Cell::default(); 3]];
+        let mut highlights = vec![Highlight::selection((0, 0), (0, 2))];
+        highlights.extend(Highlight::underline(0, 1, 2));
+        let cmds = RenderPlan::new(&cells, TerminalPalette::default()).highlights(&highlights).render();
+        assert_eq!(cmds[1].bg, DEFAULT_FG);
+        assert!(cmds[1].flags.contains(CellFlags::UNDERLINE));
+        assert!(!cmds[0].flags.contains(CellFlags::UNDERLINE));
+        assert_eq!(Highlight::underline(0, 2, 2), None);
+    }
+
+    #[test]
+    fn bidi_draws_rtl_cells_in_visual_order_with_cursor() {
+        let cells = vec!["ab אב".chars().map(|c| Cell { character: c, ..Cell::default() }).collect::<Vec<_>>()];
+        let plan = RenderPlan::new(&cells, TerminalPalette::default()).cursor(Some((0, 3)));
+        let at_col = |cmds: &[RenderCommand], col| cmds.iter().find(|c| c.col == col).unwrap().character;
+        let plain = plan.clone().render();
+        assert_eq!(at_col(&plain, 3), 'א');
+
+        let cmds = plan.bidi(true).render();
+        assert_eq!((at_col(&cmds, 3), at_col(&cmds, 4)), ('ב', 'א'));
+        // 커서는 논리 셀(א)을 따라감
+        let cursor = cmds.iter().find(|c| c.bg == DEFAULT_FG).unwrap();
+        assert_eq!((cursor.col, cursor.character), (4, 'א'));
+    }
+
+    #[test]
+    fn plan_preedit_anchor_overrides_cursor() {
+        let cells = vec![vec![Cell::default(); 6]];
+        let cmds = RenderPlan::new(&cells, TerminalPalette::default())
+            .cursor(Some((0, 0)))
+            .preedit(Some("a"))
+            .preedit_anchor(Some((0, 4)))
+            .row_offset(2)
+            .render();
+        let preedit = cmds.iter().find(|c| c.character == 'a').unwrap();
+        assert_eq!((preedit.row, preedit.col), (2, 4));
+    }
+
     #[test]
     fn preedit_and_cursor_share_same_row_with_offset() {
         let cells = vec![vec![
@@ -661,6 +1150,25 @@ mod tests {
         assert_eq!(preedit_cmd.col, cursor.1);
     }
 
+    #[test]
+    fn preedit_wide_char_wraps_instead_of_straddling_edge() {
+        let layout = preedit_layout("a한", (0, 3), 5, 2);
+        assert_eq!(layout, vec![(0, 3, 'a'), (1, 0, '한')]);
+    }
+
+    #[test]
+    fn preedit_on_bottom_row_moves_up_to_stay_visible() {
+        let layout = preedit_layout("abcd", (1, 3), 5, 2);
+        assert_eq!(layout, vec![(0, 3, 'a'), (0, 4, 'b'), (1, 0, 'c'), (1, 1, 'd')]);
+    }
+
+    #[test]
+    fn preedit_anchor_past_grid_is_clamped() {
+        let layout = preedit_layout("x", (9, 9), 5, 2);
+        assert_eq!(layout, vec![(1, 4, 'x')]);
+        assert!(preedit_layout("x", (0, 0), 0, 0).is_empty());
+    }
+
     // --- BOLD color promotion tests ---
 
     #[test]
@@ -670,6 +1178,9 @@ mod tests {
             fg: Color::Indexed(1), // red (204,0,0)
             bg: Color::Default,
             flags: CellFlags::BOLD,
+            zerowidth: None,
+            underline_style: UnderlineStyle::Single,
+            underline_color: Color::Default,
         };
         let cmds = generate(&vec![vec![cell]], None, None, None);
         // BOLD + Indexed(1) → Indexed(9) = bright red (255,0,0)
@@ -683,6 +1194,9 @@ mod tests {
             fg: Color::Indexed(9), // bright red (255,0,0)
             bg: Color::Default,
             flags: CellFlags::BOLD,
+            zerowidth: None,
+            underline_style: UnderlineStyle::Single,
+            underline_color: Color::Default,
         };
         let cmds = generate(&vec![vec![cell]], None, None, None);
         assert_eq!(cmds[0].fg, Rgb::new(255, 0, 0));
@@ -695,6 +1209,9 @@ mod tests {
             fg: Color::Rgb(Rgb::new(100, 150, 200)),
             bg: Color::Default,
             flags: CellFlags::BOLD,
+            zerowidth: None,
+            underline_style: UnderlineStyle::Single,
+            underline_color: Color::Default,
         };
         let cmds = generate(&vec![vec![cell]], None, None, None);
         assert_eq!(cmds[0].fg, Rgb::new(100, 150, 200));
@@ -707,11 +1224,74 @@ mod tests {
             fg: Color::Default,
             bg: Color::Default,
             flags: CellFlags::BOLD,
+            zerowidth: None,
+            underline_style: UnderlineStyle::Single,
+            underline_color: Color::Default,
         };
         let cmds = generate(&vec![vec![cell]], None, None, None);
         assert_eq!(cmds[0].fg, DEFAULT_FG);
     }
 
+    #[test]
+    fn bold_promotion_can_be_disabled() {
+        let cell = Cell {
+            character: 'B',
+            fg: Color::Indexed(1),
+            bg: Color::Default,
+            flags: CellFlags::BOLD,
+            zerowidth: None,
+            underline_style: UnderlineStyle::Single,
+            underline_color: Color::Default,
+        };
+        let palette = TerminalPalette {
+            bold_is_bright: false,
+            ..TerminalPalette::DEFAULT
+        };
+        let cmds = super::generate(&vec![vec![cell]], None, None, None, palette);
+        assert_eq!(cmds[0].fg, Rgb::new(204, 0, 0));
+    }
+
+    // --- DIM style tests ---
+
+    #[test]
+    fn dim_scale_uses_configured_factor() {
+        let cell = Cell {
+            character: 'D',
+            fg: Color::Rgb(Rgb::new(200, 100, 50)),
+            bg: Color::Default,
+            flags: CellFlags::DIM,
+            zerowidth: None,
+            underline_style: UnderlineStyle::Single,
+            underline_color: Color::Default,
+        };
+        let palette = TerminalPalette {
+            dim: DimStyle::Scale(75),
+            ..TerminalPalette::DEFAULT
+        };
+        let cmds = super::generate(&vec![vec![cell]], None, None, None, palette);
+        assert_eq!(cmds[0].fg, Rgb::new(150, 75, 37));
+    }
+
+    #[test]
+    fn dim_blend_moves_fg_toward_bg() {
+        let cell = Cell {
+            character: 'D',
+            fg: Color::Rgb(Rgb::new(0, 0, 0)),
+            bg: Color::Rgb(Rgb::new(240, 240, 200)),
+            flags: CellFlags::DIM,
+            zerowidth: None,
+            underline_style: UnderlineStyle::Single,
+            underline_color: Color::Default,
+        };
+        let palette = TerminalPalette {
+            dim: DimStyle::BlendToBackground(50),
+            ..TerminalPalette::DEFAULT
+        };
+        let cmds = super::generate(&vec![vec![cell]], None, None, None, palette);
+        assert_eq!(cmds[0].fg, Rgb::new(120, 120, 100));
+        assert_eq!(cmds[0].bg, Rgb::new(240, 240, 200));
+    }
+
     // --- Selection highlight tests ---
 
     #[test]
@@ -778,6 +1358,55 @@ mod tests {
         assert_eq!(cmds[3].fg, DEFAULT_FG);
     }
 
+    #[test]
+    fn selection_uses_explicit_colors() {
+        let cells = vec![vec![
+            Cell {
+                character: 'A',
+                fg: Color::Indexed(1),
+                ..Cell::default()
+            },
+            Cell {
+                character: 'B',
+                flags: CellFlags::INVERSE,
+                ..Cell::default()
+            },
+        ]];
+        let palette = TerminalPalette {
+            selection: SelectionColors {
+                fg: Some(Rgb::new(255, 255, 255)),
+                bg: Some(Rgb::new(40, 80, 160)),
+                alpha: 255,
+            },
+            ..TerminalPalette::DEFAULT
+        };
+        let cmds = super::generate(&cells, None, None, Some(((0, 0), (0, 1))), palette);
+        for cmd in &cmds {
+            assert_eq!(cmd.fg, Rgb::new(255, 255, 255));
+            assert_eq!(cmd.bg, Rgb::new(40, 80, 160));
+        }
+    }
+
+    #[test]
+    fn selection_blends_background_and_keeps_fg() {
+        let cells = vec![vec![Cell {
+            character: 'A',
+            fg: Color::Indexed(1),
+            ..Cell::default()
+        }]];
+        let palette = TerminalPalette {
+            selection: SelectionColors {
+                fg: None,
+                bg: Some(Rgb::new(200, 100, 0)),
+                alpha: 128,
+            },
+            ..TerminalPalette::DEFAULT
+        };
+        let cmds = super::generate(&cells, None, None, Some(((0, 0), (0, 0))), palette);
+        assert_eq!(cmds[0].fg, Rgb::new(204, 0, 0));
+        assert_eq!(cmds[0].bg, Rgb::new(100, 50, 0));
+    }
+
     #[test]
     fn selection_none_no_effect() {
         let cells = vec![vec![Cell::default()]];
@@ -791,15 +1420,49 @@ mod tests {
         let palette = TerminalPalette {
             default_fg: Rgb::new(12, 34, 56),
             default_bg: Rgb::new(65, 43, 21),
+            ..TerminalPalette::DEFAULT
         };
         let cell = Cell {
             character: 'D',
             fg: Color::Default,
             bg: Color::Default,
             flags: CellFlags::empty(),
+            zerowidth: None,
+            underline_style: UnderlineStyle::Single,
+            underline_color: Color::Default,
         };
         let cmds = super::generate(&vec![vec![cell]], None, None, None, palette);
         assert_eq!(cmds[0].fg, Rgb::new(12, 34, 56));
         assert_eq!(cmds[0].bg, Rgb::new(65, 43, 21));
     }
+
+    // --- Trailing whitespace debug view ---
+
+    fn space(explicit: bool) -> Cell {
+        Cell {
+            flags: if explicit { CellFlags::EXPLICIT_SPACE } else { CellFlags::empty() },
+            ..Cell::default()
+        }
+    }
+
+    #[test]
+    fn trailing_explicit_spaces_become_dots() {
+        let a = Cell { character: 'a', ..Cell::default() };
+        let cells = vec![vec![a, space(true), space(true), space(false)]];
+        let mut cmds = generate(&cells, None, None, None);
+        mark_trailing_whitespace(&mut cmds);
+        let chars: String = cmds.iter().map(|c| c.character).collect();
+        assert_eq!(chars, "a·· ");
+        assert_ne!(cmds[1].fg, DEFAULT_FG);
+    }
+
+    #[test]
+    fn inner_spaces_are_not_marked() {
+        let a = Cell { character: 'a', ..Cell::default() };
+        let cells = vec![vec![a, space(true), a]];
+        let mut cmds = generate(&cells, None, None, None);
+        mark_trailing_whitespace(&mut cmds);
+        let chars: String = cmds.iter().map(|c| c.character).collect();
+        assert_eq!(chars, "a a");
+    }
 }
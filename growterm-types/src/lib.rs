@@ -1,8 +1,9 @@
 use bitflags::bitflags;
+use serde::{Deserialize, Serialize};
 
 // --- Rgb ---
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
 pub struct Rgb {
     pub r: u8,
     pub g: u8,
@@ -17,7 +18,7 @@ impl Rgb {
 
 // --- Color ---
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum Color {
     Default,
     Indexed(u8),
@@ -30,11 +31,18 @@ impl Default for Color {
     }
 }
 
+impl Color {
+    pub fn is_default(&self) -> bool {
+        *self == Color::Default
+    }
+}
+
 // --- CellFlags ---
 
 bitflags! {
-    #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
-    pub struct CellFlags: u8 {
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+    #[serde(transparent)]
+    pub struct CellFlags: u16 {
         const BOLD          = 0b0000_0001;
         const DIM           = 0b0000_0010;
         const ITALIC        = 0b0000_0100;
@@ -43,17 +51,67 @@ bitflags! {
         const HIDDEN        = 0b0010_0000;
         const STRIKETHROUGH = 0b0100_0000;
         const WIDE_CHAR     = 0b1000_0000;
+        /// Space printed by the program, as opposed to a blank/erased cell.
+        const EXPLICIT_SPACE = 0b1_0000_0000;
+        /// Set on a row's last cell when the text auto-wrapped onto the next row.
+        const WRAPLINE      = 0b10_0000_0000;
+    }
+}
+
+// --- UnderlineStyle ---
+
+/// SGR 4:x. Drawn only while `CellFlags::UNDERLINE` is set.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum UnderlineStyle {
+    #[default]
+    Single,
+    Double,
+    Curly,
+    Dotted,
+    Dashed,
+}
+
+impl UnderlineStyle {
+    /// Style for the `x` in SGR `4:x`. None for 0 (no underline) and unknown values.
+    pub fn from_sgr(x: u16) -> Option<Self> {
+        match x {
+            1 => Some(Self::Single),
+            2 => Some(Self::Double),
+            3 => Some(Self::Curly),
+            4 => Some(Self::Dotted),
+            5 => Some(Self::Dashed),
+            _ => None,
+        }
+    }
+
+    pub fn is_single(&self) -> bool {
+        *self == Self::Single
     }
 }
 
 // --- Cell ---
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+/// Serialized compactly: attributes equal to their default are omitted.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
 pub struct Cell {
+    #[serde(rename = "c")]
     pub character: char,
+    #[serde(skip_serializing_if = "Color::is_default")]
     pub fg: Color,
+    #[serde(skip_serializing_if = "Color::is_default")]
     pub bg: Color,
+    #[serde(skip_serializing_if = "CellFlags::is_empty")]
     pub flags: CellFlags,
+    /// Combining marks / ZWJ continuation attached to `character`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub zerowidth: Option<ZeroWidth>,
+    #[serde(rename = "us", skip_serializing_if = "UnderlineStyle::is_single")]
+    pub underline_style: UnderlineStyle,
+    /// SGR 58; `Default` draws the underline in the foreground color
+    #[serde(rename = "uc", skip_serializing_if = "Color::is_default")]
+    pub underline_color: Color,
 }
 
 impl Default for Cell {
@@ -63,7 +121,115 @@ impl Default for Cell {
             fg: Color::Default,
             bg: Color::Default,
             flags: CellFlags::empty(),
+            zerowidth: None,
+            underline_style: UnderlineStyle::Single,
+            underline_color: Color::Default,
+        }
+    }
+}
+
+// --- ZeroWidth ---
+
+/// Most zero-width chars kept on one cell; the rest are dropped (Zalgo text).
+pub const MAX_ZEROWIDTH_CHARS: usize = 16;
+/// Upper bound on distinct interned clusters so a hostile stream can't grow
+/// the table forever. New clusters past this are dropped.
+const MAX_INTERNED: usize = 1 << 16;
+
+/// Zero-width chars following a cell's base character, interned in a
+/// process-wide table so `Cell` stays `Copy`. Real text only ever produces a
+/// small set of distinct clusters.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ZeroWidth(std::num::NonZeroU32);
+
+/// Slots per arena chunk. Chunks are allocated on first use and never move,
+/// so resolving an id is two atomic loads instead of taking the intern lock.
+const CHUNK_BITS: u32 = 10;
+const CHUNK_LEN: usize = 1 << CHUNK_BITS;
+
+type Chunk = Box<[std::sync::OnceLock<&'static str>]>;
+
+/// Append-only: a slot is filled before its id is handed out and never changes.
+static ARENA: [std::sync::OnceLock<Chunk>; MAX_INTERNED / CHUNK_LEN] =
+    [const { std::sync::OnceLock::new() }; MAX_INTERNED / CHUNK_LEN];
+
+/// Only `intern` takes this; lookups by id go straight to `ARENA`.
+#[derive(Default)]
+struct Interner {
+    ids: std::collections::HashMap<&'static str, ZeroWidth>,
+    len: usize,
+}
+
+fn interner() -> std::sync::MutexGuard<'static, Interner> {
+    static INTERNER: std::sync::OnceLock<std::sync::Mutex<Interner>> = std::sync::OnceLock::new();
+    INTERNER
+        .get_or_init(Default::default)
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+}
+
+fn arena_slot(index: usize) -> &'static std::sync::OnceLock<&'static str> {
+    let chunk = ARENA[index >> CHUNK_BITS]
+        .get_or_init(|| (0..CHUNK_LEN).map(|_| std::sync::OnceLock::new()).collect());
+    &chunk[index & (CHUNK_LEN - 1)]
+}
+
+impl ZeroWidth {
+    pub fn intern(s: &str) -> Option<Self> {
+        if s.is_empty() || s.chars().count() > MAX_ZEROWIDTH_CHARS {
+            return None;
         }
+        let mut table = interner();
+        if let Some(id) = table.ids.get(s) {
+            return Some(*id);
+        }
+        if table.len >= MAX_INTERNED {
+            return None;
+        }
+        let s: &'static str = Box::leak(s.to_owned().into_boxed_str());
+        let _ = arena_slot(table.len).set(s);
+        table.len += 1;
+        let id = ZeroWidth(std::num::NonZeroU32::new(table.len as u32)?);
+        table.ids.insert(s, id);
+        Some(id)
+    }
+
+    /// `self` followed by `c`, or `self` unchanged if the cluster is full.
+    pub fn push(this: Option<Self>, c: char) -> Option<Self> {
+        let mut s = this.map(|z| z.as_str().to_owned()).unwrap_or_default();
+        s.push(c);
+        Self::intern(&s).or(this)
+    }
+
+    pub fn as_str(self) -> &'static str {
+        arena_slot(self.0.get() as usize - 1)
+            .get()
+            .copied()
+            .expect("ZeroWidth ids are only handed out for filled slots")
+    }
+
+    /// Ends in U+200D ZERO WIDTH JOINER: the next printed char joins this cell.
+    pub fn ends_with_joiner(self) -> bool {
+        self.as_str().ends_with('\u{200D}')
+    }
+
+    /// Part of an emoji ZWJ sequence rather than plain combining marks.
+    pub fn has_joiner(self) -> bool {
+        self.as_str().contains('\u{200D}')
+    }
+}
+
+/// Interned ids are process-local, so the cluster is serialized as its text.
+impl Serialize for ZeroWidth {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+impl<'de> Deserialize<'de> for ZeroWidth {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let s = String::deserialize(deserializer)?;
+        Self::intern(&s).ok_or_else(|| serde::de::Error::custom("invalid zero-width cluster"))
     }
 }
 
@@ -77,6 +243,75 @@ pub struct RenderCommand {
     pub fg: Rgb,
     pub bg: Rgb,
     pub flags: CellFlags,
+    pub zerowidth: Option<ZeroWidth>,
+    pub underline_style: UnderlineStyle,
+    /// Already resolved: the foreground unless SGR 58 set one
+    pub underline_color: Rgb,
+}
+
+// --- Attributes ---
+
+/// SGR state applied to newly printed cells.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Attributes {
+    pub fg: Color,
+    pub bg: Color,
+    pub flags: CellFlags,
+    #[serde(default)]
+    pub underline_style: UnderlineStyle,
+    #[serde(default)]
+    pub underline_color: Color,
+}
+
+impl Default for Attributes {
+    fn default() -> Self {
+        Self {
+            fg: Color::Default,
+            bg: Color::Default,
+            flags: CellFlags::empty(),
+            underline_style: UnderlineStyle::Single,
+            underline_color: Color::Default,
+        }
+    }
+}
+
+impl Attributes {
+    /// Applies an SGR-derived command. Returns false for non-attribute commands.
+    /// Each Set/Reset touches exactly one attribute; SGR 22 arrives as ResetBold + ResetDim.
+    pub fn apply(&mut self, cmd: &TerminalCommand) -> bool {
+        match cmd {
+            TerminalCommand::SetForeground(c) => self.fg = *c,
+            TerminalCommand::SetBackground(c) => self.bg = *c,
+            TerminalCommand::SetBold => self.flags |= CellFlags::BOLD,
+            TerminalCommand::SetDim => self.flags |= CellFlags::DIM,
+            TerminalCommand::SetItalic => self.flags |= CellFlags::ITALIC,
+            TerminalCommand::SetUnderline => {
+                self.flags |= CellFlags::UNDERLINE;
+                self.underline_style = UnderlineStyle::Single;
+            }
+            TerminalCommand::SetUnderlineStyle(style) => {
+                self.flags |= CellFlags::UNDERLINE;
+                self.underline_style = *style;
+            }
+            TerminalCommand::SetUnderlineColor(c) => self.underline_color = *c,
+            TerminalCommand::SetInverse => self.flags |= CellFlags::INVERSE,
+            TerminalCommand::SetHidden => self.flags |= CellFlags::HIDDEN,
+            TerminalCommand::SetStrikethrough => self.flags |= CellFlags::STRIKETHROUGH,
+            TerminalCommand::ResetBold => self.flags.remove(CellFlags::BOLD),
+            TerminalCommand::ResetDim => self.flags.remove(CellFlags::DIM),
+            TerminalCommand::ResetItalic => self.flags.remove(CellFlags::ITALIC),
+            TerminalCommand::ResetUnderline => {
+                self.flags.remove(CellFlags::UNDERLINE);
+                self.underline_style = UnderlineStyle::Single;
+            }
+            TerminalCommand::ResetInverse => self.flags.remove(CellFlags::INVERSE),
+            TerminalCommand::ResetHidden => self.flags.remove(CellFlags::HIDDEN),
+            TerminalCommand::ResetStrikethrough => self.flags.remove(CellFlags::STRIKETHROUGH),
+            TerminalCommand::ResetAttributes => *self = Self::default(),
+            _ => return false,
+        }
+        true
+    }
 }
 
 // --- TerminalCommand ---
@@ -95,10 +330,15 @@ pub enum TerminalCommand {
     SetDim,
     SetItalic,
     SetUnderline,
+    /// SGR 4:x (x = 1..5) and SGR 21 (double)
+    SetUnderlineStyle(UnderlineStyle),
+    /// SGR 58 / SGR 59 (`Color::Default`)
+    SetUnderlineColor(Color),
     SetInverse,
     SetHidden,
     SetStrikethrough,
     ResetBold,
+    ResetDim,
     ResetItalic,
     ResetUnderline,
     ResetInverse,
@@ -116,6 +356,8 @@ pub enum TerminalCommand {
     DeleteChars(u16),
     InsertChars(u16),
     EraseChars(u16),
+    /// REP (`CSI Ps b`): print the last graphic character Ps more times
+    RepeatChar(u16),
     InsertLines(u16),
     DeleteLines(u16),
     ScrollUp(u16),
@@ -129,6 +371,73 @@ pub enum TerminalCommand {
     LeaveAltScreen,
     ShowCursor,
     HideCursor,
+    /// DEC private mode set/reset (`CSI ? Pm h` / `CSI ? Pm l`), one per parameter
+    SetMode(u16, bool),
+    /// DECRQM for a DEC private mode (`CSI ? Ps $ p`)
+    QueryMode(u16),
+    /// DSR 6 (`CSI 6 n`): report the cursor position
+    QueryCursorPosition,
+    /// Primary DA (`CSI c`)
+    QueryPrimaryDeviceAttributes,
+    /// Secondary DA (`CSI > c`)
+    QuerySecondaryDeviceAttributes,
+    /// Kitty keyboard protocol flags query (`CSI ? u`)
+    QueryKittyKeyboard,
+    /// `CSI > flags u`: push the current flags and switch to `flags`
+    PushKittyKeyboard(u16),
+    /// `CSI < n u`: pop `n` entries (at least one)
+    PopKittyKeyboard(u16),
+    /// `CSI = flags ; mode u`: 1 = replace, 2 = set bits, 3 = clear bits
+    SetKittyKeyboard(u16, u16),
+    Osc(OscCommand),
+    /// RIS (`ESC c`): back to the power-on state, scrollback included
+    FullReset,
+    /// DECSTR (`CSI ! p`): reset modes, attributes and margins, keep the screen
+    SoftReset,
+    /// Device Control String (`ESC P params intermediates action data ST`),
+    /// e.g. DECRQSS is `intermediates: b"$"`, `action: 'q'`.
+    Dcs { params: Vec<u16>, intermediates: Vec<u8>, action: char, data: Vec<u8> },
+}
+
+// --- OSC ---
+
+/// Operating System Command (`ESC ] Ps ; Pt BEL/ST`) decoded by the parser.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum OscCommand {
+    /// OSC 0 / OSC 2
+    SetTitle(String),
+    /// OSC 4 ; index ; spec
+    SetPaletteColor(u8, Rgb),
+    /// OSC 4 ; index ; ?
+    QueryPaletteColor(u8),
+    /// OSC 7 ; file://host/path — path part only
+    WorkingDirectory(String),
+    /// OSC 8 ; params ; uri — `None` closes the link
+    Hyperlink(Option<String>),
+    /// OSC 10 / OSC 11 with a color spec
+    SetDefaultForeground(Rgb),
+    SetDefaultBackground(Rgb),
+    /// OSC 10 ; ? / OSC 11 ; ?
+    QueryDefaultForeground,
+    QueryDefaultBackground,
+    /// OSC 52 ; selection ; base64 data (`?` for a query), undecoded
+    Clipboard { selection: String, data: String },
+    /// OSC 133 shell integration marks
+    ShellMark(ShellMark),
+    /// OSC 1337 (iTerm2) payload, e.g. `SetUserVar=...`
+    ITerm(String),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ShellMark {
+    /// A: prompt start
+    PromptStart,
+    /// B: command input start
+    CommandStart,
+    /// C: command output start
+    OutputStart,
+    /// D [; exit code]: command finished
+    CommandFinished(Option<i32>),
 }
 
 // --- Key & Modifiers ---
@@ -149,6 +458,39 @@ pub enum Key {
     End,
     PageUp,
     PageDown,
+    Insert,
+    /// Function key F1–F12
+    F(u8),
+    Keypad(KeypadKey),
+}
+
+/// Numeric keypad keys, distinct from the main keyboard row.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum KeypadKey {
+    Digit(u8),
+    Decimal,
+    Divide,
+    Multiply,
+    Minus,
+    Plus,
+    Enter,
+    Equals,
+}
+
+impl KeypadKey {
+    /// Character the key produces in normal (numeric) keypad mode.
+    pub fn as_char(self) -> char {
+        match self {
+            KeypadKey::Digit(d) => char::from(b'0' + d.min(9)),
+            KeypadKey::Decimal => '.',
+            KeypadKey::Divide => '/',
+            KeypadKey::Multiply => '*',
+            KeypadKey::Minus => '-',
+            KeypadKey::Plus => '+',
+            KeypadKey::Enter => '\r',
+            KeypadKey::Equals => '=',
+        }
+    }
 }
 
 bitflags! {
@@ -261,6 +603,28 @@ mod tests {
         assert!(cell.flags.is_empty());
     }
 
+    #[test]
+    fn cell_serialization_omits_defaults() {
+        assert_eq!(serde_json::to_string(&Cell::default()).unwrap(), r#"{"c":" "}"#);
+        let cell: Cell = serde_json::from_str(r#"{"c":"x"}"#).unwrap();
+        assert_eq!(cell, Cell { character: 'x', ..Cell::default() });
+    }
+
+    #[test]
+    fn cell_serialization_round_trips() {
+        let cell = Cell {
+            character: 'e',
+            fg: Color::Indexed(3),
+            bg: Color::Rgb(Rgb::new(1, 2, 3)),
+            flags: CellFlags::BOLD | CellFlags::UNDERLINE,
+            zerowidth: ZeroWidth::intern("\u{0301}"),
+            underline_style: UnderlineStyle::Curly,
+            underline_color: Color::Indexed(5),
+        };
+        let json = serde_json::to_string(&cell).unwrap();
+        assert_eq!(serde_json::from_str::<Cell>(&json).unwrap(), cell);
+    }
+
     // --- RenderCommand ---
     #[test]
     fn render_command_fields() {
@@ -271,6 +635,9 @@ mod tests {
             fg: Rgb::new(255, 255, 255),
             bg: Rgb::new(0, 0, 0),
             flags: CellFlags::BOLD,
+            zerowidth: None,
+            underline_style: UnderlineStyle::Single,
+            underline_color: Rgb::new(255, 255, 255),
         };
         assert_eq!(cmd.col, 5);
         assert_eq!(cmd.row, 10);
@@ -350,5 +717,106 @@ mod tests {
         assert!(matches!(Key::End, Key::End));
         assert!(matches!(Key::PageUp, Key::PageUp));
         assert!(matches!(Key::PageDown, Key::PageDown));
+        assert!(matches!(Key::Insert, Key::Insert));
+        assert!(matches!(Key::F(5), Key::F(5)));
+    }
+
+    // --- Attributes ---
+
+    #[test]
+    fn attributes_default_is_plain() {
+        let attrs = Attributes::default();
+        assert_eq!(attrs.fg, Color::Default);
+        assert_eq!(attrs.bg, Color::Default);
+        assert!(attrs.flags.is_empty());
+    }
+
+    #[test]
+    fn attributes_reset_bold_keeps_dim() {
+        let mut attrs = Attributes::default();
+        attrs.apply(&TerminalCommand::SetBold);
+        attrs.apply(&TerminalCommand::SetDim);
+        attrs.apply(&TerminalCommand::ResetBold);
+        assert_eq!(attrs.flags, CellFlags::DIM);
+        attrs.apply(&TerminalCommand::ResetDim);
+        assert!(attrs.flags.is_empty());
+    }
+
+    #[test]
+    fn attributes_reset_all() {
+        let mut attrs = Attributes::default();
+        attrs.apply(&TerminalCommand::SetForeground(Color::Indexed(1)));
+        attrs.apply(&TerminalCommand::SetItalic);
+        attrs.apply(&TerminalCommand::ResetAttributes);
+        assert_eq!(attrs, Attributes::default());
+    }
+
+    #[test]
+    fn attributes_ignore_non_sgr_commands() {
+        let mut attrs = Attributes::default();
+        assert!(!attrs.apply(&TerminalCommand::Print('a')));
+        assert!(attrs.apply(&TerminalCommand::SetUnderline));
+    }
+
+    #[test]
+    fn keypad_key_chars() {
+        assert_eq!(KeypadKey::Digit(7).as_char(), '7');
+        assert_eq!(KeypadKey::Multiply.as_char(), '*');
+        assert_eq!(KeypadKey::Enter.as_char(), '\r');
+    }
+
+    // --- ZeroWidth ---
+    #[test]
+    fn zerowidth_interning_is_stable() {
+        let a = ZeroWidth::intern("\u{0301}").unwrap();
+        let b = ZeroWidth::intern("\u{0301}").unwrap();
+        assert_eq!(a, b);
+        assert_eq!(a.as_str(), "\u{0301}");
+        assert_ne!(ZeroWidth::intern("\u{0300}"), Some(a));
+        assert_eq!(ZeroWidth::intern(""), None);
+    }
+
+    #[test]
+    fn zerowidth_push_appends_and_caps() {
+        let mut z = None;
+        z = ZeroWidth::push(z, '\u{0323}');
+        z = ZeroWidth::push(z, '\u{0302}');
+        assert_eq!(z.unwrap().as_str(), "\u{0323}\u{0302}");
+        for _ in 0..MAX_ZEROWIDTH_CHARS {
+            z = ZeroWidth::push(z, '\u{0301}');
+        }
+        assert_eq!(z.unwrap().as_str().chars().count(), MAX_ZEROWIDTH_CHARS);
+    }
+
+    #[test]
+    fn zerowidth_joiner() {
+        let z = ZeroWidth::intern("\u{200D}").unwrap();
+        assert!(z.ends_with_joiner());
+        let z = ZeroWidth::push(Some(z), '👩').unwrap();
+        assert!(!z.ends_with_joiner());
+        assert!(z.has_joiner());
+    }
+
+    #[test]
+    fn zerowidth_resolves_across_arena_chunks() {
+        let clusters: Vec<String> = (0..CHUNK_LEN as u32 + 8)
+            .map(|i| ['\u{0300}', char::from_u32(0xFE00 + i % 16).unwrap(), char::from_u32(0x0300 + i / 16).unwrap()]
+                .iter()
+                .collect())
+            .collect();
+        let ids: Vec<ZeroWidth> = std::thread::scope(|s| {
+            let reader = s.spawn(|| {
+                let a = ZeroWidth::intern("\u{0301}").unwrap();
+                for _ in 0..1000 {
+                    assert_eq!(a.as_str(), "\u{0301}");
+                }
+            });
+            let ids = clusters.iter().map(|c| ZeroWidth::intern(c).unwrap()).collect();
+            reader.join().unwrap();
+            ids
+        });
+        for (id, cluster) in ids.iter().zip(&clusters) {
+            assert_eq!(id.as_str(), cluster);
+        }
     }
 }
@@ -0,0 +1,89 @@
+/// Character set designated into G0/G1 via `ESC ( F` / `ESC ) F`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Charset {
+    Ascii,
+    /// DEC Special Graphics (`F = 0`): line drawing in the 0x5F..0x7E range
+    DecSpecialGraphics,
+}
+
+impl Charset {
+    /// Final byte of an SCS sequence. UK (`A`) and the DEC alternate ROM sets
+    /// (`1`, `2`) are close enough to ASCII to be treated as such.
+    pub(crate) fn from_final(byte: u8) -> Option<Self> {
+        match byte {
+            b'0' => Some(Charset::DecSpecialGraphics),
+            b'A' | b'B' | b'1' | b'2' => Some(Charset::Ascii),
+            _ => None,
+        }
+    }
+
+    pub(crate) fn map(self, c: char) -> char {
+        match self {
+            Charset::Ascii => c,
+            Charset::DecSpecialGraphics => dec_special_graphics(c),
+        }
+    }
+}
+
+fn dec_special_graphics(c: char) -> char {
+    match c {
+        '_' => ' ',
+        '`' => '◆',
+        'a' => '▒',
+        'b' => '␉',
+        'c' => '␌',
+        'd' => '␍',
+        'e' => '␊',
+        'f' => '°',
+        'g' => '±',
+        'h' => '␤',
+        'i' => '␋',
+        'j' => '┘',
+        'k' => '┐',
+        'l' => '┌',
+        'm' => '└',
+        'n' => '┼',
+        'o' => '⎺',
+        'p' => '⎻',
+        'q' => '─',
+        'r' => '⎼',
+        's' => '⎽',
+        't' => '├',
+        'u' => '┤',
+        'v' => '┴',
+        'w' => '┬',
+        'x' => '│',
+        'y' => '≤',
+        'z' => '≥',
+        '{' => 'π',
+        '|' => '≠',
+        '}' => '£',
+        '~' => '·',
+        _ => c,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn box_drawing() {
+        let line: String = "lqqkxmj".chars().map(|c| Charset::DecSpecialGraphics.map(c)).collect();
+        assert_eq!(line, "┌──┐│└┘");
+    }
+
+    #[test]
+    fn outside_graphics_range_unchanged() {
+        assert_eq!(Charset::DecSpecialGraphics.map('A'), 'A');
+        assert_eq!(Charset::DecSpecialGraphics.map('가'), '가');
+        assert_eq!(Charset::Ascii.map('q'), 'q');
+    }
+
+    #[test]
+    fn designation_finals() {
+        assert_eq!(Charset::from_final(b'0'), Some(Charset::DecSpecialGraphics));
+        assert_eq!(Charset::from_final(b'B'), Some(Charset::Ascii));
+        assert_eq!(Charset::from_final(b'%'), None);
+    }
+}
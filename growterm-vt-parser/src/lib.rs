@@ -1,18 +1,46 @@
-use growterm_types::{Color, Rgb, TerminalCommand};
+mod charset;
+mod osc;
+
+pub use osc::parse_color_spec;
+
+use charset::Charset;
+use growterm_types::{Color, Rgb, TerminalCommand, UnderlineStyle};
+
+/// Upper bound on buffered DCS payload; larger strings (e.g. huge sixel
+/// images) are dropped rather than growing without limit.
+const MAX_DCS_DATA: usize = 1 << 20;
 
 struct Handler {
     commands: Vec<TerminalCommand>,
+    show_control_pictures: bool,
+    dcs: Option<PendingDcs>,
+    /// G0 and G1 designations; SO/SI pick which one is active
+    charsets: [Charset; 2],
+    shift_out: bool,
+}
+
+struct PendingDcs {
+    params: Vec<u16>,
+    intermediates: Vec<u8>,
+    action: char,
+    data: Vec<u8>,
+    overflowed: bool,
 }
 
 impl Handler {
     fn new() -> Self {
         Self {
             commands: Vec::new(),
+            show_control_pictures: false,
+            dcs: None,
+            charsets: [Charset::Ascii; 2],
+            shift_out: false,
         }
     }
 
-    fn take(&mut self) -> Vec<TerminalCommand> {
-        std::mem::take(&mut self.commands)
+    fn reset_charsets(&mut self) {
+        self.charsets = [Charset::Ascii; 2];
+        self.shift_out = false;
     }
 
     fn handle_sgr(&mut self, params: &vte::Params) {
@@ -26,11 +54,28 @@ impl Handler {
                 1 => self.commands.push(TerminalCommand::SetBold),
                 2 => self.commands.push(TerminalCommand::SetDim),
                 3 => self.commands.push(TerminalCommand::SetItalic),
-                4 => self.commands.push(TerminalCommand::SetUnderline),
+                // 4:x selects the style (kitty/VTE); 4:0 turns the underline off
+                4 => match part.get(1) {
+                    None => self.commands.push(TerminalCommand::SetUnderline),
+                    Some(0) => self.commands.push(TerminalCommand::ResetUnderline),
+                    Some(&x) => {
+                        if let Some(style) = UnderlineStyle::from_sgr(x) {
+                            self.commands.push(TerminalCommand::SetUnderlineStyle(style));
+                        }
+                    }
+                },
                 7 => self.commands.push(TerminalCommand::SetInverse),
                 8 => self.commands.push(TerminalCommand::SetHidden),
                 9 => self.commands.push(TerminalCommand::SetStrikethrough),
-                22 => self.commands.push(TerminalCommand::ResetBold),
+                // ECMA-48 / xterm: doubly underlined
+                21 => self
+                    .commands
+                    .push(TerminalCommand::SetUnderlineStyle(UnderlineStyle::Double)),
+                // Normal intensity: neither bold nor faint
+                22 => {
+                    self.commands.push(TerminalCommand::ResetBold);
+                    self.commands.push(TerminalCommand::ResetDim);
+                }
                 23 => self.commands.push(TerminalCommand::ResetItalic),
                 24 => self.commands.push(TerminalCommand::ResetUnderline),
                 27 => self.commands.push(TerminalCommand::ResetInverse),
@@ -68,6 +113,15 @@ impl Handler {
                 49 => self
                     .commands
                     .push(TerminalCommand::SetBackground(Color::Default)),
+                58 => {
+                    if let Some((color, consumed)) = self.parse_extended_color(&parts, i) {
+                        self.commands.push(TerminalCommand::SetUnderlineColor(color));
+                        i += consumed;
+                    }
+                }
+                59 => self
+                    .commands
+                    .push(TerminalCommand::SetUnderlineColor(Color::Default)),
                 // Bright foreground colors 90-97
                 90..=97 => {
                     self.commands
@@ -140,6 +194,7 @@ impl Handler {
 
 impl vte::Perform for Handler {
     fn print(&mut self, c: char) {
+        let c = self.charsets[self.shift_out as usize].map(c);
         self.commands.push(TerminalCommand::Print(c));
     }
 
@@ -150,19 +205,81 @@ impl vte::Perform for Handler {
             0x09 => TerminalCommand::Tab,
             0x0A => TerminalCommand::Newline,
             0x0D => TerminalCommand::CarriageReturn,
+            // SO / SI: invoke G1 / G0
+            0x0E | 0x0F => {
+                self.shift_out = byte == 0x0E;
+                return;
+            }
+            // Debug view: show otherwise-ignored C0 controls as U+2400.. control pictures
+            0x00..=0x1F if self.show_control_pictures => {
+                TerminalCommand::Print(control_picture(byte))
+            }
             _ => return,
         };
         self.commands.push(cmd);
     }
 
-    fn esc_dispatch(&mut self, intermediates: &[u8], _ignore: bool, byte: u8) {
-        if !intermediates.is_empty() {
+    fn hook(&mut self, params: &vte::Params, intermediates: &[u8], _ignore: bool, action: char) {
+        self.dcs = Some(PendingDcs {
+            params: params.iter().map(|p| p[0]).collect(),
+            intermediates: intermediates.to_vec(),
+            action,
+            data: Vec::new(),
+            overflowed: false,
+        });
+    }
+
+    fn put(&mut self, byte: u8) {
+        if let Some(dcs) = self.dcs.as_mut() {
+            if dcs.data.len() < MAX_DCS_DATA {
+                dcs.data.push(byte);
+            } else {
+                dcs.overflowed = true;
+            }
+        }
+    }
+
+    fn unhook(&mut self) {
+        let Some(dcs) = self.dcs.take() else {
+            return;
+        };
+        if dcs.overflowed {
             return;
         }
+        self.commands.push(TerminalCommand::Dcs {
+            params: dcs.params,
+            intermediates: dcs.intermediates,
+            action: dcs.action,
+            data: dcs.data,
+        });
+    }
+
+    fn osc_dispatch(&mut self, params: &[&[u8]], _bell_terminated: bool) {
+        for osc in osc::parse_osc(params) {
+            self.commands.push(TerminalCommand::Osc(osc));
+        }
+    }
+
+    fn esc_dispatch(&mut self, intermediates: &[u8], _ignore: bool, byte: u8) {
+        match intermediates {
+            [] => {}
+            // SCS: ESC ( F designates G0, ESC ) F designates G1
+            [b'('] | [b')'] => {
+                if let Some(charset) = Charset::from_final(byte) {
+                    self.charsets[(intermediates[0] == b')') as usize] = charset;
+                }
+                return;
+            }
+            _ => return,
+        }
         match byte {
             b'M' => self.commands.push(TerminalCommand::ReverseIndex),
             b'7' => self.commands.push(TerminalCommand::SaveCursor),
             b'8' => self.commands.push(TerminalCommand::RestoreCursor),
+            b'c' => {
+                self.reset_charsets();
+                self.commands.push(TerminalCommand::FullReset);
+            }
             _ => {}
         }
     }
@@ -176,18 +293,56 @@ impl vte::Perform for Handler {
     ) {
         let first = params.iter().next().map(|p| p[0]).unwrap_or(0);
 
-        // Private mode sequences (CSI ? ... h/l)
+        if intermediates == [b'!'] {
+            if action == 'p' {
+                self.reset_charsets();
+                self.commands.push(TerminalCommand::SoftReset);
+            }
+            return;
+        }
+
+        if intermediates == [b'?', b'$'] {
+            if action == 'p' {
+                self.commands.push(TerminalCommand::QueryMode(first));
+            }
+            return;
+        }
+
+        // Private mode sequences (CSI ? Pm h/l), e.g. ?25, ?1049, ?2004, ?2026
         if intermediates == [b'?'] {
-            match (action, first) {
-                ('h', 25) => self.commands.push(TerminalCommand::ShowCursor),
-                ('l', 25) => self.commands.push(TerminalCommand::HideCursor),
-                ('h', 1049) => self.commands.push(TerminalCommand::EnterAltScreen),
-                ('l', 1049) => self.commands.push(TerminalCommand::LeaveAltScreen),
-                _ => {}
+            let enabled = match action {
+                'h' => true,
+                'l' => false,
+                'u' => {
+                    self.commands.push(TerminalCommand::QueryKittyKeyboard);
+                    return;
+                }
+                _ => return,
+            };
+            for param in params.iter() {
+                self.commands.push(TerminalCommand::SetMode(param[0], enabled));
             }
             return;
         }
 
+        // 나머지 private marker/intermediate가 붙은 CSI는 같은 final 바이트의
+        // 표준 CSI와 다름. 아는 것(kitty 키보드 스택, 2차 DA) 외에는 무시
+        // (XTMODKEYS `CSI > 4;2 m` 등)
+        if !intermediates.is_empty() {
+            let cmd = match (intermediates, action) {
+                ([b'>'], 'u') => Some(TerminalCommand::PushKittyKeyboard(first)),
+                ([b'<'], 'u') => Some(TerminalCommand::PopKittyKeyboard(first.max(1))),
+                ([b'='], 'u') => {
+                    let mode = params.iter().nth(1).map(|p| p[0]).filter(|&m| m != 0).unwrap_or(1);
+                    Some(TerminalCommand::SetKittyKeyboard(first, mode))
+                }
+                ([b'>'], 'c') if first == 0 => Some(TerminalCommand::QuerySecondaryDeviceAttributes),
+                _ => None,
+            };
+            self.commands.extend(cmd);
+            return;
+        }
+
         match action {
             'A' => self.commands.push(TerminalCommand::CursorUp(first.max(1))),
             'B' => self
@@ -226,6 +381,9 @@ impl vte::Perform for Handler {
             'X' => self
                 .commands
                 .push(TerminalCommand::EraseChars(first.max(1))),
+            'b' => self
+                .commands
+                .push(TerminalCommand::RepeatChar(first.max(1))),
             'L' => self
                 .commands
                 .push(TerminalCommand::InsertLines(first.max(1))),
@@ -244,6 +402,10 @@ impl vte::Perform for Handler {
             'd' => self
                 .commands
                 .push(TerminalCommand::CursorRow(first.max(1))),
+            'n' if first == 6 => self.commands.push(TerminalCommand::QueryCursorPosition),
+            'c' if first == 0 => self
+                .commands
+                .push(TerminalCommand::QueryPrimaryDeviceAttributes),
             's' => self.commands.push(TerminalCommand::SaveCursor),
             'u' => self.commands.push(TerminalCommand::RestoreCursor),
             'r' => {
@@ -272,17 +434,48 @@ impl VtParser {
         }
     }
 
+    /// Debug view: print unhandled C0 controls as Unicode control pictures
+    /// instead of silently dropping them.
+    pub fn set_show_control_pictures(&mut self, enabled: bool) {
+        self.handler.show_control_pictures = enabled;
+    }
+
     pub fn parse(&mut self, bytes: &[u8]) -> Vec<TerminalCommand> {
+        let mut out = Vec::new();
+        self.parse_to(bytes, &mut out);
+        out
+    }
+
+    /// Append parsed commands to `out`. Reusing `out` across reads avoids
+    /// allocating a new Vec for every PTY chunk.
+    pub fn parse_to(&mut self, bytes: &[u8], out: &mut Vec<TerminalCommand>) {
+        std::mem::swap(&mut self.handler.commands, out);
+        for &byte in bytes {
+            self.parser.advance(&mut self.handler, byte);
+        }
+        std::mem::swap(&mut self.handler.commands, out);
+    }
+
+    /// Hand each command to `f` as soon as it is parsed, in order.
+    /// The handler's internal buffer is reused, so nothing is allocated per call.
+    pub fn parse_with(&mut self, bytes: &[u8], mut f: impl FnMut(TerminalCommand)) {
         for &byte in bytes {
             self.parser.advance(&mut self.handler, byte);
+            for cmd in self.handler.commands.drain(..) {
+                f(cmd);
+            }
         }
-        self.handler.take()
     }
 }
 
+fn control_picture(byte: u8) -> char {
+    char::from_u32(0x2400 + byte as u32).unwrap_or('\u{FFFD}')
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use growterm_types::{OscCommand, ShellMark};
 
     // --- ASCII text ---
 
@@ -302,6 +495,48 @@ mod tests {
         );
     }
 
+    #[test]
+    fn parse_to_appends_and_matches_parse() {
+        let input = b"a\x1b[1;31mb\x1b[0m\r\n";
+        let expected = VtParser::new().parse(input);
+        let mut parser = VtParser::new();
+        let mut out = vec![TerminalCommand::Bell];
+        parser.parse_to(input, &mut out);
+        assert_eq!(out[0], TerminalCommand::Bell);
+        assert_eq!(&out[1..], &expected[..]);
+    }
+
+    #[test]
+    fn parse_to_keeps_state_across_chunks() {
+        let mut parser = VtParser::new();
+        let mut out = Vec::new();
+        parser.parse_to(b"\x1b[", &mut out);
+        assert!(out.is_empty());
+        parser.parse_to(b"2J", &mut out);
+        assert_eq!(out, vec![TerminalCommand::EraseInDisplay(2)]);
+    }
+
+    #[test]
+    fn parse_with_streams_same_commands_as_parse() {
+        let input = "가\x1b[38;2;1;2;3mb\x1b[22m\x07\r\n".as_bytes();
+        let expected = VtParser::new().parse(input);
+        let mut parser = VtParser::new();
+        let mut streamed = Vec::new();
+        parser.parse_with(input, |cmd| streamed.push(cmd));
+        assert_eq!(streamed, expected);
+    }
+
+    #[test]
+    fn parse_with_split_utf8_across_calls() {
+        let bytes = "한".as_bytes();
+        let mut parser = VtParser::new();
+        let mut streamed = Vec::new();
+        parser.parse_with(&bytes[..1], |cmd| streamed.push(cmd));
+        assert!(streamed.is_empty());
+        parser.parse_with(&bytes[1..], |cmd| streamed.push(cmd));
+        assert_eq!(streamed, vec![TerminalCommand::Print('한')]);
+    }
+
     #[test]
     fn parse_empty_input() {
         let mut parser = VtParser::new();
@@ -348,6 +583,28 @@ mod tests {
 
     // --- CSI cursor movement ---
 
+    #[test]
+    fn unhandled_c0_ignored_by_default() {
+        let mut parser = VtParser::new();
+        let cmds = parser.parse(b"\x01\x0E");
+        assert!(cmds.is_empty());
+    }
+
+    #[test]
+    fn unhandled_c0_as_control_pictures_in_debug_view() {
+        let mut parser = VtParser::new();
+        parser.set_show_control_pictures(true);
+        let cmds = parser.parse(b"\x01\x00\r");
+        assert_eq!(
+            cmds,
+            vec![
+                TerminalCommand::Print('\u{2401}'),
+                TerminalCommand::Print('\u{2400}'),
+                TerminalCommand::CarriageReturn,
+            ]
+        );
+    }
+
     #[test]
     fn parse_cursor_up() {
         let mut parser = VtParser::new();
@@ -436,6 +693,67 @@ mod tests {
         assert_eq!(cmds, vec![TerminalCommand::ReverseIndex]);
     }
 
+    #[test]
+    fn parse_full_reset() {
+        let mut parser = VtParser::new();
+        assert_eq!(parser.parse(b"\x1bc"), vec![TerminalCommand::FullReset]);
+    }
+
+    #[test]
+    fn parse_dec_special_graphics() {
+        let mut parser = VtParser::new();
+        let cmds = parser.parse(b"\x1b(0lqk\x1b(Bq");
+        assert_eq!(
+            cmds,
+            vec![
+                TerminalCommand::Print('┌'),
+                TerminalCommand::Print('─'),
+                TerminalCommand::Print('┐'),
+                TerminalCommand::Print('q'),
+            ]
+        );
+    }
+
+    #[test]
+    fn parse_shift_out_uses_g1() {
+        let mut parser = VtParser::new();
+        let cmds = parser.parse(b"\x1b)0x\x0ex\x0fx");
+        assert_eq!(
+            cmds,
+            vec![
+                TerminalCommand::Print('x'),
+                TerminalCommand::Print('│'),
+                TerminalCommand::Print('x'),
+            ]
+        );
+    }
+
+    #[test]
+    fn parse_reset_restores_ascii_charset() {
+        let mut parser = VtParser::new();
+        parser.parse(b"\x1b(0");
+        assert_eq!(
+            parser.parse(b"\x1bcq"),
+            vec![TerminalCommand::FullReset, TerminalCommand::Print('q')]
+        );
+    }
+
+    #[test]
+    fn parse_private_mode_query() {
+        let mut parser = VtParser::new();
+        assert_eq!(parser.parse(b"\x1b[?2027$p"), vec![TerminalCommand::QueryMode(2027)]);
+        // ANSI (non-private) DECRQM is not handled
+        assert_eq!(parser.parse(b"\x1b[4$p"), vec![]);
+    }
+
+    #[test]
+    fn parse_soft_reset() {
+        let mut parser = VtParser::new();
+        assert_eq!(parser.parse(b"\x1b[!p"), vec![TerminalCommand::SoftReset]);
+        // other '!' sequences must not fall through to plain CSI handling
+        assert_eq!(parser.parse(b"\x1b[!m"), vec![]);
+    }
+
     #[test]
     fn parse_cursor_position() {
         let mut parser = VtParser::new();
@@ -528,7 +846,41 @@ mod tests {
     fn parse_sgr_reset_bold() {
         let mut parser = VtParser::new();
         let cmds = parser.parse(b"\x1b[22m");
-        assert_eq!(cmds, vec![TerminalCommand::ResetBold]);
+        assert_eq!(cmds, vec![TerminalCommand::ResetBold, TerminalCommand::ResetDim]);
+    }
+
+    #[test]
+    fn parse_sgr_double_underline() {
+        let mut parser = VtParser::new();
+        let cmds = parser.parse(b"\x1b[21m");
+        assert_eq!(cmds, vec![TerminalCommand::SetUnderlineStyle(UnderlineStyle::Double)]);
+    }
+
+    #[test]
+    fn parse_sgr_underline_styles() {
+        let mut parser = VtParser::new();
+        assert_eq!(
+            parser.parse(b"\x1b[4:3m\x1b[4:5m"),
+            vec![
+                TerminalCommand::SetUnderlineStyle(UnderlineStyle::Curly),
+                TerminalCommand::SetUnderlineStyle(UnderlineStyle::Dashed),
+            ]
+        );
+        assert_eq!(parser.parse(b"\x1b[4:0m"), vec![TerminalCommand::ResetUnderline]);
+        assert_eq!(parser.parse(b"\x1b[4:9m"), vec![]);
+    }
+
+    #[test]
+    fn parse_sgr_underline_color() {
+        let mut parser = VtParser::new();
+        assert_eq!(
+            parser.parse(b"\x1b[58:2::255:0:0m\x1b[58;5;4m\x1b[59m"),
+            vec![
+                TerminalCommand::SetUnderlineColor(Color::Rgb(Rgb::new(255, 0, 0))),
+                TerminalCommand::SetUnderlineColor(Color::Indexed(4)),
+                TerminalCommand::SetUnderlineColor(Color::Default),
+            ]
+        );
     }
 
     #[test]
@@ -794,36 +1146,227 @@ mod tests {
         assert_eq!(cmds, vec![TerminalCommand::DeleteChars(1)]);
     }
 
-    // --- DECTCEM (cursor visibility) ---
+    // --- OSC ---
+
+    #[test]
+    fn parse_osc_title_bel_terminated() {
+        let mut parser = VtParser::new();
+        let cmds = parser.parse(b"\x1b]0;my title\x07x");
+        assert_eq!(
+            cmds,
+            vec![
+                TerminalCommand::Osc(OscCommand::SetTitle("my title".into())),
+                TerminalCommand::Print('x'),
+            ]
+        );
+    }
+
+    #[test]
+    fn parse_osc_st_terminated() {
+        let mut parser = VtParser::new();
+        let cmds = parser.parse(b"\x1b]11;?\x1b\\");
+        assert_eq!(cmds, vec![TerminalCommand::Osc(OscCommand::QueryDefaultBackground)]);
+    }
+
+    #[test]
+    fn parse_osc_split_across_chunks() {
+        let mut parser = VtParser::new();
+        assert!(parser.parse(b"\x1b]133;").is_empty());
+        assert!(parser.parse(b"D;0").is_empty());
+        assert_eq!(
+            parser.parse(b"\x07"),
+            vec![TerminalCommand::Osc(OscCommand::ShellMark(ShellMark::CommandFinished(Some(0))))]
+        );
+    }
+
+    #[test]
+    fn parse_osc_utf8_title() {
+        let mut parser = VtParser::new();
+        let cmds = parser.parse("\x1b]2;한글 제목\x07".as_bytes());
+        assert_eq!(cmds, vec![TerminalCommand::Osc(OscCommand::SetTitle("한글 제목".into()))]);
+    }
+
+    #[test]
+    fn parse_unknown_osc_is_dropped() {
+        let mut parser = VtParser::new();
+        assert!(parser.parse(b"\x1b]999;x\x07").is_empty());
+    }
+
+    // --- DCS ---
+
+    #[test]
+    fn kitty_keyboard_csi_u_does_not_restore_cursor() {
+        let mut parser = VtParser::new();
+        for seq in [&b"\x1b[=1;1u"[..], b"\x1b[>1u", b"\x1b[<u", b"\x1b[?u"] {
+            let cmds = parser.parse(seq);
+            assert!(!cmds.contains(&TerminalCommand::RestoreCursor), "{seq:?} → {cmds:?}");
+        }
+        assert_eq!(parser.parse(b"\x1b[u"), vec![TerminalCommand::RestoreCursor]);
+    }
+
+    #[test]
+    fn parse_device_queries() {
+        let mut parser = VtParser::new();
+        assert_eq!(
+            parser.parse(b"\x1b[6n\x1b[c\x1b[0c\x1b[>c\x1b[>0c"),
+            vec![
+                TerminalCommand::QueryCursorPosition,
+                TerminalCommand::QueryPrimaryDeviceAttributes,
+                TerminalCommand::QueryPrimaryDeviceAttributes,
+                TerminalCommand::QuerySecondaryDeviceAttributes,
+                TerminalCommand::QuerySecondaryDeviceAttributes,
+            ]
+        );
+        // DSR 5, DA with a parameter: not answered
+        assert!(parser.parse(b"\x1b[5n\x1b[1c\x1b[>1c").is_empty());
+    }
+
+    #[test]
+    fn parse_kitty_keyboard_stack() {
+        let mut parser = VtParser::new();
+        assert_eq!(
+            parser.parse(b"\x1b[>7u\x1b[?u\x1b[<u\x1b[<3u\x1b[=5;2u\x1b[=3u"),
+            vec![
+                TerminalCommand::PushKittyKeyboard(7),
+                TerminalCommand::QueryKittyKeyboard,
+                TerminalCommand::PopKittyKeyboard(1),
+                TerminalCommand::PopKittyKeyboard(3),
+                TerminalCommand::SetKittyKeyboard(5, 2),
+                TerminalCommand::SetKittyKeyboard(3, 1),
+            ]
+        );
+    }
+
+    #[test]
+    fn queries_split_across_reads_stay_in_stream_order() {
+        let mut parser = VtParser::new();
+        assert!(parser.parse(b"\x1b[>").is_empty());
+        assert_eq!(
+            parser.parse(b"7u\x1bc\x1b[?2026$p\x1b[6"),
+            vec![
+                TerminalCommand::PushKittyKeyboard(7),
+                TerminalCommand::FullReset,
+                TerminalCommand::QueryMode(2026),
+            ]
+        );
+        assert_eq!(parser.parse(b"n"), vec![TerminalCommand::QueryCursorPosition]);
+    }
+
+    #[test]
+    fn xtmodkeys_is_not_sgr() {
+        let mut parser = VtParser::new();
+        assert!(parser.parse(b"\x1b[>4;2m").is_empty());
+    }
+
+    #[test]
+    fn parse_dcs_decrqss() {
+        let mut parser = VtParser::new();
+        let cmds = parser.parse(b"\x1bP$qm\x1b\\");
+        assert_eq!(
+            cmds,
+            vec![TerminalCommand::Dcs {
+                params: vec![],
+                intermediates: b"$".to_vec(),
+                action: 'q',
+                data: b"m".to_vec(),
+            }]
+        );
+    }
+
+    #[test]
+    fn parse_dcs_with_params_split_across_chunks() {
+        let mut parser = VtParser::new();
+        assert!(parser.parse(b"\x1bP1;2+q").is_empty());
+        assert!(parser.parse(b"544e").is_empty());
+        assert_eq!(
+            parser.parse(b"\x1b\\x"),
+            vec![
+                TerminalCommand::Dcs {
+                    params: vec![1, 2],
+                    intermediates: b"+".to_vec(),
+                    action: 'q',
+                    data: b"544e".to_vec(),
+                },
+                TerminalCommand::Print('x'),
+            ]
+        );
+    }
+
+    #[test]
+    fn parse_oversized_dcs_is_dropped() {
+        let mut parser = VtParser::new();
+        let mut input = b"\x1bPq".to_vec();
+        input.extend(std::iter::repeat(b'#').take(MAX_DCS_DATA + 1));
+        input.extend_from_slice(b"\x1b\\");
+        assert!(parser.parse(&input).is_empty());
+    }
+
+    // --- DEC private modes ---
 
     #[test]
     fn parse_hide_cursor() {
         let mut parser = VtParser::new();
         let cmds = parser.parse(b"\x1b[?25l");
-        assert_eq!(cmds, vec![TerminalCommand::HideCursor]);
+        assert_eq!(cmds, vec![TerminalCommand::SetMode(25, false)]);
     }
 
     #[test]
     fn parse_show_cursor() {
         let mut parser = VtParser::new();
         let cmds = parser.parse(b"\x1b[?25h");
-        assert_eq!(cmds, vec![TerminalCommand::ShowCursor]);
+        assert_eq!(cmds, vec![TerminalCommand::SetMode(25, true)]);
     }
 
-    // --- Alternate Screen Buffer ---
-
     #[test]
     fn parse_enter_alt_screen() {
         let mut parser = VtParser::new();
         let cmds = parser.parse(b"\x1b[?1049h");
-        assert_eq!(cmds, vec![TerminalCommand::EnterAltScreen]);
+        assert_eq!(cmds, vec![TerminalCommand::SetMode(1049, true)]);
     }
 
     #[test]
     fn parse_leave_alt_screen() {
         let mut parser = VtParser::new();
         let cmds = parser.parse(b"\x1b[?1049l");
-        assert_eq!(cmds, vec![TerminalCommand::LeaveAltScreen]);
+        assert_eq!(cmds, vec![TerminalCommand::SetMode(1049, false)]);
+    }
+
+    #[test]
+    fn parse_multiple_private_modes_in_one_sequence() {
+        let mut parser = VtParser::new();
+        let cmds = parser.parse(b"\x1b[?1003;1006h");
+        assert_eq!(
+            cmds,
+            vec![TerminalCommand::SetMode(1003, true), TerminalCommand::SetMode(1006, true)]
+        );
+    }
+
+    #[test]
+    fn parse_private_mode_split_across_chunks() {
+        let mut parser = VtParser::new();
+        assert!(parser.parse(b"\x1b[?20").is_empty());
+        assert_eq!(parser.parse(b"26h"), vec![TerminalCommand::SetMode(2026, true)]);
+    }
+
+    #[test]
+    fn parse_private_modes_amid_text() {
+        let mut parser = VtParser::new();
+        let cmds = parser.parse(b"a\x1b[?2004hb\x1b[?2004l");
+        assert_eq!(
+            cmds,
+            vec![
+                TerminalCommand::Print('a'),
+                TerminalCommand::SetMode(2004, true),
+                TerminalCommand::Print('b'),
+                TerminalCommand::SetMode(2004, false),
+            ]
+        );
+    }
+
+    #[test]
+    fn parse_private_mode_other_final_ignored() {
+        let mut parser = VtParser::new();
+        assert!(parser.parse(b"\x1b[?25s").is_empty());
     }
 
     // --- Scroll Region (DECSTBM) ---
@@ -933,6 +1476,13 @@ mod tests {
         assert_eq!(cmds, vec![TerminalCommand::EraseChars(4)]);
     }
 
+    #[test]
+    fn parse_repeat_char() {
+        let mut parser = VtParser::new();
+        assert_eq!(parser.parse(b"\x1b[5b"), vec![TerminalCommand::RepeatChar(5)]);
+        assert_eq!(parser.parse(b"\x1b[b"), vec![TerminalCommand::RepeatChar(1)]);
+    }
+
     // --- Mixed content ---
 
     #[test]
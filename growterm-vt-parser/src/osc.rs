@@ -0,0 +1,266 @@
+use growterm_types::{OscCommand, Rgb, ShellMark};
+
+/// Decode one OSC sequence. vte has already split the payload on ';' and
+/// stripped the BEL/ST terminator. OSC 4 may carry several index/spec pairs,
+/// so this returns a list.
+pub(crate) fn parse_osc(params: &[&[u8]]) -> Vec<OscCommand> {
+    let Some((code, args)) = params.split_first() else {
+        return Vec::new();
+    };
+    let Ok(code) = std::str::from_utf8(code) else {
+        return Vec::new();
+    };
+    match code {
+        "0" | "2" => vec![OscCommand::SetTitle(join(args))],
+        "4" => parse_palette(args),
+        "7" => parse_working_directory(&join(args)).into_iter().collect(),
+        "8" => {
+            // OSC 8 ; params ; uri — the uri itself may contain ';'
+            let uri = join(args.get(1..).unwrap_or(&[]));
+            vec![OscCommand::Hyperlink((!uri.is_empty()).then_some(uri))]
+        }
+        "10" | "11" => {
+            let spec = join(args);
+            let foreground = code == "10";
+            if spec == "?" {
+                return vec![if foreground {
+                    OscCommand::QueryDefaultForeground
+                } else {
+                    OscCommand::QueryDefaultBackground
+                }];
+            }
+            match parse_color_spec(&spec) {
+                Some(color) if foreground => vec![OscCommand::SetDefaultForeground(color)],
+                Some(color) => vec![OscCommand::SetDefaultBackground(color)],
+                None => Vec::new(),
+            }
+        }
+        "52" => {
+            let selection = args.first().map(|s| lossy(s)).unwrap_or_default();
+            let data = join(args.get(1..).unwrap_or(&[]));
+            vec![OscCommand::Clipboard { selection, data }]
+        }
+        "133" => parse_shell_mark(args).into_iter().map(OscCommand::ShellMark).collect(),
+        "1337" => vec![OscCommand::ITerm(join(args))],
+        _ => Vec::new(),
+    }
+}
+
+fn lossy(bytes: &[u8]) -> String {
+    String::from_utf8_lossy(bytes).into_owned()
+}
+
+/// Re-join parameters that vte split on ';'.
+fn join(args: &[&[u8]]) -> String {
+    lossy(&args.join(&b';'))
+}
+
+fn parse_palette(args: &[&[u8]]) -> Vec<OscCommand> {
+    args.chunks(2)
+        .filter_map(|pair| {
+            let [index, spec] = pair else { return None };
+            let index: u8 = std::str::from_utf8(index).ok()?.parse().ok()?;
+            if *spec == b"?" {
+                return Some(OscCommand::QueryPaletteColor(index));
+            }
+            let color = parse_color_spec(std::str::from_utf8(spec).ok()?)?;
+            Some(OscCommand::SetPaletteColor(index, color))
+        })
+        .collect()
+}
+
+/// `file://host/path` → `/path` (percent-escapes decoded). Bare paths pass through.
+fn parse_working_directory(url: &str) -> Option<OscCommand> {
+    let path = match url.strip_prefix("file://") {
+        Some(rest) => &rest[rest.find('/')?..],
+        None if url.starts_with('/') => url,
+        None => return None,
+    };
+    Some(OscCommand::WorkingDirectory(percent_decode(path)))
+}
+
+fn percent_decode(text: &str) -> String {
+    let bytes = text.as_bytes();
+    let mut out = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        if bytes[i] == b'%' && i + 2 < bytes.len() {
+            let hex = std::str::from_utf8(&bytes[i + 1..i + 3]).ok();
+            if let Some(b) = hex.and_then(|h| u8::from_str_radix(h, 16).ok()) {
+                out.push(b);
+                i += 3;
+                continue;
+            }
+        }
+        out.push(bytes[i]);
+        i += 1;
+    }
+    String::from_utf8_lossy(&out).into_owned()
+}
+
+fn parse_shell_mark(args: &[&[u8]]) -> Option<ShellMark> {
+    let mark = match *args.first()? {
+        b"A" => ShellMark::PromptStart,
+        b"B" => ShellMark::CommandStart,
+        b"C" => ShellMark::OutputStart,
+        b"D" => {
+            let code = args
+                .get(1)
+                .and_then(|c| std::str::from_utf8(c).ok())
+                .and_then(|c| c.parse().ok());
+            ShellMark::CommandFinished(code)
+        }
+        _ => return None,
+    };
+    Some(mark)
+}
+
+/// X11 color spec as used by OSC 4/10/11: `rgb:r/g/b` (1–4 hex digits per
+/// component) or `#rgb` / `#rrggbb` / `#rrrgggbbb` / `#rrrrggggbbbb`.
+pub fn parse_color_spec(text: &str) -> Option<Rgb> {
+    let text = text.trim();
+    if let Some(rgb) = text.strip_prefix("rgb:") {
+        let parts: Vec<&str> = rgb.split('/').collect();
+        if parts.len() != 3 {
+            return None;
+        }
+        return Some(Rgb::new(
+            parse_scaled_hex(parts[0])?,
+            parse_scaled_hex(parts[1])?,
+            parse_scaled_hex(parts[2])?,
+        ));
+    }
+
+    if let Some(hex) = text.strip_prefix('#') {
+        if hex.is_empty() || hex.len() % 3 != 0 {
+            return None;
+        }
+        let comp_len = hex.len() / 3;
+        if comp_len > 4 {
+            return None;
+        }
+        return Some(Rgb::new(
+            parse_scaled_hex(&hex[..comp_len])?,
+            parse_scaled_hex(&hex[comp_len..comp_len * 2])?,
+            parse_scaled_hex(&hex[comp_len * 2..])?,
+        ));
+    }
+
+    None
+}
+
+fn parse_scaled_hex(hex: &str) -> Option<u8> {
+    if hex.is_empty() || hex.len() > 4 {
+        return None;
+    }
+    let value = u16::from_str_radix(hex, 16).ok()? as u32;
+    let max = (1u32 << (hex.len() * 4)) - 1;
+    Some(((value * 255 + (max / 2)) / max) as u8)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn osc(payload: &str) -> Vec<OscCommand> {
+        let parts: Vec<&[u8]> = payload.as_bytes().split(|&b| b == b';').collect();
+        parse_osc(&parts)
+    }
+
+    #[test]
+    fn title() {
+        assert_eq!(osc("0;hello"), vec![OscCommand::SetTitle("hello".into())]);
+        assert_eq!(osc("2;a;b"), vec![OscCommand::SetTitle("a;b".into())]);
+    }
+
+    #[test]
+    fn palette_set_and_query() {
+        assert_eq!(
+            osc("4;1;rgb:ff/00/00;2;?"),
+            vec![
+                OscCommand::SetPaletteColor(1, Rgb::new(255, 0, 0)),
+                OscCommand::QueryPaletteColor(2),
+            ]
+        );
+        assert!(osc("4;300;#fff").is_empty());
+    }
+
+    #[test]
+    fn working_directory() {
+        assert_eq!(
+            osc("7;file://mac.local/Users/me/my%20dir"),
+            vec![OscCommand::WorkingDirectory("/Users/me/my dir".into())]
+        );
+        assert!(osc("7;http://x/y").is_empty());
+    }
+
+    #[test]
+    fn hyperlink_open_and_close() {
+        assert_eq!(
+            osc("8;id=1;https://example.com/a;b"),
+            vec![OscCommand::Hyperlink(Some("https://example.com/a;b".into()))]
+        );
+        assert_eq!(osc("8;;"), vec![OscCommand::Hyperlink(None)]);
+    }
+
+    #[test]
+    fn default_colors() {
+        assert_eq!(osc("10;?"), vec![OscCommand::QueryDefaultForeground]);
+        assert_eq!(osc("11;?"), vec![OscCommand::QueryDefaultBackground]);
+        assert_eq!(
+            osc("10;rgb:ffff/0000/0000"),
+            vec![OscCommand::SetDefaultForeground(Rgb::new(255, 0, 0))]
+        );
+        assert_eq!(
+            osc("11;#112233"),
+            vec![OscCommand::SetDefaultBackground(Rgb::new(0x11, 0x22, 0x33))]
+        );
+        assert!(osc("11;not-a-color").is_empty());
+    }
+
+    #[test]
+    fn clipboard() {
+        assert_eq!(
+            osc("52;c;aGk="),
+            vec![OscCommand::Clipboard { selection: "c".into(), data: "aGk=".into() }]
+        );
+    }
+
+    #[test]
+    fn shell_marks() {
+        assert_eq!(osc("133;A"), vec![OscCommand::ShellMark(ShellMark::PromptStart)]);
+        assert_eq!(osc("133;C"), vec![OscCommand::ShellMark(ShellMark::OutputStart)]);
+        assert_eq!(
+            osc("133;D;1"),
+            vec![OscCommand::ShellMark(ShellMark::CommandFinished(Some(1)))]
+        );
+        assert_eq!(
+            osc("133;D"),
+            vec![OscCommand::ShellMark(ShellMark::CommandFinished(None))]
+        );
+        assert!(osc("133;Z").is_empty());
+    }
+
+    #[test]
+    fn iterm() {
+        assert_eq!(
+            osc("1337;SetUserVar=foo=YmFy"),
+            vec![OscCommand::ITerm("SetUserVar=foo=YmFy".into())]
+        );
+    }
+
+    #[test]
+    fn unknown_code_ignored() {
+        assert!(osc("999;x").is_empty());
+        assert!(parse_osc(&[]).is_empty());
+    }
+
+    #[test]
+    fn color_spec_forms() {
+        assert_eq!(parse_color_spec("rgb:f/8/0"), Some(Rgb::new(255, 136, 0)));
+        assert_eq!(parse_color_spec("#abc"), Some(Rgb::new(0xaa, 0xbb, 0xcc)));
+        assert_eq!(parse_color_spec("#aabbcc"), Some(Rgb::new(0xaa, 0xbb, 0xcc)));
+        assert_eq!(parse_color_spec("#ab"), None);
+        assert_eq!(parse_color_spec("rgb:1/2"), None);
+    }
+}
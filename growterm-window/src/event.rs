@@ -0,0 +1,110 @@
+/// 윈도우 백엔드에서 발생하는 이벤트.
+///
+/// 키코드는 백엔드와 무관하게 macOS 가상 키코드(`key_convert::keycode`)를 사용함.
+#[derive(Debug, Clone)]
+pub enum AppEvent {
+    /// insertText: — 조합 완료 텍스트, PTY에 전송
+    TextCommit(String),
+    /// setMarkedText: — 조합 중 표시
+    Preedit(String),
+    /// doCommandBySelector: — IME가 패스한 키, 앱이 직접 처리
+    KeyInput { keycode: u16, characters: Option<String>, modifiers: Modifiers },
+    /// keyUp: — 키 해제, kitty 키보드 프로토콜 이벤트 타입 보고용
+    KeyRelease { keycode: u16, characters: Option<String>, modifiers: Modifiers },
+    /// 윈도우 리사이즈
+    Resize(u32, u32),
+    /// 윈도우 닫기 요청
+    CloseRequested,
+    /// 종료 요청 (Cmd+Q). 실행 중인 프로세스가 있으면 앱이 먼저 확인함
+    QuitRequested,
+    /// 종료 직전 세션 저장 요청. 앱은 현재 탭 스냅샷으로 답함
+    SaveSession,
+    /// 리드로우 요청
+    RedrawRequested,
+    /// 마우스 버튼 누름 (x, y in backing pixels, modifiers)
+    MouseDown(f64, f64, Modifiers),
+    /// 마우스 드래그 (x, y in backing pixels)
+    MouseDragged(f64, f64),
+    /// 마우스 버튼 뗌 (x, y in backing pixels)
+    MouseUp(f64, f64),
+    /// 마우스 스크롤 (delta_y 픽셀: 양수=위, 음수=아래, precise: 트랙패드처럼 픽셀 단위 입력)
+    ScrollWheel(f64, bool),
+    /// 트랙패드에서 손가락을 뗌. 플랫폼이 관성 스크롤을 만들어 주지 않을 때만 보냄
+    ScrollGestureEnded,
+    /// 마우스 이동 (x, y in backing pixels, modifiers)
+    MouseMoved(f64, f64, Modifiers),
+    /// 파일 드래그 앤 드롭 (파일 경로 목록)
+    FileDropped(Vec<String>),
+    /// 뽀모도로 타이머 토글
+    TogglePomodoro,
+    /// 휴식을 바로 끝냄 (Skip Break 메뉴)
+    SkipBreak,
+    /// 현재 휴식을 5분 늘림 (Extend Break 메뉴)
+    ExtendBreak,
+    /// 응답 타이머 토글
+    ToggleResponseTimer,
+    /// 응답 시간 리포트 오버레이 토글 (Cmd+Option+R)
+    ToggleResponseReport,
+    /// Export Response Times… 메뉴
+    ExportResponseTimes,
+    /// 저장 대화상자에서 고른 응답 시간 CSV 경로
+    ExportResponseTimesTo(String),
+    /// AI 코칭 토글
+    ToggleCoaching,
+    /// 반투명 탭바 토글
+    ToggleTransparentTabBar,
+    /// 디버그 보기 토글 (줄 끝 공백, 제어문자 표시)
+    ToggleDebugView,
+    /// 지연 HUD 토글 (입력→화면 지연, PTY 읽기 속도, 프레임 시간, 아틀라스)
+    ToggleLatencyHud,
+    /// 설정 파일 리로드 (메뉴, 또는 파일이 바뀌면 자동으로)
+    ReloadConfig,
+    /// 진단 정보 표시 (탭별 스크롤백, 메모리, 아틀라스, PTY)
+    ShowDiagnostics,
+    /// 스크롤백 메모리 경고에서 "줄이기" 선택
+    TrimScrollback,
+    /// 여러 줄 붙여넣기 확인 대화상자에서 "붙여넣기" 선택
+    ConfirmPaste,
+    /// 실행 중인 프로세스가 있는 탭 닫기 확인에서 "닫기" 선택
+    ConfirmCloseTab,
+    /// 실행 중인 프로세스가 있을 때 종료 확인에서 "종료" 선택
+    ConfirmQuit,
+    /// 시스템 라이트/다크 모드 변경 (true면 다크)
+    AppearanceChanged(bool),
+    /// 새 탭 (Cmd+T, Dock 메뉴 "New Tab"). 활성 탭의 CWD를 이어받음
+    NewTab,
+    /// 이 폴더에서 새 탭 (Finder 서비스 "New growTerm Tab Here")
+    NewTabAt(String),
+    /// "New Tab With Profile" 메뉴 선택 (설정의 profiles 인덱스)
+    NewTabWithProfile(usize),
+    /// Clear Scrollback & Screen 메뉴 (Cmd+K)
+    ClearScrollback,
+    /// Export Buffer… 메뉴
+    ExportBuffer,
+    /// 저장 대화상자에서 고른 내보내기 경로
+    ExportBufferTo(String),
+    /// Copy Screen as Text 메뉴: 보이는 화면을 글자 그대로 복사
+    CopyScreenText,
+    /// Save Screenshot… 메뉴
+    SaveScreenshot,
+    /// 저장 대화상자에서 고른 스크린샷 경로
+    SaveScreenshotTo(String),
+    /// 윈도우 이동이 끝남 (위치 저장용)
+    WindowMoved,
+    /// 전체 화면 진입(true)/해제(false) 완료
+    FullScreenChanged(bool),
+    /// 윈도우가 키 입력 포커스를 얻음(true)/잃음(false)
+    FocusChanged(bool),
+    /// 백킹 스케일이 바뀜 (Retina ↔ 일반 모니터로 이동)
+    ScaleFactorChanged(f64),
+}
+
+bitflags::bitflags! {
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+    pub struct Modifiers: u8 {
+        const SHIFT   = 0b0001;
+        const CONTROL = 0b0010;
+        const ALT     = 0b0100;
+        const SUPER   = 0b1000;
+    }
+}
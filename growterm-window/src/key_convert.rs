@@ -47,6 +47,82 @@ pub mod keycode {
     pub const ANSI_Y: u16 = 0x10;
     pub const ANSI_R: u16 = 0x0F;
     pub const ANSI_GRAVE: u16 = 0x32; // ` (backtick / ₩)
+    pub const HELP: u16 = 0x72; // PC 키보드의 Insert
+    pub const F1: u16 = 0x7A;
+    pub const F2: u16 = 0x78;
+    pub const F3: u16 = 0x63;
+    pub const F4: u16 = 0x76;
+    pub const F5: u16 = 0x60;
+    pub const F6: u16 = 0x61;
+    pub const F7: u16 = 0x62;
+    pub const F8: u16 = 0x64;
+    pub const F9: u16 = 0x65;
+    pub const F10: u16 = 0x6D;
+    pub const F11: u16 = 0x67;
+    pub const F12: u16 = 0x6F;
+    pub const KEYPAD_0: u16 = 0x52;
+    pub const KEYPAD_1: u16 = 0x53;
+    pub const KEYPAD_2: u16 = 0x54;
+    pub const KEYPAD_3: u16 = 0x55;
+    pub const KEYPAD_4: u16 = 0x56;
+    pub const KEYPAD_5: u16 = 0x57;
+    pub const KEYPAD_6: u16 = 0x58;
+    pub const KEYPAD_7: u16 = 0x59;
+    pub const KEYPAD_8: u16 = 0x5B;
+    pub const KEYPAD_9: u16 = 0x5C;
+    pub const KEYPAD_DECIMAL: u16 = 0x41;
+    pub const KEYPAD_MULTIPLY: u16 = 0x43;
+    pub const KEYPAD_PLUS: u16 = 0x45;
+    pub const KEYPAD_DIVIDE: u16 = 0x4B;
+    pub const KEYPAD_ENTER: u16 = 0x4C;
+    pub const KEYPAD_MINUS: u16 = 0x4E;
+    pub const KEYPAD_EQUALS: u16 = 0x51;
+}
+
+/// macOS keycode → 기능키 번호 (F1–F12)
+fn function_key_number(code: u16) -> Option<u8> {
+    let n = match code {
+        keycode::F1 => 1,
+        keycode::F2 => 2,
+        keycode::F3 => 3,
+        keycode::F4 => 4,
+        keycode::F5 => 5,
+        keycode::F6 => 6,
+        keycode::F7 => 7,
+        keycode::F8 => 8,
+        keycode::F9 => 9,
+        keycode::F10 => 10,
+        keycode::F11 => 11,
+        keycode::F12 => 12,
+        _ => return None,
+    };
+    Some(n)
+}
+
+/// macOS keycode → 숫자 키패드 키
+fn keypad_key(code: u16) -> Option<growterm_types::KeypadKey> {
+    use growterm_types::KeypadKey;
+    let key = match code {
+        keycode::KEYPAD_0 => KeypadKey::Digit(0),
+        keycode::KEYPAD_1 => KeypadKey::Digit(1),
+        keycode::KEYPAD_2 => KeypadKey::Digit(2),
+        keycode::KEYPAD_3 => KeypadKey::Digit(3),
+        keycode::KEYPAD_4 => KeypadKey::Digit(4),
+        keycode::KEYPAD_5 => KeypadKey::Digit(5),
+        keycode::KEYPAD_6 => KeypadKey::Digit(6),
+        keycode::KEYPAD_7 => KeypadKey::Digit(7),
+        keycode::KEYPAD_8 => KeypadKey::Digit(8),
+        keycode::KEYPAD_9 => KeypadKey::Digit(9),
+        keycode::KEYPAD_DECIMAL => KeypadKey::Decimal,
+        keycode::KEYPAD_MULTIPLY => KeypadKey::Multiply,
+        keycode::KEYPAD_PLUS => KeypadKey::Plus,
+        keycode::KEYPAD_DIVIDE => KeypadKey::Divide,
+        keycode::KEYPAD_ENTER => KeypadKey::Enter,
+        keycode::KEYPAD_MINUS => KeypadKey::Minus,
+        keycode::KEYPAD_EQUALS => KeypadKey::Equals,
+        _ => return None,
+    };
+    Some(key)
 }
 
 /// 문자열 → macOS keycode 변환 (복사모드 키 설정용)
@@ -98,6 +174,11 @@ pub fn convert_key(
         keycode::PAGE_UP => growterm_types::Key::PageUp,
         keycode::PAGE_DOWN => growterm_types::Key::PageDown,
         keycode::SPACE => growterm_types::Key::Char(' '),
+        keycode::HELP => growterm_types::Key::Insert,
+        code if function_key_number(code).is_some() => {
+            growterm_types::Key::F(function_key_number(code)?)
+        }
+        code if keypad_key(code).is_some() => growterm_types::Key::Keypad(keypad_key(code)?),
         _ => {
             // 문자 키: characters에서 추출
             let c = characters.and_then(|s| {
@@ -106,6 +187,10 @@ pub fn convert_key(
                 if chars.next().is_some() {
                     return None;
                 }
+                // NSFunctionKey 영역(U+F700–U+F8FF)은 매핑되지 않은 특수키 → 무시
+                if ('\u{F700}'..='\u{F8FF}').contains(&c) {
+                    return None;
+                }
                 Some(c)
             })?;
             growterm_types::Key::Char(c)
@@ -267,6 +352,45 @@ mod tests {
         assert!(result.modifiers.contains(TypeMods::SHIFT));
     }
 
+    #[test]
+    fn function_keys() {
+        assert_eq!(
+            convert_key(keycode::F1, Some("\u{F704}"), Modifiers::empty()).unwrap().key,
+            Key::F(1)
+        );
+        assert_eq!(
+            convert_key(keycode::F12, None, Modifiers::empty()).unwrap().key,
+            Key::F(12)
+        );
+    }
+
+    #[test]
+    fn insert_key() {
+        assert_eq!(
+            convert_key(keycode::HELP, None, Modifiers::empty()).unwrap().key,
+            Key::Insert
+        );
+    }
+
+    #[test]
+    fn keypad_keys() {
+        use growterm_types::KeypadKey;
+        assert_eq!(
+            convert_key(keycode::KEYPAD_7, Some("7"), Modifiers::empty()).unwrap().key,
+            Key::Keypad(KeypadKey::Digit(7))
+        );
+        assert_eq!(
+            convert_key(keycode::KEYPAD_ENTER, Some("\u{3}"), Modifiers::empty()).unwrap().key,
+            Key::Keypad(KeypadKey::Enter)
+        );
+    }
+
+    #[test]
+    fn unmapped_function_key_char_returns_none() {
+        let result = convert_key(0xFE, Some("\u{F72C}"), Modifiers::empty());
+        assert_eq!(result, None);
+    }
+
     #[test]
     fn unknown_keycode_no_characters_returns_none() {
         let result = convert_key(0xFF, None, Modifiers::empty());
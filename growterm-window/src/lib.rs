@@ -0,0 +1,155 @@
+pub mod event;
+pub mod key_convert;
+
+pub use event::{AppEvent, Modifiers};
+pub use key_convert::convert_key;
+
+use raw_window_handle::{HasDisplayHandle, HasWindowHandle};
+
+/// 마우스 포인터 모양
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum MouseCursor {
+    /// 탭바, 스크롤바
+    #[default]
+    Arrow,
+    /// 터미널 텍스트 위
+    IBeam,
+    /// Cmd+클릭으로 열 수 있는 URL/파일 링크 위
+    PointingHand,
+}
+
+/// 앱 레이어가 사용하는 윈도우 인터페이스.
+///
+/// 모든 메서드는 IO/앱 스레드에서 호출될 수 있으므로 구현체는
+/// 필요하면 메인 스레드로 작업을 넘겨야 함.
+/// 메뉴·Dock처럼 플랫폼에 없는 기능은 기본 구현(무시)을 그대로 둠.
+pub trait TerminalWindow: HasWindowHandle + HasDisplayHandle + Send + Sync {
+    /// 콘텐츠 영역 크기 (물리 픽셀)
+    fn inner_size(&self) -> (u32, u32);
+
+    fn backing_scale_factor(&self) -> f64;
+
+    fn request_redraw(&self);
+
+    /// 이 윈도우의 앱 루프에 이벤트를 바로 보냄. 메인 스레드를 거치지 않으므로
+    /// 메인 스레드가 종료 중에 기다리는 동안에도 전달됨
+    fn post_event(&self, event: AppEvent);
+
+    fn set_title(&self, title: &str);
+
+    /// copy mode 동안 IME를 우회하여 키를 바로 받음
+    fn set_copy_mode(&self, enabled: bool);
+
+    /// IME 후보창 위치 (x, y, w, h in points)
+    fn set_ime_cursor_rect(&self, rect: Option<(f32, f32, f32, f32)>);
+
+    fn discard_marked_text(&self);
+
+    /// 타이틀바가 콘텐츠 위에 겹칠 때 그 높이 (물리 픽셀)
+    fn title_bar_height(&self) -> f64;
+
+    /// 타이틀바 아래까지 콘텐츠를 확장 (반투명 탭바)
+    fn set_transparent_mode(&self, _enabled: bool) {}
+
+    /// 알림 창을 띄움.
+    /// `confirm`이 있으면 (버튼 제목, 이벤트) 버튼을 추가하고,
+    /// 해당 버튼을 누르면 이벤트를 앱으로 보냄.
+    fn show_alert(&self, title: &str, message: &str, confirm: Option<(&str, AppEvent)>);
+
+    /// 파일 저장 대화상자. 경로를 고르면 `on_save(path)` 이벤트를 앱으로 보냄
+    fn show_save_panel(&self, _default_name: &str, _on_save: fn(String) -> AppEvent) {}
+
+    /// 일반 텍스트와 함께 HTML/RTF를 클립보드에 올림.
+    /// 지원하지 않으면 false를 반환하고, 앱은 HTML만 올림.
+    fn set_rich_clipboard(&self, _text: &str, _html: &str, _rtf: &str) -> bool {
+        false
+    }
+
+    /// 시스템 경고음 재생
+    fn beep(&self);
+
+    /// Dock 아이콘 배지 설정 (None이면 제거)
+    fn set_dock_badge(&self, _label: Option<&str>) {}
+
+    /// 앱이 비활성일 때 Dock 아이콘을 한 번 튀겨 주의를 끔
+    fn request_attention(&self) {}
+
+    fn set_mouse_cursor(&self, cursor: MouseCursor);
+
+    /// GPU 없이 그린 프레임(RGBA8, 물리 픽셀)을 화면에 표시.
+    /// `can_present_pixels`가 false인 백엔드에서는 호출되지 않음
+    fn present_pixels(&self, _rgba: &[u8], _width: u32, _height: u32) {}
+
+    /// `present_pixels`를 구현했는지. false면 소프트웨어 렌더러로 대체할 수 없음
+    fn can_present_pixels(&self) -> bool {
+        false
+    }
+
+    /// 같은 프로세스에 새 윈도우를 엶. `as_tab`이면 이 윈도우의 네이티브 탭으로 붙임.
+    /// 지원하지 않으면 false를 반환하고, 앱은 새 프로세스를 띄움.
+    fn open_window(&self, _as_tab: bool) -> bool {
+        false
+    }
+
+    /// 이 윈도우를 닫음. 마지막 윈도우면 프로세스가 종료됨
+    fn close(&self);
+
+    /// 앱 전체 종료. 윈도우가 하나뿐인 백엔드는 `close`와 같음
+    fn quit(&self) {
+        self.close();
+    }
+
+    /// 화면 좌상단 기준 윈도우 위치와 콘텐츠 크기 (x, y, width, height), 논리 좌표.
+    /// 전체 화면이거나 알 수 없으면 None
+    fn window_frame(&self) -> Option<(f64, f64, f64, f64)> {
+        None
+    }
+
+    /// 전체 화면 진입/해제. 상태가 바뀌면 `FullScreenChanged`를 보냄
+    fn set_fullscreen(&self, _enabled: bool) {}
+
+    /// 윈도우를 앞으로 가져오고 키 입력 포커스를 줌
+    fn focus(&self) {}
+
+    /// Quick Look 패널로 파일 미리보기. 이미 열려 있으면 닫음.
+    /// 지원하지 않으면 false를 반환하고, 앱은 키를 그대로 처리함
+    fn toggle_quick_look(&self, _path: &str) -> bool {
+        false
+    }
+
+    /// 시스템이 다크 모드인지. 알 수 없으면 다크로 취급
+    fn is_dark_appearance(&self) -> bool {
+        true
+    }
+
+    fn set_pomodoro_checked(&self, _checked: bool) {}
+
+    fn set_response_timer_checked(&self, _checked: bool) {}
+
+    fn set_coaching_checked(&self, _checked: bool) {}
+
+    fn set_coaching_menu_enabled(&self, _enabled: bool) {}
+
+    fn set_transparent_tab_bar_checked(&self, _checked: bool) {}
+
+    fn set_debug_view_checked(&self, _checked: bool) {}
+
+    fn set_latency_hud_checked(&self, _checked: bool) {}
+
+    /// "New Tab With Profile" 메뉴 항목을 프로필 이름으로 채움
+    fn set_profiles(&self, _names: &[String]) {}
+}
+
+static BEFORE_EXIT: std::sync::OnceLock<fn()> = std::sync::OnceLock::new();
+
+/// 프로세스가 끝나기 직전에 부를 함수 (세션 저장 등). 한 번만 설정됨
+pub fn set_before_exit(f: fn()) {
+    let _ = BEFORE_EXIT.set(f);
+}
+
+/// 백엔드가 종료 경로(앱 종료, 마지막 윈도우 닫기)에서 프로세스를 끝내기 전에 호출
+pub fn run_before_exit() {
+    if let Some(f) = BEFORE_EXIT.get() {
+        f();
+    }
+}
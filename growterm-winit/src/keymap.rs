@@ -0,0 +1,198 @@
+use growterm_window::key_convert::keycode;
+use growterm_window::{AppEvent, Modifiers};
+use winit::keyboard::{KeyCode, ModifiersState, PhysicalKey};
+
+/// 매핑되지 않은 물리 키. `convert_key`는 characters로 처리함.
+pub const UNMAPPED: u16 = u16::MAX;
+
+/// winit 물리 키 → macOS 가상 키코드 (Carbon kVK_ 값)
+pub fn keycode(key: PhysicalKey) -> Option<u16> {
+    let PhysicalKey::Code(code) = key else {
+        return None;
+    };
+    let kc = match code {
+        KeyCode::KeyA => 0x00,
+        KeyCode::KeyS => 0x01,
+        KeyCode::KeyD => 0x02,
+        KeyCode::KeyF => 0x03,
+        KeyCode::KeyH => 0x04,
+        KeyCode::KeyG => 0x05,
+        KeyCode::KeyZ => 0x06,
+        KeyCode::KeyX => 0x07,
+        KeyCode::KeyC => 0x08,
+        KeyCode::KeyV => 0x09,
+        KeyCode::IntlBackslash => 0x0A,
+        KeyCode::KeyB => 0x0B,
+        KeyCode::KeyQ => 0x0C,
+        KeyCode::KeyW => 0x0D,
+        KeyCode::KeyE => 0x0E,
+        KeyCode::KeyR => 0x0F,
+        KeyCode::KeyY => 0x10,
+        KeyCode::KeyT => 0x11,
+        KeyCode::Digit1 => 0x12,
+        KeyCode::Digit2 => 0x13,
+        KeyCode::Digit3 => 0x14,
+        KeyCode::Digit4 => 0x15,
+        KeyCode::Digit6 => 0x16,
+        KeyCode::Digit5 => 0x17,
+        KeyCode::Equal => 0x18,
+        KeyCode::Digit9 => 0x19,
+        KeyCode::Digit7 => 0x1A,
+        KeyCode::Minus => 0x1B,
+        KeyCode::Digit8 => 0x1C,
+        KeyCode::Digit0 => 0x1D,
+        KeyCode::BracketRight => 0x1E,
+        KeyCode::KeyO => 0x1F,
+        KeyCode::KeyU => 0x20,
+        KeyCode::BracketLeft => 0x21,
+        KeyCode::KeyI => 0x22,
+        KeyCode::KeyP => 0x23,
+        KeyCode::Enter => keycode::RETURN,
+        KeyCode::KeyL => 0x25,
+        KeyCode::KeyJ => 0x26,
+        KeyCode::Quote => 0x27,
+        KeyCode::KeyK => 0x28,
+        KeyCode::Semicolon => 0x29,
+        KeyCode::Backslash => 0x2A,
+        KeyCode::Comma => 0x2B,
+        KeyCode::Slash => 0x2C,
+        KeyCode::KeyN => 0x2D,
+        KeyCode::KeyM => 0x2E,
+        KeyCode::Period => 0x2F,
+        KeyCode::Tab => keycode::TAB,
+        KeyCode::Space => keycode::SPACE,
+        KeyCode::Backquote => keycode::ANSI_GRAVE,
+        KeyCode::Backspace => keycode::DELETE,
+        KeyCode::Escape => keycode::ESCAPE,
+        KeyCode::NumpadDecimal => keycode::KEYPAD_DECIMAL,
+        KeyCode::NumpadMultiply => keycode::KEYPAD_MULTIPLY,
+        KeyCode::NumpadAdd => keycode::KEYPAD_PLUS,
+        KeyCode::NumpadDivide => keycode::KEYPAD_DIVIDE,
+        KeyCode::NumpadEnter => keycode::KEYPAD_ENTER,
+        KeyCode::NumpadSubtract => keycode::KEYPAD_MINUS,
+        KeyCode::NumpadEqual => keycode::KEYPAD_EQUALS,
+        KeyCode::Numpad0 => keycode::KEYPAD_0,
+        KeyCode::Numpad1 => keycode::KEYPAD_1,
+        KeyCode::Numpad2 => keycode::KEYPAD_2,
+        KeyCode::Numpad3 => keycode::KEYPAD_3,
+        KeyCode::Numpad4 => keycode::KEYPAD_4,
+        KeyCode::Numpad5 => keycode::KEYPAD_5,
+        KeyCode::Numpad6 => keycode::KEYPAD_6,
+        KeyCode::Numpad7 => keycode::KEYPAD_7,
+        KeyCode::Numpad8 => keycode::KEYPAD_8,
+        KeyCode::Numpad9 => keycode::KEYPAD_9,
+        KeyCode::F1 => keycode::F1,
+        KeyCode::F2 => keycode::F2,
+        KeyCode::F3 => keycode::F3,
+        KeyCode::F4 => keycode::F4,
+        KeyCode::F5 => keycode::F5,
+        KeyCode::F6 => keycode::F6,
+        KeyCode::F7 => keycode::F7,
+        KeyCode::F8 => keycode::F8,
+        KeyCode::F9 => keycode::F9,
+        KeyCode::F10 => keycode::F10,
+        KeyCode::F11 => keycode::F11,
+        KeyCode::F12 => keycode::F12,
+        KeyCode::Insert => keycode::HELP,
+        KeyCode::Home => keycode::HOME,
+        KeyCode::PageUp => keycode::PAGE_UP,
+        KeyCode::Delete => keycode::FORWARD_DELETE,
+        KeyCode::End => keycode::END,
+        KeyCode::PageDown => keycode::PAGE_DOWN,
+        KeyCode::ArrowLeft => keycode::LEFT_ARROW,
+        KeyCode::ArrowRight => keycode::RIGHT_ARROW,
+        KeyCode::ArrowDown => keycode::DOWN_ARROW,
+        KeyCode::ArrowUp => keycode::UP_ARROW,
+        _ => return None,
+    };
+    Some(kc)
+}
+
+pub fn modifiers(state: ModifiersState) -> Modifiers {
+    let mut mods = Modifiers::empty();
+    if state.shift_key() {
+        mods |= Modifiers::SHIFT;
+    }
+    if state.control_key() {
+        mods |= Modifiers::CONTROL;
+    }
+    if state.alt_key() {
+        mods |= Modifiers::ALT;
+    }
+    if state.super_key() {
+        mods |= Modifiers::SUPER;
+    }
+    mods
+}
+
+/// macOS 메뉴 단축키(Super+Q, Super+P, Super+Shift+R, Super+Alt+R)에 해당하는 이벤트
+pub fn menu_shortcut(keycode: u16, modifiers: Modifiers) -> Option<AppEvent> {
+    if !modifiers.contains(Modifiers::SUPER) {
+        return None;
+    }
+    match keycode {
+        keycode::ANSI_Q => Some(AppEvent::QuitRequested),
+        keycode::ANSI_P => Some(AppEvent::TogglePomodoro),
+        keycode::ANSI_R if modifiers.contains(Modifiers::SHIFT) => Some(AppEvent::ReloadConfig),
+        keycode::ANSI_R if modifiers.contains(Modifiers::ALT) => Some(AppEvent::ToggleResponseReport),
+        _ => None,
+    }
+}
+
+/// IME를 거치지 않은 키 입력을 TextCommit으로 보낼 수 있는지.
+/// 수식키 조합과 제어문자(Enter, Tab, Backspace 등)는 KeyInput으로 보냄.
+pub fn commit_text(text: Option<&str>, modifiers: Modifiers) -> Option<&str> {
+    if modifiers.intersects(Modifiers::CONTROL | Modifiers::ALT | Modifiers::SUPER) {
+        return None;
+    }
+    text.filter(|t| !t.is_empty() && !t.chars().any(char::is_control))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use growterm_types::Key;
+
+    #[test]
+    fn letters_use_mac_keycodes() {
+        assert_eq!(keycode(PhysicalKey::Code(KeyCode::KeyA)), Some(keycode::ANSI_A));
+        assert_eq!(keycode(PhysicalKey::Code(KeyCode::KeyV)), Some(keycode::ANSI_V));
+        assert_eq!(keycode(PhysicalKey::Code(KeyCode::KeyW)), Some(keycode::ANSI_W));
+        assert_eq!(keycode(PhysicalKey::Code(KeyCode::Digit5)), Some(keycode::ANSI_5));
+        assert_eq!(keycode(PhysicalKey::Code(KeyCode::BracketLeft)), Some(keycode::ANSI_LEFT_BRACKET));
+    }
+
+    #[test]
+    fn special_keys_convert_like_macos() {
+        let kc = keycode(PhysicalKey::Code(KeyCode::ArrowUp)).unwrap();
+        let event = growterm_window::convert_key(kc, None, Modifiers::empty()).unwrap();
+        assert_eq!(event.key, Key::ArrowUp);
+        let kc = keycode(PhysicalKey::Code(KeyCode::F5)).unwrap();
+        let event = growterm_window::convert_key(kc, None, Modifiers::empty()).unwrap();
+        assert_eq!(event.key, Key::F(5));
+    }
+
+    #[test]
+    fn printable_text_is_committed() {
+        assert_eq!(commit_text(Some("a"), Modifiers::empty()), Some("a"));
+        assert_eq!(commit_text(Some("A"), Modifiers::SHIFT), Some("A"));
+        assert_eq!(commit_text(Some("\r"), Modifiers::empty()), None);
+        assert_eq!(commit_text(Some("c"), Modifiers::CONTROL), None);
+        assert_eq!(commit_text(None, Modifiers::empty()), None);
+    }
+
+    #[test]
+    fn menu_shortcuts() {
+        assert!(matches!(menu_shortcut(keycode::ANSI_Q, Modifiers::SUPER), Some(AppEvent::QuitRequested)));
+        assert!(matches!(
+            menu_shortcut(keycode::ANSI_R, Modifiers::SUPER | Modifiers::SHIFT),
+            Some(AppEvent::ReloadConfig)
+        ));
+        assert!(matches!(
+            menu_shortcut(keycode::ANSI_R, Modifiers::SUPER | Modifiers::ALT),
+            Some(AppEvent::ToggleResponseReport)
+        ));
+        assert!(menu_shortcut(keycode::ANSI_R, Modifiers::SUPER).is_none());
+        assert!(menu_shortcut(keycode::ANSI_Q, Modifiers::CONTROL).is_none());
+    }
+}
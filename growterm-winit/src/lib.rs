@@ -0,0 +1,199 @@
+//! winit 기반 윈도우 백엔드 (Linux X11/Wayland).
+//!
+//! growterm-macos와 같은 `run` 진입점과 `AppEvent` 스트림을 제공하므로
+//! 앱 레이어는 백엔드를 구분하지 않음.
+
+mod keymap;
+mod window;
+
+pub use growterm_window::{convert_key, event, key_convert, AppEvent, Modifiers, TerminalWindow};
+pub use window::WinitWindow;
+
+use std::sync::{mpsc, Arc};
+
+use winit::application::ApplicationHandler;
+use winit::dpi::{LogicalPosition, LogicalSize};
+use winit::event::{ElementState, Ime, MouseButton, MouseScrollDelta, TouchPhase, WindowEvent};
+use winit::event_loop::{ActiveEventLoop, EventLoop};
+use winit::keyboard::Key;
+use winit::window::{Theme, WindowId};
+
+type Setup = Box<dyn FnOnce(Arc<WinitWindow>, mpsc::Receiver<AppEvent>)>;
+
+/// winit 이벤트 루프 실행.
+///
+/// 메인 스레드에서 호출되어야 하며 반환하지 않음.
+/// `setup` 콜백은 윈도우가 만들어진 직후 한 번 호출됨.
+pub fn run(
+    window_size: (f64, f64),
+    window_position: Option<(f64, f64)>,
+    setup: impl FnOnce(Arc<WinitWindow>, mpsc::Receiver<AppEvent>) + 'static,
+) -> ! {
+    let event_loop = EventLoop::new().expect("failed to create event loop");
+    let mut handler = Handler {
+        window_size,
+        window_position,
+        setup: Some(Box::new(setup)),
+        window: None,
+        modifiers: Modifiers::empty(),
+        cursor: (0.0, 0.0),
+        left_button_down: false,
+        preedit_active: false,
+    };
+    let _ = event_loop.run_app(&mut handler);
+    growterm_window::run_before_exit();
+    std::process::exit(0);
+}
+
+struct Handler {
+    window_size: (f64, f64),
+    window_position: Option<(f64, f64)>,
+    setup: Option<Setup>,
+    window: Option<Arc<WinitWindow>>,
+    modifiers: Modifiers,
+    /// 마지막 커서 위치 (physical pixels)
+    cursor: (f64, f64),
+    left_button_down: bool,
+    /// IME 조합 중이면 키 이벤트를 앱에 보내지 않음
+    preedit_active: bool,
+}
+
+impl ApplicationHandler for Handler {
+    fn resumed(&mut self, event_loop: &ActiveEventLoop) {
+        if self.window.is_some() {
+            return;
+        }
+        let (width, height) = self.window_size;
+        let mut attrs = winit::window::Window::default_attributes()
+            .with_title("growTerm")
+            .with_inner_size(LogicalSize::new(width, height));
+        if let Some((x, y)) = self.window_position {
+            attrs = attrs.with_position(LogicalPosition::new(x, y));
+        }
+        let window = event_loop.create_window(attrs).expect("failed to create window");
+        let (tx, rx) = mpsc::channel();
+        let window = Arc::new(WinitWindow::new(window, tx));
+        self.window = Some(window.clone());
+        if let Some(setup) = self.setup.take() {
+            setup(window, rx);
+        }
+    }
+
+    fn window_event(&mut self, event_loop: &ActiveEventLoop, _id: WindowId, event: WindowEvent) {
+        let Some(window) = self.window.clone() else {
+            return;
+        };
+        match event {
+            WindowEvent::CloseRequested => window.send_event(AppEvent::CloseRequested),
+            WindowEvent::Destroyed => event_loop.exit(),
+            WindowEvent::RedrawRequested => window.send_event(AppEvent::RedrawRequested),
+            WindowEvent::Resized(size) => {
+                if size.width > 0 && size.height > 0 {
+                    window.send_event(AppEvent::Resize(size.width, size.height));
+                }
+            }
+            WindowEvent::Moved(_) => window.send_event(AppEvent::WindowMoved),
+            WindowEvent::Focused(focused) => window.send_event(AppEvent::FocusChanged(focused)),
+            WindowEvent::ScaleFactorChanged { scale_factor, .. } => {
+                window.send_event(AppEvent::ScaleFactorChanged(scale_factor))
+            }
+            WindowEvent::ModifiersChanged(mods) => {
+                self.modifiers = keymap::modifiers(mods.state());
+                // Super 키 변경 시 마지막 마우스 위치로 MouseMoved 재전송 (링크 hover)
+                let (x, y) = self.cursor;
+                window.send_event(AppEvent::MouseMoved(x, y, self.modifiers));
+            }
+            WindowEvent::KeyboardInput { event, is_synthetic: false, .. } => {
+                if self.preedit_active && !window.copy_mode() {
+                    return;
+                }
+                let keycode = keymap::keycode(event.physical_key);
+                let characters = match &event.logical_key {
+                    Key::Character(s) => Some(s.to_string()),
+                    _ => None,
+                };
+                let modifiers = self.modifiers;
+                if event.state == ElementState::Released {
+                    if let Some(keycode) = keycode {
+                        if !modifiers.contains(Modifiers::SUPER) {
+                            window.send_event(AppEvent::KeyRelease { keycode, characters, modifiers });
+                        }
+                    }
+                    return;
+                }
+                if let Some(shortcut) = keycode.and_then(|kc| keymap::menu_shortcut(kc, modifiers)) {
+                    window.send_event(shortcut);
+                    return;
+                }
+                if !window.copy_mode() {
+                    if let Some(text) = keymap::commit_text(event.text.as_deref(), modifiers) {
+                        window.send_event(AppEvent::TextCommit(text.to_string()));
+                        return;
+                    }
+                }
+                if keycode.is_none() && characters.is_none() {
+                    return;
+                }
+                window.send_event(AppEvent::KeyInput {
+                    keycode: keycode.unwrap_or(keymap::UNMAPPED),
+                    characters,
+                    modifiers,
+                });
+            }
+            WindowEvent::Ime(Ime::Preedit(text, _)) => {
+                self.preedit_active = !text.is_empty();
+                window.send_event(AppEvent::Preedit(text));
+            }
+            WindowEvent::Ime(Ime::Commit(text)) => {
+                if self.preedit_active {
+                    self.preedit_active = false;
+                    window.send_event(AppEvent::Preedit(String::new()));
+                }
+                window.send_event(AppEvent::TextCommit(text));
+            }
+            WindowEvent::CursorMoved { position, .. } => {
+                self.cursor = (position.x, position.y);
+                if self.left_button_down {
+                    window.send_event(AppEvent::MouseDragged(position.x, position.y));
+                } else {
+                    window.send_event(AppEvent::MouseMoved(position.x, position.y, self.modifiers));
+                }
+            }
+            WindowEvent::MouseInput { state, button: MouseButton::Left, .. } => {
+                let (x, y) = self.cursor;
+                match state {
+                    ElementState::Pressed => {
+                        self.left_button_down = true;
+                        window.send_event(AppEvent::MouseDown(x, y, self.modifiers));
+                    }
+                    ElementState::Released => {
+                        self.left_button_down = false;
+                        window.send_event(AppEvent::MouseUp(x, y));
+                    }
+                }
+            }
+            WindowEvent::MouseWheel { delta, phase, .. } => {
+                let (delta_y, precise) = match delta {
+                    // 마우스 휠: line 단위 → macOS와 같은 배율로 픽셀 단위 변환
+                    MouseScrollDelta::LineDelta(_, y) => (y as f64 * 40.0 * window.backing_scale_factor(), false),
+                    // 터치패드: 픽셀 단위 → 그대로 전달 (app에서 누적)
+                    MouseScrollDelta::PixelDelta(pos) => (pos.y, true),
+                };
+                if delta_y != 0.0 {
+                    window.send_event(AppEvent::ScrollWheel(delta_y, precise));
+                }
+                // macOS는 관성 이벤트를 직접 보내므로 app 쪽 관성은 다른 플랫폼에서만
+                if precise && phase == TouchPhase::Ended && !cfg!(target_os = "macos") {
+                    window.send_event(AppEvent::ScrollGestureEnded);
+                }
+            }
+            WindowEvent::ThemeChanged(theme) => {
+                window.send_event(AppEvent::AppearanceChanged(theme == Theme::Dark));
+            }
+            WindowEvent::DroppedFile(path) => {
+                window.send_event(AppEvent::FileDropped(vec![path.to_string_lossy().into_owned()]));
+            }
+            _ => {}
+        }
+    }
+}
@@ -0,0 +1,198 @@
+use std::process::Command;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::Sender;
+
+use growterm_window::{AppEvent, MouseCursor, TerminalWindow};
+use raw_window_handle::{HasDisplayHandle, HasWindowHandle};
+use winit::dpi::{PhysicalPosition, PhysicalSize};
+use winit::window::{CursorIcon, Fullscreen, Theme, UserAttentionType, Window};
+
+/// winit 윈도우. X11/Wayland에서는 윈도우 메서드를 어느 스레드에서든 호출할 수 있음.
+pub struct WinitWindow {
+    window: Window,
+    sender: Sender<AppEvent>,
+    copy_mode: AtomicBool,
+}
+
+impl WinitWindow {
+    pub(crate) fn new(window: Window, sender: Sender<AppEvent>) -> Self {
+        window.set_ime_allowed(true);
+        Self { window, sender, copy_mode: AtomicBool::new(false) }
+    }
+
+    pub(crate) fn send_event(&self, event: AppEvent) {
+        let _ = self.sender.send(event);
+    }
+
+    pub(crate) fn copy_mode(&self) -> bool {
+        self.copy_mode.load(Ordering::Relaxed)
+    }
+}
+
+impl TerminalWindow for WinitWindow {
+    fn inner_size(&self) -> (u32, u32) {
+        let size = self.window.inner_size();
+        (size.width.max(1), size.height.max(1))
+    }
+
+    fn backing_scale_factor(&self) -> f64 {
+        self.window.scale_factor()
+    }
+
+    fn request_redraw(&self) {
+        self.window.request_redraw();
+    }
+
+    fn post_event(&self, event: AppEvent) {
+        self.send_event(event);
+    }
+
+    fn set_title(&self, title: &str) {
+        self.window.set_title(title);
+    }
+
+    fn set_copy_mode(&self, enabled: bool) {
+        self.copy_mode.store(enabled, Ordering::Relaxed);
+        self.window.set_ime_allowed(!enabled);
+    }
+
+    fn set_ime_cursor_rect(&self, rect: Option<(f32, f32, f32, f32)>) {
+        if let Some((x, y, w, h)) = rect {
+            self.window.set_ime_cursor_area(PhysicalPosition::new(x, y), PhysicalSize::new(w, h));
+        }
+    }
+
+    fn discard_marked_text(&self) {
+        // 입력 컨텍스트를 다시 만들어 조합 중인 텍스트를 버림
+        if !self.copy_mode() {
+            self.window.set_ime_allowed(false);
+            self.window.set_ime_allowed(true);
+        }
+    }
+
+    /// 서버 측 데코레이션은 콘텐츠 영역 밖에 있으므로 겹치는 타이틀바가 없음
+    fn title_bar_height(&self) -> f64 {
+        0.0
+    }
+
+    /// zenity가 있으면 대화상자로, 없으면 stderr로 알림.
+    fn show_alert(&self, title: &str, message: &str, confirm: Option<(&str, AppEvent)>) {
+        let title = title.to_owned();
+        let message = message.to_owned();
+        let confirm = confirm.map(|(label, event)| (label.to_owned(), event));
+        let sender = self.sender.clone();
+        std::thread::spawn(move || {
+            let mut cmd = Command::new("zenity");
+            cmd.arg("--title").arg(&title).arg("--text").arg(&message).arg("--no-markup");
+            match &confirm {
+                Some((label, _)) => {
+                    cmd.arg("--question").arg("--ok-label").arg(label).arg("--cancel-label").arg("Ignore");
+                }
+                None => {
+                    cmd.arg("--info");
+                }
+            }
+            match cmd.status() {
+                Ok(status) => {
+                    if status.success() {
+                        if let Some((_, event)) = confirm {
+                            let _ = sender.send(event);
+                        }
+                    }
+                }
+                Err(_) => eprintln!("[growterm] {title}: {message}"),
+            }
+        });
+    }
+
+    /// zenity 저장 대화상자. 없으면 아무것도 하지 않음
+    fn show_save_panel(&self, default_name: &str, on_save: fn(String) -> AppEvent) {
+        let default_name = default_name.to_owned();
+        let sender = self.sender.clone();
+        std::thread::spawn(move || {
+            let output = Command::new("zenity")
+                .arg("--file-selection")
+                .arg("--save")
+                .arg("--confirm-overwrite")
+                .arg(format!("--filename={default_name}"))
+                .output();
+            match output {
+                Ok(output) if output.status.success() => {
+                    let path = String::from_utf8_lossy(&output.stdout).trim_end().to_string();
+                    if !path.is_empty() {
+                        let _ = sender.send(on_save(path));
+                    }
+                }
+                Ok(_) => {}
+                Err(_) => eprintln!("[growterm] zenity not found; cannot choose where to save"),
+            }
+        });
+    }
+
+    /// 경고음 API가 없으므로 윈도우 매니저에 주의 요청을 보냄
+    fn beep(&self) {
+        self.window.request_user_attention(Some(UserAttentionType::Informational));
+    }
+
+    fn request_attention(&self) {
+        self.window.request_user_attention(Some(UserAttentionType::Informational));
+    }
+
+    fn set_mouse_cursor(&self, cursor: MouseCursor) {
+        self.window.set_cursor(match cursor {
+            MouseCursor::Arrow => CursorIcon::Default,
+            MouseCursor::IBeam => CursorIcon::Text,
+            MouseCursor::PointingHand => CursorIcon::Pointer,
+        });
+    }
+
+    /// 윈도우가 하나뿐이므로 프로세스 종료
+    fn close(&self) {
+        growterm_window::run_before_exit();
+        std::process::exit(0);
+    }
+
+    fn is_dark_appearance(&self) -> bool {
+        self.window.theme() != Some(Theme::Light)
+    }
+
+    fn window_frame(&self) -> Option<(f64, f64, f64, f64)> {
+        if self.window.fullscreen().is_some() {
+            return None;
+        }
+        // Wayland는 윈도우 위치를 알려주지 않음
+        let scale = self.window.scale_factor();
+        let position = self.window.outer_position().ok()?.to_logical::<f64>(scale);
+        let size = self.window.inner_size().to_logical::<f64>(scale);
+        Some((position.x, position.y, size.width, size.height))
+    }
+
+    /// winit에는 전체 화면 전환 이벤트가 없어 여기서 직접 알림
+    fn set_fullscreen(&self, enabled: bool) {
+        if self.window.fullscreen().is_some() == enabled {
+            return;
+        }
+        self.window.set_fullscreen(enabled.then_some(Fullscreen::Borderless(None)));
+        self.send_event(AppEvent::FullScreenChanged(enabled));
+    }
+
+    fn focus(&self) {
+        self.window.focus_window();
+    }
+}
+
+impl HasWindowHandle for WinitWindow {
+    fn window_handle(
+        &self,
+    ) -> Result<raw_window_handle::WindowHandle<'_>, raw_window_handle::HandleError> {
+        self.window.window_handle()
+    }
+}
+
+impl HasDisplayHandle for WinitWindow {
+    fn display_handle(
+        &self,
+    ) -> Result<raw_window_handle::DisplayHandle<'_>, raw_window_handle::HandleError> {
+        self.window.display_handle()
+    }
+}
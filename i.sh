@@ -30,6 +30,24 @@ cat > "$APP/Contents/Info.plist" << 'EOF'
 	<string>AppIcon</string>
 	<key>NSHighResolutionCapable</key>
 	<true/>
+	<key>NSServices</key>
+	<array>
+		<dict>
+			<key>NSMenuItem</key>
+			<dict>
+				<key>default</key>
+				<string>New growTerm Tab Here</string>
+			</dict>
+			<key>NSMessage</key>
+			<string>openTabHere</string>
+			<key>NSRequiredContext</key>
+			<dict/>
+			<key>NSSendFileTypes</key>
+			<array>
+				<string>public.folder</string>
+			</array>
+		</dict>
+	</array>
 </dict>
 </plist>
 EOF